@@ -1,10 +1,12 @@
 use crate::service::{
-    AuthService, GrpcClients, SaldoService, TopupService, TransferService, UserService,
+    AccountService, AuthService, GrpcClients, RetryPolicy, SaldoService, SystemService,
+    TopupService, TransferService, UserService, VirtualCardService, WebhookService,
     WithdrawService,
 };
 use shared::{
     abstract_trait::{
-        DynAuthService, DynSaldoService, DynTopupService, DynTransferService, DynUserService,
+        DynAccountService, DynAuthService, DynSaldoService, DynSystemService, DynTopupService,
+        DynTransferService, DynUserService, DynVirtualCardService, DynWebhookService,
         DynWithdrawService,
     },
     utils::Metrics,
@@ -13,27 +15,34 @@ use shared::{
 use anyhow::Result;
 use prometheus_client::registry::Registry;
 use std::sync::Arc;
-use tokio::sync::Mutex;
 
 #[derive(Clone)]
 pub struct DependenciesInject {
+    pub account_service: DynAccountService,
     pub auth_service: DynAuthService,
     pub user_service: DynUserService,
     pub saldo_service: DynSaldoService,
     pub topup_service: DynTopupService,
     pub transfer_service: DynTransferService,
     pub withdraw_service: DynWithdrawService,
+    pub virtual_card_service: DynVirtualCardService,
+    pub webhook_service: DynWebhookService,
+    pub system_service: DynSystemService,
 }
 
 impl std::fmt::Debug for DependenciesInject {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DependenciesInject")
+            .field("account_service", &"DynAccountService")
             .field("auth_service", &"DynAuthService")
             .field("user_service", &"DynUserService")
             .field("saldo_service", &"DynSaldoService")
             .field("topup_service", &"DynTopupService")
             .field("transfer_service", &"DynTransferService")
             .field("withdraw_service", &"DynWithdrawService")
+            .field("virtual_card_service", &"DynVirtualCardService")
+            .field("webhook_service", &"DynWebhookService")
+            .field("system_service", &"DynSystemService")
             .finish()
     }
 }
@@ -41,29 +50,59 @@ impl std::fmt::Debug for DependenciesInject {
 impl DependenciesInject {
     pub async fn new(
         clients: GrpcClients,
-        metrics: Arc<Mutex<Metrics>>,
+        retry_policy: RetryPolicy,
+        metrics: Arc<Metrics>,
         registry: &mut Registry,
     ) -> Result<Self> {
+        let account_service: DynAccountService = Arc::new(
+            AccountService::new(clients.account, retry_policy, metrics.clone(), registry).await,
+        );
         let auth_service: DynAuthService =
-            Arc::new(AuthService::new(clients.auth, metrics.clone(), registry).await);
+            Arc::new(AuthService::new(clients.auth, retry_policy, metrics.clone(), registry).await);
         let user_service: DynUserService =
-            Arc::new(UserService::new(clients.user, metrics.clone(), registry).await);
-        let saldo_service: DynSaldoService =
-            Arc::new(SaldoService::new(clients.saldo, metrics.clone(), registry).await);
-        let topup_service: DynTopupService =
-            Arc::new(TopupService::new(clients.topup, metrics.clone(), registry).await);
-        let transfer_service: DynTransferService =
-            Arc::new(TransferService::new(clients.transfer, metrics.clone(), registry).await);
-        let withdraw_service: DynWithdrawService =
-            Arc::new(WithdrawService::new(clients.withdraw, metrics.clone(), registry).await);
+            Arc::new(UserService::new(clients.user, retry_policy, metrics.clone(), registry).await);
+        let saldo_service: DynSaldoService = Arc::new(
+            SaldoService::new(clients.saldo, retry_policy, metrics.clone(), registry).await,
+        );
+        let topup_service: DynTopupService = Arc::new(
+            TopupService::new(clients.topup, retry_policy, metrics.clone(), registry).await,
+        );
+        let transfer_service: DynTransferService = Arc::new(
+            TransferService::new(clients.transfer, retry_policy, metrics.clone(), registry).await,
+        );
+        let withdraw_service: DynWithdrawService = Arc::new(
+            WithdrawService::new(clients.withdraw, retry_policy, metrics.clone(), registry).await,
+        );
+
+        let virtual_card_service: DynVirtualCardService = Arc::new(
+            VirtualCardService::new(
+                clients.virtual_card,
+                retry_policy,
+                metrics.clone(),
+                registry,
+            )
+            .await,
+        );
+
+        let webhook_service: DynWebhookService = Arc::new(
+            WebhookService::new(clients.webhook, retry_policy, metrics.clone(), registry).await,
+        );
+
+        let system_service: DynSystemService = Arc::new(
+            SystemService::new(clients.system, retry_policy, metrics.clone(), registry).await,
+        );
 
         Ok(Self {
+            account_service,
             auth_service,
             user_service,
             saldo_service,
             topup_service,
             transfer_service,
             withdraw_service,
+            virtual_card_service,
+            webhook_service,
+            system_service,
         })
     }
 }
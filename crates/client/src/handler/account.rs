@@ -0,0 +1,55 @@
+use crate::{middleware::jwt, state::AppState};
+use axum::{
+    Json,
+    extract::{Extension, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::get,
+};
+use serde_json::json;
+use shared::domain::{
+    request::AccountSummaryRequest,
+    response::{ApiResponse, ErrorResponse, account::AccountSummaryResponse},
+};
+use std::sync::Arc;
+use utoipa_axum::router::OpenApiRouter;
+
+#[utoipa::path(
+    get,
+    path = "/api/account/summary",
+    tag = "Account",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(AccountSummaryRequest),
+    responses(
+        (status = 200, description = "Main balance and most recent topups, transfers and withdrawals", body = ApiResponse<AccountSummaryResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 422, description = "limit is outside 1-20", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn get_account_summary(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i32>,
+    Query(params): Query<AccountSummaryRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match data
+        .di_container
+        .account_service
+        .get_account_summary(user_id, &params)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn account_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    OpenApiRouter::new()
+        .route("/api/account/summary", get(get_account_summary))
+        .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
+        .with_state(app_state.clone())
+}
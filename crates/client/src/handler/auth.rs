@@ -1,32 +1,48 @@
+use super::{forbidden, small_body_limit};
 use crate::{
     middleware::{jwt, validate::SimpleValidatedJson},
     state::AppState,
 };
 use axum::{
     Extension, Json,
-    extract::State,
-    http::StatusCode,
+    extract::{Path, State},
+    http::{HeaderMap, StatusCode, header::USER_AGENT},
     middleware,
     response::IntoResponse,
-    routing::{get, post},
+    routing::{delete, get, post},
 };
-use serde_json::{Value, json};
-use shared::domain::{
-    request::{LoginRequest, RegisterRequest},
-    response::{ApiResponse, user::UserResponse},
+use serde_json::json;
+use shared::{
+    domain::{
+        request::{ChangePasswordRequest, LoginRequest, RefreshTokenRequest, RegisterRequest},
+        response::{
+            ApiResponse, ErrorResponse,
+            auth::{LoginResponse, SessionResponse},
+            user::UserResponse,
+        },
+    },
+    utils::HealthChecker,
 };
 use std::sync::Arc;
 use utoipa_axum::router::OpenApiRouter;
 
-pub async fn health_checker_handler() -> impl IntoResponse {
-    const MESSAGE: &str = "JWT Authentication in Rust using Axum, Postgres, and SQLX";
+pub async fn health_checker_handler(State(data): State<Arc<AppState>>) -> impl IntoResponse {
+    let endpoint = data.grpc_endpoint.clone();
 
-    let json_response = serde_json::json!({
-        "status": "success",
-        "message": MESSAGE
-    });
+    let report = HealthChecker::new()
+        .check("grpc_server", async move {
+            endpoint.connect().await.map(|_| ())
+        })
+        .await
+        .report();
 
-    Json(json_response)
+    let status = if report.is_healthy() {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(report))
 }
 
 #[utoipa::path(
@@ -42,10 +58,10 @@ pub async fn health_checker_handler() -> impl IntoResponse {
 pub async fn register_user_handler(
     State(data): State<Arc<AppState>>,
     SimpleValidatedJson(body): SimpleValidatedJson<RegisterRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.auth_service.register_user(&body).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -54,18 +70,25 @@ pub async fn register_user_handler(
     path = "/api/auth/login",
     request_body = LoginRequest,
     responses(
-        (status = 200, description = "Login successful", body = ApiResponse<String>),
-        (status = 401, description = "Unauthorized")
+        (status = 200, description = "Login successful", body = ApiResponse<LoginResponse>),
+        (status = 401, description = "Unauthorized"),
+        (status = 429, description = "Too many failed attempts, see the Retry-After header")
     ),
     tag = "Auth"
 )]
 pub async fn login_user_handler(
     State(data): State<Arc<AppState>>,
-    SimpleValidatedJson(body): SimpleValidatedJson<LoginRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<Value>)> {
+    headers: HeaderMap,
+    SimpleValidatedJson(mut body): SimpleValidatedJson<LoginRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    body.user_agent = headers
+        .get(USER_AGENT)
+        .and_then(|value| value.to_str().ok())
+        .map(str::to_string);
+
     match data.di_container.auth_service.login_user(&body).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
-        Err(e) => Err((StatusCode::UNAUTHORIZED, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -83,27 +106,157 @@ pub async fn login_user_handler(
 pub async fn get_me_handler(
     State(data): State<Arc<AppState>>,
     Extension(user_id): Extension<i32>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.user_service.get_user(user_id).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
-        Err(e) => Err((
-            StatusCode::INTERNAL_SERVER_ERROR,
-            Json(json!({
-                "status": "fail",
-                "message": e.message
-            })),
-        )),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/change-password",
+    request_body = ChangePasswordRequest,
+    responses(
+        (status = 200, description = "Password changed successfully", body = String),
+        (status = 400, description = "Weak or mismatched new password", body = String),
+        (status = 401, description = "Invalid old password", body = String)
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth",
+)]
+pub async fn change_password_handler(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i32>,
+    SimpleValidatedJson(body): SimpleValidatedJson<ChangePasswordRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match data
+        .di_container
+        .auth_service
+        .change_password(user_id, &body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/auth/refresh",
+    request_body = RefreshTokenRequest,
+    responses(
+        (status = 200, description = "Access token refreshed", body = ApiResponse<LoginResponse>),
+        (status = 401, description = "Refresh token invalid, expired or revoked")
+    ),
+    tag = "Auth"
+)]
+pub async fn refresh_token_handler(
+    State(data): State<Arc<AppState>>,
+    SimpleValidatedJson(body): SimpleValidatedJson<RefreshTokenRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match data.di_container.auth_service.refresh_token(&body).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/sessions",
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "Active sessions of the user", body = ApiResponse<Vec<SessionResponse>>),
+        (status = 403, description = "Caller is not an administrator")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth",
+)]
+pub async fn list_sessions_handler(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i32>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may list sessions"));
+    }
+
+    match data.di_container.auth_service.list_sessions(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/{id}/sessions/{session_id}",
+    params(
+        ("id" = i32, Path, description = "User ID"),
+        ("session_id" = i32, Path, description = "Session ID")
+    ),
+    responses(
+        (status = 200, description = "Session revoked", body = serde_json::Value),
+        (status = 403, description = "Caller is not an administrator"),
+        (status = 404, description = "Active session not found")
+    ),
+    security(
+        ("bearer_auth" = [])
+    ),
+    tag = "Auth",
+)]
+pub async fn revoke_session_handler(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i32>,
+    Path((id, session_id)): Path<(i32, i32)>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may revoke sessions"));
+    }
+
+    match data
+        .di_container
+        .auth_service
+        .revoke_session(id, session_id)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
     }
 }
 
 pub fn auth_routes(app_state: Arc<AppState>) -> OpenApiRouter {
     let public_routues = OpenApiRouter::new()
-        .route("/api/auth/register", post(register_user_handler))
-        .route("/api/auth/login", post(login_user_handler))
+        .route(
+            "/api/auth/register",
+            post(register_user_handler).layer(small_body_limit()),
+        )
+        .route(
+            "/api/auth/login",
+            post(login_user_handler).layer(small_body_limit()),
+        )
+        .route(
+            "/api/auth/refresh",
+            post(refresh_token_handler).layer(small_body_limit()),
+        )
         .route("/api/healthchecker", get(health_checker_handler));
 
     let private_routes = OpenApiRouter::new()
         .route("/api/auth/me", get(get_me_handler))
+        .route(
+            "/api/auth/change-password",
+            post(change_password_handler).layer(small_body_limit()),
+        )
+        .route("/api/users/{id}/sessions", get(list_sessions_handler))
+        .route(
+            "/api/users/{id}/sessions/{session_id}",
+            delete(revoke_session_handler),
+        )
         .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth));
 
     public_routues.merge(private_routes).with_state(app_state)
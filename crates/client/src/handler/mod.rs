@@ -1,67 +1,115 @@
+mod account;
 mod auth;
 mod saldo;
+mod system;
 mod topup;
 mod transfer;
 mod user;
+mod virtual_card;
+mod webhook;
 mod withdraw;
 
+use crate::middleware::{
+    compression::rest_compression, cors::rest_cors, deadline::deadline, load_shed::load_shed,
+    request_id::request_id,
+};
 use crate::state::AppState;
 use anyhow::Result;
 use axum::body::Body;
 use axum::extract::{DefaultBodyLimit, State};
 use axum::http::StatusCode;
 use axum::http::header::CONTENT_TYPE;
+use axum::middleware;
 use axum::response::{IntoResponse, Response};
 use axum::routing::get;
+use axum::{Json, Router};
 use prometheus_client::encoding::text::encode;
-use shared::utils::shutdown_signal;
+use shared::domain::request::max_page_size;
+use shared::domain::response::ErrorResponse;
+use shared::utils::{BUILD_INFO, shutdown_signal};
 use std::sync::Arc;
 use tokio::net::TcpListener;
-use tower_http::limit::RequestBodyLimitLayer;
 use utoipa::openapi::security::SecurityScheme;
+use utoipa::openapi::server::Server;
+use utoipa::openapi::{RefOr, Schema};
 use utoipa::{Modify, OpenApi};
 use utoipa_axum::router::OpenApiRouter;
 use utoipa_swagger_ui::SwaggerUi;
 
+pub use self::account::account_routes;
 pub use self::auth::auth_routes;
 pub use self::saldo::saldos_routes;
+pub use self::system::system_routes;
 pub use self::topup::topup_routes;
 pub use self::transfer::transfers_routes;
 pub use self::user::users_routes;
+pub use self::virtual_card::virtual_card_routes;
+pub use self::webhook::webhook_routes;
 pub use self::withdraw::withdraw_routes;
 
 #[derive(OpenApi)]
 #[openapi(
     paths(
+        account::get_account_summary,
         auth::login_user_handler,
         auth::get_me_handler,
+        auth::change_password_handler,
         auth::register_user_handler,
+        auth::refresh_token_handler,
+        auth::list_sessions_handler,
+        auth::revoke_session_handler,
         saldo::get_saldos,
         saldo::get_saldo,
         saldo::get_saldo_users,
         saldo::get_saldo_user,
+        system::get_runtime_config,
+        system::get_cache_stats,
+        system::flush_cache_prefix,
         saldo::create_saldo,
         saldo::update_saldo,
         saldo::delete_saldo,
+        saldo::get_top_saldos,
+        saldo::adjust_balance,
+        saldo::reconcile_balance,
         topup::get_topups,
         topup::get_topup,
+        topup::get_topup_by_no,
         topup::get_topup_users,
         topup::get_topup_user,
         topup::create_topup,
         topup::update_topup,
         topup::delete_topup,
+        topup::confirm_topup,
+        topup::fail_topup,
+        topup::refund_topup,
+        topup::get_topup_stats,
         transfer::get_transfers,
         transfer::get_transfer,
         transfer::get_transfer_users,
+        transfer::get_user_transfer_history,
         transfer::get_transfer_user,
         transfer::create_transfer,
         transfer::update_transfer,
         transfer::delete_transfer,
+        transfer::reverse_transfer,
+        transfer::approve_transfer,
+        transfer::get_transfer_receipt,
+        transfer::get_daily_transfer_totals,
         user::get_users,
         user::get_user,
         user::create_user,
         user::update_user,
         user::delete_user,
+        user::can_delete_user,
+        user::restore_user,
+        user::freeze_user,
+        user::unfreeze_user,
+        user::purge_user,
+        user::import_users,
+        virtual_card::generate_virtual_card,
+        virtual_card::list_virtual_cards,
+        virtual_card::revoke_virtual_card,
+        webhook::retry_webhook,
         withdraw::get_withdraws,
         withdraw::get_withdraw,
         withdraw::get_withdraw_users,
@@ -70,18 +118,45 @@ pub use self::withdraw::withdraw_routes;
         withdraw::update_withdraw,
         withdraw::delete_withdraw
     ),
-    modifiers(&SecurityAddon),
+    modifiers(&SecurityAddon, &PaginationAddon),
     tags(
         (name = "Auth", description = "Authentication endpoints"),
+        (name = "Account", description = "Account overview endpoints"),
         (name = "User", description = "User management endpoints"),
         (name = "Saldo", description = "Balance management endpoints"),
         (name = "Topup", description = "Top up endpoints"),
         (name = "Transfer", description = "Transfer endpoints"),
-        (name = "Withdraw", description = "Withdrawal endpoints")
+        (name = "Withdraw", description = "Withdrawal endpoints"),
+        (name = "VirtualCard", description = "Virtual card endpoints"),
+        (name = "Webhook", description = "Webhook delivery endpoints"),
+        (name = "System", description = "Runtime configuration endpoints")
     )
 )]
 struct ApiDoc;
 
+// batas default untuk semua route, route auth/create memakai batas yang lebih kecil
+const DEFAULT_BODY_LIMIT: usize = 64 * 1024;
+const SMALL_BODY_LIMIT: usize = 16 * 1024;
+const IMPORT_BODY_LIMIT: usize = 1024 * 1024;
+
+pub(crate) fn small_body_limit() -> DefaultBodyLimit {
+    DefaultBodyLimit::max(SMALL_BODY_LIMIT)
+}
+
+pub(crate) fn import_body_limit() -> DefaultBodyLimit {
+    DefaultBodyLimit::max(IMPORT_BODY_LIMIT)
+}
+
+pub(crate) fn forbidden(message: &str) -> ErrorResponse {
+    ErrorResponse {
+        status: "fail".to_string(),
+        message: message.to_string(),
+        code: StatusCode::FORBIDDEN,
+        retry_after: None,
+        errors: None,
+    }
+}
+
 struct SecurityAddon;
 
 impl Modify for SecurityAddon {
@@ -97,6 +172,37 @@ impl Modify for SecurityAddon {
     }
 }
 
+// batas maksimum page_size baru diketahui saat runtime (MAX_PAGE_SIZE), jadi diisi di sini
+struct PaginationAddon;
+
+impl Modify for PaginationAddon {
+    fn modify(&self, openapi: &mut utoipa::openapi::OpenApi) {
+        let parameters = openapi
+            .paths
+            .paths
+            .values_mut()
+            .flat_map(|item| {
+                [
+                    item.get.as_mut(),
+                    item.put.as_mut(),
+                    item.post.as_mut(),
+                    item.delete.as_mut(),
+                    item.patch.as_mut(),
+                ]
+            })
+            .flatten()
+            .filter_map(|operation| operation.parameters.as_mut())
+            .flatten()
+            .filter(|parameter| parameter.name == "page_size");
+
+        for parameter in parameters {
+            if let Some(RefOr::T(Schema::Object(schema))) = parameter.schema.as_mut() {
+                schema.maximum = Some(max_page_size().into());
+            }
+        }
+    }
+}
+
 pub async fn metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
     let mut buffer = String::new();
 
@@ -119,38 +225,78 @@ pub async fn metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoRes
         .unwrap()
 }
 
+pub async fn version_handler() -> impl IntoResponse {
+    Json(BUILD_INFO)
+}
+
 pub struct AppRouter;
 
+// path di OpenAPI tetap tanpa prefix, prefix-nya dicatat sebagai server
+fn mount_under_prefix(router: Router, api: &mut utoipa::openapi::OpenApi, prefix: &str) -> Router {
+    if prefix.is_empty() {
+        return router;
+    }
+
+    api.servers = Some(vec![Server::new(prefix)]);
+    Router::new().nest(prefix, router)
+}
+
 impl AppRouter {
     pub async fn serve(port: u16, app_state: AppState) -> Result<()> {
         let shared_state = Arc::new(app_state);
 
         let mut router = OpenApiRouter::with_openapi(ApiDoc::openapi())
             .route("/metrics", get(metrics_handler))
+            .route("/version", get(version_handler))
             .with_state(shared_state.clone());
 
         router = router.merge(auth_routes(shared_state.clone()));
+        router = router.merge(account_routes(shared_state.clone()));
         router = router.merge(users_routes(shared_state.clone()));
         router = router.merge(saldos_routes(shared_state.clone()));
         router = router.merge(topup_routes(shared_state.clone()));
         router = router.merge(transfers_routes(shared_state.clone()));
         router = router.merge(withdraw_routes(shared_state.clone()));
+        router = router.merge(virtual_card_routes(shared_state.clone()));
+        router = router.merge(webhook_routes(shared_state.clone()));
+        router = router.merge(system_routes(shared_state.clone()));
+
+        // Swagger UI di-merge belakangan sehingga tidak ikut dikompresi
+        let router = router
+            .layer(DefaultBodyLimit::max(DEFAULT_BODY_LIMIT))
+            .layer(rest_compression(shared_state.compression_min_size));
+
+        let (router, mut api) = router.split_for_parts();
 
+        // load shed dipasang sebelum nest supaya path yang dicek sudah tanpa prefix
         let router = router
-            .layer(DefaultBodyLimit::disable())
-            .layer(RequestBodyLimitLayer::new(250 * 1024 * 1024));
+            .layer(middleware::from_fn_with_state(
+                shared_state.request_timeout,
+                deadline,
+            ))
+            .layer(middleware::from_fn_with_state(
+                shared_state.concurrency_limit.clone(),
+                load_shed,
+            ));
 
-        let (router, api) = router.split_for_parts();
+        let prefix = shared_state.api_prefix.clone();
+        let router = mount_under_prefix(router, &mut api, &prefix);
 
-        let app =
-            router.merge(SwaggerUi::new("/swagger-ui").url("/api-docs/openapi.json", api.clone()));
+        let app = router
+            .merge(
+                SwaggerUi::new(format!("{prefix}/swagger-ui"))
+                    .url(format!("{prefix}/api-docs/openapi.json"), api.clone()),
+            )
+            .layer(middleware::from_fn(request_id))
+            // paling luar supaya preflight OPTIONS dijawab sebelum auth dan load shed
+            .layer(rest_cors(&shared_state.cors_allowed_origins)?);
 
         let addr = format!("0.0.0.0:{port}");
         let listener = TcpListener::bind(&addr).await?;
 
         println!("Server running on http://{}", listener.local_addr()?);
         println!("API Documentation available at:");
-        println!("- Swagger UI: http://localhost:{port}/swagger-ui");
+        println!("- Swagger UI: http://localhost:{port}{prefix}/swagger-ui");
 
         axum::serve(listener, app)
             .with_graceful_shutdown(shutdown_signal())
@@ -159,3 +305,134 @@ impl AppRouter {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+    use axum::extract::Query;
+    use shared::domain::request::user::FindAllUserRequest;
+    use shared::domain::request::{install_default_page_size, install_max_page_size};
+    use shared::utils::AppError;
+    use tonic::Status;
+    use tower::ServiceExt;
+    use validator::ValidationErrors;
+
+    // error dibuat di server, dikirim sebagai status gRPC, lalu dijawab gateway seperti handler
+    async fn gateway_response(err: AppError) -> (StatusCode, serde_json::Value) {
+        let status = Status::from(ErrorResponse::from(err));
+        let handler_result: Result<(), ErrorResponse> = Err(ErrorResponse::from(status));
+
+        let response = handler_result.into_response();
+        let status_code = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        (status_code, serde_json::from_slice(&body).unwrap())
+    }
+
+    #[tokio::test]
+    async fn handler_errors_map_to_http_statuses() {
+        let cases = [
+            (
+                AppError::NotFound("User 7 not found".to_string()),
+                StatusCode::NOT_FOUND,
+            ),
+            (AppError::InvalidCredentials, StatusCode::UNAUTHORIZED),
+            (AppError::TokenExpiredError, StatusCode::UNAUTHORIZED),
+            (AppError::TokenValidationError, StatusCode::UNAUTHORIZED),
+            (AppError::EmailAlreadyExists, StatusCode::CONFLICT),
+            (
+                AppError::ValidationError(ValidationErrors::new()),
+                StatusCode::UNPROCESSABLE_ENTITY,
+            ),
+            (
+                AppError::InternalError("boom".to_string()),
+                StatusCode::INTERNAL_SERVER_ERROR,
+            ),
+            (
+                AppError::Custom("Insufficient balance".to_string()),
+                StatusCode::BAD_REQUEST,
+            ),
+        ];
+
+        for (err, expected) in cases {
+            let kind = err.kind();
+            let (status, body) = gateway_response(err).await;
+
+            assert_eq!(status, expected, "{kind}");
+            assert!(body["message"].is_string(), "{kind}: {body}");
+            assert!(body["status"].is_string(), "{kind}: {body}");
+        }
+    }
+
+    #[tokio::test]
+    async fn not_found_message_reaches_the_client() {
+        let (_, body) = gateway_response(AppError::NotFound("User 7 not found".to_string())).await;
+
+        assert_eq!(body["message"], "User 7 not found");
+    }
+
+    async fn status_of(app: Router, uri: &str) -> StatusCode {
+        app.oneshot(
+            axum::http::Request::builder()
+                .uri(uri)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+        .status()
+    }
+
+    fn version_router() -> Router {
+        Router::new().route("/version", get(|| async { "1.0.0" }))
+    }
+
+    #[tokio::test]
+    async fn prefixed_routes_answer_only_under_the_prefix() {
+        let mut api = ApiDoc::openapi();
+
+        let app = mount_under_prefix(version_router(), &mut api, "/payments");
+
+        assert_eq!(
+            status_of(app.clone(), "/payments/version").await,
+            StatusCode::OK
+        );
+        assert_eq!(status_of(app, "/version").await, StatusCode::NOT_FOUND);
+        let servers = api.servers.unwrap();
+        assert_eq!(servers[0].url, "/payments");
+    }
+
+    #[tokio::test]
+    async fn empty_prefix_keeps_root_routes() {
+        let mut api = ApiDoc::openapi();
+
+        let app = mount_under_prefix(version_router(), &mut api, "");
+
+        assert_eq!(status_of(app, "/version").await, StatusCode::OK);
+        assert!(api.servers.is_none());
+    }
+
+    #[test]
+    fn omitted_page_size_uses_the_configured_default_and_is_documented() {
+        // satu-satunya test di biner ini yang memasang ukuran halaman
+        install_default_page_size(25);
+        install_max_page_size(50);
+
+        let uri: axum::http::Uri = "/api/users?page=2".parse().unwrap();
+        let Query(params) = Query::<FindAllUserRequest>::try_from_uri(&uri).unwrap();
+        assert_eq!(params.page, 2);
+        assert_eq!(params.page_size, 25);
+
+        let api = serde_json::to_value(ApiDoc::openapi()).unwrap();
+        let page_size = api["paths"]["/api/users"]["get"]["parameters"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|parameter| parameter["name"] == "page_size")
+            .unwrap();
+        assert_eq!(page_size["schema"]["default"], 25);
+        assert_eq!(page_size["schema"]["maximum"], 50);
+        assert_eq!(page_size["schema"]["minimum"], 1);
+    }
+}
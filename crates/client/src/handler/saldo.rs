@@ -1,3 +1,4 @@
+use super::{forbidden, small_body_limit};
 use crate::{
     middleware::{jwt, validate::SimpleValidatedJson},
     state::AppState,
@@ -12,8 +13,14 @@ use axum::{
 };
 use serde_json::json;
 use shared::domain::{
-    request::{CreateSaldoRequest, FindAllSaldoRequest, UpdateSaldoRequest},
-    response::{ApiResponse, ApiResponsePagination, saldo::SaldoResponse},
+    request::{
+        AdjustBalanceRequest, CreateSaldoRequest, FindAllSaldoRequest, PageRequest,
+        ReconcileBalanceRequest, TopSaldosRequest, UpdateSaldoRequest,
+    },
+    response::{
+        ApiResponse, ApiResponsePagination, ErrorResponse,
+        saldo::{BalanceReconciliationResponse, SaldoRankingResponse, SaldoResponse},
+    },
 };
 use std::sync::Arc;
 use utoipa_axum::router::OpenApiRouter;
@@ -35,10 +42,10 @@ use utoipa_axum::router::OpenApiRouter;
 pub async fn get_saldos(
     State(data): State<Arc<AppState>>,
     Query(params): Query<FindAllSaldoRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.saldo_service.get_saldos(&params).await {
         Ok(saldoes) => Ok((StatusCode::OK, Json(json!(saldoes)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -62,11 +69,11 @@ pub async fn get_saldo(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.saldo_service.get_saldo(id).await {
         Ok(saldo) => Ok((StatusCode::OK, Json(json!(saldo)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -78,10 +85,11 @@ pub async fn get_saldo(
         ("bearer_auth" = [])
     ),
     params(
-        ("id" = i32, Path, description = "User ID")
+        ("id" = i32, Path, description = "User ID"),
+        PageRequest
     ),
     responses(
-        (status = 200, description = "Saldo details retrieved successfully", body = ApiResponse<Option<Vec<SaldoResponse>>>),
+        (status = 200, description = "Saldo details retrieved successfully", body = ApiResponsePagination<Vec<SaldoResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
         (status = 404, description = "Saldo records not found for the user", body = String),
     )
@@ -89,12 +97,18 @@ pub async fn get_saldo(
 pub async fn get_saldo_users(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
+    Query(params): Query<PageRequest>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match data.di_container.saldo_service.get_saldo_users(id).await {
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match data
+        .di_container
+        .saldo_service
+        .get_saldo_users(id, &params)
+        .await
+    {
         Ok(saldo) => Ok((StatusCode::OK, Json(json!(saldo)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -118,11 +132,11 @@ pub async fn get_saldo_user(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.saldo_service.get_saldo_user(id).await {
         Ok(saldo) => Ok((StatusCode::OK, Json(json!(saldo)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -143,10 +157,10 @@ pub async fn get_saldo_user(
 pub async fn create_saldo(
     State(data): State<Arc<AppState>>,
     SimpleValidatedJson(body): SimpleValidatedJson<CreateSaldoRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.saldo_service.create_saldo(&body).await {
         Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -171,13 +185,13 @@ pub async fn update_saldo(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     SimpleValidatedJson(mut body): SimpleValidatedJson<UpdateSaldoRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     body.saldo_id = id;
 
     match data.di_container.saldo_service.update_saldo(&body).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -201,7 +215,7 @@ pub async fn delete_saldo(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.saldo_service.delete_saldo(id).await {
         Ok(_) => Ok((
             StatusCode::OK,
@@ -210,17 +224,129 @@ pub async fn delete_saldo(
                 "message": "Saldo deleted successfully"
             })),
         )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/saldos/top",
+    tag = "Saldo",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(TopSaldosRequest),
+    responses(
+        (status = 200, description = "Highest-balance saldos, ties ordered by user id", body = ApiResponse<Vec<SaldoRankingResponse>>),
+        (status = 400, description = "limit is outside 1-100", body = String),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn get_top_saldos(
+    State(data): State<Arc<AppState>>,
+    Query(params): Query<TopSaldosRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match data
+        .di_container
+        .saldo_service
+        .get_top_saldos(&params)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/saldos/adjust",
+    tag = "Saldo",
+    security(
+        ("bearer_auth" = [])
+    ),
+    request_body = AdjustBalanceRequest,
+    responses(
+        (status = 200, description = "Balance adjusted and recorded in the ledger", body = ApiResponse<SaldoResponse>),
+        (status = 400, description = "Adjustment would make the balance negative", body = String),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Only administrators may adjust balances", body = String),
+        (status = 404, description = "Saldo not found", body = String),
+        (status = 422, description = "Delta is zero or reason is missing", body = String),
+    )
+)]
+pub async fn adjust_balance(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i32>,
+    SimpleValidatedJson(body): SimpleValidatedJson<AdjustBalanceRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may adjust balances"));
+    }
+
+    match data
+        .di_container
+        .saldo_service
+        .adjust_balance(user_id, &body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/saldos/reconcile",
+    tag = "Saldo",
+    security(
+        ("bearer_auth" = [])
+    ),
+    request_body = ReconcileBalanceRequest,
+    responses(
+        (status = 200, description = "Balances that do not match their transaction history", body = ApiResponse<Vec<BalanceReconciliationResponse>>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Only administrators may reconcile balances", body = String),
+        (status = 404, description = "User not found", body = String),
+        (status = 422, description = "Invalid user id", body = String),
+    )
+)]
+pub async fn reconcile_balance(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i32>,
+    SimpleValidatedJson(body): SimpleValidatedJson<ReconcileBalanceRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may reconcile balances"));
+    }
+
+    match data
+        .di_container
+        .saldo_service
+        .reconcile_balance(user_id, &body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
     }
 }
 
 pub fn saldos_routes(app_state: Arc<AppState>) -> OpenApiRouter {
     OpenApiRouter::new()
         .route("/api/saldos", get(get_saldos))
+        .route("/api/saldos/top", get(get_top_saldos))
         .route("/api/saldos/{id}", get(get_saldo))
         .route("/api/saldos/users/{id}", get(get_saldo_users))
         .route("/api/saldos/user/{id}", get(get_saldo_user))
-        .route("/api/saldos", post(create_saldo))
+        .route("/api/saldos", post(create_saldo).layer(small_body_limit()))
+        .route(
+            "/api/saldos/adjust",
+            post(adjust_balance).layer(small_body_limit()),
+        )
+        .route(
+            "/api/saldos/reconcile",
+            post(reconcile_balance).layer(small_body_limit()),
+        )
         .route("/api/saldos/{id}", put(update_saldo))
         .route("/api/saldos/{id}", delete(delete_saldo))
         .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
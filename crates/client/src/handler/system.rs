@@ -0,0 +1,128 @@
+use super::{forbidden, small_body_limit};
+use crate::{
+    middleware::{jwt, validate::SimpleValidatedJson},
+    state::AppState,
+};
+use axum::{
+    Json,
+    extract::{Extension, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::{get, post},
+};
+use serde_json::json;
+use shared::domain::{
+    request::CacheFlushRequest,
+    response::{
+        ApiResponse, ErrorResponse,
+        system::{CacheFlushResponse, CacheStatsResponse, RuntimeConfigResponse},
+    },
+};
+use std::sync::Arc;
+use utoipa_axum::router::OpenApiRouter;
+
+#[utoipa::path(
+    get,
+    path = "/api/system/config",
+    tag = "System",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Runtime configuration without secrets", body = ApiResponse<RuntimeConfigResponse>),
+        (status = 401, description = "Unauthorized", body = String),
+        (status = 403, description = "Only administrators may view the runtime configuration", body = String)
+    )
+)]
+pub async fn get_runtime_config(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden(
+            "Only administrators may view the runtime configuration",
+        ));
+    }
+
+    match data.di_container.system_service.get_runtime_config().await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/system/cache",
+    tag = "System",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 200, description = "Approximate cache key count per prefix", body = ApiResponse<CacheStatsResponse>),
+        (status = 401, description = "Unauthorized", body = String),
+        (status = 403, description = "Only administrators may inspect the cache", body = String),
+        (status = 500, description = "Cache is unavailable", body = String)
+    )
+)]
+pub async fn get_cache_stats(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may inspect the cache"));
+    }
+
+    match data.di_container.system_service.cache_stats().await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/system/cache/flush",
+    tag = "System",
+    security(
+        ("bearer_auth" = [])
+    ),
+    request_body = CacheFlushRequest,
+    responses(
+        (status = 200, description = "Number of cache keys removed under the prefix", body = ApiResponse<CacheFlushResponse>),
+        (status = 401, description = "Unauthorized", body = String),
+        (status = 403, description = "Only administrators may flush the cache", body = String),
+        (status = 422, description = "Invalid prefix", body = String),
+        (status = 500, description = "Cache is unavailable", body = String)
+    )
+)]
+pub async fn flush_cache_prefix(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i32>,
+    SimpleValidatedJson(body): SimpleValidatedJson<CacheFlushRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may flush the cache"));
+    }
+
+    match data
+        .di_container
+        .system_service
+        .cache_flush_prefix(&body)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn system_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    OpenApiRouter::new()
+        .route("/api/system/config", get(get_runtime_config))
+        .route("/api/system/cache", get(get_cache_stats))
+        .route(
+            "/api/system/cache/flush",
+            post(flush_cache_prefix).layer(small_body_limit()),
+        )
+        .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
+        .with_state(app_state.clone())
+}
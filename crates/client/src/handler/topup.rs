@@ -1,3 +1,4 @@
+use super::{forbidden, small_body_limit};
 use crate::{
     middleware::{jwt, validate::SimpleValidatedJson},
     state::AppState,
@@ -12,8 +13,13 @@ use axum::{
 };
 use serde_json::json;
 use shared::domain::{
-    request::{CreateTopupRequest, FindAllTopupRequest, UpdateTopupRequest},
-    response::{ApiResponse, ApiResponsePagination, topup::TopupResponse},
+    request::{
+        CreateTopupRequest, FindAllTopupRequest, PageRequest, TopupStatsRequest, UpdateTopupRequest,
+    },
+    response::{
+        ApiResponse, ApiResponsePagination, ErrorResponse,
+        topup::{TopupMethodStatsResponse, TopupResponse},
+    },
 };
 use std::sync::Arc;
 use utoipa_axum::router::OpenApiRouter;
@@ -35,10 +41,10 @@ use utoipa_axum::router::OpenApiRouter;
 pub async fn get_topups(
     State(data): State<Arc<AppState>>,
     Query(params): Query<FindAllTopupRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.topup_service.get_topups(&params).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -62,11 +68,43 @@ pub async fn get_topup(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.topup_service.get_topup(id).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/topups/no/{topup_no}",
+    tag = "Topup",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("topup_no" = String, Path, description = "Topup reference number")
+    ),
+    responses(
+        (status = 200, description = "Topup details retrieved successfully", body = ApiResponse<TopupResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 404, description = "Topup record not found", body = String),
+    )
+)]
+pub async fn get_topup_by_no(
+    State(data): State<Arc<AppState>>,
+    Path(topup_no): Path<String>,
+    Extension(_user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match data
+        .di_container
+        .topup_service
+        .get_topup_by_no(&topup_no)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -78,10 +116,11 @@ pub async fn get_topup(
         ("bearer_auth" = [])
     ),
     params(
-        ("id" = i32, Path, description = "User ID")
+        ("id" = i32, Path, description = "User ID"),
+        PageRequest
     ),
     responses(
-        (status = 200, description = "Topup details retrieved successfully", body = ApiResponse<Option<Vec<TopupResponse>>>),
+        (status = 200, description = "Topup details retrieved successfully", body = ApiResponsePagination<Vec<TopupResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
         (status = 404, description = "Topup records not found for the user", body = String),
     )
@@ -89,12 +128,18 @@ pub async fn get_topup(
 pub async fn get_topup_users(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
+    Query(params): Query<PageRequest>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match data.di_container.topup_service.get_topup_users(id).await {
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match data
+        .di_container
+        .topup_service
+        .get_topup_users(id, &params)
+        .await
+    {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -118,11 +163,11 @@ pub async fn get_topup_user(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.topup_service.get_topup_user(id).await {
         Ok(saldo) => Ok((StatusCode::OK, Json(json!(saldo)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -143,10 +188,10 @@ pub async fn get_topup_user(
 pub async fn create_topup(
     State(data): State<Arc<AppState>>,
     SimpleValidatedJson(body): SimpleValidatedJson<CreateTopupRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.topup_service.create_topup(&body).await {
         Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -171,13 +216,13 @@ pub async fn update_topup(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     SimpleValidatedJson(mut body): SimpleValidatedJson<UpdateTopupRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     body.topup_id = id;
 
     match data.di_container.topup_service.update_topup(&body).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -201,7 +246,7 @@ pub async fn delete_topup(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.topup_service.delete_topup(id).await {
         Ok(_) => Ok((
             StatusCode::OK,
@@ -210,19 +255,158 @@ pub async fn delete_topup(
                 "message": "Topup deleted successfully"
             })),
         )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/topups/{id}/confirm",
+    tag = "Topup",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "Topup ID")
+    ),
+    responses(
+        (status = 200, description = "Topup confirmed and saldo credited", body = ApiResponse<TopupResponse>),
+        (status = 400, description = "Topup is not pending", body = String),
+        (status = 401, description = "Unauthorized", body = String),
+        (status = 403, description = "Only administrators may confirm topups", body = String),
+        (status = 404, description = "Topup not found", body = String)
+    )
+)]
+pub async fn confirm_topup(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may confirm topups"));
+    }
+
+    match data.di_container.topup_service.confirm_topup(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/topups/{id}/fail",
+    tag = "Topup",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "Topup ID")
+    ),
+    responses(
+        (status = 200, description = "Topup marked as failed", body = ApiResponse<TopupResponse>),
+        (status = 400, description = "Topup is not pending", body = String),
+        (status = 401, description = "Unauthorized", body = String),
+        (status = 403, description = "Only administrators may fail topups", body = String),
+        (status = 404, description = "Topup not found", body = String)
+    )
+)]
+pub async fn fail_topup(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may fail topups"));
+    }
+
+    match data.di_container.topup_service.fail_topup(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/topups/{id}/refund",
+    tag = "Topup",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "Topup ID")
+    ),
+    responses(
+        (status = 200, description = "Topup refunded and saldo debited", body = ApiResponse<TopupResponse>),
+        (status = 400, description = "Topup is not successful or balance is insufficient", body = String),
+        (status = 401, description = "Unauthorized", body = String),
+        (status = 403, description = "Only administrators may refund topups", body = String),
+        (status = 404, description = "Topup not found", body = String)
+    )
+)]
+pub async fn refund_topup(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may refund topups"));
+    }
+
+    match data.di_container.topup_service.refund_topup(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/topups/stats",
+    tag = "Topup",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(TopupStatsRequest),
+    responses(
+        (status = 200, description = "Topup count and volume per method", body = ApiResponse<Vec<TopupMethodStatsResponse>>),
+        (status = 400, description = "start_date is after end_date", body = String),
+        (status = 401, description = "Unauthorized", body = String),
+        (status = 403, description = "Only administrators may view topup stats", body = String)
+    )
+)]
+pub async fn get_topup_stats(
+    State(data): State<Arc<AppState>>,
+    Query(params): Query<TopupStatsRequest>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may view topup stats"));
+    }
+
+    match data
+        .di_container
+        .topup_service
+        .get_topup_stats(&params)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
     }
 }
 
 pub fn topup_routes(app_state: Arc<AppState>) -> OpenApiRouter {
     OpenApiRouter::new()
         .route("/api/topups", get(get_topups))
+        .route("/api/topups/stats", get(get_topup_stats))
         .route("/api/topups/{id}", get(get_topup))
+        .route("/api/topups/no/{topup_no}", get(get_topup_by_no))
         .route("/api/topups/users/{id}", get(get_topup_users))
         .route("/api/topups/user/{id}", get(get_topup_user))
-        .route("/api/topups", post(create_topup))
+        .route("/api/topups", post(create_topup).layer(small_body_limit()))
         .route("/api/topups/{id}", put(update_topup))
         .route("/api/topups/{id}", delete(delete_topup))
+        .route("/api/topups/{id}/confirm", post(confirm_topup))
+        .route("/api/topups/{id}/fail", post(fail_topup))
+        .route("/api/topups/{id}/refund", post(refund_topup))
         .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
         .with_state(app_state.clone())
 }
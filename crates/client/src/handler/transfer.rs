@@ -1,5 +1,7 @@
+use super::{forbidden, small_body_limit};
 use crate::{
     middleware::{jwt, validate::SimpleValidatedJson},
+    receipt::TransferReceipt,
     state::AppState,
 };
 use axum::{
@@ -11,9 +13,18 @@ use axum::{
     routing::{delete, get, post, put},
 };
 use serde_json::json;
-use shared::domain::{
-    request::{CreateTransferRequest, FindAllTransferRequest, UpdateTransferRequest},
-    response::{ApiResponse, ApiResponsePagination, transfer::TransferResponse},
+use shared::{
+    domain::{
+        request::{
+            CreateTransferRequest, DailyTransferTotalsRequest, FindAllTransferRequest, PageRequest,
+            UpdateTransferRequest,
+        },
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            transfer::{DailyTransferTotalResponse, TransferResponse},
+        },
+    },
+    utils::AppError,
 };
 use std::sync::Arc;
 use utoipa_axum::router::OpenApiRouter;
@@ -35,7 +46,7 @@ use utoipa_axum::router::OpenApiRouter;
 pub async fn get_transfers(
     State(data): State<Arc<AppState>>,
     Query(params): Query<FindAllTransferRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data
         .di_container
         .transfer_service
@@ -43,7 +54,7 @@ pub async fn get_transfers(
         .await
     {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -67,11 +78,11 @@ pub async fn get_transfer(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.transfer_service.get_transfer(id).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -83,10 +94,11 @@ pub async fn get_transfer(
         ("bearer_auth" = [])
     ),
     params(
-        ("id" = i32, Path, description = "User ID")
+        ("id" = i32, Path, description = "User ID"),
+        PageRequest
     ),
     responses(
-        (status = 200, description = "Transfer details retrieved successfully", body = ApiResponse<Option<Vec<TransferResponse>>>),
+        (status = 200, description = "Transfer details retrieved successfully", body = ApiResponsePagination<Vec<TransferResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
         (status = 404, description = "Transfer records not found for the user", body = String),
     )
@@ -94,17 +106,51 @@ pub async fn get_transfer(
 pub async fn get_transfer_users(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
+    Query(params): Query<PageRequest>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data
         .di_container
         .transfer_service
-        .get_transfer_users(id)
+        .get_transfer_users(id, &params)
         .await
     {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/transfers/users/{id}/history",
+    tag = "Transfer",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID"),
+        PageRequest
+    ),
+    responses(
+        (status = 200, description = "Transfers sent and received by the user, newest first", body = ApiResponsePagination<Vec<TransferResponse>>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 404, description = "User not found", body = String),
+    )
+)]
+pub async fn get_user_transfer_history(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Query(params): Query<PageRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match data
+        .di_container
+        .transfer_service
+        .get_user_transfer_history(id, &params)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -128,7 +174,7 @@ pub async fn get_transfer_user(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data
         .di_container
         .transfer_service
@@ -137,7 +183,7 @@ pub async fn get_transfer_user(
     {
         Ok(saldo) => Ok((StatusCode::OK, Json(json!(saldo)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -151,6 +197,8 @@ pub async fn get_transfer_user(
     request_body = CreateTransferRequest,
     responses(
         (status = 201, description = "Transfer record created successfully", body = ApiResponse<TransferResponse>),
+        (status = 200, description = "Dry run passed, projected balances returned without saving", body = ApiResponse<TransferResponse>),
+        (status = 400, description = "Insufficient balance or frozen account", body = String),
         (status = 401, description = "Unauthorized access", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
@@ -158,15 +206,16 @@ pub async fn get_transfer_user(
 pub async fn create_transfer(
     State(data): State<Arc<AppState>>,
     SimpleValidatedJson(body): SimpleValidatedJson<CreateTransferRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data
         .di_container
         .transfer_service
         .create_transfer(&body)
         .await
     {
+        Ok(response) if body.dry_run => Ok((StatusCode::OK, Json(json!(response)))),
         Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -191,7 +240,7 @@ pub async fn update_transfer(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     SimpleValidatedJson(mut body): SimpleValidatedJson<UpdateTransferRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     body.transfer_id = id;
 
     match data
@@ -202,7 +251,7 @@ pub async fn update_transfer(
     {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -226,7 +275,7 @@ pub async fn delete_transfer(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.topup_service.delete_topup(id).await {
         Ok(_) => Ok((
             StatusCode::OK,
@@ -235,19 +284,201 @@ pub async fn delete_transfer(
                 "message": "Transfer deleted successfully"
             })),
         )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/transfers/{id}/reverse",
+    tag = "Transfer",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "Transfer ID")
+    ),
+    responses(
+        (status = 200, description = "Compensating transfer created, or held transfer cancelled", body = ApiResponse<TransferResponse>),
+        (status = 400, description = "Transfer already reversed, account frozen, or insufficient balance", body = String),
+        (status = 401, description = "Unauthorized", body = String),
+        (status = 403, description = "Only administrators may reverse transfers", body = String),
+        (status = 404, description = "Transfer not found", body = String)
+    )
+)]
+pub async fn reverse_transfer(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may reverse transfers"));
+    }
+
+    match data
+        .di_container
+        .transfer_service
+        .reverse_transfer(id)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/transfers/{id}/approve",
+    tag = "Transfer",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "Transfer ID")
+    ),
+    responses(
+        (status = 200, description = "Held transfer settled", body = ApiResponse<TransferResponse>),
+        (status = 400, description = "Transfer not awaiting review, account frozen, or insufficient balance", body = String),
+        (status = 401, description = "Unauthorized", body = String),
+        (status = 403, description = "Only administrators may approve transfers", body = String),
+        (status = 404, description = "Transfer not found", body = String)
+    )
+)]
+pub async fn approve_transfer(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may approve transfers"));
+    }
+
+    match data
+        .di_container
+        .transfer_service
+        .approve_transfer(id)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/transfers/{id}/receipt.pdf",
+    tag = "Transfer",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "Transfer ID")
+    ),
+    responses(
+        (status = 200, description = "Printable receipt, watermarked when the transfer was reversed", content_type = "application/pdf", body = Vec<u8>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Only the sender, the receiver or an administrator may download the receipt", body = String),
+        (status = 404, description = "Transfer not found", body = String),
+    )
+)]
+pub async fn get_transfer_receipt(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    let transfer = data
+        .di_container
+        .transfer_service
+        .get_transfer(id)
+        .await?
+        .data
+        .ok_or_else(|| AppError::NotFound(format!("Transfer with id {id} not found")))?;
+
+    if user_id != transfer.transfer_from
+        && user_id != transfer.transfer_to
+        && !data.is_admin(user_id)
+    {
+        return Err(forbidden(
+            "Only the sender, the receiver or an administrator may download the receipt",
+        ));
+    }
+
+    let receipt = TransferReceipt {
+        sender_name: party_name(&data, transfer.transfer_from, &transfer.from_label).await,
+        receiver_name: party_name(&data, transfer.transfer_to, &transfer.to_label).await,
+        transfer: &transfer,
+    };
+    Ok(receipt.response()?)
+}
+
+// user yang sudah dihapus tetap tercetak memakai label yang tersimpan di transfer
+async fn party_name(data: &AppState, user_id: i32, fallback: &str) -> String {
+    match data.di_container.user_service.get_user(user_id).await {
+        Ok(ApiResponse {
+            data: Some(user), ..
+        }) => format!("{} {}", user.firstname, user.lastname),
+        _ => fallback.to_string(),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/transfers/daily-totals",
+    tag = "Transfer",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(DailyTransferTotalsRequest),
+    responses(
+        (status = 200, description = "Amount sent and received per user per day", body = ApiResponse<Vec<DailyTransferTotalResponse>>),
+        (status = 400, description = "Invalid or too wide date range", body = String),
+        (status = 401, description = "Unauthorized", body = String),
+        (status = 403, description = "Only administrators may view transfer totals", body = String)
+    )
+)]
+pub async fn get_daily_transfer_totals(
+    State(data): State<Arc<AppState>>,
+    Query(params): Query<DailyTransferTotalsRequest>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may view transfer totals"));
+    }
+
+    match data
+        .di_container
+        .transfer_service
+        .get_daily_transfer_totals(&params)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
     }
 }
 
 pub fn transfers_routes(app_state: Arc<AppState>) -> OpenApiRouter {
     OpenApiRouter::new()
         .route("/api/transfers", get(get_transfers))
+        .route(
+            "/api/transfers/daily-totals",
+            get(get_daily_transfer_totals),
+        )
         .route("/api/transfers/{id}", get(get_transfer))
         .route("/api/transfers/users/{id}", get(get_transfer_users))
+        .route(
+            "/api/transfers/users/{id}/history",
+            get(get_user_transfer_history),
+        )
         .route("/api/transfers/user/{id}", get(get_transfer_user))
-        .route("/api/transfers", post(create_transfer))
+        .route(
+            "/api/transfers",
+            post(create_transfer).layer(small_body_limit()),
+        )
         .route("/api/transfers/{id}", put(update_transfer))
         .route("/api/transfers/{id}", delete(delete_transfer))
+        .route("/api/transfers/{id}/reverse", post(reverse_transfer))
+        .route("/api/transfers/{id}/approve", post(approve_transfer))
+        .route("/api/transfers/{id}/receipt.pdf", get(get_transfer_receipt))
         .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
         .with_state(app_state.clone())
 }
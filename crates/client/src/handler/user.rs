@@ -1,3 +1,4 @@
+use super::{forbidden, import_body_limit, small_body_limit};
 use crate::{
     middleware::{jwt, validate::SimpleValidatedJson},
     state::AppState,
@@ -12,8 +13,14 @@ use axum::{
 };
 use serde_json::json;
 use shared::domain::{
-    request::{FindAllUserRequest, RegisterRequest, UpdateUserRequest},
-    response::{ApiResponse, ApiResponsePagination, user::UserResponse},
+    request::{
+        DeleteUserRequest, FindAllUserRequest, ImportUsersParams, ImportUsersRequest,
+        PurgeUserRequest, RegisterRequest, UpdateUserRequest,
+    },
+    response::{
+        ApiResponse, ApiResponsePagination, ErrorResponse,
+        user::{ImportUsersResponse, PurgeUserResponse, UserDeletionCheckResponse, UserResponse},
+    },
 };
 use std::sync::Arc;
 use utoipa_axum::router::OpenApiRouter;
@@ -29,16 +36,22 @@ use utoipa_axum::router::OpenApiRouter;
     responses(
         (status = 200, description = "List of user records", body = ApiResponsePagination<Vec<UserResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Only administrators may include deleted users", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn get_users(
     State(data): State<Arc<AppState>>,
     Query(params): Query<FindAllUserRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if params.include_deleted && !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may include deleted users"));
+    }
+
     match data.di_container.user_service.get_users(&params).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -62,11 +75,11 @@ pub async fn get_user(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.user_service.get_user(id).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -87,10 +100,10 @@ pub async fn get_user(
 pub async fn create_user(
     State(data): State<Arc<AppState>>,
     SimpleValidatedJson(body): SimpleValidatedJson<RegisterRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.user_service.create_user(&body).await {
         Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -115,13 +128,13 @@ pub async fn update_user(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     SimpleValidatedJson(mut body): SimpleValidatedJson<UpdateUserRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     body.id = id;
 
     match data.di_container.user_service.update_user(&body).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -133,20 +146,34 @@ pub async fn update_user(
         ("bearer_auth" = [])
     ),
     params(
-        ("id" = i32, Path, description = "User ID")
+        ("id" = i32, Path, description = "User ID"),
+        DeleteUserRequest
     ),
     responses(
         (status = 200, description = "User record deleted successfully", body = serde_json::Value),
+        (status = 400, description = "User still has incoming transfers or active sessions and force was not set", body = String),
         (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Only administrators may force a deletion", body = String),
+        (status = 404, description = "User not found", body = String),
         (status = 500, description = "Internal server error", body = String),
     )
 )]
 pub async fn delete_user(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
-    Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
-    match data.di_container.user_service.delete_user(id).await {
+    Query(params): Query<DeleteUserRequest>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if params.force && !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may force a deletion"));
+    }
+
+    match data
+        .di_container
+        .user_service
+        .delete_user(id, params.force)
+        .await
+    {
         Ok(_) => Ok((
             StatusCode::OK,
             Json(json!({
@@ -154,17 +181,239 @@ pub async fn delete_user(
                 "message": "User deleted successfully"
             })),
         )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/users/{id}/can-delete",
+    tag = "User",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "References that would block deleting the user", body = ApiResponse<UserDeletionCheckResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Only administrators may check user deletion", body = String),
+        (status = 404, description = "User not found", body = String),
+    )
+)]
+pub async fn can_delete_user(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may check user deletion"));
+    }
+
+    match data.di_container.user_service.can_delete_user(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/{id}/restore",
+    tag = "User",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "User restored successfully", body = ApiResponse<UserResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Only administrators may restore users", body = String),
+        (status = 404, description = "Deleted user not found", body = String),
+    )
+)]
+pub async fn restore_user(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may restore users"));
+    }
+
+    match data.di_container.user_service.restore_user(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/{id}/freeze",
+    tag = "User",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "User frozen successfully", body = ApiResponse<UserResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Only administrators may freeze users", body = String),
+        (status = 404, description = "User not found", body = String),
+    )
+)]
+pub async fn freeze_user(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may freeze users"));
+    }
+
+    match data.di_container.user_service.freeze_user(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/{id}/unfreeze",
+    tag = "User",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID")
+    ),
+    responses(
+        (status = 200, description = "User unfrozen successfully", body = ApiResponse<UserResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Only administrators may unfreeze users", body = String),
+        (status = 404, description = "User not found", body = String),
+    )
+)]
+pub async fn unfreeze_user(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may unfreeze users"));
     }
+
+    match data.di_container.user_service.unfreeze_user(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/users/{id}/purge",
+    tag = "User",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "User ID"),
+        PurgeUserRequest
+    ),
+    responses(
+        (status = 200, description = "User and related records purged", body = ApiResponse<PurgeUserResponse>),
+        (status = 400, description = "User is a transfer counterparty and force was not set", body = String),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Only administrators may purge users", body = String),
+        (status = 404, description = "User not found", body = String),
+    )
+)]
+pub async fn purge_user(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i32>,
+    Query(params): Query<PurgeUserRequest>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may purge users"));
+    }
+
+    match data
+        .di_container
+        .user_service
+        .purge_user(id, params.force)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    post,
+    path = "/api/users/import",
+    tag = "User",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(ImportUsersParams),
+    request_body(content = String, content_type = "text/csv", description = "CSV with a name,email,password header"),
+    responses(
+        (status = 201, description = "Users imported, failed rows are listed per row", body = ApiResponse<ImportUsersResponse>),
+        (status = 400, description = "Malformed CSV or too many rows", body = String),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 403, description = "Only administrators may import users", body = String),
+        (status = 422, description = "No users were imported", body = ApiResponse<ImportUsersResponse>),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn import_users(
+    State(data): State<Arc<AppState>>,
+    Query(params): Query<ImportUsersParams>,
+    Extension(user_id): Extension<i32>,
+    body: String,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may import users"));
+    }
+
+    let request = ImportUsersRequest::from_csv(&body, params.continue_on_error)?;
+
+    let response = data
+        .di_container
+        .user_service
+        .import_users(&request)
+        .await?;
+
+    let status = if response.data.imported > 0 {
+        StatusCode::CREATED
+    } else {
+        StatusCode::UNPROCESSABLE_ENTITY
+    };
+
+    Ok((status, Json(json!(response))))
 }
 
 pub fn users_routes(app_state: Arc<AppState>) -> OpenApiRouter {
     OpenApiRouter::new()
         .route("/api/users", get(get_users))
         .route("/api/users/{id}", get(get_user))
-        .route("/api/users", post(create_user))
+        .route("/api/users", post(create_user).layer(small_body_limit()))
+        .route(
+            "/api/users/import",
+            post(import_users).layer(import_body_limit()),
+        )
         .route("/api/users/{id}", put(update_user))
         .route("/api/users/{id}", delete(delete_user))
+        .route("/api/users/{id}/can-delete", get(can_delete_user))
+        .route("/api/users/{id}/restore", post(restore_user))
+        .route("/api/users/{id}/freeze", post(freeze_user))
+        .route("/api/users/{id}/unfreeze", post(unfreeze_user))
+        .route("/api/users/{id}/purge", delete(purge_user))
         .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
         .with_state(app_state.clone())
 }
@@ -0,0 +1,122 @@
+use crate::{middleware::jwt, state::AppState};
+use axum::{
+    Json,
+    extract::{Extension, Path, Query, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::{delete, post},
+};
+use serde_json::json;
+use shared::domain::{
+    request::FindVirtualCardsRequest,
+    response::{
+        ApiResponse, ApiResponsePagination, ErrorResponse,
+        virtual_card::{VirtualCardResponse, VirtualCardSummaryResponse},
+    },
+};
+use std::sync::Arc;
+use utoipa_axum::router::OpenApiRouter;
+
+#[utoipa::path(
+    post,
+    path = "/api/virtual-cards",
+    tag = "VirtualCard",
+    security(
+        ("bearer_auth" = [])
+    ),
+    responses(
+        (status = 201, description = "Virtual card generated successfully", body = ApiResponse<VirtualCardResponse>),
+        (status = 400, description = "Account is frozen", body = String),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn generate_virtual_card(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match data
+        .di_container
+        .virtual_card_service
+        .generate_virtual_card(user_id)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    get,
+    path = "/api/virtual-cards",
+    tag = "VirtualCard",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(FindVirtualCardsRequest),
+    responses(
+        (status = 200, description = "Virtual cards of the authenticated user", body = ApiResponsePagination<Vec<VirtualCardSummaryResponse>>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn list_virtual_cards(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i32>,
+    Query(params): Query<FindVirtualCardsRequest>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match data
+        .di_container
+        .virtual_card_service
+        .list_virtual_cards(user_id, &params)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+#[utoipa::path(
+    delete,
+    path = "/api/virtual-cards/{id}",
+    tag = "VirtualCard",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i32, Path, description = "Virtual card ID")
+    ),
+    responses(
+        (status = 200, description = "Virtual card revoked", body = ApiResponse<VirtualCardSummaryResponse>),
+        (status = 401, description = "Unauthorized access", body = String),
+        (status = 404, description = "Active virtual card not found", body = String),
+        (status = 500, description = "Internal server error", body = String),
+    )
+)]
+pub async fn revoke_virtual_card(
+    State(data): State<Arc<AppState>>,
+    Extension(user_id): Extension<i32>,
+    Path(id): Path<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    match data
+        .di_container
+        .virtual_card_service
+        .revoke_virtual_card(user_id, id)
+        .await
+    {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn virtual_card_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    OpenApiRouter::new()
+        .route(
+            "/api/virtual-cards",
+            post(generate_virtual_card).get(list_virtual_cards),
+        )
+        .route("/api/virtual-cards/{id}", delete(revoke_virtual_card))
+        .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
+        .with_state(app_state.clone())
+}
@@ -0,0 +1,53 @@
+use crate::{handler::forbidden, middleware::jwt, state::AppState};
+use axum::{
+    Json,
+    extract::{Extension, Path, State},
+    http::StatusCode,
+    middleware,
+    response::IntoResponse,
+    routing::post,
+};
+use serde_json::json;
+use shared::domain::response::{ApiResponse, ErrorResponse, webhook::WebhookDeliveryResponse};
+use std::sync::Arc;
+use utoipa_axum::router::OpenApiRouter;
+
+#[utoipa::path(
+    post,
+    path = "/api/webhooks/{id}/retry",
+    tag = "Webhook",
+    security(
+        ("bearer_auth" = [])
+    ),
+    params(
+        ("id" = i64, Path, description = "Outbox event ID")
+    ),
+    responses(
+        (status = 200, description = "Webhook delivery requeued", body = ApiResponse<WebhookDeliveryResponse>),
+        (status = 400, description = "Webhook already delivered", body = String),
+        (status = 401, description = "Unauthorized", body = String),
+        (status = 403, description = "Only administrators may retry webhooks", body = String),
+        (status = 404, description = "Webhook event not found", body = String)
+    )
+)]
+pub async fn retry_webhook(
+    State(data): State<Arc<AppState>>,
+    Path(id): Path<i64>,
+    Extension(user_id): Extension<i32>,
+) -> Result<impl IntoResponse, ErrorResponse> {
+    if !data.is_admin(user_id) {
+        return Err(forbidden("Only administrators may retry webhooks"));
+    }
+
+    match data.di_container.webhook_service.retry_webhook(id).await {
+        Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
+        Err(e) => Err(e),
+    }
+}
+
+pub fn webhook_routes(app_state: Arc<AppState>) -> OpenApiRouter {
+    OpenApiRouter::new()
+        .route("/api/webhooks/{id}/retry", post(retry_webhook))
+        .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
+        .with_state(app_state.clone())
+}
@@ -1,3 +1,4 @@
+use super::small_body_limit;
 use crate::{
     middleware::{jwt, validate::SimpleValidatedJson},
     state::AppState,
@@ -12,8 +13,8 @@ use axum::{
 };
 use serde_json::json;
 use shared::domain::{
-    request::{CreateWithdrawRequest, FindAllWithdrawRequest, UpdateWithdrawRequest},
-    response::{ApiResponse, ApiResponsePagination, withdraw::WithdrawResponse},
+    request::{CreateWithdrawRequest, FindAllWithdrawRequest, PageRequest, UpdateWithdrawRequest},
+    response::{ApiResponse, ApiResponsePagination, ErrorResponse, withdraw::WithdrawResponse},
 };
 use std::sync::Arc;
 use utoipa_axum::router::OpenApiRouter;
@@ -35,7 +36,7 @@ use utoipa_axum::router::OpenApiRouter;
 pub async fn get_withdraws(
     State(data): State<Arc<AppState>>,
     Query(params): Query<FindAllWithdrawRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data
         .di_container
         .withdraw_service
@@ -43,7 +44,7 @@ pub async fn get_withdraws(
         .await
     {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -67,11 +68,11 @@ pub async fn get_withdraw(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.withdraw_service.get_withdraw(id).await {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -83,10 +84,11 @@ pub async fn get_withdraw(
         ("bearer_auth" = [])
     ),
     params(
-        ("id" = i32, Path, description = "User ID")
+        ("id" = i32, Path, description = "User ID"),
+        PageRequest
     ),
     responses(
-        (status = 200, description = "List of user withdrawals", body = ApiResponse<Option<Vec<WithdrawResponse>>>),
+        (status = 200, description = "List of user withdrawals", body = ApiResponsePagination<Vec<WithdrawResponse>>),
         (status = 401, description = "Unauthorized access", body = String),
         (status = 404, description = "Withdrawals not found", body = String),
     )
@@ -94,17 +96,18 @@ pub async fn get_withdraw(
 pub async fn get_withdraw_users(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
+    Query(params): Query<PageRequest>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data
         .di_container
         .withdraw_service
-        .get_withdraw_users(id)
+        .get_withdraw_users(id, &params)
         .await
     {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -128,7 +131,7 @@ pub async fn get_withdraw_user(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data
         .di_container
         .withdraw_service
@@ -137,7 +140,7 @@ pub async fn get_withdraw_user(
     {
         Ok(saldo) => Ok((StatusCode::OK, Json(json!(saldo)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -158,7 +161,7 @@ pub async fn get_withdraw_user(
 pub async fn create_withdraw(
     State(data): State<Arc<AppState>>,
     SimpleValidatedJson(body): SimpleValidatedJson<CreateWithdrawRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data
         .di_container
         .withdraw_service
@@ -166,7 +169,7 @@ pub async fn create_withdraw(
         .await
     {
         Ok(response) => Ok((StatusCode::CREATED, Json(json!(response)))),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -191,7 +194,7 @@ pub async fn update_withdraw(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     SimpleValidatedJson(mut body): SimpleValidatedJson<UpdateWithdrawRequest>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     body.withdraw_id = id;
 
     match data
@@ -202,7 +205,7 @@ pub async fn update_withdraw(
     {
         Ok(response) => Ok((StatusCode::OK, Json(json!(response)))),
 
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -226,7 +229,7 @@ pub async fn delete_withdraw(
     State(data): State<Arc<AppState>>,
     Path(id): Path<i32>,
     Extension(_user_id): Extension<i64>,
-) -> Result<impl IntoResponse, (StatusCode, Json<serde_json::Value>)> {
+) -> Result<impl IntoResponse, ErrorResponse> {
     match data.di_container.withdraw_service.delete_withdraw(id).await {
         Ok(_) => Ok((
             StatusCode::OK,
@@ -235,7 +238,7 @@ pub async fn delete_withdraw(
                 "message": "Withdraw deleted successfully"
             })),
         )),
-        Err(e) => Err((StatusCode::INTERNAL_SERVER_ERROR, Json(json!(e)))),
+        Err(e) => Err(e),
     }
 }
 
@@ -245,7 +248,10 @@ pub fn withdraw_routes(app_state: Arc<AppState>) -> OpenApiRouter {
         .route("/api/withdraw_service/{id}", get(get_withdraw))
         .route("/api/withdraws/users/{id}", get(get_withdraw_users))
         .route("/api/withdraws/user/{id}", get(get_withdraw_user))
-        .route("/api/withdraws", post(create_withdraw))
+        .route(
+            "/api/withdraws",
+            post(create_withdraw).layer(small_body_limit()),
+        )
         .route("/api/withdraws/{id}", put(update_withdraw))
         .route("/api/withdraws/{id}", delete(delete_withdraw))
         .route_layer(middleware::from_fn_with_state(app_state.clone(), jwt::auth))
@@ -1,5 +1,6 @@
 pub mod di;
 pub mod handler;
 pub mod middleware;
+pub mod receipt;
 pub mod service;
 pub mod state;
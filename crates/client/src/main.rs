@@ -3,6 +3,9 @@ use dotenv::dotenv;
 use seaquery_client_payment_gateway::{handler::AppRouter, state::AppState};
 use shared::{
     config::Config,
+    domain::request::{
+        install_default_page_size, install_max_page_size, install_min_transfer_amount,
+    },
     utils::{Telemetry, init_logger},
 };
 use tracing::info;
@@ -11,19 +14,30 @@ use tracing::info;
 async fn main() -> Result<()> {
     dotenv().ok();
 
-    let telemetry = Telemetry::new("myclient", "http://otel-collector:4317".to_string());
+    let config = Config::init().context("Failed to load configuration")?;
+    config.validate()?;
+
+    // gateway memvalidasi body dengan batas yang sama seperti server
+    install_min_transfer_amount(config.min_transfer_amount);
+    // ukuran halaman default/maksimum ikut tampil di dokumen OpenAPI
+    install_max_page_size(config.max_page_size);
+    install_default_page_size(config.default_page_size);
+
+    let telemetry = Telemetry::new(
+        "myclient",
+        "http://otel-collector:4317".to_string(),
+        config.otel_sample_ratio,
+    );
 
     let logger_provider = telemetry.init_logger();
     let _meter_provider = telemetry.init_meter();
     let _tracer_provider = telemetry.init_tracer();
 
-    init_logger(logger_provider.clone(), "client");
-
-    let config = Config::init().context("Failed to load configuration")?;
+    init_logger(logger_provider.clone(), "client", &config.log);
 
     let port = config.port;
 
-    let state = AppState::new(&config.jwt_secret)
+    let state = AppState::new(&config)
         .await
         .context("Failed to create AppState")?;
 
@@ -0,0 +1,100 @@
+use tower_http::compression::{
+    CompressionLayer, Predicate,
+    predicate::{NotForContentType, SizeAbove},
+};
+
+// output /metrics diambil Prometheus di jaringan internal, tidak perlu dikompresi
+const METRICS_CONTENT_TYPE: &str = "application/openmetrics-text";
+
+// gzip/br dipilih dari Accept-Encoding; response di bawah `min_size` byte dikirim apa adanya
+// karena overhead kompresinya tidak sebanding
+pub fn rest_compression(min_size: u16) -> CompressionLayer<impl Predicate> {
+    let predicate = SizeAbove::new(min_size)
+        .and(NotForContentType::GRPC)
+        .and(NotForContentType::IMAGES)
+        .and(NotForContentType::SSE)
+        .and(NotForContentType::const_new(METRICS_CONTENT_TYPE));
+
+    CompressionLayer::new()
+        .gzip(true)
+        .br(true)
+        .compress_when(predicate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Json, Router,
+        body::{Body, to_bytes},
+        http::{
+            Request, StatusCode,
+            header::{ACCEPT_ENCODING, CONTENT_ENCODING},
+        },
+        routing::get,
+    };
+    use flate2::read::GzDecoder;
+    use serde_json::{Value, json};
+    use std::io::Read;
+    use tower::ServiceExt;
+
+    fn transfers(count: i32) -> Value {
+        json!({
+            "status": "success",
+            "data": (1..=count)
+                .map(|id| json!({ "transfer_id": id, "transfer_amount": 50_000, "status": "completed" }))
+                .collect::<Vec<_>>(),
+        })
+    }
+
+    fn app() -> Router {
+        Router::new()
+            .route("/api/transfers", get(|| async { Json(transfers(500)) }))
+            .route("/api/transfers/1", get(|| async { Json(transfers(1)) }))
+            .layer(rest_compression(1024))
+    }
+
+    fn get_gzip(uri: &str) -> Request<Body> {
+        Request::builder()
+            .uri(uri)
+            .header(ACCEPT_ENCODING, "gzip")
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn large_list_is_gzipped_and_decodes_to_the_same_body() {
+        let response = app().oneshot(get_gzip("/api/transfers")).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_ENCODING], "gzip");
+
+        let compressed = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let mut decoded = String::new();
+        GzDecoder::new(&compressed[..])
+            .read_to_string(&mut decoded)
+            .unwrap();
+        assert_eq!(
+            serde_json::from_str::<Value>(&decoded).unwrap(),
+            transfers(500)
+        );
+        assert!(compressed.len() < decoded.len());
+    }
+
+    #[tokio::test]
+    async fn small_responses_and_clients_without_gzip_are_not_compressed() {
+        let small = app().oneshot(get_gzip("/api/transfers/1")).await.unwrap();
+        assert!(!small.headers().contains_key(CONTENT_ENCODING));
+
+        let plain = app()
+            .oneshot(
+                Request::builder()
+                    .uri("/api/transfers")
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert!(!plain.headers().contains_key(CONTENT_ENCODING));
+    }
+}
@@ -0,0 +1,129 @@
+use anyhow::{Context, Result};
+use axum::http::{
+    HeaderName, HeaderValue, Method,
+    header::{AUTHORIZATION, CONTENT_DISPOSITION, CONTENT_TYPE},
+};
+use shared::utils::{REQUEST_ID_HEADER, RETRY_AFTER_HEADER, TIMEOUT_HEADER};
+use std::time::Duration;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+
+// origin yang tidak terdaftar tidak mendapat header Access-Control-*, sehingga browser
+// memblokirnya; daftar kosong berarti hanya same-origin
+pub fn rest_cors(origins: &[String]) -> Result<CorsLayer> {
+    let origins = origins
+        .iter()
+        .map(|origin| {
+            origin
+                .parse::<HeaderValue>()
+                .with_context(|| format!("Invalid CORS origin: '{origin}'"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(CorsLayer::new()
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_methods([
+            Method::GET,
+            Method::POST,
+            Method::PUT,
+            Method::PATCH,
+            Method::DELETE,
+            Method::OPTIONS,
+        ])
+        .allow_headers([
+            CONTENT_TYPE,
+            AUTHORIZATION,
+            HeaderName::from_static(REQUEST_ID_HEADER),
+            HeaderName::from_static(TIMEOUT_HEADER),
+        ])
+        .expose_headers([
+            CONTENT_DISPOSITION,
+            HeaderName::from_static(REQUEST_ID_HEADER),
+            HeaderName::from_static(RETRY_AFTER_HEADER),
+        ])
+        .max_age(Duration::from_secs(60 * 60)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        Router,
+        body::Body,
+        http::{
+            Request, StatusCode,
+            header::{ACCESS_CONTROL_ALLOW_METHODS, ACCESS_CONTROL_ALLOW_ORIGIN, ORIGIN},
+        },
+        routing::get,
+    };
+    use tower::ServiceExt;
+
+    const SPA_ORIGIN: &str = "https://app.example.com";
+
+    fn app(origins: &[&str]) -> Router {
+        let origins: Vec<String> = origins.iter().map(|o| o.to_string()).collect();
+        Router::new()
+            .route("/api/transfers", get(|| async { "[]" }))
+            .layer(rest_cors(&origins).unwrap())
+    }
+
+    fn get_from(origin: &str) -> Request<Body> {
+        Request::builder()
+            .uri("/api/transfers")
+            .header(ORIGIN, origin)
+            .body(Body::empty())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn allowed_origin_gets_the_allow_origin_header() {
+        let response = app(&[SPA_ORIGIN])
+            .oneshot(get_from(SPA_ORIGIN))
+            .await
+            .unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN], SPA_ORIGIN);
+    }
+
+    #[tokio::test]
+    async fn disallowed_origin_gets_no_cors_headers() {
+        for app in [app(&[SPA_ORIGIN]), app(&[])] {
+            let response = app
+                .oneshot(get_from("https://evil.example.com"))
+                .await
+                .unwrap();
+
+            assert!(!response.headers().contains_key(ACCESS_CONTROL_ALLOW_ORIGIN));
+        }
+    }
+
+    #[tokio::test]
+    async fn preflight_is_answered_for_an_allowed_origin() {
+        let preflight = Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/api/transfers")
+            .header(ORIGIN, SPA_ORIGIN)
+            .header("access-control-request-method", "DELETE")
+            .header(
+                "access-control-request-headers",
+                "authorization,content-type",
+            )
+            .body(Body::empty())
+            .unwrap();
+
+        let response = app(&[SPA_ORIGIN]).oneshot(preflight).await.unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[ACCESS_CONTROL_ALLOW_ORIGIN], SPA_ORIGIN);
+        let methods = response.headers()[ACCESS_CONTROL_ALLOW_METHODS]
+            .to_str()
+            .unwrap();
+        assert!(methods.contains("DELETE"), "{methods}");
+    }
+
+    #[test]
+    fn invalid_origin_is_rejected() {
+        let err = rest_cors(&["bad\norigin".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("Invalid CORS origin"));
+    }
+}
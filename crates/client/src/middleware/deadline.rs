@@ -0,0 +1,122 @@
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use shared::{
+    domain::response::ErrorResponse,
+    utils::{AppError, TIMEOUT_HEADER, parse_timeout_ms, with_deadline},
+};
+use std::time::Duration;
+use tokio::time::Instant;
+use tracing::warn;
+
+// X-Timeout-Ms hanya boleh mempersingkat batas bawaan gateway (GRPC_REQUEST_TIMEOUT_SECS);
+// deadline-nya ikut terkirim ke server sebagai grpc-timeout
+pub async fn deadline(
+    State(max_timeout): State<Duration>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let timeout = match req.headers().get(TIMEOUT_HEADER) {
+        None => max_timeout,
+        Some(value) => match value.to_str().ok().and_then(parse_timeout_ms) {
+            Some(timeout) => timeout.min(max_timeout),
+            None => return invalid_timeout(),
+        },
+    };
+
+    let method = req.method().clone();
+    let path = req.uri().path().to_owned();
+    let deadline = Instant::now() + timeout;
+
+    match tokio::time::timeout_at(deadline, with_deadline(deadline, next.run(req))).await {
+        Ok(response) => response,
+        Err(_) => {
+            warn!(
+                "⏱️ {method} {path} exceeded its deadline of {}ms",
+                timeout.as_millis()
+            );
+            AppError::DeadlineExceeded.into_response()
+        }
+    }
+}
+
+fn invalid_timeout() -> Response {
+    ErrorResponse {
+        status: "fail".to_string(),
+        message: format!("{TIMEOUT_HEADER} must be a positive number of milliseconds"),
+        code: StatusCode::BAD_REQUEST,
+        retry_after: None,
+        errors: None,
+    }
+    .into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, middleware, routing::get};
+    use shared::utils::remaining_time;
+    use tower::ServiceExt;
+
+    // handler tiruan: melaporkan sisa waktu yang akan dikirim sebagai grpc-timeout, lalu tidur
+    fn app(work: Duration) -> Router {
+        Router::new()
+            .route(
+                "/",
+                get(move || async move {
+                    let remaining = remaining_time().unwrap();
+                    tokio::time::sleep(work).await;
+                    remaining.as_millis().to_string()
+                }),
+            )
+            .layer(middleware::from_fn_with_state(
+                Duration::from_secs(30),
+                deadline,
+            ))
+    }
+
+    async fn call(app: Router, timeout_ms: &str) -> Response {
+        app.oneshot(
+            Request::builder()
+                .uri("/")
+                .header(TIMEOUT_HEADER, timeout_ms)
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap()
+    }
+
+    #[tokio::test]
+    async fn short_deadline_reaches_the_outgoing_call() {
+        let response = call(app(Duration::ZERO), "200").await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = axum::body::to_bytes(response.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let remaining: u128 = String::from_utf8(body.to_vec()).unwrap().parse().unwrap();
+        assert!(remaining > 0 && remaining <= 200);
+    }
+
+    #[tokio::test]
+    async fn slow_operation_past_the_deadline_is_504() {
+        let started = Instant::now();
+
+        let response = call(app(Duration::from_secs(5)), "50").await;
+
+        assert_eq!(response.status(), StatusCode::GATEWAY_TIMEOUT);
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+
+    #[tokio::test]
+    async fn malformed_timeout_header_is_rejected() {
+        let response = call(app(Duration::ZERO), "soon").await;
+
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    }
+}
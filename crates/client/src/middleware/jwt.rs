@@ -1,13 +1,15 @@
 use axum::{
-    Json,
     body::Body,
     extract::State,
     http::{Request, StatusCode, header},
     middleware::Next,
-    response::IntoResponse,
+    response::Response,
 };
 use axum_extra::extract::cookie::CookieJar;
-use shared::domain::response::ErrorResponse;
+use shared::{
+    domain::response::ErrorResponse,
+    utils::{parse_bearer_token, with_bearer_token},
+};
 use std::sync::Arc;
 
 use crate::state::AppState;
@@ -15,9 +17,9 @@ use crate::state::AppState;
 pub async fn auth(
     cookie_jar: CookieJar,
     State(data): State<Arc<AppState>>,
-    mut req: Request<Body>,
+    req: Request<Body>,
     next: Next,
-) -> Result<impl IntoResponse, (StatusCode, Json<ErrorResponse>)> {
+) -> Result<Response, ErrorResponse> {
     let token = cookie_jar
         .get("token")
         .map(|cookie| cookie.value().to_string())
@@ -25,35 +27,76 @@ pub async fn auth(
             req.headers()
                 .get(header::AUTHORIZATION)
                 .and_then(|auth_header| auth_header.to_str().ok())
-                .and_then(|auth_value| auth_value.strip_prefix("Bearer ").map(str::to_owned))
+                .and_then(parse_bearer_token)
+                .map(str::to_owned)
         });
 
     let token = match token {
         Some(token) => token,
         None => {
-            return Err((
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    status: "fail".to_string(),
-                    message: "You are not logged in, please provide token".to_string(),
-                }),
-            ));
+            return Err(ErrorResponse {
+                status: "fail".to_string(),
+                message: "You are not logged in, please provide token".to_string(),
+                code: StatusCode::UNAUTHORIZED,
+                retry_after: None,
+                errors: None,
+            });
         }
     };
 
-    let user_id = match data.jwt_config.verify_token(&token) {
-        Ok(id) => id as i32,
+    // panggilan gRPC selama request ini membawa token yang sama ke server
+    with_bearer_token(token.clone(), authenticate(data, token, req, next)).await
+}
+
+async fn authenticate(
+    data: Arc<AppState>,
+    token: String,
+    mut req: Request<Body>,
+    next: Next,
+) -> Result<Response, ErrorResponse> {
+    let claims = match data.jwt_config.verify_claims(&token) {
+        Ok(claims) => claims,
         Err(_) => {
-            return Err((
-                StatusCode::UNAUTHORIZED,
-                Json(ErrorResponse {
-                    status: "fail".to_string(),
-                    message: "Invalid token".to_string(),
-                }),
-            ));
+            return Err(ErrorResponse {
+                status: "fail".to_string(),
+                message: "Invalid token".to_string(),
+                code: StatusCode::UNAUTHORIZED,
+                retry_after: None,
+                errors: None,
+            });
         }
     };
 
+    let user_id = claims.user_id as i32;
+
+    // token milik user yang sudah dihapus tidak lagi berlaku
+    let user = match data.di_container.user_service.get_user(user_id).await {
+        Ok(response) => response.data,
+        Err(err) if err.code == StatusCode::NOT_FOUND => {
+            return Err(ErrorResponse {
+                status: "fail".to_string(),
+                message: "The user belonging to this token no longer exists".to_string(),
+                code: StatusCode::UNAUTHORIZED,
+                retry_after: None,
+                errors: None,
+            });
+        }
+        Err(err) => return Err(err),
+    };
+
+    // token yang terbit sebelum password diganti sudah dicabut
+    if let Some(changed_at) = user.and_then(|user| user.password_changed_at)
+        && (claims.iat as i64) < changed_at.timestamp()
+    {
+        return Err(ErrorResponse {
+            status: "fail".to_string(),
+            message: "Password was changed recently, please log in again".to_string(),
+            code: StatusCode::UNAUTHORIZED,
+            retry_after: None,
+            errors: None,
+        });
+    }
+
     req.extensions_mut().insert(user_id);
 
     Ok(next.run(req).await)
@@ -0,0 +1,108 @@
+use axum::{
+    body::Body,
+    extract::State,
+    http::{Request, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use shared::{domain::response::ErrorResponse, utils::ConcurrencyLimit};
+use tracing::warn;
+
+// endpoint metrics tetap dilayani supaya kondisi overload masih bisa diamati
+const EXEMPT_PATHS: [&str; 1] = ["/metrics"];
+
+pub async fn load_shed(
+    State(limit): State<ConcurrencyLimit>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    if EXEMPT_PATHS.contains(&req.uri().path()) {
+        return next.run(req).await;
+    }
+
+    let Some(_permit) = limit.try_acquire() else {
+        warn!(
+            "🚦 Shedding {} {} | {} requests already in flight",
+            req.method(),
+            req.uri().path(),
+            limit.max_concurrent()
+        );
+
+        let retry_after = limit.retry_after_secs();
+        return ErrorResponse {
+            status: "fail".to_string(),
+            message: format!("Server is overloaded, retry after {retry_after}s"),
+            code: StatusCode::SERVICE_UNAVAILABLE,
+            retry_after: Some(retry_after),
+            errors: None,
+        }
+        .into_response();
+    };
+
+    next.run(req).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, middleware, routing::get};
+    use shared::utils::RETRY_AFTER_HEADER;
+    use std::{sync::Arc, time::Duration};
+    use tokio::sync::Semaphore;
+    use tower::ServiceExt;
+
+    // handler ditahan sampai test melepas `gate`, meniru request yang lambat
+    fn app(limit: ConcurrencyLimit, gate: Arc<Semaphore>) -> Router {
+        Router::new()
+            .route(
+                "/slow",
+                get(move || async move {
+                    let _ = gate.acquire().await.unwrap();
+                }),
+            )
+            .layer(middleware::from_fn_with_state(limit, load_shed))
+    }
+
+    fn get_slow() -> Request<Body> {
+        Request::builder().uri("/slow").body(Body::empty()).unwrap()
+    }
+
+    #[tokio::test]
+    async fn requests_under_the_limit_are_served() {
+        let limit = ConcurrencyLimit::new(2, Duration::from_secs(1));
+        let app = app(limit, Arc::new(Semaphore::new(Semaphore::MAX_PERMITS)));
+
+        for _ in 0..5 {
+            let response = app.clone().oneshot(get_slow()).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+    }
+
+    #[tokio::test]
+    async fn request_beyond_the_limit_is_shed_without_waiting() {
+        let limit = ConcurrencyLimit::new(2, Duration::from_secs(3));
+        let gate = Arc::new(Semaphore::new(0));
+        let app = app(limit, gate.clone());
+
+        let in_flight: Vec<_> = (0..2)
+            .map(|_| tokio::spawn(app.clone().oneshot(get_slow())))
+            .collect();
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let shed =
+            tokio::time::timeout(Duration::from_millis(200), app.clone().oneshot(get_slow()))
+                .await
+                .expect("excess request should be rejected immediately")
+                .unwrap();
+        assert_eq!(shed.status(), StatusCode::SERVICE_UNAVAILABLE);
+        assert_eq!(shed.headers()[RETRY_AFTER_HEADER], "3");
+
+        gate.add_permits(2);
+        for request in in_flight {
+            assert_eq!(request.await.unwrap().unwrap().status(), StatusCode::OK);
+        }
+        gate.add_permits(1);
+        let response = app.oneshot(get_slow()).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
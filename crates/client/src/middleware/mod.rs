@@ -1,2 +1,7 @@
+pub mod compression;
+pub mod cors;
+pub mod deadline;
 pub mod jwt;
+pub mod load_shed;
+pub mod request_id;
 pub mod validate;
@@ -0,0 +1,101 @@
+use axum::{
+    body::Body,
+    http::{HeaderValue, Request},
+    middleware::Next,
+    response::Response,
+};
+use shared::utils::{REQUEST_ID_HEADER, is_valid_request_id, with_request_id};
+use tracing::{Instrument, info_span};
+use uuid::Uuid;
+
+#[derive(Debug, Clone)]
+pub struct RequestId(pub String);
+
+pub async fn request_id(mut req: Request<Body>, next: Next) -> Response {
+    let request_id = req
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .filter(|value| is_valid_request_id(value))
+        .map(str::to_owned)
+        .unwrap_or_else(|| Uuid::new_v4().to_string());
+
+    req.extensions_mut().insert(RequestId(request_id.clone()));
+
+    let span = info_span!(
+        "http_request",
+        request_id = %request_id,
+        method = %req.method(),
+        path = %req.uri().path(),
+    );
+
+    let mut response = with_request_id(request_id.clone(), next.run(req))
+        .instrument(span)
+        .await;
+
+    if let Ok(value) = HeaderValue::from_str(&request_id) {
+        response.headers_mut().insert(REQUEST_ID_HEADER, value);
+    }
+
+    response
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, body::to_bytes, middleware, routing::get};
+    use shared::utils::MetadataInjector;
+    use tower::ServiceExt;
+
+    // handler tiruan mengembalikan request id yang akan ikut ke metadata gRPC
+    async fn forwarded_request_id() -> String {
+        let mut request = tonic::Request::new(());
+        MetadataInjector(request.metadata_mut()).inject_request_id();
+
+        request
+            .metadata()
+            .get(REQUEST_ID_HEADER)
+            .map(|value| value.to_str().unwrap().to_string())
+            .unwrap_or_default()
+    }
+
+    async fn call(request_id: Option<&str>) -> (String, String) {
+        let app = Router::new()
+            .route("/", get(forwarded_request_id))
+            .layer(middleware::from_fn(super::request_id));
+
+        let mut request = Request::builder().uri("/");
+        if let Some(request_id) = request_id {
+            request = request.header(REQUEST_ID_HEADER, request_id);
+        }
+
+        let response = app
+            .oneshot(request.body(Body::empty()).unwrap())
+            .await
+            .unwrap();
+        let header = response.headers()[REQUEST_ID_HEADER]
+            .to_str()
+            .unwrap()
+            .to_string();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        (header, String::from_utf8(body.to_vec()).unwrap())
+    }
+
+    #[tokio::test]
+    async fn incoming_request_id_reaches_grpc_metadata_and_response() {
+        let (header, forwarded) = call(Some("order-42.retry_1")).await;
+
+        assert_eq!(header, "order-42.retry_1");
+        assert_eq!(forwarded, "order-42.retry_1");
+    }
+
+    #[tokio::test]
+    async fn invalid_request_id_is_replaced() {
+        let (header, forwarded) = call(Some("bad id\twith spaces")).await;
+
+        assert_ne!(header, "bad id\twith spaces");
+        assert!(Uuid::parse_str(&header).is_ok());
+        assert_eq!(forwarded, header);
+    }
+}
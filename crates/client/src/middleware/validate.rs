@@ -1,9 +1,11 @@
 use axum::{
     extract::{FromRequest, Request},
     http::StatusCode,
+    response::{IntoResponse, Response},
 };
 use serde::de::DeserializeOwned;
 use serde_json::{Value, json};
+use shared::domain::response::ErrorResponse;
 use validator::{Validate, ValidationErrors};
 
 pub struct SimpleValidatedJson<T>(pub T);
@@ -13,18 +15,30 @@ where
     T: DeserializeOwned + Validate + Send,
     S: Send + Sync,
 {
-    type Rejection = (StatusCode, axum::Json<Value>);
+    type Rejection = Response;
 
     async fn from_request(req: Request, state: &S) -> Result<Self, Self::Rejection> {
         let axum::Json(json_value) =
             axum::Json::<T>::from_request(req, state)
                 .await
                 .map_err(|rejection| {
+                    // body melebihi DefaultBodyLimit route
+                    if rejection.status() == StatusCode::PAYLOAD_TOO_LARGE {
+                        return ErrorResponse {
+                            status: "fail".to_string(),
+                            message: "Request body too large".to_string(),
+                            code: StatusCode::PAYLOAD_TOO_LARGE,
+                            retry_after: None,
+                            errors: None,
+                        }
+                        .into_response();
+                    }
+
                     let payload = json!({
                         "error": "Invalid JSON",
                         "message": rejection.body_text(),
                     });
-                    (rejection.status(), axum::Json(payload))
+                    (rejection.status(), axum::Json(payload)).into_response()
                 })?;
 
         json_value.validate().map_err(|validation_errors| {
@@ -33,7 +47,7 @@ where
                 "message": format_validation_errors(&validation_errors),
                 "details": format_validation_errors_detailed(&validation_errors)
             });
-            (StatusCode::BAD_REQUEST, axum::Json(payload))
+            (StatusCode::BAD_REQUEST, axum::Json(payload)).into_response()
         })?;
 
         Ok(Self(json_value))
@@ -93,3 +107,59 @@ fn format_validation_errors_detailed(errors: &ValidationErrors) -> Value {
 
     json!(error_map)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::handler::small_body_limit;
+    use axum::{
+        Router,
+        body::{Body, to_bytes},
+        http::header::CONTENT_TYPE,
+        routing::post,
+    };
+    use shared::domain::request::LoginRequest;
+    use tower::ServiceExt;
+
+    async fn login(SimpleValidatedJson(body): SimpleValidatedJson<LoginRequest>) -> String {
+        body.email
+    }
+
+    async fn post_login(password: &str) -> (StatusCode, Value) {
+        let app = Router::new().route("/api/auth/login", post(login).layer(small_body_limit()));
+        let body = json!({ "email": "budi@example.com", "password": password }).to_string();
+
+        let response = app
+            .oneshot(
+                Request::builder()
+                    .method("POST")
+                    .uri("/api/auth/login")
+                    .header(CONTENT_TYPE, "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        let status = response.status();
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+
+        (status, serde_json::from_slice(&body).unwrap_or(Value::Null))
+    }
+
+    #[tokio::test]
+    async fn normal_login_body_is_accepted() {
+        let (status, _) = post_login("rahasia123").await;
+
+        assert_eq!(status, StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn oversized_login_body_is_rejected_with_error_response() {
+        // 17 KB, di atas batas 16 KB route auth
+        let (status, body) = post_login(&"a".repeat(17 * 1024)).await;
+
+        assert_eq!(status, StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(body["status"], "fail");
+        assert_eq!(body["message"], "Request body too large");
+    }
+}
@@ -0,0 +1,190 @@
+use axum::{
+    http::{
+        StatusCode,
+        header::{CONTENT_DISPOSITION, CONTENT_TYPE},
+    },
+    response::{IntoResponse, Response},
+};
+use printpdf::{
+    BuiltinFont, Color, IndirectFontRef, Mm, PdfDocument, PdfLayerReference, Pt, Rgb, TextMatrix,
+};
+use shared::{domain::response::transfer::TransferResponse, utils::AppError};
+
+const PAGE_WIDTH: Mm = Mm(210.0);
+const PAGE_HEIGHT: Mm = Mm(148.0);
+const LEFT: Mm = Mm(20.0);
+const VALUE_LEFT: Mm = Mm(70.0);
+
+// data yang dicetak di struk; nama pihak diambil terpisah dari layanan user
+pub struct TransferReceipt<'a> {
+    pub transfer: &'a TransferResponse,
+    pub sender_name: String,
+    pub receiver_name: String,
+}
+
+impl TransferReceipt<'_> {
+    pub fn reference(&self) -> String {
+        format!("TRF-{:08}", self.transfer.transfer_id)
+    }
+
+    pub fn filename(&self) -> String {
+        format!("transfer-{}-receipt.pdf", self.transfer.transfer_id)
+    }
+
+    pub fn render(&self) -> Result<Vec<u8>, AppError> {
+        let transfer = self.transfer;
+        let (doc, page, layer) = PdfDocument::new(
+            format!("Transfer receipt {}", self.reference()),
+            PAGE_WIDTH,
+            PAGE_HEIGHT,
+            "receipt",
+        );
+
+        let regular = doc
+            .add_builtin_font(BuiltinFont::Helvetica)
+            .map_err(pdf_error)?;
+        let bold = doc
+            .add_builtin_font(BuiltinFont::HelveticaBold)
+            .map_err(pdf_error)?;
+
+        let layer = doc.get_page(page).get_layer(layer);
+
+        if transfer.status == "reversed" {
+            watermark(&layer, &bold);
+        }
+
+        layer.use_text("Transfer Receipt", 20.0, LEFT, Mm(125.0), &bold);
+
+        // transfer antar user tidak dikenai biaya, baris fee tetap dicetak agar format struk baku
+        let rows = [
+            ("Reference", self.reference()),
+            ("Status", transfer.status.clone()),
+            (
+                "Date",
+                transfer
+                    .transfer_time
+                    .format("%Y-%m-%d %H:%M:%S UTC")
+                    .to_string(),
+            ),
+            (
+                "From",
+                format!("{} (#{})", self.sender_name, transfer.transfer_from),
+            ),
+            (
+                "To",
+                format!("{} (#{})", self.receiver_name, transfer.transfer_to),
+            ),
+            ("Amount", transfer.transfer_amount.to_string()),
+            ("Fee", "0".to_string()),
+            ("Total", transfer.transfer_amount.to_string()),
+            ("Note", transfer.note.clone().unwrap_or_else(|| "-".into())),
+        ];
+
+        for (index, (label, value)) in rows.iter().enumerate() {
+            let y = Mm(108.0 - index as f32 * 9.0);
+            layer.use_text(*label, 11.0, LEFT, y, &bold);
+            layer.use_text(value.as_str(), 11.0, VALUE_LEFT, y, &regular);
+        }
+
+        doc.save_to_bytes().map_err(pdf_error)
+    }
+
+    // diunduh sebagai lampiran supaya browser menyimpan file, bukan membukanya di tab
+    pub fn response(&self) -> Result<Response, AppError> {
+        let pdf = self.render()?;
+
+        Ok((
+            StatusCode::OK,
+            [
+                (CONTENT_TYPE, "application/pdf".to_string()),
+                (
+                    CONTENT_DISPOSITION,
+                    format!("attachment; filename=\"{}\"", self.filename()),
+                ),
+            ],
+            pdf,
+        )
+            .into_response())
+    }
+}
+
+// tanda air miring berwarna merah muda di belakang isi struk
+fn watermark(layer: &PdfLayerReference, font: &IndirectFontRef) {
+    layer.set_fill_color(Color::Rgb(Rgb::new(0.95, 0.75, 0.75, None)));
+    layer.begin_text_section();
+    layer.set_font(font, 72.0);
+    layer.set_text_matrix(TextMatrix::TranslateRotate(
+        Pt::from(Mm(40.0)),
+        Pt::from(Mm(25.0)),
+        30.0,
+    ));
+    layer.write_text("REVERSED", font);
+    layer.end_text_section();
+    layer.set_fill_color(Color::Rgb(Rgb::new(0.0, 0.0, 0.0, None)));
+}
+
+fn pdf_error(err: printpdf::Error) -> AppError {
+    AppError::InternalError(format!("Failed to render receipt: {err}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+    use chrono::Utc;
+
+    fn transfer(status: &str) -> TransferResponse {
+        TransferResponse {
+            transfer_id: 42,
+            transfer_from: 1,
+            transfer_to: 2,
+            transfer_amount: 150_000,
+            transfer_time: Utc::now(),
+            created_at: None,
+            updated_at: None,
+            note: Some("Rent".to_string()),
+            status: status.to_string(),
+            from_label: "main".to_string(),
+            to_label: "main".to_string(),
+            projected_sender_balance: None,
+            projected_receiver_balance: None,
+            direction: None,
+        }
+    }
+
+    fn receipt(transfer: &TransferResponse) -> TransferReceipt<'_> {
+        TransferReceipt {
+            transfer,
+            sender_name: "Alice Sender".to_string(),
+            receiver_name: "Bob Receiver".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn receipt_is_a_pdf_attachment() {
+        let transfer = transfer("completed");
+
+        let response = receipt(&transfer).response().unwrap();
+
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(response.headers()[CONTENT_TYPE], "application/pdf");
+        assert_eq!(
+            response.headers()[CONTENT_DISPOSITION],
+            "attachment; filename=\"transfer-42-receipt.pdf\""
+        );
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        assert!(body.starts_with(b"%PDF"));
+    }
+
+    #[test]
+    fn reversed_transfers_are_watermarked() {
+        let completed = transfer("completed");
+        let reversed = transfer("reversed");
+
+        let plain = receipt(&completed).render().unwrap();
+        let watermarked = receipt(&reversed).render().unwrap();
+
+        assert_eq!(receipt(&reversed).reference(), "TRF-00000042");
+        assert!(watermarked.len() > plain.len());
+    }
+}
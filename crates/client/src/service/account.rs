@@ -0,0 +1,232 @@
+use async_trait::async_trait;
+use genproto::account::{GetAccountSummaryRequest, account_service_client::AccountServiceClient};
+use opentelemetry::{
+    Context, KeyValue,
+    global::{self, BoxedTracer},
+    trace::{Span, SpanKind, TraceContextExt, Tracer},
+};
+use prometheus_client::registry::Registry;
+
+use shared::{
+    domain::{
+        request::AccountSummaryRequest,
+        response::{ApiResponse, ErrorResponse, account::AccountSummaryResponse},
+    },
+    utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+};
+use std::sync::Arc;
+use tokio::time::Instant;
+use tonic::{Request, transport::Channel};
+use tracing::{error, info};
+
+use crate::service::{ClientPool, RetryPolicy};
+use shared::abstract_trait::AccountServiceTrait;
+
+#[derive(Debug)]
+pub struct AccountService {
+    client: ClientPool<AccountServiceClient<Channel>>,
+    retry_policy: RetryPolicy,
+    metrics: Arc<Metrics>,
+}
+
+impl AccountService {
+    pub async fn new(
+        client: ClientPool<AccountServiceClient<Channel>>,
+        retry_policy: RetryPolicy,
+        metrics: Arc<Metrics>,
+        registry: &mut Registry,
+    ) -> Self {
+        registry.register(
+            "account_handler_request_counter",
+            "Total number of requests to the AccountService",
+            metrics.request_counter.clone(),
+        );
+        registry.register(
+            "account_handler_request_duration",
+            "Histogram of request durations for the AccountService",
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "account_handler_error_counter",
+            "Total number of failed requests to the AccountService by error kind",
+            metrics.error_counter.clone(),
+        );
+
+        Self {
+            client,
+            retry_policy,
+            metrics,
+        }
+    }
+
+    fn get_tracer(&self) -> BoxedTracer {
+        global::tracer("account-service-client")
+    }
+
+    fn inject_trace_context<T>(&self, cx: &Context, request: &mut Request<T>) {
+        let mut injector = MetadataInjector(request.metadata_mut());
+        global::get_text_map_propagator(|propagator| propagator.inject_context(cx, &mut injector));
+        injector.inject_request_id();
+        injector.inject_bearer_token();
+    }
+
+    fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
+        let start_time = Instant::now();
+        let tracer = self.get_tracer();
+        let mut span = tracer
+            .span_builder(operation_name.to_string())
+            .with_kind(SpanKind::Server)
+            .with_attributes(attributes)
+            .start(&tracer);
+
+        info!("Starting operation: {operation_name}");
+
+        span.add_event(
+            "Operation started",
+            vec![
+                KeyValue::new("operation", operation_name.to_string()),
+                KeyValue::new("timestamp", start_time.elapsed().as_secs_f64().to_string()),
+            ],
+        );
+
+        let cx = Context::current_with_span(span);
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
+    }
+
+    async fn complete_tracing_success(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        message: &str,
+    ) {
+        self.complete_tracing_internal(tracing_ctx, method, true, message)
+            .await;
+    }
+
+    async fn complete_tracing_error(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        error_kind: &str,
+        error_message: &str,
+    ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
+        self.complete_tracing_internal(tracing_ctx, method, false, error_message)
+            .await;
+    }
+
+    async fn complete_tracing_internal(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        is_success: bool,
+        message: &str,
+    ) {
+        let status_str = if is_success { "SUCCESS" } else { "ERROR" };
+        let status = if is_success {
+            StatusUtils::Success
+        } else {
+            StatusUtils::Error
+        };
+        let elapsed = tracing_ctx.start_time.elapsed().as_secs_f64();
+
+        tracing_ctx.cx.span().add_event(
+            "Operation completed",
+            vec![
+                KeyValue::new("status", status_str),
+                KeyValue::new("duration_secs", elapsed.to_string()),
+                KeyValue::new("message", message.to_string()),
+            ],
+        );
+
+        if is_success {
+            info!("Operation completed successfully: {message}");
+        } else {
+            error!("Operation failed: {message}");
+        }
+
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
+
+        tracing_ctx.cx.span().end();
+    }
+}
+
+#[async_trait]
+impl AccountServiceTrait for AccountService {
+    async fn get_account_summary(
+        &self,
+        user_id: i32,
+        req: &AccountSummaryRequest,
+    ) -> Result<ApiResponse<AccountSummaryResponse>, ErrorResponse> {
+        info!("Account summary request for user_id: {user_id}");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "GetAccountSummary",
+            vec![
+                KeyValue::new("component", "account"),
+                KeyValue::new("operation", "summary"),
+                KeyValue::new("account.user_id", user_id as i64),
+                KeyValue::new("limit", req.limit as i64),
+            ],
+        );
+
+        let mut request = Request::new(GetAccountSummaryRequest {
+            user_id,
+            limit: req.limit,
+        });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().get_account_summary(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response: ApiResponse<AccountSummaryResponse> = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    &format!("Account summary for user_id {user_id} retrieved successfully"),
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!(
+                    "Failed to fetch account summary for user_id {user_id}: {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &error_response.message,
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+}
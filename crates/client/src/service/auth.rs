@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use genproto::auth::{
-    GetMeRequest, LoginRequest, RegisterRequest, auth_service_client::AuthServiceClient,
+    ChangePasswordRequest, GetMeRequest, ListSessionsRequest, LoginRequest, RefreshTokenRequest,
+    RegisterRequest, RevokeSessionRequest, auth_service_client::AuthServiceClient,
 };
 use opentelemetry::{
     Context, KeyValue,
@@ -11,40 +12,61 @@ use prometheus_client::registry::Registry;
 use shared::{
     abstract_trait::AuthServiceTrait,
     domain::{
-        request::{LoginRequest as LoginDomainRequest, RegisterRequest as RegisterDomainRequest},
-        response::{ApiResponse, ErrorResponse, user::UserResponse},
+        request::{
+            ChangePasswordRequest as ChangePasswordDomainRequest,
+            LoginRequest as LoginDomainRequest, RefreshTokenRequest as RefreshTokenDomainRequest,
+            RegisterRequest as RegisterDomainRequest,
+        },
+        response::{
+            ApiResponse, ErrorResponse,
+            auth::{LoginResponse, SessionResponse},
+            user::UserResponse,
+        },
     },
     utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
 };
 use std::sync::Arc;
-use tokio::{sync::Mutex, time::Instant};
+use tokio::time::Instant;
 use tonic::{Request, transport::Channel};
 use tracing::{error, info};
 
+use crate::service::{ClientPool, RetryPolicy};
+
 #[derive(Debug)]
 pub struct AuthService {
-    client: Arc<Mutex<AuthServiceClient<Channel>>>,
-    metrics: Arc<Mutex<Metrics>>,
+    client: ClientPool<AuthServiceClient<Channel>>,
+    retry_policy: RetryPolicy,
+    metrics: Arc<Metrics>,
 }
 
 impl AuthService {
     pub async fn new(
-        client: Arc<Mutex<AuthServiceClient<Channel>>>,
-        metrics: Arc<Mutex<Metrics>>,
+        client: ClientPool<AuthServiceClient<Channel>>,
+        retry_policy: RetryPolicy,
+        metrics: Arc<Metrics>,
         registry: &mut Registry,
     ) -> Self {
         registry.register(
             "auth_handler_request_counter",
             "Total number of requests to the AuthService",
-            metrics.lock().await.request_counter.clone(),
+            metrics.request_counter.clone(),
         );
         registry.register(
             "auth_handler_request_duration",
             "Histogram of request durations for the AuthService",
-            metrics.lock().await.request_duration.clone(),
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "auth_handler_error_counter",
+            "Total number of failed requests to the AuthService by error kind",
+            metrics.error_counter.clone(),
         );
 
-        Self { client, metrics }
+        Self {
+            client,
+            retry_policy,
+            metrics,
+        }
     }
 
     fn get_tracer(&self) -> BoxedTracer {
@@ -52,9 +74,10 @@ impl AuthService {
     }
 
     fn inject_trace_context<T>(&self, cx: &Context, request: &mut Request<T>) {
-        global::get_text_map_propagator(|propagator| {
-            propagator.inject_context(cx, &mut MetadataInjector(request.metadata_mut()))
-        });
+        let mut injector = MetadataInjector(request.metadata_mut());
+        global::get_text_map_propagator(|propagator| propagator.inject_context(cx, &mut injector));
+        injector.inject_request_id();
+        injector.inject_bearer_token();
     }
 
     fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
@@ -77,7 +100,11 @@ impl AuthService {
         );
 
         let cx = Context::current_with_span(span);
-        TracingContext { cx, start_time }
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
     }
 
     async fn complete_tracing_success(
@@ -94,8 +121,12 @@ impl AuthService {
         &self,
         tracing_ctx: &TracingContext,
         method: Method,
+        error_kind: &str,
         error_message: &str,
     ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
         self.complete_tracing_internal(tracing_ctx, method, false, error_message)
             .await;
     }
@@ -130,7 +161,8 @@ impl AuthService {
             error!("Operation failed: {message}");
         }
 
-        self.metrics.lock().await.record(method, status, elapsed);
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
 
         tracing_ctx.cx.span().end();
     }
@@ -165,10 +197,12 @@ impl AuthServiceTrait for AuthService {
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let result = {
-            let mut client = self.client.lock().await;
-            client.register_user(request).await
-        };
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().register_user(request).await
+            })
+            .await;
 
         match result {
             Ok(resp) => {
@@ -191,10 +225,7 @@ impl AuthServiceTrait for AuthService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to register user {}: {}",
@@ -204,6 +235,7 @@ impl AuthServiceTrait for AuthService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to register user {}: {}",
                         request_data.email, error_response.message
@@ -219,7 +251,7 @@ impl AuthServiceTrait for AuthService {
     async fn login_user(
         &self,
         request_data: &LoginDomainRequest,
-    ) -> Result<ApiResponse<String>, ErrorResponse> {
+    ) -> Result<ApiResponse<LoginResponse>, ErrorResponse> {
         info!("Logging in user: {}", request_data.email);
 
         let method = Method::Post;
@@ -235,14 +267,17 @@ impl AuthServiceTrait for AuthService {
         let mut request = Request::new(LoginRequest {
             email: request_data.email.clone(),
             password: request_data.password.clone(),
+            user_agent: request_data.user_agent.clone().unwrap_or_default(),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let result = {
-            let mut client = self.client.lock().await;
-            client.login_user(request).await
-        };
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().login_user(request).await
+            })
+            .await;
 
         match result {
             Ok(resp) => {
@@ -250,7 +285,7 @@ impl AuthServiceTrait for AuthService {
                 let response = ApiResponse {
                     status: inner.status,
                     message: inner.message,
-                    data: inner.data,
+                    data: inner.data.map(Into::into).unwrap_or_default(),
                 };
 
                 info!("User {} logged in successfully", request_data.email);
@@ -265,10 +300,7 @@ impl AuthServiceTrait for AuthService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to login user {}: {}",
@@ -278,6 +310,7 @@ impl AuthServiceTrait for AuthService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to login user {}: {}",
                         request_data.email, error_response.message
@@ -304,10 +337,12 @@ impl AuthServiceTrait for AuthService {
         let mut request = Request::new(GetMeRequest { id });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let result = {
-            let mut client = self.client.lock().await;
-            client.get_me(request).await
-        };
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().get_me(request).await
+            })
+            .await;
 
         match result {
             Ok(resp) => {
@@ -330,10 +365,7 @@ impl AuthServiceTrait for AuthService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to retrieve user profile {id}: {}",
@@ -343,6 +375,7 @@ impl AuthServiceTrait for AuthService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to retrieve user profile {id}: {}",
                         error_response.message
@@ -354,4 +387,284 @@ impl AuthServiceTrait for AuthService {
             }
         }
     }
+
+    async fn change_password(
+        &self,
+        id: i32,
+        request_data: &ChangePasswordDomainRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        info!("Changing password for user: {id}");
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "ChangePassword",
+            vec![
+                KeyValue::new("component", "auth"),
+                KeyValue::new("operation", "change_password"),
+                KeyValue::new("user.id", id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(ChangePasswordRequest {
+            id,
+            old_password: request_data.old_password.clone(),
+            new_password: request_data.new_password.clone(),
+            confirm_password: request_data.confirm_password.clone(),
+        });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().change_password(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: (),
+                };
+
+                info!("Password for user {id} changed successfully");
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    &format!("Password for user {id} changed successfully"),
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!(
+                    "Failed to change password for user {id}: {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!(
+                        "Failed to change password for user {id}: {}",
+                        error_response.message
+                    ),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn refresh_token(
+        &self,
+        request_data: &RefreshTokenDomainRequest,
+    ) -> Result<ApiResponse<LoginResponse>, ErrorResponse> {
+        info!("Refreshing access token");
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "RefreshToken",
+            vec![
+                KeyValue::new("component", "auth"),
+                KeyValue::new("operation", "refresh_token"),
+            ],
+        );
+
+        let mut request = Request::new(RefreshTokenRequest {
+            refresh_token: request_data.refresh_token.clone(),
+        });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().refresh_token(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.map(Into::into).unwrap_or_default(),
+                };
+
+                self.complete_tracing_success(&tracing_ctx, method, "Access token refreshed")
+                    .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!("Failed to refresh access token: {}", error_response.message);
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!("Failed to refresh access token: {}", error_response.message),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn list_sessions(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<Vec<SessionResponse>>, ErrorResponse> {
+        info!("Listing sessions of user {user_id}");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "ListSessions",
+            vec![
+                KeyValue::new("component", "auth"),
+                KeyValue::new("operation", "list_sessions"),
+                KeyValue::new("user.id", user_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(ListSessionsRequest { user_id });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().list_sessions(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into_iter().map(Into::into).collect(),
+                };
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    &format!("Sessions of user {user_id} listed"),
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!(
+                    "Failed to list sessions of user {user_id}: {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!(
+                        "Failed to list sessions of user {user_id}: {}",
+                        error_response.message
+                    ),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn revoke_session(
+        &self,
+        user_id: i32,
+        session_id: i32,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        info!("Revoking session {session_id} of user {user_id}");
+
+        let method = Method::Delete;
+        let tracing_ctx = self.start_tracing(
+            "RevokeSession",
+            vec![
+                KeyValue::new("component", "auth"),
+                KeyValue::new("operation", "revoke_session"),
+                KeyValue::new("user.id", user_id.to_string()),
+                KeyValue::new("session.id", session_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(RevokeSessionRequest {
+            user_id,
+            session_id,
+        });
+
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().revoke_session(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: (),
+                };
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    &format!("Session {session_id} revoked"),
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!(
+                    "Failed to revoke session {session_id}: {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!(
+                        "Failed to revoke session {session_id}: {}",
+                        error_response.message
+                    ),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
 }
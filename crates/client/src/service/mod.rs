@@ -1,48 +1,244 @@
+mod account;
 mod auth;
+mod pool;
+mod retry;
 mod saldo;
+mod system;
 mod topup;
 mod transfer;
 mod user;
+mod virtual_card;
+mod webhook;
 mod withdraw;
 
+pub use self::account::AccountService;
 pub use self::auth::AuthService;
+pub use self::pool::ClientPool;
+pub use self::retry::RetryPolicy;
 pub use self::saldo::SaldoService;
+pub use self::system::SystemService;
 pub use self::topup::TopupService;
 pub use self::transfer::TransferService;
 pub use self::user::UserService;
+pub use self::virtual_card::VirtualCardService;
+pub use self::webhook::WebhookService;
 pub use self::withdraw::WithdrawService;
 
-use std::sync::Arc;
-use tokio::sync::Mutex;
-use tonic::transport::Channel;
+use std::time::Duration;
+use tonic::transport::{Channel, Endpoint};
 
 use genproto::{
+    account::account_service_client::AccountServiceClient,
     auth::auth_service_client::AuthServiceClient, saldo::saldo_service_client::SaldoServiceClient,
+    system::system_service_client::SystemServiceClient,
     topup::topup_service_client::TopupServiceClient,
     transfer::transfer_service_client::TransferServiceClient,
     user::user_service_client::UserServiceClient,
+    virtual_card::virtual_card_service_client::VirtualCardServiceClient,
+    webhook::webhook_service_client::WebhookServiceClient,
     withdraw::withdraw_service_client::WithdrawServiceClient,
 };
 
 #[derive(Clone)]
 pub struct GrpcClients {
-    pub auth: Arc<Mutex<AuthServiceClient<Channel>>>,
-    pub saldo: Arc<Mutex<SaldoServiceClient<Channel>>>,
-    pub topup: Arc<Mutex<TopupServiceClient<Channel>>>,
-    pub transfer: Arc<Mutex<TransferServiceClient<Channel>>>,
-    pub user: Arc<Mutex<UserServiceClient<Channel>>>,
-    pub withdraw: Arc<Mutex<WithdrawServiceClient<Channel>>>,
+    pub account: ClientPool<AccountServiceClient<Channel>>,
+    pub auth: ClientPool<AuthServiceClient<Channel>>,
+    pub saldo: ClientPool<SaldoServiceClient<Channel>>,
+    pub topup: ClientPool<TopupServiceClient<Channel>>,
+    pub transfer: ClientPool<TransferServiceClient<Channel>>,
+    pub user: ClientPool<UserServiceClient<Channel>>,
+    pub withdraw: ClientPool<WithdrawServiceClient<Channel>>,
+    pub virtual_card: ClientPool<VirtualCardServiceClient<Channel>>,
+    pub webhook: ClientPool<WebhookServiceClient<Channel>>,
+    pub system: ClientPool<SystemServiceClient<Channel>>,
 }
 
 impl GrpcClients {
-    pub async fn init(channel: Channel) -> Self {
+    pub async fn init(
+        endpoint: &Endpoint,
+        pool_size: usize,
+        request_timeout: Duration,
+        message_size: GrpcMessageSize,
+    ) -> Self {
+        // batas waktu per panggilan, server yang macet tidak membuat handler menggantung
+        let endpoint = endpoint.clone().timeout(request_timeout);
+
+        // tiap channel punya koneksi HTTP/2 sendiri, dibuat saat request pertama
+        let channels: Vec<Channel> = (0..pool_size.max(1))
+            .map(|_| endpoint.connect_lazy())
+            .collect();
+
+        let GrpcMessageSize {
+            max_decoding,
+            max_encoding,
+        } = message_size;
+
         Self {
-            auth: Arc::new(Mutex::new(AuthServiceClient::new(channel.clone()))),
-            user: Arc::new(Mutex::new(UserServiceClient::new(channel.clone()))),
-            saldo: Arc::new(Mutex::new(SaldoServiceClient::new(channel.clone()))),
-            topup: Arc::new(Mutex::new(TopupServiceClient::new(channel.clone()))),
-            transfer: Arc::new(Mutex::new(TransferServiceClient::new(channel.clone()))),
-            withdraw: Arc::new(Mutex::new(WithdrawServiceClient::new(channel))),
+            account: pool(&channels, |channel| {
+                AccountServiceClient::new(channel)
+                    .max_decoding_message_size(max_decoding)
+                    .max_encoding_message_size(max_encoding)
+            }),
+            auth: pool(&channels, |channel| {
+                AuthServiceClient::new(channel)
+                    .max_decoding_message_size(max_decoding)
+                    .max_encoding_message_size(max_encoding)
+            }),
+            user: pool(&channels, |channel| {
+                UserServiceClient::new(channel)
+                    .max_decoding_message_size(max_decoding)
+                    .max_encoding_message_size(max_encoding)
+            }),
+            saldo: pool(&channels, |channel| {
+                SaldoServiceClient::new(channel)
+                    .max_decoding_message_size(max_decoding)
+                    .max_encoding_message_size(max_encoding)
+            }),
+            topup: pool(&channels, |channel| {
+                TopupServiceClient::new(channel)
+                    .max_decoding_message_size(max_decoding)
+                    .max_encoding_message_size(max_encoding)
+            }),
+            transfer: pool(&channels, |channel| {
+                TransferServiceClient::new(channel)
+                    .max_decoding_message_size(max_decoding)
+                    .max_encoding_message_size(max_encoding)
+            }),
+            withdraw: pool(&channels, |channel| {
+                WithdrawServiceClient::new(channel)
+                    .max_decoding_message_size(max_decoding)
+                    .max_encoding_message_size(max_encoding)
+            }),
+            virtual_card: pool(&channels, |channel| {
+                VirtualCardServiceClient::new(channel)
+                    .max_decoding_message_size(max_decoding)
+                    .max_encoding_message_size(max_encoding)
+            }),
+            webhook: pool(&channels, |channel| {
+                WebhookServiceClient::new(channel)
+                    .max_decoding_message_size(max_decoding)
+                    .max_encoding_message_size(max_encoding)
+            }),
+            system: pool(&channels, |channel| {
+                SystemServiceClient::new(channel)
+                    .max_decoding_message_size(max_decoding)
+                    .max_encoding_message_size(max_encoding)
+            }),
         }
     }
 }
+
+// batas ukuran pesan dari sisi gateway, dipasangkan dengan batas server gRPC
+#[derive(Debug, Clone, Copy)]
+pub struct GrpcMessageSize {
+    pub max_decoding: usize,
+    pub max_encoding: usize,
+}
+
+fn pool<T: Clone>(channels: &[Channel], build: impl Fn(Channel) -> T) -> ClientPool<T> {
+    ClientPool::new(channels.iter().cloned().map(build).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{Router, http::StatusCode};
+    use genproto::{saldo::FindSaldoByIdRequest, transfer::CreateTransferRequest};
+    use shared::domain::response::ErrorResponse;
+    use tokio::net::TcpListener;
+    use tonic::{Code, Request};
+
+    const DEFAULT_MESSAGE_SIZE: usize = 4 * 1024 * 1024;
+
+    // server gRPC tiruan yang membalas Unimplemented setelah `delay`
+    async fn mock_server(delay: Duration) -> Endpoint {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let app = Router::new().fallback(move || async move {
+            tokio::time::sleep(delay).await;
+            (
+                [("content-type", "application/grpc"), ("grpc-status", "12")],
+                (),
+            )
+        });
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        Endpoint::from_shared(format!("http://{addr}")).unwrap()
+    }
+
+    async fn find_saldo(endpoint: &Endpoint) -> ErrorResponse {
+        let size = GrpcMessageSize {
+            max_decoding: DEFAULT_MESSAGE_SIZE,
+            max_encoding: DEFAULT_MESSAGE_SIZE,
+        };
+        let clients = GrpcClients::init(endpoint, 1, Duration::from_millis(100), size).await;
+
+        let status = RetryPolicy::default()
+            .run(Request::new(FindSaldoByIdRequest { id: 1 }), |request| {
+                let mut client = clients.saldo.get();
+                async move { client.find_saldo_by_id(request).await }
+            })
+            .await
+            .unwrap_err();
+
+        ErrorResponse::from(status)
+    }
+
+    #[tokio::test]
+    async fn responsive_server_answers_within_the_timeout() {
+        let endpoint = mock_server(Duration::ZERO).await;
+
+        let err = find_saldo(&endpoint).await;
+
+        assert_eq!(err.code, StatusCode::NOT_IMPLEMENTED);
+    }
+
+    #[tokio::test]
+    async fn stalled_server_times_out_instead_of_hanging() {
+        let endpoint = mock_server(Duration::from_secs(30)).await;
+
+        let err = tokio::time::timeout(Duration::from_secs(5), find_saldo(&endpoint))
+            .await
+            .expect("request timeout should fire before the server answers");
+
+        assert_eq!(err.code, StatusCode::GATEWAY_TIMEOUT);
+        assert_eq!(err.message, "upstream timeout");
+    }
+
+    // pesan sedikit di atas 4 MB, dikirim tanpa retry agar kode status aslinya terlihat
+    async fn send_oversized_transfer(endpoint: &Endpoint, max_encoding: usize) -> Code {
+        let size = GrpcMessageSize {
+            max_decoding: DEFAULT_MESSAGE_SIZE,
+            max_encoding,
+        };
+        let clients = GrpcClients::init(endpoint, 1, Duration::from_secs(5), size).await;
+        let request = CreateTransferRequest {
+            note: "x".repeat(DEFAULT_MESSAGE_SIZE + 1),
+            ..Default::default()
+        };
+
+        clients
+            .transfer
+            .get()
+            .create_transfer(request)
+            .await
+            .unwrap_err()
+            .code()
+    }
+
+    #[tokio::test]
+    async fn message_over_the_default_limit_needs_a_raised_limit() {
+        let endpoint = mock_server(Duration::ZERO).await;
+
+        // encoder client menolak pesan dan membatalkan stream sebelum server sempat menjawab
+        assert_eq!(
+            send_oversized_transfer(&endpoint, DEFAULT_MESSAGE_SIZE).await,
+            Code::Internal
+        );
+        // dengan batas dinaikkan pesan sampai ke server tiruan yang membalas Unimplemented
+        assert_eq!(
+            send_oversized_transfer(&endpoint, 2 * DEFAULT_MESSAGE_SIZE).await,
+            Code::Unimplemented
+        );
+    }
+}
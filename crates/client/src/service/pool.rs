@@ -0,0 +1,76 @@
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+#[derive(Debug, Clone)]
+pub struct ClientPool<T> {
+    clients: Arc<[T]>,
+    next: Arc<AtomicUsize>,
+}
+
+impl<T: Clone> ClientPool<T> {
+    pub fn new(clients: Vec<T>) -> Self {
+        assert!(
+            !clients.is_empty(),
+            "ClientPool requires at least one client"
+        );
+
+        Self {
+            clients: clients.into(),
+            next: Arc::new(AtomicUsize::new(0)),
+        }
+    }
+
+    // round-robin, tanpa lock di hot path
+    pub fn get(&self) -> T {
+        let index = self.next.fetch_add(1, Ordering::Relaxed) % self.clients.len();
+        self.clients[index].clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::{Duration, Instant};
+
+    #[test]
+    fn clients_are_handed_out_round_robin() {
+        let pool = ClientPool::new(vec!["a", "b", "c"]);
+
+        let picked: Vec<_> = (0..6).map(|_| pool.get()).collect();
+
+        assert_eq!(picked, ["a", "b", "c", "a", "b", "c"]);
+    }
+
+    #[test]
+    #[should_panic(expected = "ClientPool requires at least one client")]
+    fn empty_pool_is_rejected() {
+        ClientPool::<u8>::new(Vec::new());
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_calls_do_not_serialize() {
+        let pool = ClientPool::new(vec![0usize, 1, 2, 3]);
+        let used = Arc::new([0, 1, 2, 3].map(|_| AtomicUsize::new(0)));
+        let started = Instant::now();
+
+        let calls: Vec<_> = (0..16)
+            .map(|_| {
+                let (pool, used) = (pool.clone(), used.clone());
+                tokio::spawn(async move {
+                    let client = pool.get();
+                    // RPC tiruan yang memakan waktu; dengan Mutex 16 panggilan butuh 1.6s
+                    tokio::time::sleep(Duration::from_millis(100)).await;
+                    used[client].fetch_add(1, Ordering::Relaxed);
+                })
+            })
+            .collect();
+        for call in calls {
+            call.await.unwrap();
+        }
+
+        assert!(started.elapsed() < Duration::from_millis(800));
+        assert!(used.iter().all(|count| count.load(Ordering::Relaxed) == 4));
+    }
+}
@@ -0,0 +1,278 @@
+use shared::utils::remaining_time;
+use std::{error::Error, future::Future, time::Duration};
+use tonic::{Code, ConnectError, Request, Response, Status, TimeoutExpired};
+use tracing::warn;
+
+#[derive(Debug, Clone, Copy)]
+pub struct RetryPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+}
+
+impl Default for RetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(100),
+            max_backoff: Duration::from_secs(2),
+        }
+    }
+}
+
+impl RetryPolicy {
+    fn backoff(&self, attempt: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(attempt))
+            .min(self.max_backoff)
+    }
+
+    // untuk RPC baca atau idempotent: Unavailable di-retry, status lain langsung dikembalikan
+    pub async fn run<Req, Resp, F, Fut>(
+        &self,
+        request: Request<Req>,
+        call: F,
+    ) -> Result<Response<Resp>, Status>
+    where
+        Req: Clone,
+        F: FnMut(Request<Req>) -> Fut,
+        Fut: Future<Output = Result<Response<Resp>, Status>>,
+    {
+        self.run_with(request, call, |status| status.code() == Code::Unavailable)
+            .await
+    }
+
+    // untuk RPC tulis (uang berpindah): hanya di-retry kalau request pasti belum sampai ke server,
+    // Unavailable setelah request terkirim bisa berarti transaksi sudah di-commit
+    pub async fn run_write<Req, Resp, F, Fut>(
+        &self,
+        request: Request<Req>,
+        call: F,
+    ) -> Result<Response<Resp>, Status>
+    where
+        Req: Clone,
+        F: FnMut(Request<Req>) -> Fut,
+        Fut: Future<Output = Result<Response<Resp>, Status>>,
+    {
+        self.run_with(request, call, never_reached_server).await
+    }
+
+    async fn run_with<Req, Resp, F, Fut>(
+        &self,
+        request: Request<Req>,
+        mut call: F,
+        retryable: fn(&Status) -> bool,
+    ) -> Result<Response<Resp>, Status>
+    where
+        Req: Clone,
+        F: FnMut(Request<Req>) -> Fut,
+        Fut: Future<Output = Result<Response<Resp>, Status>>,
+    {
+        let (metadata, extensions, message) = request.into_parts();
+        let mut attempt = 0;
+
+        loop {
+            let mut request =
+                Request::from_parts(metadata.clone(), extensions.clone(), message.clone());
+
+            // sisa deadline dikirim sebagai grpc-timeout supaya server ikut berhenti tepat waktu
+            if let Some(remaining) = remaining_time() {
+                if remaining.is_zero() {
+                    return Err(Status::deadline_exceeded("Request deadline exceeded"));
+                }
+                request.set_timeout(remaining);
+            }
+
+            match call(request).await {
+                Err(status) if retryable(&status) && attempt < self.max_retries => {
+                    let delay = self.backoff(attempt);
+                    attempt += 1;
+
+                    warn!(
+                        "gRPC upstream unavailable, retrying in {delay:?} (attempt {attempt}/{}): {}",
+                        self.max_retries,
+                        status.message()
+                    );
+
+                    tokio::time::sleep(delay).await;
+                }
+                result => return result.map_err(upstream_timeout),
+            }
+        }
+    }
+}
+
+// koneksi gagal dibuka atau stream ditolak server (REFUSED_STREAM) sebelum diproses
+fn never_reached_server(status: &Status) -> bool {
+    if status.code() != Code::Unavailable {
+        return false;
+    }
+
+    let mut source = status.source();
+    while let Some(err) = source {
+        if err.is::<ConnectError>() {
+            return true;
+        }
+
+        if err
+            .downcast_ref::<h2::Error>()
+            .is_some_and(|e| e.reason() == Some(h2::Reason::REFUSED_STREAM))
+        {
+            return true;
+        }
+
+        source = err.source();
+    }
+
+    false
+}
+
+// timeout dari Endpoint muncul sebagai Cancelled, diubah jadi DeadlineExceeded supaya handler membalas 504
+fn upstream_timeout(status: Status) -> Status {
+    if status.code() == Code::Cancelled && status.message() == TimeoutExpired(()).to_string() {
+        warn!("gRPC upstream did not respond within the request timeout");
+        return Status::deadline_exceeded("upstream timeout");
+    }
+
+    status
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::utils::{parse_grpc_timeout, with_deadline};
+    use std::sync::atomic::{AtomicU32, Ordering};
+
+    fn policy() -> RetryPolicy {
+        RetryPolicy {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(1),
+            max_backoff: Duration::from_millis(1),
+        }
+    }
+
+    fn connect_refused() -> Status {
+        Status::from_error(Box::new(ConnectError(Box::new(std::io::Error::from(
+            std::io::ErrorKind::ConnectionRefused,
+        )))))
+    }
+
+    // gagal `failures` kali dengan status dari `fail`, lalu sukses
+    async fn flaky(
+        attempts: &AtomicU32,
+        failures: u32,
+        fail: fn() -> Status,
+    ) -> Result<Response<u32>, Status> {
+        let attempt = attempts.fetch_add(1, Ordering::SeqCst);
+        if attempt < failures {
+            Err(fail())
+        } else {
+            Ok(Response::new(attempt))
+        }
+    }
+
+    #[tokio::test]
+    async fn read_is_retried_on_unavailable() {
+        let attempts = AtomicU32::new(0);
+
+        let response = policy()
+            .run(Request::new(()), |_| {
+                flaky(&attempts, 2, || Status::unavailable("connection reset"))
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.into_inner(), 2);
+        assert_eq!(attempts.load(Ordering::SeqCst), 3);
+    }
+
+    #[tokio::test]
+    async fn write_is_not_retried_once_sent() {
+        let attempts = AtomicU32::new(0);
+
+        let status = policy()
+            .run_write(Request::new(()), |_| {
+                flaky(&attempts, 1, || Status::unavailable("connection reset"))
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(status.code(), Code::Unavailable);
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn write_is_retried_when_connect_failed() {
+        let attempts = AtomicU32::new(0);
+
+        let response = policy()
+            .run_write(Request::new(()), |_| flaky(&attempts, 2, connect_refused))
+            .await
+            .unwrap();
+
+        assert_eq!(response.into_inner(), 2);
+    }
+
+    #[tokio::test]
+    async fn write_is_retried_when_stream_refused() {
+        let attempts = AtomicU32::new(0);
+
+        let response = policy()
+            .run_write(Request::new(()), |_| {
+                flaky(&attempts, 1, || {
+                    Status::from_error(Box::new(h2::Error::from(h2::Reason::REFUSED_STREAM)))
+                })
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(response.into_inner(), 1);
+    }
+
+    #[test]
+    fn only_unsent_requests_count_as_never_reached() {
+        assert!(never_reached_server(&connect_refused()));
+        assert!(!never_reached_server(&Status::unavailable(
+            "upstream closed"
+        )));
+        assert!(!never_reached_server(&Status::internal("boom")));
+    }
+
+    #[tokio::test]
+    async fn remaining_deadline_is_sent_as_grpc_timeout() {
+        let deadline = tokio::time::Instant::now() + Duration::from_millis(200);
+
+        let timeout = with_deadline(
+            deadline,
+            policy().run(Request::new(()), |request| async move {
+                let header = request.metadata().get("grpc-timeout").cloned();
+                Ok(Response::new(header))
+            }),
+        )
+        .await
+        .unwrap()
+        .into_inner()
+        .expect("grpc-timeout is set");
+
+        let timeout = parse_grpc_timeout(timeout.to_str().unwrap()).unwrap();
+        assert!(!timeout.is_zero() && timeout <= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn expired_deadline_fails_without_calling_the_server() {
+        let attempts = AtomicU32::new(0);
+        let expired = tokio::time::Instant::now();
+
+        let status = with_deadline(
+            expired,
+            policy().run(Request::new(()), |_| {
+                flaky(&attempts, 0, || Status::internal(""))
+            }),
+        )
+        .await
+        .unwrap_err();
+
+        assert_eq!(status.code(), Code::DeadlineExceeded);
+        assert_eq!(attempts.load(Ordering::SeqCst), 0);
+    }
+}
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use genproto::saldo::{
-    CreateSaldoRequest, FindAllSaldoRequest, FindSaldoByIdRequest, FindSaldoByUserIdRequest,
+    AdjustBalanceRequest, CreateSaldoRequest, FindAllSaldoRequest, FindSaldoByIdRequest,
+    FindSaldoByUserIdRequest, FindSaldoByUsersIdRequest, ReconcileBalanceRequest, TopSaldosRequest,
     UpdateSaldoRequest, saldo_service_client::SaldoServiceClient,
 };
 use opentelemetry::{
@@ -13,45 +14,63 @@ use prometheus_client::registry::Registry;
 use shared::{
     domain::{
         request::{
+            AdjustBalanceRequest as DomainAdjustBalanceRequest,
             CreateSaldoRequest as DomainCreateSaldoRequest,
-            FindAllSaldoRequest as DomainFindAllSaldoRequest,
+            FindAllSaldoRequest as DomainFindAllSaldoRequest, PageRequest,
+            ReconcileBalanceRequest as DomainReconcileBalanceRequest,
+            TopSaldosRequest as DomainTopSaldosRequest,
             UpdateSaldoRequest as DomainUpdateSaldoRequest,
         },
-        response::{ApiResponse, ApiResponsePagination, ErrorResponse, saldo::SaldoResponse},
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            saldo::{BalanceReconciliationResponse, SaldoRankingResponse, SaldoResponse},
+        },
     },
     utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
 };
 use std::sync::Arc;
-use tokio::{sync::Mutex, time::Instant};
+use tokio::time::Instant;
 use tonic::{Request, transport::Channel};
 use tracing::{error, info};
 
+use crate::service::{ClientPool, RetryPolicy};
 use shared::abstract_trait::SaldoServiceTrait;
 
 #[derive(Debug)]
 pub struct SaldoService {
-    client: Arc<Mutex<SaldoServiceClient<Channel>>>,
-    metrics: Arc<Mutex<Metrics>>,
+    client: ClientPool<SaldoServiceClient<Channel>>,
+    retry_policy: RetryPolicy,
+    metrics: Arc<Metrics>,
 }
 
 impl SaldoService {
     pub async fn new(
-        client: Arc<Mutex<SaldoServiceClient<Channel>>>,
-        metrics: Arc<Mutex<Metrics>>,
+        client: ClientPool<SaldoServiceClient<Channel>>,
+        retry_policy: RetryPolicy,
+        metrics: Arc<Metrics>,
         registry: &mut Registry,
     ) -> Self {
         registry.register(
             "saldo_handler_request_counter",
             "Total number of requests to the SaldoService",
-            metrics.lock().await.request_counter.clone(),
+            metrics.request_counter.clone(),
         );
         registry.register(
             "saldo_handler_request_duration",
             "Histogram of request durations for the SaldoService",
-            metrics.lock().await.request_duration.clone(),
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "saldo_handler_error_counter",
+            "Total number of failed requests to the SaldoService by error kind",
+            metrics.error_counter.clone(),
         );
 
-        Self { client, metrics }
+        Self {
+            client,
+            retry_policy,
+            metrics,
+        }
     }
 
     fn get_tracer(&self) -> BoxedTracer {
@@ -59,9 +78,10 @@ impl SaldoService {
     }
 
     fn inject_trace_context<T>(&self, cx: &Context, request: &mut Request<T>) {
-        global::get_text_map_propagator(|propagator| {
-            propagator.inject_context(cx, &mut MetadataInjector(request.metadata_mut()))
-        });
+        let mut injector = MetadataInjector(request.metadata_mut());
+        global::get_text_map_propagator(|propagator| propagator.inject_context(cx, &mut injector));
+        injector.inject_request_id();
+        injector.inject_bearer_token();
     }
 
     fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
@@ -84,7 +104,11 @@ impl SaldoService {
         );
 
         let cx = Context::current_with_span(span);
-        TracingContext { cx, start_time }
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
     }
 
     async fn complete_tracing_success(
@@ -101,8 +125,12 @@ impl SaldoService {
         &self,
         tracing_ctx: &TracingContext,
         method: Method,
+        error_kind: &str,
         error_message: &str,
     ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
         self.complete_tracing_internal(tracing_ctx, method, false, error_message)
             .await;
     }
@@ -137,7 +165,8 @@ impl SaldoService {
             error!("Operation failed: {message}");
         }
 
-        self.metrics.lock().await.record(method, status, elapsed);
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
 
         tracing_ctx.cx.span().end();
     }
@@ -173,7 +202,14 @@ impl SaldoServiceTrait for SaldoService {
         });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.find_all_saldo(request).await {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_all_saldo(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponsePagination {
@@ -201,10 +237,7 @@ impl SaldoServiceTrait for SaldoService {
                 Ok(response)
             }
             Err(err) => {
-                let error_response = ErrorResponse {
-                    status: err.code().to_string(),
-                    message: err.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(err);
 
                 error!(
                     "Failed to retrieve saldos (page: {}, size: {}): {}",
@@ -214,6 +247,7 @@ impl SaldoServiceTrait for SaldoService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to retrieve saldos (page: {}, size: {}): {}",
                         req.page, req.page_size, error_response.message
@@ -245,7 +279,14 @@ impl SaldoServiceTrait for SaldoService {
         let mut request = Request::new(FindSaldoByIdRequest { id });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.find_saldo_by_id(request).await {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_saldo_by_id(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -266,16 +307,14 @@ impl SaldoServiceTrait for SaldoService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!("Failed to find saldo {id}: {}", error_response.message);
 
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!("Failed to find saldo {id}: {}", error_response.message),
                 )
                 .await;
@@ -288,7 +327,8 @@ impl SaldoServiceTrait for SaldoService {
     async fn get_saldo_users(
         &self,
         id: i32,
-    ) -> Result<ApiResponse<Option<Vec<SaldoResponse>>>, ErrorResponse> {
+        req: &PageRequest,
+    ) -> Result<ApiResponsePagination<Vec<SaldoResponse>>, ErrorResponse> {
         info!("Get saldo users request: {id}");
 
         let method = Method::Get;
@@ -298,25 +338,33 @@ impl SaldoServiceTrait for SaldoService {
                 KeyValue::new("component", "saldo"),
                 KeyValue::new("operation", "get_saldo_users"),
                 KeyValue::new("user.id", id as i64),
+                KeyValue::new("page", req.page.to_string()),
+                KeyValue::new("page_size", req.page_size.to_string()),
             ],
         );
 
-        let mut request = Request::new(FindSaldoByUserIdRequest { user_id: id });
+        let mut request = Request::new(FindSaldoByUsersIdRequest {
+            user_id: id,
+            page: req.page,
+            page_size: req.page_size,
+        });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self
-            .client
-            .lock()
-            .await
-            .find_saldo_by_users_id(request)
-            .await
-        {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_saldo_by_users_id(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
-                let response = ApiResponse {
+                let response = ApiResponsePagination {
                     status: inner.status,
                     message: inner.message,
-                    data: Some(inner.data.into_iter().map(Into::into).collect()),
+                    data: inner.data.into_iter().map(Into::into).collect(),
+                    pagination: inner.pagination.unwrap_or_default().into(),
                 };
 
                 info!("Saldo for user {id} retrieved successfully");
@@ -331,10 +379,7 @@ impl SaldoServiceTrait for SaldoService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to retrieve saldo for user {id}: {}",
@@ -344,6 +389,7 @@ impl SaldoServiceTrait for SaldoService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to retrieve saldo for user {id}: {}",
                         error_response.message
@@ -375,13 +421,14 @@ impl SaldoServiceTrait for SaldoService {
         let mut request = Request::new(FindSaldoByUserIdRequest { user_id: id });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self
-            .client
-            .lock()
-            .await
-            .find_saldo_by_user_id(request)
-            .await
-        {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_saldo_by_user_id(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -402,10 +449,7 @@ impl SaldoServiceTrait for SaldoService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to find saldo for user {id}: {}",
@@ -415,6 +459,7 @@ impl SaldoServiceTrait for SaldoService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to find saldo for user {id}: {}",
                         error_response.message
@@ -447,10 +492,18 @@ impl SaldoServiceTrait for SaldoService {
         let mut request = Request::new(CreateSaldoRequest {
             user_id: input.user_id,
             total_balance: input.total_balance,
+            label: input.label.clone().unwrap_or_default(),
         });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.create_saldo(request).await {
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().create_saldo(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -471,10 +524,7 @@ impl SaldoServiceTrait for SaldoService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to create saldo for user_id {}: {}",
@@ -484,6 +534,7 @@ impl SaldoServiceTrait for SaldoService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to create saldo for user_id {}: {}",
                         input.user_id, error_response.message
@@ -527,7 +578,14 @@ impl SaldoServiceTrait for SaldoService {
         let mut request = Request::new(update_request);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.update_saldo(request).await {
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().update_saldo(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -548,14 +606,12 @@ impl SaldoServiceTrait for SaldoService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to update saldo (ID: {saldo_id}, user_id: {user_id}): {}",
                         error_response.message
@@ -584,7 +640,14 @@ impl SaldoServiceTrait for SaldoService {
         let mut request = Request::new(FindSaldoByIdRequest { id });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.delete_saldo(request).await {
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().delete_saldo(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -605,16 +668,14 @@ impl SaldoServiceTrait for SaldoService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!("Failed to delete saldo {id}: {}", error_response.message);
 
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!("Failed to delete saldo {id}: {}", error_response.message),
                 )
                 .await;
@@ -623,4 +684,215 @@ impl SaldoServiceTrait for SaldoService {
             }
         }
     }
+
+    async fn get_top_saldos(
+        &self,
+        req: &DomainTopSaldosRequest,
+    ) -> Result<ApiResponse<Vec<SaldoRankingResponse>>, ErrorResponse> {
+        info!("Retrieving top {} saldos", req.limit);
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "GetTopSaldos",
+            vec![
+                KeyValue::new("component", "saldo"),
+                KeyValue::new("operation", "top"),
+                KeyValue::new("limit", req.limit as i64),
+            ],
+        );
+
+        let mut request = Request::new(TopSaldosRequest { limit: req.limit });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().top_saldos(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner
+                        .data
+                        .into_iter()
+                        .map(SaldoRankingResponse::from)
+                        .collect::<Vec<_>>(),
+                };
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Top saldos retrieved successfully",
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!("Failed to retrieve top saldos: {}", error_response.message);
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!("Failed to retrieve top saldos: {}", error_response.message),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn adjust_balance(
+        &self,
+        actor_id: i32,
+        input: &DomainAdjustBalanceRequest,
+    ) -> Result<ApiResponse<SaldoResponse>, ErrorResponse> {
+        info!(
+            "Adjusting balance of user {} by {} (actor {actor_id})",
+            input.user_id, input.delta
+        );
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "AdjustBalance",
+            vec![
+                KeyValue::new("component", "saldo"),
+                KeyValue::new("operation", "adjust"),
+                KeyValue::new("user_id", input.user_id.to_string()),
+                KeyValue::new("actor_id", actor_id.to_string()),
+                KeyValue::new("delta", input.delta.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(AdjustBalanceRequest {
+            user_id: input.user_id,
+            delta: input.delta,
+            reason: input.reason.clone(),
+        });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().adjust_balance(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: SaldoResponse::from(inner.data),
+                };
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Balance adjusted successfully",
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!("Failed to adjust balance: {}", error_response.message);
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!("Failed to adjust balance: {}", error_response.message),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn reconcile_balance(
+        &self,
+        actor_id: i32,
+        input: &DomainReconcileBalanceRequest,
+    ) -> Result<ApiResponse<Vec<BalanceReconciliationResponse>>, ErrorResponse> {
+        info!(
+            "Reconciling balance of user {:?} (fix: {}, actor {actor_id})",
+            input.user_id, input.fix
+        );
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "ReconcileBalance",
+            vec![
+                KeyValue::new("component", "saldo"),
+                KeyValue::new("operation", "reconcile"),
+                KeyValue::new(
+                    "user_id",
+                    input
+                        .user_id
+                        .map_or_else(|| "all".to_string(), |id| id.to_string()),
+                ),
+                KeyValue::new("actor_id", actor_id.to_string()),
+                KeyValue::new("fix", input.fix),
+            ],
+        );
+
+        let mut request = Request::new(ReconcileBalanceRequest {
+            user_id: input.user_id,
+            fix: input.fix,
+        });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        // aman diulang: koreksi yang sudah masuk membuat saldo seimbang sehingga tidak dikoreksi lagi
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().reconcile_balance(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into_iter().map(Into::into).collect(),
+                };
+
+                self.complete_tracing_success(&tracing_ctx, method, "Balance reconciled")
+                    .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!("Failed to reconcile balance: {}", error_response.message);
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!("Failed to reconcile balance: {}", error_response.message),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
 }
@@ -0,0 +1,352 @@
+use async_trait::async_trait;
+use genproto::system::{
+    CacheFlushPrefixRequest, CacheStatsRequest, GetRuntimeConfigRequest,
+    system_service_client::SystemServiceClient,
+};
+use opentelemetry::{
+    Context, KeyValue,
+    global::{self, BoxedTracer},
+    trace::{Span, SpanKind, TraceContextExt, Tracer},
+};
+use prometheus_client::registry::Registry;
+
+use shared::{
+    domain::{
+        request::CacheFlushRequest,
+        response::{
+            ApiResponse, ErrorResponse,
+            system::{CacheFlushResponse, CacheStatsResponse, RuntimeConfigResponse},
+        },
+    },
+    utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+};
+use std::sync::Arc;
+use tokio::time::Instant;
+use tonic::{Request, transport::Channel};
+use tracing::{error, info};
+
+use crate::service::{ClientPool, RetryPolicy};
+use shared::abstract_trait::SystemServiceTrait;
+
+#[derive(Debug)]
+pub struct SystemService {
+    client: ClientPool<SystemServiceClient<Channel>>,
+    retry_policy: RetryPolicy,
+    metrics: Arc<Metrics>,
+}
+
+impl SystemService {
+    pub async fn new(
+        client: ClientPool<SystemServiceClient<Channel>>,
+        retry_policy: RetryPolicy,
+        metrics: Arc<Metrics>,
+        registry: &mut Registry,
+    ) -> Self {
+        registry.register(
+            "system_handler_request_counter",
+            "Total number of requests to the SystemService",
+            metrics.request_counter.clone(),
+        );
+        registry.register(
+            "system_handler_request_duration",
+            "Histogram of request durations for the SystemService",
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "system_handler_error_counter",
+            "Total number of failed requests to the SystemService by error kind",
+            metrics.error_counter.clone(),
+        );
+
+        Self {
+            client,
+            retry_policy,
+            metrics,
+        }
+    }
+
+    fn get_tracer(&self) -> BoxedTracer {
+        global::tracer("system-service-client")
+    }
+
+    fn inject_trace_context<T>(&self, cx: &Context, request: &mut Request<T>) {
+        let mut injector = MetadataInjector(request.metadata_mut());
+        global::get_text_map_propagator(|propagator| propagator.inject_context(cx, &mut injector));
+        injector.inject_request_id();
+        injector.inject_bearer_token();
+    }
+
+    fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
+        let start_time = Instant::now();
+        let tracer = self.get_tracer();
+        let mut span = tracer
+            .span_builder(operation_name.to_string())
+            .with_kind(SpanKind::Server)
+            .with_attributes(attributes)
+            .start(&tracer);
+
+        info!("Starting operation: {operation_name}");
+
+        span.add_event(
+            "Operation started",
+            vec![
+                KeyValue::new("operation", operation_name.to_string()),
+                KeyValue::new("timestamp", start_time.elapsed().as_secs_f64().to_string()),
+            ],
+        );
+
+        let cx = Context::current_with_span(span);
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
+    }
+
+    async fn complete_tracing_success(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        message: &str,
+    ) {
+        self.complete_tracing_internal(tracing_ctx, method, true, message)
+            .await;
+    }
+
+    async fn complete_tracing_error(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        error_kind: &str,
+        error_message: &str,
+    ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
+        self.complete_tracing_internal(tracing_ctx, method, false, error_message)
+            .await;
+    }
+
+    async fn complete_tracing_internal(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        is_success: bool,
+        message: &str,
+    ) {
+        let status_str = if is_success { "SUCCESS" } else { "ERROR" };
+        let status = if is_success {
+            StatusUtils::Success
+        } else {
+            StatusUtils::Error
+        };
+        let elapsed = tracing_ctx.start_time.elapsed().as_secs_f64();
+
+        tracing_ctx.cx.span().add_event(
+            "Operation completed",
+            vec![
+                KeyValue::new("status", status_str),
+                KeyValue::new("duration_secs", elapsed.to_string()),
+                KeyValue::new("message", message.to_string()),
+            ],
+        );
+
+        if is_success {
+            info!("Operation completed successfully: {message}");
+        } else {
+            error!("Operation failed: {message}");
+        }
+
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
+
+        tracing_ctx.cx.span().end();
+    }
+}
+
+#[async_trait]
+impl SystemServiceTrait for SystemService {
+    async fn get_runtime_config(
+        &self,
+    ) -> Result<ApiResponse<RuntimeConfigResponse>, ErrorResponse> {
+        info!("Get runtime config request");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "GetRuntimeConfig",
+            vec![
+                KeyValue::new("component", "system"),
+                KeyValue::new("operation", "get_runtime_config"),
+            ],
+        );
+
+        let mut request = Request::new(GetRuntimeConfigRequest {});
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().get_runtime_config(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response: ApiResponse<RuntimeConfigResponse> = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                info!("Runtime config retrieved successfully");
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Runtime config retrieved successfully",
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!(
+                    "Failed to retrieve runtime config: {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &error_response.message,
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn cache_stats(&self) -> Result<ApiResponse<CacheStatsResponse>, ErrorResponse> {
+        info!("Get cache stats request");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "CacheStats",
+            vec![
+                KeyValue::new("component", "system"),
+                KeyValue::new("operation", "cache_stats"),
+            ],
+        );
+
+        let mut request = Request::new(CacheStatsRequest {});
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().cache_stats(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response: ApiResponse<CacheStatsResponse> = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Cache stats retrieved successfully",
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!("Failed to retrieve cache stats: {}", error_response.message);
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &error_response.message,
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn cache_flush_prefix(
+        &self,
+        input: &CacheFlushRequest,
+    ) -> Result<ApiResponse<CacheFlushResponse>, ErrorResponse> {
+        info!("Flush cache prefix request: {}", input.prefix);
+
+        let method = Method::Delete;
+        let tracing_ctx = self.start_tracing(
+            "CacheFlushPrefix",
+            vec![
+                KeyValue::new("component", "system"),
+                KeyValue::new("operation", "cache_flush_prefix"),
+                KeyValue::new("prefix", input.prefix.clone()),
+            ],
+        );
+
+        let mut request = Request::new(CacheFlushPrefixRequest {
+            prefix: input.prefix.clone(),
+        });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        // aman diulang: key yang sudah terhapus hanya tidak ikut terhitung lagi
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().cache_flush_prefix(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response: ApiResponse<CacheFlushResponse> = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                self.complete_tracing_success(&tracing_ctx, method, "Cache prefix flushed")
+                    .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!("Failed to flush cache prefix: {}", error_response.message);
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &error_response.message,
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+}
@@ -1,7 +1,8 @@
 use async_trait::async_trait;
 use genproto::topup::{
-    CreateTopupRequest, FindAllTopupRequest, FindTopupByIdRequest, FindTopupByUserIdRequest,
-    UpdateTopupRequest, topup_service_client::TopupServiceClient,
+    CreateTopupRequest, FindAllTopupRequest, FindTopupByIdRequest, FindTopupByNoRequest,
+    FindTopupByUserIdRequest, FindTopupByUsersIdRequest, TopupStatsRequest, UpdateTopupRequest,
+    topup_service_client::TopupServiceClient,
 };
 use opentelemetry::{
     Context, KeyValue,
@@ -14,44 +15,60 @@ use shared::{
     domain::{
         request::{
             CreateTopupRequest as DomainCreateTopupRequest,
-            FindAllTopupRequest as DomainFindAllTopupRequest,
+            FindAllTopupRequest as DomainFindAllTopupRequest, PageRequest,
+            TopupStatsRequest as DomainTopupStatsRequest,
             UpdateTopupRequest as DomainUpdateTopupRequest,
         },
-        response::{ApiResponse, ApiResponsePagination, ErrorResponse, topup::TopupResponse},
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            topup::{TopupMethodStatsResponse, TopupResponse},
+        },
     },
     utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
 };
 use std::sync::Arc;
-use tokio::{sync::Mutex, time::Instant};
+use tokio::time::Instant;
 use tonic::{Request, transport::Channel};
 use tracing::{error, info};
 
+use crate::service::{ClientPool, RetryPolicy};
 use shared::abstract_trait::TopupServiceTrait;
 
 #[derive(Debug)]
 pub struct TopupService {
-    client: Arc<Mutex<TopupServiceClient<Channel>>>,
-    metrics: Arc<Mutex<Metrics>>,
+    client: ClientPool<TopupServiceClient<Channel>>,
+    retry_policy: RetryPolicy,
+    metrics: Arc<Metrics>,
 }
 
 impl TopupService {
     pub async fn new(
-        client: Arc<Mutex<TopupServiceClient<Channel>>>,
-        metrics: Arc<Mutex<Metrics>>,
+        client: ClientPool<TopupServiceClient<Channel>>,
+        retry_policy: RetryPolicy,
+        metrics: Arc<Metrics>,
         registry: &mut Registry,
     ) -> Self {
         registry.register(
             "topup_handler_request_counter",
             "total number of requests to the TopupService",
-            metrics.lock().await.request_counter.clone(),
+            metrics.request_counter.clone(),
         );
         registry.register(
             "topup_handler_request_duration",
             "Histogram of request durations for the TopupService",
-            metrics.lock().await.request_duration.clone(),
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "topup_handler_error_counter",
+            "Total number of failed requests to the TopupService by error kind",
+            metrics.error_counter.clone(),
         );
 
-        Self { client, metrics }
+        Self {
+            client,
+            retry_policy,
+            metrics,
+        }
     }
 
     fn get_tracer(&self) -> BoxedTracer {
@@ -59,9 +76,10 @@ impl TopupService {
     }
 
     fn inject_trace_context<T>(&self, cx: &Context, request: &mut Request<T>) {
-        global::get_text_map_propagator(|propagator| {
-            propagator.inject_context(cx, &mut MetadataInjector(request.metadata_mut()))
-        });
+        let mut injector = MetadataInjector(request.metadata_mut());
+        global::get_text_map_propagator(|propagator| propagator.inject_context(cx, &mut injector));
+        injector.inject_request_id();
+        injector.inject_bearer_token();
     }
 
     fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
@@ -84,7 +102,11 @@ impl TopupService {
         );
 
         let cx = Context::current_with_span(span);
-        TracingContext { cx, start_time }
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
     }
 
     async fn complete_tracing_success(
@@ -101,8 +123,12 @@ impl TopupService {
         &self,
         tracing_ctx: &TracingContext,
         method: Method,
+        error_kind: &str,
         error_message: &str,
     ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
         self.complete_tracing_internal(tracing_ctx, method, false, error_message)
             .await;
     }
@@ -137,7 +163,8 @@ impl TopupService {
             error!("Operation failed: {message}");
         }
 
-        self.metrics.lock().await.record(method, status, elapsed);
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
 
         tracing_ctx.cx.span().end();
     }
@@ -174,7 +201,14 @@ impl TopupServiceTrait for TopupService {
         });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.find_all_topup(request).await {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_all_topup(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponsePagination {
@@ -194,10 +228,7 @@ impl TopupServiceTrait for TopupService {
                 Ok(response)
             }
             Err(err) => {
-                let error_response = ErrorResponse {
-                    status: err.code().to_string(),
-                    message: err.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(err);
 
                 error!(
                     "Failed to retrieve topups (page: {}, size: {}): {}",
@@ -207,6 +238,7 @@ impl TopupServiceTrait for TopupService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to retrieve topups (page: {}, size: {}): {}",
                         req.page, req.page_size, error_response.message
@@ -237,7 +269,14 @@ impl TopupServiceTrait for TopupService {
         let mut request = Request::new(FindTopupByIdRequest { id });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.find_topup_by_id(request).await {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_topup_by_id(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -253,10 +292,7 @@ impl TopupServiceTrait for TopupService {
                 Ok(response)
             }
             Err(err) => {
-                let error_response = ErrorResponse {
-                    status: err.code().to_string(),
-                    message: err.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(err);
 
                 error!(
                     "Failed to retrieve topup (id: {}): {id}",
@@ -266,6 +302,7 @@ impl TopupServiceTrait for TopupService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to retrieve topup (id: {id}): {}",
                         error_response.message
@@ -278,10 +315,80 @@ impl TopupServiceTrait for TopupService {
         }
     }
 
+    async fn get_topup_by_no(
+        &self,
+        topup_no: &str,
+    ) -> Result<ApiResponse<TopupResponse>, ErrorResponse> {
+        let method = Method::Get;
+
+        let tracing_ctx = self.start_tracing(
+            "GetTopupByNo",
+            vec![
+                KeyValue::new("component", "topup"),
+                KeyValue::new("operation", "get_by_no"),
+                KeyValue::new("topup_no", topup_no.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(FindTopupByNoRequest {
+            topup_no: topup_no.to_string(),
+        });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_topup_by_no(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                info!(
+                    "Retrieved topup (topup_no: {topup_no}): {}",
+                    response.message
+                );
+
+                self.complete_tracing_success(&tracing_ctx, method, &response.message)
+                    .await;
+                Ok(response)
+            }
+            Err(err) => {
+                let error_response = ErrorResponse::from(err);
+
+                error!(
+                    "Failed to retrieve topup (topup_no: {topup_no}): {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!(
+                        "Failed to retrieve topup (topup_no: {topup_no}): {}",
+                        error_response.message
+                    ),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
     async fn get_topup_users(
         &self,
         user_id: i32,
-    ) -> Result<ApiResponse<Option<Vec<TopupResponse>>>, ErrorResponse> {
+        req: &PageRequest,
+    ) -> Result<ApiResponsePagination<Vec<TopupResponse>>, ErrorResponse> {
         let method = Method::Get;
 
         let tracing_ctx = self.start_tracing(
@@ -290,25 +397,33 @@ impl TopupServiceTrait for TopupService {
                 KeyValue::new("component", "topup"),
                 KeyValue::new("operation", "get_users"),
                 KeyValue::new("user_id", user_id.to_string()),
+                KeyValue::new("page", req.page.to_string()),
+                KeyValue::new("page_size", req.page_size.to_string()),
             ],
         );
 
-        let mut request = Request::new(FindTopupByUserIdRequest { user_id });
+        let mut request = Request::new(FindTopupByUsersIdRequest {
+            user_id,
+            page: req.page,
+            page_size: req.page_size,
+        });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self
-            .client
-            .lock()
-            .await
-            .find_topup_by_users_id(request)
-            .await
-        {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_topup_by_users_id(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
-                let response = ApiResponse {
+                let response = ApiResponsePagination {
                     status: inner.status,
                     message: inner.message,
-                    data: Some(inner.data.into_iter().map(Into::into).collect()),
+                    data: inner.data.into_iter().map(Into::into).collect(),
+                    pagination: inner.pagination.unwrap_or_default().into(),
                 };
 
                 info!(
@@ -321,10 +436,7 @@ impl TopupServiceTrait for TopupService {
                 Ok(response)
             }
             Err(err) => {
-                let error_response = ErrorResponse {
-                    status: err.code().to_string(),
-                    message: err.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(err);
 
                 error!(
                     "Failed to retrieve topup (user_id: {user_id}): {}",
@@ -334,6 +446,7 @@ impl TopupServiceTrait for TopupService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to retrieve topup (user_id: {user_id}): {}",
                         error_response.message
@@ -366,13 +479,14 @@ impl TopupServiceTrait for TopupService {
         let mut request = Request::new(FindTopupByUserIdRequest { user_id });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self
-            .client
-            .lock()
-            .await
-            .find_topup_by_user_id(request)
-            .await
-        {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_topup_by_user_id(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -388,10 +502,7 @@ impl TopupServiceTrait for TopupService {
                 Ok(response)
             }
             Err(err) => {
-                let error_response = ErrorResponse {
-                    status: err.code().to_string(),
-                    message: err.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(err);
 
                 error!(
                     "Failed to retrieve topup (user_id: {user_id}): {}",
@@ -401,6 +512,7 @@ impl TopupServiceTrait for TopupService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to retrieve topup (user_id: {user_id}): {}",
                         error_response.message
@@ -443,7 +555,14 @@ impl TopupServiceTrait for TopupService {
         });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.create_topup(request).await {
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().create_topup(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -470,10 +589,7 @@ impl TopupServiceTrait for TopupService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to create topup {} for user_id {}: {}",
@@ -483,6 +599,7 @@ impl TopupServiceTrait for TopupService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to create topup {} for user_id {}: {}",
                         input.topup_no, input.user_id, error_response.message
@@ -500,7 +617,7 @@ impl TopupServiceTrait for TopupService {
         input: &DomainUpdateTopupRequest,
     ) -> Result<ApiResponse<TopupResponse>, ErrorResponse> {
         info!(
-            "Updating topup (topup_id: {}, user_id: {}, topup_amount: {}, topup_method: {})",
+            "Updating topup (topup_id: {}, user_id: {}, topup_amount: {:?}, topup_method: {:?})",
             input.topup_id, input.user_id, input.topup_amount, input.topup_method
         );
 
@@ -517,8 +634,16 @@ impl TopupServiceTrait for TopupService {
                 KeyValue::new("operation", "update"),
                 KeyValue::new("topup.id", topup_id as i64),
                 KeyValue::new("topup.user_id", user_id as i64),
-                KeyValue::new("topup.amount", topup_amount as i64),
-                KeyValue::new("topup.method", topup_method.clone()),
+                KeyValue::new(
+                    "topup.amount",
+                    topup_amount.map_or_else(|| "unchanged".to_string(), |a| a.to_string()),
+                ),
+                KeyValue::new(
+                    "topup.method",
+                    topup_method
+                        .clone()
+                        .unwrap_or_else(|| "unchanged".to_string()),
+                ),
             ],
         );
 
@@ -532,7 +657,14 @@ impl TopupServiceTrait for TopupService {
         let mut request = Request::new(update_request);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.update_topup(request).await {
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().update_topup(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -553,10 +685,7 @@ impl TopupServiceTrait for TopupService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to update topup (ID: {topup_id}, user_id: {user_id}): {}",
@@ -566,6 +695,7 @@ impl TopupServiceTrait for TopupService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to update topup (ID: {topup_id}, user_id: {user_id}): {}",
                         error_response.message
@@ -594,7 +724,14 @@ impl TopupServiceTrait for TopupService {
         let mut request = Request::new(FindTopupByIdRequest { id });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.delete_topup(request).await {
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().delete_topup(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -615,16 +752,14 @@ impl TopupServiceTrait for TopupService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!("Failed to delete topup {}: {}", id, error_response.message);
 
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!("Failed to delete topup {}: {}", id, error_response.message),
                 )
                 .await;
@@ -633,4 +768,259 @@ impl TopupServiceTrait for TopupService {
             }
         }
     }
+
+    async fn confirm_topup(&self, id: i32) -> Result<ApiResponse<TopupResponse>, ErrorResponse> {
+        let method = Method::Post;
+
+        let tracing_ctx = self.start_tracing(
+            "ConfirmTopup",
+            vec![
+                KeyValue::new("component", "topup"),
+                KeyValue::new("operation", "confirm"),
+                KeyValue::new("id", id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(FindTopupByIdRequest { id });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().confirm_topup(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                info!("Topup (id: {id}) confirmed");
+
+                self.complete_tracing_success(&tracing_ctx, method, &response.message)
+                    .await;
+                Ok(response)
+            }
+            Err(err) => {
+                let error_response = ErrorResponse::from(err);
+
+                error!(
+                    "Failed to confirm topup (id: {id}): {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!(
+                        "Failed to confirm topup (id: {id}): {}",
+                        error_response.message
+                    ),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn fail_topup(&self, id: i32) -> Result<ApiResponse<TopupResponse>, ErrorResponse> {
+        let method = Method::Post;
+
+        let tracing_ctx = self.start_tracing(
+            "FailTopup",
+            vec![
+                KeyValue::new("component", "topup"),
+                KeyValue::new("operation", "fail"),
+                KeyValue::new("id", id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(FindTopupByIdRequest { id });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().fail_topup(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                info!("Topup (id: {id}) marked as failed");
+
+                self.complete_tracing_success(&tracing_ctx, method, &response.message)
+                    .await;
+                Ok(response)
+            }
+            Err(err) => {
+                let error_response = ErrorResponse::from(err);
+
+                error!(
+                    "Failed to fail topup (id: {id}): {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!(
+                        "Failed to fail topup (id: {id}): {}",
+                        error_response.message
+                    ),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn refund_topup(&self, id: i32) -> Result<ApiResponse<TopupResponse>, ErrorResponse> {
+        let method = Method::Post;
+
+        let tracing_ctx = self.start_tracing(
+            "RefundTopup",
+            vec![
+                KeyValue::new("component", "topup"),
+                KeyValue::new("operation", "refund"),
+                KeyValue::new("id", id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(FindTopupByIdRequest { id });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().refund_topup(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                info!("Topup (id: {id}) refunded");
+
+                self.complete_tracing_success(&tracing_ctx, method, &response.message)
+                    .await;
+                Ok(response)
+            }
+            Err(err) => {
+                let error_response = ErrorResponse::from(err);
+
+                error!(
+                    "Failed to refund topup (id: {id}): {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!(
+                        "Failed to refund topup (id: {id}): {}",
+                        error_response.message
+                    ),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn get_topup_stats(
+        &self,
+        req: &DomainTopupStatsRequest,
+    ) -> Result<ApiResponse<Vec<TopupMethodStatsResponse>>, ErrorResponse> {
+        let method = Method::Get;
+
+        let start_date = req.start_date.map(|d| d.to_string()).unwrap_or_default();
+        let end_date = req.end_date.map(|d| d.to_string()).unwrap_or_default();
+
+        let tracing_ctx = self.start_tracing(
+            "GetTopupStats",
+            vec![
+                KeyValue::new("component", "topup"),
+                KeyValue::new("operation", "stats"),
+                KeyValue::new("start_date", start_date.clone()),
+                KeyValue::new("end_date", end_date.clone()),
+            ],
+        );
+
+        let mut request = Request::new(TopupStatsRequest {
+            start_date,
+            end_date,
+        });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().topup_stats(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner
+                        .data
+                        .into_iter()
+                        .map(TopupMethodStatsResponse::from)
+                        .collect::<Vec<_>>(),
+                };
+
+                info!(
+                    "Topup stats retrieved for {} method(s)",
+                    response.data.len()
+                );
+
+                self.complete_tracing_success(&tracing_ctx, method, &response.message)
+                    .await;
+                Ok(response)
+            }
+            Err(err) => {
+                let error_response = ErrorResponse::from(err);
+
+                error!("Failed to retrieve topup stats: {}", error_response.message);
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!("Failed to retrieve topup stats: {}", error_response.message),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
 }
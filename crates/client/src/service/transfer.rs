@@ -1,7 +1,8 @@
 use async_trait::async_trait;
 use genproto::transfer::{
-    CreateTransferRequest, FindAllTransferRequest, FindTransferByIdRequest,
-    FindTransferByUserIdRequest, UpdateTransferRequest,
+    CreateTransferRequest, DailyTransferTotalsRequest, FindAllTransferRequest,
+    FindTransferByIdRequest, FindTransferByUserIdRequest, FindTransferByUsersIdRequest,
+    StreamAllTransfersRequest, UpdateTransferRequest,
     transfer_service_client::TransferServiceClient,
 };
 use opentelemetry::{
@@ -15,44 +16,61 @@ use shared::{
     domain::{
         request::{
             CreateTransferRequest as DomainCreateTransferRequest,
-            FindAllTransferRequest as DomainFindAllTransferRequest,
+            DailyTransferTotalsRequest as DomainDailyTransferTotalsRequest,
+            FindAllTransferRequest as DomainFindAllTransferRequest, PageRequest,
+            StreamTransfersRequest as DomainStreamTransfersRequest,
             UpdateTransferRequest as DomainUpdateTransferRequest,
         },
-        response::{ApiResponse, ApiResponsePagination, ErrorResponse, transfer::TransferResponse},
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            transfer::{DailyTransferTotalResponse, TransferResponse},
+        },
     },
     utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
 };
 use std::sync::Arc;
-use tokio::{sync::Mutex, time::Instant};
+use tokio::time::Instant;
 use tonic::{Request, transport::Channel};
 use tracing::{error, info};
 
+use crate::service::{ClientPool, RetryPolicy};
 use shared::abstract_trait::TransferServiceTrait;
 
 #[derive(Debug)]
 pub struct TransferService {
-    client: Arc<Mutex<TransferServiceClient<Channel>>>,
-    metrics: Arc<Mutex<Metrics>>,
+    client: ClientPool<TransferServiceClient<Channel>>,
+    retry_policy: RetryPolicy,
+    metrics: Arc<Metrics>,
 }
 
 impl TransferService {
     pub async fn new(
-        client: Arc<Mutex<TransferServiceClient<Channel>>>,
-        metrics: Arc<Mutex<Metrics>>,
+        client: ClientPool<TransferServiceClient<Channel>>,
+        retry_policy: RetryPolicy,
+        metrics: Arc<Metrics>,
         registry: &mut Registry,
     ) -> Self {
         registry.register(
             "transfer_handler_request_counter",
             "total number of requests to the TransferService",
-            metrics.lock().await.request_counter.clone(),
+            metrics.request_counter.clone(),
         );
         registry.register(
             "transfer_handler_request_duration",
             "Histogram of request durations for the TransferService",
-            metrics.lock().await.request_duration.clone(),
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "transfer_handler_error_counter",
+            "Total number of failed requests to the TransferService by error kind",
+            metrics.error_counter.clone(),
         );
 
-        Self { client, metrics }
+        Self {
+            client,
+            retry_policy,
+            metrics,
+        }
     }
 
     fn get_tracer(&self) -> BoxedTracer {
@@ -60,9 +78,10 @@ impl TransferService {
     }
 
     fn inject_trace_context<T>(&self, cx: &Context, request: &mut Request<T>) {
-        global::get_text_map_propagator(|propagator| {
-            propagator.inject_context(cx, &mut MetadataInjector(request.metadata_mut()))
-        });
+        let mut injector = MetadataInjector(request.metadata_mut());
+        global::get_text_map_propagator(|propagator| propagator.inject_context(cx, &mut injector));
+        injector.inject_request_id();
+        injector.inject_bearer_token();
     }
 
     fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
@@ -85,7 +104,11 @@ impl TransferService {
         );
 
         let cx = Context::current_with_span(span);
-        TracingContext { cx, start_time }
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
     }
 
     async fn complete_tracing_success(
@@ -102,8 +125,12 @@ impl TransferService {
         &self,
         tracing_ctx: &TracingContext,
         method: Method,
+        error_kind: &str,
         error_message: &str,
     ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
         self.complete_tracing_internal(tracing_ctx, method, false, error_message)
             .await;
     }
@@ -138,7 +165,8 @@ impl TransferService {
             error!("Operation failed: {message}");
         }
 
-        self.metrics.lock().await.record(method, status, elapsed);
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
 
         tracing_ctx.cx.span().end();
     }
@@ -172,10 +200,20 @@ impl TransferServiceTrait for TransferService {
             page: req.page,
             page_size: req.page_size,
             search: req.search.clone(),
+            min_amount: req.min_amount,
+            max_amount: req.max_amount,
+            status: req.status.clone(),
         });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.find_all_transfer(request).await {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_all_transfer(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponsePagination {
@@ -195,10 +233,7 @@ impl TransferServiceTrait for TransferService {
                 Ok(response)
             }
             Err(err) => {
-                let error_response = ErrorResponse {
-                    status: err.code().to_string(),
-                    message: err.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(err);
 
                 error!(
                     "Failed to retrieve transfers (page: {}, size: {}): {}",
@@ -208,6 +243,7 @@ impl TransferServiceTrait for TransferService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to retrieve saldos (page: {}, size: {}): {}",
                         req.page, req.page_size, error_response.message
@@ -220,6 +256,99 @@ impl TransferServiceTrait for TransferService {
         }
     }
 
+    async fn get_transfer_batch(
+        &self,
+        req: &DomainStreamTransfersRequest,
+        after_id: i32,
+    ) -> Result<Vec<TransferResponse>, ErrorResponse> {
+        info!("Retrieving transfer batch (after_id: {after_id})");
+
+        let method = Method::Get;
+        let batch_size = req.effective_batch_size();
+
+        let tracing_ctx = self.start_tracing(
+            "GetTransferBatch",
+            vec![
+                KeyValue::new("component", "transfer"),
+                KeyValue::new("operation", "get_transfer_batch"),
+                KeyValue::new("after_id", after_id.to_string()),
+                KeyValue::new("batch_size", batch_size.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(StreamAllTransfersRequest {
+            start_date: req
+                .start_date
+                .map(|date| date.to_string())
+                .unwrap_or_default(),
+            end_date: req
+                .end_date
+                .map(|date| date.to_string())
+                .unwrap_or_default(),
+            batch_size,
+            after_id,
+        });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().stream_all_transfers(request).await
+            })
+            .await;
+
+        // cukup ambil satu batch, stream di-drop setelahnya sehingga server berhenti membaca
+        let mut batch = Vec::with_capacity(batch_size as usize);
+        let outcome = match result {
+            Ok(resp) => {
+                let mut stream = resp.into_inner();
+                loop {
+                    if batch.len() >= batch_size as usize {
+                        break Ok(());
+                    }
+                    match stream.message().await {
+                        Ok(Some(transfer)) => batch.push(TransferResponse::from(transfer)),
+                        Ok(None) => break Ok(()),
+                        Err(status) => break Err(status),
+                    }
+                }
+            }
+            Err(status) => Err(status),
+        };
+
+        match outcome {
+            Ok(()) => {
+                let message = format!("Retrieved {} transfer(s) after id {after_id}", batch.len());
+                info!("{message}");
+
+                self.complete_tracing_success(&tracing_ctx, method, &message)
+                    .await;
+                Ok(batch)
+            }
+            Err(err) => {
+                let error_response = ErrorResponse::from(err);
+
+                error!(
+                    "Failed to retrieve transfer batch (after_id: {after_id}): {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!(
+                        "Failed to retrieve transfer batch (after_id: {after_id}): {}",
+                        error_response.message
+                    ),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
     async fn get_transfer(
         &self,
         id: i32,
@@ -240,7 +369,14 @@ impl TransferServiceTrait for TransferService {
         let mut request = Request::new(FindTransferByIdRequest { id });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.find_transfer_by_id(request).await {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_transfer_by_id(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -256,10 +392,7 @@ impl TransferServiceTrait for TransferService {
                 Ok(response)
             }
             Err(err) => {
-                let error_response = ErrorResponse {
-                    status: err.code().to_string(),
-                    message: err.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(err);
 
                 error!(
                     "Failed to retrieve transfer (id: {id}): {}",
@@ -269,6 +402,7 @@ impl TransferServiceTrait for TransferService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to retrieve transfer (id: {id}): {}",
                         error_response.message
@@ -284,7 +418,8 @@ impl TransferServiceTrait for TransferService {
     async fn get_transfer_users(
         &self,
         user_id: i32,
-    ) -> Result<ApiResponse<Option<Vec<TransferResponse>>>, ErrorResponse> {
+        req: &PageRequest,
+    ) -> Result<ApiResponsePagination<Vec<TransferResponse>>, ErrorResponse> {
         info!("Retrieving transfers (user_id: {user_id})");
 
         let method = Method::Get;
@@ -295,25 +430,33 @@ impl TransferServiceTrait for TransferService {
                 KeyValue::new("component", "transfer"),
                 KeyValue::new("operation", "get_transfer_users"),
                 KeyValue::new("user_id", user_id.to_string()),
+                KeyValue::new("page", req.page.to_string()),
+                KeyValue::new("page_size", req.page_size.to_string()),
             ],
         );
 
-        let mut request = Request::new(FindTransferByUserIdRequest { user_id });
+        let mut request = Request::new(FindTransferByUsersIdRequest {
+            user_id,
+            page: req.page,
+            page_size: req.page_size,
+        });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self
-            .client
-            .lock()
-            .await
-            .find_transfer_by_users_id(request)
-            .await
-        {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_transfer_by_users_id(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
-                let response = ApiResponse {
+                let response = ApiResponsePagination {
                     status: inner.status,
                     message: inner.message,
-                    data: Some(inner.data.into_iter().map(Into::into).collect()),
+                    data: inner.data.into_iter().map(Into::into).collect(),
+                    pagination: inner.pagination.unwrap_or_default().into(),
                 };
 
                 info!(
@@ -326,10 +469,7 @@ impl TransferServiceTrait for TransferService {
                 Ok(response)
             }
             Err(err) => {
-                let error_response = ErrorResponse {
-                    status: err.code().to_string(),
-                    message: err.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(err);
 
                 error!(
                     "Failed to retrieve transfers (user_id: {user_id}): {}",
@@ -339,6 +479,7 @@ impl TransferServiceTrait for TransferService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to retrieve transfers (user_id: {user_id}): {}",
                         error_response.message
@@ -351,6 +492,83 @@ impl TransferServiceTrait for TransferService {
         }
     }
 
+    async fn get_user_transfer_history(
+        &self,
+        user_id: i32,
+        req: &PageRequest,
+    ) -> Result<ApiResponsePagination<Vec<TransferResponse>>, ErrorResponse> {
+        info!("Retrieving transfer history (user_id: {user_id})");
+
+        let method = Method::Get;
+
+        let tracing_ctx = self.start_tracing(
+            "GetUserTransferHistory",
+            vec![
+                KeyValue::new("component", "transfer"),
+                KeyValue::new("operation", "get_user_transfer_history"),
+                KeyValue::new("user_id", user_id.to_string()),
+                KeyValue::new("page", req.page.to_string()),
+                KeyValue::new("page_size", req.page_size.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(FindTransferByUsersIdRequest {
+            user_id,
+            page: req.page,
+            page_size: req.page_size,
+        });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().get_user_transfer_history(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponsePagination {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into_iter().map(Into::into).collect(),
+                    pagination: inner.pagination.unwrap_or_default().into(),
+                };
+
+                info!(
+                    "Retrieved transfer history (user_id: {user_id}): {}",
+                    response.message
+                );
+
+                self.complete_tracing_success(&tracing_ctx, method, &response.message)
+                    .await;
+                Ok(response)
+            }
+            Err(err) => {
+                let error_response = ErrorResponse::from(err);
+
+                error!(
+                    "Failed to retrieve transfer history (user_id: {user_id}): {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!(
+                        "Failed to retrieve transfer history (user_id: {user_id}): {}",
+                        error_response.message
+                    ),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
     async fn get_transfer_user(
         &self,
         user_id: i32,
@@ -371,13 +589,14 @@ impl TransferServiceTrait for TransferService {
         let mut request = Request::new(FindTransferByUserIdRequest { user_id });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self
-            .client
-            .lock()
-            .await
-            .find_transfer_by_user_id(request)
-            .await
-        {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_transfer_by_user_id(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -396,10 +615,7 @@ impl TransferServiceTrait for TransferService {
                 Ok(response)
             }
             Err(err) => {
-                let error_response = ErrorResponse {
-                    status: err.code().to_string(),
-                    message: err.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(err);
 
                 error!(
                     "Failed to retrieve transfer (user_id: {user_id}): {}",
@@ -409,6 +625,7 @@ impl TransferServiceTrait for TransferService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to retrieve transfer (user_id: {user_id}): {}",
                         error_response.message
@@ -446,11 +663,22 @@ impl TransferServiceTrait for TransferService {
             transfer_from: input.transfer_from,
             transfer_to: input.transfer_to,
             transfer_amount: input.transfer_amount,
+            note: input.note.clone().unwrap_or_default(),
+            dry_run: input.dry_run,
+            from_label: input.from_label.clone().unwrap_or_default(),
+            to_label: input.to_label.clone().unwrap_or_default(),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.create_transfer(request).await {
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().create_transfer(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -477,10 +705,7 @@ impl TransferServiceTrait for TransferService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to create transfer from {} to {}: {}",
@@ -490,6 +715,7 @@ impl TransferServiceTrait for TransferService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to create transfer from {} to {}: {}",
                         input.transfer_from, input.transfer_to, error_response.message
@@ -534,12 +760,20 @@ impl TransferServiceTrait for TransferService {
             transfer_from,
             transfer_to,
             transfer_amount,
+            note: input.note.clone().unwrap_or_default(),
         };
 
         let mut request = Request::new(update_request);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.update_transfer(request).await {
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().update_transfer(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -564,10 +798,7 @@ impl TransferServiceTrait for TransferService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to update transfer (ID: {transfer_id}, from: {transfer_from}, to: {transfer_to}): {}",
@@ -576,7 +807,7 @@ impl TransferServiceTrait for TransferService {
 
                 self.complete_tracing_error(
                 &tracing_ctx,
-                method,
+                method, error_response.kind(),
                 &format!(
                     "Failed to update transfer (ID: {transfer_id}, from: {transfer_from}, to: {transfer_to}): {}",
                     error_response.message
@@ -605,7 +836,14 @@ impl TransferServiceTrait for TransferService {
         let mut request = Request::new(FindTransferByIdRequest { id });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.delete_transfer(request).await {
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().delete_transfer(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -626,16 +864,14 @@ impl TransferServiceTrait for TransferService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!("Failed to delete transfer {id}: {}", error_response.message);
 
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!("Failed to delete transfer {id}: {}", error_response.message),
                 )
                 .await;
@@ -644,4 +880,217 @@ impl TransferServiceTrait for TransferService {
             }
         }
     }
+
+    async fn reverse_transfer(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<TransferResponse>, ErrorResponse> {
+        info!("Reversing transfer (id: {id})");
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "ReverseTransfer",
+            vec![
+                KeyValue::new("component", "transfer"),
+                KeyValue::new("operation", "reverse"),
+                KeyValue::new("transfer.id", id as i64),
+            ],
+        );
+
+        let mut request = Request::new(FindTransferByIdRequest { id });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().reverse_transfer(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response: ApiResponse<TransferResponse> = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                info!(
+                    "Transfer {id} reversed by transfer {}",
+                    response.data.transfer_id
+                );
+
+                self.complete_tracing_success(&tracing_ctx, method, &response.message)
+                    .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!(
+                    "Failed to reverse transfer {id}: {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!(
+                        "Failed to reverse transfer {id}: {}",
+                        error_response.message
+                    ),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn approve_transfer(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<TransferResponse>, ErrorResponse> {
+        info!("Approving transfer (id: {id})");
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "ApproveTransfer",
+            vec![
+                KeyValue::new("component", "transfer"),
+                KeyValue::new("operation", "approve"),
+                KeyValue::new("transfer.id", id as i64),
+            ],
+        );
+
+        let mut request = Request::new(FindTransferByIdRequest { id });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().approve_transfer(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response: ApiResponse<TransferResponse> = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                info!("Transfer {id} approved");
+
+                self.complete_tracing_success(&tracing_ctx, method, &response.message)
+                    .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!(
+                    "Failed to approve transfer {id}: {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!(
+                        "Failed to approve transfer {id}: {}",
+                        error_response.message
+                    ),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn get_daily_transfer_totals(
+        &self,
+        req: &DomainDailyTransferTotalsRequest,
+    ) -> Result<ApiResponse<Vec<DailyTransferTotalResponse>>, ErrorResponse> {
+        let method = Method::Get;
+
+        let start_date = req.start_date.to_string();
+        let end_date = req.end_date.to_string();
+
+        let tracing_ctx = self.start_tracing(
+            "GetDailyTransferTotals",
+            vec![
+                KeyValue::new("component", "transfer"),
+                KeyValue::new("operation", "daily_totals"),
+                KeyValue::new("start_date", start_date.clone()),
+                KeyValue::new("end_date", end_date.clone()),
+            ],
+        );
+
+        let mut request = Request::new(DailyTransferTotalsRequest {
+            start_date,
+            end_date,
+        });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().daily_transfer_totals(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner
+                        .data
+                        .into_iter()
+                        .map(DailyTransferTotalResponse::from)
+                        .collect::<Vec<_>>(),
+                };
+
+                info!(
+                    "Daily transfer totals retrieved for {} user-day(s)",
+                    response.data.len()
+                );
+
+                self.complete_tracing_success(&tracing_ctx, method, &response.message)
+                    .await;
+                Ok(response)
+            }
+            Err(err) => {
+                let error_response = ErrorResponse::from(err);
+
+                error!(
+                    "Failed to retrieve daily transfer totals: {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!(
+                        "Failed to retrieve daily transfer totals: {}",
+                        error_response.message
+                    ),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
 }
@@ -1,6 +1,7 @@
 use async_trait::async_trait;
 use genproto::user::{
-    CreateUserRequest, FindAllUserRequest, FindUserByIdRequest, UpdateUserRequest,
+    CreateUserRequest, DeleteUserRequest, FindAllUserRequest, FindUserByIdRequest, ImportUserRow,
+    ImportUsersRequest, PurgeUserRequest, UpdateUserRequest,
     user_service_client::UserServiceClient,
 };
 use opentelemetry::{
@@ -14,44 +15,62 @@ use shared::{
     domain::{
         request::{
             FindAllUserRequest as DomainFindAllUserRequest,
+            ImportUsersRequest as DomainImportUsersRequest,
             RegisterRequest as DomainCreateUserRequest,
             UpdateUserRequest as DomainUpdateUserRequest,
         },
-        response::{ApiResponse, ApiResponsePagination, ErrorResponse, user::UserResponse},
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            user::{
+                ImportUsersResponse, PurgeUserResponse, UserDeletionCheckResponse, UserResponse,
+            },
+        },
     },
     utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
 };
 use std::sync::Arc;
-use tokio::{sync::Mutex, time::Instant};
+use tokio::time::Instant;
 use tonic::{Request, transport::Channel};
 use tracing::{error, info};
 
+use crate::service::{ClientPool, RetryPolicy};
 use shared::abstract_trait::UserServiceTrait;
 
 #[derive(Debug)]
 pub struct UserService {
-    client: Arc<Mutex<UserServiceClient<Channel>>>,
-    metrics: Arc<Mutex<Metrics>>,
+    client: ClientPool<UserServiceClient<Channel>>,
+    retry_policy: RetryPolicy,
+    metrics: Arc<Metrics>,
 }
 
 impl UserService {
     pub async fn new(
-        client: Arc<Mutex<UserServiceClient<Channel>>>,
-        metrics: Arc<Mutex<Metrics>>,
+        client: ClientPool<UserServiceClient<Channel>>,
+        retry_policy: RetryPolicy,
+        metrics: Arc<Metrics>,
         registry: &mut Registry,
     ) -> Self {
         registry.register(
             "User_handler_request_counter",
             "total number of requests to the UserService",
-            metrics.lock().await.request_counter.clone(),
+            metrics.request_counter.clone(),
         );
         registry.register(
             "User_handler_request_duration",
             "Histogram of request durations for the UserService",
-            metrics.lock().await.request_duration.clone(),
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "user_handler_error_counter",
+            "Total number of failed requests to the UserService by error kind",
+            metrics.error_counter.clone(),
         );
 
-        Self { client, metrics }
+        Self {
+            client,
+            retry_policy,
+            metrics,
+        }
     }
 
     fn get_tracer(&self) -> BoxedTracer {
@@ -59,9 +78,10 @@ impl UserService {
     }
 
     fn inject_trace_context<T>(&self, cx: &Context, request: &mut Request<T>) {
-        global::get_text_map_propagator(|propagator| {
-            propagator.inject_context(cx, &mut MetadataInjector(request.metadata_mut()))
-        });
+        let mut injector = MetadataInjector(request.metadata_mut());
+        global::get_text_map_propagator(|propagator| propagator.inject_context(cx, &mut injector));
+        injector.inject_request_id();
+        injector.inject_bearer_token();
     }
 
     fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
@@ -84,7 +104,11 @@ impl UserService {
         );
 
         let cx = Context::current_with_span(span);
-        TracingContext { cx, start_time }
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
     }
 
     async fn complete_tracing_success(
@@ -101,8 +125,12 @@ impl UserService {
         &self,
         tracing_ctx: &TracingContext,
         method: Method,
+        error_kind: &str,
         error_message: &str,
     ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
         self.complete_tracing_internal(tracing_ctx, method, false, error_message)
             .await;
     }
@@ -137,7 +165,8 @@ impl UserService {
             error!("Operation failed: {message}");
         }
 
-        self.metrics.lock().await.record(method, status, elapsed);
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
 
         tracing_ctx.cx.span().end();
     }
@@ -164,6 +193,7 @@ impl UserServiceTrait for UserService {
                 KeyValue::new("page", req.page.to_string()),
                 KeyValue::new("page_size", req.page_size.to_string()),
                 KeyValue::new("search", req.search.clone()),
+                KeyValue::new("include_deleted", req.include_deleted),
             ],
         );
 
@@ -171,10 +201,27 @@ impl UserServiceTrait for UserService {
             page: req.page,
             page_size: req.page_size,
             search: req.search.clone(),
+            include_deleted: req.include_deleted,
+            email: req.email.clone(),
+            created_before: req
+                .created_before
+                .map(|d| d.to_rfc3339())
+                .unwrap_or_default(),
+            created_after: req
+                .created_after
+                .map(|d| d.to_rfc3339())
+                .unwrap_or_default(),
         });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.find_all_users(request).await {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_all_users(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponsePagination {
@@ -194,10 +241,7 @@ impl UserServiceTrait for UserService {
                 Ok(response)
             }
             Err(err) => {
-                let error_response = ErrorResponse {
-                    status: err.code().to_string(),
-                    message: err.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(err);
 
                 error!(
                     "Failed to retrieve users (page: {}, size: {}): {}",
@@ -207,6 +251,7 @@ impl UserServiceTrait for UserService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to retrieve saldos (page: {}, size: {}): {}",
                         req.page, req.page_size, error_response.message
@@ -235,7 +280,14 @@ impl UserServiceTrait for UserService {
         let mut request = Request::new(FindUserByIdRequest { id });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.find_by_id(request).await {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_by_id(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -251,10 +303,7 @@ impl UserServiceTrait for UserService {
                 Ok(response)
             }
             Err(err) => {
-                let error_response = ErrorResponse {
-                    status: err.code().to_string(),
-                    message: err.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(err);
 
                 error!(
                     "Failed to retrieve user (id: {id}): {}",
@@ -264,6 +313,7 @@ impl UserServiceTrait for UserService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to retrieve user (id: {id}): {}",
                         error_response.message
@@ -303,7 +353,14 @@ impl UserServiceTrait for UserService {
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.create_user(request).await {
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().create_user(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -324,10 +381,7 @@ impl UserServiceTrait for UserService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to register user {}: {}",
@@ -337,6 +391,7 @@ impl UserServiceTrait for UserService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to register user {}: {}",
                         input.email, error_response.message
@@ -369,9 +424,9 @@ impl UserServiceTrait for UserService {
 
         let update_request = UpdateUserRequest {
             id: user_id,
-            firstname: input.firstname.clone().unwrap_or_default(),
-            lastname: input.lastname.clone().unwrap_or_default(),
-            email: input.email.clone().unwrap_or_default(),
+            firstname: input.firstname.clone(),
+            lastname: input.lastname.clone(),
+            email: input.email.clone(),
             password: input.password.clone(),
             confirm_password: input.confirm_password.clone(),
         };
@@ -379,7 +434,14 @@ impl UserServiceTrait for UserService {
         let mut request = Request::new(update_request);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.update_user(request).await {
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().update_user(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -400,10 +462,7 @@ impl UserServiceTrait for UserService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to update user (ID: {user_id}): {}",
@@ -413,6 +472,7 @@ impl UserServiceTrait for UserService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to update user (ID: {user_id}): {}",
                         error_response.message
@@ -425,8 +485,78 @@ impl UserServiceTrait for UserService {
         }
     }
 
-    async fn delete_user(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
-        info!("Deleting user (id: {id})");
+    async fn can_delete_user(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<UserDeletionCheckResponse>, ErrorResponse> {
+        info!("Checking whether user can be deleted (id: {id})");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "CanDeleteUser",
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("operation", "can_delete"),
+                KeyValue::new("user.id", id as i64),
+            ],
+        );
+
+        let mut request = Request::new(FindUserByIdRequest { id });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().can_delete_user(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response: ApiResponse<UserDeletionCheckResponse> = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                info!("Deletion check for user {id} completed");
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    &format!("Deletion check for user {id} completed"),
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!(
+                    "Failed to check deletion of user {id}: {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!(
+                        "Failed to check deletion of user {id}: {}",
+                        error_response.message
+                    ),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn delete_user(&self, id: i32, force: bool) -> Result<ApiResponse<()>, ErrorResponse> {
+        info!("Deleting user (id: {id}, force: {force})");
 
         let method = Method::Delete;
         let tracing_ctx = self.start_tracing(
@@ -435,13 +565,21 @@ impl UserServiceTrait for UserService {
                 KeyValue::new("component", "user"),
                 KeyValue::new("operation", "delete"),
                 KeyValue::new("user.id", id as i64),
+                KeyValue::new("user.delete_force", force),
             ],
         );
 
-        let mut request = Request::new(FindUserByIdRequest { id });
+        let mut request = Request::new(DeleteUserRequest { id, force });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.delete_user(request).await {
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().delete_user(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -462,16 +600,14 @@ impl UserServiceTrait for UserService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!("Failed to delete user {id}: {}", error_response.message);
 
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!("Failed to delete user {id}: {}", error_response.message),
                 )
                 .await;
@@ -480,4 +616,325 @@ impl UserServiceTrait for UserService {
             }
         }
     }
+
+    async fn restore_user(&self, id: i32) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        info!("Restoring user (id: {id})");
+
+        let method = Method::Put;
+        let tracing_ctx = self.start_tracing(
+            "RestoreUser",
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("operation", "restore"),
+                KeyValue::new("user.id", id as i64),
+            ],
+        );
+
+        let mut request = Request::new(FindUserByIdRequest { id });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().restore_user(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                info!("User {id} restored successfully");
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    &format!("User {id} restored successfully"),
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!("Failed to restore user {id}: {}", error_response.message);
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!("Failed to restore user {id}: {}", error_response.message),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn freeze_user(&self, id: i32) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        info!("Freezing user (id: {id})");
+
+        let method = Method::Put;
+        let tracing_ctx = self.start_tracing(
+            "FreezeUser",
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("operation", "freeze"),
+                KeyValue::new("user.id", id as i64),
+            ],
+        );
+
+        let mut request = Request::new(FindUserByIdRequest { id });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().freeze_user(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                info!("User {id} frozen successfully");
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    &format!("User {id} frozen successfully"),
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!("Failed to freeze user {id}: {}", error_response.message);
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!("Failed to freeze user {id}: {}", error_response.message),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn unfreeze_user(&self, id: i32) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        info!("Unfreezing user (id: {id})");
+
+        let method = Method::Put;
+        let tracing_ctx = self.start_tracing(
+            "UnfreezeUser",
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("operation", "unfreeze"),
+                KeyValue::new("user.id", id as i64),
+            ],
+        );
+
+        let mut request = Request::new(FindUserByIdRequest { id });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().unfreeze_user(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                info!("User {id} unfrozen successfully");
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    &format!("User {id} unfrozen successfully"),
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!("Failed to unfreeze user {id}: {}", error_response.message);
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!("Failed to unfreeze user {id}: {}", error_response.message),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn purge_user(
+        &self,
+        id: i32,
+        force: bool,
+    ) -> Result<ApiResponse<PurgeUserResponse>, ErrorResponse> {
+        info!("Purging user (id: {id}, force: {force})");
+
+        let method = Method::Delete;
+        let tracing_ctx = self.start_tracing(
+            "PurgeUser",
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("operation", "purge"),
+                KeyValue::new("user.id", id as i64),
+                KeyValue::new("user.purge_force", force),
+            ],
+        );
+
+        let mut request = Request::new(PurgeUserRequest { id, force });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().purge_user(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response: ApiResponse<PurgeUserResponse> = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                info!("User {id} purged successfully");
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    &format!("User {id} purged successfully"),
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!("Failed to purge user {id}: {}", error_response.message);
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!("Failed to purge user {id}: {}", error_response.message),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn import_users(
+        &self,
+        input: &DomainImportUsersRequest,
+    ) -> Result<ApiResponse<ImportUsersResponse>, ErrorResponse> {
+        info!("Importing {} users", input.rows.len());
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "ImportUsers",
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("operation", "import"),
+                KeyValue::new("user.import_rows", input.rows.len() as i64),
+                KeyValue::new("user.import_continue_on_error", input.continue_on_error),
+            ],
+        );
+
+        let mut request = Request::new(ImportUsersRequest {
+            rows: input
+                .rows
+                .iter()
+                .map(|row| ImportUserRow {
+                    name: row.name.clone(),
+                    email: row.email.clone(),
+                    password: row.password.clone(),
+                })
+                .collect(),
+            continue_on_error: input.continue_on_error,
+        });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().import_users(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response: ApiResponse<ImportUsersResponse> = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                info!("{}", response.message);
+
+                self.complete_tracing_success(&tracing_ctx, method, &response.message)
+                    .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!("Failed to import users: {}", error_response.message);
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &format!("Failed to import users: {}", error_response.message),
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
 }
@@ -0,0 +1,384 @@
+use async_trait::async_trait;
+use genproto::virtual_card::{
+    FindVirtualCardsRequest, GenerateVirtualCardRequest, RevokeVirtualCardRequest,
+    virtual_card_service_client::VirtualCardServiceClient,
+};
+use opentelemetry::{
+    Context, KeyValue,
+    global::{self, BoxedTracer},
+    trace::{Span, SpanKind, TraceContextExt, Tracer},
+};
+use prometheus_client::registry::Registry;
+
+use shared::{
+    domain::{
+        request::FindVirtualCardsRequest as SharedFindVirtualCardsRequest,
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            virtual_card::{VirtualCardResponse, VirtualCardSummaryResponse},
+        },
+    },
+    utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+};
+use std::sync::Arc;
+use tokio::time::Instant;
+use tonic::{Request, transport::Channel};
+use tracing::{error, info};
+
+use crate::service::{ClientPool, RetryPolicy};
+use shared::abstract_trait::VirtualCardServiceTrait;
+
+#[derive(Debug)]
+pub struct VirtualCardService {
+    client: ClientPool<VirtualCardServiceClient<Channel>>,
+    retry_policy: RetryPolicy,
+    metrics: Arc<Metrics>,
+}
+
+impl VirtualCardService {
+    pub async fn new(
+        client: ClientPool<VirtualCardServiceClient<Channel>>,
+        retry_policy: RetryPolicy,
+        metrics: Arc<Metrics>,
+        registry: &mut Registry,
+    ) -> Self {
+        registry.register(
+            "virtual_card_handler_request_counter",
+            "Total number of requests to the VirtualCardService",
+            metrics.request_counter.clone(),
+        );
+        registry.register(
+            "virtual_card_handler_request_duration",
+            "Histogram of request durations for the VirtualCardService",
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "virtual_card_handler_error_counter",
+            "Total number of failed requests to the VirtualCardService by error kind",
+            metrics.error_counter.clone(),
+        );
+
+        Self {
+            client,
+            retry_policy,
+            metrics,
+        }
+    }
+
+    fn get_tracer(&self) -> BoxedTracer {
+        global::tracer("virtual-card-service-client")
+    }
+
+    fn inject_trace_context<T>(&self, cx: &Context, request: &mut Request<T>) {
+        let mut injector = MetadataInjector(request.metadata_mut());
+        global::get_text_map_propagator(|propagator| propagator.inject_context(cx, &mut injector));
+        injector.inject_request_id();
+        injector.inject_bearer_token();
+    }
+
+    fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
+        let start_time = Instant::now();
+        let tracer = self.get_tracer();
+        let mut span = tracer
+            .span_builder(operation_name.to_string())
+            .with_kind(SpanKind::Server)
+            .with_attributes(attributes)
+            .start(&tracer);
+
+        info!("Starting operation: {operation_name}");
+
+        span.add_event(
+            "Operation started",
+            vec![
+                KeyValue::new("operation", operation_name.to_string()),
+                KeyValue::new("timestamp", start_time.elapsed().as_secs_f64().to_string()),
+            ],
+        );
+
+        let cx = Context::current_with_span(span);
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
+    }
+
+    async fn complete_tracing_success(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        message: &str,
+    ) {
+        self.complete_tracing_internal(tracing_ctx, method, true, message)
+            .await;
+    }
+
+    async fn complete_tracing_error(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        error_kind: &str,
+        error_message: &str,
+    ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
+        self.complete_tracing_internal(tracing_ctx, method, false, error_message)
+            .await;
+    }
+
+    async fn complete_tracing_internal(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        is_success: bool,
+        message: &str,
+    ) {
+        let status_str = if is_success { "SUCCESS" } else { "ERROR" };
+        let status = if is_success {
+            StatusUtils::Success
+        } else {
+            StatusUtils::Error
+        };
+        let elapsed = tracing_ctx.start_time.elapsed().as_secs_f64();
+
+        tracing_ctx.cx.span().add_event(
+            "Operation completed",
+            vec![
+                KeyValue::new("status", status_str),
+                KeyValue::new("duration_secs", elapsed.to_string()),
+                KeyValue::new("message", message.to_string()),
+            ],
+        );
+
+        if is_success {
+            info!("Operation completed successfully: {message}");
+        } else {
+            error!("Operation failed: {message}");
+        }
+
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
+
+        tracing_ctx.cx.span().end();
+    }
+}
+
+#[async_trait]
+impl VirtualCardServiceTrait for VirtualCardService {
+    async fn generate_virtual_card(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<VirtualCardResponse>, ErrorResponse> {
+        info!("Generate virtual card request for user_id: {user_id}");
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "GenerateVirtualCard",
+            vec![
+                KeyValue::new("component", "virtual_card"),
+                KeyValue::new("operation", "generate"),
+                KeyValue::new("virtual_card.user_id", user_id as i64),
+            ],
+        );
+
+        let mut request = Request::new(GenerateVirtualCardRequest { user_id });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().generate_virtual_card(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response: ApiResponse<VirtualCardResponse> = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                info!("Virtual card for user_id {user_id} generated successfully");
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    &format!("Virtual card for user_id {user_id} generated successfully"),
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!(
+                    "Failed to generate virtual card for user_id {user_id}: {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &error_response.message,
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn list_virtual_cards(
+        &self,
+        user_id: i32,
+        req: &SharedFindVirtualCardsRequest,
+    ) -> Result<ApiResponsePagination<Vec<VirtualCardSummaryResponse>>, ErrorResponse> {
+        info!("List virtual cards request for user_id: {user_id}");
+
+        let method = Method::Get;
+        let tracing_ctx = self.start_tracing(
+            "ListVirtualCards",
+            vec![
+                KeyValue::new("component", "virtual_card"),
+                KeyValue::new("operation", "list"),
+                KeyValue::new("virtual_card.user_id", user_id as i64),
+                KeyValue::new("page", req.page.to_string()),
+                KeyValue::new("page_size", req.page_size.to_string()),
+                KeyValue::new("include_revoked", req.include_revoked),
+            ],
+        );
+
+        let mut request = Request::new(FindVirtualCardsRequest {
+            user_id,
+            page: req.page,
+            page_size: req.page_size,
+            include_revoked: req.include_revoked,
+        });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().list_virtual_cards(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response = ApiResponsePagination {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into_iter().map(Into::into).collect(),
+                    pagination: inner.pagination.unwrap_or_default().into(),
+                };
+
+                info!("Virtual cards for user_id {user_id} retrieved successfully");
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    &format!("Virtual cards for user_id {user_id} retrieved successfully"),
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!(
+                    "Failed to list virtual cards for user_id {user_id}: {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &error_response.message,
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+
+    async fn revoke_virtual_card(
+        &self,
+        user_id: i32,
+        virtual_card_id: i32,
+    ) -> Result<ApiResponse<VirtualCardSummaryResponse>, ErrorResponse> {
+        info!("Revoke virtual card {virtual_card_id} request for user_id: {user_id}");
+
+        let method = Method::Post;
+        let tracing_ctx = self.start_tracing(
+            "RevokeVirtualCard",
+            vec![
+                KeyValue::new("component", "virtual_card"),
+                KeyValue::new("operation", "revoke"),
+                KeyValue::new("virtual_card.user_id", user_id as i64),
+                KeyValue::new("virtual_card.id", virtual_card_id as i64),
+            ],
+        );
+
+        let mut request = Request::new(RevokeVirtualCardRequest {
+            user_id,
+            virtual_card_id,
+        });
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().revoke_virtual_card(request).await
+            })
+            .await;
+
+        match result {
+            Ok(resp) => {
+                let inner = resp.into_inner();
+                let response: ApiResponse<VirtualCardSummaryResponse> = ApiResponse {
+                    status: inner.status,
+                    message: inner.message,
+                    data: inner.data.into(),
+                };
+
+                info!("Virtual card {virtual_card_id} revoked successfully");
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    &format!("Virtual card {virtual_card_id} revoked successfully"),
+                )
+                .await;
+
+                Ok(response)
+            }
+            Err(status) => {
+                let error_response = ErrorResponse::from(status);
+
+                error!(
+                    "Failed to revoke virtual card {virtual_card_id}: {}",
+                    error_response.message
+                );
+
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    error_response.kind(),
+                    &error_response.message,
+                )
+                .await;
+
+                Err(error_response)
+            }
+        }
+    }
+}
@@ -1,7 +1,7 @@
 use async_trait::async_trait;
 use genproto::withdraw::{
     CreateWithdrawRequest, FindAllWithdrawRequest, FindWithdrawByIdRequest,
-    FindWithdrawByUserIdRequest, UpdateWithdrawRequest,
+    FindWithdrawByUserIdRequest, FindWithdrawByUsersIdRequest, UpdateWithdrawRequest,
     withdraw_service_client::WithdrawServiceClient,
 };
 use opentelemetry::{
@@ -15,7 +15,7 @@ use shared::{
     domain::{
         request::{
             CreateWithdrawRequest as DomainCreateWithdrawRequest,
-            FindAllWithdrawRequest as DomainFindAllWithdrawRequest,
+            FindAllWithdrawRequest as DomainFindAllWithdrawRequest, PageRequest,
             UpdateWithdrawRequest as DomainUpdateWithdrawRequest,
         },
         response::{ApiResponse, ApiResponsePagination, ErrorResponse, withdraw::WithdrawResponse},
@@ -23,36 +23,48 @@ use shared::{
     utils::{MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
 };
 use std::sync::Arc;
-use tokio::{sync::Mutex, time::Instant};
+use tokio::time::Instant;
 use tonic::{Request, transport::Channel};
 use tracing::{error, info};
 
+use crate::service::{ClientPool, RetryPolicy};
 use shared::abstract_trait::WithdrawServiceTrait;
 
 #[derive(Debug)]
 pub struct WithdrawService {
-    client: Arc<Mutex<WithdrawServiceClient<Channel>>>,
-    metrics: Arc<Mutex<Metrics>>,
+    client: ClientPool<WithdrawServiceClient<Channel>>,
+    retry_policy: RetryPolicy,
+    metrics: Arc<Metrics>,
 }
 
 impl WithdrawService {
     pub async fn new(
-        client: Arc<Mutex<WithdrawServiceClient<Channel>>>,
-        metrics: Arc<Mutex<Metrics>>,
+        client: ClientPool<WithdrawServiceClient<Channel>>,
+        retry_policy: RetryPolicy,
+        metrics: Arc<Metrics>,
         registry: &mut Registry,
     ) -> Self {
         registry.register(
             "withdraw_handler_request_counter",
             "total number of requests to the WithdrawService",
-            metrics.lock().await.request_counter.clone(),
+            metrics.request_counter.clone(),
         );
         registry.register(
             "withdraw_handler_request_duration",
             "Histogram of request durations for the WithdrawService",
-            metrics.lock().await.request_duration.clone(),
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "withdraw_handler_error_counter",
+            "Total number of failed requests to the WithdrawService by error kind",
+            metrics.error_counter.clone(),
         );
 
-        Self { client, metrics }
+        Self {
+            client,
+            retry_policy,
+            metrics,
+        }
     }
 
     fn get_tracer(&self) -> BoxedTracer {
@@ -60,9 +72,10 @@ impl WithdrawService {
     }
 
     fn inject_trace_context<T>(&self, cx: &Context, request: &mut Request<T>) {
-        global::get_text_map_propagator(|propagator| {
-            propagator.inject_context(cx, &mut MetadataInjector(request.metadata_mut()))
-        });
+        let mut injector = MetadataInjector(request.metadata_mut());
+        global::get_text_map_propagator(|propagator| propagator.inject_context(cx, &mut injector));
+        injector.inject_request_id();
+        injector.inject_bearer_token();
     }
 
     fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
@@ -85,7 +98,11 @@ impl WithdrawService {
         );
 
         let cx = Context::current_with_span(span);
-        TracingContext { cx, start_time }
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
     }
 
     async fn complete_tracing_success(
@@ -102,8 +119,12 @@ impl WithdrawService {
         &self,
         tracing_ctx: &TracingContext,
         method: Method,
+        error_kind: &str,
         error_message: &str,
     ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
         self.complete_tracing_internal(tracing_ctx, method, false, error_message)
             .await;
     }
@@ -138,7 +159,8 @@ impl WithdrawService {
             error!("Operation failed: {message}");
         }
 
-        self.metrics.lock().await.record(method, status, elapsed);
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
 
         tracing_ctx.cx.span().end();
     }
@@ -174,7 +196,14 @@ impl WithdrawServiceTrait for WithdrawService {
         });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.find_all_withdraw(request).await {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_all_withdraw(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponsePagination {
@@ -202,10 +231,7 @@ impl WithdrawServiceTrait for WithdrawService {
                 Ok(response)
             }
             Err(err) => {
-                let error_response = ErrorResponse {
-                    status: err.code().to_string(),
-                    message: err.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(err);
 
                 error!(
                     "Failed to retrieve withdraws (page: {}, size: {}): {}",
@@ -215,6 +241,7 @@ impl WithdrawServiceTrait for WithdrawService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to retrieve withdraws (page: {}, size: {}): {}",
                         req.page, req.page_size, error_response.message
@@ -246,7 +273,14 @@ impl WithdrawServiceTrait for WithdrawService {
         let mut request = Request::new(FindWithdrawByIdRequest { id });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.find_withdraw_by_id(request).await {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_withdraw_by_id(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -267,16 +301,14 @@ impl WithdrawServiceTrait for WithdrawService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!("Failed to find withdraw {id}: {}", error_response.message);
 
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!("Failed to find withdraw {id}: {}", error_response.message),
                 )
                 .await;
@@ -289,7 +321,8 @@ impl WithdrawServiceTrait for WithdrawService {
     async fn get_withdraw_users(
         &self,
         id: i32,
-    ) -> Result<ApiResponse<Option<Vec<WithdrawResponse>>>, ErrorResponse> {
+        req: &PageRequest,
+    ) -> Result<ApiResponsePagination<Vec<WithdrawResponse>>, ErrorResponse> {
         info!("Getting withdraws for user {id}");
 
         let method = Method::Get;
@@ -299,25 +332,33 @@ impl WithdrawServiceTrait for WithdrawService {
                 KeyValue::new("component", "withdraw"),
                 KeyValue::new("operation", "get_withdraw_users"),
                 KeyValue::new("user.id", id as i64),
+                KeyValue::new("page", req.page.to_string()),
+                KeyValue::new("page_size", req.page_size.to_string()),
             ],
         );
 
-        let mut request = Request::new(FindWithdrawByUserIdRequest { user_id: id });
+        let mut request = Request::new(FindWithdrawByUsersIdRequest {
+            user_id: id,
+            page: req.page,
+            page_size: req.page_size,
+        });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self
-            .client
-            .lock()
-            .await
-            .find_withdraw_by_users_id(request)
-            .await
-        {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_withdraw_by_users_id(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
-                let response = ApiResponse {
+                let response = ApiResponsePagination {
                     status: inner.status,
                     message: inner.message,
-                    data: Some(inner.data.into_iter().map(Into::into).collect()),
+                    data: inner.data.into_iter().map(Into::into).collect(),
+                    pagination: inner.pagination.unwrap_or_default().into(),
                 };
 
                 info!("Withdraws for user {id} retrieved successfully");
@@ -332,10 +373,7 @@ impl WithdrawServiceTrait for WithdrawService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to retrieve withdraws for user {id}: {}",
@@ -345,6 +383,7 @@ impl WithdrawServiceTrait for WithdrawService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to retrieve withdraws for user {id}: {}",
                         error_response.message
@@ -376,13 +415,14 @@ impl WithdrawServiceTrait for WithdrawService {
         let mut request = Request::new(FindWithdrawByUserIdRequest { user_id: id });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self
-            .client
-            .lock()
-            .await
-            .find_withdraw_by_user_id(request)
-            .await
-        {
+        let result = self
+            .retry_policy
+            .run(request, |request| async move {
+                self.client.get().find_withdraw_by_user_id(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -403,10 +443,7 @@ impl WithdrawServiceTrait for WithdrawService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to find withdraw for user {id}: {}",
@@ -416,6 +453,7 @@ impl WithdrawServiceTrait for WithdrawService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to find withdraw for user {id}: {}",
                         error_response.message
@@ -454,7 +492,14 @@ impl WithdrawServiceTrait for WithdrawService {
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.create_withdraw(request).await {
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().create_withdraw(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -481,10 +526,7 @@ impl WithdrawServiceTrait for WithdrawService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to create withdraw for user_id {}: {}",
@@ -494,6 +536,7 @@ impl WithdrawServiceTrait for WithdrawService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to create withdraw for user_id {}: {}",
                         input.user_id, error_response.message
@@ -540,7 +583,14 @@ impl WithdrawServiceTrait for WithdrawService {
         let mut request = Request::new(update_request);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.update_withdraw(request).await {
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().update_withdraw(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -563,10 +613,7 @@ impl WithdrawServiceTrait for WithdrawService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!(
                     "Failed to update withdraw (ID: {withdraw_id}, user_id: {user_id}): {}",
@@ -576,6 +623,7 @@ impl WithdrawServiceTrait for WithdrawService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!(
                         "Failed to update withdraw (ID: {withdraw_id}, user_id: {user_id}): {}",
                         error_response.message
@@ -604,7 +652,14 @@ impl WithdrawServiceTrait for WithdrawService {
         let mut request = Request::new(FindWithdrawByIdRequest { id });
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.client.lock().await.delete_withdraw(request).await {
+        let result = self
+            .retry_policy
+            .run_write(request, |request| async move {
+                self.client.get().delete_withdraw(request).await
+            })
+            .await;
+
+        match result {
             Ok(resp) => {
                 let inner = resp.into_inner();
                 let response = ApiResponse {
@@ -625,16 +680,14 @@ impl WithdrawServiceTrait for WithdrawService {
                 Ok(response)
             }
             Err(status) => {
-                let error_response = ErrorResponse {
-                    status: status.code().to_string(),
-                    message: status.message().to_string(),
-                };
+                let error_response = ErrorResponse::from(status);
 
                 error!("Failed to delete withdraw {id}: {}", error_response.message);
 
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    error_response.kind(),
                     &format!("Failed to delete withdraw {id}: {}", error_response.message),
                 )
                 .await;
@@ -1,45 +1,76 @@
-use crate::{di::DependenciesInject, service::GrpcClients};
+use crate::{
+    di::DependenciesInject,
+    service::{GrpcClients, GrpcMessageSize, RetryPolicy},
+};
 use anyhow::{Context, Result};
 use prometheus_client::registry::Registry;
 use shared::{
     abstract_trait::DynJwtService,
-    config::JwtConfig,
-    utils::{Metrics, SystemMetrics, run_metrics_collector},
+    config::{Config, GrpcKeepaliveConfig},
+    utils::{ConcurrencyLimit, Metrics, SystemMetrics, run_metrics_collector},
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::sync::Mutex;
-use tonic::transport::Channel;
+use tonic::transport::{Channel, Endpoint};
 
 #[derive(Debug)]
 pub struct AppState {
     pub jwt_config: DynJwtService,
+    pub grpc_endpoint: Endpoint,
+    pub admin_user_ids: Vec<i32>,
     pub di_container: DependenciesInject,
     pub registry: Arc<Mutex<Registry>>,
-    pub metrics: Arc<Mutex<Metrics>>,
+    pub metrics: Arc<Metrics>,
     pub system_metrics: Arc<SystemMetrics>,
+    pub concurrency_limit: ConcurrencyLimit,
+    pub api_prefix: String,
+    pub request_timeout: Duration,
+    pub cors_allowed_origins: Vec<String>,
+    pub compression_min_size: u16,
 }
 
 impl AppState {
-    pub async fn new(jwt_secret: &str) -> Result<Self> {
-        let jwt_config = Arc::new(JwtConfig::new(jwt_secret)) as DynJwtService;
+    pub async fn new(config: &Config) -> Result<Self> {
+        let jwt_config = Arc::new(config.jwt_config()) as DynJwtService;
         let registry = Arc::new(Mutex::new(Registry::default()));
-        let metrics = Arc::new(Mutex::new(Metrics::new()));
+        let metrics = Arc::new(Metrics::new(config.metrics_buckets.clone()));
         let system_metrics = Arc::new(SystemMetrics::new());
 
         registry.lock().await.register_metrics(&system_metrics);
 
         tokio::spawn(run_metrics_collector(system_metrics.clone()));
 
-        let channel = Channel::from_static("http://payment-server:50051")
-            .connect()
-            .await
-            .context("gRPC connection to payment-server:50051 failed")?;
+        let grpc_endpoint = with_keepalive(
+            Channel::from_static("http://payment-server:50051")
+                .connect_timeout(Duration::from_secs(config.grpc_connect_timeout_secs)),
+            config.grpc_keepalive,
+        );
+
+        let request_timeout = Duration::from_secs(config.grpc_request_timeout_secs);
+
+        // request gateway adalah pesan yang didecode server, response adalah yang diencode server
+        let message_size = GrpcMessageSize {
+            max_encoding: config.grpc_max_decoding_size,
+            max_decoding: config.grpc_max_encoding_size,
+        };
 
-        let clients = GrpcClients::init(channel).await;
+        let clients = GrpcClients::init(
+            &grpc_endpoint,
+            config.grpc_pool_size,
+            request_timeout,
+            message_size,
+        )
+        .await;
+
+        let retry_policy = RetryPolicy {
+            max_retries: config.grpc_max_retries,
+            initial_backoff: Duration::from_millis(config.grpc_retry_initial_backoff_ms),
+            max_backoff: Duration::from_millis(config.grpc_retry_max_backoff_ms),
+        };
 
         let di_container = {
             let mut registry = registry.lock().await;
-            DependenciesInject::new(clients, metrics.clone(), &mut registry)
+            DependenciesInject::new(clients, retry_policy, metrics.clone(), &mut registry)
                 .await
                 .context("Failed to initialize dependency injection container")?
         };
@@ -47,11 +78,30 @@ impl AppState {
         Ok(Self {
             registry,
             jwt_config,
+            grpc_endpoint,
+            admin_user_ids: config.admin_user_ids.clone(),
             metrics,
             di_container,
             system_metrics,
+            concurrency_limit: config.concurrency_limit(),
+            api_prefix: config.api_prefix.clone(),
+            request_timeout,
+            cors_allowed_origins: config.cors_allowed_origins.clone(),
+            compression_min_size: config.compression_min_size,
         })
     }
+
+    pub fn is_admin(&self, user_id: i32) -> bool {
+        self.admin_user_ids.contains(&user_id)
+    }
+}
+
+// ping HTTP/2 menjaga channel idle ke server tetap hidup melewati load balancer
+fn with_keepalive(endpoint: Endpoint, keepalive: GrpcKeepaliveConfig) -> Endpoint {
+    endpoint
+        .http2_keep_alive_interval(keepalive.interval)
+        .keep_alive_timeout(keepalive.timeout)
+        .keep_alive_while_idle(keepalive.while_idle)
 }
 
 trait MetricsRegister {
@@ -63,3 +113,101 @@ impl MetricsRegister for Registry {
         metrics.register(self);
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::{
+        io::{AsyncReadExt, AsyncWriteExt},
+        net::{TcpListener, TcpStream},
+    };
+    use tonic::transport::{Server, server::TcpIncoming};
+    use tonic_health::pb::{HealthCheckRequest, health_client::HealthClient};
+
+    // meniru load balancer: koneksi tanpa byte lewat selama `idle` diputus diam-diam
+    async fn pipe_until_idle(mut client: TcpStream, mut server: TcpStream, idle: Duration) {
+        let (mut client_read, mut client_write) = client.split();
+        let (mut server_read, mut server_write) = server.split();
+        let (mut upstream, mut downstream) = ([0u8; 8192], [0u8; 8192]);
+
+        loop {
+            tokio::select! {
+                read = client_read.read(&mut upstream) => match read {
+                    Ok(n) if n > 0 => {
+                        if server_write.write_all(&upstream[..n]).await.is_err() {
+                            return;
+                        }
+                    }
+                    _ => return,
+                },
+                read = server_read.read(&mut downstream) => match read {
+                    Ok(n) if n > 0 => {
+                        if client_write.write_all(&downstream[..n]).await.is_err() {
+                            return;
+                        }
+                    }
+                    _ => return,
+                },
+                _ = tokio::time::sleep(idle) => return,
+            }
+        }
+    }
+
+    async fn idle_dropping_proxy(
+        upstream: std::net::SocketAddr,
+        idle: Duration,
+        connections: Arc<AtomicUsize>,
+    ) -> std::net::SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+
+        tokio::spawn(async move {
+            while let Ok((client, _)) = listener.accept().await {
+                connections.fetch_add(1, Ordering::SeqCst);
+                let server = TcpStream::connect(upstream).await.unwrap();
+                tokio::spawn(pipe_until_idle(client, server, idle));
+            }
+        });
+
+        addr
+    }
+
+    #[tokio::test]
+    async fn idle_channel_survives_an_intermediary_idle_timeout() {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let server_addr = listener.local_addr().unwrap();
+        let (_, health_service) = tonic_health::server::health_reporter();
+        tokio::spawn(
+            Server::builder()
+                .add_service(health_service)
+                .serve_with_incoming(TcpIncoming::from(listener)),
+        );
+
+        let idle_timeout = Duration::from_millis(300);
+        let connections = Arc::new(AtomicUsize::new(0));
+        let proxy_addr = idle_dropping_proxy(server_addr, idle_timeout, connections.clone()).await;
+
+        let keepalive = GrpcKeepaliveConfig {
+            interval: Duration::from_millis(100),
+            timeout: Duration::from_secs(1),
+            while_idle: true,
+        };
+        let channel = with_keepalive(
+            Endpoint::from_shared(format!("http://{proxy_addr}")).unwrap(),
+            keepalive,
+        )
+        .connect()
+        .await
+        .unwrap();
+        let mut client = HealthClient::new(channel);
+
+        client.check(HealthCheckRequest::default()).await.unwrap();
+        // jauh melewati idle timeout perantara tanpa request apa pun
+        tokio::time::sleep(idle_timeout * 4).await;
+        client.check(HealthCheckRequest::default()).await.unwrap();
+
+        // ping menjaga koneksi pertama tetap terbuka, tidak ada koneksi ulang
+        assert_eq!(connections.load(Ordering::SeqCst), 1);
+    }
+}
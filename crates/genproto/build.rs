@@ -1,5 +1,7 @@
 use std::fs;
 
+const SERDE_ATTRIBUTES: &str = "#[derive(serde::Serialize, serde::Deserialize)]\n#[serde(default)]";
+
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     let out_dir = "src/gen";
 
@@ -8,6 +10,9 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     tonic_prost_build::configure()
         .build_server(true)
         .out_dir(out_dir)
+        // message saldo dan api ikut bisa di-(de)serialize JSON untuk facade transcoding server
+        .type_attribute(".api", SERDE_ATTRIBUTES)
+        .type_attribute(".saldo", SERDE_ATTRIBUTES)
         .compile_protos(
             &[
                 "../../proto/user.proto",
@@ -16,6 +21,10 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
                 "../../proto/topup.proto",
                 "../../proto/transfer.proto",
                 "../../proto/withdraw.proto",
+                "../../proto/virtual_card.proto",
+                "../../proto/webhook.proto",
+                "../../proto/account.proto",
+                "../../proto/system.proto",
             ],
             &["../../proto"],
         )?;
@@ -1,4 +1,6 @@
 // This file is @generated by prost-build.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct Pagination {
     #[prost(int32, tag = "1")]
@@ -9,7 +11,13 @@ pub struct Pagination {
     pub total_items: i64,
     #[prost(int32, tag = "4")]
     pub total_pages: i32,
+    #[prost(bool, tag = "5")]
+    pub has_next: bool,
+    #[prost(bool, tag = "6")]
+    pub has_prev: bool,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ApiResponseEmpty {
     #[prost(string, tag = "1")]
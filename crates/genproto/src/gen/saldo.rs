@@ -1,4 +1,6 @@
 // This file is @generated by prost-build.
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct FindAllSaldoRequest {
     #[prost(int32, tag = "1")]
@@ -8,16 +10,33 @@ pub struct FindAllSaldoRequest {
     #[prost(string, tag = "3")]
     pub search: ::prost::alloc::string::String,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct FindSaldoByIdRequest {
     #[prost(int32, tag = "1")]
     pub id: i32,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct FindSaldoByUserIdRequest {
     #[prost(int32, tag = "1")]
     pub user_id: i32,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct FindSaldoByUsersIdRequest {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(int32, tag = "2")]
+    pub page: i32,
+    #[prost(int32, tag = "3")]
+    pub page_size: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct SaldoResponse {
     #[prost(int32, tag = "1")]
@@ -34,14 +53,22 @@ pub struct SaldoResponse {
     pub created_at: ::prost::alloc::string::String,
     #[prost(string, tag = "7")]
     pub updated_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub label: ::prost::alloc::string::String,
 }
-#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct CreateSaldoRequest {
     #[prost(int32, tag = "1")]
     pub user_id: i32,
     #[prost(int32, tag = "2")]
     pub total_balance: i32,
+    #[prost(string, tag = "3")]
+    pub label: ::prost::alloc::string::String,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct UpdateSaldoRequest {
     #[prost(int32, tag = "1")]
@@ -51,6 +78,8 @@ pub struct UpdateSaldoRequest {
     #[prost(int32, tag = "3")]
     pub total_balance: i32,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ApiResponseSaldoResponse {
     #[prost(string, tag = "1")]
@@ -60,6 +89,8 @@ pub struct ApiResponseSaldoResponse {
     #[prost(message, optional, tag = "3")]
     pub data: ::core::option::Option<SaldoResponse>,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ApiResponsesSaldoResponse {
     #[prost(string, tag = "1")]
@@ -69,6 +100,89 @@ pub struct ApiResponsesSaldoResponse {
     #[prost(message, repeated, tag = "3")]
     pub data: ::prost::alloc::vec::Vec<SaldoResponse>,
 }
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct TopSaldosRequest {
+    #[prost(int32, tag = "1")]
+    pub limit: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct SaldoRankingResponse {
+    #[prost(int32, tag = "1")]
+    pub rank: i32,
+    #[prost(int32, tag = "2")]
+    pub saldo_id: i32,
+    #[prost(int32, tag = "3")]
+    pub user_id: i32,
+    #[prost(string, tag = "4")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "5")]
+    pub label: ::prost::alloc::string::String,
+    #[prost(int32, tag = "6")]
+    pub total_balance: i32,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct AdjustBalanceRequest {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(int32, tag = "2")]
+    pub delta: i32,
+    #[prost(string, tag = "3")]
+    pub reason: ::prost::alloc::string::String,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ReconcileBalanceRequest {
+    #[prost(int32, optional, tag = "1")]
+    pub user_id: ::core::option::Option<i32>,
+    #[prost(bool, tag = "2")]
+    pub fix: bool,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct BalanceReconciliationResponse {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(int64, tag = "2")]
+    pub stored_balance: i64,
+    #[prost(int64, tag = "3")]
+    pub expected_balance: i64,
+    #[prost(int64, tag = "4")]
+    pub difference: i64,
+    #[prost(bool, tag = "5")]
+    pub corrected: bool,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApiResponseBalanceReconciliation {
+    #[prost(string, tag = "1")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub data: ::prost::alloc::vec::Vec<BalanceReconciliationResponse>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApiResponseTopSaldos {
+    #[prost(string, tag = "1")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub data: ::prost::alloc::vec::Vec<SaldoRankingResponse>,
+}
+#[derive(serde::Serialize, serde::Deserialize)]
+#[serde(default)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ApiResponsesSaldoPaginated {
     #[prost(string, tag = "1")]
@@ -245,9 +359,9 @@ pub mod saldo_service_client {
         }
         pub async fn find_saldo_by_users_id(
             &mut self,
-            request: impl tonic::IntoRequest<super::FindSaldoByUserIdRequest>,
+            request: impl tonic::IntoRequest<super::FindSaldoByUsersIdRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::ApiResponsesSaldoResponse>,
+            tonic::Response<super::ApiResponsesSaldoPaginated>,
             tonic::Status,
         > {
             self.inner
@@ -339,6 +453,78 @@ pub mod saldo_service_client {
                 .insert(GrpcMethod::new("saldo.SaldoService", "DeleteSaldo"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn top_saldos(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TopSaldosRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTopSaldos>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/saldo.SaldoService/TopSaldos",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("saldo.SaldoService", "TopSaldos"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn adjust_balance(
+            &mut self,
+            request: impl tonic::IntoRequest<super::AdjustBalanceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseSaldoResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/saldo.SaldoService/AdjustBalance",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("saldo.SaldoService", "AdjustBalance"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn reconcile_balance(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ReconcileBalanceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseBalanceReconciliation>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/saldo.SaldoService/ReconcileBalance",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("saldo.SaldoService", "ReconcileBalance"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -377,9 +563,9 @@ pub mod saldo_service_server {
         >;
         async fn find_saldo_by_users_id(
             &self,
-            request: tonic::Request<super::FindSaldoByUserIdRequest>,
+            request: tonic::Request<super::FindSaldoByUsersIdRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::ApiResponsesSaldoResponse>,
+            tonic::Response<super::ApiResponsesSaldoPaginated>,
             tonic::Status,
         >;
         async fn create_saldo(
@@ -403,6 +589,27 @@ pub mod saldo_service_server {
             tonic::Response<super::super::api::ApiResponseEmpty>,
             tonic::Status,
         >;
+        async fn top_saldos(
+            &self,
+            request: tonic::Request<super::TopSaldosRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTopSaldos>,
+            tonic::Status,
+        >;
+        async fn adjust_balance(
+            &self,
+            request: tonic::Request<super::AdjustBalanceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseSaldoResponse>,
+            tonic::Status,
+        >;
+        async fn reconcile_balance(
+            &self,
+            request: tonic::Request<super::ReconcileBalanceRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseBalanceReconciliation>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct SaldoServiceServer<T> {
@@ -621,16 +828,16 @@ pub mod saldo_service_server {
                     struct FindSaldoByUsersIdSvc<T: SaldoService>(pub Arc<T>);
                     impl<
                         T: SaldoService,
-                    > tonic::server::UnaryService<super::FindSaldoByUserIdRequest>
+                    > tonic::server::UnaryService<super::FindSaldoByUsersIdRequest>
                     for FindSaldoByUsersIdSvc<T> {
-                        type Response = super::ApiResponsesSaldoResponse;
+                        type Response = super::ApiResponsesSaldoPaginated;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::FindSaldoByUserIdRequest>,
+                            request: tonic::Request<super::FindSaldoByUsersIdRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
@@ -797,6 +1004,142 @@ pub mod saldo_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/saldo.SaldoService/TopSaldos" => {
+                    #[allow(non_camel_case_types)]
+                    struct TopSaldosSvc<T: SaldoService>(pub Arc<T>);
+                    impl<
+                        T: SaldoService,
+                    > tonic::server::UnaryService<super::TopSaldosRequest>
+                    for TopSaldosSvc<T> {
+                        type Response = super::ApiResponseTopSaldos;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TopSaldosRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SaldoService>::top_saldos(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = TopSaldosSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/saldo.SaldoService/AdjustBalance" => {
+                    #[allow(non_camel_case_types)]
+                    struct AdjustBalanceSvc<T: SaldoService>(pub Arc<T>);
+                    impl<
+                        T: SaldoService,
+                    > tonic::server::UnaryService<super::AdjustBalanceRequest>
+                    for AdjustBalanceSvc<T> {
+                        type Response = super::ApiResponseSaldoResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::AdjustBalanceRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SaldoService>::adjust_balance(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = AdjustBalanceSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/saldo.SaldoService/ReconcileBalance" => {
+                    #[allow(non_camel_case_types)]
+                    struct ReconcileBalanceSvc<T: SaldoService>(pub Arc<T>);
+                    impl<
+                        T: SaldoService,
+                    > tonic::server::UnaryService<super::ReconcileBalanceRequest>
+                    for ReconcileBalanceSvc<T> {
+                        type Response = super::ApiResponseBalanceReconciliation;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ReconcileBalanceRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as SaldoService>::reconcile_balance(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ReconcileBalanceSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(
@@ -13,11 +13,25 @@ pub struct FindTopupByIdRequest {
     #[prost(int32, tag = "1")]
     pub id: i32,
 }
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct FindTopupByNoRequest {
+    #[prost(string, tag = "1")]
+    pub topup_no: ::prost::alloc::string::String,
+}
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct FindTopupByUserIdRequest {
     #[prost(int32, tag = "1")]
     pub user_id: i32,
 }
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct FindTopupByUsersIdRequest {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(int32, tag = "2")]
+    pub page: i32,
+    #[prost(int32, tag = "3")]
+    pub page_size: i32,
+}
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct CreateTopupRequest {
     #[prost(int32, tag = "1")]
@@ -35,10 +49,26 @@ pub struct UpdateTopupRequest {
     pub topup_id: i32,
     #[prost(int32, tag = "2")]
     pub user_id: i32,
-    #[prost(int32, tag = "3")]
-    pub topup_amount: i32,
-    #[prost(string, tag = "4")]
+    #[prost(int32, optional, tag = "3")]
+    pub topup_amount: ::core::option::Option<i32>,
+    #[prost(string, optional, tag = "4")]
+    pub topup_method: ::core::option::Option<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct TopupStatsRequest {
+    #[prost(string, tag = "1")]
+    pub start_date: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub end_date: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct TopupMethodStats {
+    #[prost(string, tag = "1")]
     pub topup_method: ::prost::alloc::string::String,
+    #[prost(int64, tag = "2")]
+    pub total_count: i64,
+    #[prost(int64, tag = "3")]
+    pub total_amount: i64,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct TopupResponse {
@@ -58,6 +88,8 @@ pub struct TopupResponse {
     pub created_at: ::prost::alloc::string::String,
     #[prost(string, tag = "8")]
     pub updated_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub status: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ApiResponseTopupResponse {
@@ -88,6 +120,15 @@ pub struct ApiResponsesTopupPaginated {
     #[prost(message, optional, tag = "4")]
     pub pagination: ::core::option::Option<super::api::Pagination>,
 }
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApiResponseTopupStats {
+    #[prost(string, tag = "1")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub data: ::prost::alloc::vec::Vec<TopupMethodStats>,
+}
 /// Generated client implementations.
 pub mod topup_service_client {
     #![allow(
@@ -227,6 +268,30 @@ pub mod topup_service_client {
                 .insert(GrpcMethod::new("topup.TopupService", "FindTopupById"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn find_topup_by_no(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindTopupByNoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTopupResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topup.TopupService/FindTopupByNo",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topup.TopupService", "FindTopupByNo"));
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn find_topup_by_user_id(
             &mut self,
             request: impl tonic::IntoRequest<super::FindTopupByUserIdRequest>,
@@ -253,9 +318,9 @@ pub mod topup_service_client {
         }
         pub async fn find_topup_by_users_id(
             &mut self,
-            request: impl tonic::IntoRequest<super::FindTopupByUserIdRequest>,
+            request: impl tonic::IntoRequest<super::FindTopupByUsersIdRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::ApiResponsesTopupResponse>,
+            tonic::Response<super::ApiResponsesTopupPaginated>,
             tonic::Status,
         > {
             self.inner
@@ -347,6 +412,102 @@ pub mod topup_service_client {
                 .insert(GrpcMethod::new("topup.TopupService", "DeleteTopup"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn confirm_topup(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindTopupByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTopupResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topup.TopupService/ConfirmTopup",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topup.TopupService", "ConfirmTopup"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn fail_topup(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindTopupByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTopupResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topup.TopupService/FailTopup",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topup.TopupService", "FailTopup"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn refund_topup(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindTopupByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTopupResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topup.TopupService/RefundTopup",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topup.TopupService", "RefundTopup"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn topup_stats(
+            &mut self,
+            request: impl tonic::IntoRequest<super::TopupStatsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTopupStats>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/topup.TopupService/TopupStats",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("topup.TopupService", "TopupStats"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -376,6 +537,13 @@ pub mod topup_service_server {
             tonic::Response<super::ApiResponseTopupResponse>,
             tonic::Status,
         >;
+        async fn find_topup_by_no(
+            &self,
+            request: tonic::Request<super::FindTopupByNoRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTopupResponse>,
+            tonic::Status,
+        >;
         async fn find_topup_by_user_id(
             &self,
             request: tonic::Request<super::FindTopupByUserIdRequest>,
@@ -385,9 +553,9 @@ pub mod topup_service_server {
         >;
         async fn find_topup_by_users_id(
             &self,
-            request: tonic::Request<super::FindTopupByUserIdRequest>,
+            request: tonic::Request<super::FindTopupByUsersIdRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::ApiResponsesTopupResponse>,
+            tonic::Response<super::ApiResponsesTopupPaginated>,
             tonic::Status,
         >;
         async fn create_topup(
@@ -411,6 +579,34 @@ pub mod topup_service_server {
             tonic::Response<super::super::api::ApiResponseEmpty>,
             tonic::Status,
         >;
+        async fn confirm_topup(
+            &self,
+            request: tonic::Request<super::FindTopupByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTopupResponse>,
+            tonic::Status,
+        >;
+        async fn fail_topup(
+            &self,
+            request: tonic::Request<super::FindTopupByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTopupResponse>,
+            tonic::Status,
+        >;
+        async fn refund_topup(
+            &self,
+            request: tonic::Request<super::FindTopupByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTopupResponse>,
+            tonic::Status,
+        >;
+        async fn topup_stats(
+            &self,
+            request: tonic::Request<super::TopupStatsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTopupStats>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct TopupServiceServer<T> {
@@ -578,6 +774,51 @@ pub mod topup_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/topup.TopupService/FindTopupByNo" => {
+                    #[allow(non_camel_case_types)]
+                    struct FindTopupByNoSvc<T: TopupService>(pub Arc<T>);
+                    impl<
+                        T: TopupService,
+                    > tonic::server::UnaryService<super::FindTopupByNoRequest>
+                    for FindTopupByNoSvc<T> {
+                        type Response = super::ApiResponseTopupResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindTopupByNoRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as TopupService>::find_topup_by_no(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FindTopupByNoSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/topup.TopupService/FindTopupByUserId" => {
                     #[allow(non_camel_case_types)]
                     struct FindTopupByUserIdSvc<T: TopupService>(pub Arc<T>);
@@ -629,16 +870,16 @@ pub mod topup_service_server {
                     struct FindTopupByUsersIdSvc<T: TopupService>(pub Arc<T>);
                     impl<
                         T: TopupService,
-                    > tonic::server::UnaryService<super::FindTopupByUserIdRequest>
+                    > tonic::server::UnaryService<super::FindTopupByUsersIdRequest>
                     for FindTopupByUsersIdSvc<T> {
-                        type Response = super::ApiResponsesTopupResponse;
+                        type Response = super::ApiResponsesTopupPaginated;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::FindTopupByUserIdRequest>,
+                            request: tonic::Request<super::FindTopupByUsersIdRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
@@ -805,6 +1046,186 @@ pub mod topup_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/topup.TopupService/ConfirmTopup" => {
+                    #[allow(non_camel_case_types)]
+                    struct ConfirmTopupSvc<T: TopupService>(pub Arc<T>);
+                    impl<
+                        T: TopupService,
+                    > tonic::server::UnaryService<super::FindTopupByIdRequest>
+                    for ConfirmTopupSvc<T> {
+                        type Response = super::ApiResponseTopupResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindTopupByIdRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as TopupService>::confirm_topup(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ConfirmTopupSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topup.TopupService/FailTopup" => {
+                    #[allow(non_camel_case_types)]
+                    struct FailTopupSvc<T: TopupService>(pub Arc<T>);
+                    impl<
+                        T: TopupService,
+                    > tonic::server::UnaryService<super::FindTopupByIdRequest>
+                    for FailTopupSvc<T> {
+                        type Response = super::ApiResponseTopupResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindTopupByIdRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as TopupService>::fail_topup(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FailTopupSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topup.TopupService/RefundTopup" => {
+                    #[allow(non_camel_case_types)]
+                    struct RefundTopupSvc<T: TopupService>(pub Arc<T>);
+                    impl<
+                        T: TopupService,
+                    > tonic::server::UnaryService<super::FindTopupByIdRequest>
+                    for RefundTopupSvc<T> {
+                        type Response = super::ApiResponseTopupResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindTopupByIdRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as TopupService>::refund_topup(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RefundTopupSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/topup.TopupService/TopupStats" => {
+                    #[allow(non_camel_case_types)]
+                    struct TopupStatsSvc<T: TopupService>(pub Arc<T>);
+                    impl<
+                        T: TopupService,
+                    > tonic::server::UnaryService<super::TopupStatsRequest>
+                    for TopupStatsSvc<T> {
+                        type Response = super::ApiResponseTopupStats;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::TopupStatsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as TopupService>::topup_stats(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = TopupStatsSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(
@@ -7,6 +7,23 @@ pub struct FindAllTransferRequest {
     pub page_size: i32,
     #[prost(string, tag = "3")]
     pub search: ::prost::alloc::string::String,
+    #[prost(int32, optional, tag = "4")]
+    pub min_amount: ::core::option::Option<i32>,
+    #[prost(int32, optional, tag = "5")]
+    pub max_amount: ::core::option::Option<i32>,
+    #[prost(string, tag = "6")]
+    pub status: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct StreamAllTransfersRequest {
+    #[prost(string, tag = "1")]
+    pub start_date: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub end_date: ::prost::alloc::string::String,
+    #[prost(int32, tag = "3")]
+    pub batch_size: i32,
+    #[prost(int32, tag = "4")]
+    pub after_id: i32,
 }
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct FindTransferByIdRequest {
@@ -19,6 +36,15 @@ pub struct FindTransferByUserIdRequest {
     pub user_id: i32,
 }
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct FindTransferByUsersIdRequest {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(int32, tag = "2")]
+    pub page: i32,
+    #[prost(int32, tag = "3")]
+    pub page_size: i32,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct CreateTransferRequest {
     #[prost(int32, tag = "1")]
     pub transfer_from: i32,
@@ -26,8 +52,16 @@ pub struct CreateTransferRequest {
     pub transfer_to: i32,
     #[prost(int32, tag = "3")]
     pub transfer_amount: i32,
+    #[prost(string, tag = "4")]
+    pub note: ::prost::alloc::string::String,
+    #[prost(bool, tag = "5")]
+    pub dry_run: bool,
+    #[prost(string, tag = "6")]
+    pub from_label: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub to_label: ::prost::alloc::string::String,
 }
-#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct UpdateTransferRequest {
     #[prost(int32, tag = "1")]
     pub transfer_id: i32,
@@ -37,6 +71,8 @@ pub struct UpdateTransferRequest {
     pub transfer_to: i32,
     #[prost(int32, tag = "4")]
     pub transfer_amount: i32,
+    #[prost(string, tag = "5")]
+    pub note: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct TransferResponse {
@@ -54,6 +90,20 @@ pub struct TransferResponse {
     pub created_at: ::prost::alloc::string::String,
     #[prost(string, tag = "7")]
     pub updated_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub note: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(int32, optional, tag = "10")]
+    pub projected_sender_balance: ::core::option::Option<i32>,
+    #[prost(int32, optional, tag = "11")]
+    pub projected_receiver_balance: ::core::option::Option<i32>,
+    #[prost(string, tag = "12")]
+    pub from_label: ::prost::alloc::string::String,
+    #[prost(string, tag = "13")]
+    pub to_label: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "14")]
+    pub direction: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ApiResponseTransferResponse {
@@ -84,6 +134,33 @@ pub struct ApiResponsesTransferPaginated {
     #[prost(message, optional, tag = "4")]
     pub pagination: ::core::option::Option<super::api::Pagination>,
 }
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct DailyTransferTotalsRequest {
+    #[prost(string, tag = "1")]
+    pub start_date: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub end_date: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct DailyTransferTotal {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(string, tag = "2")]
+    pub date: ::prost::alloc::string::String,
+    #[prost(int64, tag = "3")]
+    pub total_sent: i64,
+    #[prost(int64, tag = "4")]
+    pub total_received: i64,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApiResponseDailyTransferTotals {
+    #[prost(string, tag = "1")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(message, repeated, tag = "3")]
+    pub data: ::prost::alloc::vec::Vec<DailyTransferTotal>,
+}
 /// Generated client implementations.
 pub mod transfer_service_client {
     #![allow(
@@ -199,6 +276,32 @@ pub mod transfer_service_client {
                 .insert(GrpcMethod::new("transfer.TransferService", "FindAllTransfer"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn stream_all_transfers(
+            &mut self,
+            request: impl tonic::IntoRequest<super::StreamAllTransfersRequest>,
+        ) -> std::result::Result<
+            tonic::Response<tonic::codec::Streaming<super::TransferResponse>>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/transfer.TransferService/StreamAllTransfers",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("transfer.TransferService", "StreamAllTransfers"),
+                );
+            self.inner.server_streaming(req, path, codec).await
+        }
         pub async fn find_transfer_by_id(
             &mut self,
             request: impl tonic::IntoRequest<super::FindTransferByIdRequest>,
@@ -251,9 +354,9 @@ pub mod transfer_service_client {
         }
         pub async fn find_transfer_by_users_id(
             &mut self,
-            request: impl tonic::IntoRequest<super::FindTransferByUserIdRequest>,
+            request: impl tonic::IntoRequest<super::FindTransferByUsersIdRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::ApiResponsesTransferResponse>,
+            tonic::Response<super::ApiResponsesTransferPaginated>,
             tonic::Status,
         > {
             self.inner
@@ -275,6 +378,32 @@ pub mod transfer_service_client {
                 );
             self.inner.unary(req, path, codec).await
         }
+        pub async fn get_user_transfer_history(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindTransferByUsersIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponsesTransferPaginated>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/transfer.TransferService/GetUserTransferHistory",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("transfer.TransferService", "GetUserTransferHistory"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
         pub async fn create_transfer(
             &mut self,
             request: impl tonic::IntoRequest<super::CreateTransferRequest>,
@@ -347,6 +476,80 @@ pub mod transfer_service_client {
                 .insert(GrpcMethod::new("transfer.TransferService", "DeleteTransfer"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn reverse_transfer(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindTransferByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTransferResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/transfer.TransferService/ReverseTransfer",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("transfer.TransferService", "ReverseTransfer"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn approve_transfer(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindTransferByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTransferResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/transfer.TransferService/ApproveTransfer",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("transfer.TransferService", "ApproveTransfer"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn daily_transfer_totals(
+            &mut self,
+            request: impl tonic::IntoRequest<super::DailyTransferTotalsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseDailyTransferTotals>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/transfer.TransferService/DailyTransferTotals",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(
+                    GrpcMethod::new("transfer.TransferService", "DailyTransferTotals"),
+                );
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -369,6 +572,19 @@ pub mod transfer_service_server {
             tonic::Response<super::ApiResponsesTransferPaginated>,
             tonic::Status,
         >;
+        /// Server streaming response type for the StreamAllTransfers method.
+        type StreamAllTransfersStream: tonic::codegen::tokio_stream::Stream<
+                Item = std::result::Result<super::TransferResponse, tonic::Status>,
+            >
+            + std::marker::Send
+            + 'static;
+        async fn stream_all_transfers(
+            &self,
+            request: tonic::Request<super::StreamAllTransfersRequest>,
+        ) -> std::result::Result<
+            tonic::Response<Self::StreamAllTransfersStream>,
+            tonic::Status,
+        >;
         async fn find_transfer_by_id(
             &self,
             request: tonic::Request<super::FindTransferByIdRequest>,
@@ -385,9 +601,16 @@ pub mod transfer_service_server {
         >;
         async fn find_transfer_by_users_id(
             &self,
-            request: tonic::Request<super::FindTransferByUserIdRequest>,
+            request: tonic::Request<super::FindTransferByUsersIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponsesTransferPaginated>,
+            tonic::Status,
+        >;
+        async fn get_user_transfer_history(
+            &self,
+            request: tonic::Request<super::FindTransferByUsersIdRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::ApiResponsesTransferResponse>,
+            tonic::Response<super::ApiResponsesTransferPaginated>,
             tonic::Status,
         >;
         async fn create_transfer(
@@ -411,6 +634,27 @@ pub mod transfer_service_server {
             tonic::Response<super::super::api::ApiResponseEmpty>,
             tonic::Status,
         >;
+        async fn reverse_transfer(
+            &self,
+            request: tonic::Request<super::FindTransferByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTransferResponse>,
+            tonic::Status,
+        >;
+        async fn approve_transfer(
+            &self,
+            request: tonic::Request<super::FindTransferByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseTransferResponse>,
+            tonic::Status,
+        >;
+        async fn daily_transfer_totals(
+            &self,
+            request: tonic::Request<super::DailyTransferTotalsRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseDailyTransferTotals>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct TransferServiceServer<T> {
@@ -534,6 +778,57 @@ pub mod transfer_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/transfer.TransferService/StreamAllTransfers" => {
+                    #[allow(non_camel_case_types)]
+                    struct StreamAllTransfersSvc<T: TransferService>(pub Arc<T>);
+                    impl<
+                        T: TransferService,
+                    > tonic::server::ServerStreamingService<
+                        super::StreamAllTransfersRequest,
+                    > for StreamAllTransfersSvc<T> {
+                        type Response = super::TransferResponse;
+                        type ResponseStream = T::StreamAllTransfersStream;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::ResponseStream>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::StreamAllTransfersRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as TransferService>::stream_all_transfers(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = StreamAllTransfersSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.server_streaming(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/transfer.TransferService/FindTransferById" => {
                     #[allow(non_camel_case_types)]
                     struct FindTransferByIdSvc<T: TransferService>(pub Arc<T>);
@@ -634,16 +929,16 @@ pub mod transfer_service_server {
                     struct FindTransferByUsersIdSvc<T: TransferService>(pub Arc<T>);
                     impl<
                         T: TransferService,
-                    > tonic::server::UnaryService<super::FindTransferByUserIdRequest>
+                    > tonic::server::UnaryService<super::FindTransferByUsersIdRequest>
                     for FindTransferByUsersIdSvc<T> {
-                        type Response = super::ApiResponsesTransferResponse;
+                        type Response = super::ApiResponsesTransferPaginated;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::FindTransferByUserIdRequest>,
+                            request: tonic::Request<super::FindTransferByUsersIdRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
@@ -678,6 +973,55 @@ pub mod transfer_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/transfer.TransferService/GetUserTransferHistory" => {
+                    #[allow(non_camel_case_types)]
+                    struct GetUserTransferHistorySvc<T: TransferService>(pub Arc<T>);
+                    impl<
+                        T: TransferService,
+                    > tonic::server::UnaryService<super::FindTransferByUsersIdRequest>
+                    for GetUserTransferHistorySvc<T> {
+                        type Response = super::ApiResponsesTransferPaginated;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindTransferByUsersIdRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as TransferService>::get_user_transfer_history(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = GetUserTransferHistorySvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 "/transfer.TransferService/CreateTransfer" => {
                     #[allow(non_camel_case_types)]
                     struct CreateTransferSvc<T: TransferService>(pub Arc<T>);
@@ -816,6 +1160,147 @@ pub mod transfer_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/transfer.TransferService/ReverseTransfer" => {
+                    #[allow(non_camel_case_types)]
+                    struct ReverseTransferSvc<T: TransferService>(pub Arc<T>);
+                    impl<
+                        T: TransferService,
+                    > tonic::server::UnaryService<super::FindTransferByIdRequest>
+                    for ReverseTransferSvc<T> {
+                        type Response = super::ApiResponseTransferResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindTransferByIdRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as TransferService>::reverse_transfer(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ReverseTransferSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/transfer.TransferService/ApproveTransfer" => {
+                    #[allow(non_camel_case_types)]
+                    struct ApproveTransferSvc<T: TransferService>(pub Arc<T>);
+                    impl<
+                        T: TransferService,
+                    > tonic::server::UnaryService<super::FindTransferByIdRequest>
+                    for ApproveTransferSvc<T> {
+                        type Response = super::ApiResponseTransferResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindTransferByIdRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as TransferService>::approve_transfer(&inner, request)
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ApproveTransferSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/transfer.TransferService/DailyTransferTotals" => {
+                    #[allow(non_camel_case_types)]
+                    struct DailyTransferTotalsSvc<T: TransferService>(pub Arc<T>);
+                    impl<
+                        T: TransferService,
+                    > tonic::server::UnaryService<super::DailyTransferTotalsRequest>
+                    for DailyTransferTotalsSvc<T> {
+                        type Response = super::ApiResponseDailyTransferTotals;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::DailyTransferTotalsRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as TransferService>::daily_transfer_totals(
+                                        &inner,
+                                        request,
+                                    )
+                                    .await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = DailyTransferTotalsSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(
@@ -7,6 +7,14 @@ pub struct FindAllUserRequest {
     pub page_size: i32,
     #[prost(string, tag = "3")]
     pub search: ::prost::alloc::string::String,
+    #[prost(bool, tag = "4")]
+    pub include_deleted: bool,
+    #[prost(string, tag = "5")]
+    pub email: ::prost::alloc::string::String,
+    #[prost(string, tag = "6")]
+    pub created_before: ::prost::alloc::string::String,
+    #[prost(string, tag = "7")]
+    pub created_after: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct CreateUserRequest {
@@ -25,22 +33,36 @@ pub struct CreateUserRequest {
 pub struct UpdateUserRequest {
     #[prost(int32, tag = "1")]
     pub id: i32,
-    #[prost(string, tag = "2")]
-    pub firstname: ::prost::alloc::string::String,
-    #[prost(string, tag = "3")]
-    pub lastname: ::prost::alloc::string::String,
-    #[prost(string, tag = "4")]
-    pub email: ::prost::alloc::string::String,
-    #[prost(string, tag = "5")]
-    pub password: ::prost::alloc::string::String,
-    #[prost(string, tag = "6")]
-    pub confirm_password: ::prost::alloc::string::String,
+    #[prost(string, optional, tag = "2")]
+    pub firstname: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "3")]
+    pub lastname: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "4")]
+    pub email: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "5")]
+    pub password: ::core::option::Option<::prost::alloc::string::String>,
+    #[prost(string, optional, tag = "6")]
+    pub confirm_password: ::core::option::Option<::prost::alloc::string::String>,
 }
 #[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct FindUserByIdRequest {
     #[prost(int32, tag = "1")]
     pub id: i32,
 }
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct DeleteUserRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(bool, tag = "2")]
+    pub force: bool,
+}
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct PurgeUserRequest {
+    #[prost(int32, tag = "1")]
+    pub id: i32,
+    #[prost(bool, tag = "2")]
+    pub force: bool,
+}
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct UserResponse {
     #[prost(int32, tag = "1")]
@@ -57,6 +79,12 @@ pub struct UserResponse {
     pub created_at: ::prost::alloc::string::String,
     #[prost(string, tag = "7")]
     pub updated_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "8")]
+    pub deleted_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "9")]
+    pub password_changed_at: ::prost::alloc::string::String,
+    #[prost(string, tag = "10")]
+    pub status: ::prost::alloc::string::String,
 }
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct ApiResponseUserResponse {
@@ -67,6 +95,95 @@ pub struct ApiResponseUserResponse {
     #[prost(message, optional, tag = "3")]
     pub data: ::core::option::Option<UserResponse>,
 }
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct PurgeUserResponse {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(int64, tag = "2")]
+    pub saldos: i64,
+    #[prost(int64, tag = "3")]
+    pub topups: i64,
+    #[prost(int64, tag = "4")]
+    pub transfers: i64,
+    #[prost(int64, tag = "5")]
+    pub withdraws: i64,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ApiResponsePurgeUser {
+    #[prost(string, tag = "1")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub data: ::core::option::Option<PurgeUserResponse>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct UserDeletionCheckResponse {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(bool, tag = "2")]
+    pub deletable: bool,
+    #[prost(int64, tag = "3")]
+    pub incoming_transfers: i64,
+    #[prost(int64, tag = "4")]
+    pub active_sessions: i64,
+    #[prost(string, repeated, tag = "5")]
+    pub blockers: ::prost::alloc::vec::Vec<::prost::alloc::string::String>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ApiResponseUserDeletionCheck {
+    #[prost(string, tag = "1")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub data: ::core::option::Option<UserDeletionCheckResponse>,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ImportUserRow {
+    #[prost(string, tag = "1")]
+    pub name: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub email: ::prost::alloc::string::String,
+    #[prost(string, tag = "3")]
+    pub password: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImportUsersRequest {
+    #[prost(message, repeated, tag = "1")]
+    pub rows: ::prost::alloc::vec::Vec<ImportUserRow>,
+    #[prost(bool, tag = "2")]
+    pub continue_on_error: bool,
+}
+#[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct ImportUserResult {
+    #[prost(int32, tag = "1")]
+    pub row: i32,
+    #[prost(string, tag = "2")]
+    pub email: ::prost::alloc::string::String,
+    #[prost(int32, optional, tag = "3")]
+    pub user_id: ::core::option::Option<i32>,
+    #[prost(string, tag = "4")]
+    pub error: ::prost::alloc::string::String,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ImportUsersResponse {
+    #[prost(int32, tag = "1")]
+    pub imported: i32,
+    #[prost(int32, tag = "2")]
+    pub failed: i32,
+    #[prost(message, repeated, tag = "3")]
+    pub results: ::prost::alloc::vec::Vec<ImportUserResult>,
+}
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct ApiResponseImportUsers {
+    #[prost(string, tag = "1")]
+    pub status: ::prost::alloc::string::String,
+    #[prost(string, tag = "2")]
+    pub message: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "3")]
+    pub data: ::core::option::Option<ImportUsersResponse>,
+}
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct ApiResponsesUserPaginated {
     #[prost(string, tag = "1")]
@@ -266,7 +383,7 @@ pub mod user_service_client {
         }
         pub async fn delete_user(
             &mut self,
-            request: impl tonic::IntoRequest<super::FindUserByIdRequest>,
+            request: impl tonic::IntoRequest<super::DeleteUserRequest>,
         ) -> std::result::Result<
             tonic::Response<super::super::api::ApiResponseEmpty>,
             tonic::Status,
@@ -288,6 +405,150 @@ pub mod user_service_client {
                 .insert(GrpcMethod::new("user.UserService", "DeleteUser"));
             self.inner.unary(req, path, codec).await
         }
+        pub async fn can_delete_user(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindUserByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseUserDeletionCheck>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/user.UserService/CanDeleteUser",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("user.UserService", "CanDeleteUser"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn restore_user(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindUserByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseUserResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/user.UserService/RestoreUser",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("user.UserService", "RestoreUser"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn freeze_user(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindUserByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseUserResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/user.UserService/FreezeUser",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("user.UserService", "FreezeUser"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn unfreeze_user(
+            &mut self,
+            request: impl tonic::IntoRequest<super::FindUserByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseUserResponse>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/user.UserService/UnfreezeUser",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("user.UserService", "UnfreezeUser"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn purge_user(
+            &mut self,
+            request: impl tonic::IntoRequest<super::PurgeUserRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponsePurgeUser>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/user.UserService/PurgeUser",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("user.UserService", "PurgeUser"));
+            self.inner.unary(req, path, codec).await
+        }
+        pub async fn import_users(
+            &mut self,
+            request: impl tonic::IntoRequest<super::ImportUsersRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseImportUsers>,
+            tonic::Status,
+        > {
+            self.inner
+                .ready()
+                .await
+                .map_err(|e| {
+                    tonic::Status::unknown(
+                        format!("Service was not ready: {}", e.into()),
+                    )
+                })?;
+            let codec = tonic_prost::ProstCodec::default();
+            let path = http::uri::PathAndQuery::from_static(
+                "/user.UserService/ImportUsers",
+            );
+            let mut req = request.into_request();
+            req.extensions_mut()
+                .insert(GrpcMethod::new("user.UserService", "ImportUsers"));
+            self.inner.unary(req, path, codec).await
+        }
     }
 }
 /// Generated server implementations.
@@ -333,11 +594,53 @@ pub mod user_service_server {
         >;
         async fn delete_user(
             &self,
-            request: tonic::Request<super::FindUserByIdRequest>,
+            request: tonic::Request<super::DeleteUserRequest>,
         ) -> std::result::Result<
             tonic::Response<super::super::api::ApiResponseEmpty>,
             tonic::Status,
         >;
+        async fn can_delete_user(
+            &self,
+            request: tonic::Request<super::FindUserByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseUserDeletionCheck>,
+            tonic::Status,
+        >;
+        async fn restore_user(
+            &self,
+            request: tonic::Request<super::FindUserByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseUserResponse>,
+            tonic::Status,
+        >;
+        async fn freeze_user(
+            &self,
+            request: tonic::Request<super::FindUserByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseUserResponse>,
+            tonic::Status,
+        >;
+        async fn unfreeze_user(
+            &self,
+            request: tonic::Request<super::FindUserByIdRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseUserResponse>,
+            tonic::Status,
+        >;
+        async fn purge_user(
+            &self,
+            request: tonic::Request<super::PurgeUserRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponsePurgeUser>,
+            tonic::Status,
+        >;
+        async fn import_users(
+            &self,
+            request: tonic::Request<super::ImportUsersRequest>,
+        ) -> std::result::Result<
+            tonic::Response<super::ApiResponseImportUsers>,
+            tonic::Status,
+        >;
     }
     #[derive(Debug)]
     pub struct UserServiceServer<T> {
@@ -600,7 +903,7 @@ pub mod user_service_server {
                     struct DeleteUserSvc<T: UserService>(pub Arc<T>);
                     impl<
                         T: UserService,
-                    > tonic::server::UnaryService<super::FindUserByIdRequest>
+                    > tonic::server::UnaryService<super::DeleteUserRequest>
                     for DeleteUserSvc<T> {
                         type Response = super::super::api::ApiResponseEmpty;
                         type Future = BoxFuture<
@@ -609,7 +912,7 @@ pub mod user_service_server {
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::FindUserByIdRequest>,
+                            request: tonic::Request<super::DeleteUserRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
@@ -640,6 +943,276 @@ pub mod user_service_server {
                     };
                     Box::pin(fut)
                 }
+                "/user.UserService/CanDeleteUser" => {
+                    #[allow(non_camel_case_types)]
+                    struct CanDeleteUserSvc<T: UserService>(pub Arc<T>);
+                    impl<
+                        T: UserService,
+                    > tonic::server::UnaryService<super::FindUserByIdRequest>
+                    for CanDeleteUserSvc<T> {
+                        type Response = super::ApiResponseUserDeletionCheck;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindUserByIdRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UserService>::can_delete_user(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = CanDeleteUserSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/user.UserService/RestoreUser" => {
+                    #[allow(non_camel_case_types)]
+                    struct RestoreUserSvc<T: UserService>(pub Arc<T>);
+                    impl<
+                        T: UserService,
+                    > tonic::server::UnaryService<super::FindUserByIdRequest>
+                    for RestoreUserSvc<T> {
+                        type Response = super::ApiResponseUserResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindUserByIdRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UserService>::restore_user(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = RestoreUserSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/user.UserService/FreezeUser" => {
+                    #[allow(non_camel_case_types)]
+                    struct FreezeUserSvc<T: UserService>(pub Arc<T>);
+                    impl<
+                        T: UserService,
+                    > tonic::server::UnaryService<super::FindUserByIdRequest>
+                    for FreezeUserSvc<T> {
+                        type Response = super::ApiResponseUserResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindUserByIdRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UserService>::freeze_user(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = FreezeUserSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/user.UserService/UnfreezeUser" => {
+                    #[allow(non_camel_case_types)]
+                    struct UnfreezeUserSvc<T: UserService>(pub Arc<T>);
+                    impl<
+                        T: UserService,
+                    > tonic::server::UnaryService<super::FindUserByIdRequest>
+                    for UnfreezeUserSvc<T> {
+                        type Response = super::ApiResponseUserResponse;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::FindUserByIdRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UserService>::unfreeze_user(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = UnfreezeUserSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/user.UserService/PurgeUser" => {
+                    #[allow(non_camel_case_types)]
+                    struct PurgeUserSvc<T: UserService>(pub Arc<T>);
+                    impl<
+                        T: UserService,
+                    > tonic::server::UnaryService<super::PurgeUserRequest>
+                    for PurgeUserSvc<T> {
+                        type Response = super::ApiResponsePurgeUser;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::PurgeUserRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UserService>::purge_user(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = PurgeUserSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
+                "/user.UserService/ImportUsers" => {
+                    #[allow(non_camel_case_types)]
+                    struct ImportUsersSvc<T: UserService>(pub Arc<T>);
+                    impl<
+                        T: UserService,
+                    > tonic::server::UnaryService<super::ImportUsersRequest>
+                    for ImportUsersSvc<T> {
+                        type Response = super::ApiResponseImportUsers;
+                        type Future = BoxFuture<
+                            tonic::Response<Self::Response>,
+                            tonic::Status,
+                        >;
+                        fn call(
+                            &mut self,
+                            request: tonic::Request<super::ImportUsersRequest>,
+                        ) -> Self::Future {
+                            let inner = Arc::clone(&self.0);
+                            let fut = async move {
+                                <T as UserService>::import_users(&inner, request).await
+                            };
+                            Box::pin(fut)
+                        }
+                    }
+                    let accept_compression_encodings = self.accept_compression_encodings;
+                    let send_compression_encodings = self.send_compression_encodings;
+                    let max_decoding_message_size = self.max_decoding_message_size;
+                    let max_encoding_message_size = self.max_encoding_message_size;
+                    let inner = self.inner.clone();
+                    let fut = async move {
+                        let method = ImportUsersSvc(inner);
+                        let codec = tonic_prost::ProstCodec::default();
+                        let mut grpc = tonic::server::Grpc::new(codec)
+                            .apply_compression_config(
+                                accept_compression_encodings,
+                                send_compression_encodings,
+                            )
+                            .apply_max_message_size_config(
+                                max_decoding_message_size,
+                                max_encoding_message_size,
+                            );
+                        let res = grpc.unary(method, req).await;
+                        Ok(res)
+                    };
+                    Box::pin(fut)
+                }
                 _ => {
                     Box::pin(async move {
                         let mut response = http::Response::new(
@@ -18,6 +18,15 @@ pub struct FindWithdrawByUserIdRequest {
     #[prost(int32, tag = "1")]
     pub user_id: i32,
 }
+#[derive(Clone, Copy, PartialEq, Eq, Hash, ::prost::Message)]
+pub struct FindWithdrawByUsersIdRequest {
+    #[prost(int32, tag = "1")]
+    pub user_id: i32,
+    #[prost(int32, tag = "2")]
+    pub page: i32,
+    #[prost(int32, tag = "3")]
+    pub page_size: i32,
+}
 #[derive(Clone, PartialEq, Eq, Hash, ::prost::Message)]
 pub struct CreateWithdrawRequest {
     #[prost(int32, tag = "1")]
@@ -249,9 +258,9 @@ pub mod withdraw_service_client {
         }
         pub async fn find_withdraw_by_users_id(
             &mut self,
-            request: impl tonic::IntoRequest<super::FindWithdrawByUserIdRequest>,
+            request: impl tonic::IntoRequest<super::FindWithdrawByUsersIdRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::ApiResponsesWithdrawResponse>,
+            tonic::Response<super::ApiResponsesWithdrawPaginated>,
             tonic::Status,
         > {
             self.inner
@@ -383,9 +392,9 @@ pub mod withdraw_service_server {
         >;
         async fn find_withdraw_by_users_id(
             &self,
-            request: tonic::Request<super::FindWithdrawByUserIdRequest>,
+            request: tonic::Request<super::FindWithdrawByUsersIdRequest>,
         ) -> std::result::Result<
-            tonic::Response<super::ApiResponsesWithdrawResponse>,
+            tonic::Response<super::ApiResponsesWithdrawPaginated>,
             tonic::Status,
         >;
         async fn create_withdraw(
@@ -632,16 +641,16 @@ pub mod withdraw_service_server {
                     struct FindWithdrawByUsersIdSvc<T: WithdrawService>(pub Arc<T>);
                     impl<
                         T: WithdrawService,
-                    > tonic::server::UnaryService<super::FindWithdrawByUserIdRequest>
+                    > tonic::server::UnaryService<super::FindWithdrawByUsersIdRequest>
                     for FindWithdrawByUsersIdSvc<T> {
-                        type Response = super::ApiResponsesWithdrawResponse;
+                        type Response = super::ApiResponsesWithdrawPaginated;
                         type Future = BoxFuture<
                             tonic::Response<Self::Response>,
                             tonic::Status,
                         >;
                         fn call(
                             &mut self,
-                            request: tonic::Request<super::FindWithdrawByUserIdRequest>,
+                            request: tonic::Request<super::FindWithdrawByUsersIdRequest>,
                         ) -> Self::Future {
                             let inner = Arc::clone(&self.0);
                             let fut = async move {
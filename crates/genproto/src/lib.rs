@@ -25,3 +25,19 @@ pub mod transfer {
 pub mod withdraw {
     include!("gen/withdraw.rs");
 }
+
+pub mod virtual_card {
+    include!("gen/virtual_card.rs");
+}
+
+pub mod webhook {
+    include!("gen/webhook.rs");
+}
+
+pub mod account {
+    include!("gen/account.rs");
+}
+
+pub mod system {
+    include!("gen/system.rs");
+}
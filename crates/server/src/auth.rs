@@ -0,0 +1,529 @@
+use axum::http::{Request, Response};
+use shared::{
+    abstract_trait::{DynJwtService, DynUserService},
+    utils::{AUTHORIZATION_HEADER, parse_bearer_token},
+};
+use std::{
+    collections::HashSet,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+use tonic::{Code, Status};
+use tower::{Layer, Service};
+use tracing::warn;
+
+const HEALTH_SERVICE_PREFIX: &str = "/grpc.health.v1.Health/";
+
+// belum ada token sebelum login/register, jadi selalu terbuka
+const PUBLIC_METHODS: &[&str] = &[
+    "/auth.AuthService/RegisterUser",
+    "/auth.AuthService/LoginUser",
+    "/auth.AuthService/RefreshToken",
+];
+
+// hanya untuk user di ADMIN_USER_IDS, sama dengan pemeriksaan admin di REST gateway;
+// tidak pernah dibebaskan oleh allowlist
+const ADMIN_METHODS: &[&str] = &[
+    "/auth.AuthService/ListSessions",
+    "/auth.AuthService/RevokeSession",
+    "/user.UserService/UpdateUser",
+    "/user.UserService/DeleteUser",
+    "/user.UserService/CanDeleteUser",
+    "/user.UserService/RestoreUser",
+    "/user.UserService/FreezeUser",
+    "/user.UserService/UnfreezeUser",
+    "/user.UserService/PurgeUser",
+    "/user.UserService/ImportUsers",
+    "/saldo.SaldoService/CreateSaldo",
+    "/saldo.SaldoService/UpdateSaldo",
+    "/saldo.SaldoService/DeleteSaldo",
+    "/saldo.SaldoService/AdjustBalance",
+    "/saldo.SaldoService/ReconcileBalance",
+    "/topup.TopupService/UpdateTopup",
+    "/topup.TopupService/DeleteTopup",
+    "/topup.TopupService/ConfirmTopup",
+    "/topup.TopupService/FailTopup",
+    "/topup.TopupService/RefundTopup",
+    "/topup.TopupService/TopupStats",
+    "/transfer.TransferService/StreamAllTransfers",
+    "/transfer.TransferService/UpdateTransfer",
+    "/transfer.TransferService/DeleteTransfer",
+    "/transfer.TransferService/ReverseTransfer",
+    "/transfer.TransferService/ApproveTransfer",
+    "/transfer.TransferService/DailyTransferTotals",
+    "/system.SystemService/GetRuntimeConfig",
+    "/system.SystemService/CacheStats",
+    "/system.SystemService/CacheFlushPrefix",
+    "/webhook.WebhookService/RetryWebhook",
+];
+
+// user yang terautentikasi, tersedia di `request.extensions()` pada handler gRPC
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct AuthenticatedUser {
+    pub user_id: i32,
+    pub is_admin: bool,
+}
+
+// opsi admin di dalam request biasa (include_deleted, force) diperiksa oleh handler-nya
+pub fn require_admin<T>(request: &tonic::Request<T>) -> Result<AuthenticatedUser, Status> {
+    match request.extensions().get::<AuthenticatedUser>().copied() {
+        Some(user) if user.is_admin => Ok(user),
+        Some(_) => Err(Status::permission_denied("Admin access required")),
+        None => Err(Status::unauthenticated("Missing bearer token")),
+    }
+}
+
+// write milik user (transfer_from, user_id) hanya boleh atas akun pemanggil sendiri, admin bebas
+pub fn require_owner<T>(
+    request: &tonic::Request<T>,
+    user_id: i32,
+) -> Result<AuthenticatedUser, Status> {
+    match request.extensions().get::<AuthenticatedUser>().copied() {
+        Some(user) if user.is_admin || user.user_id == user_id => Ok(user),
+        Some(user) => {
+            warn!(
+                "🔒 User {} attempted to act on the account of user {user_id}",
+                user.user_id
+            );
+            Err(Status::permission_denied(
+                "Cannot act on another user's account",
+            ))
+        }
+        None => Err(Status::unauthenticated("Missing bearer token")),
+    }
+}
+
+// tanpa layer ini pemanggil gRPC langsung bisa melewati auth REST gateway;
+// method di luar allowlist wajib membawa bearer token yang valid dan belum dicabut
+#[derive(Clone)]
+pub struct AuthLayer {
+    jwt: DynJwtService,
+    users: DynUserService,
+    allowlist: Arc<HashSet<String>>,
+    admins: Arc<HashSet<i32>>,
+}
+
+impl AuthLayer {
+    pub fn new(
+        jwt: DynJwtService,
+        users: DynUserService,
+        allowlist: &[String],
+        admin_user_ids: &[i32],
+    ) -> Self {
+        for method in allowlist {
+            if ADMIN_METHODS.contains(&method.as_str()) {
+                warn!("⚠️  Ignoring admin method {method} in GRPC_AUTH_ALLOWLIST");
+            }
+        }
+
+        Self {
+            jwt,
+            users,
+            allowlist: Arc::new(allowlist.iter().cloned().collect()),
+            admins: Arc::new(admin_user_ids.iter().copied().collect()),
+        }
+    }
+}
+
+impl<S> Layer<S> for AuthLayer {
+    type Service = AuthService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthService {
+            inner,
+            jwt: self.jwt.clone(),
+            users: self.users.clone(),
+            allowlist: self.allowlist.clone(),
+            admins: self.admins.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AuthService<S> {
+    inner: S,
+    jwt: DynJwtService,
+    users: DynUserService,
+    allowlist: Arc<HashSet<String>>,
+    admins: Arc<HashSet<i32>>,
+}
+
+impl<S> AuthService<S> {
+    fn is_exempt(&self, path: &str) -> bool {
+        if ADMIN_METHODS.contains(&path) {
+            return false;
+        }
+
+        path.starts_with(HEALTH_SERVICE_PREFIX)
+            || PUBLIC_METHODS.contains(&path)
+            || self.allowlist.contains(path)
+    }
+}
+
+// sama dengan middleware REST: token milik user yang sudah dihapus, atau yang terbit sebelum
+// password diganti, sudah tidak berlaku
+async fn ensure_not_revoked(
+    users: &DynUserService,
+    user_id: i32,
+    iat: usize,
+) -> Result<(), Status> {
+    let user = match users.get_user(user_id).await {
+        Ok(response) => response.data,
+        Err(err) => {
+            let status = Status::from(err);
+            if status.code() == Code::NotFound {
+                return Err(Status::unauthenticated(
+                    "The user belonging to this token no longer exists",
+                ));
+            }
+            return Err(status);
+        }
+    };
+
+    let Some(user) = user else {
+        return Err(Status::unauthenticated(
+            "The user belonging to this token no longer exists",
+        ));
+    };
+
+    if let Some(changed_at) = user.password_changed_at
+        && (iat as i64) < changed_at.timestamp()
+    {
+        return Err(Status::unauthenticated(
+            "Password was changed recently, please log in again",
+        ));
+    }
+
+    Ok(())
+}
+
+impl<S, B, ResBody> Service<Request<B>> for AuthService<S>
+where
+    S: Service<Request<B>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    B: Send + 'static,
+    ResBody: Default + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        let path = request.uri().path().to_string();
+
+        if self.is_exempt(&path) {
+            return Box::pin(self.inner.call(request));
+        }
+
+        let token = request
+            .headers()
+            .get(AUTHORIZATION_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_bearer_token);
+
+        let Some(token) = token else {
+            warn!("🔒 Rejecting {path} | missing bearer token");
+
+            let response = Status::unauthenticated("Missing bearer token").into_http::<ResBody>();
+            return Box::pin(async move { Ok(response) });
+        };
+
+        let claims = match self.jwt.verify_claims(token) {
+            Ok(claims) => claims,
+            Err(err) => {
+                warn!("🔒 Rejecting {path} | {err}");
+
+                let response = Status::unauthenticated("Invalid token").into_http::<ResBody>();
+                return Box::pin(async move { Ok(response) });
+            }
+        };
+
+        let user = AuthenticatedUser {
+            user_id: claims.user_id as i32,
+            is_admin: self.admins.contains(&(claims.user_id as i32)),
+        };
+        let users = self.users.clone();
+
+        // service yang sudah poll_ready dipakai untuk request ini, salinannya menggantikan
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            if let Err(status) = ensure_not_revoked(&users, user.user_id, claims.iat).await {
+                warn!("🔒 Rejecting {path} | {}", status.message());
+                return Ok(status.into_http::<ResBody>());
+            }
+
+            if ADMIN_METHODS.contains(&path.as_str()) && !user.is_admin {
+                warn!(
+                    "🔒 Rejecting {path} | user {} is not an admin",
+                    user.user_id
+                );
+                let status = Status::permission_denied("Admin access required");
+                return Ok(status.into_http::<ResBody>());
+            }
+
+            request.extensions_mut().insert(user);
+            inner.call(request).await
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Utc;
+    use jsonwebtoken::{EncodingKey, Header, encode};
+    use prometheus_client::registry::Registry;
+    use shared::{
+        abstract_trait::JwtServiceTrait,
+        cache::CacheStore,
+        config::{CacheTtlConfig, Claims, HashAlgorithm, Hashing, JwtConfig},
+        domain::request::CreateUserRequest,
+        repository::memory::InMemoryRepositories,
+        service::user::UserService,
+        utils::Metrics,
+    };
+    use std::convert::Infallible;
+    use tower::{ServiceExt, service_fn};
+
+    const SECRET: &str = "test-secret";
+    const CREATE_TRANSFER: &str = "/transfer.TransferService/CreateTransfer";
+    const ADJUST_BALANCE: &str = "/saldo.SaldoService/AdjustBalance";
+    const UPDATE_SALDO: &str = "/saldo.SaldoService/UpdateSaldo";
+
+    struct Fixture {
+        repos: InMemoryRepositories,
+        layer: AuthLayer,
+        jwt: JwtConfig,
+    }
+
+    async fn fixture(allowlist: &[&str], admin_user_ids: &[i32]) -> Fixture {
+        fixture_with_jwt(JwtConfig::new(SECRET), allowlist, admin_user_ids).await
+    }
+
+    async fn fixture_with_jwt(
+        jwt: JwtConfig,
+        allowlist: &[&str],
+        admin_user_ids: &[i32],
+    ) -> Fixture {
+        let repos = InMemoryRepositories::new();
+        // port 1 tidak pernah menjawab, cache langsung dilewati
+        let cache = CacheStore::new(
+            redis::Client::open("redis://127.0.0.1:1/").unwrap(),
+            CacheTtlConfig::default(),
+        );
+        let users = UserService::new(
+            repos.user.clone(),
+            repos.session.clone(),
+            Arc::new(Hashing::new(HashAlgorithm::Bcrypt)),
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+            Arc::new(cache),
+        )
+        .await;
+        let allowlist: Vec<String> = allowlist.iter().map(|m| m.to_string()).collect();
+
+        Fixture {
+            layer: AuthLayer::new(
+                Arc::new(jwt.clone()),
+                Arc::new(users),
+                &allowlist,
+                admin_user_ids,
+            ),
+            repos,
+            jwt,
+        }
+    }
+
+    async fn create_user(fixture: &Fixture, email: &str) -> i32 {
+        fixture
+            .repos
+            .user
+            .create_user(&CreateUserRequest {
+                firstname: "Test".to_string(),
+                lastname: "User".to_string(),
+                email: email.to_string(),
+                password: "secret123".to_string(),
+                confirm_password: "secret123".to_string(),
+                noc_transfer: None,
+            })
+            .await
+            .unwrap()
+            .user_id
+    }
+
+    // handler tiruan mengembalikan user yang diisi layer sebagai body
+    async fn call(fixture: &Fixture, path: &str, token: Option<&str>) -> Response<String> {
+        let inner = service_fn(|request: Request<String>| async move {
+            let user = request.extensions().get::<AuthenticatedUser>().copied();
+            Ok::<_, Infallible>(Response::new(format!("{user:?}")))
+        });
+
+        let mut request = Request::builder().uri(path);
+        if let Some(token) = token {
+            request = request.header(AUTHORIZATION_HEADER, format!("Bearer {token}"));
+        }
+
+        fixture
+            .layer
+            .layer(inner)
+            .oneshot(request.body(String::new()).unwrap())
+            .await
+            .unwrap()
+    }
+
+    fn grpc_code(response: &Response<String>) -> Option<Code> {
+        response
+            .headers()
+            .get("grpc-status")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<i32>().ok())
+            .map(Code::from_i32)
+    }
+
+    #[tokio::test]
+    async fn authenticated_create_reaches_handler_with_user() {
+        let fixture = fixture(&[], &[]).await;
+        let user_id = create_user(&fixture, "sender@example.com").await;
+        let token = fixture.jwt.generate_token(user_id as i64).unwrap();
+
+        let response = call(&fixture, CREATE_TRANSFER, Some(&token)).await;
+
+        assert_eq!(grpc_code(&response), None);
+        assert!(response.body().contains(&format!("user_id: {user_id}")));
+    }
+
+    #[tokio::test]
+    async fn token_for_another_audience_is_rejected() {
+        let jwt = JwtConfig::new(SECRET).with_audience(Some("payment-gateway".to_string()));
+        let fixture = fixture_with_jwt(jwt, &[], &[]).await;
+        let user_id = create_user(&fixture, "sender@example.com").await;
+
+        // secret sama, tapi diterbitkan untuk layanan lain
+        let foreign = JwtConfig::new(SECRET)
+            .with_audience(Some("reporting".to_string()))
+            .generate_token(user_id as i64)
+            .unwrap();
+        let response = call(&fixture, CREATE_TRANSFER, Some(&foreign)).await;
+        assert_eq!(grpc_code(&response), Some(Code::Unauthenticated));
+
+        let own = fixture.jwt.generate_token(user_id as i64).unwrap();
+        let response = call(&fixture, CREATE_TRANSFER, Some(&own)).await;
+        assert_eq!(grpc_code(&response), None);
+    }
+
+    #[tokio::test]
+    async fn unauthenticated_create_is_rejected() {
+        let fixture = fixture(&[], &[]).await;
+
+        let response = call(&fixture, CREATE_TRANSFER, None).await;
+        assert_eq!(grpc_code(&response), Some(Code::Unauthenticated));
+
+        let response = call(&fixture, CREATE_TRANSFER, Some("not-a-jwt")).await;
+        assert_eq!(grpc_code(&response), Some(Code::Unauthenticated));
+    }
+
+    #[tokio::test]
+    async fn admin_methods_require_admin_user() {
+        let fixture = fixture(&[], &[1]).await;
+        let admin = create_user(&fixture, "admin@example.com").await;
+        let user = create_user(&fixture, "user@example.com").await;
+        assert_eq!(admin, 1);
+
+        let token = fixture.jwt.generate_token(user as i64).unwrap();
+        let response = call(&fixture, ADJUST_BALANCE, Some(&token)).await;
+        assert_eq!(grpc_code(&response), Some(Code::PermissionDenied));
+
+        let token = fixture.jwt.generate_token(admin as i64).unwrap();
+        let response = call(&fixture, ADJUST_BALANCE, Some(&token)).await;
+        assert_eq!(grpc_code(&response), None);
+        assert!(response.body().contains("is_admin: true"));
+    }
+
+    #[tokio::test]
+    async fn raw_saldo_writes_require_admin_user() {
+        let fixture = fixture(&[], &[]).await;
+        let user = create_user(&fixture, "user@example.com").await;
+        let token = fixture.jwt.generate_token(user as i64).unwrap();
+
+        let response = call(&fixture, UPDATE_SALDO, Some(&token)).await;
+
+        assert_eq!(grpc_code(&response), Some(Code::PermissionDenied));
+    }
+
+    #[test]
+    fn user_scoped_write_on_another_account_is_denied() {
+        let mut request = tonic::Request::new(());
+        request.extensions_mut().insert(AuthenticatedUser {
+            user_id: 1,
+            is_admin: false,
+        });
+
+        assert!(require_owner(&request, 1).is_ok());
+        let status = require_owner(&request, 2).unwrap_err();
+        assert_eq!(status.code(), Code::PermissionDenied);
+
+        request.extensions_mut().insert(AuthenticatedUser {
+            user_id: 1,
+            is_admin: true,
+        });
+        assert!(require_owner(&request, 2).is_ok());
+    }
+
+    #[tokio::test]
+    async fn allowlist_cannot_exempt_admin_methods() {
+        let fixture = fixture(&[ADJUST_BALANCE], &[]).await;
+
+        let response = call(&fixture, ADJUST_BALANCE, None).await;
+
+        assert_eq!(grpc_code(&response), Some(Code::Unauthenticated));
+    }
+
+    #[tokio::test]
+    async fn token_of_deleted_user_is_rejected() {
+        let fixture = fixture(&[], &[]).await;
+        let user_id = create_user(&fixture, "gone@example.com").await;
+        let token = fixture.jwt.generate_token(user_id as i64).unwrap();
+
+        fixture.repos.user.delete_user(user_id).await.unwrap();
+        let response = call(&fixture, CREATE_TRANSFER, Some(&token)).await;
+
+        assert_eq!(grpc_code(&response), Some(Code::Unauthenticated));
+    }
+
+    #[tokio::test]
+    async fn token_issued_before_password_change_is_rejected() {
+        let fixture = fixture(&[], &[]).await;
+        let user_id = create_user(&fixture, "rotated@example.com").await;
+
+        // token diterbitkan satu menit sebelum password diganti
+        let now = Utc::now().timestamp() as usize;
+        let claims = Claims::new(user_id as i64, now + 3600, now - 60);
+        let token = encode(
+            &Header::default(),
+            &claims,
+            &EncodingKey::from_secret(SECRET.as_bytes()),
+        )
+        .unwrap();
+
+        fixture
+            .repos
+            .user
+            .update_password(user_id, "new-password-hash")
+            .await
+            .unwrap();
+        let response = call(&fixture, CREATE_TRANSFER, Some(&token)).await;
+        assert_eq!(grpc_code(&response), Some(Code::Unauthenticated));
+
+        let fresh = fixture.jwt.generate_token(user_id as i64).unwrap();
+        let response = call(&fixture, CREATE_TRANSFER, Some(&fresh)).await;
+        assert_eq!(grpc_code(&response), None);
+    }
+}
@@ -0,0 +1,100 @@
+use axum::http::Request;
+use shared::utils::with_cancellation;
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tokio_util::sync::CancellationToken;
+use tower::{Layer, Service};
+
+// hyper membuang future handler saat client me-reset stream; drop guard di sini
+// ikut membatalkan token sehingga query yang dibungkus `cancellable` berhenti
+#[derive(Debug, Clone, Default)]
+pub struct CancellationLayer;
+
+impl<S> Layer<S> for CancellationLayer {
+    type Service = CancellationService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CancellationService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct CancellationService<S> {
+    inner: S,
+}
+
+impl<S, B> Service<Request<B>> for CancellationService<S>
+where
+    S: Service<Request<B>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let token = CancellationToken::new();
+        let guard = token.clone().drop_guard();
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = with_cancellation(token, future).await;
+            // request selesai normal, token tidak perlu dibatalkan
+            guard.disarm();
+            response
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::utils::current_cancellation;
+    use std::{convert::Infallible, time::Duration};
+    use tokio::sync::mpsc;
+    use tower::{ServiceExt, service_fn};
+
+    // handler tiruan mengirim token request lalu menunggu `hold` sebelum selesai
+    async fn call(hold: Duration) -> (Option<CancellationToken>, bool) {
+        let (tx, mut rx) = mpsc::unbounded_channel();
+        let service = CancellationLayer.layer(service_fn(move |_: Request<()>| {
+            let tx = tx.clone();
+            async move {
+                tx.send(current_cancellation()).unwrap();
+                tokio::time::sleep(hold).await;
+                Ok::<_, Infallible>(())
+            }
+        }));
+
+        let request = service.oneshot(Request::new(()));
+        let completed = tokio::time::timeout(Duration::from_millis(50), request)
+            .await
+            .is_ok();
+
+        (rx.recv().await.unwrap(), completed)
+    }
+
+    #[tokio::test]
+    async fn completed_request_leaves_the_token_alone() {
+        let (token, completed) = call(Duration::ZERO).await;
+
+        assert!(completed);
+        assert!(!token.expect("token is in scope").is_cancelled());
+    }
+
+    #[tokio::test]
+    async fn dropped_request_cancels_the_token() {
+        // timeout membuang future seperti hyper saat client me-reset stream
+        let (token, completed) = call(Duration::from_secs(5)).await;
+
+        assert!(!completed);
+        assert!(token.expect("token is in scope").is_cancelled());
+    }
+}
@@ -1,27 +1,79 @@
-use anyhow::{Context, Result};
-use shared::config::Config;
+use anyhow::Result;
+use shared::{
+    config::{
+        CacheTtlConfig, Config, GrpcKeepaliveConfig, HashAlgorithm, JwtConfig, PoolConfig,
+        TransferVelocityConfig, WebhookConfig, WriteRateLimitConfig,
+    },
+    repository::retry::SqlRetryPolicy,
+    utils::ConcurrencyLimit,
+};
+use std::{net::SocketAddr, time::Duration};
 
 #[derive(Debug, Clone)]
 pub struct ServerConfig {
-    pub grpc_addr: std::net::SocketAddr,
-    pub metrics_addr: std::net::SocketAddr,
+    pub grpc_addrs: Vec<SocketAddr>,
+    pub metrics_addrs: Vec<SocketAddr>,
     pub database_url: String,
-    pub jwt_secret: String,
+    pub database_read_url: Option<String>,
+    pub jwt: JwtConfig,
     pub run_migrations: bool,
+    pub metrics_buckets: Vec<f64>,
+    pub pool_config: PoolConfig,
+    pub sql_retry_policy: SqlRetryPolicy,
+    pub max_page_size: i32,
+    pub default_page_size: i32,
+    pub min_transfer_amount: i32,
+    pub cache_ttl: CacheTtlConfig,
+    pub webhook: WebhookConfig,
+    pub write_rate_limit: WriteRateLimitConfig,
+    pub saldo_metrics_interval: Duration,
+    pub grpc_web_enabled: bool,
+    pub grpc_keepalive: GrpcKeepaliveConfig,
+    pub grpc_max_decoding_size: usize,
+    pub grpc_max_encoding_size: usize,
+    pub grpc_json_addr: Option<SocketAddr>,
+    pub grpc_auth_allowlist: Vec<String>,
+    pub admin_user_ids: Vec<i32>,
+    pub cors_origins: Vec<String>,
+    pub concurrency_limit: ConcurrencyLimit,
+    pub shutdown_grace: Duration,
+    pub shutdown_timeout: Duration,
+    pub hash_algorithm: HashAlgorithm,
+    pub transfer_velocity: TransferVelocityConfig,
 }
 
 impl ServerConfig {
     pub fn from_config(config: &Config) -> Result<Self> {
         Ok(Self {
-            grpc_addr: format!("0.0.0.0:{}", config.grpc_port)
-                .parse()
-                .context("Invalid gRPC address")?,
-            metrics_addr: format!("0.0.0.0:{}", config.metric_port)
-                .parse()
-                .context("Invalid metrics address")?,
+            grpc_addrs: config.grpc_addrs.clone(),
+            metrics_addrs: config.metric_addrs.clone(),
             database_url: config.database_url.clone(),
-            jwt_secret: config.jwt_secret.clone(),
+            database_read_url: config.database_read_url.clone(),
+            jwt: config.jwt_config(),
             run_migrations: config.run_migrations,
+            metrics_buckets: config.metrics_buckets.clone(),
+            pool_config: config.pool_config(),
+            sql_retry_policy: config.sql_retry_policy(),
+            max_page_size: config.max_page_size,
+            default_page_size: config.default_page_size,
+            min_transfer_amount: config.min_transfer_amount,
+            cache_ttl: config.cache_ttl,
+            webhook: config.webhook.clone(),
+            write_rate_limit: config.write_rate_limit,
+            saldo_metrics_interval: Duration::from_secs(config.saldo_metrics_interval_secs),
+            grpc_web_enabled: config.grpc_web_enabled,
+            grpc_keepalive: config.grpc_keepalive,
+            grpc_max_decoding_size: config.grpc_max_decoding_size,
+            grpc_max_encoding_size: config.grpc_max_encoding_size,
+            grpc_json_addr: config.grpc_json_addr,
+            grpc_auth_allowlist: config.grpc_auth_allowlist.clone(),
+            admin_user_ids: config.admin_user_ids.clone(),
+            cors_origins: config.cors_origins.clone(),
+            concurrency_limit: config.concurrency_limit(),
+            shutdown_grace: Duration::from_secs(config.shutdown_grace_secs),
+            shutdown_timeout: Duration::from_secs(config.shutdown_timeout_secs),
+            hash_algorithm: config.hash_algorithm,
+            transfer_velocity: config.transfer_velocity,
         })
     }
 }
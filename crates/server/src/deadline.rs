@@ -0,0 +1,108 @@
+use axum::http::Request;
+use shared::utils::{GRPC_TIMEOUT_HEADER, parse_grpc_timeout, with_deadline};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::time::Instant;
+use tower::{Layer, Service};
+
+// tonic sendiri membalas CANCELLED saat grpc-timeout habis; deadline untuk query dimajukan
+// sedikit supaya `cancellable` lebih dulu mengembalikan DEADLINE_EXCEEDED
+const DEADLINE_MARGIN: Duration = Duration::from_millis(20);
+
+// grpc-timeout dari gateway dipasang sebagai deadline task-local sehingga query
+// yang dibungkus `cancellable` berhenti begitu waktunya habis
+#[derive(Debug, Clone, Default)]
+pub struct DeadlineLayer;
+
+impl<S> Layer<S> for DeadlineLayer {
+    type Service = DeadlineService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        DeadlineService { inner }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct DeadlineService<S> {
+    inner: S,
+}
+
+impl<S, B> Service<Request<B>> for DeadlineService<S>
+where
+    S: Service<Request<B>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let timeout = request
+            .headers()
+            .get(GRPC_TIMEOUT_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(parse_grpc_timeout);
+
+        let future = self.inner.call(request);
+
+        match timeout {
+            Some(timeout) => {
+                let deadline = Instant::now() + timeout.saturating_sub(DEADLINE_MARGIN);
+                Box::pin(with_deadline(deadline, future))
+            }
+            None => Box::pin(future),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::utils::{AppError, cancellable};
+    use tower::{ServiceExt, service_fn};
+
+    // query tiruan yang butuh `work` sebelum selesai, dibungkus `cancellable` seperti di service
+    async fn call(grpc_timeout: Option<&str>, work: Duration) -> Result<(), AppError> {
+        let service = DeadlineLayer.layer(service_fn(move |_: Request<()>| async move {
+            Ok::<_, std::convert::Infallible>(
+                cancellable(async {
+                    tokio::time::sleep(work).await;
+                    Ok(())
+                })
+                .await,
+            )
+        }));
+
+        let mut request = Request::builder();
+        if let Some(timeout) = grpc_timeout {
+            request = request.header(GRPC_TIMEOUT_HEADER, timeout);
+        }
+
+        service.oneshot(request.body(()).unwrap()).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn work_within_the_deadline_completes() {
+        let result = call(Some("1S"), Duration::from_millis(10)).await;
+
+        assert!(result.is_ok());
+    }
+
+    #[tokio::test]
+    async fn work_past_the_grpc_timeout_is_deadline_exceeded() {
+        let started = Instant::now();
+
+        let result = call(Some("50m"), Duration::from_secs(5)).await;
+
+        assert!(matches!(result, Err(AppError::DeadlineExceeded)));
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
+}
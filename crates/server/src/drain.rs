@@ -0,0 +1,222 @@
+use shared::utils::ConcurrencyLimit;
+use std::{
+    sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+use tokio::task::JoinHandle;
+use tonic_health::{ServingStatus, server::HealthReporter};
+use tracing::{info, warn};
+
+// ditandai saat shutdown dimulai; request baru ditolak UNAVAILABLE supaya client pindah instance
+#[derive(Debug, Clone, Default)]
+pub struct DrainSignal(Arc<AtomicBool>);
+
+impl DrainSignal {
+    pub fn start(&self) {
+        self.0.store(true, Ordering::SeqCst);
+    }
+
+    pub fn is_draining(&self) -> bool {
+        self.0.load(Ordering::SeqCst)
+    }
+}
+
+// transfer multi-langkah yang sedang berjalan dibiarkan selesai sebelum listener ditutup
+pub async fn drain_in_flight(
+    health: &HealthReporter,
+    signal: &DrainSignal,
+    limit: &ConcurrencyLimit,
+    grace: Duration,
+) {
+    // service "" mewakili status server secara keseluruhan
+    health
+        .set_service_status("", ServingStatus::NotServing)
+        .await;
+    drain_requests(signal, limit, grace).await;
+}
+
+// tanpa health reporter, dipakai juga oleh facade JSON yang berbagi batas konkurensi yang sama
+pub async fn drain_requests(signal: &DrainSignal, limit: &ConcurrencyLimit, grace: Duration) {
+    signal.start();
+
+    info!(
+        "🚰 Draining in-flight requests (grace period {}s)",
+        grace.as_secs()
+    );
+
+    match tokio::time::timeout(grace, limit.wait_idle()).await {
+        Ok(()) => info!("✅ All in-flight requests finished"),
+        Err(_) => warn!(
+            "⚠️  Drain grace period of {}s elapsed with requests still in flight",
+            grace.as_secs()
+        ),
+    }
+}
+
+// menunggu semua task server berhenti; bila melewati batas, task yang tersisa di-abort dan
+// hasilnya false supaya pemanggil bisa keluar paksa setelah telemetry di-flush
+pub async fn await_shutdown(handles: Vec<JoinHandle<()>>, timeout: Duration) -> bool {
+    let aborts: Vec<_> = handles.iter().map(JoinHandle::abort_handle).collect();
+
+    let joined = tokio::time::timeout(timeout, async {
+        for handle in handles {
+            let _ = handle.await;
+        }
+    })
+    .await;
+
+    if joined.is_ok() {
+        return true;
+    }
+
+    let stuck = aborts.iter().filter(|handle| !handle.is_finished()).count();
+    warn!(
+        "⚠️  Shutdown timeout of {}s reached, aborting {stuck} task(s) still running",
+        timeout.as_secs()
+    );
+    for handle in &aborts {
+        handle.abort();
+    }
+
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::load_shed::LoadShedLayer;
+    use axum::http::{Request, Response};
+    use std::{convert::Infallible, sync::atomic::AtomicI32};
+    use tonic::Code;
+    use tower::{Layer, ServiceExt, service_fn};
+
+    // transfer tiruan dua langkah: debit, jeda, lalu kredit
+    fn transfer_service(
+        limit: ConcurrencyLimit,
+        signal: DrainSignal,
+        balance: Arc<AtomicI32>,
+        step: Duration,
+    ) -> impl tower::Service<
+        Request<()>,
+        Response = Response<String>,
+        Error = Infallible,
+        Future: Send + 'static,
+    > + Clone
+    + Send
+    + 'static {
+        let handler = service_fn(move |_: Request<()>| {
+            let balance = balance.clone();
+            async move {
+                balance.fetch_sub(100, Ordering::SeqCst);
+                tokio::time::sleep(step).await;
+                balance.fetch_add(100, Ordering::SeqCst);
+                Ok::<_, Infallible>(Response::new(String::new()))
+            }
+        });
+        LoadShedLayer::new(limit, signal).layer(handler)
+    }
+
+    fn grpc_code(response: &Response<String>) -> Option<Code> {
+        tonic::Status::from_header_map(response.headers()).map(|status| status.code())
+    }
+
+    #[tokio::test]
+    async fn transfer_started_before_shutdown_completes() {
+        let (limit, signal) = (
+            ConcurrencyLimit::new(4, Duration::from_secs(1)),
+            DrainSignal::default(),
+        );
+        let balance = Arc::new(AtomicI32::new(0));
+        let service = transfer_service(
+            limit.clone(),
+            signal.clone(),
+            balance.clone(),
+            Duration::from_millis(100),
+        );
+        let (health, _) = tonic_health::server::health_reporter();
+
+        let in_flight = tokio::spawn(service.clone().oneshot(Request::new(())));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert_eq!(balance.load(Ordering::SeqCst), -100);
+
+        let drain = tokio::spawn({
+            let (signal, limit) = (signal.clone(), limit.clone());
+            async move { drain_in_flight(&health, &signal, &limit, Duration::from_secs(5)).await }
+        });
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        // request baru selama drain langsung ditolak
+        let rejected = service.oneshot(Request::new(())).await.unwrap();
+        assert_eq!(grpc_code(&rejected), Some(Code::Unavailable));
+
+        drain.await.unwrap();
+        assert!(in_flight.is_finished());
+        assert_eq!(grpc_code(&in_flight.await.unwrap().unwrap()), None);
+        assert_eq!(balance.load(Ordering::SeqCst), 0);
+    }
+
+    #[tokio::test]
+    async fn drain_gives_up_after_the_grace_period() {
+        let (limit, signal) = (
+            ConcurrencyLimit::new(4, Duration::from_secs(1)),
+            DrainSignal::default(),
+        );
+        let service = transfer_service(
+            limit.clone(),
+            signal.clone(),
+            Arc::new(AtomicI32::new(0)),
+            Duration::from_secs(30),
+        );
+        let _stuck = tokio::spawn(service.oneshot(Request::new(())));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let started = tokio::time::Instant::now();
+        drain_requests(&signal, &limit, Duration::from_millis(100)).await;
+
+        assert!(started.elapsed() < Duration::from_secs(1));
+        assert!(signal.is_draining());
+    }
+
+    // ikut di-drop saat task yang memegangnya di-abort
+    struct DropFlag(Arc<AtomicBool>);
+
+    impl Drop for DropFlag {
+        fn drop(&mut self) {
+            self.0.store(true, Ordering::SeqCst);
+        }
+    }
+
+    #[tokio::test]
+    async fn finished_tasks_shut_down_gracefully() {
+        let handles = vec![
+            tokio::spawn(async {}),
+            tokio::spawn(tokio::time::sleep(Duration::from_millis(20))),
+        ];
+
+        assert!(await_shutdown(handles, Duration::from_secs(5)).await);
+    }
+
+    #[tokio::test]
+    async fn stuck_task_is_aborted_once_the_timeout_elapses() {
+        let dropped = Arc::new(AtomicBool::new(false));
+        let stuck = tokio::spawn({
+            let flag = DropFlag(dropped.clone());
+            async move {
+                let _flag = flag;
+                std::future::pending::<()>().await
+            }
+        });
+        let handles = vec![tokio::spawn(async {}), stuck];
+
+        let started = tokio::time::Instant::now();
+        let graceful = await_shutdown(handles, Duration::from_millis(100)).await;
+
+        assert!(!graceful);
+        assert!(started.elapsed() < Duration::from_secs(1));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        assert!(dropped.load(Ordering::SeqCst));
+    }
+}
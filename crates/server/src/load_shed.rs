@@ -0,0 +1,164 @@
+use axum::http::{HeaderValue, Request, Response};
+use shared::utils::{ConcurrencyLimit, RETRY_AFTER_HEADER};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tower::{Layer, Service};
+use tracing::warn;
+
+use crate::drain::DrainSignal;
+
+// probe health harus tetap dijawab, dan stream Watch tidak boleh menahan drain
+const HEALTH_SERVICE_PREFIX: &str = "/grpc.health.v1.Health/";
+
+// tanpa batas ini lonjakan request menghabiskan pool DB dan semuanya timeout;
+// request di atas batas langsung dijawab RESOURCE_EXHAUSTED tanpa diantrekan
+#[derive(Debug, Clone)]
+pub struct LoadShedLayer {
+    limit: ConcurrencyLimit,
+    drain: DrainSignal,
+}
+
+impl LoadShedLayer {
+    pub fn new(limit: ConcurrencyLimit, drain: DrainSignal) -> Self {
+        Self { limit, drain }
+    }
+}
+
+impl<S> Layer<S> for LoadShedLayer {
+    type Service = LoadShedService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        LoadShedService {
+            inner,
+            limit: self.limit.clone(),
+            drain: self.drain.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LoadShedService<S> {
+    inner: S,
+    limit: ConcurrencyLimit,
+    drain: DrainSignal,
+}
+
+impl<S, B, ResBody> Service<Request<B>> for LoadShedService<S>
+where
+    S: Service<Request<B>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+    ResBody: Default + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        if request.uri().path().starts_with(HEALTH_SERVICE_PREFIX) {
+            return Box::pin(self.inner.call(request));
+        }
+
+        if self.drain.is_draining() {
+            warn!("🚰 Rejecting {} | server is draining", request.uri().path());
+
+            let response =
+                tonic::Status::unavailable("Server is shutting down").into_http::<ResBody>();
+            return Box::pin(async move { Ok(response) });
+        }
+
+        let Some(permit) = self.limit.try_acquire() else {
+            warn!(
+                "🚦 Shedding {} | {} requests already in flight",
+                request.uri().path(),
+                self.limit.max_concurrent()
+            );
+
+            let retry_after = self.limit.retry_after_secs();
+            let mut response = tonic::Status::resource_exhausted(format!(
+                "Server is overloaded, retry after {retry_after}s"
+            ))
+            .into_http::<ResBody>();
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER_HEADER, HeaderValue::from(retry_after));
+
+            return Box::pin(async move { Ok(response) });
+        };
+
+        let future = self.inner.call(request);
+
+        Box::pin(async move {
+            let response = future.await;
+            drop(permit);
+            response
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{convert::Infallible, sync::Arc, time::Duration};
+    use tokio::sync::Semaphore;
+    use tonic::Code;
+    use tower::{ServiceExt, service_fn};
+
+    // handler gRPC tiruan yang ditahan sampai `gate` dibuka
+    fn service(
+        limit: ConcurrencyLimit,
+        gate: Arc<Semaphore>,
+    ) -> impl Service<
+        Request<()>,
+        Response = Response<String>,
+        Error = Infallible,
+        Future: Send + 'static,
+    > + Clone
+    + Send
+    + 'static {
+        let handler = service_fn(move |_: Request<()>| {
+            let gate = gate.clone();
+            async move {
+                let _ = gate.acquire().await.unwrap();
+                Ok::<_, Infallible>(Response::new(String::new()))
+            }
+        });
+        LoadShedLayer::new(limit, DrainSignal::default()).layer(handler)
+    }
+
+    fn grpc_code(response: &Response<String>) -> Option<Code> {
+        tonic::Status::from_header_map(response.headers()).map(|status| status.code())
+    }
+
+    #[tokio::test]
+    async fn calls_beyond_the_limit_are_resource_exhausted_immediately() {
+        let limit = ConcurrencyLimit::new(1, Duration::from_secs(2));
+        let gate = Arc::new(Semaphore::new(0));
+        let service = service(limit, gate.clone());
+
+        let in_flight = tokio::spawn(service.clone().oneshot(Request::new(())));
+        tokio::time::sleep(Duration::from_millis(20)).await;
+
+        let shed = tokio::time::timeout(
+            Duration::from_millis(200),
+            service.clone().oneshot(Request::new(())),
+        )
+        .await
+        .expect("excess call should be rejected immediately")
+        .unwrap();
+        assert_eq!(grpc_code(&shed), Some(Code::ResourceExhausted));
+        assert_eq!(shed.headers()[RETRY_AFTER_HEADER], "2");
+
+        gate.add_permits(2);
+        let served = in_flight.await.unwrap().unwrap();
+        assert_eq!(grpc_code(&served), None);
+        let served = service.oneshot(Request::new(())).await.unwrap();
+        assert_eq!(grpc_code(&served), None);
+    }
+}
@@ -1,35 +1,88 @@
 use anyhow::{Context, Result};
 use axum::{
-    Router,
+    Json, Router,
     body::Body,
     extract::State,
-    http::{HeaderValue, StatusCode, header::CONTENT_TYPE},
+    http::{
+        HeaderName, HeaderValue, Method, StatusCode,
+        header::{AUTHORIZATION, CONTENT_TYPE},
+    },
     response::{IntoResponse, Response},
 };
 use genproto::{
+    account::account_service_server::AccountServiceServer,
     auth::auth_service_server::AuthServiceServer, saldo::saldo_service_server::SaldoServiceServer,
+    system::system_service_server::SystemServiceServer,
     topup::topup_service_server::TopupServiceServer,
     transfer::transfer_service_server::TransferServiceServer,
     user::user_service_server::UserServiceServer,
+    virtual_card::virtual_card_service_server::VirtualCardServiceServer,
+    webhook::webhook_service_server::WebhookServiceServer,
     withdraw::withdraw_service_server::WithdrawServiceServer,
 };
 use prometheus_client::encoding::text::encode;
 use shared::{
-    config::{Config, ConnectionManager},
+    abstract_trait::DynOutboxRepository,
+    cache::CacheStore,
+    config::{Config, ConnectionManager, DatabasePools, GrpcKeepaliveConfig},
+    domain::{
+        request::{install_default_page_size, install_max_page_size, install_min_transfer_amount},
+        response::system::RuntimeConfigResponse,
+    },
+    repository::outbox::OutboxRepository,
     state::AppState,
     utils::Telemetry,
-    utils::init_logger,
+    utils::{
+        ConcurrencyLimit, HealthChecker, REQUEST_ID_HEADER, check_database, check_redis,
+        init_logger, run_outbox_worker,
+    },
 };
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::sync::broadcast;
-use tracing::{error, info, warn};
-
-use crate::{config::ServerConfig, service::ServiceContainer};
+use tonic_web::GrpcWebLayer;
+use tower::util::option_layer;
+use tower_http::{
+    cors::{AllowOrigin, CorsLayer},
+    trace::TraceLayer,
+};
+use tracing::{Span, error, info, info_span, warn};
+
+use crate::{
+    auth::AuthLayer,
+    cancellation::CancellationLayer,
+    config::ServerConfig,
+    deadline::DeadlineLayer,
+    drain::{DrainSignal, await_shutdown, drain_in_flight, drain_requests},
+    load_shed::LoadShedLayer,
+    probe::{ProbeState, livez, not_ready, readyz, status, version},
+    rate_limit::RateLimitLayer,
+    replica::ReplicaReadLayer,
+    service::ServiceContainer,
+    trace::TraceContextLayer,
+    transcode::grpc_rejection_to_json,
+};
 
+mod auth;
+mod cancellation;
 mod config;
+mod deadline;
+mod drain;
+mod load_shed;
+mod probe;
+mod rate_limit;
+mod replica;
 mod service;
+mod trace;
+mod transcode;
+
+// sisa waktu untuk mengirim span dan log terakhir sebelum proses selesai
+const TELEMETRY_FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub async fn metrics_handler(State(probe): State<ProbeState>) -> Response {
+    let Some(state) = probe.app() else {
+        return not_ready();
+    };
 
-pub async fn metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoResponse {
     let mut buffer = String::new();
     let registry = state.registry.lock().await;
 
@@ -50,8 +103,26 @@ pub async fn metrics_handler(State(state): State<Arc<AppState>>) -> impl IntoRes
         .unwrap()
 }
 
-async fn health_check() -> &'static str {
-    "OK"
+async fn health_check(State(probe): State<ProbeState>) -> Response {
+    let Some(state) = probe.app() else {
+        return not_ready();
+    };
+
+    let report = HealthChecker::new()
+        .check("database", check_database(&state.db_pool))
+        .await
+        .check("redis", check_redis(&state.redis))
+        .await
+        .report();
+
+    let status = if report.is_healthy() {
+        StatusCode::OK
+    } else {
+        warn!("Health check failed: {:?}", report.checks);
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(report)).into_response()
 }
 
 #[tokio::main]
@@ -59,75 +130,225 @@ async fn main() -> Result<()> {
     dotenv::dotenv().ok();
 
     let config = Config::init().context("Failed to load configuration")?;
+    config.validate()?;
     let server_config = ServerConfig::from_config(&config)?;
 
-    let telemetry = Telemetry::new("payment-service", "http://otel-collector:4317".to_string());
+    let telemetry = Telemetry::new(
+        "payment-service",
+        "http://otel-collector:4317".to_string(),
+        config.otel_sample_ratio,
+    );
 
     let logger_provider = telemetry.init_logger();
     let _meter_provider = telemetry.init_meter();
     let _tracer_provider = telemetry.init_tracer();
 
-    init_logger(logger_provider.clone(), "payment-service");
+    init_logger(logger_provider.clone(), "payment-service", &config.log);
 
     info!("🚀 Starting Payment Service initialization...");
 
-    let db_pool =
-        ConnectionManager::new_pool(&server_config.database_url, server_config.run_migrations)
-            .await
-            .context("Failed to initialize database pool")?;
+    let (shutdown_tx, _) = broadcast::channel(1);
+
+    // probe dan metrics dijalankan lebih dulu supaya /readyz bisa menjawab 503 selama startup
+    let probe = ProbeState::default();
+    // satu task per alamat, semuanya berhenti lewat broadcast shutdown yang sama
+    let metrics_handles = server_config
+        .metrics_addrs
+        .iter()
+        .copied()
+        .map(|metrics_addr| {
+            let probe = probe.clone();
+            let metrics_shutdown_rx = shutdown_tx.subscribe();
+            tokio::spawn(async move {
+                loop {
+                    info!("🔧 Starting metrics server on {metrics_addr}");
+                    match start_metrics_server(
+                        probe.clone(),
+                        metrics_addr,
+                        metrics_shutdown_rx.resubscribe(),
+                    )
+                    .await
+                    {
+                        Ok(()) => {
+                            info!("Metrics server on {metrics_addr} stopped gracefully");
+                            break;
+                        }
+                        Err(e) => {
+                            error!("❌ Metrics server failed: {e}. Retrying in 3s...");
+                            tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
+                        }
+                    }
+                }
+            })
+        })
+        .collect::<Vec<_>>();
+
+    server_config.sql_retry_policy.install();
+    install_max_page_size(server_config.max_page_size);
+    install_default_page_size(server_config.default_page_size);
+    install_min_transfer_amount(server_config.min_transfer_amount);
+
+    let db_pool = ConnectionManager::new_pool(
+        &server_config.database_url,
+        server_config.run_migrations,
+        server_config.pool_config,
+    )
+    .await
+    .context("Failed to initialize database pool")?;
+
+    let read_replica = match &server_config.database_read_url {
+        Some(read_url) => Some(
+            ConnectionManager::new_read_pool(read_url, server_config.pool_config)
+                .await
+                .context("Failed to initialize read replica pool")?,
+        ),
+        None => None,
+    };
+    let pools = DatabasePools::new(db_pool, read_replica);
+    if pools.read.has_replica() {
+        info!("📚 Read-only RPCs are served from the read replica");
+    }
 
     let state = Arc::new(
-        AppState::new(db_pool, &server_config.jwt_secret)
-            .await
-            .context("Failed to create AppState")?,
+        AppState::new(
+            pools.clone(),
+            server_config.jwt.clone(),
+            server_config.metrics_buckets.clone(),
+            server_config.cache_ttl,
+            server_config.saldo_metrics_interval,
+            server_config.hash_algorithm,
+            server_config.transfer_velocity,
+        )
+        .await
+        .context("Failed to create AppState")?,
     );
 
-    let services = ServiceContainer::new(state.clone());
+    probe.mark_ready(state.clone());
+    info!("✅ Payment Service is ready");
 
-    let (shutdown_tx, _) = broadcast::channel(1);
+    let services = ServiceContainer::new(
+        state.clone(),
+        RuntimeConfigResponse::from(&config),
+        CacheStore::new(state.redis.client.clone(), server_config.cache_ttl),
+    );
+
+    let grpc_web = if server_config.grpc_web_enabled {
+        info!(
+            "🌐 gRPC-Web enabled, allowed origins: {:?}",
+            server_config.cors_origins
+        );
+        Some(grpc_web_cors(&server_config.cors_origins)?)
+    } else {
+        None
+    };
 
     // 🛰️ gRPC server
-    let grpc_addr = server_config.grpc_addr;
-    let grpc_shutdown_rx = shutdown_tx.subscribe();
-    let grpc_handle = tokio::spawn(async move {
-        loop {
-            match start_grpc_server(services.clone(), grpc_addr, grpc_shutdown_rx.resubscribe())
-                .await
-            {
-                Ok(()) => {
-                    info!("gRPC server stopped gracefully");
-                    break;
+    let options = GrpcServerOptions {
+        grpc_web,
+        auth: AuthLayer::new(
+            state.jwt_config.clone(),
+            state.di_container.user_service.clone(),
+            &server_config.grpc_auth_allowlist,
+            &server_config.admin_user_ids,
+        ),
+        rate_limit: RateLimitLayer::new(
+            CacheStore::new(state.redis.client.clone(), server_config.cache_ttl),
+            server_config.write_rate_limit,
+        ),
+        replica_read: pools.read.has_replica().then(|| {
+            ReplicaReadLayer::new(CacheStore::new(
+                state.redis.client.clone(),
+                server_config.cache_ttl,
+            ))
+        }),
+        concurrency_limit: server_config.concurrency_limit.clone(),
+        shutdown_grace: server_config.shutdown_grace,
+        keepalive: server_config.grpc_keepalive,
+        max_decoding_size: server_config.grpc_max_decoding_size,
+        max_encoding_size: server_config.grpc_max_encoding_size,
+    };
+    info!(
+        "🚦 gRPC concurrency limit: {} in-flight requests",
+        options.concurrency_limit.max_concurrent()
+    );
+    info!(
+        "📦 gRPC message size limit: {} bytes in, {} bytes out",
+        options.max_decoding_size, options.max_encoding_size
+    );
+    info!(
+        "🔒 gRPC auth required except for {} allowlisted read-only methods",
+        server_config.grpc_auth_allowlist.len()
+    );
+    let grpc_handles = server_config
+        .grpc_addrs
+        .iter()
+        .copied()
+        .map(|grpc_addr| {
+            let services = services.clone();
+            let options = options.clone();
+            let grpc_shutdown_rx = shutdown_tx.subscribe();
+            tokio::spawn(async move {
+                loop {
+                    match start_grpc_server(
+                        services.clone(),
+                        grpc_addr,
+                        options.clone(),
+                        grpc_shutdown_rx.resubscribe(),
+                    )
+                    .await
+                    {
+                        Ok(()) => {
+                            info!("gRPC server on {grpc_addr} stopped gracefully");
+                            break;
+                        }
+                        Err(e) => {
+                            error!("❌ gRPC server failed: {e}. Restarting in 5s...");
+                            tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                        }
+                    }
                 }
-                Err(e) => {
-                    error!("❌ gRPC server failed: {e}. Restarting in 5s...");
-                    tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+            })
+        })
+        .collect::<Vec<_>>();
+
+    // 🧩 JSON transcoding facade
+    let json_handle = server_config.grpc_json_addr.map(|json_addr| {
+        let services = services.clone();
+        let options = options.clone();
+        let json_shutdown_rx = shutdown_tx.subscribe();
+        tokio::spawn(async move {
+            loop {
+                match start_json_server(
+                    services.clone(),
+                    json_addr,
+                    options.clone(),
+                    json_shutdown_rx.resubscribe(),
+                )
+                .await
+                {
+                    Ok(()) => {
+                        info!("JSON transcoding server on {json_addr} stopped gracefully");
+                        break;
+                    }
+                    Err(e) => {
+                        error!("❌ JSON transcoding server failed: {e}. Restarting in 5s...");
+                        tokio::time::sleep(tokio::time::Duration::from_secs(5)).await;
+                    }
                 }
             }
-        }
+        })
     });
 
-    let metrics_addr = server_config.metrics_addr;
-    let state_clone = state.clone();
-    let metrics_shutdown_rx = shutdown_tx.subscribe();
-    let metrics_handle = tokio::spawn(async move {
-        loop {
-            info!("🔧 Starting metrics server on {metrics_addr}");
-            match start_metrics_server(
-                state_clone.clone(),
-                metrics_addr,
-                metrics_shutdown_rx.resubscribe(),
-            )
-            .await
-            {
-                Ok(()) => {
-                    info!("Metrics server stopped gracefully");
-                    break;
-                }
-                Err(e) => {
-                    error!("❌ Metrics server failed: {e}. Retrying in 3s...");
-                    tokio::time::sleep(tokio::time::Duration::from_secs(3)).await;
-                }
-            }
+    // 📮 Outbox webhook worker
+    let outbox_repository =
+        Arc::new(OutboxRepository::new(state.db_pool.clone())) as DynOutboxRepository;
+    let webhook_config = server_config.webhook.clone();
+    let outbox_shutdown_rx = shutdown_tx.subscribe();
+    let outbox_handle = tokio::spawn(async move {
+        if let Err(e) =
+            run_outbox_worker(outbox_repository, webhook_config, outbox_shutdown_rx).await
+        {
+            error!("❌ Outbox worker failed: {e}");
         }
     });
 
@@ -149,21 +370,28 @@ async fn main() -> Result<()> {
 
     info!("🛑 Shutting down all servers...");
 
-    let shutdown_timeout = tokio::time::Duration::from_secs(30);
-    let shutdown_result = tokio::time::timeout(shutdown_timeout, async {
-        let _ = tokio::join!(grpc_handle, metrics_handle);
-    })
-    .await;
+    let handles = grpc_handles
+        .into_iter()
+        .chain(metrics_handles)
+        .chain(json_handle)
+        .chain([outbox_handle])
+        .collect();
+    let graceful = await_shutdown(handles, server_config.shutdown_timeout).await;
 
-    match shutdown_result {
-        Ok(()) => info!("✅ All servers shutdown gracefully"),
-        Err(_) => {
-            warn!("⚠️  Shutdown timeout reached, forcing exit");
-        }
+    if graceful {
+        info!("✅ All servers shutdown gracefully");
     }
 
-    if let Err(e) = telemetry.shutdown().await {
-        error!("Failed to shutdown telemetry: {}", e);
+    // flush telemetry juga dibatasi; collector yang mati tidak boleh menahan exit paksa
+    match tokio::time::timeout(TELEMETRY_FLUSH_TIMEOUT, telemetry.shutdown()).await {
+        Ok(Ok(())) => {}
+        Ok(Err(e)) => error!("Failed to shutdown telemetry: {}", e),
+        Err(_) => error!("Telemetry flush timed out"),
+    }
+
+    if !graceful {
+        error!("❌ Payment Service did not stop within the shutdown timeout, exiting");
+        std::process::exit(1);
     }
 
     info!("✅ Payment Service shutdown complete.");
@@ -171,32 +399,224 @@ async fn main() -> Result<()> {
     Ok(())
 }
 
+// header yang dibutuhkan client gRPC-Web dari browser
+fn grpc_web_cors(origins: &[String]) -> Result<CorsLayer> {
+    let origins = origins
+        .iter()
+        .map(|origin| {
+            origin
+                .parse::<HeaderValue>()
+                .with_context(|| format!("Invalid CORS origin: '{origin}'"))
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(CorsLayer::new()
+        .allow_origin(AllowOrigin::list(origins))
+        .allow_methods([Method::POST, Method::OPTIONS])
+        .allow_headers([
+            CONTENT_TYPE,
+            AUTHORIZATION,
+            HeaderName::from_static("x-grpc-web"),
+            HeaderName::from_static("x-user-agent"),
+            HeaderName::from_static("grpc-timeout"),
+        ])
+        .expose_headers([
+            HeaderName::from_static("grpc-status"),
+            HeaderName::from_static("grpc-message"),
+            HeaderName::from_static("grpc-status-details-bin"),
+        ])
+        .max_age(Duration::from_secs(60 * 60 * 24)))
+}
+
+// request id dari gateway ikut tercatat di setiap log handler gRPC
+fn grpc_request_span<B>(request: &axum::http::Request<B>) -> Span {
+    let request_id = request
+        .headers()
+        .get(REQUEST_ID_HEADER)
+        .and_then(|value| value.to_str().ok())
+        .unwrap_or("-");
+
+    info_span!(
+        "grpc_request",
+        request_id = %request_id,
+        path = %request.uri().path(),
+    )
+}
+
+// layer dan batas server gRPC yang dirakit dari konfigurasi, dipakai ulang setiap restart
+#[derive(Clone)]
+struct GrpcServerOptions {
+    grpc_web: Option<CorsLayer>,
+    auth: AuthLayer,
+    rate_limit: RateLimitLayer,
+    replica_read: Option<ReplicaReadLayer>,
+    concurrency_limit: ConcurrencyLimit,
+    shutdown_grace: Duration,
+    keepalive: GrpcKeepaliveConfig,
+    max_decoding_size: usize,
+    max_encoding_size: usize,
+}
+
 async fn start_grpc_server(
     services: ServiceContainer,
     addr: std::net::SocketAddr,
+    options: GrpcServerOptions,
     mut shutdown_rx: broadcast::Receiver<()>,
 ) -> Result<()> {
     info!("📡 Starting gRPC server on {addr}");
 
-    let shutdown_future = async move {
-        let _ = shutdown_rx.recv().await;
-        info!("gRPC server received shutdown signal");
+    let GrpcServerOptions {
+        grpc_web,
+        auth,
+        rate_limit,
+        replica_read,
+        concurrency_limit,
+        shutdown_grace,
+        keepalive,
+        max_decoding_size,
+        max_encoding_size,
+    } = options;
+
+    let (health_reporter, health_service) = tonic_health::server::health_reporter();
+    let drain = DrainSignal::default();
+
+    // listener baru ditutup setelah request yang sedang berjalan selesai atau grace period habis
+    let shutdown_future = {
+        let drain = drain.clone();
+        let concurrency_limit = concurrency_limit.clone();
+        async move {
+            let _ = shutdown_rx.recv().await;
+            info!("gRPC server received shutdown signal");
+            drain_in_flight(&health_reporter, &drain, &concurrency_limit, shutdown_grace).await;
+        }
     };
 
+    // gRPC-Web butuh HTTP/1.1, tanpa itu server tetap gRPC murni
+    let grpc_web_enabled = grpc_web.is_some();
+
     tonic::transport::Server::builder()
-        .add_service(AuthServiceServer::new(services.auth))
-        .add_service(UserServiceServer::new(services.user))
-        .add_service(SaldoServiceServer::new(services.saldo))
-        .add_service(TopupServiceServer::new(services.topup))
-        .add_service(TransferServiceServer::new(services.transfer))
-        .add_service(WithdrawServiceServer::new(services.withdraw))
+        .accept_http1(grpc_web_enabled)
+        // ping dari sisi server juga menjaga koneksi gateway tetap hangat melewati load balancer
+        .http2_keepalive_interval(Some(keepalive.interval))
+        .http2_keepalive_timeout(Some(keepalive.timeout))
+        .layer(
+            TraceLayer::new_for_grpc()
+                .make_span_with(grpc_request_span::<tonic::body::Body>)
+                .on_request(())
+                .on_response(())
+                .on_failure(()),
+        )
+        .layer(LoadShedLayer::new(concurrency_limit, drain))
+        .layer(TraceContextLayer)
+        .layer(CancellationLayer)
+        .layer(DeadlineLayer)
+        .layer(option_layer(grpc_web))
+        .layer(option_layer(grpc_web_enabled.then(GrpcWebLayer::new)))
+        .layer(auth)
+        // setelah auth supaya bucket dikunci per user yang terautentikasi
+        .layer(rate_limit)
+        // butuh user dari auth untuk menahan bacaan ke primary setelah user itu menulis
+        .layer(option_layer(replica_read))
+        .add_service(health_service)
+        // batas ukuran pesan dipasang per service karena tonic tidak punya pengaturan global
+        .add_service(
+            AccountServiceServer::new(services.account)
+                .max_decoding_message_size(max_decoding_size)
+                .max_encoding_message_size(max_encoding_size),
+        )
+        .add_service(
+            AuthServiceServer::new(services.auth)
+                .max_decoding_message_size(max_decoding_size)
+                .max_encoding_message_size(max_encoding_size),
+        )
+        .add_service(
+            UserServiceServer::new(services.user)
+                .max_decoding_message_size(max_decoding_size)
+                .max_encoding_message_size(max_encoding_size),
+        )
+        .add_service(
+            SaldoServiceServer::new(services.saldo)
+                .max_decoding_message_size(max_decoding_size)
+                .max_encoding_message_size(max_encoding_size),
+        )
+        .add_service(
+            TopupServiceServer::new(services.topup)
+                .max_decoding_message_size(max_decoding_size)
+                .max_encoding_message_size(max_encoding_size),
+        )
+        .add_service(
+            TransferServiceServer::new(services.transfer)
+                .max_decoding_message_size(max_decoding_size)
+                .max_encoding_message_size(max_encoding_size),
+        )
+        .add_service(
+            WithdrawServiceServer::new(services.withdraw)
+                .max_decoding_message_size(max_decoding_size)
+                .max_encoding_message_size(max_encoding_size),
+        )
+        .add_service(
+            VirtualCardServiceServer::new(services.virtual_card)
+                .max_decoding_message_size(max_decoding_size)
+                .max_encoding_message_size(max_encoding_size),
+        )
+        .add_service(
+            WebhookServiceServer::new(services.webhook)
+                .max_decoding_message_size(max_decoding_size)
+                .max_encoding_message_size(max_encoding_size),
+        )
+        .add_service(
+            SystemServiceServer::new(services.system)
+                .max_decoding_message_size(max_decoding_size)
+                .max_encoding_message_size(max_encoding_size),
+        )
         .serve_with_shutdown(addr, shutdown_future)
         .await
         .with_context(|| format!("gRPC server failed to start on {addr}"))
 }
 
+// SaldoService sebagai JSON over HTTP, melewati auth (termasuk daftar method admin), rate limit,
+// load shedding dan drain yang sama dengan gRPC
+async fn start_json_server(
+    services: ServiceContainer,
+    addr: std::net::SocketAddr,
+    options: GrpcServerOptions,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> Result<()> {
+    info!("🧩 Starting JSON transcoding server on {addr}");
+
+    let drain = DrainSignal::default();
+    let concurrency_limit = options.concurrency_limit.clone();
+    let shutdown_grace = options.shutdown_grace;
+
+    let app = transcode::saldo_routes(services.saldo)
+        .layer(option_layer(options.replica_read))
+        .layer(options.rate_limit)
+        .layer(options.auth)
+        .layer(LoadShedLayer::new(options.concurrency_limit, drain.clone()))
+        .layer(axum::middleware::map_response(grpc_rejection_to_json))
+        .layer(DeadlineLayer)
+        .layer(CancellationLayer)
+        .layer(TraceContextLayer)
+        .layer(TraceLayer::new_for_http().make_span_with(grpc_request_span::<Body>));
+
+    let listener = tokio::net::TcpListener::bind(addr)
+        .await
+        .with_context(|| format!("Failed to bind JSON transcoding listener on {addr}"))?;
+
+    let shutdown_future = async move {
+        let _ = shutdown_rx.recv().await;
+        info!("JSON transcoding server received shutdown signal");
+        drain_requests(&drain, &concurrency_limit, shutdown_grace).await;
+    };
+
+    axum::serve(listener, app)
+        .with_graceful_shutdown(shutdown_future)
+        .await
+        .with_context(|| format!("JSON transcoding server failed on {addr}"))
+}
+
 async fn start_metrics_server(
-    state: Arc<AppState>,
+    probe: ProbeState,
     addr: std::net::SocketAddr,
     mut shutdown_rx: broadcast::Receiver<()>,
 ) -> Result<()> {
@@ -205,7 +625,11 @@ async fn start_metrics_server(
     let app = Router::new()
         .route("/metrics", axum::routing::get(metrics_handler))
         .route("/health", axum::routing::get(health_check))
-        .with_state(state);
+        .route("/livez", axum::routing::get(livez))
+        .route("/readyz", axum::routing::get(readyz))
+        .route("/status", axum::routing::get(status))
+        .route("/version", axum::routing::get(version))
+        .with_state(probe);
 
     let listener = tokio::net::TcpListener::bind(addr)
         .await
@@ -221,3 +645,215 @@ async fn start_metrics_server(
         .await
         .with_context(|| format!("Metrics server failed on {addr}"))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{
+        body::to_bytes,
+        http::{Request, Version, header::ORIGIN},
+    };
+    use genproto::saldo::ApiResponseSaldoResponse;
+    use prost::Message;
+    use shared::config::{CacheTtlConfig, HashAlgorithm, JwtConfig, TransferVelocityConfig};
+    use sqlx::postgres::PgPoolOptions;
+    use std::net::SocketAddr;
+    use tower::{ServiceBuilder, ServiceExt};
+
+    const APP_ORIGIN: &str = "https://app.example.com";
+
+    // satu frame gRPC-Web: flag, panjang big-endian 4 byte, lalu payload
+    fn frame(flag: u8, payload: &[u8]) -> Vec<u8> {
+        let mut framed = vec![flag];
+        framed.extend_from_slice(&(payload.len() as u32).to_be_bytes());
+        framed.extend_from_slice(payload);
+        framed
+    }
+
+    async fn seed_saldo(pool: &shared::config::ConnectionPool, balance: i32) -> i32 {
+        let tag = format!(
+            "grpcweb{}",
+            chrono::Utc::now().timestamp_nanos_opt().unwrap()
+        );
+        let (user_id,) = sqlx::query_as::<_, (i32,)>(
+            "INSERT INTO users (firstname, lastname, email, password, noc_transfer) \
+             VALUES ('Test', 'User', $1, 'password', $2) RETURNING user_id",
+        )
+        .bind(format!("{tag}@example.com"))
+        .bind(&tag)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        let (saldo_id,) = sqlx::query_as::<_, (i32,)>(
+            "INSERT INTO saldo (user_id, total_balance) VALUES ($1, $2) RETURNING saldo_id",
+        )
+        .bind(user_id)
+        .bind(balance)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+
+        saldo_id
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn grpc_web_find_saldo_by_id_over_http1() {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let pool = PgPoolOptions::new().connect_lazy(&url).unwrap();
+        let saldo_id = seed_saldo(&pool, 75_000).await;
+        let state = AppState::new(
+            DatabasePools::new(pool, None),
+            JwtConfig::new("test-secret"),
+            vec![0.1],
+            CacheTtlConfig::default(),
+            Duration::from_secs(3600),
+            HashAlgorithm::Bcrypt,
+            TransferVelocityConfig::default(),
+        )
+        .await
+        .unwrap();
+        let cache = state.cache.clone();
+        let services = ServiceContainer::new(Arc::new(state), Default::default(), cache);
+        let service = ServiceBuilder::new()
+            .layer(grpc_web_cors(&[APP_ORIGIN.to_string()]).unwrap())
+            .layer(GrpcWebLayer::new())
+            .service(SaldoServiceServer::new(services.saldo));
+
+        // FindSaldoByIdRequest { id }: field 1, varint
+        let mut message = Vec::new();
+        prost::encoding::int32::encode(1, &saldo_id, &mut message);
+        let request = Request::post("/saldo.SaldoService/FindSaldoById")
+            .version(Version::HTTP_11)
+            .header(CONTENT_TYPE, "application/grpc-web+proto")
+            .header(ORIGIN, APP_ORIGIN)
+            .body(Body::from(frame(0, &message)))
+            .unwrap();
+
+        let response = service.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        assert_eq!(
+            response.headers()["access-control-allow-origin"],
+            APP_ORIGIN
+        );
+
+        let body = to_bytes(Body::new(response.into_body()), usize::MAX)
+            .await
+            .unwrap();
+        assert_eq!(body[0], 0);
+        let len = u32::from_be_bytes(body[1..5].try_into().unwrap()) as usize;
+        let reply = ApiResponseSaldoResponse::decode(&body[5..5 + len]).unwrap();
+        let saldo = reply.data.unwrap();
+        assert_eq!(saldo.saldo_id, saldo_id);
+        assert_eq!(saldo.total_balance, 75_000);
+
+        // frame trailer (flag 0x80) membawa grpc-status
+        let trailers = String::from_utf8_lossy(&body[5 + len + 5..]);
+        assert_eq!(body[5 + len], 0x80);
+        assert!(trailers.contains("grpc-status:0"));
+    }
+
+    #[tokio::test]
+    async fn grpc_web_preflight_rejects_unknown_origins() {
+        let service = ServiceBuilder::new()
+            .layer(grpc_web_cors(&[APP_ORIGIN.to_string()]).unwrap())
+            .service(tower::service_fn(|_: Request<Body>| async {
+                Ok::<_, std::convert::Infallible>(Response::new(Body::empty()))
+            }));
+        let preflight = |origin: &str| {
+            Request::builder()
+                .method(Method::OPTIONS)
+                .uri("/saldo.SaldoService/FindSaldoById")
+                .header(ORIGIN, origin)
+                .header("access-control-request-method", "POST")
+                .header("access-control-request-headers", "content-type,x-grpc-web")
+                .body(Body::empty())
+                .unwrap()
+        };
+
+        let allowed = service
+            .clone()
+            .oneshot(preflight(APP_ORIGIN))
+            .await
+            .unwrap();
+        assert_eq!(allowed.headers()["access-control-allow-origin"], APP_ORIGIN);
+
+        let rejected = service
+            .oneshot(preflight("https://evil.example.com"))
+            .await
+            .unwrap();
+        assert!(
+            !rejected
+                .headers()
+                .contains_key("access-control-allow-origin")
+        );
+    }
+
+    async fn free_loopback_addr() -> SocketAddr {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        listener.local_addr().unwrap()
+    }
+
+    async fn get_livez(addr: SocketAddr) -> String {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+        stream
+            .write_all(b"GET /livez HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+            .await
+            .unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).await.unwrap();
+        response
+    }
+
+    #[tokio::test]
+    async fn metrics_server_listens_on_every_configured_address() {
+        let (shutdown_tx, _) = broadcast::channel(1);
+        let addrs = [free_loopback_addr().await, free_loopback_addr().await];
+
+        let handles: Vec<_> = addrs
+            .iter()
+            .copied()
+            .map(|addr| {
+                tokio::spawn(start_metrics_server(
+                    ProbeState::default(),
+                    addr,
+                    shutdown_tx.subscribe(),
+                ))
+            })
+            .collect();
+
+        for addr in addrs {
+            let response = tokio::time::timeout(Duration::from_secs(5), async {
+                // listener dibind di dalam task, jadi tunggu sampai siap menerima koneksi
+                while tokio::net::TcpStream::connect(addr).await.is_err() {
+                    tokio::time::sleep(Duration::from_millis(10)).await;
+                }
+                get_livez(addr).await
+            })
+            .await
+            .unwrap();
+            assert!(
+                response.starts_with("HTTP/1.1 200 OK"),
+                "{addr}: {response}"
+            );
+        }
+
+        shutdown_tx.send(()).unwrap();
+        for handle in handles {
+            tokio::time::timeout(Duration::from_secs(5), handle)
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+        }
+    }
+
+    #[test]
+    fn invalid_cors_origin_is_rejected() {
+        let err = grpc_web_cors(&["bad\norigin".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("Invalid CORS origin"));
+    }
+}
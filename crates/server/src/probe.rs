@@ -0,0 +1,250 @@
+use axum::{
+    Json,
+    body::Body,
+    extract::State,
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
+use serde::Serialize;
+use serde_json::json;
+use shared::{
+    config::ConnectionPool,
+    state::AppState,
+    utils::{
+        BUILD_INFO, BuildInfo, HealthChecker, HealthReport, ProcessSnapshot, check_database,
+        check_migrations, check_redis,
+    },
+};
+use std::sync::{Arc, OnceLock};
+use tracing::warn;
+
+// server probe sudah jalan sebelum AppState siap, jadi state diisi belakangan
+#[derive(Clone, Default)]
+pub struct ProbeState {
+    app: Arc<OnceLock<Arc<AppState>>>,
+}
+
+impl ProbeState {
+    pub fn mark_ready(&self, state: Arc<AppState>) {
+        let _ = self.app.set(state);
+    }
+
+    pub fn app(&self) -> Option<&Arc<AppState>> {
+        self.app.get()
+    }
+}
+
+pub fn not_ready() -> Response {
+    (
+        StatusCode::SERVICE_UNAVAILABLE,
+        Json(HealthReport::not_ready("initializing")),
+    )
+        .into_response()
+}
+
+// liveness tidak memeriksa dependency, cukup proses masih bisa menjawab
+pub async fn livez() -> impl IntoResponse {
+    (StatusCode::OK, Json(json!({ "status": "ok" })))
+}
+
+pub async fn version() -> impl IntoResponse {
+    Json(BUILD_INFO)
+}
+
+pub async fn readyz(State(probe): State<ProbeState>) -> Response<Body> {
+    let Some(state) = probe.app() else {
+        return not_ready();
+    };
+
+    let report = HealthChecker::new()
+        .check("database", check_database(&state.db_pool))
+        .await
+        .check("redis", check_redis(&state.redis))
+        .await
+        .check("migrations", check_migrations(&state.db_pool))
+        .await
+        .report();
+
+    let status = if report.is_healthy() {
+        StatusCode::OK
+    } else {
+        warn!("Readiness check failed: {:?}", report.checks);
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+
+    (status, Json(report)).into_response()
+}
+
+// snapshot untuk halaman status; tidak memanggil dependency sehingga murah dipanggil sering
+#[derive(Debug, Clone, Serialize)]
+pub struct SystemStatus {
+    pub build: BuildInfo,
+    pub process: ProcessSnapshot,
+    pub database: PoolStatus,
+    pub cache: CacheStatus,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct PoolStatus {
+    pub max_connections: u32,
+    pub in_use: u32,
+    pub idle: u32,
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CacheStatus {
+    pub degraded: bool,
+}
+
+impl PoolStatus {
+    fn from_pool(pool: &ConnectionPool) -> Self {
+        let idle = pool.num_idle() as u32;
+
+        Self {
+            max_connections: pool.options().get_max_connections(),
+            in_use: pool.size().saturating_sub(idle),
+            idle,
+        }
+    }
+}
+
+pub async fn status(State(probe): State<ProbeState>) -> Response<Body> {
+    let Some(state) = probe.app() else {
+        return not_ready();
+    };
+
+    let status = SystemStatus {
+        build: BUILD_INFO,
+        process: state.system_metrics.snapshot(),
+        database: PoolStatus::from_pool(&state.db_pool),
+        cache: CacheStatus {
+            degraded: state.cache.is_degraded(),
+        },
+    };
+
+    (StatusCode::OK, Json(status)).into_response()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::body::to_bytes;
+    use shared::cache::CacheStore;
+    use shared::config::{
+        CacheTtlConfig, DatabasePools, HashAlgorithm, JwtConfig, RedisClient,
+        TransferVelocityConfig,
+    };
+    use sqlx::postgres::PgPoolOptions;
+    use std::time::Duration;
+
+    async fn app_state(redis_url: &str) -> Arc<AppState> {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let pool = PgPoolOptions::new().connect_lazy(&url).unwrap();
+        let mut state = AppState::new(
+            DatabasePools::new(pool, None),
+            JwtConfig::new("test-secret"),
+            vec![0.1],
+            CacheTtlConfig::default(),
+            Duration::from_secs(3600),
+            HashAlgorithm::Bcrypt,
+            TransferVelocityConfig::default(),
+        )
+        .await
+        .unwrap();
+        state.redis = RedisClient {
+            client: redis::Client::open(redis_url).unwrap(),
+        };
+
+        Arc::new(state)
+    }
+
+    #[tokio::test]
+    async fn livez_answers_without_any_dependency() {
+        let response = livez().await.into_response();
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    async fn version_reports_the_crate_version_and_build() {
+        let response = version().await.into_response();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let info: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(info["version"], "0.1.0");
+        assert_eq!(info["git_sha"], BUILD_INFO.git_sha);
+        assert!(!info["build_timestamp"].as_str().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn readyz_is_503_before_the_state_is_ready() {
+        let response = readyz(State(ProbeState::default())).await;
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    // butuh Redis sungguhan, misalnya: TEST_REDIS_URL=redis://127.0.0.1:6379/ cargo test -- --ignored
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL and Redis at TEST_REDIS_URL"]
+    async fn readyz_is_200_once_every_dependency_is_reachable() {
+        let redis_url = std::env::var("TEST_REDIS_URL")
+            .unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
+        let probe = ProbeState::default();
+        probe.mark_ready(app_state(&redis_url).await);
+
+        let response = readyz(State(probe)).await;
+
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn readyz_is_503_while_redis_is_down() {
+        let probe = ProbeState::default();
+        probe.mark_ready(app_state("redis://127.0.0.1:1/").await);
+
+        let response = readyz(State(probe)).await;
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    async fn status_is_503_before_the_state_is_ready() {
+        let response = status(State(ProbeState::default())).await;
+
+        assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn status_reports_process_pool_and_cache_health() {
+        let mut state = app_state("redis://127.0.0.1:1/").await;
+        let app = Arc::get_mut(&mut state).unwrap();
+        // Redis yang menolak koneksi membuka breaker pada pemakaian pertama
+        app.cache = CacheStore::new(app.redis.client.clone(), CacheTtlConfig::default());
+        app.cache.delete_from_cache("status:probe");
+        state.system_metrics.update_metrics().await;
+        let probe = ProbeState::default();
+        probe.mark_ready(state);
+
+        let response = status(State(probe)).await;
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let body = to_bytes(response.into_body(), usize::MAX).await.unwrap();
+        let status: serde_json::Value = serde_json::from_slice(&body).unwrap();
+
+        assert_eq!(status["build"]["version"], "0.1.0");
+        assert!(status["process"]["uptime_secs"].as_u64().is_some());
+        assert!(status["process"]["thread_count"].as_i64().unwrap() >= 1);
+        assert!(status["process"]["memory_bytes"].as_i64().unwrap() > 0);
+
+        let database = &status["database"];
+        let max = database["max_connections"].as_u64().unwrap();
+        assert!(max >= 1);
+        assert!(database["in_use"].as_u64().unwrap() + database["idle"].as_u64().unwrap() <= max);
+
+        assert_eq!(status["cache"]["degraded"], true);
+    }
+}
@@ -0,0 +1,262 @@
+use axum::http::{Request, Response};
+use shared::{
+    cache::CacheStore,
+    config::{RateLimit, WriteRateLimitConfig},
+    utils::RETRY_AFTER_HEADER,
+};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+use tonic::metadata::MetadataValue;
+use tower::{Layer, Service};
+use tracing::warn;
+
+use crate::auth::AuthenticatedUser;
+
+// RPC dengan awalan ini hanya membaca data sehingga tidak dibatasi
+const READ_PREFIXES: &[&str] = &["Find", "Get", "List", "Stream"];
+const READ_METHODS: &[&str] = &[
+    "TopSaldos",
+    "TopupStats",
+    "DailyTransferTotals",
+    "CanDeleteUser",
+    "CacheStats",
+];
+
+pub(crate) fn is_read_method(path: &str) -> bool {
+    let method = path.rsplit('/').next().unwrap_or(path);
+
+    READ_METHODS.contains(&method)
+        || READ_PREFIXES
+            .iter()
+            .any(|prefix| method.starts_with(prefix))
+}
+
+// token yang bocor tidak boleh dipakai membanjiri transfer/withdraw; bucket disimpan di
+// Redis sehingga batasnya berlaku gabungan untuk semua instance server
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    cache_store: CacheStore,
+    limits: WriteRateLimitConfig,
+}
+
+impl RateLimitLayer {
+    pub fn new(cache_store: CacheStore, limits: WriteRateLimitConfig) -> Self {
+        Self {
+            cache_store,
+            limits,
+        }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimitService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimitService {
+            inner,
+            cache_store: self.cache_store.clone(),
+            limits: self.limits,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimitService<S> {
+    inner: S,
+    cache_store: CacheStore,
+    limits: WriteRateLimitConfig,
+}
+
+impl<S> RateLimitService<S> {
+    fn limit_for(&self, path: &str) -> Option<RateLimit> {
+        match path {
+            "/transfer.TransferService/CreateTransfer" => Some(self.limits.transfer),
+            "/withdraw.WithdrawService/CreateWithdraw" => Some(self.limits.withdraw),
+            "/topup.TopupService/CreateTopup" => Some(self.limits.topup),
+            _ if is_read_method(path) => None,
+            _ => Some(self.limits.default),
+        }
+    }
+}
+
+impl<S, B, ResBody> Service<Request<B>> for RateLimitService<S>
+where
+    S: Service<Request<B>, Response = Response<ResBody>>,
+    S::Future: Send + 'static,
+    ResBody: Default + Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        // method publik dan health check tidak punya user, batas login diatur AuthService
+        let Some(AuthenticatedUser { user_id, .. }) = request.extensions().get().copied() else {
+            return Box::pin(self.inner.call(request));
+        };
+
+        let path = request.uri().path();
+        let Some(limit) = self.limit_for(path) else {
+            return Box::pin(self.inner.call(request));
+        };
+
+        let key = format!("rate_limit:user={user_id}:{path}");
+
+        if let Some(retry_after) = self.cache_store.throttle(&key, limit) {
+            warn!(
+                "🚫 Rate limit exceeded for user {user_id} on {path}, retry after {retry_after}s"
+            );
+
+            let mut status = tonic::Status::resource_exhausted(format!(
+                "Too many requests, retry after {retry_after}s"
+            ));
+            status
+                .metadata_mut()
+                .insert(RETRY_AFTER_HEADER, MetadataValue::from(retry_after));
+
+            let response = status.into_http::<ResBody>();
+            return Box::pin(async move { Ok(response) });
+        }
+
+        Box::pin(self.inner.call(request))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::config::CacheTtlConfig;
+    use std::{convert::Infallible, time::Duration};
+    use tonic::Code;
+    use tower::{ServiceExt, service_fn};
+
+    const CREATE_TRANSFER: &str = "/transfer.TransferService/CreateTransfer";
+    const CREATE_WITHDRAW: &str = "/withdraw.WithdrawService/CreateWithdraw";
+
+    fn layer(redis_url: &str, limits: WriteRateLimitConfig) -> RateLimitLayer {
+        let store = CacheStore::new(
+            redis::Client::open(redis_url).unwrap(),
+            CacheTtlConfig::default(),
+        );
+        RateLimitLayer::new(store, limits)
+    }
+
+    async fn call(layer: &RateLimitLayer, path: &str, user_id: Option<i32>) -> Response<String> {
+        let inner = service_fn(|_: Request<String>| async {
+            Ok::<_, Infallible>(Response::new("handled".to_string()))
+        });
+
+        let mut request = Request::builder().uri(path).body(String::new()).unwrap();
+        if let Some(user_id) = user_id {
+            request.extensions_mut().insert(AuthenticatedUser {
+                user_id,
+                is_admin: false,
+            });
+        }
+
+        layer.layer(inner).oneshot(request).await.unwrap()
+    }
+
+    fn grpc_code(response: &Response<String>) -> Option<Code> {
+        response
+            .headers()
+            .get("grpc-status")
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<i32>().ok())
+            .map(Code::from_i32)
+    }
+
+    #[test]
+    fn read_methods_are_not_limited() {
+        assert!(is_read_method("/saldo.SaldoService/FindByUserId"));
+        assert!(is_read_method("/transfer.TransferService/ListTransfers"));
+        assert!(is_read_method("/saldo.SaldoService/TopSaldos"));
+        assert!(!is_read_method(CREATE_TRANSFER));
+        assert!(!is_read_method("/user.UserService/DeleteUser"));
+    }
+
+    #[test]
+    fn create_rpcs_use_their_own_limit_and_other_writes_the_default() {
+        let limits = WriteRateLimitConfig::default();
+        let service = layer("redis://127.0.0.1:1/", limits).layer(());
+
+        assert_eq!(service.limit_for(CREATE_TRANSFER), Some(limits.transfer));
+        assert_eq!(service.limit_for(CREATE_WITHDRAW), Some(limits.withdraw));
+        assert_eq!(
+            service.limit_for("/topup.TopupService/CreateTopup"),
+            Some(limits.topup)
+        );
+        assert_eq!(
+            service.limit_for("/user.UserService/UpdateUser"),
+            Some(limits.default)
+        );
+        assert_eq!(service.limit_for("/saldo.SaldoService/FindAll"), None);
+    }
+
+    #[tokio::test]
+    async fn requests_without_user_or_redis_pass_through() {
+        let limits = WriteRateLimitConfig {
+            transfer: RateLimit::new(1, Duration::from_secs(60)),
+            ..WriteRateLimitConfig::default()
+        };
+        // port 1 tidak pernah menjawab, limiter gagal terbuka
+        let layer = layer("redis://127.0.0.1:1/", limits);
+
+        for user_id in [None, Some(1), Some(1)] {
+            let response = call(&layer, CREATE_TRANSFER, user_id).await;
+            assert_eq!(grpc_code(&response), None);
+            assert_eq!(response.body(), "handled");
+        }
+    }
+
+    // butuh Redis sungguhan, misalnya: TEST_REDIS_URL=redis://127.0.0.1:6379/ cargo test -- --ignored
+    #[tokio::test]
+    #[ignore = "needs a Redis server at TEST_REDIS_URL"]
+    async fn writes_over_the_limit_are_rejected_until_the_bucket_refills() {
+        let url = std::env::var("TEST_REDIS_URL")
+            .unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
+        let limits = WriteRateLimitConfig {
+            transfer: RateLimit::new(2, Duration::from_secs(1)),
+            ..WriteRateLimitConfig::default()
+        };
+        // dua layer dengan Redis yang sama meniru dua instance server
+        let (first, second) = (layer(&url, limits), layer(&url, limits));
+        let user_id = std::process::id() as i32;
+
+        assert_eq!(
+            grpc_code(&call(&first, CREATE_TRANSFER, Some(user_id)).await),
+            None
+        );
+        assert_eq!(
+            grpc_code(&call(&second, CREATE_TRANSFER, Some(user_id)).await),
+            None
+        );
+
+        let rejected = call(&first, CREATE_TRANSFER, Some(user_id)).await;
+        assert_eq!(grpc_code(&rejected), Some(Code::ResourceExhausted));
+        assert_eq!(rejected.headers().get(RETRY_AFTER_HEADER).unwrap(), "1");
+
+        // bucket per user dan per method
+        assert_eq!(
+            grpc_code(&call(&first, CREATE_TRANSFER, Some(user_id + 1)).await),
+            None
+        );
+        assert_eq!(
+            grpc_code(&call(&first, CREATE_WITHDRAW, Some(user_id)).await),
+            None
+        );
+
+        tokio::time::sleep(Duration::from_millis(1100)).await;
+        assert_eq!(
+            grpc_code(&call(&second, CREATE_TRANSFER, Some(user_id)).await),
+            None
+        );
+    }
+}
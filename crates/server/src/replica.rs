@@ -0,0 +1,166 @@
+use axum::http::Request;
+use shared::{cache::CacheStore, config::with_replica_reads};
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+    time::Duration,
+};
+use tower::{Layer, Service};
+
+use crate::{auth::AuthenticatedUser, rate_limit::is_read_method};
+
+// selama jendela ini setelah user menulis, bacaannya tetap ke primary supaya data
+// yang baru saja ia tulis tidak hilang karena replika tertinggal
+const READ_AFTER_WRITE_WINDOW: Duration = Duration::from_secs(5);
+
+// RPC baca-saja dijalankan di dalam scope replika; RPC lain tidak disentuh sehingga
+// pengecekan di jalur write tetap membaca primary
+#[derive(Clone)]
+pub struct ReplicaReadLayer {
+    cache_store: CacheStore,
+}
+
+impl ReplicaReadLayer {
+    pub fn new(cache_store: CacheStore) -> Self {
+        Self { cache_store }
+    }
+}
+
+impl<S> Layer<S> for ReplicaReadLayer {
+    type Service = ReplicaReadService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        ReplicaReadService {
+            inner,
+            cache_store: self.cache_store.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct ReplicaReadService<S> {
+    inner: S,
+    cache_store: CacheStore,
+}
+
+fn recent_write_key(user_id: i32) -> String {
+    format!("read_primary:user={user_id}")
+}
+
+impl<S, B> Service<Request<B>> for ReplicaReadService<S>
+where
+    S: Service<Request<B>>,
+    S::Future: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<S::Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let user = request.extensions().get::<AuthenticatedUser>().copied();
+        let is_read = is_read_method(request.uri().path());
+
+        if !is_read {
+            if let Some(AuthenticatedUser { user_id, .. }) = user {
+                self.cache_store.set_to_cache(
+                    &recent_write_key(user_id),
+                    &true,
+                    READ_AFTER_WRITE_WINDOW,
+                );
+            }
+            return Box::pin(self.inner.call(request));
+        }
+
+        let wrote_recently = user.is_some_and(|AuthenticatedUser { user_id, .. }| {
+            self.cache_store
+                .remaining_ttl(&recent_write_key(user_id))
+                .is_some()
+        });
+        if wrote_recently {
+            return Box::pin(self.inner.call(request));
+        }
+
+        Box::pin(with_replica_reads(self.inner.call(request)))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use shared::config::{CacheTtlConfig, ReadPool};
+    use sqlx::postgres::PgPoolOptions;
+    use std::convert::Infallible;
+    use tower::{ServiceExt, service_fn};
+
+    const CREATE_TRANSFER: &str = "/transfer.TransferService/CreateTransfer";
+    const FIND_TRANSFER: &str = "/transfer.TransferService/FindByIdTransfer";
+
+    // pool lazy tidak pernah terhubung; nama database hanya penanda pool mana yang dipilih
+    fn read_pool() -> ReadPool {
+        let lazy = |db: &str| {
+            PgPoolOptions::new()
+                .connect_lazy(&format!("postgres://postgres@127.0.0.1:1/{db}"))
+                .unwrap()
+        };
+        ReadPool::new(lazy("primary"), Some(lazy("replica")))
+    }
+
+    async fn pool_used_by(layer: &ReplicaReadLayer, path: &str, user_id: i32) -> String {
+        let read = read_pool();
+        let service = layer.layer(service_fn(move |_: Request<()>| {
+            let read = read.clone();
+            async move {
+                let database = read
+                    .get()
+                    .connect_options()
+                    .get_database()
+                    .unwrap()
+                    .to_string();
+                Ok::<_, Infallible>(database)
+            }
+        }));
+
+        let mut request = Request::builder().uri(path).body(()).unwrap();
+        request.extensions_mut().insert(AuthenticatedUser {
+            user_id,
+            is_admin: false,
+        });
+
+        service.oneshot(request).await.unwrap()
+    }
+
+    // butuh Redis sungguhan, misalnya: TEST_REDIS_URL=redis://127.0.0.1:6379/ cargo test -- --ignored
+    #[tokio::test]
+    #[ignore = "needs a Redis server at TEST_REDIS_URL"]
+    async fn read_after_write_goes_to_the_primary() {
+        let url = std::env::var("TEST_REDIS_URL")
+            .unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
+        let store = CacheStore::new(redis::Client::open(url).unwrap(), CacheTtlConfig::default());
+        let user_id = 7;
+        store.delete_from_cache(&recent_write_key(user_id));
+        store.delete_from_cache(&recent_write_key(user_id + 1));
+        let layer = ReplicaReadLayer::new(store);
+
+        assert_eq!(
+            pool_used_by(&layer, FIND_TRANSFER, user_id).await,
+            "replica"
+        );
+
+        pool_used_by(&layer, CREATE_TRANSFER, user_id).await;
+        assert_eq!(
+            pool_used_by(&layer, FIND_TRANSFER, user_id).await,
+            "primary"
+        );
+
+        // user lain tidak terpengaruh jendela read-your-writes milik user ini
+        assert_eq!(
+            pool_used_by(&layer, FIND_TRANSFER, user_id + 1).await,
+            "replica"
+        );
+    }
+}
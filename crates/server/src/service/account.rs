@@ -0,0 +1,50 @@
+use genproto::account::{
+    ApiResponseAccountSummary, GetAccountSummaryRequest, account_service_server::AccountService,
+};
+use shared::{domain::request::AccountSummaryRequest, state::AppState};
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use tracing::{error, info};
+
+#[derive(Debug, Clone)]
+pub struct AccountServiceImpl {
+    state: Arc<AppState>,
+}
+
+impl AccountServiceImpl {
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self { state }
+    }
+}
+
+#[tonic::async_trait]
+impl AccountService for AccountServiceImpl {
+    async fn get_account_summary(
+        &self,
+        request: Request<GetAccountSummaryRequest>,
+    ) -> Result<Response<ApiResponseAccountSummary>, Status> {
+        let req = request.get_ref();
+
+        info!("Fetching account summary for user id : {}", req.user_id);
+
+        let body = AccountSummaryRequest { limit: req.limit };
+
+        match self
+            .state
+            .di_container
+            .account_service
+            .get_account_summary(req.user_id, &body)
+            .await
+        {
+            Ok(api_response) => Ok(Response::new(ApiResponseAccountSummary {
+                status: api_response.status,
+                message: api_response.message,
+                data: Some(api_response.data.into()),
+            })),
+            Err(err) => {
+                error!("Failed to fetch account summary: {}", err.message);
+                Err(err.into())
+            }
+        }
+    }
+}
@@ -1,14 +1,17 @@
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
 
+use genproto::api::ApiResponseEmpty;
 use genproto::auth::{
-    ApiResponseGetMe, ApiResponseLogin, ApiResponseRegister, GetMeRequest, LoginRequest,
-    RegisterRequest, auth_service_server::AuthService,
+    ApiResponseGetMe, ApiResponseLogin, ApiResponseRegister, ApiResponseSessions,
+    ChangePasswordRequest, GetMeRequest, ListSessionsRequest, LoginRequest, RefreshTokenRequest,
+    RegisterRequest, RevokeSessionRequest, auth_service_server::AuthService,
 };
 
 use shared::{
     domain::request::{
-        LoginRequest as LoginDomainRequest, RegisterRequest as RegisterDomainRequest,
+        ChangePasswordRequest as ChangePasswordDomainRequest, LoginRequest as LoginDomainRequest,
+        RefreshTokenRequest as RefreshTokenDomainRequest, RegisterRequest as RegisterDomainRequest,
     },
     state::AppState,
 };
@@ -38,6 +41,7 @@ impl AuthService for AuthServiceImpl {
         let domain_req = LoginDomainRequest {
             email: req.email,
             password: req.password,
+            user_agent: Some(req.user_agent).filter(|agent| !agent.is_empty()),
         };
 
         match self
@@ -51,7 +55,7 @@ impl AuthService for AuthServiceImpl {
                 let reply = ApiResponseLogin {
                     status: api_response.status,
                     message: api_response.message,
-                    data: api_response.data,
+                    data: Some(api_response.data.into()),
                 };
 
                 info!("User logged in successfully");
@@ -60,7 +64,7 @@ impl AuthService for AuthServiceImpl {
             }
             Err(err) => {
                 error!("Error logging in user: {}", err.message);
-                Err(Status::internal(err.message))
+                Err(err.into())
             }
         }
     }
@@ -102,7 +106,7 @@ impl AuthService for AuthServiceImpl {
             }
             Err(err) => {
                 error!("Error registering user: {}", err.message);
-                Err(Status::internal(err.message))
+                Err(err.into())
             }
         }
     }
@@ -129,7 +133,132 @@ impl AuthService for AuthServiceImpl {
             }
             Err(err) => {
                 error!("Error fetching user: {}", err.message);
-                Err(Status::internal(err.message))
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn change_password(
+        &self,
+        request: Request<ChangePasswordRequest>,
+    ) -> Result<Response<ApiResponseEmpty>, Status> {
+        info!("Changing password for user {}", request.get_ref().id);
+
+        let req = request.into_inner();
+
+        let domain_req = ChangePasswordDomainRequest {
+            old_password: req.old_password,
+            new_password: req.new_password,
+            confirm_password: req.confirm_password,
+        };
+
+        match self
+            .state
+            .di_container
+            .auth_service
+            .change_password(req.id, &domain_req)
+            .await
+        {
+            Ok(api_response) => {
+                info!("Password changed successfully");
+
+                Ok(Response::new(ApiResponseEmpty {
+                    status: api_response.status,
+                    message: api_response.message,
+                }))
+            }
+            Err(err) => {
+                error!("Error changing password: {}", err.message);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn refresh_token(
+        &self,
+        request: Request<RefreshTokenRequest>,
+    ) -> Result<Response<ApiResponseLogin>, Status> {
+        info!("Refreshing access token");
+
+        let req = request.into_inner();
+
+        let domain_req = RefreshTokenDomainRequest {
+            refresh_token: req.refresh_token,
+        };
+
+        match self
+            .state
+            .di_container
+            .auth_service
+            .refresh_token(&domain_req)
+            .await
+        {
+            Ok(api_response) => {
+                info!("Access token refreshed successfully");
+
+                Ok(Response::new(ApiResponseLogin {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data: Some(api_response.data.into()),
+                }))
+            }
+            Err(err) => {
+                error!("Error refreshing access token: {}", err.message);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn list_sessions(
+        &self,
+        request: Request<ListSessionsRequest>,
+    ) -> Result<Response<ApiResponseSessions>, Status> {
+        let user_id = request.get_ref().user_id;
+        info!("Listing sessions of user {user_id}");
+
+        match self
+            .state
+            .di_container
+            .auth_service
+            .list_sessions(user_id)
+            .await
+        {
+            Ok(api_response) => Ok(Response::new(ApiResponseSessions {
+                status: api_response.status,
+                message: api_response.message,
+                data: api_response.data.into_iter().map(Into::into).collect(),
+            })),
+            Err(err) => {
+                error!("Error listing sessions: {}", err.message);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn revoke_session(
+        &self,
+        request: Request<RevokeSessionRequest>,
+    ) -> Result<Response<ApiResponseEmpty>, Status> {
+        let req = request.into_inner();
+        info!(
+            "Revoking session {} of user {}",
+            req.session_id, req.user_id
+        );
+
+        match self
+            .state
+            .di_container
+            .auth_service
+            .revoke_session(req.user_id, req.session_id)
+            .await
+        {
+            Ok(api_response) => Ok(Response::new(ApiResponseEmpty {
+                status: api_response.status,
+                message: api_response.message,
+            })),
+            Err(err) => {
+                error!("Error revoking session: {}", err.message);
+                Err(err.into())
             }
         }
     }
@@ -1,40 +1,73 @@
+mod account;
 mod auth;
 mod saldo;
+mod system;
 mod topup;
 mod transfer;
 mod user;
+mod virtual_card;
+mod webhook;
 mod withdraw;
 
 use std::sync::Arc;
 
-use shared::state::AppState;
+use chrono::NaiveDate;
+use shared::{cache::CacheStore, domain::response::system::RuntimeConfigResponse, state::AppState};
+use tonic::Status;
 
+use self::account::AccountServiceImpl;
 use self::auth::AuthServiceImpl;
 use self::saldo::SaldoServiceImpl;
+use self::system::SystemServiceImpl;
 use self::topup::TopupServiceImpl;
 use self::transfer::TransferServiceImpl;
 use self::user::UserServiceImpl;
+use self::virtual_card::VirtualCardServiceImpl;
+use self::webhook::WebhookServiceImpl;
 use self::withdraw::WithdrawServiceImpl;
 
 #[derive(Clone)]
 pub struct ServiceContainer {
+    pub account: AccountServiceImpl,
     pub auth: AuthServiceImpl,
     pub user: UserServiceImpl,
     pub topup: TopupServiceImpl,
     pub saldo: SaldoServiceImpl,
     pub transfer: TransferServiceImpl,
     pub withdraw: WithdrawServiceImpl,
+    pub virtual_card: VirtualCardServiceImpl,
+    pub webhook: WebhookServiceImpl,
+    pub system: SystemServiceImpl,
 }
 
 impl ServiceContainer {
-    pub fn new(state: Arc<AppState>) -> Self {
+    pub fn new(
+        state: Arc<AppState>,
+        runtime_config: RuntimeConfigResponse,
+        cache_store: CacheStore,
+    ) -> Self {
         Self {
+            account: AccountServiceImpl::new(state.clone()),
             auth: AuthServiceImpl::new(state.clone()),
             user: UserServiceImpl::new(state.clone()),
             topup: TopupServiceImpl::new(state.clone()),
             saldo: SaldoServiceImpl::new(state.clone()),
             transfer: TransferServiceImpl::new(state.clone()),
             withdraw: WithdrawServiceImpl::new(state.clone()),
+            virtual_card: VirtualCardServiceImpl::new(state.clone()),
+            webhook: WebhookServiceImpl::new(state.clone()),
+            system: SystemServiceImpl::new(runtime_config, cache_store),
         }
     }
 }
+
+// tanggal kosong berarti rentang tidak dibatasi di sisi tersebut
+pub(crate) fn parse_date(field: &str, value: &str) -> Result<Option<NaiveDate>, Status> {
+    if value.is_empty() {
+        return Ok(None);
+    }
+
+    NaiveDate::parse_from_str(value, "%Y-%m-%d")
+        .map(Some)
+        .map_err(|_| Status::invalid_argument(format!("{field} must use the YYYY-MM-DD format")))
+}
@@ -1,14 +1,18 @@
 use genproto::api::ApiResponseEmpty;
 use genproto::saldo::{
-    ApiResponseSaldoResponse, ApiResponsesSaldoPaginated, ApiResponsesSaldoResponse,
-    CreateSaldoRequest, FindAllSaldoRequest, FindSaldoByIdRequest, FindSaldoByUserIdRequest,
-    UpdateSaldoRequest, saldo_service_server::SaldoService,
+    AdjustBalanceRequest, ApiResponseBalanceReconciliation, ApiResponseSaldoResponse,
+    ApiResponseTopSaldos, ApiResponsesSaldoPaginated, CreateSaldoRequest, FindAllSaldoRequest,
+    FindSaldoByIdRequest, FindSaldoByUserIdRequest, FindSaldoByUsersIdRequest,
+    ReconcileBalanceRequest, TopSaldosRequest, UpdateSaldoRequest,
+    saldo_service_server::SaldoService,
 };
 use shared::{
     domain::request::{
+        AdjustBalanceRequest as SharedAdjustBalanceRequest,
         CreateSaldoRequest as SharedCreateSaldoRequest,
-        FindAllSaldoRequest as SharedFindAllSaldoRequest,
-        UpdateSaldoRequest as SharedUpdateSaldoRequest,
+        FindAllSaldoRequest as SharedFindAllSaldoRequest, PageRequest,
+        ReconcileBalanceRequest as SharedReconcileBalanceRequest,
+        TopSaldosRequest as SharedTopSaldosRequest, UpdateSaldoRequest as SharedUpdateSaldoRequest,
     },
     state::AppState,
 };
@@ -16,6 +20,8 @@ use std::sync::Arc;
 use tonic::{Request, Response, Status};
 use tracing::{error, info};
 
+use crate::auth::require_admin;
+
 #[derive(Debug, Clone)]
 pub struct SaldoServiceImpl {
     state: Arc<AppState>,
@@ -65,7 +71,7 @@ impl SaldoService for SaldoServiceImpl {
             }
             Err(err) => {
                 error!("Failed to fetch saldo: {}", err);
-                Err(Status::internal("Failed to fetch saldo"))
+                Err(err.into())
             }
         }
     }
@@ -94,7 +100,7 @@ impl SaldoService for SaldoServiceImpl {
             },
             Err(err) => {
                 error!("Failed to fetch saldo: {}", err);
-                Err(Status::internal("Failed to fetch saldo"))
+                Err(err.into())
             }
         }
     }
@@ -129,49 +135,46 @@ impl SaldoService for SaldoServiceImpl {
             },
             Err(err) => {
                 error!("Failed to fetch saldo: {err}");
-                Err(Status::internal("Failed to fetch saldo"))
+                Err(err.into())
             }
         }
     }
 
     async fn find_saldo_by_users_id(
         &self,
-        request: Request<FindSaldoByUserIdRequest>,
-    ) -> Result<Response<ApiResponsesSaldoResponse>, Status> {
-        let request = request.into_inner();
+        request: Request<FindSaldoByUsersIdRequest>,
+    ) -> Result<Response<ApiResponsesSaldoPaginated>, Status> {
+        let req = request.into_inner();
 
-        info!("Finding saldo by user id : {}", request.user_id);
+        info!("Finding saldo by user id : {}", req.user_id);
 
-        let user_id = request.user_id;
+        let page_request = PageRequest {
+            page: req.page,
+            page_size: req.page_size,
+        };
 
         match self
             .state
             .di_container
             .saldo_service
-            .get_saldo_users(user_id)
+            .get_saldo_users(req.user_id, &page_request)
             .await
         {
             Ok(api_response) => {
-                let data_vec = api_response
-                    .data
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(Into::into)
-                    .collect();
-
-                let reply = ApiResponsesSaldoResponse {
-                    status: "success".into(),
-                    message: "Saldo fetched successfully".into(),
-                    data: data_vec,
-                };
+                let data = api_response.data.into_iter().map(Into::into).collect();
 
-                info!("Saldo fetched successfully for user id : {}", user_id);
+                info!("Saldo fetched successfully for user id : {}", req.user_id);
 
-                Ok(Response::new(reply))
+                Ok(Response::new(ApiResponsesSaldoPaginated {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data,
+                    pagination: Some(api_response.pagination.into()),
+                }))
             }
             Err(err) => {
-                error!("Failed to fetch saldo: {err}");
-                Err(Status::internal("Failed to fetch saldo"))
+                error!("Failed to fetch saldo: {}", err.message);
+                Err(err.into())
             }
         }
     }
@@ -187,6 +190,7 @@ impl SaldoService for SaldoServiceImpl {
         let body = SharedCreateSaldoRequest {
             user_id: req.user_id,
             total_balance: req.total_balance,
+            label: (!req.label.is_empty()).then(|| req.label.clone()),
         };
 
         match self
@@ -209,7 +213,7 @@ impl SaldoService for SaldoServiceImpl {
             }
             Err(err) => {
                 error!("Failed to create saldo: {}", err);
-                Err(Status::internal("Failed to create saldo"))
+                Err(err.into())
             }
         }
     }
@@ -250,7 +254,7 @@ impl SaldoService for SaldoServiceImpl {
             }
             Err(err) => {
                 error!("Failed to update saldo: {}", err);
-                Err(Status::internal("Failed to update saldo"))
+                Err(err.into())
             }
         }
     }
@@ -272,7 +276,110 @@ impl SaldoService for SaldoServiceImpl {
             }
             Err(err) => {
                 error!("Failed to delete saldo: {}", err);
-                Err(Status::internal("Failed to delete saldo"))
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn top_saldos(
+        &self,
+        request: Request<TopSaldosRequest>,
+    ) -> Result<Response<ApiResponseTopSaldos>, Status> {
+        let req = request.get_ref();
+
+        info!("Fetching top {} saldos", req.limit);
+
+        let body = SharedTopSaldosRequest { limit: req.limit };
+
+        match self
+            .state
+            .di_container
+            .saldo_service
+            .get_top_saldos(&body)
+            .await
+        {
+            Ok(api_response) => Ok(Response::new(ApiResponseTopSaldos {
+                status: api_response.status,
+                message: api_response.message,
+                data: api_response.data.into_iter().map(Into::into).collect(),
+            })),
+            Err(err) => {
+                error!("Failed to fetch top saldos: {}", err.message);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn adjust_balance(
+        &self,
+        request: Request<AdjustBalanceRequest>,
+    ) -> Result<Response<ApiResponseSaldoResponse>, Status> {
+        // actor ledger adalah pemilik token, tidak bisa diisi sendiri oleh pemanggil
+        let actor = require_admin(&request)?;
+        let req = request.into_inner();
+
+        info!(
+            "Adjusting balance of user id {} by {} (actor id {})",
+            req.user_id, req.delta, actor.user_id
+        );
+
+        let body = SharedAdjustBalanceRequest {
+            user_id: req.user_id,
+            delta: req.delta,
+            reason: req.reason,
+        };
+
+        match self
+            .state
+            .di_container
+            .saldo_service
+            .adjust_balance(actor.user_id, &body)
+            .await
+        {
+            Ok(api_response) => Ok(Response::new(ApiResponseSaldoResponse {
+                status: api_response.status,
+                message: api_response.message,
+                data: Some(api_response.data.into()),
+            })),
+            Err(err) => {
+                error!("Failed to adjust balance: {}", err.message);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn reconcile_balance(
+        &self,
+        request: Request<ReconcileBalanceRequest>,
+    ) -> Result<Response<ApiResponseBalanceReconciliation>, Status> {
+        let actor = require_admin(&request)?;
+        let req = request.into_inner();
+
+        info!(
+            "Reconciling balance of user id {:?} (fix: {}, actor id {})",
+            req.user_id, req.fix, actor.user_id
+        );
+
+        let body = SharedReconcileBalanceRequest {
+            user_id: req.user_id,
+            fix: req.fix,
+        };
+
+        match self
+            .state
+            .di_container
+            .saldo_service
+            .reconcile_balance(actor.user_id, &body)
+            .await
+        {
+            Ok(api_response) => Ok(Response::new(ApiResponseBalanceReconciliation {
+                status: api_response.status,
+                message: api_response.message,
+                data: api_response.data.into_iter().map(Into::into).collect(),
+            })),
+            Err(err) => {
+                error!("Failed to reconcile balance: {}", err.message);
+                Err(err.into())
             }
         }
     }
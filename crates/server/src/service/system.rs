@@ -0,0 +1,122 @@
+use genproto::system::{
+    ApiResponseCacheFlush, ApiResponseCacheStats, ApiResponseRuntimeConfig,
+    CacheFlushPrefixRequest, CacheStatsRequest, GetRuntimeConfigRequest,
+    system_service_server::SystemService,
+};
+use shared::{
+    cache::CacheStore,
+    domain::{
+        request::CacheFlushRequest,
+        response::{
+            ErrorResponse,
+            system::{CacheFlushResponse, CacheStatsResponse, RuntimeConfigResponse},
+        },
+    },
+    utils::AppError,
+};
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use tracing::{error, info};
+use validator::Validate;
+
+#[derive(Clone)]
+pub struct SystemServiceImpl {
+    runtime_config: Arc<RuntimeConfigResponse>,
+    cache_store: Arc<CacheStore>,
+}
+
+impl SystemServiceImpl {
+    pub fn new(runtime_config: RuntimeConfigResponse, cache_store: CacheStore) -> Self {
+        Self {
+            runtime_config: Arc::new(runtime_config),
+            cache_store: Arc::new(cache_store),
+        }
+    }
+
+    // SCAN memakai koneksi redis sinkron, jadi dijalankan di luar worker async
+    async fn run_cache_task<T, F>(&self, f: F) -> Result<T, Status>
+    where
+        T: Send + 'static,
+        F: FnOnce(&CacheStore) -> Result<T, AppError> + Send + 'static,
+    {
+        let cache_store = self.cache_store.clone();
+
+        tokio::task::spawn_blocking(move || f(&cache_store))
+            .await
+            .map_err(|e| Status::internal(format!("Cache task failed: {e}")))?
+            .map_err(|err| ErrorResponse::from(err).into())
+    }
+}
+
+#[tonic::async_trait]
+impl SystemService for SystemServiceImpl {
+    async fn get_runtime_config(
+        &self,
+        _request: Request<GetRuntimeConfigRequest>,
+    ) -> Result<Response<ApiResponseRuntimeConfig>, Status> {
+        info!("Fetching runtime configuration");
+
+        let reply = ApiResponseRuntimeConfig {
+            status: "success".to_string(),
+            message: "Runtime configuration retrieved successfully".to_string(),
+            data: Some(self.runtime_config.as_ref().clone().into()),
+        };
+
+        Ok(Response::new(reply))
+    }
+
+    async fn cache_stats(
+        &self,
+        _request: Request<CacheStatsRequest>,
+    ) -> Result<Response<ApiResponseCacheStats>, Status> {
+        info!("Collecting cache key statistics");
+
+        let counts = self
+            .run_cache_task(|cache| cache.key_counts_by_prefix())
+            .await
+            .inspect_err(|status| error!("Failed to collect cache stats: {}", status.message()))?;
+
+        let reply = ApiResponseCacheStats {
+            status: "success".to_string(),
+            message: "Cache statistics retrieved successfully".to_string(),
+            data: Some(CacheStatsResponse::from(counts).into()),
+        };
+
+        Ok(Response::new(reply))
+    }
+
+    async fn cache_flush_prefix(
+        &self,
+        request: Request<CacheFlushPrefixRequest>,
+    ) -> Result<Response<ApiResponseCacheFlush>, Status> {
+        let input = CacheFlushRequest {
+            prefix: request.into_inner().prefix,
+        };
+
+        info!("Flushing cache keys under prefix: {}", input.prefix);
+
+        if let Err(errors) = input.validate() {
+            return Err(ErrorResponse::from(AppError::ValidationError(errors)).into());
+        }
+
+        let prefix = input.prefix.clone();
+        let removed = self
+            .run_cache_task(move |cache| cache.flush_prefix(&prefix))
+            .await
+            .inspect_err(|status| error!("Failed to flush cache prefix: {}", status.message()))?;
+
+        let reply = ApiResponseCacheFlush {
+            status: "success".to_string(),
+            message: format!("Removed {removed} cache key(s)"),
+            data: Some(
+                CacheFlushResponse {
+                    prefix: input.prefix,
+                    removed: removed as i64,
+                }
+                .into(),
+            ),
+        };
+
+        Ok(Response::new(reply))
+    }
+}
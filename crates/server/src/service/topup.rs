@@ -1,13 +1,16 @@
+use super::parse_date;
 use genproto::api::ApiResponseEmpty;
 use genproto::topup::{
-    ApiResponseTopupResponse, ApiResponsesTopupPaginated, ApiResponsesTopupResponse,
-    CreateTopupRequest, FindAllTopupRequest, FindTopupByIdRequest, FindTopupByUserIdRequest,
-    UpdateTopupRequest, topup_service_server::TopupService,
+    ApiResponseTopupResponse, ApiResponseTopupStats, ApiResponsesTopupPaginated,
+    CreateTopupRequest, FindAllTopupRequest, FindTopupByIdRequest, FindTopupByNoRequest,
+    FindTopupByUserIdRequest, FindTopupByUsersIdRequest, TopupStatsRequest, UpdateTopupRequest,
+    topup_service_server::TopupService,
 };
 use shared::{
     domain::request::{
         CreateTopupRequest as SharedCreateTopupRequest,
-        FindAllTopupRequest as SharedFindAllTopupRequest,
+        FindAllTopupRequest as SharedFindAllTopupRequest, PageRequest,
+        TopupStatsRequest as SharedTopupStatsRequest,
         UpdateTopupRequest as SharedUpdateTopupRequest,
     },
     state::AppState,
@@ -16,6 +19,8 @@ use std::sync::Arc;
 use tonic::{Request, Response, Status};
 use tracing::{error, info};
 
+use crate::auth::require_owner;
+
 #[derive(Debug, Clone)]
 pub struct TopupServiceImpl {
     pub state: Arc<AppState>,
@@ -69,7 +74,7 @@ impl TopupService for TopupServiceImpl {
             Err(err) => {
                 error!("Failed to fetch topups: {}", err.message);
 
-                Err(Status::internal("Failed to fetch topups"))
+                Err(err.into())
             }
         }
     }
@@ -100,7 +105,45 @@ impl TopupService for TopupServiceImpl {
             Err(err) => {
                 error!("Failed to fetch topup: {}", err.message);
 
-                Err(Status::internal("Failed to fetch topup"))
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn find_topup_by_no(
+        &self,
+        request: Request<FindTopupByNoRequest>,
+    ) -> Result<Response<ApiResponseTopupResponse>, Status> {
+        let topup_no = request.into_inner().topup_no;
+
+        info!("Finding topup by topup_no: {}", topup_no);
+
+        if topup_no.trim().is_empty() {
+            return Err(Status::invalid_argument("topup_no must not be empty"));
+        }
+
+        match self
+            .state
+            .di_container
+            .topup_service
+            .get_topup_by_no(&topup_no)
+            .await
+        {
+            Ok(api_response) => {
+                let reply = ApiResponseTopupResponse {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data: Some(api_response.data.into()),
+                };
+
+                info!("Topup fetched successfully");
+
+                Ok(Response::new(reply))
+            }
+            Err(err) => {
+                error!("Failed to fetch topup by topup_no: {}", err.message);
+
+                Err(err.into())
             }
         }
     }
@@ -137,43 +180,47 @@ impl TopupService for TopupServiceImpl {
             Err(err) => {
                 error!("Failed to fetch topup: {}", err.message);
 
-                Err(Status::internal("Failed to fetch topup"))
+                Err(err.into())
             }
         }
     }
 
     async fn find_topup_by_users_id(
         &self,
-        request: Request<FindTopupByUserIdRequest>,
-    ) -> Result<Response<ApiResponsesTopupResponse>, Status> {
-        let request = request.into_inner();
+        request: Request<FindTopupByUsersIdRequest>,
+    ) -> Result<Response<ApiResponsesTopupPaginated>, Status> {
+        let req = request.into_inner();
 
-        info!("Finding topup by user id: {}", request.user_id);
+        info!("Finding topup by user id : {}", req.user_id);
+
+        let page_request = PageRequest {
+            page: req.page,
+            page_size: req.page_size,
+        };
 
         match self
             .state
             .di_container
             .topup_service
-            .get_topup_users(request.user_id)
+            .get_topup_users(req.user_id, &page_request)
             .await
         {
             Ok(api_response) => {
-                let data = api_response
-                    .data
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(Into::into)
-                    .collect();
-
-                let reply = ApiResponsesTopupResponse {
-                    status: "success".into(),
-                    message: "Topup fetched successfully".into(),
-                    data,
-                };
+                let data = api_response.data.into_iter().map(Into::into).collect();
 
-                Ok(Response::new(reply))
+                info!("Topup fetched successfully for user id : {}", req.user_id);
+
+                Ok(Response::new(ApiResponsesTopupPaginated {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data,
+                    pagination: Some(api_response.pagination.into()),
+                }))
+            }
+            Err(err) => {
+                error!("Failed to fetch topup: {}", err.message);
+                Err(err.into())
             }
-            Err(err) => Err(Status::internal(err.message)),
         }
     }
 
@@ -183,6 +230,7 @@ impl TopupService for TopupServiceImpl {
     ) -> Result<Response<ApiResponseTopupResponse>, Status> {
         info!("Creating topup");
 
+        require_owner(&request, request.get_ref().user_id)?;
         let req = request.get_ref();
 
         let body = SharedCreateTopupRequest {
@@ -213,7 +261,7 @@ impl TopupService for TopupServiceImpl {
             Err(err) => {
                 error!("Failed to create topup: {}", err.message);
 
-                Err(Status::internal("Failed to create topup"))
+                Err(err.into())
             }
         }
     }
@@ -230,7 +278,7 @@ impl TopupService for TopupServiceImpl {
             user_id: req.user_id,
             topup_id: req.topup_id,
             topup_amount: req.topup_amount,
-            topup_method: req.topup_method.to_string(),
+            topup_method: req.topup_method.clone(),
         };
 
         match self
@@ -254,7 +302,7 @@ impl TopupService for TopupServiceImpl {
             Err(err) => {
                 error!("Failed to update topup: {}", err.message);
 
-                Err(Status::internal("Failed to update topup"))
+                Err(err.into())
             }
         }
     }
@@ -276,7 +324,127 @@ impl TopupService for TopupServiceImpl {
             }
             Err(err) => {
                 error!("Failed to delete topup: {}", err);
-                Err(Status::internal("Failed to delete topup"))
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn confirm_topup(
+        &self,
+        request: Request<FindTopupByIdRequest>,
+    ) -> Result<Response<ApiResponseTopupResponse>, Status> {
+        info!("Confirming topup");
+
+        let id = request.into_inner().id;
+
+        match self
+            .state
+            .di_container
+            .topup_service
+            .confirm_topup(id)
+            .await
+        {
+            Ok(api_response) => {
+                info!("Topup confirmed successfully");
+
+                Ok(Response::new(ApiResponseTopupResponse {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data: Some(api_response.data.into()),
+                }))
+            }
+            Err(err) => {
+                error!("Failed to confirm topup: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn fail_topup(
+        &self,
+        request: Request<FindTopupByIdRequest>,
+    ) -> Result<Response<ApiResponseTopupResponse>, Status> {
+        info!("Failing topup");
+
+        let id = request.into_inner().id;
+
+        match self.state.di_container.topup_service.fail_topup(id).await {
+            Ok(api_response) => {
+                info!("Topup marked as failed successfully");
+
+                Ok(Response::new(ApiResponseTopupResponse {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data: Some(api_response.data.into()),
+                }))
+            }
+            Err(err) => {
+                error!("Failed to fail topup: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn refund_topup(
+        &self,
+        request: Request<FindTopupByIdRequest>,
+    ) -> Result<Response<ApiResponseTopupResponse>, Status> {
+        info!("Refunding topup");
+
+        let id = request.into_inner().id;
+
+        match self.state.di_container.topup_service.refund_topup(id).await {
+            Ok(api_response) => {
+                info!("Topup refunded successfully");
+
+                Ok(Response::new(ApiResponseTopupResponse {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data: Some(api_response.data.into()),
+                }))
+            }
+            Err(err) => {
+                error!("Failed to refund topup: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn topup_stats(
+        &self,
+        request: Request<TopupStatsRequest>,
+    ) -> Result<Response<ApiResponseTopupStats>, Status> {
+        let req = request.into_inner();
+
+        info!(
+            "Aggregating topup stats, start_date: {:?}, end_date: {:?}",
+            req.start_date, req.end_date
+        );
+
+        let stats_request = SharedTopupStatsRequest {
+            start_date: parse_date("start_date", &req.start_date)?,
+            end_date: parse_date("end_date", &req.end_date)?,
+        };
+
+        match self
+            .state
+            .di_container
+            .topup_service
+            .get_topup_stats(&stats_request)
+            .await
+        {
+            Ok(api_response) => {
+                info!("Topup stats aggregated successfully");
+
+                Ok(Response::new(ApiResponseTopupStats {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data: api_response.data.into_iter().map(Into::into).collect(),
+                }))
+            }
+            Err(err) => {
+                error!("Failed to aggregate topup stats: {}", err);
+                Err(err.into())
             }
         }
     }
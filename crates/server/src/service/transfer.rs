@@ -1,21 +1,33 @@
+use super::parse_date;
 use genproto::api::ApiResponseEmpty;
 use genproto::transfer::{
-    ApiResponseTransferResponse, ApiResponsesTransferPaginated, ApiResponsesTransferResponse,
-    CreateTransferRequest, FindAllTransferRequest, FindTransferByIdRequest,
-    FindTransferByUserIdRequest, UpdateTransferRequest, transfer_service_server::TransferService,
+    ApiResponseDailyTransferTotals, ApiResponseTransferResponse, ApiResponsesTransferPaginated,
+    CreateTransferRequest, DailyTransferTotalsRequest, FindAllTransferRequest,
+    FindTransferByIdRequest, FindTransferByUserIdRequest, FindTransferByUsersIdRequest,
+    StreamAllTransfersRequest, TransferResponse, UpdateTransferRequest,
+    transfer_service_server::TransferService,
 };
 use shared::{
     domain::request::{
         CreateTransferRequest as SharedCreateTransferRequest,
-        FindAllTransferRequest as SharedFindAllTransferRequest,
+        DailyTransferTotalsRequest as SharedDailyTransferTotalsRequest,
+        FindAllTransferRequest as SharedFindAllTransferRequest, PageRequest,
+        StreamTransfersRequest as SharedStreamTransfersRequest,
         UpdateTransferRequest as SharedUpdateTransferRequest,
     },
     state::AppState,
 };
 use std::sync::Arc;
+use tokio::sync::mpsc;
+use tokio_stream::wrappers::ReceiverStream;
 use tonic::{Request, Response, Status};
 use tracing::{error, info};
 
+use crate::auth::require_owner;
+
+// buffer kecil supaya pembacaan DB ikut tertahan saat client lambat menerima
+const STREAM_BUFFER: usize = 64;
+
 #[derive(Debug, Clone)]
 pub struct TransferServiceImpl {
     pub state: Arc<AppState>,
@@ -39,6 +51,9 @@ impl TransferService for TransferServiceImpl {
             page: req.page,
             page_size: req.page_size,
             search: req.search.clone(),
+            min_amount: req.min_amount,
+            max_amount: req.max_amount,
+            status: req.status.clone(),
         };
 
         match self
@@ -63,11 +78,74 @@ impl TransferService for TransferServiceImpl {
             }
             Err(err) => {
                 error!("Failed to fetch transfer: {}", err.message);
-                Err(Status::internal(err.message))
+                Err(err.into())
             }
         }
     }
 
+    type StreamAllTransfersStream = ReceiverStream<Result<TransferResponse, Status>>;
+
+    async fn stream_all_transfers(
+        &self,
+        request: Request<StreamAllTransfersRequest>,
+    ) -> Result<Response<Self::StreamAllTransfersStream>, Status> {
+        let req = request.get_ref();
+
+        let body = SharedStreamTransfersRequest {
+            start_date: parse_date("start_date", &req.start_date)?,
+            end_date: parse_date("end_date", &req.end_date)?,
+            batch_size: req.batch_size,
+        };
+
+        // after_id memungkinkan client melanjutkan ekspor yang terputus
+        let after_id = req.after_id.max(0);
+        let service = self.state.di_container.transfer_service.clone();
+        let (tx, rx) = mpsc::channel(STREAM_BUFFER);
+
+        tokio::spawn(async move {
+            let mut cursor = after_id;
+            let mut sent = 0usize;
+
+            loop {
+                // receiver di-drop saat client putus, query yang sedang jalan ikut dibatalkan
+                let batch = tokio::select! {
+                    _ = tx.closed() => {
+                        info!("Client disconnected, stopping transfer stream after {sent} transfer(s)");
+                        return;
+                    }
+                    batch = service.get_transfer_batch(&body, cursor) => batch,
+                };
+
+                let batch = match batch {
+                    Ok(batch) => batch,
+                    Err(err) => {
+                        error!("Failed to stream transfers: {}", err.message);
+                        let _ = tx.send(Err(err.into())).await;
+                        return;
+                    }
+                };
+
+                let Some(last) = batch.last() else {
+                    info!("Transfer stream completed with {sent} transfer(s)");
+                    return;
+                };
+                cursor = last.transfer_id;
+
+                for transfer in batch {
+                    if tx.send(Ok(transfer.into())).await.is_err() {
+                        info!(
+                            "Client disconnected, stopping transfer stream after {sent} transfer(s)"
+                        );
+                        return;
+                    }
+                    sent += 1;
+                }
+            }
+        });
+
+        Ok(Response::new(ReceiverStream::new(rx)))
+    }
+
     async fn find_transfer_by_id(
         &self,
         request: Request<FindTransferByIdRequest>,
@@ -100,7 +178,7 @@ impl TransferService for TransferServiceImpl {
             Err(err) => {
                 error!("Failed to fetch transfer: {}", err.message);
 
-                Err(Status::internal("Failed to fetch transfer"))
+                Err(err.into())
             }
         }
     }
@@ -137,48 +215,91 @@ impl TransferService for TransferServiceImpl {
             Err(err) => {
                 error!("Failed to fetch transfer: {}", err.message);
 
-                Err(Status::internal("Failed to fetch transfer"))
+                Err(err.into())
             }
         }
     }
 
     async fn find_transfer_by_users_id(
         &self,
-        request: Request<FindTransferByUserIdRequest>,
-    ) -> Result<Response<ApiResponsesTransferResponse>, Status> {
-        let user_id = request.into_inner().user_id;
+        request: Request<FindTransferByUsersIdRequest>,
+    ) -> Result<Response<ApiResponsesTransferPaginated>, Status> {
+        let req = request.into_inner();
 
-        info!("Finding transfer by user id : {}", user_id);
+        info!("Finding transfer by user id : {}", req.user_id);
+
+        let page_request = PageRequest {
+            page: req.page,
+            page_size: req.page_size,
+        };
 
         match self
             .state
             .di_container
             .transfer_service
-            .get_transfer_users(user_id)
+            .get_transfer_users(req.user_id, &page_request)
             .await
         {
             Ok(api_response) => {
-                let data_vec = api_response
-                    .data
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(Into::into)
-                    .collect();
-
-                let reply = ApiResponsesTransferResponse {
-                    status: "success".into(),
-                    message: "Transfer fetched successfully".into(),
-                    data: data_vec,
-                };
+                let data = api_response.data.into_iter().map(Into::into).collect();
 
-                info!("Transfer fetched successfully");
+                info!(
+                    "Transfer fetched successfully for user id : {}",
+                    req.user_id
+                );
 
-                Ok(Response::new(reply))
+                Ok(Response::new(ApiResponsesTransferPaginated {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data,
+                    pagination: Some(api_response.pagination.into()),
+                }))
             }
             Err(err) => {
                 error!("Failed to fetch transfer: {}", err.message);
+                Err(err.into())
+            }
+        }
+    }
 
-                Err(Status::internal("Failed to fetch transfer"))
+    async fn get_user_transfer_history(
+        &self,
+        request: Request<FindTransferByUsersIdRequest>,
+    ) -> Result<Response<ApiResponsesTransferPaginated>, Status> {
+        let req = request.into_inner();
+
+        info!("Finding transfer history for user id : {}", req.user_id);
+
+        let page_request = PageRequest {
+            page: req.page,
+            page_size: req.page_size,
+        };
+
+        match self
+            .state
+            .di_container
+            .transfer_service
+            .get_user_transfer_history(req.user_id, &page_request)
+            .await
+        {
+            Ok(api_response) => {
+                let data = api_response.data.into_iter().map(Into::into).collect();
+
+                info!(
+                    "Transfer history fetched successfully for user id : {}",
+                    req.user_id
+                );
+
+                Ok(Response::new(ApiResponsesTransferPaginated {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data,
+                    pagination: Some(api_response.pagination.into()),
+                }))
+            }
+            Err(err) => {
+                error!("Failed to fetch transfer history: {}", err.message);
+                Err(err.into())
             }
         }
     }
@@ -189,12 +310,17 @@ impl TransferService for TransferServiceImpl {
     ) -> Result<Response<ApiResponseTransferResponse>, Status> {
         info!("Creating transfer");
 
+        require_owner(&request, request.get_ref().transfer_from)?;
         let req = request.get_ref();
 
         let body = SharedCreateTransferRequest {
             transfer_from: req.transfer_from,
             transfer_to: req.transfer_to,
             transfer_amount: req.transfer_amount,
+            note: (!req.note.is_empty()).then(|| req.note.clone()),
+            dry_run: req.dry_run,
+            from_label: (!req.from_label.is_empty()).then(|| req.from_label.clone()),
+            to_label: (!req.to_label.is_empty()).then(|| req.to_label.clone()),
         };
 
         match self
@@ -218,7 +344,7 @@ impl TransferService for TransferServiceImpl {
             Err(err) => {
                 error!("Failed to create transfer: {}", err.message);
 
-                Err(Status::internal("Failed to create transfer"))
+                Err(err.into())
             }
         }
     }
@@ -236,6 +362,7 @@ impl TransferService for TransferServiceImpl {
             transfer_from: req.transfer_from,
             transfer_to: req.transfer_to,
             transfer_amount: req.transfer_amount,
+            note: (!req.note.is_empty()).then(|| req.note.clone()),
         };
 
         match self
@@ -259,7 +386,7 @@ impl TransferService for TransferServiceImpl {
             Err(err) => {
                 error!("Failed to update transfer: {}", err.message);
 
-                Err(Status::internal("Failed to update transfer"))
+                Err(err.into())
             }
         }
     }
@@ -288,7 +415,112 @@ impl TransferService for TransferServiceImpl {
             Err(err) => {
                 error!("Failed to delete transfer: {}", err.message);
 
-                Err(Status::internal("Failed to delete transfer"))
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn reverse_transfer(
+        &self,
+        request: Request<FindTransferByIdRequest>,
+    ) -> Result<Response<ApiResponseTransferResponse>, Status> {
+        info!("Reversing transfer");
+
+        let id = request.into_inner().id;
+
+        match self
+            .state
+            .di_container
+            .transfer_service
+            .reverse_transfer(id)
+            .await
+        {
+            Ok(api_response) => {
+                info!("Transfer reversed successfully");
+
+                Ok(Response::new(ApiResponseTransferResponse {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data: Some(api_response.data.into()),
+                }))
+            }
+            Err(err) => {
+                error!("Failed to reverse transfer: {}", err.message);
+
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn approve_transfer(
+        &self,
+        request: Request<FindTransferByIdRequest>,
+    ) -> Result<Response<ApiResponseTransferResponse>, Status> {
+        info!("Approving transfer");
+
+        let id = request.into_inner().id;
+
+        match self
+            .state
+            .di_container
+            .transfer_service
+            .approve_transfer(id)
+            .await
+        {
+            Ok(api_response) => {
+                info!("Transfer approved successfully");
+
+                Ok(Response::new(ApiResponseTransferResponse {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data: Some(api_response.data.into()),
+                }))
+            }
+            Err(err) => {
+                error!("Failed to approve transfer: {}", err.message);
+
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn daily_transfer_totals(
+        &self,
+        request: Request<DailyTransferTotalsRequest>,
+    ) -> Result<Response<ApiResponseDailyTransferTotals>, Status> {
+        let req = request.into_inner();
+
+        info!(
+            "Aggregating daily transfer totals, start_date: {:?}, end_date: {:?}",
+            req.start_date, req.end_date
+        );
+
+        let totals_request = SharedDailyTransferTotalsRequest {
+            start_date: parse_date("start_date", &req.start_date)?
+                .ok_or_else(|| Status::invalid_argument("start_date is required"))?,
+            end_date: parse_date("end_date", &req.end_date)?
+                .ok_or_else(|| Status::invalid_argument("end_date is required"))?,
+        };
+
+        match self
+            .state
+            .di_container
+            .transfer_service
+            .get_daily_transfer_totals(&totals_request)
+            .await
+        {
+            Ok(api_response) => {
+                info!("Daily transfer totals aggregated successfully");
+
+                Ok(Response::new(ApiResponseDailyTransferTotals {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data: api_response.data.into_iter().map(Into::into).collect(),
+                }))
+            }
+            Err(err) => {
+                error!("Failed to aggregate daily transfer totals: {}", err);
+                Err(err.into())
             }
         }
     }
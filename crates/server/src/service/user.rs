@@ -1,19 +1,25 @@
 use genproto::api::ApiResponseEmpty;
 use genproto::user::{
-    ApiResponseUserResponse, ApiResponsesUserPaginated, CreateUserRequest, FindAllUserRequest,
-    FindUserByIdRequest, UpdateUserRequest, user_service_server::UserService,
+    ApiResponseImportUsers, ApiResponsePurgeUser, ApiResponseUserDeletionCheck,
+    ApiResponseUserResponse, ApiResponsesUserPaginated, CreateUserRequest, DeleteUserRequest,
+    FindAllUserRequest, FindUserByIdRequest, ImportUsersRequest, PurgeUserRequest,
+    UpdateUserRequest, user_service_server::UserService,
 };
 use shared::{
     domain::request::{
-        FindAllUserRequest as SharedFindAllUserRequest, RegisterRequest,
+        FindAllUserRequest as SharedFindAllUserRequest, ImportUserRow,
+        ImportUsersRequest as SharedImportUsersRequest, RegisterRequest,
         UpdateUserRequest as SharedUpdateUserRequest,
     },
     state::AppState,
+    utils::parse_datetime,
 };
 use std::sync::Arc;
 use tonic::{Request, Response, Status};
 use tracing::{error, info};
 
+use crate::auth::require_admin;
+
 #[derive(Debug, Clone)]
 pub struct UserServiceImpl {
     pub state: Arc<AppState>,
@@ -33,12 +39,20 @@ impl UserService for UserServiceImpl {
     ) -> Result<Response<ApiResponsesUserPaginated>, Status> {
         info!("Finding all users");
 
+        if request.get_ref().include_deleted {
+            require_admin(&request)?;
+        }
+
         let req = request.get_ref();
 
         let myrequest = SharedFindAllUserRequest {
             page: req.page,
             page_size: req.page_size,
             search: req.search.clone(),
+            include_deleted: req.include_deleted,
+            email: req.email.clone(),
+            created_before: parse_datetime(&req.created_before),
+            created_after: parse_datetime(&req.created_after),
         };
 
         match self
@@ -63,7 +77,7 @@ impl UserService for UserServiceImpl {
             }
             Err(err) => {
                 error!("Failed to fetch users: {}", err.message);
-                Err(Status::internal("Failed to fetch users"))
+                Err(err.into())
             }
         }
     }
@@ -93,7 +107,7 @@ impl UserService for UserServiceImpl {
             },
             Err(err) => {
                 error!("Failed to fetch user: {}", err.message);
-                Err(Status::internal("Failed to fetch user"))
+                Err(err.into())
             }
         }
     }
@@ -131,7 +145,7 @@ impl UserService for UserServiceImpl {
             }
             Err(err) => {
                 error!("Failed to create user: {}", err.message);
-                Err(Status::internal("Failed to create user"))
+                Err(err.into())
             }
         }
     }
@@ -146,9 +160,9 @@ impl UserService for UserServiceImpl {
 
         let body = SharedUpdateUserRequest {
             id: req.id,
-            firstname: Some(req.firstname.clone()),
-            lastname: Some(req.lastname.clone()),
-            email: Some(req.email.clone()),
+            firstname: req.firstname.clone(),
+            lastname: req.lastname.clone(),
+            email: req.email.clone(),
             password: req.password.clone(),
             confirm_password: req.confirm_password.clone(),
         };
@@ -170,22 +184,32 @@ impl UserService for UserServiceImpl {
             }
             Err(err) => {
                 error!("Failed to update user: {}", err.message);
-                Err(Status::internal("Failed to update user"))
+                Err(err.into())
             }
         }
     }
 
     async fn delete_user(
         &self,
-        request: Request<FindUserByIdRequest>,
+        request: Request<DeleteUserRequest>,
     ) -> Result<Response<ApiResponseEmpty>, Status> {
-        info!("Deleting user");
+        if request.get_ref().force {
+            require_admin(&request)?;
+        }
 
-        let id = request.into_inner().id;
+        let req = request.into_inner();
 
-        match self.state.di_container.user_service.delete_user(id).await {
+        info!("Deleting user {} (force: {})", req.id, req.force);
+
+        match self
+            .state
+            .di_container
+            .user_service
+            .delete_user(req.id, req.force)
+            .await
+        {
             Ok(user) => {
-                info!("User deleted successfully");
+                info!("User {} deleted successfully", req.id);
                 Ok(Response::new(ApiResponseEmpty {
                     status: user.status,
                     message: user.message,
@@ -193,7 +217,183 @@ impl UserService for UserServiceImpl {
             }
             Err(err) => {
                 error!("Failed to delete user: {}", err.message);
-                Err(Status::internal("Failed to delete user"))
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn can_delete_user(
+        &self,
+        request: Request<FindUserByIdRequest>,
+    ) -> Result<Response<ApiResponseUserDeletionCheck>, Status> {
+        info!("Checking whether user can be deleted");
+
+        let id = request.into_inner().id;
+
+        match self
+            .state
+            .di_container
+            .user_service
+            .can_delete_user(id)
+            .await
+        {
+            Ok(response) => {
+                info!("User deletion check completed");
+                Ok(Response::new(ApiResponseUserDeletionCheck {
+                    status: response.status,
+                    message: response.message,
+                    data: Some(response.data.into()),
+                }))
+            }
+            Err(err) => {
+                error!("Failed to check user deletion: {}", err.message);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn restore_user(
+        &self,
+        request: Request<FindUserByIdRequest>,
+    ) -> Result<Response<ApiResponseUserResponse>, Status> {
+        info!("Restoring user");
+
+        let id = request.into_inner().id;
+
+        match self.state.di_container.user_service.restore_user(id).await {
+            Ok(user) => {
+                info!("User restored successfully");
+                Ok(Response::new(ApiResponseUserResponse {
+                    status: user.status,
+                    message: user.message,
+                    data: Some(user.data.into()),
+                }))
+            }
+            Err(err) => {
+                error!("Failed to restore user: {}", err.message);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn freeze_user(
+        &self,
+        request: Request<FindUserByIdRequest>,
+    ) -> Result<Response<ApiResponseUserResponse>, Status> {
+        info!("Freezing user");
+
+        let id = request.into_inner().id;
+
+        match self.state.di_container.user_service.freeze_user(id).await {
+            Ok(user) => {
+                info!("User frozen successfully");
+                Ok(Response::new(ApiResponseUserResponse {
+                    status: user.status,
+                    message: user.message,
+                    data: Some(user.data.into()),
+                }))
+            }
+            Err(err) => {
+                error!("Failed to freeze user: {}", err.message);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn unfreeze_user(
+        &self,
+        request: Request<FindUserByIdRequest>,
+    ) -> Result<Response<ApiResponseUserResponse>, Status> {
+        info!("Unfreezing user");
+
+        let id = request.into_inner().id;
+
+        match self.state.di_container.user_service.unfreeze_user(id).await {
+            Ok(user) => {
+                info!("User unfrozen successfully");
+                Ok(Response::new(ApiResponseUserResponse {
+                    status: user.status,
+                    message: user.message,
+                    data: Some(user.data.into()),
+                }))
+            }
+            Err(err) => {
+                error!("Failed to unfreeze user: {}", err.message);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn purge_user(
+        &self,
+        request: Request<PurgeUserRequest>,
+    ) -> Result<Response<ApiResponsePurgeUser>, Status> {
+        let req = request.into_inner();
+
+        info!("Purging user {} (force: {})", req.id, req.force);
+
+        match self
+            .state
+            .di_container
+            .user_service
+            .purge_user(req.id, req.force)
+            .await
+        {
+            Ok(response) => {
+                info!("User {} purged successfully", req.id);
+                Ok(Response::new(ApiResponsePurgeUser {
+                    status: response.status,
+                    message: response.message,
+                    data: Some(response.data.into()),
+                }))
+            }
+            Err(err) => {
+                error!("Failed to purge user: {}", err.message);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn import_users(
+        &self,
+        request: Request<ImportUsersRequest>,
+    ) -> Result<Response<ApiResponseImportUsers>, Status> {
+        let req = request.into_inner();
+
+        info!(
+            "Importing {} users (continue_on_error: {})",
+            req.rows.len(),
+            req.continue_on_error
+        );
+
+        let body = SharedImportUsersRequest {
+            rows: req
+                .rows
+                .into_iter()
+                .map(|row| ImportUserRow {
+                    name: row.name,
+                    email: row.email,
+                    password: row.password,
+                })
+                .collect(),
+            continue_on_error: req.continue_on_error,
+        };
+
+        match self
+            .state
+            .di_container
+            .user_service
+            .import_users(&body)
+            .await
+        {
+            Ok(response) => Ok(Response::new(ApiResponseImportUsers {
+                status: response.status,
+                message: response.message,
+                data: Some(response.data.into()),
+            })),
+            Err(err) => {
+                error!("Failed to import users: {}", err.message);
+                Err(err.into())
             }
         }
     }
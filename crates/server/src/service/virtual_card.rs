@@ -0,0 +1,141 @@
+use genproto::virtual_card::{
+    ApiResponseVirtualCardResponse, ApiResponseVirtualCardSummary,
+    ApiResponsesVirtualCardPaginated, FindVirtualCardsRequest, GenerateVirtualCardRequest,
+    RevokeVirtualCardRequest, virtual_card_service_server::VirtualCardService,
+};
+use shared::{
+    domain::request::FindVirtualCardsRequest as SharedFindVirtualCardsRequest, state::AppState,
+};
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use tracing::{error, info};
+
+use crate::auth::require_owner;
+
+#[derive(Debug, Clone)]
+pub struct VirtualCardServiceImpl {
+    state: Arc<AppState>,
+}
+
+impl VirtualCardServiceImpl {
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self { state }
+    }
+}
+
+#[tonic::async_trait]
+impl VirtualCardService for VirtualCardServiceImpl {
+    async fn generate_virtual_card(
+        &self,
+        request: Request<GenerateVirtualCardRequest>,
+    ) -> Result<Response<ApiResponseVirtualCardResponse>, Status> {
+        let user_id = request.get_ref().user_id;
+        require_owner(&request, user_id)?;
+
+        info!("Generating virtual card for user id : {user_id}");
+
+        match self
+            .state
+            .di_container
+            .virtual_card_service
+            .generate_virtual_card(user_id)
+            .await
+        {
+            Ok(api_response) => {
+                let reply = ApiResponseVirtualCardResponse {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data: Some(api_response.data.into()),
+                };
+
+                info!("Virtual card generated successfully for user id : {user_id}");
+
+                Ok(Response::new(reply))
+            }
+            Err(err) => {
+                error!("Failed to generate virtual card: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn list_virtual_cards(
+        &self,
+        request: Request<FindVirtualCardsRequest>,
+    ) -> Result<Response<ApiResponsesVirtualCardPaginated>, Status> {
+        let req = request.into_inner();
+
+        info!("Listing virtual cards for user id : {}", req.user_id);
+
+        let body = SharedFindVirtualCardsRequest {
+            page: req.page,
+            page_size: req.page_size,
+            include_revoked: req.include_revoked,
+        };
+
+        match self
+            .state
+            .di_container
+            .virtual_card_service
+            .list_virtual_cards(req.user_id, &body)
+            .await
+        {
+            Ok(api_response) => {
+                let data = api_response.data.into_iter().map(Into::into).collect();
+
+                info!(
+                    "Virtual cards fetched successfully for user id : {}",
+                    req.user_id
+                );
+
+                Ok(Response::new(ApiResponsesVirtualCardPaginated {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data,
+                    pagination: Some(api_response.pagination.into()),
+                }))
+            }
+            Err(err) => {
+                error!("Failed to list virtual cards: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn revoke_virtual_card(
+        &self,
+        request: Request<RevokeVirtualCardRequest>,
+    ) -> Result<Response<ApiResponseVirtualCardSummary>, Status> {
+        require_owner(&request, request.get_ref().user_id)?;
+        let req = request.into_inner();
+
+        info!(
+            "Revoking virtual card {} for user id : {}",
+            req.virtual_card_id, req.user_id
+        );
+
+        match self
+            .state
+            .di_container
+            .virtual_card_service
+            .revoke_virtual_card(req.user_id, req.virtual_card_id)
+            .await
+        {
+            Ok(api_response) => {
+                let reply = ApiResponseVirtualCardSummary {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data: Some(api_response.data.into()),
+                };
+
+                info!("Virtual card {} revoked successfully", req.virtual_card_id);
+
+                Ok(Response::new(reply))
+            }
+            Err(err) => {
+                error!("Failed to revoke virtual card: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
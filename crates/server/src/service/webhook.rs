@@ -0,0 +1,54 @@
+use genproto::webhook::{
+    ApiResponseWebhookDelivery, RetryWebhookRequest, webhook_service_server::WebhookService,
+};
+use shared::state::AppState;
+use std::sync::Arc;
+use tonic::{Request, Response, Status};
+use tracing::{error, info};
+
+#[derive(Debug, Clone)]
+pub struct WebhookServiceImpl {
+    state: Arc<AppState>,
+}
+
+impl WebhookServiceImpl {
+    pub fn new(state: Arc<AppState>) -> Self {
+        Self { state }
+    }
+}
+
+#[tonic::async_trait]
+impl WebhookService for WebhookServiceImpl {
+    async fn retry_webhook(
+        &self,
+        request: Request<RetryWebhookRequest>,
+    ) -> Result<Response<ApiResponseWebhookDelivery>, Status> {
+        let event_id = request.get_ref().event_id;
+
+        info!("Retrying webhook delivery for event id : {event_id}");
+
+        match self
+            .state
+            .di_container
+            .webhook_service
+            .retry_webhook(event_id)
+            .await
+        {
+            Ok(api_response) => {
+                let reply = ApiResponseWebhookDelivery {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data: Some(api_response.data.into()),
+                };
+
+                info!("Webhook delivery requeued for event id : {event_id}");
+
+                Ok(Response::new(reply))
+            }
+            Err(err) => {
+                error!("Failed to retry webhook delivery: {}", err);
+                Err(err.into())
+            }
+        }
+    }
+}
@@ -1,13 +1,13 @@
 use genproto::api::ApiResponseEmpty;
 use genproto::withdraw::{
-    ApiResponseWithdrawResponse, ApiResponsesWithdrawPaginated, ApiResponsesWithdrawResponse,
-    CreateWithdrawRequest, FindAllWithdrawRequest, FindWithdrawByIdRequest,
-    FindWithdrawByUserIdRequest, UpdateWithdrawRequest, withdraw_service_server::WithdrawService,
+    ApiResponseWithdrawResponse, ApiResponsesWithdrawPaginated, CreateWithdrawRequest,
+    FindAllWithdrawRequest, FindWithdrawByIdRequest, FindWithdrawByUserIdRequest,
+    FindWithdrawByUsersIdRequest, UpdateWithdrawRequest, withdraw_service_server::WithdrawService,
 };
 use shared::{
     domain::request::{
         CreateWithdrawRequest as SharedCreateWithdrawRequest,
-        FindAllWithdrawRequest as SharedFindAllWithdrawRequest,
+        FindAllWithdrawRequest as SharedFindAllWithdrawRequest, PageRequest,
         UpdateWithdrawRequest as SharedUpdateWithdrawRequest,
     },
     state::AppState,
@@ -16,6 +16,8 @@ use std::sync::Arc;
 use tonic::{Request, Response, Status};
 use tracing::{error, info};
 
+use crate::auth::require_owner;
+
 #[derive(Debug, Clone)]
 pub struct WithdrawServiceImpl {
     pub state: Arc<AppState>,
@@ -68,7 +70,7 @@ impl WithdrawService for WithdrawServiceImpl {
             Err(err) => {
                 error!("Failed to fetch withdraws: {}", err.message);
 
-                Err(Status::internal("Failed to fetch withdraws"))
+                Err(err.into())
             }
         }
     }
@@ -105,7 +107,7 @@ impl WithdrawService for WithdrawServiceImpl {
             Err(err) => {
                 error!("Failed to fetch withdraw: {}", err.message);
 
-                Err(Status::internal("Failed to fetch withdraw"))
+                Err(err.into())
             }
         }
     }
@@ -142,48 +144,49 @@ impl WithdrawService for WithdrawServiceImpl {
             Err(err) => {
                 error!("Failed to fetch withdraw: {}", err.message);
 
-                Err(Status::internal("Failed to fetch withdraw"))
+                Err(err.into())
             }
         }
     }
 
     async fn find_withdraw_by_users_id(
         &self,
-        request: Request<FindWithdrawByUserIdRequest>,
-    ) -> Result<Response<ApiResponsesWithdrawResponse>, Status> {
-        info!("Finding withdraw by user id");
+        request: Request<FindWithdrawByUsersIdRequest>,
+    ) -> Result<Response<ApiResponsesWithdrawPaginated>, Status> {
+        let req = request.into_inner();
 
-        let id = request.into_inner().user_id;
+        info!("Finding withdraw by user id : {}", req.user_id);
+
+        let page_request = PageRequest {
+            page: req.page,
+            page_size: req.page_size,
+        };
 
         match self
             .state
             .di_container
             .withdraw_service
-            .get_withdraw_users(id)
+            .get_withdraw_users(req.user_id, &page_request)
             .await
         {
             Ok(api_response) => {
-                let data = api_response
-                    .data
-                    .unwrap_or_default()
-                    .into_iter()
-                    .map(Into::into)
-                    .collect();
-
-                let reply = ApiResponsesWithdrawResponse {
-                    status: "success".into(),
-                    message: "Topup fetched successfully".into(),
-                    data,
-                };
+                let data = api_response.data.into_iter().map(Into::into).collect();
 
-                info!("Topup fetched successfully");
+                info!(
+                    "Withdraw fetched successfully for user id : {}",
+                    req.user_id
+                );
 
-                Ok(Response::new(reply))
+                Ok(Response::new(ApiResponsesWithdrawPaginated {
+                    status: api_response.status,
+                    message: api_response.message,
+                    data,
+                    pagination: Some(api_response.pagination.into()),
+                }))
             }
             Err(err) => {
-                error!("Failed to fetch topup: {}", err.message);
-
-                Err(Status::internal("Failed to fetch topup"))
+                error!("Failed to fetch withdraw: {}", err.message);
+                Err(err.into())
             }
         }
     }
@@ -194,6 +197,7 @@ impl WithdrawService for WithdrawServiceImpl {
     ) -> Result<Response<ApiResponseWithdrawResponse>, Status> {
         info!("Creating withdraw");
 
+        require_owner(&request, request.get_ref().user_id)?;
         let req = request.get_ref();
 
         let body = SharedCreateWithdrawRequest {
@@ -223,7 +227,7 @@ impl WithdrawService for WithdrawServiceImpl {
             Err(err) => {
                 error!("Failed to create withdraw: {}", err.message);
 
-                Err(Status::internal("Failed to create withdraw"))
+                Err(err.into())
             }
         }
     }
@@ -234,6 +238,7 @@ impl WithdrawService for WithdrawServiceImpl {
     ) -> Result<Response<ApiResponseWithdrawResponse>, Status> {
         info!("Updating withdraw");
 
+        require_owner(&request, request.get_ref().user_id)?;
         let req = request.get_ref();
 
         let body = SharedUpdateWithdrawRequest {
@@ -264,7 +269,7 @@ impl WithdrawService for WithdrawServiceImpl {
             Err(err) => {
                 error!("Failed to update withdraw: {}", err.message);
 
-                Err(Status::internal("Failed to update withdraw"))
+                Err(err.into())
             }
         }
     }
@@ -293,7 +298,7 @@ impl WithdrawService for WithdrawServiceImpl {
             }
             Err(err) => {
                 error!("Failed to delete withdraw: {}", err.message);
-                Err(Status::internal("Failed to delete withdraw"))
+                Err(err.into())
             }
         }
     }
@@ -0,0 +1,142 @@
+use axum::{
+    Json, Router,
+    body::to_bytes,
+    extract::Request,
+    response::{IntoResponse, Response},
+    routing::{MethodRouter, post},
+};
+use genproto::saldo::saldo_service_server::SaldoService;
+use serde::{Serialize, de::DeserializeOwned};
+use shared::domain::response::ErrorResponse;
+use std::{future::Future, sync::Arc};
+use tonic::{Code, Status};
+
+const MAX_JSON_BODY_BYTES: usize = 64 * 1024;
+
+// path sama persis dengan method gRPC supaya allowlist auth dan rate limit berlaku sama
+const SALDO_SERVICE: &str = "/saldo.SaldoService";
+
+// facade JSON untuk mitra yang tidak bisa gRPC: setiap method SaldoService menjadi
+// POST /saldo.SaldoService/{Method} yang memanggil implementasi gRPC yang sama
+pub fn saldo_routes<S: SaldoService>(service: S) -> Router {
+    let service = Arc::new(service);
+    let path = |method: &str| format!("{SALDO_SERVICE}/{method}");
+
+    Router::new()
+        .route(
+            &path("FindAllSaldo"),
+            unary(
+                service.clone(),
+                |s, r| async move { s.find_all_saldo(r).await },
+            ),
+        )
+        .route(
+            &path("FindSaldoById"),
+            unary(service.clone(), |s, r| async move {
+                s.find_saldo_by_id(r).await
+            }),
+        )
+        .route(
+            &path("FindSaldoByUserId"),
+            unary(service.clone(), |s, r| async move {
+                s.find_saldo_by_user_id(r).await
+            }),
+        )
+        .route(
+            &path("FindSaldoByUsersId"),
+            unary(service.clone(), |s, r| async move {
+                s.find_saldo_by_users_id(r).await
+            }),
+        )
+        .route(
+            &path("CreateSaldo"),
+            unary(
+                service.clone(),
+                |s, r| async move { s.create_saldo(r).await },
+            ),
+        )
+        .route(
+            &path("UpdateSaldo"),
+            unary(
+                service.clone(),
+                |s, r| async move { s.update_saldo(r).await },
+            ),
+        )
+        .route(
+            &path("DeleteSaldo"),
+            unary(
+                service.clone(),
+                |s, r| async move { s.delete_saldo(r).await },
+            ),
+        )
+        .route(
+            &path("TopSaldos"),
+            unary(service.clone(), |s, r| async move { s.top_saldos(r).await }),
+        )
+        .route(
+            &path("AdjustBalance"),
+            unary(
+                service.clone(),
+                |s, r| async move { s.adjust_balance(r).await },
+            ),
+        )
+        .route(
+            &path("ReconcileBalance"),
+            unary(service, |s, r| async move { s.reconcile_balance(r).await }),
+        )
+}
+
+// body JSON didecode ke request prost (body kosong = request default), header dan extension
+// HTTP ikut diteruskan sebagai metadata gRPC, lalu response prost dikirim balik sebagai JSON
+fn unary<S, Req, Res, F, Fut>(service: Arc<S>, call: F) -> MethodRouter
+where
+    S: Send + Sync + 'static,
+    Req: DeserializeOwned + Default + Send + 'static,
+    Res: Serialize,
+    F: Fn(Arc<S>, tonic::Request<Req>) -> Fut + Clone + Send + Sync + 'static,
+    Fut: Future<Output = Result<tonic::Response<Res>, Status>> + Send,
+{
+    post(move |request: Request| async move {
+        let (parts, body) = request.into_parts();
+
+        let bytes = match to_bytes(body, MAX_JSON_BODY_BYTES).await {
+            Ok(bytes) => bytes,
+            Err(e) => {
+                return ErrorResponse::from(Status::invalid_argument(format!(
+                    "Failed to read request body: {e}"
+                )))
+                .into_response();
+            }
+        };
+
+        let message = if bytes.is_empty() {
+            Req::default()
+        } else {
+            match serde_json::from_slice(&bytes) {
+                Ok(message) => message,
+                Err(e) => {
+                    return ErrorResponse::from(Status::invalid_argument(format!(
+                        "Invalid JSON body: {e}"
+                    )))
+                    .into_response();
+                }
+            }
+        };
+
+        let request = tonic::Request::from_http(axum::http::Request::from_parts(parts, message));
+
+        match call(service, request).await {
+            Ok(response) => Json(response.into_inner()).into_response(),
+            Err(status) => ErrorResponse::from(status).into_response(),
+        }
+    })
+}
+
+// auth dan rate limit menolak dengan response gRPC (grpc-status di header, body kosong);
+// untuk client JSON penolakan itu diubah menjadi ErrorResponse biasa
+pub async fn grpc_rejection_to_json(response: Response) -> Response {
+    match Status::from_header_map(response.headers()) {
+        Some(status) if status.code() != Code::Ok => ErrorResponse::from(status).into_response(),
+        _ => response,
+    }
+}
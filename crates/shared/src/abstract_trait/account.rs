@@ -0,0 +1,19 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::{
+    request::AccountSummaryRequest,
+    response::{ApiResponse, ErrorResponse, account::AccountSummaryResponse},
+};
+
+pub type DynAccountService = Arc<dyn AccountServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait AccountServiceTrait {
+    async fn get_account_summary(
+        &self,
+        user_id: i32,
+        req: &AccountSummaryRequest,
+    ) -> Result<ApiResponse<AccountSummaryResponse>, ErrorResponse>;
+}
@@ -3,8 +3,12 @@ use async_trait::async_trait;
 use std::sync::Arc;
 
 use crate::domain::{
-    request::auth::{LoginRequest, RegisterRequest},
-    response::{ApiResponse, ErrorResponse, user::UserResponse},
+    request::auth::{ChangePasswordRequest, LoginRequest, RefreshTokenRequest, RegisterRequest},
+    response::{
+        ApiResponse, ErrorResponse,
+        auth::{LoginResponse, SessionResponse},
+        user::UserResponse,
+    },
 };
 
 pub type DynAuthService = Arc<dyn AuthServiceTrait + Send + Sync>;
@@ -15,6 +19,27 @@ pub trait AuthServiceTrait {
         &self,
         input: &RegisterRequest,
     ) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
-    async fn login_user(&self, input: &LoginRequest) -> Result<ApiResponse<String>, ErrorResponse>;
+    async fn login_user(
+        &self,
+        input: &LoginRequest,
+    ) -> Result<ApiResponse<LoginResponse>, ErrorResponse>;
+    async fn refresh_token(
+        &self,
+        input: &RefreshTokenRequest,
+    ) -> Result<ApiResponse<LoginResponse>, ErrorResponse>;
     async fn get_me(&self, id: i32) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
+    async fn change_password(
+        &self,
+        id: i32,
+        input: &ChangePasswordRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn list_sessions(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<Vec<SessionResponse>>, ErrorResponse>;
+    async fn revoke_session(
+        &self,
+        user_id: i32,
+        session_id: i32,
+    ) -> Result<ApiResponse<()>, ErrorResponse>;
 }
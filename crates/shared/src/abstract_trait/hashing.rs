@@ -11,6 +11,8 @@ pub trait HashingTrait: Send + Sync {
     async fn hash_password(&self, password: &str) -> Result<String, BcryptError>;
     async fn compare_password(&self, hashed_password: &str, password: &str)
     -> Result<(), AppError>;
+    // true kalau hash tersimpan dibuat dengan algoritma selain yang sedang dikonfigurasi
+    fn needs_rehash(&self, hashed_password: &str) -> bool;
 }
 
 pub type DynHashing = Arc<dyn HashingTrait + Send + Sync>;
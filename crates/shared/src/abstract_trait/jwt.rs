@@ -1,4 +1,4 @@
-use crate::utils::AppError;
+use crate::{config::Claims, utils::AppError};
 use async_trait::async_trait;
 use std::sync::Arc;
 
@@ -8,6 +8,7 @@ use anyhow::Result;
 pub trait JwtServiceTrait: Send + Sync + std::fmt::Debug {
     fn generate_token(&self, user_id: i64) -> Result<String, AppError>;
     fn verify_token(&self, token: &str) -> Result<i64, AppError>;
+    fn verify_claims(&self, token: &str) -> Result<Claims, AppError>;
 }
 
 pub type DynJwtService = Arc<dyn JwtServiceTrait + Send + Sync>;
@@ -1,21 +1,34 @@
+pub mod account;
 pub mod auth;
 pub mod hashing;
 pub mod jwt;
+pub mod outbox;
 pub mod saldo;
+pub mod session;
+pub mod system;
 pub mod topup;
 pub mod transfer;
 pub mod user;
+pub mod virtual_card;
+pub mod webhook;
 pub mod withdraw;
 
+pub use self::account::{AccountServiceTrait, DynAccountService};
 pub use self::auth::{AuthServiceTrait, DynAuthService};
 pub use self::hashing::{DynHashing, HashingTrait};
 
 pub use self::jwt::{DynJwtService, JwtServiceTrait};
 
+pub use self::outbox::{DynOutboxRepository, OutboxRepositoryTrait};
+
 pub use self::saldo::{
     DynSaldoRepository, DynSaldoService, SaldoRepositoryTrait, SaldoServiceTrait,
 };
 
+pub use self::session::{DynSessionRepository, SessionRepositoryTrait};
+
+pub use self::system::{DynSystemService, SystemServiceTrait};
+
 pub use self::topup::{
     DynTopupRepository, DynTopupService, TopupRepositoryTrait, TopupServiceTrait,
 };
@@ -29,3 +42,10 @@ pub use self::user::{DynUserRepository, DynUserService, UserRepositoryTrait, Use
 pub use self::withdraw::{
     DynWithdrawRepository, DynWithdrawService, WithdrawRepositoryTrait, WithdrawServiceTrait,
 };
+
+pub use self::virtual_card::{
+    DynVirtualCardRepository, DynVirtualCardService, VirtualCardRepositoryTrait,
+    VirtualCardServiceTrait,
+};
+
+pub use self::webhook::{DynWebhookService, WebhookServiceTrait};
@@ -0,0 +1,31 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::NaiveDateTime;
+use std::{sync::Arc, time::Duration};
+
+use crate::{model::outbox::OutboxEvent, utils::AppError};
+
+pub type DynOutboxRepository = Arc<dyn OutboxRepositoryTrait + Send + Sync>;
+
+#[async_trait]
+pub trait OutboxRepositoryTrait {
+    // mengklaim event pending secara atomik: next_attempt_at dimajukan sebesar lease sehingga
+    // worker lain melewatinya selama event sedang dikirim. Pengiriman bersifat at-least-once:
+    // worker yang mati sebelum mark_delivered, atau pengiriman yang melewati lease, membuat
+    // event dikirim ulang, jadi penerima harus dedup berdasarkan header X-Event-Id
+    async fn claim_pending(
+        &self,
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<OutboxEvent>, AppError>;
+    async fn find_by_id(&self, id: i64) -> Result<Option<OutboxEvent>, AppError>;
+    async fn requeue(&self, id: i64) -> Result<Option<OutboxEvent>, AppError>;
+    async fn mark_delivered(&self, id: i64) -> Result<(), AppError>;
+    async fn schedule_retry(
+        &self,
+        id: i64,
+        next_attempt_at: NaiveDateTime,
+        last_error: &str,
+    ) -> Result<(), AppError>;
+    async fn mark_failed(&self, id: i64, last_error: &str) -> Result<(), AppError>;
+}
@@ -5,12 +5,19 @@ use std::sync::Arc;
 use crate::{
     domain::{
         request::{
-            CreateSaldoRequest, FindAllSaldoRequest, UpdateSaldoBalance, UpdateSaldoRequest,
+            AdjustBalanceRequest, CreateSaldoRequest, FindAllSaldoRequest, PageRequest,
+            ReconcileBalanceRequest, TopSaldosRequest, UpdateSaldoBalance, UpdateSaldoRequest,
             UpdateSaldoWithdraw,
         },
-        response::{ApiResponse, ApiResponsePagination, ErrorResponse, saldo::SaldoResponse},
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            saldo::{BalanceReconciliationResponse, SaldoRankingResponse, SaldoResponse},
+        },
+    },
+    model::{
+        ledger::LedgerEntryType,
+        saldo::{BalanceReconciliation, Saldo, SaldoRanking, SaldoWithUser},
     },
-    model::saldo::Saldo,
     utils::AppError,
 };
 
@@ -26,14 +33,37 @@ pub trait SaldoRepositoryTrait {
         search: Option<String>,
     ) -> Result<(Vec<Saldo>, i64), AppError>;
     async fn find_by_id(&self, id: i32) -> Result<Option<Saldo>, AppError>;
+    async fn exists_by_id(&self, id: i32) -> Result<bool, AppError>;
 
-    async fn find_by_users_id(&self, id: i32) -> Result<Vec<Saldo>, AppError>;
+    async fn find_by_users_id(
+        &self,
+        id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Saldo>, i64), AppError>;
     async fn find_by_user_id(&self, id: i32) -> Result<Option<Saldo>, AppError>;
+    async fn find_with_user_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<SaldoWithUser>, AppError>;
     async fn create(&self, input: &CreateSaldoRequest) -> Result<Saldo, AppError>;
     async fn update(&self, input: &UpdateSaldoRequest) -> Result<Saldo, AppError>;
     async fn update_balance(&self, input: &UpdateSaldoBalance) -> Result<Saldo, AppError>;
     async fn update_saldo_withdraw(&self, input: &UpdateSaldoWithdraw) -> Result<Saldo, AppError>;
     async fn delete(&self, id: i32) -> Result<(), AppError>;
+    async fn totals(&self) -> Result<(i64, i64), AppError>;
+    async fn top_by_balance(&self, limit: i32) -> Result<Vec<SaldoRanking>, AppError>;
+    async fn adjust_balance(
+        &self,
+        actor_id: i32,
+        input: &AdjustBalanceRequest,
+        entry_type: LedgerEntryType,
+    ) -> Result<Saldo, AppError>;
+    async fn find_reconciliation(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<BalanceReconciliation>, AppError>;
+    async fn find_balance_mismatches(&self) -> Result<Vec<BalanceReconciliation>, AppError>;
 }
 
 #[async_trait]
@@ -47,7 +77,8 @@ pub trait SaldoServiceTrait {
     async fn get_saldo_users(
         &self,
         id: i32,
-    ) -> Result<ApiResponse<Option<Vec<SaldoResponse>>>, ErrorResponse>;
+        req: &PageRequest,
+    ) -> Result<ApiResponsePagination<Vec<SaldoResponse>>, ErrorResponse>;
     async fn get_saldo_user(
         &self,
         id: i32,
@@ -61,4 +92,18 @@ pub trait SaldoServiceTrait {
         input: &UpdateSaldoRequest,
     ) -> Result<ApiResponse<SaldoResponse>, ErrorResponse>;
     async fn delete_saldo(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn get_top_saldos(
+        &self,
+        req: &TopSaldosRequest,
+    ) -> Result<ApiResponse<Vec<SaldoRankingResponse>>, ErrorResponse>;
+    async fn adjust_balance(
+        &self,
+        actor_id: i32,
+        input: &AdjustBalanceRequest,
+    ) -> Result<ApiResponse<SaldoResponse>, ErrorResponse>;
+    async fn reconcile_balance(
+        &self,
+        actor_id: i32,
+        input: &ReconcileBalanceRequest,
+    ) -> Result<ApiResponse<Vec<BalanceReconciliationResponse>>, ErrorResponse>;
 }
@@ -0,0 +1,20 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::{domain::request::CreateSessionRequest, model::session::Session, utils::AppError};
+
+pub type DynSessionRepository = Arc<dyn SessionRepositoryTrait + Send + Sync>;
+
+#[async_trait]
+pub trait SessionRepositoryTrait {
+    async fn create(&self, input: &CreateSessionRequest) -> Result<Session, AppError>;
+    async fn find_active_by_user(&self, user_id: i32) -> Result<Vec<Session>, AppError>;
+    async fn find_active_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<Session>, AppError>;
+    async fn touch(&self, session_id: i32) -> Result<(), AppError>;
+    async fn revoke(&self, user_id: i32, session_id: i32) -> Result<(), AppError>;
+    async fn revoke_all_for_user(&self, user_id: i32) -> Result<u64, AppError>;
+}
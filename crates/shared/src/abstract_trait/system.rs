@@ -0,0 +1,24 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::{
+    request::CacheFlushRequest,
+    response::{
+        ApiResponse, ErrorResponse,
+        system::{CacheFlushResponse, CacheStatsResponse, RuntimeConfigResponse},
+    },
+};
+
+pub type DynSystemService = Arc<dyn SystemServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait SystemServiceTrait {
+    async fn get_runtime_config(&self)
+    -> Result<ApiResponse<RuntimeConfigResponse>, ErrorResponse>;
+    async fn cache_stats(&self) -> Result<ApiResponse<CacheStatsResponse>, ErrorResponse>;
+    async fn cache_flush_prefix(
+        &self,
+        input: &CacheFlushRequest,
+    ) -> Result<ApiResponse<CacheFlushResponse>, ErrorResponse>;
+}
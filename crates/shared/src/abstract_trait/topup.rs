@@ -4,10 +4,16 @@ use std::sync::Arc;
 
 use crate::{
     domain::{
-        request::{CreateTopupRequest, FindAllTopupRequest, UpdateTopupAmount, UpdateTopupRequest},
-        response::{ApiResponse, ApiResponsePagination, ErrorResponse, topup::TopupResponse},
+        request::{
+            CreateTopupRequest, FindAllTopupRequest, PageRequest, TopupStatsRequest,
+            UpdateTopupAmount, UpdateTopupRequest,
+        },
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            topup::{TopupMethodStatsResponse, TopupResponse},
+        },
     },
-    model::topup::Topup,
+    model::topup::{Topup, TopupMethodStats},
     utils::AppError,
 };
 
@@ -24,12 +30,28 @@ pub trait TopupRepositoryTrait {
     ) -> Result<(Vec<Topup>, i64), AppError>;
 
     async fn find_by_id(&self, id: i32) -> Result<Option<Topup>, AppError>;
-    async fn find_by_users(&self, id: i32) -> Result<Vec<Topup>, AppError>;
+    async fn find_by_topup_no(&self, topup_no: &str) -> Result<Option<Topup>, AppError>;
+    async fn find_by_users(
+        &self,
+        id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Topup>, i64), AppError>;
     async fn find_by_user(&self, id: i32) -> Result<Option<Topup>, AppError>;
+    async fn find_recent_by_user(&self, user_id: i32, limit: i32) -> Result<Vec<Topup>, AppError>;
     async fn create(&self, input: &CreateTopupRequest) -> Result<Topup, AppError>;
     async fn update(&self, input: &UpdateTopupRequest) -> Result<Topup, AppError>;
+    // selisih nominal topup sukses ditambahkan ke saldo utama dalam transaksi yang sama
+    async fn update_with_settlement(&self, input: &UpdateTopupRequest) -> Result<Topup, AppError>;
     async fn update_amount(&self, input: &UpdateTopupAmount) -> Result<Topup, AppError>;
     async fn delete(&self, id: i32) -> Result<(), AppError>;
+    async fn confirm(&self, id: i32) -> Result<Topup, AppError>;
+    async fn fail(&self, id: i32) -> Result<Topup, AppError>;
+    async fn refund(&self, id: i32) -> Result<Topup, AppError>;
+    async fn stats_by_method(
+        &self,
+        req: &TopupStatsRequest,
+    ) -> Result<Vec<TopupMethodStats>, AppError>;
 }
 
 #[async_trait]
@@ -40,10 +62,15 @@ pub trait TopupServiceTrait {
     ) -> Result<ApiResponsePagination<Vec<TopupResponse>>, ErrorResponse>;
     async fn get_topup(&self, id: i32)
     -> Result<ApiResponse<Option<TopupResponse>>, ErrorResponse>;
+    async fn get_topup_by_no(
+        &self,
+        topup_no: &str,
+    ) -> Result<ApiResponse<TopupResponse>, ErrorResponse>;
     async fn get_topup_users(
         &self,
         id: i32,
-    ) -> Result<ApiResponse<Option<Vec<TopupResponse>>>, ErrorResponse>;
+        req: &PageRequest,
+    ) -> Result<ApiResponsePagination<Vec<TopupResponse>>, ErrorResponse>;
     async fn get_topup_user(
         &self,
         id: i32,
@@ -57,4 +84,11 @@ pub trait TopupServiceTrait {
         input: &UpdateTopupRequest,
     ) -> Result<ApiResponse<TopupResponse>, ErrorResponse>;
     async fn delete_topup(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn confirm_topup(&self, id: i32) -> Result<ApiResponse<TopupResponse>, ErrorResponse>;
+    async fn fail_topup(&self, id: i32) -> Result<ApiResponse<TopupResponse>, ErrorResponse>;
+    async fn refund_topup(&self, id: i32) -> Result<ApiResponse<TopupResponse>, ErrorResponse>;
+    async fn get_topup_stats(
+        &self,
+        req: &TopupStatsRequest,
+    ) -> Result<ApiResponse<Vec<TopupMethodStatsResponse>>, ErrorResponse>;
 }
@@ -1,16 +1,20 @@
 use anyhow::Result;
 use async_trait::async_trait;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 
 use crate::{
     domain::{
         request::{
-            CreateTransferRequest, FindAllTransferRequest, UpdateTransferAmountRequest,
+            CreateTransferRequest, DailyTransferTotalsRequest, FindAllTransferRequest, PageRequest,
+            StreamTransfersRequest, TransferFilter, UpdateTransferAmountRequest,
             UpdateTransferRequest,
         },
-        response::{ApiResponse, ApiResponsePagination, ErrorResponse, transfer::TransferResponse},
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            transfer::{DailyTransferTotalResponse, TransferResponse},
+        },
     },
-    model::transfer::Transfer,
+    model::transfer::{DailyTransferTotal, Transfer, TransferPreview, TransferStatus},
     utils::AppError,
 };
 
@@ -23,12 +27,50 @@ pub trait TransferRepositoryTrait {
         &self,
         page: i32,
         page_size: i32,
-        search: Option<String>,
+        filter: &TransferFilter,
     ) -> Result<(Vec<Transfer>, i64), AppError>;
+    async fn find_batch_after(
+        &self,
+        after_id: i32,
+        req: &StreamTransfersRequest,
+    ) -> Result<Vec<Transfer>, AppError>;
+    async fn daily_totals(
+        &self,
+        req: &DailyTransferTotalsRequest,
+    ) -> Result<Vec<DailyTransferTotal>, AppError>;
     async fn find_by_id(&self, id: i32) -> Result<Option<Transfer>, AppError>;
-    async fn find_by_users(&self, id: i32) -> Result<Vec<Transfer>, AppError>;
+    async fn find_by_users(
+        &self,
+        id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Transfer>, i64), AppError>;
+    async fn find_all_involving_user(
+        &self,
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Transfer>, i64), AppError>;
     async fn find_by_user(&self, id: i32) -> Result<Option<Transfer>, AppError>;
     async fn create(&self, input: &CreateTransferRequest) -> Result<Transfer, AppError>;
+    async fn count_recent_by_sender(&self, user_id: i32, window: Duration)
+    -> Result<i64, AppError>;
+    async fn create_with_settlement(
+        &self,
+        input: &CreateTransferRequest,
+        status: TransferStatus,
+    ) -> Result<Transfer, AppError>;
+    async fn preview_settlement(
+        &self,
+        input: &CreateTransferRequest,
+        status: TransferStatus,
+    ) -> Result<TransferPreview, AppError>;
+    async fn reverse(&self, id: i32) -> Result<Transfer, AppError>;
+    async fn approve(&self, id: i32) -> Result<Transfer, AppError>;
+    async fn update_with_settlement(
+        &self,
+        input: &UpdateTransferRequest,
+    ) -> Result<Transfer, AppError>;
     async fn update(&self, input: &UpdateTransferRequest) -> Result<Transfer, AppError>;
     async fn update_amount(
         &self,
@@ -43,6 +85,11 @@ pub trait TransferServiceTrait {
         &self,
         req: &FindAllTransferRequest,
     ) -> Result<ApiResponsePagination<Vec<TransferResponse>>, ErrorResponse>;
+    async fn get_transfer_batch(
+        &self,
+        req: &StreamTransfersRequest,
+        after_id: i32,
+    ) -> Result<Vec<TransferResponse>, ErrorResponse>;
     async fn get_transfer(
         &self,
         id: i32,
@@ -50,7 +97,13 @@ pub trait TransferServiceTrait {
     async fn get_transfer_users(
         &self,
         id: i32,
-    ) -> Result<ApiResponse<Option<Vec<TransferResponse>>>, ErrorResponse>;
+        req: &PageRequest,
+    ) -> Result<ApiResponsePagination<Vec<TransferResponse>>, ErrorResponse>;
+    async fn get_user_transfer_history(
+        &self,
+        user_id: i32,
+        req: &PageRequest,
+    ) -> Result<ApiResponsePagination<Vec<TransferResponse>>, ErrorResponse>;
     async fn get_transfer_user(
         &self,
         id: i32,
@@ -64,4 +117,16 @@ pub trait TransferServiceTrait {
         input: &UpdateTransferRequest,
     ) -> Result<ApiResponse<TransferResponse>, ErrorResponse>;
     async fn delete_transfer(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn reverse_transfer(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<TransferResponse>, ErrorResponse>;
+    async fn approve_transfer(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<TransferResponse>, ErrorResponse>;
+    async fn get_daily_transfer_totals(
+        &self,
+        req: &DailyTransferTotalsRequest,
+    ) -> Result<ApiResponse<Vec<DailyTransferTotalResponse>>, ErrorResponse>;
 }
@@ -4,10 +4,18 @@ use std::sync::Arc;
 
 use crate::{
     domain::{
-        request::{CreateUserRequest, FindAllUserRequest, RegisterRequest, UpdateUserRequest},
-        response::{ApiResponse, ApiResponsePagination, ErrorResponse, user::UserResponse},
+        request::{
+            CreateUserRequest, FindAllUserRequest, ImportUsersRequest, RegisterRequest,
+            UpdateUserRequest, UserFilter,
+        },
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            user::{
+                ImportUsersResponse, PurgeUserResponse, UserDeletionCheckResponse, UserResponse,
+            },
+        },
     },
-    model::user::User,
+    model::user::{User, UserDeleteBlockers, UserPurge, UserStatus},
     utils::AppError,
 };
 
@@ -20,14 +28,30 @@ pub trait UserRepositoryTrait {
         &self,
         page: i32,
         page_size: i32,
-        search: Option<String>,
+        filter: &UserFilter,
     ) -> Result<(Vec<User>, i64), AppError>;
     async fn find_by_email_exists(&self, email: &str) -> Result<bool, AppError>;
     async fn create_user(&self, input: &CreateUserRequest) -> Result<User, AppError>;
+    // satu transaksi; tanpa continue_on_error baris gagal pertama me-rollback semuanya
+    // dan hasilnya berhenti di baris tersebut
+    async fn create_many(
+        &self,
+        inputs: &[CreateUserRequest],
+        continue_on_error: bool,
+    ) -> Result<Vec<Result<User, AppError>>, AppError>;
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError>;
     async fn find_by_id(&self, id: i32) -> Result<Option<User>, AppError>;
+    async fn exists_by_id(&self, id: i32) -> Result<bool, AppError>;
     async fn update_user(&self, input: &UpdateUserRequest) -> Result<User, AppError>;
+    // hanya menghitung, tidak mengunci; delete_user bisa tetap dipanggil dengan force
+    async fn find_delete_blockers(&self, id: i32) -> Result<UserDeleteBlockers, AppError>;
     async fn delete_user(&self, id: i32) -> Result<(), AppError>;
+    async fn restore_user(&self, id: i32) -> Result<User, AppError>;
+    async fn update_password(&self, id: i32, password: &str) -> Result<User, AppError>;
+    // ganti hash tanpa mengubah password_changed_at, password-nya sendiri tetap sama
+    async fn rehash_password(&self, id: i32, password: &str) -> Result<(), AppError>;
+    async fn update_status(&self, id: i32, status: UserStatus) -> Result<User, AppError>;
+    async fn purge_user(&self, id: i32, force: bool) -> Result<UserPurge, AppError>;
 }
 
 #[async_trait]
@@ -45,5 +69,21 @@ pub trait UserServiceTrait {
         &self,
         input: &UpdateUserRequest,
     ) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
-    async fn delete_user(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn can_delete_user(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<UserDeletionCheckResponse>, ErrorResponse>;
+    async fn delete_user(&self, id: i32, force: bool) -> Result<ApiResponse<()>, ErrorResponse>;
+    async fn restore_user(&self, id: i32) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
+    async fn freeze_user(&self, id: i32) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
+    async fn unfreeze_user(&self, id: i32) -> Result<ApiResponse<UserResponse>, ErrorResponse>;
+    async fn purge_user(
+        &self,
+        id: i32,
+        force: bool,
+    ) -> Result<ApiResponse<PurgeUserResponse>, ErrorResponse>;
+    async fn import_users(
+        &self,
+        input: &ImportUsersRequest,
+    ) -> Result<ApiResponse<ImportUsersResponse>, ErrorResponse>;
 }
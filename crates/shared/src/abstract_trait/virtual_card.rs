@@ -0,0 +1,49 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::{
+    domain::{
+        request::{CreateVirtualCardRequest, FindVirtualCardsRequest},
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            virtual_card::{VirtualCardResponse, VirtualCardSummaryResponse},
+        },
+    },
+    model::virtual_card::VirtualCard,
+    utils::AppError,
+};
+
+pub type DynVirtualCardRepository = Arc<dyn VirtualCardRepositoryTrait + Send + Sync>;
+pub type DynVirtualCardService = Arc<dyn VirtualCardServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait VirtualCardRepositoryTrait {
+    async fn create(&self, input: &CreateVirtualCardRequest) -> Result<VirtualCard, AppError>;
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+        include_revoked: bool,
+    ) -> Result<(Vec<VirtualCard>, i64), AppError>;
+    async fn revoke(&self, user_id: i32, virtual_card_id: i32) -> Result<VirtualCard, AppError>;
+}
+
+#[async_trait]
+pub trait VirtualCardServiceTrait {
+    async fn generate_virtual_card(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<VirtualCardResponse>, ErrorResponse>;
+    async fn list_virtual_cards(
+        &self,
+        user_id: i32,
+        req: &FindVirtualCardsRequest,
+    ) -> Result<ApiResponsePagination<Vec<VirtualCardSummaryResponse>>, ErrorResponse>;
+    async fn revoke_virtual_card(
+        &self,
+        user_id: i32,
+        virtual_card_id: i32,
+    ) -> Result<ApiResponse<VirtualCardSummaryResponse>, ErrorResponse>;
+}
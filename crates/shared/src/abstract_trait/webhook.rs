@@ -0,0 +1,15 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use std::sync::Arc;
+
+use crate::domain::response::{ApiResponse, ErrorResponse, webhook::WebhookDeliveryResponse};
+
+pub type DynWebhookService = Arc<dyn WebhookServiceTrait + Send + Sync>;
+
+#[async_trait]
+pub trait WebhookServiceTrait {
+    async fn retry_webhook(
+        &self,
+        event_id: i64,
+    ) -> Result<ApiResponse<WebhookDeliveryResponse>, ErrorResponse>;
+}
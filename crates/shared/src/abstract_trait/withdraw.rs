@@ -4,7 +4,9 @@ use std::sync::Arc;
 
 use crate::{
     domain::{
-        request::{CreateWithdrawRequest, FindAllWithdrawRequest, UpdateWithdrawRequest},
+        request::{
+            CreateWithdrawRequest, FindAllWithdrawRequest, PageRequest, UpdateWithdrawRequest,
+        },
         response::{ApiResponse, ApiResponsePagination, ErrorResponse, withdraw::WithdrawResponse},
     },
     model::withdraw::Withdraw,
@@ -23,10 +25,31 @@ pub trait WithdrawRepositoryTrait {
         search: Option<String>,
     ) -> Result<(Vec<Withdraw>, i64), AppError>;
     async fn find_by_id(&self, id: i32) -> Result<Option<Withdraw>, AppError>;
-    async fn find_by_users(&self, id: i32) -> Result<Vec<Withdraw>, AppError>;
+    async fn exists_by_id(&self, id: i32) -> Result<bool, AppError>;
+    async fn find_by_users(
+        &self,
+        id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Withdraw>, i64), AppError>;
     async fn find_by_user(&self, id: i32) -> Result<Option<Withdraw>, AppError>;
+    async fn find_recent_by_user(
+        &self,
+        user_id: i32,
+        limit: i32,
+    ) -> Result<Vec<Withdraw>, AppError>;
     async fn create(&self, input: &CreateWithdrawRequest) -> Result<Withdraw, AppError>;
+    // nominal withdraw dipotong dari saldo utama dalam transaksi yang sama dengan insert-nya
+    async fn create_with_settlement(
+        &self,
+        input: &CreateWithdrawRequest,
+    ) -> Result<Withdraw, AppError>;
     async fn update(&self, input: &UpdateWithdrawRequest) -> Result<Withdraw, AppError>;
+    // selisih nominal lama dan baru dipotong dari saldo utama dalam transaksi yang sama
+    async fn update_with_settlement(
+        &self,
+        input: &UpdateWithdrawRequest,
+    ) -> Result<Withdraw, AppError>;
     async fn delete(&self, id: i32) -> Result<(), AppError>;
 }
 
@@ -43,7 +66,8 @@ pub trait WithdrawServiceTrait {
     async fn get_withdraw_users(
         &self,
         id: i32,
-    ) -> Result<ApiResponse<Option<Vec<WithdrawResponse>>>, ErrorResponse>;
+        req: &PageRequest,
+    ) -> Result<ApiResponsePagination<Vec<WithdrawResponse>>, ErrorResponse>;
     async fn get_withdraw_user(
         &self,
         id: i32,
@@ -0,0 +1,108 @@
+use std::{
+    sync::Mutex as StdMutex,
+    time::{Duration, Instant},
+};
+use tracing::{debug, info, warn};
+
+#[derive(Debug, Clone, Copy)]
+enum BreakerState {
+    Closed,
+    Open { until: Instant },
+}
+
+// setelah Redis gagal, cache dilewati selama `cooldown`; satu request kemudian
+// dibiarkan mencoba lagi untuk memastikan Redis sudah pulih
+#[derive(Debug)]
+pub(crate) struct CircuitBreaker {
+    state: StdMutex<BreakerState>,
+    cooldown: Duration,
+}
+
+impl CircuitBreaker {
+    pub(crate) fn new(cooldown: Duration) -> Self {
+        Self {
+            state: StdMutex::new(BreakerState::Closed),
+            cooldown,
+        }
+    }
+
+    pub(crate) fn allow(&self) -> bool {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        match *state {
+            BreakerState::Closed => true,
+            BreakerState::Open { until } if Instant::now() < until => false,
+            BreakerState::Open { .. } => {
+                // hanya pemanggil pertama setelah cooldown yang menjadi probe
+                *state = BreakerState::Open {
+                    until: Instant::now() + self.cooldown,
+                };
+                debug!("🔌 Probing Redis after cooldown");
+                true
+            }
+        }
+    }
+
+    // true selama Redis dianggap mati, termasuk saat probe setelah cooldown sedang berjalan
+    pub(crate) fn is_open(&self) -> bool {
+        let state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        matches!(*state, BreakerState::Open { .. })
+    }
+
+    pub(crate) fn record_success(&self) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let BreakerState::Open { .. } = *state {
+            *state = BreakerState::Closed;
+            info!("✅ Redis reachable again, cache re-enabled");
+        }
+    }
+
+    pub(crate) fn record_failure(&self, reason: &str) {
+        let mut state = self.state.lock().unwrap_or_else(|e| e.into_inner());
+
+        if let BreakerState::Closed = *state {
+            warn!(
+                "⚠️  Redis unavailable ({reason}), bypassing cache for {}s",
+                self.cooldown.as_secs()
+            );
+        }
+
+        *state = BreakerState::Open {
+            until: Instant::now() + self.cooldown,
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn healthy_redis_keeps_the_breaker_closed() {
+        let breaker = CircuitBreaker::new(Duration::from_secs(30));
+
+        breaker.record_success();
+
+        assert!(breaker.allow());
+        assert!(!breaker.is_open());
+    }
+
+    #[test]
+    fn failure_bypasses_redis_until_one_probe_after_the_cooldown() {
+        let breaker = CircuitBreaker::new(Duration::from_millis(50));
+
+        breaker.record_failure("connection refused");
+        assert!(breaker.is_open());
+        assert!(!breaker.allow());
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert!(breaker.allow());
+        assert!(!breaker.allow());
+
+        breaker.record_success();
+        assert!(!breaker.is_open());
+        assert!(breaker.allow());
+    }
+}
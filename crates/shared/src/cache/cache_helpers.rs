@@ -1,36 +1,302 @@
-use redis::{Commands, Connection};
-use serde::{Serialize, de::DeserializeOwned};
-use std::{sync::Arc, time::Duration};
-use tracing::{debug, error, warn};
+use crate::{
+    cache::breaker::CircuitBreaker,
+    config::{CacheTtlConfig, RateLimit},
+    utils::AppError,
+};
+use chrono::Utc;
+use redis::{AsyncConnectionConfig, Commands, Connection, RedisResult, aio::MultiplexedConnection};
+use serde::{Deserialize, Serialize, de::DeserializeOwned};
+use std::{
+    collections::{BTreeMap, HashMap, HashSet},
+    fmt::Display,
+    future::Future,
+    sync::{
+        Arc, Mutex as StdMutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+use tokio::{
+    runtime::Handle,
+    sync::OnceCell,
+    task::JoinHandle,
+    time::{Instant, sleep},
+};
+use tracing::{debug, error, info, warn};
+use uuid::Uuid;
+
+// hasil compute yang sedang berjalan per key, dibagi ke semua pemanggil dalam bentuk JSON
+type InflightMap = HashMap<String, Arc<OnceCell<String>>>;
+
+// nilai stale-while-revalidate disimpan bersama waktu terakhir disegarkan
+#[derive(Serialize, Deserialize)]
+struct SwrEntry<T> {
+    refreshed_at: i64,
+    value: T,
+}
+
+impl<T> SwrEntry<T> {
+    fn fresh(value: T) -> Self {
+        Self {
+            refreshed_at: Utc::now().timestamp(),
+            value,
+        }
+    }
+
+    fn age(&self) -> Duration {
+        Duration::from_secs((Utc::now().timestamp() - self.refreshed_at).max(0) as u64)
+    }
+}
+
+// token bucket dihitung atomik di Redis dengan jam Redis sendiri, sehingga semua instance
+// server berbagi bucket yang sama tanpa bergantung pada jam lokal masing-masing;
+// mengembalikan 0 jika token diambil atau milidetik sampai token berikutnya tersedia
+const TOKEN_BUCKET_SCRIPT: &str = r#"
+local capacity = tonumber(ARGV[1])
+local period_ms = tonumber(ARGV[2])
+local time = redis.call('TIME')
+local now = tonumber(time[1]) * 1000 + math.floor(tonumber(time[2]) / 1000)
+
+local state = redis.call('HMGET', KEYS[1], 'tokens', 'updated_at')
+local tokens = tonumber(state[1]) or capacity
+local updated_at = tonumber(state[2]) or now
+local rate = capacity / period_ms
+
+tokens = math.min(capacity, tokens + math.max(0, now - updated_at) * rate)
+
+local wait_ms = 0
+if tokens >= 1 then
+    tokens = tokens - 1
+else
+    wait_ms = math.ceil((1 - tokens) / rate)
+end
+
+redis.call('HSET', KEYS[1], 'tokens', tostring(tokens), 'updated_at', now)
+redis.call('PEXPIRE', KEYS[1], period_ms)
+
+return wait_ms
+"#;
+
+// lock hanya dihapus oleh pemiliknya, lock yang sudah kedaluwarsa dan diambil request lain
+// tidak ikut terhapus
+const UNLOCK_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('DEL', KEYS[1])
+end
+return 0
+"#;
+
+// TTL hanya diperpanjang oleh pemiliknya selama lock masih dipegang
+const RENEW_SCRIPT: &str = r#"
+if redis.call('GET', KEYS[1]) == ARGV[1] then
+    return redis.call('PEXPIRE', KEYS[1], ARGV[2])
+end
+return 0
+"#;
+
+// lock saldo per user kedaluwarsa sendiri jika pemegangnya mati di tengah mutasi,
+// selama pemegangnya hidup TTL diperpanjang setiap sepertiga TTL
+const USER_LOCK_TTL: Duration = Duration::from_secs(10);
+const USER_LOCK_WAIT: Duration = Duration::from_secs(5);
+const USER_LOCK_RETRY: Duration = Duration::from_millis(50);
+
+// SCAN dan UNLINK dikerjakan per batch supaya Redis tidak tertahan satu perintah besar
+const SCAN_BATCH: usize = 500;
+
+// key operasional, bukan cache: lock saldo, bucket rate limit, penanda read-after-write dan
+// counter login gagal tidak boleh ikut terhapus oleh flush prefix
+const PROTECTED_PREFIXES: &[&str] = &[
+    "lock:",
+    "rate_limit:",
+    "read_primary:",
+    "auth:login_failures:",
+];
+
+// Redis yang lambat tidak boleh menahan request lebih lama dari query DB-nya sendiri
+const REDIS_TIMEOUT: Duration = Duration::from_millis(250);
+const REDIS_COOLDOWN: Duration = Duration::from_secs(30);
+
+// dilepas saat drop, termasuk ketika future pemegang lock dibatalkan di tengah jalan;
+// UNLOCK dikirim dari task terpisah lewat koneksi async supaya drop tidak memblok runtime
+struct UserLockGuard {
+    store: CacheStore,
+    conn: Option<MultiplexedConnection>,
+    token: String,
+    keys: Vec<String>,
+    renewal: Option<JoinHandle<()>>,
+}
+
+impl Drop for UserLockGuard {
+    fn drop(&mut self) {
+        if let Some(renewal) = self.renewal.take() {
+            renewal.abort();
+        }
+
+        if self.keys.is_empty() {
+            return;
+        }
+
+        let (Some(mut conn), Ok(runtime)) = (self.conn.take(), Handle::try_current()) else {
+            return;
+        };
+
+        let store = self.store.clone();
+        let token = std::mem::take(&mut self.token);
+        let keys = std::mem::take(&mut self.keys);
+
+        runtime.spawn(async move {
+            for key in keys {
+                let result: RedisResult<i64> = store.track(
+                    redis::Script::new(UNLOCK_SCRIPT)
+                        .key(&key)
+                        .arg(&token)
+                        .invoke_async(&mut conn)
+                        .await,
+                );
+
+                if let Err(e) = result {
+                    error!("Failed to release lock {key}: {e:?}");
+                }
+            }
+        });
+    }
+}
 
 #[derive(Clone)]
 pub struct CacheStore {
     pub redis: Arc<redis::Client>,
+    inflight: Arc<StdMutex<InflightMap>>,
+    refreshing: Arc<StdMutex<HashSet<String>>>,
+    breaker: Arc<CircuitBreaker>,
+    ttl_config: CacheTtlConfig,
+    ttl: Duration,
+    require_user_locks: bool,
+}
+
+impl std::fmt::Debug for CacheStore {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("CacheStore")
+            .field("ttl", &self.ttl)
+            .field("degraded", &self.is_degraded())
+            .finish()
+    }
 }
 
 impl CacheStore {
-    pub fn new(redis: redis::Client) -> Self {
+    pub fn new(redis: redis::Client, ttl_config: CacheTtlConfig) -> Self {
         Self {
             redis: Arc::new(redis),
+            inflight: Arc::new(StdMutex::new(HashMap::new())),
+            refreshing: Arc::new(StdMutex::new(HashSet::new())),
+            breaker: Arc::new(CircuitBreaker::new(REDIS_COOLDOWN)),
+            ttl_config,
+            ttl: CacheTtlConfig::default().user,
+            require_user_locks: true,
+        }
+    }
+
+    // repository in-memory sudah menserialkan semua tulisan di bawah satu mutex, jadi test
+    // boleh berjalan tanpa Redis; di produksi lock saldo selalu wajib
+    #[cfg(any(test, feature = "test-utils"))]
+    pub fn without_user_locks(self) -> Self {
+        Self {
+            require_user_locks: false,
+            ..self
+        }
+    }
+
+    // salinan store dengan TTL milik satu entity, koneksi dan single-flight tetap dibagi
+    pub fn for_entity(&self, ttl: Duration) -> Self {
+        Self {
+            ttl,
+            ..self.clone()
+        }
+    }
+
+    pub fn ttl_config(&self) -> CacheTtlConfig {
+        self.ttl_config
+    }
+
+    pub fn ttl(&self) -> Duration {
+        self.ttl
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        !self.ttl.is_zero()
+    }
+
+    // cache sedang dilewati karena Redis gagal; service tetap jalan langsung ke DB
+    pub fn is_degraded(&self) -> bool {
+        self.breaker.is_open()
+    }
+
+    // koneksi async khusus lock: lock dipegang selama future mutasi berjalan, perintah
+    // blocking di sini akan menahan worker thread runtime
+    async fn get_async_conn(&self) -> Option<MultiplexedConnection> {
+        if !self.breaker.allow() {
+            return None;
+        }
+
+        let config = AsyncConnectionConfig::new()
+            .set_connection_timeout(REDIS_TIMEOUT)
+            .set_response_timeout(REDIS_TIMEOUT);
+
+        match self
+            .redis
+            .get_multiplexed_async_connection_with_config(&config)
+            .await
+        {
+            Ok(conn) => Some(conn),
+            Err(e) => {
+                self.breaker.record_failure(&e.to_string());
+                None
+            }
         }
     }
 
+    // None saat Redis sedang dianggap mati, pemanggil langsung jatuh ke DB
     fn get_conn(&self) -> Option<Connection> {
-        match self.redis.get_connection() {
+        if !self.breaker.allow() {
+            return None;
+        }
+
+        let conn = self
+            .redis
+            .get_connection_with_timeout(REDIS_TIMEOUT)
+            .and_then(|conn| {
+                conn.set_read_timeout(Some(REDIS_TIMEOUT))?;
+                conn.set_write_timeout(Some(REDIS_TIMEOUT))?;
+                Ok(conn)
+            });
+
+        match conn {
             Ok(conn) => Some(conn),
             Err(e) => {
-                error!("Failed to get Redis connection: {:?}", e);
+                self.breaker.record_failure(&e.to_string());
                 None
             }
         }
     }
 
+    // hanya error koneksi yang membuka breaker, error perintah biasa tidak
+    fn track<R>(&self, result: RedisResult<R>) -> RedisResult<R> {
+        match &result {
+            Ok(_) => self.breaker.record_success(),
+            Err(e) if e.is_io_error() || e.is_timeout() || e.is_connection_dropped() => {
+                self.breaker.record_failure(&e.to_string())
+            }
+            Err(_) => {}
+        }
+
+        result
+    }
+
     pub fn get_from_cache<T>(&self, key: &str) -> Option<T>
     where
         T: DeserializeOwned,
     {
         let mut conn = self.get_conn()?;
-        let result: redis::RedisResult<Option<String>> = conn.get(key);
+        let result: RedisResult<Option<String>> = self.track(conn.get(key));
 
         match result {
             Ok(Some(data)) => match serde_json::from_str::<T>(&data) {
@@ -55,6 +321,10 @@ impl CacheStore {
     where
         T: Serialize,
     {
+        if expiration.is_zero() {
+            return;
+        }
+
         let json_data = match serde_json::to_string(data) {
             Ok(json) => json,
             Err(e) => {
@@ -65,28 +335,837 @@ impl CacheStore {
 
         let conn = self.get_conn();
         if let Some(mut conn) = conn {
-            let result: redis::RedisResult<()> = redis::pipe()
-                .cmd("SET")
+            let result: RedisResult<()> = self.track(
+                redis::pipe()
+                    .cmd("SET")
+                    .arg(key)
+                    .arg(&json_data)
+                    .ignore()
+                    .cmd("EXPIRE")
+                    .arg(key)
+                    .arg(expiration.as_secs() as usize)
+                    .query(&mut conn),
+            );
+
+            match result {
+                Ok(_) => debug!("Cached key {} with TTL {:?}", key, expiration),
+                Err(e) => error!("Failed to set cache key {e}: {:?}", key),
+            }
+        }
+    }
+
+    // single-flight: saat cache miss bersamaan, hanya satu compute yang jalan per key dan
+    // hasilnya dibagi langsung di proses ini, jadi tetap satu compute walau Redis sedang mati;
+    // error tidak dibagi, pemanggil berikutnya yang menunggu akan mencoba compute sendiri
+    pub async fn get_or_compute<T, E, F, Fut>(
+        &self,
+        key: &str,
+        expiration: Duration,
+        compute: F,
+    ) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        if expiration.is_zero() {
+            return compute().await;
+        }
+
+        if let Some(cached) = self.get_from_cache::<T>(key) {
+            return Ok(cached);
+        }
+
+        let cell = {
+            let mut inflight = self.inflight.lock().unwrap_or_else(|e| e.into_inner());
+            inflight
+                .entry(key.to_string())
+                .or_insert_with(|| Arc::new(OnceCell::new()))
+                .clone()
+        };
+
+        let mut compute = Some(compute);
+        let mut computed = None;
+
+        // Err(None) berarti nilai berhasil dihitung tapi gagal diserialisasi untuk dibagi
+        let shared = cell
+            .get_or_try_init(|| async {
+                let compute = compute.take().expect("compute is only taken once");
+                let value = compute().await.map_err(Some)?;
+                self.set_to_cache(key, &value, expiration);
+
+                let json = serde_json::to_string(&value).map_err(|_| None);
+                computed = Some(value);
+                json
+            })
+            .await
+            .cloned();
+
+        {
+            let mut inflight = self.inflight.lock().unwrap_or_else(|e| e.into_inner());
+            if let Some(existing) = inflight.get(key)
+                && Arc::ptr_eq(existing, &cell)
+                && (cell.initialized() || Arc::strong_count(&cell) <= 2)
+            {
+                inflight.remove(key);
+            }
+        }
+
+        if let Some(value) = computed {
+            return Ok(value);
+        }
+
+        // sampai di sini hanya pemanggil yang tidak menjalankan compute-nya sendiri
+        match shared {
+            Ok(json) => match serde_json::from_str::<T>(&json) {
+                Ok(value) => Ok(value),
+                Err(e) => {
+                    error!("Failed to deserialize shared value for key {key}: {e:?}");
+                    let compute = compute.expect("waiter keeps its own compute");
+                    compute().await
+                }
+            },
+            Err(e) => Err(e.expect("serialization failure keeps the computed value")),
+        }
+    }
+
+    // stale-while-revalidate: dalam soft TTL nilai langsung dikembalikan, antara soft dan hard
+    // nilai lama tetap dikembalikan sambil satu task menyegarkannya di background, dan lewat
+    // hard TTL key sudah kedaluwarsa di Redis sehingga jatuh ke compute single-flight biasa
+    pub async fn get_with_swr<T, E, F, Fut>(
+        &self,
+        key: &str,
+        soft_ttl: Duration,
+        hard_ttl: Duration,
+        refresh: F,
+    ) -> Result<T, E>
+    where
+        T: Serialize + DeserializeOwned + Send + 'static,
+        E: Display + Send + 'static,
+        F: Fn() -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+    {
+        if hard_ttl.is_zero() {
+            return refresh().await;
+        }
+
+        if let Some(entry) = self.get_from_cache::<SwrEntry<T>>(key) {
+            if entry.age() >= soft_ttl.min(hard_ttl) {
+                self.spawn_refresh(key, hard_ttl, refresh);
+            }
+
+            return Ok(entry.value);
+        }
+
+        self.get_or_compute(key, hard_ttl, || async {
+            refresh().await.map(SwrEntry::fresh)
+        })
+        .await
+        .map(|entry| entry.value)
+    }
+
+    // paling banyak satu refresh background per key pada satu waktu
+    fn spawn_refresh<T, E, F, Fut>(&self, key: &str, hard_ttl: Duration, refresh: F)
+    where
+        T: Serialize + Send + 'static,
+        E: Display + Send + 'static,
+        F: Fn() -> Fut + Send + 'static,
+        Fut: Future<Output = Result<T, E>> + Send + 'static,
+    {
+        {
+            let mut refreshing = self.refreshing.lock().unwrap_or_else(|e| e.into_inner());
+            if !refreshing.insert(key.to_string()) {
+                return;
+            }
+        }
+
+        let store = self.clone();
+        let key = key.to_string();
+
+        tokio::spawn(async move {
+            match refresh().await {
+                Ok(value) => {
+                    store.set_to_cache(&key, &SwrEntry::fresh(value), hard_ttl);
+                    info!("🔄 Refreshed stale cache key {key} in background");
+                }
+                Err(e) => warn!("⚠️  Background refresh of cache key {key} failed: {e}"),
+            }
+
+            store
+                .refreshing
+                .lock()
+                .unwrap_or_else(|e| e.into_inner())
+                .remove(&key);
+        });
+    }
+
+    // counter dengan jendela tetap: TTL dipasang sekali saat counter pertama kali dibuat
+    pub fn increment(&self, key: &str, window: Duration) -> Option<i64> {
+        let mut conn = self.get_conn()?;
+
+        let result: RedisResult<(i64, bool)> = self.track(
+            redis::pipe()
+                .cmd("INCR")
                 .arg(key)
-                .arg(&json_data)
-                .ignore()
                 .cmd("EXPIRE")
                 .arg(key)
-                .arg(expiration.as_secs() as usize)
-                .query(&mut conn);
+                .arg(window.as_secs())
+                .arg("NX")
+                .query(&mut conn),
+        );
+
+        match result {
+            Ok((count, _)) => Some(count),
+            Err(e) => {
+                error!("Failed to increment key {key}: {e:?}");
+                None
+            }
+        }
+    }
+
+    // sisa detik sampai request berikutnya diizinkan, None jika token tersedia;
+    // saat Redis tidak bisa dihubungi request dibiarkan lewat daripada menolak semua write
+    pub fn throttle(&self, key: &str, limit: RateLimit) -> Option<u64> {
+        let mut conn = self.get_conn()?;
+
+        let result: RedisResult<u64> = self.track(
+            redis::Script::new(TOKEN_BUCKET_SCRIPT)
+                .key(key)
+                .arg(limit.capacity)
+                .arg(limit.period.as_millis() as u64)
+                .invoke(&mut conn),
+        );
+
+        match result {
+            Ok(0) => None,
+            Ok(wait_ms) => Some(wait_ms.div_ceil(1000)),
+            Err(e) => {
+                error!("Failed to apply rate limit on key {key}: {e:?}");
+                None
+            }
+        }
+    }
+
+    // sisa umur key dalam detik, None jika key tidak ada atau tanpa expiry
+    pub fn remaining_ttl(&self, key: &str) -> Option<u64> {
+        let mut conn = self.get_conn()?;
+
+        match self.track(redis::cmd("TTL").arg(key).query::<i64>(&mut conn)) {
+            Ok(secs) if secs > 0 => Some(secs as u64),
+            Ok(_) => None,
+            Err(e) => {
+                error!("Failed to read TTL of key {key}: {e:?}");
+                None
+            }
+        }
+    }
+
+    // menjalankan f selama memegang lock saldo milik user, dipakai di antara baca saldo dan
+    // tulis saldo supaya dua request untuk user yang sama tidak saling menimpa
+    pub async fn with_user_lock<T, F, Fut>(&self, user_id: i32, f: F) -> Result<T, AppError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        self.with_user_locks(&[user_id], f).await
+    }
+
+    // lock diambil urut berdasarkan user id supaya dua transfer berlawanan arah tidak
+    // saling menunggu sampai timeout
+    pub async fn with_user_locks<T, F, Fut>(&self, user_ids: &[i32], f: F) -> Result<T, AppError>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        let mut user_ids = user_ids.to_vec();
+        user_ids.sort_unstable();
+        user_ids.dedup();
+
+        if !self.require_user_locks {
+            return Ok(f().await);
+        }
+
+        // tanpa Redis dua mutasi saldo bisa membaca saldo yang sama lalu saling menimpa,
+        // jadi mutasi ditolak dan klien diminta mencoba lagi
+        let Some(mut conn) = self.get_async_conn().await else {
+            warn!("⚠️  Redis unavailable, refusing balance mutation for users {user_ids:?}");
+            return Err(lock_unavailable());
+        };
+
+        // koneksi sudah dipegang guard sejak awal, jadi lock yang terlanjur diambil ikut
+        // dilepas saat lock berikutnya gagal diambil
+        let mut guard = UserLockGuard {
+            store: self.clone(),
+            conn: Some(conn.clone()),
+            token: Uuid::new_v4().to_string(),
+            keys: Vec::with_capacity(user_ids.len()),
+            renewal: None,
+        };
+
+        for user_id in user_ids {
+            let key = format!("lock:user:{user_id}");
+            self.acquire_lock(&mut conn, &key, &guard.token).await?;
+            guard.keys.push(key);
+        }
+
+        let lost = Arc::new(AtomicBool::new(false));
+        guard.renewal = Some(tokio::spawn(renew_locks(
+            self.clone(),
+            conn.clone(),
+            guard.token.clone(),
+            guard.keys.clone(),
+            lost.clone(),
+        )));
+
+        let result = f().await;
+
+        // tidak ada fencing token: jika lock sempat lepas, mutasi di atas mungkin berjalan
+        // bersamaan dengan pemegang lain; transfer tetap aman karena dikunci per baris di DB,
+        // sisanya dicatat di sini supaya saldo user terkait bisa diaudit
+        if lost.load(Ordering::SeqCst) {
+            error!(
+                "❌ Balance lock {:?} expired before the mutation finished",
+                guard.keys
+            );
+        }
+
+        Ok(result)
+    }
+
+    async fn acquire_lock(
+        &self,
+        conn: &mut MultiplexedConnection,
+        key: &str,
+        token: &str,
+    ) -> Result<(), AppError> {
+        let deadline = Instant::now() + USER_LOCK_WAIT;
+
+        loop {
+            let result: RedisResult<Option<String>> = self.track(
+                redis::cmd("SET")
+                    .arg(key)
+                    .arg(token)
+                    .arg("NX")
+                    .arg("PX")
+                    .arg(USER_LOCK_TTL.as_millis() as u64)
+                    .query_async(conn)
+                    .await,
+            );
 
             match result {
-                Ok(_) => debug!("Cached key {} with TTL {:?}", key, expiration),
-                Err(e) => error!("Failed to set cache key {e}: {:?}", key),
+                Ok(Some(_)) => return Ok(()),
+                Ok(None) if Instant::now() >= deadline => {
+                    warn!("⚠️  Timed out after {USER_LOCK_WAIT:?} waiting for lock {key}");
+                    return Err(lock_unavailable());
+                }
+                Ok(None) => sleep(USER_LOCK_RETRY).await,
+                Err(e) => {
+                    error!("Failed to acquire lock {key}: {e:?}");
+                    return Err(lock_unavailable());
+                }
             }
         }
     }
 
+    // jumlah key per prefix (bagian sebelum ':' pertama); SCAN bisa mengulang key
+    // yang sama saat rehash sehingga hasilnya perkiraan
+    pub fn key_counts_by_prefix(&self) -> Result<BTreeMap<String, u64>, AppError> {
+        let mut counts = BTreeMap::new();
+
+        self.scan_keys("*", |_, keys| {
+            for key in keys {
+                let prefix = match key.split_once(':') {
+                    Some((prefix, _)) => format!("{prefix}:"),
+                    None => key,
+                };
+                *counts.entry(prefix).or_insert(0) += 1;
+            }
+            Ok(())
+        })?;
+
+        Ok(counts)
+    }
+
+    // menghapus semua key di bawah prefix, key operasional dilewati; mengembalikan jumlah terhapus
+    pub fn flush_prefix(&self, prefix: &str) -> Result<u64, AppError> {
+        if prefix.is_empty() {
+            return Err(AppError::Custom(
+                "Cache prefix must not be empty".to_string(),
+            ));
+        }
+
+        let pattern = format!("{}*", escape_glob(prefix));
+        let mut removed = 0;
+
+        self.scan_keys(&pattern, |conn, keys| {
+            let keys: Vec<String> = keys
+                .into_iter()
+                .filter(|key| {
+                    !PROTECTED_PREFIXES
+                        .iter()
+                        .any(|protected| key.starts_with(protected))
+                })
+                .collect();
+
+            if keys.is_empty() {
+                return Ok(());
+            }
+
+            // UNLINK membebaskan memori di background, tidak memblok Redis seperti DEL
+            let deleted: u64 = self.track(redis::cmd("UNLINK").arg(&keys).query(conn))?;
+            removed += deleted;
+            Ok(())
+        })?;
+
+        info!("Flushed {removed} cache key(s) under prefix {prefix}");
+
+        Ok(removed)
+    }
+
+    fn scan_keys<F>(&self, pattern: &str, mut on_batch: F) -> Result<(), AppError>
+    where
+        F: FnMut(&mut Connection, Vec<String>) -> RedisResult<()>,
+    {
+        let mut conn = self
+            .get_conn()
+            .ok_or_else(|| AppError::InternalError("Cache is unavailable".to_string()))?;
+        let mut cursor: u64 = 0;
+
+        loop {
+            let (next, keys): (u64, Vec<String>) = self
+                .track(
+                    redis::cmd("SCAN")
+                        .arg(cursor)
+                        .arg("MATCH")
+                        .arg(pattern)
+                        .arg("COUNT")
+                        .arg(SCAN_BATCH)
+                        .query(&mut conn),
+                )
+                .map_err(|e| AppError::InternalError(format!("Failed to scan cache keys: {e}")))?;
+
+            on_batch(&mut conn, keys)
+                .map_err(|e| AppError::InternalError(format!("Failed to flush cache keys: {e}")))?;
+
+            if next == 0 {
+                return Ok(());
+            }
+            cursor = next;
+        }
+    }
+
     pub fn delete_from_cache(&self, key: &str) {
         if let Some(mut conn) = self.get_conn()
-            && let Err(e) = redis::cmd("DEL").arg(key).query::<()>(&mut conn)
+            && let Err(e) = self.track(redis::cmd("DEL").arg(key).query::<()>(&mut conn))
         {
             error!("Failed to delete key {e}: {:?}", key);
         }
     }
 }
+
+fn lock_unavailable() -> AppError {
+    AppError::Custom("operation in progress, retry".to_string())
+}
+
+// memperpanjang lock sampai task di-abort oleh guard; lock yang sudah diambil pemilik lain
+// atau gagal diperpanjang ditandai hilang
+async fn renew_locks(
+    store: CacheStore,
+    mut conn: MultiplexedConnection,
+    token: String,
+    keys: Vec<String>,
+    lost: Arc<AtomicBool>,
+) {
+    loop {
+        sleep(USER_LOCK_TTL / 3).await;
+
+        for key in &keys {
+            let result: RedisResult<i64> = store.track(
+                redis::Script::new(RENEW_SCRIPT)
+                    .key(key)
+                    .arg(&token)
+                    .arg(USER_LOCK_TTL.as_millis() as u64)
+                    .invoke_async(&mut conn)
+                    .await,
+            );
+
+            match result {
+                Ok(1) => {}
+                Ok(_) => {
+                    error!("❌ Lock {key} was lost before it could be renewed");
+                    lost.store(true, Ordering::SeqCst);
+                }
+                Err(e) => {
+                    error!("Failed to renew lock {key}: {e:?}");
+                    lost.store(true, Ordering::SeqCst);
+                }
+            }
+        }
+    }
+}
+
+// prefix dari operator dicocokkan apa adanya, bukan sebagai pola glob
+fn escape_glob(value: &str) -> String {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        if matches!(c, '*' | '?' | '[' | ']' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicI32, AtomicU32, Ordering};
+
+    // port 1 selalu menolak koneksi, jadi store berjalan seperti saat Redis mati
+    fn store_without_redis() -> CacheStore {
+        CacheStore::new(
+            redis::Client::open("redis://127.0.0.1:1/").unwrap(),
+            CacheTtlConfig::default(),
+        )
+    }
+
+    async fn load(calls: &AtomicU32, fail: bool) -> Result<Vec<i32>, AppError> {
+        calls.fetch_add(1, Ordering::SeqCst);
+        sleep(Duration::from_millis(50)).await;
+
+        if fail {
+            return Err(AppError::InternalError("db down".to_string()));
+        }
+        Ok(vec![1, 2, 3])
+    }
+
+    #[tokio::test]
+    async fn concurrent_misses_share_one_compute_without_redis() {
+        let store = store_without_redis();
+        let calls = AtomicU32::new(0);
+        let ttl = Duration::from_secs(60);
+
+        let (a, b, c, d) = tokio::join!(
+            store.get_or_compute("users:1", ttl, || load(&calls, false)),
+            store.get_or_compute("users:1", ttl, || load(&calls, false)),
+            store.get_or_compute("users:1", ttl, || load(&calls, false)),
+            store.get_or_compute("users:1", ttl, || load(&calls, false)),
+        );
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        for result in [a, b, c, d] {
+            assert_eq!(result.unwrap(), vec![1, 2, 3]);
+        }
+        assert!(store.inflight.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn failed_compute_is_not_shared_with_waiters() {
+        let store = store_without_redis();
+        let calls = AtomicU32::new(0);
+        let ttl = Duration::from_secs(60);
+
+        let (failed, waiter) = tokio::join!(
+            store.get_or_compute("users:2", ttl, || load(&calls, true)),
+            store.get_or_compute("users:2", ttl, || load(&calls, false)),
+        );
+
+        assert!(failed.is_err());
+        assert_eq!(waiter.unwrap(), vec![1, 2, 3]);
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(store.inflight.lock().unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn user_lock_fails_closed_without_redis() {
+        let store = store_without_redis();
+        let ran = AtomicBool::new(false);
+
+        let result = store
+            .with_user_locks(&[1, 2], || async { ran.store(true, Ordering::SeqCst) })
+            .await;
+
+        let err = result.unwrap_err();
+        assert_eq!(err.to_string(), lock_unavailable().to_string());
+        assert!(!ran.load(Ordering::SeqCst));
+    }
+
+    // butuh Redis sungguhan, misalnya: TEST_REDIS_URL=redis://127.0.0.1:6379/ cargo test -- --ignored
+    #[tokio::test]
+    #[ignore = "needs a Redis server at TEST_REDIS_URL"]
+    async fn user_lock_serializes_read_then_write() {
+        let url = std::env::var("TEST_REDIS_URL")
+            .unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
+        let store = CacheStore::new(redis::Client::open(url).unwrap(), CacheTtlConfig::default());
+        let balance = AtomicI32::new(100_000);
+
+        // baca saldo, tunggu, lalu tulis: tanpa lock kedua withdraw membaca 100_000 yang sama
+        let (store, balance) = (&store, &balance);
+        let withdraw = |amount: i32| {
+            store.with_user_lock(7, move || async move {
+                let current = balance.load(Ordering::SeqCst);
+                sleep(Duration::from_millis(50)).await;
+
+                if current < amount {
+                    return false;
+                }
+                balance.store(current - amount, Ordering::SeqCst);
+                true
+            })
+        };
+
+        let (first, second) = tokio::join!(withdraw(60_000), withdraw(60_000));
+
+        assert!(first.unwrap() ^ second.unwrap());
+        assert_eq!(balance.load(Ordering::SeqCst), 40_000);
+    }
+
+    // butuh Redis sungguhan, misalnya: TEST_REDIS_URL=redis://127.0.0.1:6379/ cargo test -- --ignored
+    #[tokio::test]
+    #[ignore = "needs a Redis server at TEST_REDIS_URL"]
+    async fn partially_acquired_locks_are_released() {
+        let url = std::env::var("TEST_REDIS_URL")
+            .unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
+        let store = CacheStore::new(redis::Client::open(url).unwrap(), CacheTtlConfig::default());
+        let mut conn = store.redis.get_connection().unwrap();
+        let _: () = redis::cmd("SET")
+            .arg("lock:user:9002")
+            .arg("other-holder")
+            .arg("PX")
+            .arg(60_000)
+            .query(&mut conn)
+            .unwrap();
+
+        // lock 9001 terambil lebih dulu, lalu lock 9002 milik pemegang lain tidak pernah lepas
+        let err = store
+            .with_user_locks(&[9001, 9002], || async {})
+            .await
+            .unwrap_err();
+        assert_eq!(err.to_string(), lock_unavailable().to_string());
+
+        sleep(Duration::from_millis(100)).await;
+        let held: Option<String> = redis::cmd("GET")
+            .arg("lock:user:9001")
+            .query(&mut conn)
+            .unwrap();
+        assert_eq!(held, None);
+
+        let started = Instant::now();
+        store.with_user_lock(9001, || async {}).await.unwrap();
+        assert!(started.elapsed() < USER_LOCK_RETRY * 4);
+
+        store.delete_from_cache("lock:user:9002");
+    }
+
+    // butuh Redis sungguhan, misalnya: TEST_REDIS_URL=redis://127.0.0.1:6379/ cargo test -- --ignored
+    #[tokio::test]
+    #[ignore = "needs a Redis server at TEST_REDIS_URL"]
+    async fn zero_ttl_always_hits_the_repository() {
+        let url = std::env::var("TEST_REDIS_URL")
+            .unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
+        let store = CacheStore::new(redis::Client::open(url).unwrap(), CacheTtlConfig::default());
+        store.delete_from_cache("ttl:cached");
+        store.delete_from_cache("ttl:disabled");
+        let (cached, disabled) = (AtomicU32::new(0), AtomicU32::new(0));
+
+        for _ in 0..3 {
+            store
+                .get_or_compute("ttl:cached", Duration::from_secs(60), || {
+                    load(&cached, false)
+                })
+                .await
+                .unwrap();
+            store
+                .get_or_compute("ttl:disabled", Duration::ZERO, || load(&disabled, false))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(cached.load(Ordering::SeqCst), 1);
+        assert_eq!(disabled.load(Ordering::SeqCst), 3);
+        assert!(store.get_from_cache::<Vec<i32>>("ttl:disabled").is_none());
+        assert!(!store.for_entity(Duration::ZERO).is_enabled());
+    }
+
+    #[tokio::test]
+    async fn dead_redis_still_serves_repository_data() {
+        let store = store_without_redis();
+        let calls = AtomicU32::new(0);
+        let ttl = Duration::from_secs(60);
+
+        store.set_to_cache("users:3", &vec![9], ttl);
+        assert!(store.is_degraded());
+
+        for _ in 0..2 {
+            let users = store
+                .get_or_compute("users:3", ttl, || load(&calls, false))
+                .await
+                .unwrap();
+            assert_eq!(users, vec![1, 2, 3]);
+        }
+
+        // tanpa cache setiap panggilan kembali ke repository
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+        assert!(store.get_from_cache::<Vec<i32>>("users:3").is_none());
+    }
+
+    // butuh Redis sungguhan, misalnya: TEST_REDIS_URL=redis://127.0.0.1:6379/ cargo test -- --ignored
+    #[tokio::test]
+    #[ignore = "needs a Redis server at TEST_REDIS_URL"]
+    async fn reachable_redis_keeps_the_cache_enabled() {
+        let url = std::env::var("TEST_REDIS_URL")
+            .unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
+        let store = CacheStore::new(redis::Client::open(url).unwrap(), CacheTtlConfig::default());
+        store.delete_from_cache("users:4");
+        let calls = AtomicU32::new(0);
+
+        for _ in 0..2 {
+            store
+                .get_or_compute("users:4", Duration::from_secs(60), || load(&calls, false))
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        assert_eq!(
+            store.get_from_cache::<Vec<i32>>("users:4"),
+            Some(vec![1, 2, 3])
+        );
+        assert!(!store.is_degraded());
+    }
+
+    // butuh Redis sungguhan, misalnya: TEST_REDIS_URL=redis://127.0.0.1:6379/ cargo test -- --ignored
+    #[tokio::test]
+    #[ignore = "needs a Redis server at TEST_REDIS_URL"]
+    async fn stale_value_is_served_while_a_refresh_runs() {
+        let url = std::env::var("TEST_REDIS_URL")
+            .unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
+        let store = CacheStore::new(redis::Client::open(url).unwrap(), CacheTtlConfig::default());
+        let (soft, hard) = (Duration::from_secs(10), Duration::from_secs(60));
+        let calls = Arc::new(AtomicU32::new(0));
+        let refresh = {
+            let calls = calls.clone();
+            move || {
+                let calls = calls.clone();
+                async move { load(&calls, false).await }
+            }
+        };
+
+        // masih dalam soft TTL: tidak ada refresh
+        store.set_to_cache("swr:fresh", &SwrEntry::fresh(vec![7]), hard);
+        let fresh = store
+            .get_with_swr("swr:fresh", soft, hard, refresh.clone())
+            .await
+            .unwrap();
+        assert_eq!(fresh, vec![7]);
+
+        // antara soft dan hard: nilai lama langsung kembali, refresh berjalan di background
+        let stale = SwrEntry {
+            refreshed_at: Utc::now().timestamp() - 30,
+            value: vec![9],
+        };
+        store.set_to_cache("swr:stale", &stale, hard);
+        let started = Instant::now();
+        let served = store
+            .get_with_swr("swr:stale", soft, hard, refresh)
+            .await
+            .unwrap();
+        assert_eq!(served, vec![9]);
+        assert!(started.elapsed() < Duration::from_millis(50));
+
+        sleep(Duration::from_millis(200)).await;
+        assert_eq!(calls.load(Ordering::SeqCst), 1);
+        let refreshed = store
+            .get_from_cache::<SwrEntry<Vec<i32>>>("swr:stale")
+            .unwrap();
+        assert_eq!(refreshed.value, vec![1, 2, 3]);
+        assert!(refreshed.age() < soft);
+    }
+
+    #[tokio::test]
+    async fn swr_without_redis_falls_back_to_the_loader() {
+        let store = store_without_redis();
+        let calls = Arc::new(AtomicU32::new(0));
+        let refresh = {
+            let calls = calls.clone();
+            move || {
+                let calls = calls.clone();
+                async move { load(&calls, false).await }
+            }
+        };
+
+        for _ in 0..2 {
+            let saldos = store
+                .get_with_swr(
+                    "swr:offline",
+                    Duration::from_secs(10),
+                    Duration::from_secs(60),
+                    refresh.clone(),
+                )
+                .await
+                .unwrap();
+            assert_eq!(saldos, vec![1, 2, 3]);
+        }
+
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    #[test]
+    fn flushing_needs_a_prefix_and_a_reachable_redis() {
+        let store = store_without_redis();
+
+        assert!(matches!(store.flush_prefix(""), Err(AppError::Custom(_))));
+        assert!(matches!(
+            store.flush_prefix("saldos:"),
+            Err(AppError::InternalError(_))
+        ));
+        assert!(matches!(
+            store.key_counts_by_prefix(),
+            Err(AppError::InternalError(_))
+        ));
+    }
+
+    #[test]
+    fn prefix_is_matched_literally() {
+        assert_eq!(escape_glob("saldos:"), "saldos:");
+        assert_eq!(escape_glob("a*b?[c]\\"), "a\\*b\\?\\[c\\]\\\\");
+    }
+
+    // butuh Redis sungguhan, misalnya: TEST_REDIS_URL=redis://127.0.0.1:6379/ cargo test -- --ignored
+    #[tokio::test]
+    #[ignore = "needs a Redis server at TEST_REDIS_URL"]
+    async fn prefix_flush_removes_only_the_targeted_keys() {
+        let url = std::env::var("TEST_REDIS_URL")
+            .unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
+        let store = CacheStore::new(redis::Client::open(url).unwrap(), CacheTtlConfig::default());
+        let ttl = Duration::from_secs(60);
+        store.flush_prefix("flushtest_a:").unwrap();
+        store.flush_prefix("flushtest_b:").unwrap();
+
+        for id in 0..3 {
+            store.set_to_cache(&format!("flushtest_a:{id}"), &id, ttl);
+        }
+        for id in 0..2 {
+            store.set_to_cache(&format!("flushtest_b:{id}"), &id, ttl);
+        }
+        store.set_to_cache("lock:flushtest_a", &1, ttl);
+
+        let counts = store.key_counts_by_prefix().unwrap();
+        assert_eq!(counts.get("flushtest_a:"), Some(&3));
+        assert_eq!(counts.get("flushtest_b:"), Some(&2));
+
+        assert_eq!(store.flush_prefix("flushtest_a:").unwrap(), 3);
+
+        let counts = store.key_counts_by_prefix().unwrap();
+        assert_eq!(counts.get("flushtest_a:"), None);
+        assert_eq!(counts.get("flushtest_b:"), Some(&2));
+        assert_eq!(store.get_from_cache::<i32>("flushtest_b:1"), Some(1));
+
+        // key operasional tidak ikut terhapus walau prefix-nya diminta langsung
+        assert_eq!(store.flush_prefix("lock:flushtest_a").unwrap(), 0);
+        assert_eq!(store.get_from_cache::<i32>("lock:flushtest_a"), Some(1));
+
+        store.delete_from_cache("lock:flushtest_a");
+        assert_eq!(store.flush_prefix("flushtest_b:").unwrap(), 2);
+    }
+}
@@ -1,2 +1,3 @@
+mod breaker;
 mod cache_helpers;
 pub use cache_helpers::CacheStore;
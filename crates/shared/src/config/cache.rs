@@ -0,0 +1,58 @@
+use anyhow::Result;
+use std::time::Duration;
+
+use crate::config::myconfig::env_or;
+
+// TTL 0 berarti cache untuk entity tersebut dimatikan
+#[derive(Debug, Clone, Copy)]
+pub struct CacheTtlConfig {
+    pub auth: Duration,
+    pub user: Duration,
+    pub saldo: Duration,
+    pub topup: Duration,
+    pub transfer: Duration,
+    pub withdraw: Duration,
+    pub stats: Duration,
+    // ringkasan akun dihapus pada setiap penulisan, TTL hanya penahan beban singkat
+    pub account_summary: Duration,
+    // daftar saldo yang lebih tua dari ini tetap dilayani sambil disegarkan di background
+    pub saldo_soft: Duration,
+}
+
+impl Default for CacheTtlConfig {
+    fn default() -> Self {
+        Self {
+            auth: Duration::from_secs(60),
+            user: Duration::from_secs(60 * 5),
+            saldo: Duration::from_secs(60 * 5),
+            topup: Duration::from_secs(60 * 5),
+            transfer: Duration::from_secs(60 * 5),
+            withdraw: Duration::from_secs(60 * 5),
+            stats: Duration::from_secs(30),
+            account_summary: Duration::from_secs(30),
+            saldo_soft: Duration::from_secs(60),
+        }
+    }
+}
+
+impl CacheTtlConfig {
+    pub fn from_env() -> Result<Self> {
+        let defaults = Self::default();
+
+        Ok(Self {
+            auth: ttl_from_env("CACHE_TTL_AUTH", defaults.auth)?,
+            user: ttl_from_env("CACHE_TTL_USER", defaults.user)?,
+            saldo: ttl_from_env("CACHE_TTL_SALDO", defaults.saldo)?,
+            topup: ttl_from_env("CACHE_TTL_TOPUP", defaults.topup)?,
+            transfer: ttl_from_env("CACHE_TTL_TRANSFER", defaults.transfer)?,
+            withdraw: ttl_from_env("CACHE_TTL_WITHDRAW", defaults.withdraw)?,
+            stats: ttl_from_env("CACHE_TTL_STATS", defaults.stats)?,
+            account_summary: ttl_from_env("CACHE_TTL_ACCOUNT_SUMMARY", defaults.account_summary)?,
+            saldo_soft: ttl_from_env("CACHE_SOFT_TTL_SALDO", defaults.saldo_soft)?,
+        })
+    }
+}
+
+fn ttl_from_env(key: &str, default: Duration) -> Result<Duration> {
+    env_or(key, default.as_secs()).map(Duration::from_secs)
+}
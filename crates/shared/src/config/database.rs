@@ -1,27 +1,311 @@
-use sqlx::{Pool, Postgres, postgres::PgPoolOptions};
+use serde::Serialize;
+use sqlx::{
+    Pool, Postgres,
+    migrate::{Migrate, Migrator},
+    postgres::PgPoolOptions,
+};
+use std::{future::Future, time::Duration};
+use tracing::{info, warn};
 
 pub type ConnectionPool = Pool<Postgres>;
 
+tokio::task_local! {
+    static REPLICA_READS: bool;
+}
+
+// hanya RPC baca-saja yang dijalankan di dalam scope ini; di luar scope (write, worker,
+// pengecekan sebelum update) semua query tetap ke primary supaya tidak membaca data basi
+pub async fn with_replica_reads<F: Future>(future: F) -> F::Output {
+    REPLICA_READS.scope(true, future).await
+}
+
+fn replica_reads_allowed() -> bool {
+    REPLICA_READS.try_with(|allowed| *allowed).unwrap_or(false)
+}
+
+// pool untuk query find_*; tanpa DATABASE_READ_URL sama dengan primary
+#[derive(Debug, Clone)]
+pub struct ReadPool {
+    primary: ConnectionPool,
+    replica: Option<ConnectionPool>,
+}
+
+impl ReadPool {
+    pub fn new(primary: ConnectionPool, replica: Option<ConnectionPool>) -> Self {
+        Self { primary, replica }
+    }
+
+    pub fn primary(primary: ConnectionPool) -> Self {
+        Self::new(primary, None)
+    }
+
+    pub fn has_replica(&self) -> bool {
+        self.replica.is_some()
+    }
+
+    pub fn get(&self) -> &ConnectionPool {
+        match &self.replica {
+            Some(replica) if replica_reads_allowed() => replica,
+            _ => &self.primary,
+        }
+    }
+}
+
+// pool primary untuk write dan pool baca untuk find_*, dibawa bersama ke AppState
+#[derive(Debug, Clone)]
+pub struct DatabasePools {
+    pub primary: ConnectionPool,
+    pub read: ReadPool,
+}
+
+impl DatabasePools {
+    pub fn new(primary: ConnectionPool, replica: Option<ConnectionPool>) -> Self {
+        Self {
+            read: ReadPool::new(primary.clone(), replica),
+            primary,
+        }
+    }
+}
+
+static MIGRATOR: Migrator = sqlx::migrate!("../../migrations");
+
+// versi migrasi yang sudah jalan, yang belum, dan yang gagal di tengah jalan (dirty)
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct MigrationStatus {
+    pub applied: Vec<i64>,
+    pub pending: Vec<i64>,
+    pub failed: Option<i64>,
+}
+
+impl MigrationStatus {
+    pub fn is_up_to_date(&self) -> bool {
+        self.pending.is_empty() && self.failed.is_none()
+    }
+
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} applied, {} pending",
+            self.applied.len(),
+            self.pending.len()
+        );
+        if !self.pending.is_empty() {
+            summary.push_str(&format!(" {:?}", self.pending));
+        }
+        if let Some(version) = self.failed {
+            summary.push_str(&format!(", migration {version} failed partway"));
+        }
+        summary
+    }
+}
+
+// hanya membaca _sqlx_migrations, tabel yang belum ada berarti database masih kosong
+pub async fn migration_status(pool: &ConnectionPool) -> anyhow::Result<MigrationStatus> {
+    let mut conn = pool.acquire().await?;
+
+    let has_table: bool = sqlx::query_scalar("SELECT to_regclass('_sqlx_migrations') IS NOT NULL")
+        .fetch_one(&mut *conn)
+        .await?;
+
+    let (recorded, failed) = if has_table {
+        (
+            conn.list_applied_migrations().await?,
+            conn.dirty_version().await?,
+        )
+    } else {
+        (Vec::new(), None)
+    };
+
+    let applied: Vec<i64> = recorded
+        .iter()
+        .map(|migration| migration.version)
+        .filter(|version| Some(*version) != failed)
+        .collect();
+
+    let pending = MIGRATOR
+        .iter()
+        .filter(|migration| migration.migration_type.is_up_migration())
+        .map(|migration| migration.version)
+        .filter(|version| !applied.contains(version) && Some(*version) != failed)
+        .collect();
+
+    Ok(MigrationStatus {
+        applied,
+        pending,
+        failed,
+    })
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct PoolConfig {
+    pub max_connections: u32,
+    pub min_connections: u32,
+    pub acquire_timeout_secs: u64,
+    pub idle_timeout_secs: u64,
+}
+
+impl Default for PoolConfig {
+    fn default() -> Self {
+        Self {
+            max_connections: 5,
+            min_connections: 1,
+            acquire_timeout_secs: 30,
+            idle_timeout_secs: 600,
+        }
+    }
+}
+
 pub struct ConnectionManager;
 
 impl ConnectionManager {
     pub async fn new_pool(
         connection_string: &str,
         run_migrations: bool,
+        pool_config: PoolConfig,
     ) -> anyhow::Result<ConnectionPool> {
+        info!(
+            "🗄️ Database pool settings: max_connections={}, min_connections={}, acquire_timeout={}s, idle_timeout={}s",
+            pool_config.max_connections,
+            pool_config.min_connections,
+            pool_config.acquire_timeout_secs,
+            pool_config.idle_timeout_secs
+        );
+
         let pool = PgPoolOptions::new()
-            .max_connections(5)
+            .max_connections(pool_config.max_connections)
+            .min_connections(pool_config.min_connections)
+            .acquire_timeout(Duration::from_secs(pool_config.acquire_timeout_secs))
+            .idle_timeout(Duration::from_secs(pool_config.idle_timeout_secs))
             .connect(connection_string)
             .await
             .map_err(|err| anyhow::anyhow!("Failed to create database connection pool: {}", err))?;
 
         if run_migrations {
-            sqlx::migrate!("../../migrations")
+            MIGRATOR
                 .run(&pool)
                 .await
                 .map_err(|err| anyhow::anyhow!("Failed to run database migrations: {}", err))?;
         }
 
+        match migration_status(&pool).await {
+            Ok(status) if status.is_up_to_date() => {
+                info!("🗄️ Database migrations: {}", status.summary());
+            }
+            Ok(status) => warn!("⚠️  Database migrations: {}", status.summary()),
+            Err(err) => warn!("⚠️  Failed to read database migration status: {err}"),
+        }
+
         Ok(pool)
     }
+
+    // koneksi replika dipaksa read-only supaya write yang salah rute langsung gagal
+    pub async fn new_read_pool(
+        connection_string: &str,
+        pool_config: PoolConfig,
+    ) -> anyhow::Result<ConnectionPool> {
+        info!(
+            "🗄️ Read replica pool settings: max_connections={}, min_connections={}",
+            pool_config.max_connections, pool_config.min_connections
+        );
+
+        PgPoolOptions::new()
+            .max_connections(pool_config.max_connections)
+            .min_connections(pool_config.min_connections)
+            .acquire_timeout(Duration::from_secs(pool_config.acquire_timeout_secs))
+            .idle_timeout(Duration::from_secs(pool_config.idle_timeout_secs))
+            .after_connect(|conn, _meta| {
+                Box::pin(async move {
+                    sqlx::query("SET SESSION CHARACTERISTICS AS TRANSACTION READ ONLY")
+                        .execute(conn)
+                        .await
+                        .map(|_| ())
+                })
+            })
+            .connect(connection_string)
+            .await
+            .map_err(|err| {
+                anyhow::anyhow!("Failed to create read replica connection pool: {}", err)
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn second_acquire_waits_for_the_only_connection() {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let pool = ConnectionManager::new_pool(
+            &url,
+            false,
+            PoolConfig {
+                max_connections: 1,
+                min_connections: 0,
+                acquire_timeout_secs: 1,
+                ..PoolConfig::default()
+            },
+        )
+        .await
+        .unwrap();
+
+        let held = pool.acquire().await.unwrap();
+
+        // pool penuh: acquire kedua menunggu lalu timeout, bukan membuka koneksi baru
+        let err = pool.acquire().await.unwrap_err();
+        assert!(matches!(err, sqlx::Error::PoolTimedOut));
+        assert_eq!(pool.size(), 1);
+
+        let waiter = tokio::spawn({
+            let pool = pool.clone();
+            async move { pool.acquire().await.map(|_| ()) }
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+        drop(held);
+
+        waiter.await.unwrap().unwrap();
+        assert_eq!(pool.size(), 1);
+    }
+
+    // database baru yang dibuat dari TEST_DATABASE_URL, jadi user-nya butuh hak CREATEDB
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn fresh_database_reports_pending_migrations_until_they_run() {
+        let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+        let admin = PgPoolOptions::new().connect(&url).await.unwrap();
+        let name = format!("migration_status_{}", std::process::id());
+        sqlx::query(&format!("DROP DATABASE IF EXISTS {name}"))
+            .execute(&admin)
+            .await
+            .unwrap();
+        sqlx::query(&format!("CREATE DATABASE {name}"))
+            .execute(&admin)
+            .await
+            .unwrap();
+        let (server, _) = url.rsplit_once('/').unwrap();
+        let fresh_url = format!("{server}/{name}");
+
+        let fresh = ConnectionManager::new_pool(&fresh_url, false, PoolConfig::default())
+            .await
+            .unwrap();
+        let before = migration_status(&fresh).await.unwrap();
+        assert!(before.applied.is_empty());
+        assert!(!before.pending.is_empty());
+        assert!(!before.is_up_to_date());
+        fresh.close().await;
+
+        let migrated = ConnectionManager::new_pool(&fresh_url, true, PoolConfig::default())
+            .await
+            .unwrap();
+        let after = migration_status(&migrated).await.unwrap();
+        assert_eq!(after.applied, before.pending);
+        assert!(after.pending.is_empty());
+        assert!(after.is_up_to_date());
+        migrated.close().await;
+
+        sqlx::query(&format!("DROP DATABASE {name} WITH (FORCE)"))
+            .execute(&admin)
+            .await
+            .unwrap();
+    }
 }
@@ -1,27 +1,99 @@
+use anyhow::anyhow;
+use argon2::{
+    Argon2, PasswordHash, PasswordHasher, PasswordVerifier,
+    password_hash::{SaltString, rand_core::OsRng},
+};
 use async_trait::async_trait;
 use bcrypt::{BcryptError, hash, verify};
+use std::str::FromStr;
 
 use crate::{abstract_trait::HashingTrait, utils::AppError};
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum HashAlgorithm {
+    #[default]
+    Bcrypt,
+    Argon2,
+}
+
+impl HashAlgorithm {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            HashAlgorithm::Bcrypt => "bcrypt",
+            HashAlgorithm::Argon2 => "argon2",
+        }
+    }
+
+    // algoritma hash lama dikenali dari prefix PHC/modular crypt yang tersimpan
+    pub fn detect(hashed_password: &str) -> Option<Self> {
+        if hashed_password.starts_with("$argon2") {
+            Some(HashAlgorithm::Argon2)
+        } else if ["$2a$", "$2b$", "$2x$", "$2y$"]
+            .iter()
+            .any(|prefix| hashed_password.starts_with(prefix))
+        {
+            Some(HashAlgorithm::Bcrypt)
+        } else {
+            None
+        }
+    }
+}
+
+impl FromStr for HashAlgorithm {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> anyhow::Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "bcrypt" => Ok(HashAlgorithm::Bcrypt),
+            "argon2" | "argon2id" => Ok(HashAlgorithm::Argon2),
+            other => Err(anyhow!(
+                "HASH_ALGO must be 'bcrypt' or 'argon2', got '{other}'"
+            )),
+        }
+    }
+}
+
 #[derive(Clone)]
-pub struct Hashing;
+pub struct Hashing {
+    algorithm: HashAlgorithm,
+}
 
 impl Hashing {
-    pub fn new() -> Self {
-        Hashing
+    pub fn new(algorithm: HashAlgorithm) -> Self {
+        Hashing { algorithm }
     }
 }
 
 impl Default for Hashing {
     fn default() -> Self {
-        Self::new()
+        Self::new(HashAlgorithm::default())
     }
 }
 
+// kegagalan argon2 dibungkus ke tipe error yang sama dengan bcrypt supaya pemanggil tidak berubah
+fn argon2_error(err: impl std::fmt::Display) -> BcryptError {
+    BcryptError::from(std::io::Error::other(format!("Argon2 error: {err}")))
+}
+
+fn mismatch() -> AppError {
+    AppError::HashingError(BcryptError::from(std::io::Error::other(
+        "Passwords do not match.",
+    )))
+}
+
 #[async_trait]
 impl HashingTrait for Hashing {
     async fn hash_password(&self, password: &str) -> Result<String, BcryptError> {
-        hash(password, 4)
+        match self.algorithm {
+            HashAlgorithm::Bcrypt => hash(password, 4),
+            HashAlgorithm::Argon2 => {
+                let salt = SaltString::generate(&mut OsRng);
+                Argon2::default()
+                    .hash_password(password.as_bytes(), &salt)
+                    .map(|hashed| hashed.to_string())
+                    .map_err(argon2_error)
+            }
+        }
     }
 
     async fn compare_password(
@@ -29,12 +101,73 @@ impl HashingTrait for Hashing {
         hashed_password: &str,
         password: &str,
     ) -> Result<(), AppError> {
-        match verify(password, hashed_password) {
-            Ok(true) => Ok(()),
-            Ok(false) => Err(AppError::HashingError(BcryptError::from(
-                std::io::Error::other("Passwords do not match."),
-            ))),
-            Err(e) => Err(AppError::BcryptError(e.to_string())),
+        match HashAlgorithm::detect(hashed_password) {
+            Some(HashAlgorithm::Argon2) => {
+                let parsed = PasswordHash::new(hashed_password)
+                    .map_err(|e| AppError::BcryptError(argon2_error(e).to_string()))?;
+
+                Argon2::default()
+                    .verify_password(password.as_bytes(), &parsed)
+                    .map_err(|_| mismatch())
+            }
+            // hash tanpa prefix yang dikenal tetap diserahkan ke bcrypt seperti sebelumnya
+            Some(HashAlgorithm::Bcrypt) | None => match verify(password, hashed_password) {
+                Ok(true) => Ok(()),
+                Ok(false) => Err(mismatch()),
+                Err(e) => Err(AppError::BcryptError(e.to_string())),
+            },
         }
     }
+
+    fn needs_rehash(&self, hashed_password: &str) -> bool {
+        HashAlgorithm::detect(hashed_password) != Some(self.algorithm)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    async fn assert_verifies(algorithm: HashAlgorithm) {
+        let hashing = Hashing::new(algorithm);
+        let hashed = hashing.hash_password("password123").await.unwrap();
+
+        assert_eq!(HashAlgorithm::detect(&hashed), Some(algorithm));
+        assert!(
+            hashing
+                .compare_password(&hashed, "password123")
+                .await
+                .is_ok()
+        );
+        assert!(
+            hashing
+                .compare_password(&hashed, "password124")
+                .await
+                .is_err()
+        );
+        assert!(!hashing.needs_rehash(&hashed));
+    }
+
+    #[tokio::test]
+    async fn bcrypt_hash_verifies() {
+        assert_verifies(HashAlgorithm::Bcrypt).await;
+    }
+
+    #[tokio::test]
+    async fn argon2_hash_verifies() {
+        assert_verifies(HashAlgorithm::Argon2).await;
+    }
+
+    #[tokio::test]
+    async fn old_bcrypt_hash_still_verifies_after_switching_to_argon2() {
+        let old = Hashing::new(HashAlgorithm::Bcrypt)
+            .hash_password("password123")
+            .await
+            .unwrap();
+        let hashing = Hashing::new(HashAlgorithm::Argon2);
+
+        assert!(hashing.compare_password(&old, "password123").await.is_ok());
+        assert!(hashing.compare_password(&old, "wrong-pass").await.is_err());
+        assert!(hashing.needs_rehash(&old));
+    }
 }
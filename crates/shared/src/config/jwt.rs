@@ -12,25 +12,75 @@ pub struct Claims {
     pub user_id: i64,
     pub exp: usize,
     pub iat: usize,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub iss: Option<String>,
+
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub aud: Option<String>,
 }
 
 impl Claims {
     pub fn new(user_id: i64, exp: usize, iat: usize) -> Self {
-        Claims { user_id, exp, iat }
+        Claims {
+            user_id,
+            exp,
+            iat,
+            iss: None,
+            aud: None,
+        }
     }
 }
 
+// issuer/audience yang diset ikut ditulis saat mint dan wajib cocok saat verifikasi,
+// supaya token dari layanan lain dengan secret yang sama tidak diterima
 #[derive(Debug, Clone)]
 pub struct JwtConfig {
     pub jwt_secret: String,
+    pub issuer: Option<String>,
+    pub audience: Option<String>,
 }
 
 impl JwtConfig {
     pub fn new(jwt_secret: &str) -> Self {
         JwtConfig {
             jwt_secret: jwt_secret.to_string(),
+            issuer: None,
+            audience: None,
         }
     }
+
+    pub fn with_issuer(mut self, issuer: Option<String>) -> Self {
+        self.issuer = issuer;
+        self
+    }
+
+    pub fn with_audience(mut self, audience: Option<String>) -> Self {
+        self.audience = audience;
+        self
+    }
+
+    // tanpa audience yang dikonfigurasi klaim aud tidak diperiksa, seperti sebelumnya
+    fn validation(&self) -> Validation {
+        let mut validation = Validation::default();
+        let mut required = vec!["exp"];
+
+        if let Some(issuer) = &self.issuer {
+            validation.set_issuer(&[issuer]);
+            required.push("iss");
+        }
+
+        match &self.audience {
+            Some(audience) => {
+                validation.set_audience(&[audience]);
+                required.push("aud");
+            }
+            None => validation.validate_aud = false,
+        }
+
+        validation.set_required_spec_claims(&required);
+        validation
+    }
 }
 
 #[async_trait]
@@ -40,7 +90,11 @@ impl JwtServiceTrait for JwtConfig {
         let iat = now.timestamp() as usize;
         let exp = (now + Duration::minutes(60)).timestamp() as usize;
 
-        let claims = Claims::new(user_id, exp, iat);
+        let claims = Claims {
+            iss: self.issuer.clone(),
+            aud: self.audience.clone(),
+            ..Claims::new(user_id, exp, iat)
+        };
 
         match encode(
             &Header::default(),
@@ -53,14 +107,18 @@ impl JwtServiceTrait for JwtConfig {
     }
 
     fn verify_token(&self, token: &str) -> Result<i64, AppError> {
+        self.verify_claims(token).map(|claims| claims.user_id)
+    }
+
+    fn verify_claims(&self, token: &str) -> Result<Claims, AppError> {
         let decoding_key = DecodingKey::from_secret(self.jwt_secret.as_ref());
 
-        match decode::<Claims>(token, &decoding_key, &Validation::default()) {
+        match decode::<Claims>(token, &decoding_key, &self.validation()) {
             Ok(token_data) => {
                 let current_time = Utc::now().timestamp() as usize;
 
                 if token_data.claims.exp >= current_time {
-                    Ok(token_data.claims.user_id)
+                    Ok(token_data.claims)
                 } else {
                     Err(AppError::TokenExpiredError)
                 }
@@ -0,0 +1,46 @@
+use anyhow::{Result, anyhow};
+use std::time::Duration;
+
+use crate::config::myconfig::env_or;
+
+// ping HTTP/2 di channel gRPC gateway <-> server supaya koneksi idle tidak diputus
+// diam-diam oleh load balancer; interval harus di bawah idle timeout perantara
+#[derive(Debug, Clone, Copy)]
+pub struct GrpcKeepaliveConfig {
+    pub interval: Duration,
+    // koneksi dianggap mati jika ping tidak dibalas dalam waktu ini
+    pub timeout: Duration,
+    // tetap kirim ping walau tidak ada request yang berjalan (hanya berlaku di client)
+    pub while_idle: bool,
+}
+
+impl Default for GrpcKeepaliveConfig {
+    fn default() -> Self {
+        Self {
+            interval: Duration::from_secs(30),
+            timeout: Duration::from_secs(10),
+            while_idle: true,
+        }
+    }
+}
+
+impl GrpcKeepaliveConfig {
+    pub fn from_env() -> Result<Self> {
+        let defaults = Self::default();
+
+        let interval = env_or("GRPC_KEEPALIVE_INTERVAL_SECS", defaults.interval.as_secs())?;
+        let timeout = env_or("GRPC_KEEPALIVE_TIMEOUT_SECS", defaults.timeout.as_secs())?;
+
+        if interval == 0 || timeout == 0 {
+            return Err(anyhow!(
+                "GRPC_KEEPALIVE_INTERVAL_SECS and GRPC_KEEPALIVE_TIMEOUT_SECS must be greater than 0"
+            ));
+        }
+
+        Ok(Self {
+            interval: Duration::from_secs(interval),
+            timeout: Duration::from_secs(timeout),
+            while_idle: env_or("GRPC_KEEPALIVE_WHILE_IDLE", defaults.while_idle)?,
+        })
+    }
+}
@@ -0,0 +1,121 @@
+use anyhow::{Context, Result, anyhow};
+use std::str::FromStr;
+use tracing_subscriber::EnvFilter;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LogFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl FromStr for LogFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "text" => Ok(LogFormat::Text),
+            "json" => Ok(LogFormat::Json),
+            other => Err(anyhow!(
+                "LOG_FORMAT must be 'text' or 'json', got '{other}'"
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct LogConfig {
+    pub format: LogFormat,
+    // directive gaya RUST_LOG, misal "shared::service::transfer=debug,sqlx=warn"
+    pub directives: Option<String>,
+}
+
+impl LogConfig {
+    pub fn from_env() -> Result<Self> {
+        let format = match std::env::var("LOG_FORMAT") {
+            Ok(value) if !value.trim().is_empty() => value.parse()?,
+            _ => LogFormat::default(),
+        };
+
+        let directives = match std::env::var("LOG_DIRECTIVES") {
+            Ok(value) if !value.trim().is_empty() => {
+                let value = value.trim().to_string();
+                EnvFilter::try_new(&value)
+                    .with_context(|| format!("LOG_DIRECTIVES has an invalid value: '{value}'"))?;
+                Some(value)
+            }
+            _ => None,
+        };
+
+        Ok(Self { format, directives })
+    }
+
+    pub fn env_filter(&self, default: &str) -> EnvFilter {
+        self.directives
+            .as_deref()
+            .and_then(|directives| EnvFilter::try_new(directives).ok())
+            .unwrap_or_else(|| EnvFilter::new(default))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{Arc, Mutex};
+    use tracing::{Event, Subscriber};
+    use tracing_subscriber::{Layer, layer::Context, prelude::*};
+
+    // mencatat target setiap event yang lolos filter
+    #[derive(Clone, Default)]
+    struct Targets(Arc<Mutex<Vec<String>>>);
+
+    impl<S: Subscriber> Layer<S> for Targets {
+        fn on_event(&self, event: &Event<'_>, _ctx: Context<'_, S>) {
+            self.0
+                .lock()
+                .unwrap()
+                .push(event.metadata().target().to_string());
+        }
+    }
+
+    fn emitted(config: &LogConfig, default: &str) -> Vec<String> {
+        let targets = Targets::default();
+        let subscriber = tracing_subscriber::registry()
+            .with(targets.clone().with_filter(config.env_filter(default)));
+
+        tracing::subscriber::with_default(subscriber, || {
+            tracing::debug!(target: "shared::service::transfer", "transfer debug");
+            tracing::info!(target: "sqlx::query", "slow statement");
+            tracing::warn!(target: "sqlx::query", "pool exhausted");
+        });
+
+        targets.0.lock().unwrap().clone()
+    }
+
+    #[test]
+    fn directive_silences_one_target_and_allows_another() {
+        let config = LogConfig {
+            directives: Some("shared::service::transfer=debug,sqlx=warn".to_string()),
+            ..LogConfig::default()
+        };
+
+        assert_eq!(
+            emitted(&config, "info"),
+            ["shared::service::transfer", "sqlx::query"]
+        );
+    }
+
+    #[test]
+    fn missing_or_invalid_directives_fall_back_to_the_default_level() {
+        assert_eq!(
+            emitted(&LogConfig::default(), "info"),
+            ["sqlx::query", "sqlx::query"]
+        );
+
+        let invalid = LogConfig {
+            directives: Some("sqlx=loud".to_string()),
+            ..LogConfig::default()
+        };
+        assert_eq!(emitted(&invalid, "warn"), ["sqlx::query"]);
+    }
+}
@@ -1,11 +1,26 @@
+mod cache;
 mod database;
 mod hashing;
 mod jwt;
+mod keepalive;
+mod log;
 mod myconfig;
+mod rate_limit;
 mod redis;
+mod velocity;
+mod webhook;
 
-pub use self::database::{ConnectionManager, ConnectionPool};
-pub use self::hashing::Hashing;
-pub use self::jwt::JwtConfig;
-pub use self::myconfig::Config;
+pub use self::cache::CacheTtlConfig;
+pub use self::database::{
+    ConnectionManager, ConnectionPool, DatabasePools, MigrationStatus, PoolConfig, ReadPool,
+    migration_status, with_replica_reads,
+};
+pub use self::hashing::{HashAlgorithm, Hashing};
+pub use self::jwt::{Claims, JwtConfig};
+pub use self::keepalive::GrpcKeepaliveConfig;
+pub use self::log::{LogConfig, LogFormat};
+pub use self::myconfig::{Config, DEFAULT_GRPC_MAX_MESSAGE_SIZE, DEFAULT_SHUTDOWN_TIMEOUT_SECS};
+pub use self::rate_limit::{RateLimit, WriteRateLimitConfig};
 pub use self::redis::{RedisClient, RedisConfig};
+pub use self::velocity::{TransferVelocityConfig, VelocityMode};
+pub use self::webhook::WebhookConfig;
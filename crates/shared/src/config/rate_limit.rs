@@ -0,0 +1,79 @@
+use anyhow::{Context, Result, anyhow};
+use std::time::Duration;
+
+// token bucket: paling banyak `capacity` request beruntun, lalu token terisi kembali
+// secara merata sepanjang `period`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RateLimit {
+    pub capacity: u32,
+    pub period: Duration,
+}
+
+impl RateLimit {
+    pub const fn new(capacity: u32, period: Duration) -> Self {
+        Self { capacity, period }
+    }
+}
+
+// batas per user untuk RPC yang mengubah data; RPC baca tidak dibatasi
+#[derive(Debug, Clone, Copy)]
+pub struct WriteRateLimitConfig {
+    pub transfer: RateLimit,
+    pub withdraw: RateLimit,
+    pub topup: RateLimit,
+    // dipakai semua write lain yang tidak punya batas sendiri
+    pub default: RateLimit,
+}
+
+impl Default for WriteRateLimitConfig {
+    fn default() -> Self {
+        Self {
+            transfer: RateLimit::new(10, Duration::from_secs(60)),
+            withdraw: RateLimit::new(5, Duration::from_secs(60)),
+            topup: RateLimit::new(10, Duration::from_secs(60)),
+            default: RateLimit::new(30, Duration::from_secs(60)),
+        }
+    }
+}
+
+impl WriteRateLimitConfig {
+    pub fn from_env() -> Result<Self> {
+        let defaults = Self::default();
+
+        Ok(Self {
+            transfer: rate_limit_from_env("RATE_LIMIT_TRANSFER", defaults.transfer)?,
+            withdraw: rate_limit_from_env("RATE_LIMIT_WITHDRAW", defaults.withdraw)?,
+            topup: rate_limit_from_env("RATE_LIMIT_TOPUP", defaults.topup)?,
+            default: rate_limit_from_env("RATE_LIMIT_WRITES", defaults.default)?,
+        })
+    }
+}
+
+// format "<jumlah request>/<detik>", misalnya "10/60"
+fn rate_limit_from_env(key: &str, default: RateLimit) -> Result<RateLimit> {
+    let Ok(value) = std::env::var(key) else {
+        return Ok(default);
+    };
+
+    let (capacity, period) = value
+        .trim()
+        .split_once('/')
+        .ok_or_else(|| anyhow!("{key} must look like '<requests>/<seconds>', got '{value}'"))?;
+
+    let capacity: u32 = capacity
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid request count in {key}: '{value}'"))?;
+    let period: u64 = period
+        .trim()
+        .parse()
+        .with_context(|| format!("Invalid period in {key}: '{value}'"))?;
+
+    if capacity == 0 || period == 0 {
+        return Err(anyhow!(
+            "{key} must allow at least 1 request over at least 1 second"
+        ));
+    }
+
+    Ok(RateLimit::new(capacity, Duration::from_secs(period)))
+}
@@ -10,7 +10,7 @@ pub struct RedisConfig {
     pub password: Option<String>,
 }
 
-#[derive(Clone)]
+#[derive(Debug, Clone)]
 pub struct RedisClient {
     pub client: Client,
 }
@@ -47,4 +47,12 @@ impl RedisClient {
 
         Ok(())
     }
+
+    pub async fn ping_async(&self) -> Result<()> {
+        let mut conn = self.client.get_multiplexed_async_connection().await?;
+
+        let _: () = redis::cmd("PING").query_async(&mut conn).await?;
+
+        Ok(())
+    }
 }
@@ -0,0 +1,99 @@
+use anyhow::{Context, Result, anyhow};
+use std::{str::FromStr, time::Duration};
+
+// apa yang terjadi pada transfer yang melewati batas kecepatan
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum VelocityMode {
+    #[default]
+    Off,
+    Reject,
+    // transfer tetap diproses tapi ditandai needs_review untuk tim fraud
+    Review,
+}
+
+impl VelocityMode {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VelocityMode::Off => "off",
+            VelocityMode::Reject => "reject",
+            VelocityMode::Review => "review",
+        }
+    }
+}
+
+impl FromStr for VelocityMode {
+    type Err = anyhow::Error;
+
+    fn from_str(value: &str) -> Result<Self> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "off" => Ok(VelocityMode::Off),
+            "reject" => Ok(VelocityMode::Reject),
+            "review" => Ok(VelocityMode::Review),
+            other => Err(anyhow!(
+                "TRANSFER_VELOCITY_MODE must be 'off', 'reject' or 'review', got '{other}'"
+            )),
+        }
+    }
+}
+
+// pengirim yang sudah membuat `max_transfers` transfer dalam `window` terakhir dianggap
+// terlalu cepat; transfer berikutnya ditolak atau ditandai sesuai `mode`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransferVelocityConfig {
+    pub mode: VelocityMode,
+    pub window: Duration,
+    pub max_transfers: u32,
+}
+
+impl Default for TransferVelocityConfig {
+    fn default() -> Self {
+        Self {
+            mode: VelocityMode::Off,
+            window: Duration::from_secs(10 * 60),
+            max_transfers: 5,
+        }
+    }
+}
+
+impl TransferVelocityConfig {
+    pub fn from_env() -> Result<Self> {
+        let defaults = Self::default();
+
+        let mode = match std::env::var("TRANSFER_VELOCITY_MODE") {
+            Ok(value) if !value.trim().is_empty() => value.parse()?,
+            _ => defaults.mode,
+        };
+
+        let window_minutes = match std::env::var("TRANSFER_VELOCITY_WINDOW_MINUTES") {
+            Ok(value) => value
+                .trim()
+                .parse::<u64>()
+                .with_context(|| format!("Invalid TRANSFER_VELOCITY_WINDOW_MINUTES: '{value}'"))?,
+            Err(_) => defaults.window.as_secs() / 60,
+        };
+
+        let max_transfers = match std::env::var("TRANSFER_VELOCITY_MAX_TRANSFERS") {
+            Ok(value) => value
+                .trim()
+                .parse::<u32>()
+                .with_context(|| format!("Invalid TRANSFER_VELOCITY_MAX_TRANSFERS: '{value}'"))?,
+            Err(_) => defaults.max_transfers,
+        };
+
+        if window_minutes == 0 || max_transfers == 0 {
+            return Err(anyhow!(
+                "TRANSFER_VELOCITY_WINDOW_MINUTES and TRANSFER_VELOCITY_MAX_TRANSFERS must be at least 1"
+            ));
+        }
+
+        Ok(Self {
+            mode,
+            window: Duration::from_secs(window_minutes * 60),
+            max_transfers,
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.mode != VelocityMode::Off
+    }
+}
@@ -0,0 +1,98 @@
+use anyhow::{Result, anyhow};
+use std::time::Duration;
+
+use crate::config::myconfig::env_or;
+
+// WEBHOOK_URL kosong berarti worker outbox tidak dijalankan
+#[derive(Debug, Clone)]
+pub struct WebhookConfig {
+    pub url: Option<String>,
+    pub secret: String,
+    pub poll_interval: Duration,
+    pub batch_size: i64,
+    pub timeout: Duration,
+    pub initial_backoff: Duration,
+    pub max_backoff: Duration,
+    pub max_attempts: i32,
+}
+
+impl Default for WebhookConfig {
+    fn default() -> Self {
+        Self {
+            url: None,
+            secret: String::new(),
+            poll_interval: Duration::from_secs(5),
+            batch_size: 50,
+            timeout: Duration::from_secs(10),
+            initial_backoff: Duration::from_secs(5),
+            max_backoff: Duration::from_secs(60 * 30),
+            max_attempts: 10,
+        }
+    }
+}
+
+impl WebhookConfig {
+    pub fn from_env() -> Result<Self> {
+        let defaults = Self::default();
+
+        let url = std::env::var("WEBHOOK_URL")
+            .ok()
+            .map(|value| value.trim().to_string())
+            .filter(|value| !value.is_empty());
+
+        let secret = std::env::var("WEBHOOK_SECRET").unwrap_or_default();
+
+        if url.is_some() && secret.is_empty() {
+            return Err(anyhow!(
+                "WEBHOOK_SECRET must be set when WEBHOOK_URL is set"
+            ));
+        }
+
+        let batch_size = env_or("WEBHOOK_BATCH_SIZE", defaults.batch_size)?;
+        let max_attempts = env_or("WEBHOOK_MAX_ATTEMPTS", defaults.max_attempts)?;
+
+        if batch_size <= 0 || max_attempts <= 0 {
+            return Err(anyhow!(
+                "WEBHOOK_BATCH_SIZE and WEBHOOK_MAX_ATTEMPTS must be greater than 0"
+            ));
+        }
+
+        Ok(Self {
+            url,
+            secret,
+            poll_interval: secs_from_env("WEBHOOK_POLL_INTERVAL_SECS", defaults.poll_interval)?,
+            batch_size,
+            timeout: secs_from_env("WEBHOOK_TIMEOUT_SECS", defaults.timeout)?,
+            initial_backoff: secs_from_env(
+                "WEBHOOK_INITIAL_BACKOFF_SECS",
+                defaults.initial_backoff,
+            )?,
+            max_backoff: secs_from_env("WEBHOOK_MAX_BACKOFF_SECS", defaults.max_backoff)?,
+            max_attempts,
+        })
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.url.is_some()
+    }
+
+    // satu batch dikirim berurutan, jadi klaimnya harus bertahan sampai event terakhir
+    // selesai dikirim walau setiap request memakai timeout penuh
+    pub fn claim_lease(&self) -> Duration {
+        self.timeout
+            .saturating_mul(self.batch_size.clamp(1, u32::MAX as i64) as u32)
+            .saturating_add(self.poll_interval)
+    }
+
+    // backoff eksponensial: initial * 2^attempts, dibatasi max_backoff
+    pub fn backoff(&self, attempts: i32) -> Duration {
+        let factor = 2u32.saturating_pow(attempts.max(0) as u32);
+        self.initial_backoff
+            .saturating_mul(factor)
+            .min(self.max_backoff)
+    }
+}
+
+fn secs_from_env(key: &str, default: Duration) -> Result<Duration> {
+    env_or(key, default.as_secs()).map(Duration::from_secs)
+}
@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+use validator::Validate;
+
+// ringkasan selalu dibangun dengan jumlah maksimum lalu dipotong sesuai `limit`,
+// sehingga satu entri cache per user cukup untuk semua nilai limit
+pub const MAX_SUMMARY_ITEMS: i32 = 20;
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams, Validate)]
+pub struct AccountSummaryRequest {
+    /// Number of recent items per type, between 1 and 20
+    #[serde(default = "default_summary_limit")]
+    #[validate(range(
+        min = 1,
+        max = MAX_SUMMARY_ITEMS,
+        message = "limit must be between 1 and 20"
+    ))]
+    pub limit: i32,
+}
+
+impl Default for AccountSummaryRequest {
+    fn default() -> Self {
+        Self {
+            limit: default_summary_limit(),
+        }
+    }
+}
+
+fn default_summary_limit() -> i32 {
+    5
+}
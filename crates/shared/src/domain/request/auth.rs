@@ -1,6 +1,21 @@
-use serde::{Deserialize, Serialize};
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Deserializer, Serialize};
 use utoipa::ToSchema;
-use validator::Validate;
+use validator::{Validate, ValidationError};
+
+// email disimpan dan dicari dalam bentuk ini, sehingga `Alice@Example.com ` dan
+// `alice@example.com` dianggap akun yang sama
+pub fn normalize_email(email: &str) -> String {
+    email.trim().to_lowercase()
+}
+
+// dinormalisasi saat body dibaca supaya validasi format melihat email yang sudah di-trim
+pub(crate) fn deserialize_email<'de, D>(deserializer: D) -> Result<String, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    String::deserialize(deserializer).map(|email| normalize_email(&email))
+}
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 pub struct RegisterRequest {
@@ -10,6 +25,7 @@ pub struct RegisterRequest {
     #[validate(length(min = 2, message = "Last name must be at least 2 characters"))]
     pub lastname: String,
 
+    #[serde(deserialize_with = "deserialize_email")]
     #[validate(email(message = "Invalid email format"))]
     pub email: String,
 
@@ -23,9 +39,57 @@ pub struct RegisterRequest {
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 pub struct LoginRequest {
+    #[serde(deserialize_with = "deserialize_email")]
     #[validate(email(message = "Invalid email format"))]
     pub email: String,
 
     #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
     pub password: String,
+
+    // diisi gateway dari header User-Agent, bukan dari body
+    #[serde(skip)]
+    pub user_agent: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct RefreshTokenRequest {
+    #[validate(length(min = 1, message = "Refresh token is required"))]
+    pub refresh_token: String,
+}
+
+#[derive(Debug, Clone)]
+pub struct CreateSessionRequest {
+    pub user_id: i32,
+    pub token_hash: String,
+    pub user_agent: Option<String>,
+    pub expires_at: NaiveDateTime,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ChangePasswordRequest {
+    #[validate(length(min = 1, message = "Old password is required"))]
+    pub old_password: String,
+
+    #[validate(custom(function = "validate_password_strength"))]
+    pub new_password: String,
+
+    #[validate(must_match(other = "new_password", message = "Passwords do not match"))]
+    pub confirm_password: String,
+}
+
+// minimal 8 karakter dengan huruf besar, huruf kecil dan angka
+fn validate_password_strength(password: &str) -> Result<(), ValidationError> {
+    let is_strong = password.chars().count() >= 8
+        && password.chars().any(|c| c.is_ascii_uppercase())
+        && password.chars().any(|c| c.is_ascii_lowercase())
+        && password.chars().any(|c| c.is_ascii_digit());
+
+    if is_strong {
+        Ok(())
+    } else {
+        Err(ValidationError::new("weak_password").with_message(
+            "Password must be at least 8 characters and contain uppercase, lowercase and a digit"
+                .into(),
+        ))
+    }
 }
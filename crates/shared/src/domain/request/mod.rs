@@ -1,26 +1,50 @@
+pub mod account;
 pub mod auth;
+pub mod pagination;
 pub mod saldo;
+pub mod system;
 pub mod topup;
 pub mod transfer;
 pub mod user;
+pub mod virtual_card;
 pub mod withdraw;
 
-pub use self::user::{CreateUserRequest, FindAllUserRequest, UpdateUserRequest};
+pub use self::account::{AccountSummaryRequest, MAX_SUMMARY_ITEMS};
 
-pub use self::auth::{LoginRequest, RegisterRequest};
+pub use self::user::{
+    CreateUserRequest, DeleteUserRequest, FindAllUserRequest, ImportUserRow, ImportUsersParams,
+    ImportUsersRequest, MAX_IMPORT_ROWS, PurgeUserRequest, UpdateUserRequest, UserFilter,
+};
+
+pub use self::pagination::{
+    DEFAULT_MAX_PAGE_SIZE, DEFAULT_PAGE_SIZE, PageRequest, default_page_size,
+    install_default_page_size, install_max_page_size, max_page_size,
+};
+
+pub use self::auth::{
+    ChangePasswordRequest, CreateSessionRequest, LoginRequest, RefreshTokenRequest,
+    RegisterRequest, normalize_email,
+};
 
 pub use self::saldo::{
-    CreateSaldoRequest, FindAllSaldoRequest, UpdateSaldoBalance, UpdateSaldoRequest,
+    AdjustBalanceRequest, CreateSaldoRequest, FindAllSaldoRequest, MAX_TOP_SALDOS,
+    ReconcileBalanceRequest, TopSaldosRequest, UpdateSaldoBalance, UpdateSaldoRequest,
     UpdateSaldoWithdraw,
 };
 
+pub use self::system::CacheFlushRequest;
+
 pub use self::transfer::{
-    CreateTransferRequest, FindAllTransferRequest, UpdateTransferAmountRequest,
-    UpdateTransferRequest,
+    CreateTransferRequest, DEFAULT_MIN_TRANSFER_AMOUNT, DailyTransferTotalsRequest,
+    FindAllTransferRequest, StreamTransfersRequest, TransferFilter, UpdateTransferAmountRequest,
+    UpdateTransferRequest, install_min_transfer_amount, min_transfer_amount,
 };
 
 pub use self::topup::{
-    CreateTopupRequest, FindAllTopupRequest, UpdateTopupAmount, UpdateTopupRequest,
+    CreateTopupRequest, FindAllTopupRequest, TopupStatsRequest, UpdateTopupAmount,
+    UpdateTopupRequest,
 };
 
 pub use self::withdraw::{CreateWithdrawRequest, FindAllWithdrawRequest, UpdateWithdrawRequest};
+
+pub use self::virtual_card::{CreateVirtualCardRequest, FindVirtualCardsRequest};
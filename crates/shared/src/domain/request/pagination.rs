@@ -0,0 +1,94 @@
+use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use tracing::warn;
+use utoipa::IntoParams;
+
+use crate::utils::AppError;
+
+pub const DEFAULT_MAX_PAGE_SIZE: i32 = 100;
+pub const DEFAULT_PAGE_SIZE: i32 = 10;
+
+static MAX_PAGE_SIZE: OnceLock<i32> = OnceLock::new();
+static PAGE_SIZE: OnceLock<i32> = OnceLock::new();
+
+// dipasang sekali saat startup dari MAX_PAGE_SIZE; sebelum itu dipakai nilai bawaan
+pub fn install_max_page_size(max: i32) {
+    if MAX_PAGE_SIZE.set(max).is_err() {
+        warn!("⚠️  Max page size already installed, ignoring new one");
+    }
+}
+
+pub fn max_page_size() -> i32 {
+    MAX_PAGE_SIZE
+        .get()
+        .copied()
+        .unwrap_or(DEFAULT_MAX_PAGE_SIZE)
+}
+
+// dipasang sekali saat startup dari DEFAULT_PAGE_SIZE; dipakai saat page_size kosong atau <= 0
+pub fn install_default_page_size(size: i32) {
+    if PAGE_SIZE.set(size).is_err() {
+        warn!("⚠️  Default page size already installed, ignoring new one");
+    }
+}
+
+pub fn default_page_size() -> i32 {
+    PAGE_SIZE.get().copied().unwrap_or(DEFAULT_PAGE_SIZE)
+}
+
+// halaman untuk daftar milik satu user; nilai kosong jatuh ke halaman 1 ukuran default_page_size()
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, IntoParams)]
+pub struct PageRequest {
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    #[param(default = default_page_size, minimum = 1)]
+    pub page_size: i32,
+}
+
+impl Default for PageRequest {
+    fn default() -> Self {
+        Self {
+            page: default_page(),
+            page_size: default_page_size(),
+        }
+    }
+}
+
+impl PageRequest {
+    pub fn new(page: i32, page_size: i32) -> Self {
+        Self { page, page_size }
+    }
+
+    // page negatif ditolak, page 0 jadi 1; page_size <= 0 jatuh ke default_page_size() dan dibatasi max_page_size()
+    pub fn bounded(self) -> Result<Self, AppError> {
+        if self.page < 0 {
+            return Err(AppError::Custom(format!(
+                "page must not be negative, got {}",
+                self.page
+            )));
+        }
+
+        Ok(Self {
+            page: self.page.max(1),
+            page_size: if self.page_size > 0 {
+                self.page_size.min(max_page_size())
+            } else {
+                default_page_size()
+            },
+        })
+    }
+
+    // tiap halaman di-cache terpisah per user
+    pub fn cache_key(&self, prefix: &str, user_id: i32) -> String {
+        format!(
+            "{prefix}:id={user_id}:page={}:size={}",
+            self.page, self.page_size
+        )
+    }
+}
+
+fn default_page() -> i32 {
+    1
+}
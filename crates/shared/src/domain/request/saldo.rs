@@ -1,7 +1,9 @@
+use crate::domain::request::pagination::default_page_size;
+use crate::model::saldo::DEFAULT_SALDO_LABEL;
 use chrono::{DateTime, NaiveDateTime, Utc};
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
-use validator::Validate;
+use validator::{Validate, ValidationError};
 
 #[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
 pub struct FindAllSaldoRequest {
@@ -9,20 +11,32 @@ pub struct FindAllSaldoRequest {
     pub page: i32,
 
     #[serde(default = "default_page_size")]
+    #[param(default = default_page_size, minimum = 1)]
     pub page_size: i32,
 
     #[serde(default)]
     pub search: String,
 }
 
-fn default_page() -> i32 {
-    1
+// batas atas leaderboard supaya query dan payload tetap kecil
+pub const MAX_TOP_SALDOS: i32 = 100;
+
+#[derive(Serialize, Deserialize, Clone, Debug, IntoParams, Validate)]
+pub struct TopSaldosRequest {
+    /// Number of saldos to return, between 1 and 100
+    #[serde(default = "default_top_limit")]
+    #[validate(range(min = 1, max = MAX_TOP_SALDOS, message = "limit must be between 1 and 100"))]
+    pub limit: i32,
 }
 
-fn default_page_size() -> i32 {
+fn default_top_limit() -> i32 {
     10
 }
 
+fn default_page() -> i32 {
+    1
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
 pub struct CreateSaldoRequest {
     #[serde(rename = "user_id")]
@@ -32,6 +46,17 @@ pub struct CreateSaldoRequest {
     #[serde(rename = "total_balance")]
     #[validate(range(min = 50000))]
     pub total_balance: i32,
+
+    // kosong berarti saldo bawaan "main"
+    #[serde(default)]
+    #[validate(length(min = 1, max = 50, message = "Label must be 1-50 characters"))]
+    pub label: Option<String>,
+}
+
+impl CreateSaldoRequest {
+    pub fn label(&self) -> &str {
+        self.label.as_deref().unwrap_or(DEFAULT_SALDO_LABEL)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate, Default)]
@@ -55,6 +80,52 @@ pub struct UpdateSaldoRequest {
     pub withdraw_time: Option<NaiveDateTime>,
 }
 
+// koreksi manual oleh admin; selalu tercatat di ledger bersama alasannya
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema, Validate)]
+pub struct AdjustBalanceRequest {
+    #[validate(range(min = 1))]
+    pub user_id: i32,
+
+    /// Amount added to the balance, negative to debit
+    #[validate(custom(function = "validate_delta"))]
+    pub delta: i32,
+
+    /// Why the correction is made, kept in the ledger for audit
+    #[validate(custom(function = "validate_reason"))]
+    pub reason: String,
+}
+
+fn validate_delta(delta: i32) -> Result<(), ValidationError> {
+    if delta == 0 {
+        return Err(ValidationError::new("delta").with_message("Delta must not be zero".into()));
+    }
+
+    Ok(())
+}
+
+fn validate_reason(reason: &str) -> Result<(), ValidationError> {
+    let len = reason.trim().chars().count();
+    if !(1..=255).contains(&len) {
+        return Err(
+            ValidationError::new("reason").with_message("Reason must be 1-255 characters".into())
+        );
+    }
+
+    Ok(())
+}
+
+// tanpa user_id semua user dipindai dan hanya yang selisih dilaporkan
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, Validate)]
+pub struct ReconcileBalanceRequest {
+    /// Reconcile a single user, or every user when omitted
+    #[validate(range(min = 1))]
+    pub user_id: Option<i32>,
+
+    /// Correct each mismatch with an audited ledger adjustment
+    #[serde(default)]
+    pub fix: bool,
+}
+
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, Validate)]
 pub struct UpdateSaldoBalance {
     #[validate(range(min = 50000))]
@@ -0,0 +1,10 @@
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+use validator::Validate;
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, ToSchema, Validate)]
+pub struct CacheFlushRequest {
+    /// Key prefix to flush, e.g. `saldos:`; lock and rate limit keys are never removed
+    #[validate(length(min = 1, max = 128, message = "prefix must be 1-128 characters"))]
+    pub prefix: String,
+}
@@ -1,3 +1,5 @@
+use crate::domain::request::pagination::default_page_size;
+use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
@@ -8,18 +10,41 @@ pub struct FindAllTopupRequest {
     pub page: i32,
 
     #[serde(default = "default_page_size")]
+    #[param(default = default_page_size, minimum = 1)]
     pub page_size: i32,
 
     #[serde(default)]
     pub search: String,
 }
 
-fn default_page() -> i32 {
-    1
+#[derive(Serialize, Deserialize, Clone, Debug, Default, IntoParams)]
+pub struct TopupStatsRequest {
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
 }
 
-fn default_page_size() -> i32 {
-    10
+impl TopupStatsRequest {
+    pub fn start_time(&self) -> Option<NaiveDateTime> {
+        self.start_date.and_then(|date| date.and_hms_opt(0, 0, 0))
+    }
+
+    // end_date inklusif, jadi batas atasnya awal hari berikutnya
+    pub fn end_time(&self) -> Option<NaiveDateTime> {
+        self.end_date
+            .and_then(|date| date.succ_opt())
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+    }
+
+    pub fn is_valid_range(&self) -> bool {
+        match (self.start_date, self.end_date) {
+            (Some(start), Some(end)) => start <= end,
+            _ => true,
+        }
+    }
+}
+
+fn default_page() -> i32 {
+    1
 }
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
 pub struct CreateTopupRequest {
@@ -44,11 +69,12 @@ pub struct UpdateTopupRequest {
     #[validate(range(min = 1))]
     pub topup_id: i32,
 
+    // field yang tidak dikirim dibiarkan seperti semula
     #[validate(range(min = 1, message = "Top-up amount must be at least 1"))]
-    pub topup_amount: i32,
+    pub topup_amount: Option<i32>,
 
-    #[validate(length(min = 1, message = "Top-up method is required"))]
-    pub topup_method: String,
+    #[validate(length(min = 1, message = "Top-up method must not be empty"))]
+    pub topup_method: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
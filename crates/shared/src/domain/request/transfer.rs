@@ -1,6 +1,48 @@
+use crate::domain::request::pagination::default_page_size;
+use crate::model::{saldo::DEFAULT_SALDO_LABEL, transfer::TransferStatus};
+use chrono::{Duration, NaiveDate, NaiveDateTime, NaiveTime};
 use serde::{Deserialize, Serialize};
+use std::sync::OnceLock;
+use tracing::warn;
 use utoipa::{IntoParams, ToSchema};
-use validator::Validate;
+use validator::{Validate, ValidationError, ValidationErrors};
+
+pub const DEFAULT_MIN_TRANSFER_AMOUNT: i32 = 50_000;
+
+static MIN_TRANSFER_AMOUNT: OnceLock<i32> = OnceLock::new();
+
+// dipasang sekali saat startup dari MIN_TRANSFER_AMOUNT; sebelum itu dipakai nilai bawaan
+pub fn install_min_transfer_amount(min: i32) {
+    if MIN_TRANSFER_AMOUNT.set(min).is_err() {
+        warn!("⚠️  Min transfer amount already installed, ignoring new one");
+    }
+}
+
+pub fn min_transfer_amount() -> i32 {
+    MIN_TRANSFER_AMOUNT
+        .get()
+        .copied()
+        .unwrap_or(DEFAULT_MIN_TRANSFER_AMOUNT)
+}
+
+fn validate_transfer_amount(amount: i32) -> Result<(), ValidationError> {
+    check_transfer_amount(amount, min_transfer_amount())
+}
+
+// nominal selalu harus positif, minimum 0 hanya mematikan batas bawah tambahan
+fn check_transfer_amount(amount: i32, min: i32) -> Result<(), ValidationError> {
+    if amount <= 0 {
+        return Err(ValidationError::new("transfer_amount")
+            .with_message("Transfer amount must be positive".into()));
+    }
+
+    if amount < min {
+        return Err(ValidationError::new("transfer_amount")
+            .with_message(format!("Transfer amount must be at least {min}").into()));
+    }
+
+    Ok(())
+}
 
 #[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
 pub struct FindAllTransferRequest {
@@ -8,18 +50,137 @@ pub struct FindAllTransferRequest {
     pub page: i32,
 
     #[serde(default = "default_page_size")]
+    #[param(default = default_page_size, minimum = 1)]
     pub page_size: i32,
 
     #[serde(default)]
     pub search: String,
+
+    #[serde(default)]
+    pub min_amount: Option<i32>,
+
+    #[serde(default)]
+    pub max_amount: Option<i32>,
+
+    /// Comma-separated statuses, e.g. `completed,reversed`; empty means all
+    #[serde(default)]
+    pub status: String,
 }
 
-fn default_page() -> i32 {
-    1
+impl FindAllTransferRequest {
+    // status unik dan terurut supaya cache key untuk filter yang sama selalu sama
+    pub fn statuses(&self) -> Result<Vec<TransferStatus>, ValidationErrors> {
+        let mut statuses = Vec::new();
+
+        for value in self
+            .status
+            .split(',')
+            .map(str::trim)
+            .filter(|v| !v.is_empty())
+        {
+            match value.parse::<TransferStatus>() {
+                Ok(status) if !statuses.contains(&status) => statuses.push(status),
+                Ok(_) => {}
+                Err(message) => {
+                    let mut errors = ValidationErrors::new();
+                    errors.add(
+                        "status",
+                        ValidationError::new("status").with_message(message.into()),
+                    );
+                    return Err(errors);
+                }
+            }
+        }
+
+        statuses.sort_by_key(|status| status.as_str());
+        Ok(statuses)
+    }
+}
+
+// filter list transfer untuk repository, batas nominal inklusif, status kosong berarti semua
+#[derive(Debug, Clone, Default)]
+pub struct TransferFilter {
+    pub search: Option<String>,
+    pub min_amount: Option<i32>,
+    pub max_amount: Option<i32>,
+    pub statuses: Vec<TransferStatus>,
+}
+
+// ekspor transfer per batch dengan cursor transfer_id, rentang tanggal berlaku pada transfer_time
+#[derive(Serialize, Deserialize, Clone, Debug, Default)]
+pub struct StreamTransfersRequest {
+    pub start_date: Option<NaiveDate>,
+    pub end_date: Option<NaiveDate>,
+    pub batch_size: i32,
+}
+
+const DEFAULT_STREAM_BATCH_SIZE: i32 = 500;
+const MAX_STREAM_BATCH_SIZE: i32 = 5000;
+
+impl StreamTransfersRequest {
+    pub fn effective_batch_size(&self) -> i32 {
+        match self.batch_size {
+            size if size <= 0 => DEFAULT_STREAM_BATCH_SIZE,
+            size => size.min(MAX_STREAM_BATCH_SIZE),
+        }
+    }
+
+    pub fn start_time(&self) -> Option<NaiveDateTime> {
+        self.start_date.and_then(|date| date.and_hms_opt(0, 0, 0))
+    }
+
+    // end_date inklusif, jadi batas atasnya awal hari berikutnya
+    pub fn end_time(&self) -> Option<NaiveDateTime> {
+        self.end_date
+            .and_then(|date| date.succ_opt())
+            .and_then(|date| date.and_hms_opt(0, 0, 0))
+    }
+}
+
+// batas rentang laporan harian supaya hasil agregasi tetap kecil
+pub const MAX_DAILY_TOTALS_RANGE_DAYS: i64 = 366;
+
+// rentang tanggal laporan harian, keduanya wajib dan end_date inklusif
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, IntoParams)]
+pub struct DailyTransferTotalsRequest {
+    pub start_date: NaiveDate,
+    pub end_date: NaiveDate,
+}
+
+impl DailyTransferTotalsRequest {
+    pub fn validate_range(&self) -> Result<(), String> {
+        if self.start_date > self.end_date {
+            return Err("start_date must not be after end_date".to_string());
+        }
+
+        if (self.end_date - self.start_date).num_days() >= MAX_DAILY_TOTALS_RANGE_DAYS {
+            return Err(format!(
+                "Date range must not exceed {MAX_DAILY_TOTALS_RANGE_DAYS} days"
+            ));
+        }
+
+        Ok(())
+    }
+
+    pub fn start_time(&self) -> NaiveDateTime {
+        self.start_date.and_time(NaiveTime::MIN)
+    }
+
+    // batas atas eksklusif: awal hari setelah end_date
+    pub fn end_time(&self) -> NaiveDateTime {
+        self.end_date.and_time(NaiveTime::MIN) + Duration::days(1)
+    }
+
+    pub fn cache_key(&self) -> String {
+        format!(
+            "transfers:daily_totals:range={}..{}",
+            self.start_date, self.end_date
+        )
+    }
 }
 
-fn default_page_size() -> i32 {
-    10
+fn default_page() -> i32 {
+    1
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
@@ -30,8 +191,40 @@ pub struct CreateTransferRequest {
     #[validate(range(min = 1, message = "Transfer to must be a positive integer"))]
     pub transfer_to: i32,
 
-    #[validate(range(min = 50000, message = "Transfer amount must be at least 50,000"))]
+    #[validate(custom(function = "validate_transfer_amount"))]
     pub transfer_amount: i32,
+
+    #[serde(default)]
+    #[validate(length(max = 255, message = "Note must be at most 255 characters"))]
+    pub note: Option<String>,
+
+    // hanya validasi dan proyeksi saldo, tidak ada yang disimpan
+    #[serde(default)]
+    pub dry_run: bool,
+
+    // label saldo sumber dan tujuan, kosong berarti saldo bawaan "main"
+    #[serde(default)]
+    #[validate(length(min = 1, max = 50, message = "From label must be 1-50 characters"))]
+    pub from_label: Option<String>,
+
+    #[serde(default)]
+    #[validate(length(min = 1, max = 50, message = "To label must be 1-50 characters"))]
+    pub to_label: Option<String>,
+}
+
+impl CreateTransferRequest {
+    pub fn from_label(&self) -> &str {
+        self.from_label.as_deref().unwrap_or(DEFAULT_SALDO_LABEL)
+    }
+
+    pub fn to_label(&self) -> &str {
+        self.to_label.as_deref().unwrap_or(DEFAULT_SALDO_LABEL)
+    }
+
+    // saldo sumber dan tujuan yang sama tidak memindahkan apa pun
+    pub fn is_same_balance(&self) -> bool {
+        self.transfer_from == self.transfer_to && self.from_label() == self.to_label()
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
@@ -45,8 +238,12 @@ pub struct UpdateTransferRequest {
     #[validate(range(min = 1, message = "Transfer to must be a positive integer"))]
     pub transfer_to: i32,
 
-    #[validate(range(min = 50000, message = "Transfer amount must be at least 50,000"))]
+    #[validate(custom(function = "validate_transfer_amount"))]
     pub transfer_amount: i32,
+
+    #[serde(default)]
+    #[validate(length(max = 255, message = "Note must be at most 255 characters"))]
+    pub note: Option<String>,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
@@ -54,6 +251,42 @@ pub struct UpdateTransferAmountRequest {
     #[validate(range(min = 1, message = "Transfer ID must be a positive integer"))]
     pub transfer_id: i32,
 
-    #[validate(range(min = 50000, message = "Transfer amount must be at least 50,000"))]
+    #[validate(custom(function = "validate_transfer_amount"))]
     pub transfer_amount: i32,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn message(err: ValidationError) -> String {
+        err.message.unwrap().into_owned()
+    }
+
+    #[test]
+    fn amount_below_the_minimum_is_rejected() {
+        let err = check_transfer_amount(49_999, 50_000).unwrap_err();
+        assert_eq!(message(err), "Transfer amount must be at least 50000");
+    }
+
+    #[test]
+    fn amount_at_the_minimum_is_accepted() {
+        assert!(check_transfer_amount(50_000, 50_000).is_ok());
+        assert!(check_transfer_amount(50_001, 50_000).is_ok());
+    }
+
+    #[test]
+    fn non_positive_amount_is_rejected_even_without_a_minimum() {
+        for amount in [0, -1, i32::MIN] {
+            let err = check_transfer_amount(amount, 0).unwrap_err();
+            assert_eq!(message(err), "Transfer amount must be positive");
+        }
+        assert!(check_transfer_amount(1, 0).is_ok());
+    }
+
+    #[test]
+    fn default_minimum_applies_before_install() {
+        assert!(validate_transfer_amount(DEFAULT_MIN_TRANSFER_AMOUNT - 1).is_err());
+        assert!(validate_transfer_amount(DEFAULT_MIN_TRANSFER_AMOUNT).is_ok());
+    }
+}
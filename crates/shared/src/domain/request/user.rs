@@ -1,6 +1,15 @@
-use serde::{Deserialize, Serialize};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
 use utoipa::{IntoParams, ToSchema};
-use validator::Validate;
+use validator::{Validate, ValidationError};
+
+use crate::domain::request::auth::{deserialize_email, normalize_email};
+use crate::domain::request::pagination::default_page_size;
+use crate::utils::AppError;
+
+// satu kali import dibatasi supaya transaksinya tidak menahan lock terlalu lama
+pub const MAX_IMPORT_ROWS: usize = 1000;
+const IMPORT_CSV_COLUMNS: [&str; 3] = ["name", "email", "password"];
 
 #[derive(Serialize, Deserialize, Clone, Debug, IntoParams)]
 pub struct FindAllUserRequest {
@@ -8,28 +17,62 @@ pub struct FindAllUserRequest {
     pub page: i32,
 
     #[serde(default = "default_page_size")]
+    #[param(default = default_page_size, minimum = 1)]
     pub page_size: i32,
 
     #[serde(default)]
     pub search: String,
+
+    #[serde(default)]
+    pub include_deleted: bool,
+
+    #[serde(default)]
+    pub email: String,
+
+    #[serde(default)]
+    pub created_before: Option<DateTime<Utc>>,
+
+    #[serde(default)]
+    pub created_after: Option<DateTime<Utc>>,
 }
 
-fn default_page() -> i32 {
-    1
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+pub struct DeleteUserRequest {
+    /// Delete even if the user still has incoming transfers or active sessions
+    #[serde(default)]
+    pub force: bool,
 }
 
-fn default_page_size() -> i32 {
-    10
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+pub struct PurgeUserRequest {
+    /// Purge even if other users have transfers with this user
+    #[serde(default)]
+    pub force: bool,
+}
+
+// filter list user untuk repository, email exact match mengabaikan search
+#[derive(Debug, Clone, Default)]
+pub struct UserFilter {
+    pub search: Option<String>,
+    pub email: Option<String>,
+    pub created_before: Option<NaiveDateTime>,
+    pub created_after: Option<NaiveDateTime>,
+    pub include_deleted: bool,
+}
+
+fn default_page() -> i32 {
+    1
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
 pub struct CreateUserRequest {
-    #[validate(length(min = 1, message = "First name is required"))]
+    #[validate(custom(function = "validate_not_blank", message = "First name is required"))]
     pub firstname: String,
 
-    #[validate(length(min = 1, message = "Last name is required"))]
+    #[validate(custom(function = "validate_not_blank", message = "Last name is required"))]
     pub lastname: String,
 
+    #[serde(deserialize_with = "deserialize_email")]
     #[validate(email(message = "Invalid email format"))]
     pub email: String,
 
@@ -44,23 +87,243 @@ pub struct CreateUserRequest {
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
+#[validate(schema(function = "validate_password_confirmation"))]
 pub struct UpdateUserRequest {
     #[validate(range(min = 1))]
     pub id: i32,
 
-    #[validate(length(min = 1, message = "First name is required"))]
+    #[validate(custom(function = "validate_not_blank", message = "First name is required"))]
     pub firstname: Option<String>,
 
-    #[validate(length(min = 1, message = "Last name is required"))]
+    #[validate(custom(function = "validate_not_blank", message = "Last name is required"))]
     pub lastname: Option<String>,
 
+    #[serde(default, deserialize_with = "deserialize_optional_email")]
     #[validate(email(message = "Invalid email format"))]
     pub email: Option<String>,
 
     #[validate(length(min = 6, message = "Password must be at least 6 characters"))]
-    pub password: String,
+    pub password: Option<String>,
 
     #[validate(length(min = 6, message = "Confirm password must be at least 6 characters"))]
-    #[validate(must_match(other = "password"))]
-    pub confirm_password: String,
+    pub confirm_password: Option<String>,
+}
+
+fn deserialize_optional_email<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    Option::<String>::deserialize(deserializer).map(|email| email.map(|e| normalize_email(&e)))
+}
+
+// length(min = 1) meloloskan nama yang hanya berisi spasi
+fn validate_not_blank(value: &str) -> Result<(), ValidationError> {
+    if value.trim().is_empty() {
+        return Err(ValidationError::new("blank"));
+    }
+
+    Ok(())
+}
+
+// password bersifat opsional, tapi kalau dikirim harus sama dengan konfirmasinya
+fn validate_password_confirmation(input: &UpdateUserRequest) -> Result<(), ValidationError> {
+    if input.password != input.confirm_password {
+        return Err(ValidationError::new("must_match")
+            .with_message("Password and confirm password do not match".into()));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default, Serialize, Deserialize, IntoParams)]
+pub struct ImportUsersParams {
+    /// Skip failing rows instead of aborting the whole import
+    #[serde(default)]
+    pub continue_on_error: bool,
+}
+
+// satu baris CSV; name dipecah di spasi pertama menjadi firstname dan lastname
+#[derive(Debug, Clone, Default, Serialize, Deserialize, Validate)]
+pub struct ImportUserRow {
+    #[validate(custom(function = "validate_full_name"))]
+    pub name: String,
+
+    #[validate(email(message = "Invalid email format"))]
+    pub email: String,
+
+    #[validate(length(min = 8, message = "Password must be at least 8 characters"))]
+    pub password: String,
+}
+
+impl ImportUserRow {
+    pub fn split_name(&self) -> (String, String) {
+        let name = self.name.trim();
+        match name.split_once(char::is_whitespace) {
+            Some((firstname, lastname)) => (firstname.to_string(), lastname.trim().to_string()),
+            None => (name.to_string(), String::new()),
+        }
+    }
+}
+
+fn validate_full_name(name: &str) -> Result<(), ValidationError> {
+    if name.split_whitespace().count() < 2 {
+        return Err(ValidationError::new("full_name")
+            .with_message("Name must contain a first and last name".into()));
+    }
+
+    Ok(())
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct ImportUsersRequest {
+    pub rows: Vec<ImportUserRow>,
+    pub continue_on_error: bool,
+}
+
+impl ImportUsersRequest {
+    // header wajib berisi name,email,password dengan urutan bebas; kolom yang kurang
+    // dibiarkan kosong supaya gagal di validasi baris, bukan menggagalkan seluruh file
+    pub fn from_csv(body: &str, continue_on_error: bool) -> Result<Self, AppError> {
+        let mut reader = csv::ReaderBuilder::new()
+            .flexible(true)
+            .trim(csv::Trim::All)
+            .from_reader(body.as_bytes());
+
+        let headers = reader
+            .headers()
+            .map_err(|e| AppError::Custom(format!("Invalid CSV header: {e}")))?
+            .clone();
+
+        let mut positions = [0; 3];
+        for (position, column) in positions.iter_mut().zip(IMPORT_CSV_COLUMNS) {
+            *position = headers
+                .iter()
+                .position(|header| header.eq_ignore_ascii_case(column))
+                .ok_or_else(|| {
+                    AppError::Custom(format!("CSV header is missing the '{column}' column"))
+                })?;
+        }
+
+        let mut rows = Vec::new();
+        for record in reader.records() {
+            let record = record.map_err(|e| AppError::Custom(format!("Invalid CSV row: {e}")))?;
+            let field = |index: usize| record.get(index).unwrap_or_default().to_string();
+
+            rows.push(ImportUserRow {
+                name: field(positions[0]),
+                email: normalize_email(&field(positions[1])),
+                password: field(positions[2]),
+            });
+        }
+
+        Ok(Self {
+            rows,
+            continue_on_error,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::response::ErrorResponse;
+
+    fn create_user() -> CreateUserRequest {
+        CreateUserRequest {
+            firstname: "Alice".to_string(),
+            lastname: "Smith".to_string(),
+            email: "alice@example.com".to_string(),
+            password: "secret123".to_string(),
+            confirm_password: "secret123".to_string(),
+            noc_transfer: None,
+        }
+    }
+
+    // field yang gagal beserta pesannya, seperti yang dikirim ke client
+    fn failed_fields(input: &CreateUserRequest) -> Vec<(String, Vec<String>)> {
+        let errors = input.validate().unwrap_err();
+        let mut fields: Vec<_> = ErrorResponse::from(AppError::ValidationError(errors))
+            .errors
+            .unwrap()
+            .into_iter()
+            .collect();
+        fields.sort();
+        fields
+    }
+
+    #[test]
+    fn valid_user_passes() {
+        assert!(create_user().validate().is_ok());
+    }
+
+    #[test]
+    fn bad_email_is_rejected() {
+        let input = CreateUserRequest {
+            email: "alice.example.com".to_string(),
+            ..create_user()
+        };
+
+        assert_eq!(
+            failed_fields(&input),
+            [(
+                "email".to_string(),
+                vec!["Invalid email format".to_string()]
+            )]
+        );
+    }
+
+    #[test]
+    fn empty_or_blank_name_is_rejected() {
+        for name in ["", "   "] {
+            let input = CreateUserRequest {
+                firstname: name.to_string(),
+                ..create_user()
+            };
+
+            assert_eq!(
+                failed_fields(&input),
+                [(
+                    "firstname".to_string(),
+                    vec!["First name is required".to_string()]
+                )]
+            );
+        }
+    }
+
+    #[test]
+    fn short_password_is_rejected() {
+        let input = CreateUserRequest {
+            password: "abc".to_string(),
+            confirm_password: "abc".to_string(),
+            ..create_user()
+        };
+
+        assert_eq!(
+            failed_fields(&input),
+            [
+                (
+                    "confirm_password".to_string(),
+                    vec!["Confirm password must be at least 6 characters".to_string()]
+                ),
+                (
+                    "password".to_string(),
+                    vec!["Password must be at least 6 characters".to_string()]
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn blank_name_update_is_rejected() {
+        let input = UpdateUserRequest {
+            id: 1,
+            firstname: Some(" ".to_string()),
+            lastname: None,
+            email: None,
+            password: None,
+            confirm_password: None,
+        };
+
+        assert!(input.validate().is_err());
+    }
 }
@@ -0,0 +1,29 @@
+use crate::domain::request::pagination::default_page_size;
+use serde::{Deserialize, Serialize};
+use utoipa::IntoParams;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CreateVirtualCardRequest {
+    pub user_id: i32,
+    pub card_number: String,
+    pub expiry_month: i32,
+    pub expiry_year: i32,
+}
+
+// kartu yang sudah dicabut hanya ikut tampil kalau include_revoked diisi
+#[derive(Serialize, Deserialize, Clone, Copy, Debug, IntoParams)]
+pub struct FindVirtualCardsRequest {
+    #[serde(default = "default_page")]
+    pub page: i32,
+
+    #[serde(default = "default_page_size")]
+    #[param(default = default_page_size, minimum = 1)]
+    pub page_size: i32,
+
+    #[serde(default)]
+    pub include_revoked: bool,
+}
+
+fn default_page() -> i32 {
+    1
+}
@@ -1,3 +1,4 @@
+use crate::domain::request::pagination::default_page_size;
 use serde::{Deserialize, Serialize};
 use utoipa::{IntoParams, ToSchema};
 use validator::Validate;
@@ -8,6 +9,7 @@ pub struct FindAllWithdrawRequest {
     pub page: i32,
 
     #[serde(default = "default_page_size")]
+    #[param(default = default_page_size, minimum = 1)]
     pub page_size: i32,
 
     #[serde(default)]
@@ -18,10 +20,6 @@ fn default_page() -> i32 {
     1
 }
 
-fn default_page_size() -> i32 {
-    10
-}
-
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize, ToSchema, Validate)]
 pub struct CreateWithdrawRequest {
     #[validate(range(min = 1, message = "User ID must be positive"))]
@@ -0,0 +1,65 @@
+use crate::domain::response::{
+    saldo::SaldoResponse, topup::TopupResponse, transfer::TransferResponse,
+    withdraw::WithdrawResponse,
+};
+use genproto::account::AccountSummaryResponse as AccountSummaryResponseProto;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// saldo utama dan aktivitas terakhir user untuk halaman ringkasan dashboard
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct AccountSummaryResponse {
+    pub user_id: i32,
+    pub saldo: Option<SaldoResponse>,
+    pub recent_topups: Vec<TopupResponse>,
+    pub recent_transfers: Vec<TransferResponse>,
+    pub recent_withdraws: Vec<WithdrawResponse>,
+}
+
+impl AccountSummaryResponse {
+    pub fn truncated(mut self, limit: usize) -> Self {
+        self.recent_topups.truncate(limit);
+        self.recent_transfers.truncate(limit);
+        self.recent_withdraws.truncate(limit);
+        self
+    }
+}
+
+impl From<AccountSummaryResponseProto> for AccountSummaryResponse {
+    fn from(value: AccountSummaryResponseProto) -> Self {
+        AccountSummaryResponse {
+            user_id: value.user_id,
+            saldo: value.saldo.map(Into::into),
+            recent_topups: value.recent_topups.into_iter().map(Into::into).collect(),
+            recent_transfers: value.recent_transfers.into_iter().map(Into::into).collect(),
+            recent_withdraws: value.recent_withdraws.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<AccountSummaryResponse> for AccountSummaryResponseProto {
+    fn from(value: AccountSummaryResponse) -> Self {
+        AccountSummaryResponseProto {
+            user_id: value.user_id,
+            saldo: value.saldo.map(Into::into),
+            recent_topups: value.recent_topups.into_iter().map(Into::into).collect(),
+            recent_transfers: value.recent_transfers.into_iter().map(Into::into).collect(),
+            recent_withdraws: value.recent_withdraws.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<Option<AccountSummaryResponseProto>> for AccountSummaryResponse {
+    fn from(value: Option<AccountSummaryResponseProto>) -> Self {
+        match value {
+            Some(proto) => proto.into(),
+            None => AccountSummaryResponse {
+                user_id: 0,
+                saldo: None,
+                recent_topups: Vec::new(),
+                recent_transfers: Vec::new(),
+                recent_withdraws: Vec::new(),
+            },
+        }
+    }
+}
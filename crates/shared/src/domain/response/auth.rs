@@ -0,0 +1,90 @@
+use crate::{model::session::Session, utils::parse_datetime};
+use chrono::{DateTime, Utc};
+use genproto::auth::{
+    LoginResponse as LoginResponseProto, SessionResponse as SessionResponseProto,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Default)]
+pub struct LoginResponse {
+    pub access_token: String,
+    pub refresh_token: String,
+}
+
+impl From<LoginResponseProto> for LoginResponse {
+    fn from(value: LoginResponseProto) -> Self {
+        LoginResponse {
+            access_token: value.access_token,
+            refresh_token: value.refresh_token,
+        }
+    }
+}
+
+impl From<LoginResponse> for LoginResponseProto {
+    fn from(value: LoginResponse) -> Self {
+        LoginResponseProto {
+            access_token: value.access_token,
+            refresh_token: value.refresh_token,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct SessionResponse {
+    pub id: i32,
+    pub user_id: i32,
+    pub user_agent: Option<String>,
+    #[schema(format = "date-time")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[schema(format = "date-time")]
+    pub last_used_at: Option<DateTime<Utc>>,
+    #[schema(format = "date-time")]
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+impl From<Session> for SessionResponse {
+    fn from(value: Session) -> Self {
+        SessionResponse {
+            id: value.session_id,
+            user_id: value.user_id,
+            user_agent: value.user_agent,
+            created_at: value
+                .created_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            last_used_at: value
+                .last_used_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            expires_at: Some(DateTime::from_naive_utc_and_offset(value.expires_at, Utc)),
+        }
+    }
+}
+
+impl From<SessionResponseProto> for SessionResponse {
+    fn from(value: SessionResponseProto) -> Self {
+        SessionResponse {
+            id: value.session_id,
+            user_id: value.user_id,
+            user_agent: Some(value.user_agent).filter(|agent| !agent.is_empty()),
+            created_at: parse_datetime(&value.created_at),
+            last_used_at: parse_datetime(&value.last_used_at),
+            expires_at: parse_datetime(&value.expires_at),
+        }
+    }
+}
+
+impl From<SessionResponse> for SessionResponseProto {
+    fn from(value: SessionResponse) -> Self {
+        SessionResponseProto {
+            session_id: value.id,
+            user_id: value.user_id,
+            user_agent: value.user_agent.unwrap_or_default(),
+            created_at: value.created_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
+            last_used_at: value
+                .last_used_at
+                .map(|d| d.to_rfc3339())
+                .unwrap_or_default(),
+            expires_at: value.expires_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
+        }
+    }
+}
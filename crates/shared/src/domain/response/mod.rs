@@ -1,14 +1,29 @@
-use crate::{domain::response::pagination::Pagination, utils::AppError};
+use crate::{
+    domain::response::pagination::Pagination,
+    utils::{AppError, CLIENT_CLOSED_REQUEST, RETRY_AFTER_HEADER},
+};
+use axum::{
+    Json,
+    http::{HeaderValue, StatusCode},
+    response::{IntoResponse, Response},
+};
 use core::fmt;
 use serde::{Deserialize, Serialize};
-use std::fmt::Formatter;
+use std::{collections::HashMap, fmt::Formatter};
+use tonic::{Code, Status, metadata::MetadataValue};
 use utoipa::ToSchema;
+use validator::ValidationErrors;
 
+pub mod account;
+pub mod auth;
 pub mod pagination;
 pub mod saldo;
+pub mod system;
 pub mod topup;
 pub mod transfer;
 pub mod user;
+pub mod virtual_card;
+pub mod webhook;
 pub mod withdraw;
 
 #[derive(Debug, Serialize, Deserialize, ToSchema)]
@@ -49,10 +64,27 @@ impl<T: Serialize> fmt::Display for ApiResponsePagination<T> {
 pub struct ErrorResponse {
     pub status: String,
     pub message: String,
+    #[serde(skip)]
+    pub code: StatusCode,
+    // detik yang disarankan sebelum client mencoba lagi, dikirim sebagai header Retry-After
+    #[serde(skip)]
+    pub retry_after: Option<u64>,
+    // pesan validasi per field, hanya ada untuk error validasi
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub errors: Option<HashMap<String, Vec<String>>>,
 }
 
 impl From<AppError> for ErrorResponse {
     fn from(error: AppError) -> Self {
+        let code = error.status_code();
+        let retry_after = match error {
+            AppError::RateLimited(secs) => Some(secs),
+            _ => None,
+        };
+        let errors = match error {
+            AppError::ValidationError(ref errors) => Some(field_error_messages(errors)),
+            _ => None,
+        };
         let (status, message) = match error {
             AppError::SqlxError(_) => ("error".to_string(), "Database error occurred".to_string()),
             AppError::HashingError(_) => (
@@ -74,17 +106,229 @@ impl From<AppError> for ErrorResponse {
             AppError::EmailAlreadyExists => {
                 ("error".to_string(), "Email already exists".to_string())
             }
-            AppError::ValidationError(_) => ("error".to_string(), "Validation error".to_string()),
+            AppError::TopupNoAlreadyExists => (
+                "error".to_string(),
+                "Topup number already exists".to_string(),
+            ),
+            AppError::ValidationError(ref errors) => (
+                "error".to_string(),
+                format!("Validation error: {}", format_validation_errors(errors)),
+            ),
             AppError::InternalError(ref msg) => ("error".to_string(), msg.clone()),
 
             AppError::Custom(ref msg) => ("error".to_string(), msg.clone()),
+            AppError::RateLimited(secs) => (
+                "error".to_string(),
+                format!("Too many failed login attempts, retry after {secs}s"),
+            ),
+            AppError::Cancelled => (
+                "error".to_string(),
+                "Request cancelled by client".to_string(),
+            ),
+            AppError::DeadlineExceeded => {
+                ("error".to_string(), "Request deadline exceeded".to_string())
+            }
+            AppError::Upstream { ref message, .. } => ("error".to_string(), message.clone()),
+        };
+        ErrorResponse {
+            status,
+            message,
+            code,
+            retry_after,
+            errors,
+        }
+    }
+}
+
+// dari grpc status (client) ke response
+impl From<Status> for ErrorResponse {
+    fn from(status: Status) -> Self {
+        let retry_after = status
+            .metadata()
+            .get(RETRY_AFTER_HEADER)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+        let errors = serde_json::from_slice(status.details()).ok();
+
+        ErrorResponse {
+            retry_after,
+            errors,
+            ..ErrorResponse::from(AppError::from(status))
+        }
+    }
+}
+
+// dari response ke grpc status (server)
+impl From<ErrorResponse> for Status {
+    fn from(error: ErrorResponse) -> Self {
+        let code = match error.code {
+            StatusCode::NOT_FOUND => Code::NotFound,
+            StatusCode::UNAUTHORIZED => Code::Unauthenticated,
+            StatusCode::FORBIDDEN => Code::PermissionDenied,
+            StatusCode::CONFLICT => Code::AlreadyExists,
+            StatusCode::UNPROCESSABLE_ENTITY => Code::InvalidArgument,
+            StatusCode::BAD_REQUEST => Code::FailedPrecondition,
+            StatusCode::TOO_MANY_REQUESTS => Code::ResourceExhausted,
+            StatusCode::SERVICE_UNAVAILABLE => Code::Unavailable,
+            StatusCode::NOT_IMPLEMENTED => Code::Unimplemented,
+            StatusCode::GATEWAY_TIMEOUT => Code::DeadlineExceeded,
+            code if code.as_u16() == CLIENT_CLOSED_REQUEST => Code::Cancelled,
+            _ => Code::Internal,
+        };
+
+        // pesan per field dibawa sebagai JSON di details agar sampai ke REST gateway
+        let mut status = match error
+            .errors
+            .and_then(|errors| serde_json::to_vec(&errors).ok())
+        {
+            Some(details) => Status::with_details(code, error.message, details.into()),
+            None => Status::new(code, error.message),
         };
-        ErrorResponse { status, message }
+        if let Some(secs) = error.retry_after {
+            status
+                .metadata_mut()
+                .insert(RETRY_AFTER_HEADER, MetadataValue::from(secs));
+        }
+
+        status
+    }
+}
+
+impl ErrorResponse {
+    pub fn kind(&self) -> &'static str {
+        match self.code {
+            StatusCode::NOT_FOUND => "not_found",
+            StatusCode::UNAUTHORIZED => "unauthenticated",
+            StatusCode::FORBIDDEN => "permission_denied",
+            StatusCode::CONFLICT => "already_exists",
+            StatusCode::UNPROCESSABLE_ENTITY => "validation_error",
+            StatusCode::BAD_REQUEST => "bad_request",
+            StatusCode::TOO_MANY_REQUESTS => "resource_exhausted",
+            StatusCode::SERVICE_UNAVAILABLE => "unavailable",
+            StatusCode::NOT_IMPLEMENTED => "unimplemented",
+            StatusCode::GATEWAY_TIMEOUT => "deadline_exceeded",
+            code if code.as_u16() == CLIENT_CLOSED_REQUEST => "cancelled",
+            _ => "internal_error",
+        }
+    }
+}
+
+impl IntoResponse for ErrorResponse {
+    fn into_response(self) -> Response {
+        let retry_after = self.retry_after;
+        let mut response = (self.code, Json(self)).into_response();
+
+        if let Some(secs) = retry_after {
+            response
+                .headers_mut()
+                .insert(RETRY_AFTER_HEADER, HeaderValue::from(secs));
+        }
+
+        response
     }
 }
 
+fn field_error_messages(errors: &ValidationErrors) -> HashMap<String, Vec<String>> {
+    errors
+        .field_errors()
+        .into_iter()
+        .map(|(field, field_errors)| {
+            let messages = field_errors
+                .iter()
+                .map(|e| {
+                    e.message
+                        .as_ref()
+                        .map(|m| m.to_string())
+                        .unwrap_or_else(|| e.code.to_string())
+                })
+                .collect();
+
+            (field.to_string(), messages)
+        })
+        .collect()
+}
+
+fn format_validation_errors(errors: &ValidationErrors) -> String {
+    let mut fields = field_error_messages(errors)
+        .into_iter()
+        .map(|(field, messages)| format!("{field}: {}", messages.join(", ")))
+        .collect::<Vec<_>>();
+
+    fields.sort();
+    fields.join("; ")
+}
+
 impl fmt::Display for ErrorResponse {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "Status: {}, Message: {}", self.status, self.message)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use validator::Validate;
+
+    #[derive(Validate)]
+    struct Signup {
+        #[validate(email(message = "Invalid email format"))]
+        email: String,
+        #[validate(length(min = 6, message = "Password must be at least 6 characters"))]
+        password: String,
+        #[validate(range(min = 1))]
+        age: i32,
+    }
+
+    fn invalid_signup() -> ErrorResponse {
+        let errors = Signup {
+            email: "not-an-email".to_string(),
+            password: "123".to_string(),
+            age: 0,
+        }
+        .validate()
+        .unwrap_err();
+
+        ErrorResponse::from(AppError::ValidationError(errors))
+    }
+
+    #[test]
+    fn validation_error_carries_messages_per_field() {
+        let response = invalid_signup();
+
+        assert_eq!(response.code, StatusCode::UNPROCESSABLE_ENTITY);
+        let errors = response.errors.as_ref().unwrap();
+        assert_eq!(errors["email"], ["Invalid email format"]);
+        assert_eq!(
+            errors["password"],
+            ["Password must be at least 6 characters"]
+        );
+        // tanpa pesan khusus yang dipakai kode validatornya
+        assert_eq!(errors["age"], ["range"]);
+
+        let body = serde_json::to_value(&response).unwrap();
+        assert_eq!(body["errors"]["email"][0], "Invalid email format");
+    }
+
+    #[test]
+    fn other_errors_omit_the_errors_field() {
+        let response = ErrorResponse::from(AppError::NotFound("User not found".to_string()));
+
+        assert!(response.errors.is_none());
+        let body = serde_json::to_value(&response).unwrap();
+        assert_eq!(
+            body,
+            serde_json::json!({ "status": "error", "message": "User not found" })
+        );
+    }
+
+    #[test]
+    fn field_errors_survive_the_grpc_round_trip() {
+        let sent = invalid_signup();
+        let expected = sent.errors.clone();
+
+        let received = ErrorResponse::from(Status::from(sent));
+
+        assert_eq!(received.code, StatusCode::UNPROCESSABLE_ENTITY);
+        assert_eq!(received.errors, expected);
+    }
+}
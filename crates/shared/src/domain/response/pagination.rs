@@ -8,6 +8,26 @@ pub struct Pagination {
     pub page_size: i32,
     pub total_items: i64,
     pub total_pages: i32,
+    #[serde(default)]
+    pub has_next: bool,
+    #[serde(default)]
+    pub has_prev: bool,
+}
+
+impl Pagination {
+    pub fn new(page: i32, page_size: i32, total_items: i64) -> Self {
+        let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
+        let has_items = total_items > 0;
+
+        Self {
+            page,
+            page_size,
+            total_items,
+            total_pages,
+            has_next: has_items && page < total_pages,
+            has_prev: has_items && page > 1,
+        }
+    }
 }
 
 impl From<ProtoPagination> for Pagination {
@@ -17,6 +37,8 @@ impl From<ProtoPagination> for Pagination {
             page_size: value.page_size,
             total_items: value.total_items,
             total_pages: value.total_pages,
+            has_next: value.has_next,
+            has_prev: value.has_prev,
         }
     }
 }
@@ -28,6 +50,47 @@ impl From<Pagination> for ProtoPagination {
             page_size: value.page_size,
             total_items: value.total_items,
             total_pages: value.total_pages,
+            has_next: value.has_next,
+            has_prev: value.has_prev,
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn navigation(page: i32, total_items: i64) -> (bool, bool) {
+        let pagination = Pagination::new(page, 10, total_items);
+        (pagination.has_prev, pagination.has_next)
+    }
+
+    #[test]
+    fn first_page_has_only_next() {
+        assert_eq!(navigation(1, 25), (false, true));
+    }
+
+    #[test]
+    fn middle_page_has_both() {
+        assert_eq!(navigation(2, 25), (true, true));
+    }
+
+    #[test]
+    fn last_page_has_only_prev() {
+        assert_eq!(navigation(3, 25), (true, false));
+        assert_eq!(Pagination::new(3, 10, 25).total_pages, 3);
+    }
+
+    #[test]
+    fn single_full_page_has_neither() {
+        assert_eq!(navigation(1, 10), (false, false));
+    }
+
+    #[test]
+    fn empty_result_has_neither() {
+        assert_eq!(navigation(1, 0), (false, false));
+        // halaman di luar jangkauan pada hasil kosong tetap tanpa navigasi
+        assert_eq!(navigation(4, 0), (false, false));
+        assert_eq!(Pagination::new(1, 10, 0).total_pages, 0);
+    }
+}
@@ -1,6 +1,12 @@
-use crate::{model::saldo::Saldo, utils::parse_datetime};
+use crate::{
+    model::saldo::{BalanceReconciliation, Saldo, SaldoRanking},
+    utils::parse_datetime,
+};
 use chrono::{DateTime, Utc};
-use genproto::saldo::SaldoResponse as SaldoResponseProto;
+use genproto::saldo::{
+    BalanceReconciliationResponse as BalanceReconciliationResponseProto,
+    SaldoRankingResponse as SaldoRankingResponseProto, SaldoResponse as SaldoResponseProto,
+};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -16,6 +22,7 @@ pub struct SaldoResponse {
     pub created_at: Option<DateTime<Utc>>,
     #[schema(format = "date-time")]
     pub updated_at: Option<DateTime<Utc>>,
+    pub label: String,
 }
 
 impl From<Saldo> for SaldoResponse {
@@ -34,6 +41,7 @@ impl From<Saldo> for SaldoResponse {
             updated_at: value
                 .updated_at
                 .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            label: value.label,
         }
     }
 }
@@ -48,6 +56,7 @@ impl From<SaldoResponseProto> for SaldoResponse {
             withdraw_time: parse_datetime(&value.withdraw_time),
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
+            label: value.label,
         }
     }
 }
@@ -65,6 +74,7 @@ impl From<SaldoResponse> for SaldoResponseProto {
                 .unwrap_or_default(),
             created_at: value.created_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
             updated_at: value.updated_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
+            label: value.label,
         }
     }
 }
@@ -81,7 +91,104 @@ impl From<Option<SaldoResponseProto>> for SaldoResponse {
                 withdraw_time: None,
                 created_at: None,
                 updated_at: None,
+                label: String::new(),
             },
         }
     }
 }
+
+// rank dimulai dari 1 sesuai urutan leaderboard
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct SaldoRankingResponse {
+    pub rank: i32,
+    pub saldo_id: i32,
+    pub user_id: i32,
+    pub name: String,
+    pub label: String,
+    pub total_balance: i32,
+}
+
+impl SaldoRankingResponse {
+    pub fn new(rank: i32, value: SaldoRanking) -> Self {
+        SaldoRankingResponse {
+            rank,
+            saldo_id: value.saldo_id,
+            user_id: value.user_id,
+            name: format!("{} {}", value.firstname, value.lastname),
+            label: value.label,
+            total_balance: value.total_balance,
+        }
+    }
+}
+
+impl From<SaldoRankingResponseProto> for SaldoRankingResponse {
+    fn from(value: SaldoRankingResponseProto) -> Self {
+        SaldoRankingResponse {
+            rank: value.rank,
+            saldo_id: value.saldo_id,
+            user_id: value.user_id,
+            name: value.name,
+            label: value.label,
+            total_balance: value.total_balance,
+        }
+    }
+}
+
+impl From<SaldoRankingResponse> for SaldoRankingResponseProto {
+    fn from(value: SaldoRankingResponse) -> Self {
+        SaldoRankingResponseProto {
+            rank: value.rank,
+            saldo_id: value.saldo_id,
+            user_id: value.user_id,
+            name: value.name,
+            label: value.label,
+            total_balance: value.total_balance,
+        }
+    }
+}
+
+// difference = expected_balance - stored_balance; corrected menandakan selisih sudah dikoreksi
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct BalanceReconciliationResponse {
+    pub user_id: i32,
+    pub stored_balance: i64,
+    pub expected_balance: i64,
+    pub difference: i64,
+    pub corrected: bool,
+}
+
+impl BalanceReconciliationResponse {
+    pub fn new(value: &BalanceReconciliation, corrected: bool) -> Self {
+        BalanceReconciliationResponse {
+            user_id: value.user_id,
+            stored_balance: value.stored_balance,
+            expected_balance: value.expected_balance,
+            difference: value.difference(),
+            corrected,
+        }
+    }
+}
+
+impl From<BalanceReconciliationResponseProto> for BalanceReconciliationResponse {
+    fn from(value: BalanceReconciliationResponseProto) -> Self {
+        BalanceReconciliationResponse {
+            user_id: value.user_id,
+            stored_balance: value.stored_balance,
+            expected_balance: value.expected_balance,
+            difference: value.difference,
+            corrected: value.corrected,
+        }
+    }
+}
+
+impl From<BalanceReconciliationResponse> for BalanceReconciliationResponseProto {
+    fn from(value: BalanceReconciliationResponse) -> Self {
+        BalanceReconciliationResponseProto {
+            user_id: value.user_id,
+            stored_balance: value.stored_balance,
+            expected_balance: value.expected_balance,
+            difference: value.difference,
+            corrected: value.corrected,
+        }
+    }
+}
@@ -0,0 +1,371 @@
+use crate::config::{CacheTtlConfig, Config, RateLimit};
+use genproto::system::{
+    CacheFlushResponse as CacheFlushResponseProto, CachePrefixCount as CachePrefixCountProto,
+    CacheStatsResponse as CacheStatsResponseProto, CacheTtlSettings as CacheTtlSettingsProto,
+    FeatureToggles as FeatureTogglesProto, LimitSettings as LimitSettingsProto,
+    PoolSettings as PoolSettingsProto, RateLimitSettings as RateLimitSettingsProto,
+    RuntimeConfigResponse as RuntimeConfigResponseProto,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use utoipa::ToSchema;
+
+// hanya konfigurasi yang aman dibaca admin: secret JWT, DATABASE_URL, dan URL/secret webhook
+// sengaja tidak punya field di sini sehingga tidak mungkin ikut terkirim
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Default)]
+pub struct RuntimeConfigResponse {
+    pub pool: PoolSettings,
+    pub cache_ttl: CacheTtlSettings,
+    pub features: FeatureToggles,
+    pub limits: LimitSettings,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Default)]
+pub struct PoolSettings {
+    pub max_connections: i32,
+    pub min_connections: i32,
+    pub acquire_timeout_secs: i64,
+    pub idle_timeout_secs: i64,
+    pub sql_max_retries: i32,
+    pub sql_retry_backoff_ms: i64,
+    pub grpc_pool_size: i32,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Default)]
+pub struct CacheTtlSettings {
+    pub auth_secs: i64,
+    pub user_secs: i64,
+    pub saldo_secs: i64,
+    pub saldo_soft_secs: i64,
+    pub topup_secs: i64,
+    pub transfer_secs: i64,
+    pub withdraw_secs: i64,
+    pub stats_secs: i64,
+    pub account_summary_secs: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Default)]
+pub struct FeatureToggles {
+    pub run_migrations: bool,
+    pub grpc_web_enabled: bool,
+    pub grpc_json_enabled: bool,
+    pub webhook_enabled: bool,
+    pub read_replica_enabled: bool,
+    pub hash_algorithm: String,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Default)]
+pub struct RateLimitSettings {
+    pub capacity: i32,
+    pub period_secs: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Default)]
+pub struct LimitSettings {
+    pub max_page_size: i32,
+    pub default_page_size: i32,
+    pub shutdown_timeout_secs: i64,
+    pub min_transfer_amount: i32,
+    pub max_concurrent_requests: i64,
+    pub grpc_max_decoding_size: i64,
+    pub grpc_max_encoding_size: i64,
+    pub grpc_request_timeout_secs: i64,
+    pub shutdown_grace_secs: i64,
+    pub transfer_rate_limit: RateLimitSettings,
+    pub withdraw_rate_limit: RateLimitSettings,
+    pub topup_rate_limit: RateLimitSettings,
+    pub default_rate_limit: RateLimitSettings,
+}
+
+impl From<&Config> for RuntimeConfigResponse {
+    fn from(config: &Config) -> Self {
+        RuntimeConfigResponse {
+            pool: PoolSettings {
+                max_connections: config.max_connections as i32,
+                min_connections: config.min_connections as i32,
+                acquire_timeout_secs: config.acquire_timeout_secs as i64,
+                idle_timeout_secs: config.idle_timeout_secs as i64,
+                sql_max_retries: config.db_retry_max_retries as i32,
+                sql_retry_backoff_ms: config.db_retry_backoff_ms as i64,
+                grpc_pool_size: config.grpc_pool_size as i32,
+            },
+            cache_ttl: CacheTtlSettings::from(config.cache_ttl),
+            features: FeatureToggles {
+                run_migrations: config.run_migrations,
+                grpc_web_enabled: config.grpc_web_enabled,
+                grpc_json_enabled: config.grpc_json_addr.is_some(),
+                webhook_enabled: config.webhook.url.is_some(),
+                read_replica_enabled: config.database_read_url.is_some(),
+                hash_algorithm: config.hash_algorithm.as_str().to_string(),
+            },
+            limits: LimitSettings {
+                max_page_size: config.max_page_size,
+                default_page_size: config.default_page_size,
+                shutdown_timeout_secs: config.shutdown_timeout_secs as i64,
+                min_transfer_amount: config.min_transfer_amount,
+                max_concurrent_requests: config.max_concurrent_requests as i64,
+                grpc_max_decoding_size: config.grpc_max_decoding_size as i64,
+                grpc_max_encoding_size: config.grpc_max_encoding_size as i64,
+                grpc_request_timeout_secs: config.grpc_request_timeout_secs as i64,
+                shutdown_grace_secs: config.shutdown_grace_secs as i64,
+                transfer_rate_limit: config.write_rate_limit.transfer.into(),
+                withdraw_rate_limit: config.write_rate_limit.withdraw.into(),
+                topup_rate_limit: config.write_rate_limit.topup.into(),
+                default_rate_limit: config.write_rate_limit.default.into(),
+            },
+        }
+    }
+}
+
+impl From<CacheTtlConfig> for CacheTtlSettings {
+    fn from(ttl: CacheTtlConfig) -> Self {
+        CacheTtlSettings {
+            auth_secs: ttl.auth.as_secs() as i64,
+            user_secs: ttl.user.as_secs() as i64,
+            saldo_secs: ttl.saldo.as_secs() as i64,
+            saldo_soft_secs: ttl.saldo_soft.as_secs() as i64,
+            topup_secs: ttl.topup.as_secs() as i64,
+            transfer_secs: ttl.transfer.as_secs() as i64,
+            withdraw_secs: ttl.withdraw.as_secs() as i64,
+            stats_secs: ttl.stats.as_secs() as i64,
+            account_summary_secs: ttl.account_summary.as_secs() as i64,
+        }
+    }
+}
+
+impl From<RateLimit> for RateLimitSettings {
+    fn from(limit: RateLimit) -> Self {
+        RateLimitSettings {
+            capacity: limit.capacity as i32,
+            period_secs: limit.period.as_secs() as i64,
+        }
+    }
+}
+
+impl From<RuntimeConfigResponse> for RuntimeConfigResponseProto {
+    fn from(value: RuntimeConfigResponse) -> Self {
+        let pool = value.pool;
+        let ttl = value.cache_ttl;
+        let features = value.features;
+        let limits = value.limits;
+
+        RuntimeConfigResponseProto {
+            pool: Some(PoolSettingsProto {
+                max_connections: pool.max_connections,
+                min_connections: pool.min_connections,
+                acquire_timeout_secs: pool.acquire_timeout_secs,
+                idle_timeout_secs: pool.idle_timeout_secs,
+                sql_max_retries: pool.sql_max_retries,
+                sql_retry_backoff_ms: pool.sql_retry_backoff_ms,
+                grpc_pool_size: pool.grpc_pool_size,
+            }),
+            cache_ttl: Some(CacheTtlSettingsProto {
+                auth_secs: ttl.auth_secs,
+                user_secs: ttl.user_secs,
+                saldo_secs: ttl.saldo_secs,
+                saldo_soft_secs: ttl.saldo_soft_secs,
+                topup_secs: ttl.topup_secs,
+                transfer_secs: ttl.transfer_secs,
+                withdraw_secs: ttl.withdraw_secs,
+                stats_secs: ttl.stats_secs,
+                account_summary_secs: ttl.account_summary_secs,
+            }),
+            features: Some(FeatureTogglesProto {
+                run_migrations: features.run_migrations,
+                grpc_web_enabled: features.grpc_web_enabled,
+                grpc_json_enabled: features.grpc_json_enabled,
+                webhook_enabled: features.webhook_enabled,
+                read_replica_enabled: features.read_replica_enabled,
+                hash_algorithm: features.hash_algorithm,
+            }),
+            limits: Some(LimitSettingsProto {
+                max_page_size: limits.max_page_size,
+                default_page_size: limits.default_page_size,
+                shutdown_timeout_secs: limits.shutdown_timeout_secs,
+                min_transfer_amount: limits.min_transfer_amount,
+                max_concurrent_requests: limits.max_concurrent_requests,
+                grpc_max_decoding_size: limits.grpc_max_decoding_size,
+                grpc_max_encoding_size: limits.grpc_max_encoding_size,
+                grpc_request_timeout_secs: limits.grpc_request_timeout_secs,
+                shutdown_grace_secs: limits.shutdown_grace_secs,
+                transfer_rate_limit: Some(limits.transfer_rate_limit.into()),
+                withdraw_rate_limit: Some(limits.withdraw_rate_limit.into()),
+                topup_rate_limit: Some(limits.topup_rate_limit.into()),
+                default_rate_limit: Some(limits.default_rate_limit.into()),
+            }),
+        }
+    }
+}
+
+impl From<RateLimitSettings> for RateLimitSettingsProto {
+    fn from(value: RateLimitSettings) -> Self {
+        RateLimitSettingsProto {
+            capacity: value.capacity,
+            period_secs: value.period_secs,
+        }
+    }
+}
+
+impl From<RuntimeConfigResponseProto> for RuntimeConfigResponse {
+    fn from(value: RuntimeConfigResponseProto) -> Self {
+        let pool = value.pool.unwrap_or_default();
+        let ttl = value.cache_ttl.unwrap_or_default();
+        let features = value.features.unwrap_or_default();
+        let limits = value.limits.unwrap_or_default();
+
+        RuntimeConfigResponse {
+            pool: PoolSettings {
+                max_connections: pool.max_connections,
+                min_connections: pool.min_connections,
+                acquire_timeout_secs: pool.acquire_timeout_secs,
+                idle_timeout_secs: pool.idle_timeout_secs,
+                sql_max_retries: pool.sql_max_retries,
+                sql_retry_backoff_ms: pool.sql_retry_backoff_ms,
+                grpc_pool_size: pool.grpc_pool_size,
+            },
+            cache_ttl: CacheTtlSettings {
+                auth_secs: ttl.auth_secs,
+                user_secs: ttl.user_secs,
+                saldo_secs: ttl.saldo_secs,
+                saldo_soft_secs: ttl.saldo_soft_secs,
+                topup_secs: ttl.topup_secs,
+                transfer_secs: ttl.transfer_secs,
+                withdraw_secs: ttl.withdraw_secs,
+                stats_secs: ttl.stats_secs,
+                account_summary_secs: ttl.account_summary_secs,
+            },
+            features: FeatureToggles {
+                run_migrations: features.run_migrations,
+                grpc_web_enabled: features.grpc_web_enabled,
+                grpc_json_enabled: features.grpc_json_enabled,
+                webhook_enabled: features.webhook_enabled,
+                read_replica_enabled: features.read_replica_enabled,
+                hash_algorithm: features.hash_algorithm,
+            },
+            limits: LimitSettings {
+                max_page_size: limits.max_page_size,
+                default_page_size: limits.default_page_size,
+                shutdown_timeout_secs: limits.shutdown_timeout_secs,
+                min_transfer_amount: limits.min_transfer_amount,
+                max_concurrent_requests: limits.max_concurrent_requests,
+                grpc_max_decoding_size: limits.grpc_max_decoding_size,
+                grpc_max_encoding_size: limits.grpc_max_encoding_size,
+                grpc_request_timeout_secs: limits.grpc_request_timeout_secs,
+                shutdown_grace_secs: limits.shutdown_grace_secs,
+                transfer_rate_limit: limits.transfer_rate_limit.unwrap_or_default().into(),
+                withdraw_rate_limit: limits.withdraw_rate_limit.unwrap_or_default().into(),
+                topup_rate_limit: limits.topup_rate_limit.unwrap_or_default().into(),
+                default_rate_limit: limits.default_rate_limit.unwrap_or_default().into(),
+            },
+        }
+    }
+}
+
+impl From<RateLimitSettingsProto> for RateLimitSettings {
+    fn from(value: RateLimitSettingsProto) -> Self {
+        RateLimitSettings {
+            capacity: value.capacity,
+            period_secs: value.period_secs,
+        }
+    }
+}
+
+impl From<Option<RuntimeConfigResponseProto>> for RuntimeConfigResponse {
+    fn from(value: Option<RuntimeConfigResponseProto>) -> Self {
+        value.unwrap_or_default().into()
+    }
+}
+
+// hitungan dari SCAN bersifat perkiraan: key yang dibuat atau kedaluwarsa selama pemindaian
+// bisa terlewat atau terhitung dua kali
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Default)]
+pub struct CacheStatsResponse {
+    pub total_keys: i64,
+    pub prefixes: Vec<CachePrefixCount>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Default)]
+pub struct CachePrefixCount {
+    pub prefix: String,
+    pub key_count: i64,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Default)]
+pub struct CacheFlushResponse {
+    pub prefix: String,
+    pub removed: i64,
+}
+
+impl From<BTreeMap<String, u64>> for CacheStatsResponse {
+    fn from(counts: BTreeMap<String, u64>) -> Self {
+        let prefixes: Vec<CachePrefixCount> = counts
+            .into_iter()
+            .map(|(prefix, count)| CachePrefixCount {
+                prefix,
+                key_count: count as i64,
+            })
+            .collect();
+
+        CacheStatsResponse {
+            total_keys: prefixes.iter().map(|p| p.key_count).sum(),
+            prefixes,
+        }
+    }
+}
+
+impl From<CacheStatsResponse> for CacheStatsResponseProto {
+    fn from(value: CacheStatsResponse) -> Self {
+        CacheStatsResponseProto {
+            total_keys: value.total_keys,
+            prefixes: value
+                .prefixes
+                .into_iter()
+                .map(|p| CachePrefixCountProto {
+                    prefix: p.prefix,
+                    key_count: p.key_count,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<CacheStatsResponseProto> for CacheStatsResponse {
+    fn from(value: CacheStatsResponseProto) -> Self {
+        CacheStatsResponse {
+            total_keys: value.total_keys,
+            prefixes: value
+                .prefixes
+                .into_iter()
+                .map(|p| CachePrefixCount {
+                    prefix: p.prefix,
+                    key_count: p.key_count,
+                })
+                .collect(),
+        }
+    }
+}
+
+impl From<Option<CacheStatsResponseProto>> for CacheStatsResponse {
+    fn from(value: Option<CacheStatsResponseProto>) -> Self {
+        value.unwrap_or_default().into()
+    }
+}
+
+impl From<CacheFlushResponse> for CacheFlushResponseProto {
+    fn from(value: CacheFlushResponse) -> Self {
+        CacheFlushResponseProto {
+            prefix: value.prefix,
+            removed: value.removed,
+        }
+    }
+}
+
+impl From<Option<CacheFlushResponseProto>> for CacheFlushResponse {
+    fn from(value: Option<CacheFlushResponseProto>) -> Self {
+        let value = value.unwrap_or_default();
+        CacheFlushResponse {
+            prefix: value.prefix,
+            removed: value.removed,
+        }
+    }
+}
@@ -1,6 +1,11 @@
-use crate::{model::topup::Topup, utils::parse_datetime};
+use crate::{
+    model::topup::{Topup, TopupMethodStats},
+    utils::parse_datetime,
+};
 use chrono::{DateTime, Utc};
-use genproto::topup::TopupResponse as TopupResponseProto;
+use genproto::topup::{
+    TopupMethodStats as TopupMethodStatsProto, TopupResponse as TopupResponseProto,
+};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -16,6 +21,7 @@ pub struct TopupResponse {
     pub created_at: Option<DateTime<Utc>>,
     #[schema(format = "date-time")]
     pub updated_at: Option<DateTime<Utc>>,
+    pub status: String,
 }
 
 // dari model ke response
@@ -34,6 +40,7 @@ impl From<Topup> for TopupResponse {
             updated_at: value
                 .updated_at
                 .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            status: value.status,
         }
     }
 }
@@ -52,6 +59,7 @@ impl From<TopupResponseProto> for TopupResponse {
                 .with_timezone(&Utc),
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
+            status: value.status,
         }
     }
 }
@@ -68,6 +76,7 @@ impl From<TopupResponse> for TopupResponseProto {
             topup_time: value.topup_time.to_rfc3339(),
             created_at: value.created_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
             updated_at: value.updated_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
+            status: value.status,
         }
     }
 }
@@ -86,7 +95,48 @@ impl From<Option<TopupResponseProto>> for TopupResponse {
                 topup_time: Utc::now(),
                 created_at: None,
                 updated_at: None,
+                status: String::new(),
             },
         }
     }
 }
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct TopupMethodStatsResponse {
+    pub topup_method: String,
+    pub total_count: i64,
+    pub total_amount: i64,
+}
+
+// dari model ke response
+impl From<TopupMethodStats> for TopupMethodStatsResponse {
+    fn from(value: TopupMethodStats) -> Self {
+        TopupMethodStatsResponse {
+            topup_method: value.topup_method,
+            total_count: value.total_count,
+            total_amount: value.total_amount,
+        }
+    }
+}
+
+// dari response ke proto
+impl From<TopupMethodStatsResponse> for TopupMethodStatsProto {
+    fn from(value: TopupMethodStatsResponse) -> Self {
+        TopupMethodStatsProto {
+            topup_method: value.topup_method,
+            total_count: value.total_count,
+            total_amount: value.total_amount,
+        }
+    }
+}
+
+// dari proto ke response
+impl From<TopupMethodStatsProto> for TopupMethodStatsResponse {
+    fn from(value: TopupMethodStatsProto) -> Self {
+        TopupMethodStatsResponse {
+            topup_method: value.topup_method,
+            total_count: value.total_count,
+            total_amount: value.total_amount,
+        }
+    }
+}
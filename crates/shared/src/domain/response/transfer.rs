@@ -1,6 +1,11 @@
-use crate::{model::transfer::Transfer, utils::parse_datetime};
-use chrono::{DateTime, Utc};
-use genproto::transfer::TransferResponse as TransferResponseProto;
+use crate::{
+    model::transfer::{DailyTransferTotal, Transfer, TransferPreview},
+    utils::parse_datetime,
+};
+use chrono::{DateTime, NaiveDate, Utc};
+use genproto::transfer::{
+    DailyTransferTotal as DailyTransferTotalProto, TransferResponse as TransferResponseProto,
+};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -17,6 +22,39 @@ pub struct TransferResponse {
 
     #[schema(format = "date-time")]
     pub updated_at: Option<DateTime<Utc>>,
+
+    pub note: Option<String>,
+
+    pub status: String,
+
+    pub from_label: String,
+
+    pub to_label: String,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projected_sender_balance: Option<i32>,
+
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub projected_receiver_balance: Option<i32>,
+
+    // "in" atau "out" dilihat dari user pada riwayat transfer
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub direction: Option<String>,
+}
+
+impl TransferResponse {
+    pub fn for_user(transfer: Transfer, user_id: i32) -> Self {
+        let direction = if transfer.transfer_from == user_id {
+            "out"
+        } else {
+            "in"
+        };
+
+        TransferResponse {
+            direction: Some(direction.to_string()),
+            ..TransferResponse::from(transfer)
+        }
+    }
 }
 
 impl From<Transfer> for TransferResponse {
@@ -33,6 +71,23 @@ impl From<Transfer> for TransferResponse {
             updated_at: value
                 .updated_at
                 .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            note: value.note,
+            status: value.status,
+            from_label: value.from_label,
+            to_label: value.to_label,
+            projected_sender_balance: None,
+            projected_receiver_balance: None,
+            direction: None,
+        }
+    }
+}
+
+impl From<TransferPreview> for TransferResponse {
+    fn from(value: TransferPreview) -> Self {
+        TransferResponse {
+            projected_sender_balance: Some(value.sender_balance),
+            projected_receiver_balance: Some(value.receiver_balance),
+            ..TransferResponse::from(value.transfer)
         }
     }
 }
@@ -49,6 +104,13 @@ impl From<TransferResponseProto> for TransferResponse {
                 .with_timezone(&Utc),
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
+            note: (!value.note.is_empty()).then_some(value.note),
+            status: value.status,
+            from_label: value.from_label,
+            to_label: value.to_label,
+            projected_sender_balance: value.projected_sender_balance,
+            projected_receiver_balance: value.projected_receiver_balance,
+            direction: value.direction,
         }
     }
 }
@@ -69,6 +131,13 @@ impl From<TransferResponse> for TransferResponseProto {
                 .updated_at
                 .map(|dt| dt.to_rfc3339())
                 .unwrap_or_default(),
+            note: value.note.unwrap_or_default(),
+            status: value.status,
+            from_label: value.from_label,
+            to_label: value.to_label,
+            projected_sender_balance: value.projected_sender_balance,
+            projected_receiver_balance: value.projected_receiver_balance,
+            direction: value.direction,
         }
     }
 }
@@ -85,7 +154,58 @@ impl From<Option<TransferResponseProto>> for TransferResponse {
                 transfer_time: Utc::now(),
                 created_at: None,
                 updated_at: None,
+                note: None,
+                status: String::new(),
+                from_label: String::new(),
+                to_label: String::new(),
+                projected_sender_balance: None,
+                projected_receiver_balance: None,
+                direction: None,
             },
         }
     }
 }
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct DailyTransferTotalResponse {
+    pub user_id: i32,
+    pub date: NaiveDate,
+    pub total_sent: i64,
+    pub total_received: i64,
+}
+
+// dari model ke response
+impl From<DailyTransferTotal> for DailyTransferTotalResponse {
+    fn from(value: DailyTransferTotal) -> Self {
+        DailyTransferTotalResponse {
+            user_id: value.user_id,
+            date: value.day,
+            total_sent: value.total_sent,
+            total_received: value.total_received,
+        }
+    }
+}
+
+// dari response ke proto
+impl From<DailyTransferTotalResponse> for DailyTransferTotalProto {
+    fn from(value: DailyTransferTotalResponse) -> Self {
+        DailyTransferTotalProto {
+            user_id: value.user_id,
+            date: value.date.to_string(),
+            total_sent: value.total_sent,
+            total_received: value.total_received,
+        }
+    }
+}
+
+// dari proto ke response
+impl From<DailyTransferTotalProto> for DailyTransferTotalResponse {
+    fn from(value: DailyTransferTotalProto) -> Self {
+        DailyTransferTotalResponse {
+            user_id: value.user_id,
+            date: NaiveDate::parse_from_str(&value.date, "%Y-%m-%d").unwrap_or_default(),
+            total_sent: value.total_sent,
+            total_received: value.total_received,
+        }
+    }
+}
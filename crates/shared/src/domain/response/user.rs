@@ -1,6 +1,13 @@
-use crate::{model::user::User, utils::parse_datetime};
+use crate::{
+    model::user::{User, UserDeleteBlockers, UserPurge},
+    utils::parse_datetime,
+};
 use chrono::{DateTime, Utc};
-use genproto::user::UserResponse as UserResponseProto;
+use genproto::user::{
+    ImportUserResult as ImportUserResultProto, ImportUsersResponse as ImportUsersResponseProto,
+    PurgeUserResponse as PurgeUserResponseProto,
+    UserDeletionCheckResponse as UserDeletionCheckResponseProto, UserResponse as UserResponseProto,
+};
 use serde::{Deserialize, Serialize};
 use utoipa::ToSchema;
 
@@ -16,6 +23,14 @@ pub struct UserResponse {
 
     #[schema(format = "date-time")]
     pub updated_at: Option<DateTime<Utc>>,
+
+    #[schema(format = "date-time")]
+    pub deleted_at: Option<DateTime<Utc>>,
+
+    #[schema(format = "date-time")]
+    pub password_changed_at: Option<DateTime<Utc>>,
+
+    pub status: String,
 }
 
 // dari database record ke response
@@ -33,6 +48,13 @@ impl From<User> for UserResponse {
             updated_at: value
                 .updated_at
                 .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            deleted_at: value
+                .deleted_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            password_changed_at: value
+                .password_changed_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            status: value.status,
         }
     }
 }
@@ -48,6 +70,9 @@ impl From<UserResponseProto> for UserResponse {
             noc_transfer: value.noc_transfer,
             created_at: parse_datetime(&value.created_at),
             updated_at: parse_datetime(&value.updated_at),
+            deleted_at: parse_datetime(&value.deleted_at),
+            password_changed_at: parse_datetime(&value.password_changed_at),
+            status: value.status,
         }
     }
 }
@@ -63,6 +88,12 @@ impl From<UserResponse> for UserResponseProto {
             noc_transfer: value.noc_transfer,
             created_at: value.created_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
             updated_at: value.updated_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
+            deleted_at: value.deleted_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
+            password_changed_at: value
+                .password_changed_at
+                .map(|d| d.to_rfc3339())
+                .unwrap_or_default(),
+            status: value.status,
         }
     }
 }
@@ -80,7 +111,208 @@ impl From<Option<UserResponseProto>> for UserResponse {
                 noc_transfer: "".to_string(),
                 created_at: None,
                 updated_at: None,
+                deleted_at: None,
+                password_changed_at: None,
+                status: "".to_string(),
             },
         }
     }
 }
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct PurgeUserResponse {
+    pub user_id: i32,
+    pub saldos: i64,
+    pub topups: i64,
+    pub transfers: i64,
+    pub withdraws: i64,
+}
+
+impl PurgeUserResponse {
+    pub fn new(user_id: i32, purge: UserPurge) -> Self {
+        PurgeUserResponse {
+            user_id,
+            saldos: purge.saldos,
+            topups: purge.topups,
+            transfers: purge.transfers,
+            withdraws: purge.withdraws,
+        }
+    }
+}
+
+impl From<PurgeUserResponseProto> for PurgeUserResponse {
+    fn from(value: PurgeUserResponseProto) -> Self {
+        PurgeUserResponse {
+            user_id: value.user_id,
+            saldos: value.saldos,
+            topups: value.topups,
+            transfers: value.transfers,
+            withdraws: value.withdraws,
+        }
+    }
+}
+
+impl From<PurgeUserResponse> for PurgeUserResponseProto {
+    fn from(value: PurgeUserResponse) -> Self {
+        PurgeUserResponseProto {
+            user_id: value.user_id,
+            saldos: value.saldos,
+            topups: value.topups,
+            transfers: value.transfers,
+            withdraws: value.withdraws,
+        }
+    }
+}
+
+impl From<Option<PurgeUserResponseProto>> for PurgeUserResponse {
+    fn from(value: Option<PurgeUserResponseProto>) -> Self {
+        value.unwrap_or_default().into()
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct UserDeletionCheckResponse {
+    pub user_id: i32,
+    pub deletable: bool,
+    pub incoming_transfers: i64,
+    pub active_sessions: i64,
+    pub blockers: Vec<String>,
+}
+
+impl UserDeletionCheckResponse {
+    pub fn new(user_id: i32, blockers: UserDeleteBlockers) -> Self {
+        UserDeletionCheckResponse {
+            user_id,
+            deletable: blockers.is_empty(),
+            incoming_transfers: blockers.incoming_transfers,
+            active_sessions: blockers.active_sessions,
+            blockers: blockers.describe(),
+        }
+    }
+}
+
+impl From<UserDeletionCheckResponseProto> for UserDeletionCheckResponse {
+    fn from(value: UserDeletionCheckResponseProto) -> Self {
+        UserDeletionCheckResponse {
+            user_id: value.user_id,
+            deletable: value.deletable,
+            incoming_transfers: value.incoming_transfers,
+            active_sessions: value.active_sessions,
+            blockers: value.blockers,
+        }
+    }
+}
+
+impl From<UserDeletionCheckResponse> for UserDeletionCheckResponseProto {
+    fn from(value: UserDeletionCheckResponse) -> Self {
+        UserDeletionCheckResponseProto {
+            user_id: value.user_id,
+            deletable: value.deletable,
+            incoming_transfers: value.incoming_transfers,
+            active_sessions: value.active_sessions,
+            blockers: value.blockers,
+        }
+    }
+}
+
+impl From<Option<UserDeletionCheckResponseProto>> for UserDeletionCheckResponse {
+    fn from(value: Option<UserDeletionCheckResponseProto>) -> Self {
+        value.unwrap_or_default().into()
+    }
+}
+
+// row dihitung dari 1, yaitu baris pertama setelah header
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct ImportUserResult {
+    pub row: i32,
+    pub email: String,
+    pub user_id: Option<i32>,
+    pub error: Option<String>,
+}
+
+impl ImportUserResult {
+    pub fn created(row: i32, user: &User) -> Self {
+        ImportUserResult {
+            row,
+            email: user.email.clone(),
+            user_id: Some(user.user_id),
+            error: None,
+        }
+    }
+
+    pub fn failed(row: i32, email: &str, error: String) -> Self {
+        ImportUserResult {
+            row,
+            email: email.to_string(),
+            user_id: None,
+            error: Some(error),
+        }
+    }
+}
+
+impl From<ImportUserResultProto> for ImportUserResult {
+    fn from(value: ImportUserResultProto) -> Self {
+        ImportUserResult {
+            row: value.row,
+            email: value.email,
+            user_id: value.user_id,
+            error: (!value.error.is_empty()).then_some(value.error),
+        }
+    }
+}
+
+impl From<ImportUserResult> for ImportUserResultProto {
+    fn from(value: ImportUserResult) -> Self {
+        ImportUserResultProto {
+            row: value.row,
+            email: value.email,
+            user_id: value.user_id,
+            error: value.error.unwrap_or_default(),
+        }
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone, Default)]
+pub struct ImportUsersResponse {
+    pub imported: i32,
+    pub failed: i32,
+    pub results: Vec<ImportUserResult>,
+}
+
+impl ImportUsersResponse {
+    pub fn new(results: Vec<ImportUserResult>) -> Self {
+        let imported = results.iter().filter(|r| r.user_id.is_some()).count() as i32;
+
+        ImportUsersResponse {
+            imported,
+            failed: results.len() as i32 - imported,
+            results,
+        }
+    }
+}
+
+impl From<ImportUsersResponseProto> for ImportUsersResponse {
+    fn from(value: ImportUsersResponseProto) -> Self {
+        ImportUsersResponse {
+            imported: value.imported,
+            failed: value.failed,
+            results: value.results.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<ImportUsersResponse> for ImportUsersResponseProto {
+    fn from(value: ImportUsersResponse) -> Self {
+        ImportUsersResponseProto {
+            imported: value.imported,
+            failed: value.failed,
+            results: value.results.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl From<Option<ImportUsersResponseProto>> for ImportUsersResponse {
+    fn from(value: Option<ImportUsersResponseProto>) -> Self {
+        value.unwrap_or_default().into()
+    }
+}
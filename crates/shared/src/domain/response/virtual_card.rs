@@ -0,0 +1,159 @@
+use crate::{
+    model::virtual_card::VirtualCard,
+    utils::{mask_card_number, parse_datetime},
+};
+use chrono::{DateTime, Utc};
+use genproto::virtual_card::{
+    VirtualCardResponse as VirtualCardResponseProto,
+    VirtualCardSummaryResponse as VirtualCardSummaryResponseProto,
+};
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct VirtualCardResponse {
+    pub id: i32,
+    pub user_id: i32,
+    pub card_number: String,
+    pub masked_number: String,
+    pub expiry: String,
+    #[schema(format = "date-time")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[schema(format = "date-time")]
+    pub updated_at: Option<DateTime<Utc>>,
+}
+
+impl From<VirtualCard> for VirtualCardResponse {
+    fn from(value: VirtualCard) -> Self {
+        VirtualCardResponse {
+            id: value.virtual_card_id,
+            user_id: value.user_id,
+            masked_number: mask_card_number(&value.card_number),
+            expiry: value.expiry(),
+            card_number: value.card_number,
+            created_at: value
+                .created_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            updated_at: value
+                .updated_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+        }
+    }
+}
+
+impl From<VirtualCardResponseProto> for VirtualCardResponse {
+    fn from(value: VirtualCardResponseProto) -> Self {
+        VirtualCardResponse {
+            id: value.virtual_card_id,
+            user_id: value.user_id,
+            card_number: value.card_number,
+            masked_number: value.masked_number,
+            expiry: value.expiry,
+            created_at: parse_datetime(&value.created_at),
+            updated_at: parse_datetime(&value.updated_at),
+        }
+    }
+}
+
+impl From<VirtualCardResponse> for VirtualCardResponseProto {
+    fn from(value: VirtualCardResponse) -> Self {
+        VirtualCardResponseProto {
+            virtual_card_id: value.id,
+            user_id: value.user_id,
+            card_number: value.card_number,
+            masked_number: value.masked_number,
+            expiry: value.expiry,
+            created_at: value.created_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
+            updated_at: value.updated_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<Option<VirtualCardResponseProto>> for VirtualCardResponse {
+    fn from(value: Option<VirtualCardResponseProto>) -> Self {
+        match value {
+            Some(proto) => proto.into(),
+            None => VirtualCardResponse {
+                id: 0,
+                user_id: 0,
+                card_number: String::new(),
+                masked_number: String::new(),
+                expiry: String::new(),
+                created_at: None,
+                updated_at: None,
+            },
+        }
+    }
+}
+
+// tampilan kartu untuk daftar: nomor lengkap hanya dikirim sekali saat kartu dibuat
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct VirtualCardSummaryResponse {
+    pub id: i32,
+    pub user_id: i32,
+    pub masked_number: String,
+    pub expiry: String,
+    #[schema(format = "date-time")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[schema(format = "date-time")]
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+impl From<VirtualCard> for VirtualCardSummaryResponse {
+    fn from(value: VirtualCard) -> Self {
+        VirtualCardSummaryResponse {
+            id: value.virtual_card_id,
+            user_id: value.user_id,
+            masked_number: mask_card_number(&value.card_number),
+            expiry: value.expiry(),
+            created_at: value
+                .created_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            revoked_at: value
+                .revoked_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+        }
+    }
+}
+
+impl From<VirtualCardSummaryResponseProto> for VirtualCardSummaryResponse {
+    fn from(value: VirtualCardSummaryResponseProto) -> Self {
+        VirtualCardSummaryResponse {
+            id: value.virtual_card_id,
+            user_id: value.user_id,
+            masked_number: value.masked_number,
+            expiry: value.expiry,
+            created_at: parse_datetime(&value.created_at),
+            revoked_at: parse_datetime(&value.revoked_at),
+        }
+    }
+}
+
+impl From<VirtualCardSummaryResponse> for VirtualCardSummaryResponseProto {
+    fn from(value: VirtualCardSummaryResponse) -> Self {
+        VirtualCardSummaryResponseProto {
+            virtual_card_id: value.id,
+            user_id: value.user_id,
+            masked_number: value.masked_number,
+            expiry: value.expiry,
+            created_at: value.created_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
+            revoked_at: value.revoked_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
+        }
+    }
+}
+
+impl From<Option<VirtualCardSummaryResponseProto>> for VirtualCardSummaryResponse {
+    fn from(value: Option<VirtualCardSummaryResponseProto>) -> Self {
+        match value {
+            Some(proto) => proto.into(),
+            None => VirtualCardSummaryResponse {
+                id: 0,
+                user_id: 0,
+                masked_number: String::new(),
+                expiry: String::new(),
+                created_at: None,
+                revoked_at: None,
+            },
+        }
+    }
+}
@@ -0,0 +1,97 @@
+use crate::{model::outbox::OutboxEvent, utils::parse_datetime};
+use chrono::{DateTime, Utc};
+use genproto::webhook::WebhookDeliveryResponse as WebhookDeliveryResponseProto;
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+// status pengiriman satu event outbox, tanpa payload-nya
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct WebhookDeliveryResponse {
+    pub event_id: i64,
+    pub event_type: String,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    #[schema(format = "date-time")]
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    #[schema(format = "date-time")]
+    pub created_at: Option<DateTime<Utc>>,
+    #[schema(format = "date-time")]
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+impl From<OutboxEvent> for WebhookDeliveryResponse {
+    fn from(value: OutboxEvent) -> Self {
+        WebhookDeliveryResponse {
+            event_id: value.id,
+            event_type: value.event_type,
+            status: value.status,
+            attempts: value.attempts,
+            last_error: value.last_error,
+            next_attempt_at: Some(DateTime::from_naive_utc_and_offset(
+                value.next_attempt_at,
+                Utc,
+            )),
+            created_at: value
+                .created_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+            delivered_at: value
+                .delivered_at
+                .map(|dt| DateTime::from_naive_utc_and_offset(dt, Utc)),
+        }
+    }
+}
+
+impl From<WebhookDeliveryResponseProto> for WebhookDeliveryResponse {
+    fn from(value: WebhookDeliveryResponseProto) -> Self {
+        WebhookDeliveryResponse {
+            event_id: value.event_id,
+            event_type: value.event_type,
+            status: value.status,
+            attempts: value.attempts,
+            last_error: Some(value.last_error).filter(|e| !e.is_empty()),
+            next_attempt_at: parse_datetime(&value.next_attempt_at),
+            created_at: parse_datetime(&value.created_at),
+            delivered_at: parse_datetime(&value.delivered_at),
+        }
+    }
+}
+
+impl From<WebhookDeliveryResponse> for WebhookDeliveryResponseProto {
+    fn from(value: WebhookDeliveryResponse) -> Self {
+        WebhookDeliveryResponseProto {
+            event_id: value.event_id,
+            event_type: value.event_type,
+            status: value.status,
+            attempts: value.attempts,
+            last_error: value.last_error.unwrap_or_default(),
+            next_attempt_at: value
+                .next_attempt_at
+                .map(|d| d.to_rfc3339())
+                .unwrap_or_default(),
+            created_at: value.created_at.map(|d| d.to_rfc3339()).unwrap_or_default(),
+            delivered_at: value
+                .delivered_at
+                .map(|d| d.to_rfc3339())
+                .unwrap_or_default(),
+        }
+    }
+}
+
+impl From<Option<WebhookDeliveryResponseProto>> for WebhookDeliveryResponse {
+    fn from(value: Option<WebhookDeliveryResponseProto>) -> Self {
+        match value {
+            Some(proto) => proto.into(),
+            None => WebhookDeliveryResponse {
+                event_id: 0,
+                event_type: String::new(),
+                status: String::new(),
+                attempts: 0,
+                last_error: None,
+                next_attempt_at: None,
+                created_at: None,
+                delivered_at: None,
+            },
+        }
+    }
+}
@@ -0,0 +1,34 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct LedgerEntry {
+    pub id: i64,
+    pub user_id: i32,
+    pub entry_type: String,
+    pub amount: i32,
+    pub reference_id: i32,
+    // alasan dan admin pelaku, hanya terisi untuk koreksi manual
+    pub reason: Option<String>,
+    pub actor_id: Option<i32>,
+    pub created_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LedgerEntryType {
+    TopupRefund,
+    ManualAdjustment,
+    // koreksi hasil rekonsiliasi, tidak ikut dihitung sebagai saldo yang diharapkan
+    Reconciliation,
+}
+
+impl LedgerEntryType {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            LedgerEntryType::TopupRefund => "topup_refund",
+            LedgerEntryType::ManualAdjustment => "manual_adjustment",
+            LedgerEntryType::Reconciliation => "reconciliation",
+        }
+    }
+}
@@ -1,5 +1,9 @@
+pub mod ledger;
+pub mod outbox;
 pub mod saldo;
+pub mod session;
 pub mod topup;
 pub mod transfer;
 pub mod user;
+pub mod virtual_card;
 pub mod withdraw;
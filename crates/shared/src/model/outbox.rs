@@ -0,0 +1,71 @@
+use chrono::{DateTime, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+use crate::model::transfer::Transfer;
+
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct OutboxEvent {
+    pub id: i64,
+    pub event_type: String,
+    pub payload: String,
+    pub status: String,
+    pub attempts: i32,
+    pub last_error: Option<String>,
+    pub next_attempt_at: NaiveDateTime,
+    pub created_at: Option<NaiveDateTime>,
+    pub delivered_at: Option<NaiveDateTime>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutboxStatus {
+    Pending,
+    Delivered,
+    Failed,
+}
+
+impl OutboxStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            OutboxStatus::Pending => "pending",
+            OutboxStatus::Delivered => "delivered",
+            OutboxStatus::Failed => "failed",
+        }
+    }
+}
+
+pub const TRANSFER_COMPLETED_EVENT: &str = "transfer.completed";
+// transfer ditahan untuk review, saldo belum berpindah; disusul transfer.completed saat disetujui
+pub const TRANSFER_NEEDS_REVIEW_EVENT: &str = "transfer.needs_review";
+
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct TransferEvent {
+    #[serde(rename = "type")]
+    pub event_type: String,
+    pub transfer_id: i32,
+    pub from: i32,
+    pub to: i32,
+    pub amount: i32,
+    pub timestamp: DateTime<Utc>,
+}
+
+impl TransferEvent {
+    pub fn completed(transfer: &Transfer) -> Self {
+        Self::new(TRANSFER_COMPLETED_EVENT, transfer)
+    }
+
+    pub fn needs_review(transfer: &Transfer) -> Self {
+        Self::new(TRANSFER_NEEDS_REVIEW_EVENT, transfer)
+    }
+
+    fn new(event_type: &str, transfer: &Transfer) -> Self {
+        Self {
+            event_type: event_type.to_string(),
+            transfer_id: transfer.transfer_id,
+            from: transfer.transfer_from,
+            to: transfer.transfer_to,
+            amount: transfer.transfer_amount,
+            timestamp: Utc::now(),
+        }
+    }
+}
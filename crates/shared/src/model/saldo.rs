@@ -2,6 +2,8 @@ use chrono::NaiveDateTime;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 
+use crate::model::user::User;
+
 #[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 pub struct Saldo {
     pub saldo_id: i32,
@@ -11,4 +13,52 @@ pub struct Saldo {
     pub withdraw_time: Option<NaiveDateTime>,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub label: String,
+}
+
+impl Saldo {
+    pub fn is_default(&self) -> bool {
+        self.label == DEFAULT_SALDO_LABEL
+    }
+}
+
+// user beserta saldo utamanya dari satu query join; saldo kosong jika belum pernah dibuat
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SaldoWithUser {
+    pub user: User,
+    pub saldo: Option<Saldo>,
+}
+
+// saldo tersimpan (semua label) dibanding saldo yang dihitung ulang dari riwayat topup,
+// transfer, withdraw, dan ledger milik user
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct BalanceReconciliation {
+    pub user_id: i32,
+    pub stored_balance: i64,
+    pub expected_balance: i64,
+}
+
+impl BalanceReconciliation {
+    // nilai yang harus ditambahkan ke saldo tersimpan agar sama dengan yang diharapkan
+    pub fn difference(&self) -> i64 {
+        self.expected_balance - self.stored_balance
+    }
+
+    pub fn is_balanced(&self) -> bool {
+        self.difference() == 0
+    }
 }
+
+// baris leaderboard saldo beserta nama pemiliknya
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct SaldoRanking {
+    pub saldo_id: i32,
+    pub user_id: i32,
+    pub label: String,
+    pub total_balance: i32,
+    pub firstname: String,
+    pub lastname: String,
+}
+
+// label saldo bawaan; topup, withdraw, dan transfer tanpa label memakai saldo ini
+pub const DEFAULT_SALDO_LABEL: &str = "main";
@@ -12,4 +12,38 @@ pub struct Topup {
     pub topup_time: NaiveDateTime,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub status: String,
+}
+
+// satu baris hasil agregasi topup per metode
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct TopupMethodStats {
+    pub topup_method: String,
+    pub total_count: i64,
+    pub total_amount: i64,
+}
+
+impl Topup {
+    pub fn is_success(&self) -> bool {
+        self.status == TopupStatus::Success.as_str()
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TopupStatus {
+    Pending,
+    Success,
+    Failed,
+    Refunded,
+}
+
+impl TopupStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TopupStatus::Pending => "pending",
+            TopupStatus::Success => "success",
+            TopupStatus::Failed => "failed",
+            TopupStatus::Refunded => "refunded",
+        }
+    }
 }
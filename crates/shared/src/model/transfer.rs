@@ -1,6 +1,8 @@
-use chrono::NaiveDateTime;
+use crate::model::saldo::DEFAULT_SALDO_LABEL;
+use chrono::{NaiveDate, NaiveDateTime};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use std::str::FromStr;
 
 #[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
 pub struct Transfer {
@@ -11,4 +13,77 @@ pub struct Transfer {
     pub transfer_time: NaiveDateTime,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub note: Option<String>,
+    pub status: String,
+    pub from_label: String,
+    pub to_label: String,
+}
+
+impl Transfer {
+    pub fn is_reversed(&self) -> bool {
+        self.status == TransferStatus::Reversed.as_str()
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.status == TransferStatus::Completed.as_str()
+    }
+
+    pub fn needs_review(&self) -> bool {
+        self.status == TransferStatus::NeedsReview.as_str()
+    }
+
+    pub fn uses_default_labels(&self) -> bool {
+        self.from_label == DEFAULT_SALDO_LABEL && self.to_label == DEFAULT_SALDO_LABEL
+    }
+}
+
+// jumlah uang keluar dan masuk satu user pada satu hari
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone, PartialEq, Eq)]
+pub struct DailyTransferTotal {
+    pub user_id: i32,
+    pub day: NaiveDate,
+    pub total_sent: i64,
+    pub total_received: i64,
+}
+
+// hasil dry run: transfer yang akan dibuat beserta saldo kedua pihak setelahnya
+#[derive(Debug, Clone)]
+pub struct TransferPreview {
+    pub transfer: Transfer,
+    pub sender_balance: i32,
+    pub receiver_balance: i32,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransferStatus {
+    Completed,
+    Reversed,
+    // melewati batas kecepatan transfer; dana ditahan (saldo belum berpindah) sampai admin
+    // menyetujuinya, atau dibatalkan lewat reversal tanpa mutasi saldo
+    NeedsReview,
+}
+
+impl TransferStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            TransferStatus::Completed => "completed",
+            TransferStatus::Reversed => "reversed",
+            TransferStatus::NeedsReview => "needs_review",
+        }
+    }
+}
+
+impl FromStr for TransferStatus {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        match value.trim().to_ascii_lowercase().as_str() {
+            "completed" => Ok(TransferStatus::Completed),
+            "reversed" => Ok(TransferStatus::Reversed),
+            "needs_review" => Ok(TransferStatus::NeedsReview),
+            other => Err(format!(
+                "Unknown transfer status '{other}', expected completed, reversed or needs_review"
+            )),
+        }
+    }
 }
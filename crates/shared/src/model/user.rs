@@ -12,4 +12,74 @@ pub struct User {
     pub noc_transfer: String,
     pub created_at: Option<NaiveDateTime>,
     pub updated_at: Option<NaiveDateTime>,
+    pub deleted_at: Option<NaiveDateTime>,
+    pub password_changed_at: Option<NaiveDateTime>,
+    pub status: String,
+    // akun premium boleh bersaldo negatif sampai batas ini, default 0
+    pub overdraft_limit: i32,
+}
+
+impl User {
+    pub fn is_frozen(&self) -> bool {
+        self.status == UserStatus::Frozen.as_str()
+    }
+
+    // saldo utama terendah yang diizinkan untuk user ini
+    pub fn balance_floor(&self) -> i32 {
+        -self.overdraft_limit
+    }
+}
+
+// jumlah baris yang dihapus per tabel saat purge user
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UserPurge {
+    pub saldos: i64,
+    pub topups: i64,
+    pub transfers: i64,
+    pub withdraws: i64,
+}
+
+// referensi yang membuat soft delete user memutus konteks data user lain
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UserDeleteBlockers {
+    pub incoming_transfers: i64,
+    pub active_sessions: i64,
+}
+
+impl UserDeleteBlockers {
+    pub fn is_empty(&self) -> bool {
+        self.incoming_transfers == 0 && self.active_sessions == 0
+    }
+
+    pub fn describe(&self) -> Vec<String> {
+        let mut blockers = Vec::new();
+
+        if self.incoming_transfers > 0 {
+            blockers.push(format!(
+                "{} incoming transfer(s) from other users",
+                self.incoming_transfers
+            ));
+        }
+
+        if self.active_sessions > 0 {
+            blockers.push(format!("{} active session(s)", self.active_sessions));
+        }
+
+        blockers
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum UserStatus {
+    Active,
+    Frozen,
+}
+
+impl UserStatus {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            UserStatus::Active => "active",
+            UserStatus::Frozen => "frozen",
+        }
+    }
 }
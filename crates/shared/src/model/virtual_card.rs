@@ -0,0 +1,26 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+#[derive(Debug, FromRow, Serialize, Deserialize, Clone)]
+pub struct VirtualCard {
+    pub virtual_card_id: i32,
+    pub user_id: i32,
+    pub card_number: String,
+    pub expiry_month: i32,
+    pub expiry_year: i32,
+    pub created_at: Option<NaiveDateTime>,
+    pub updated_at: Option<NaiveDateTime>,
+    pub revoked_at: Option<NaiveDateTime>,
+}
+
+impl VirtualCard {
+    // format MM/YY seperti yang tercetak di kartu
+    pub fn expiry(&self) -> String {
+        format!("{:02}/{:02}", self.expiry_month, self.expiry_year % 100)
+    }
+
+    pub fn is_revoked(&self) -> bool {
+        self.revoked_at.is_some()
+    }
+}
@@ -0,0 +1,258 @@
+mod outbox;
+mod saldo;
+mod session;
+mod topup;
+mod transfer;
+mod user;
+mod virtual_card;
+mod withdraw;
+
+use std::{
+    collections::HashMap,
+    sync::{Arc, Mutex, MutexGuard, PoisonError},
+};
+
+use crate::domain::request::default_page_size;
+use crate::{
+    abstract_trait::{
+        DynOutboxRepository, DynSaldoRepository, DynSessionRepository, DynTopupRepository,
+        DynTransferRepository, DynUserRepository, DynVirtualCardRepository, DynWithdrawRepository,
+    },
+    cache::CacheStore,
+    config::CacheTtlConfig,
+    domain::request::{CreateUserRequest, UpdateSaldoBalance},
+    model::{
+        ledger::LedgerEntry,
+        outbox::OutboxEvent,
+        saldo::{DEFAULT_SALDO_LABEL, Saldo},
+        session::Session,
+        topup::Topup,
+        transfer::Transfer,
+        user::User,
+        virtual_card::VirtualCard,
+        withdraw::Withdraw,
+    },
+    utils::AppError,
+};
+
+pub use self::outbox::InMemoryOutboxRepository;
+pub use self::saldo::InMemorySaldoRepository;
+pub use self::session::InMemorySessionRepository;
+pub use self::topup::InMemoryTopupRepository;
+pub use self::transfer::InMemoryTransferRepository;
+pub use self::user::InMemoryUserRepository;
+pub use self::virtual_card::InMemoryVirtualCardRepository;
+pub use self::withdraw::InMemoryWithdrawRepository;
+
+// pengganti Postgres untuk test service, semua tabel dikunci bersama
+// supaya operasi multi-tabel (konfirmasi topup, transfer) tetap atomik
+#[derive(Default)]
+pub struct InMemoryDatabase {
+    tables: Mutex<Tables>,
+}
+
+impl InMemoryDatabase {
+    pub fn new() -> Arc<Self> {
+        Arc::new(Self::default())
+    }
+
+    pub(crate) fn tables(&self) -> MutexGuard<'_, Tables> {
+        self.tables.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+#[derive(Default)]
+pub(crate) struct Tables {
+    pub users: Table<User>,
+    pub saldos: Table<Saldo>,
+    pub topups: Table<Topup>,
+    pub transfers: Table<Transfer>,
+    // kolom transfers.reversal_of: id transfer kompensasi → id transfer yang dibatalkannya
+    pub reversal_of: HashMap<i32, i32>,
+    pub withdraws: Table<Withdraw>,
+    pub virtual_cards: Table<VirtualCard>,
+    pub sessions: Table<Session>,
+    pub outbox: Vec<OutboxEvent>,
+    pub ledger: Vec<LedgerEntry>,
+}
+
+pub(crate) struct Table<T> {
+    rows: HashMap<i32, T>,
+    last_id: i32,
+}
+
+impl<T> Default for Table<T> {
+    fn default() -> Self {
+        Self {
+            rows: HashMap::new(),
+            last_id: 0,
+        }
+    }
+}
+
+impl<T: Clone> Table<T> {
+    pub fn insert_with(&mut self, build: impl FnOnce(i32) -> T) -> T {
+        self.last_id += 1;
+        let row = build(self.last_id);
+        self.rows.insert(self.last_id, row.clone());
+        row
+    }
+
+    pub fn get(&self, id: i32) -> Option<&T> {
+        self.rows.get(&id)
+    }
+
+    pub fn get_mut(&mut self, id: i32) -> Option<&mut T> {
+        self.rows.get_mut(&id)
+    }
+
+    pub fn remove(&mut self, id: i32) -> Option<T> {
+        self.rows.remove(&id)
+    }
+
+    pub fn remove_where(&mut self, predicate: impl Fn(&T) -> bool) -> i64 {
+        let before = self.rows.len();
+        self.rows.retain(|_, row| !predicate(row));
+        (before - self.rows.len()) as i64
+    }
+
+    // urut berdasarkan primary key, sama seperti ORDER BY id ASC
+    pub fn rows_where(&self, predicate: impl Fn(&T) -> bool) -> Vec<T> {
+        let mut rows = self
+            .rows
+            .iter()
+            .filter(|(_, row)| predicate(row))
+            .collect::<Vec<_>>();
+        rows.sort_by_key(|(id, _)| **id);
+        rows.into_iter().map(|(_, row)| row.clone()).collect()
+    }
+
+    pub fn first_where(&self, predicate: impl Fn(&T) -> bool) -> Option<T> {
+        self.rows_where(predicate).into_iter().next()
+    }
+
+    pub fn first_id_where(&self, predicate: impl Fn(&T) -> bool) -> Option<i32> {
+        self.rows
+            .iter()
+            .filter(|(_, row)| predicate(row))
+            .map(|(id, _)| *id)
+            .min()
+    }
+}
+
+pub(crate) fn paginate<T>(rows: Vec<T>, page: i32, page_size: i32) -> (Vec<T>, i64) {
+    let page = if page > 0 { page } else { 1 };
+    let page_size = if page_size > 0 {
+        page_size
+    } else {
+        default_page_size()
+    };
+    let total = rows.len() as i64;
+
+    let rows = rows
+        .into_iter()
+        .skip(((page - 1) * page_size) as usize)
+        .take(page_size as usize)
+        .collect();
+
+    (rows, total)
+}
+
+pub(crate) fn insufficient_balance() -> AppError {
+    AppError::Custom("Insufficient balance".to_string())
+}
+
+// meniru trigger saldo: saldo utama boleh turun sampai batas overdraft user, saldo berlabel minimal 0
+pub(crate) fn balance_floor(tables: &Tables, user_id: i32, label: &str) -> i32 {
+    if label != DEFAULT_SALDO_LABEL {
+        return 0;
+    }
+
+    tables
+        .users
+        .get(user_id)
+        .map_or(0, |user| user.balance_floor())
+}
+
+pub struct InMemoryRepositories {
+    pub database: Arc<InMemoryDatabase>,
+    pub user: DynUserRepository,
+    pub saldo: DynSaldoRepository,
+    pub topup: DynTopupRepository,
+    pub transfer: DynTransferRepository,
+    pub withdraw: DynWithdrawRepository,
+    pub virtual_card: DynVirtualCardRepository,
+    pub session: DynSessionRepository,
+    pub outbox: DynOutboxRepository,
+}
+
+impl Default for InMemoryRepositories {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl InMemoryRepositories {
+    pub fn new() -> Self {
+        let database = InMemoryDatabase::new();
+
+        Self {
+            user: Arc::new(InMemoryUserRepository::new(database.clone())),
+            saldo: Arc::new(InMemorySaldoRepository::new(database.clone())),
+            topup: Arc::new(InMemoryTopupRepository::new(database.clone())),
+            transfer: Arc::new(InMemoryTransferRepository::new(database.clone())),
+            withdraw: Arc::new(InMemoryWithdrawRepository::new(database.clone())),
+            virtual_card: Arc::new(InMemoryVirtualCardRepository::new(database.clone())),
+            session: Arc::new(InMemorySessionRepository::new(database.clone())),
+            outbox: Arc::new(InMemoryOutboxRepository::new(database.clone())),
+            database,
+        }
+    }
+
+    // user baru beserta saldo utamanya, langsung diisi `balance`
+    pub async fn seed_user(&self, email: &str, balance: i32) -> User {
+        let user = self
+            .user
+            .create_user(&CreateUserRequest {
+                firstname: "Test".to_string(),
+                lastname: "User".to_string(),
+                email: email.to_string(),
+                password: "password".to_string(),
+                confirm_password: "password".to_string(),
+                noc_transfer: None,
+            })
+            .await
+            .expect("seed user");
+
+        self.saldo
+            .update_balance(&UpdateSaldoBalance {
+                user_id: user.user_id,
+                total_balance: balance,
+            })
+            .await
+            .expect("seed saldo");
+
+        user
+    }
+
+    pub async fn balance_of(&self, user_id: i32) -> i32 {
+        self.saldo
+            .find_by_user_id(user_id)
+            .await
+            .expect("find saldo")
+            .expect("user has a saldo")
+            .total_balance
+    }
+}
+
+// port 1 selalu menolak koneksi: cache dilewati seperti saat Redis mati, lock saldo tidak
+// dibutuhkan karena repository in-memory sudah menserialkan tulisan
+pub fn offline_cache_store() -> Arc<CacheStore> {
+    Arc::new(
+        CacheStore::new(
+            redis::Client::open("redis://127.0.0.1:1/").expect("valid redis url"),
+            CacheTtlConfig::default(),
+        )
+        .without_user_locks(),
+    )
+}
@@ -0,0 +1,206 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use std::{sync::Arc, time::Duration};
+
+use crate::{
+    abstract_trait::OutboxRepositoryTrait,
+    model::outbox::{OutboxEvent, OutboxStatus},
+    repository::memory::InMemoryDatabase,
+    utils::AppError,
+};
+
+pub struct InMemoryOutboxRepository {
+    db: Arc<InMemoryDatabase>,
+}
+
+impl InMemoryOutboxRepository {
+    pub fn new(db: Arc<InMemoryDatabase>) -> Self {
+        Self { db }
+    }
+
+    // sama seperti UPDATE ... WHERE id, id yang tidak ada diabaikan
+    fn with_event(&self, id: i64, apply: impl FnOnce(&mut OutboxEvent)) -> Result<(), AppError> {
+        if let Some(event) = self
+            .db
+            .tables()
+            .outbox
+            .iter_mut()
+            .find(|event| event.id == id)
+        {
+            apply(event);
+        }
+        Ok(())
+    }
+}
+
+#[async_trait]
+impl OutboxRepositoryTrait for InMemoryOutboxRepository {
+    async fn claim_pending(
+        &self,
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<OutboxEvent>, AppError> {
+        let now = Utc::now().naive_utc();
+        let lease_until = now
+            + chrono::Duration::from_std(lease)
+                .map_err(|e| AppError::Custom(format!("Invalid outbox claim lease: {e}")))?;
+
+        Ok(self
+            .db
+            .tables()
+            .outbox
+            .iter_mut()
+            .filter(|event| {
+                event.status == OutboxStatus::Pending.as_str() && event.next_attempt_at <= now
+            })
+            .take(limit.max(0) as usize)
+            .map(|event| {
+                event.next_attempt_at = lease_until;
+                event.clone()
+            })
+            .collect())
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Option<OutboxEvent>, AppError> {
+        Ok(self
+            .db
+            .tables()
+            .outbox
+            .iter()
+            .find(|event| event.id == id)
+            .cloned())
+    }
+
+    async fn requeue(&self, id: i64) -> Result<Option<OutboxEvent>, AppError> {
+        let now = Utc::now().naive_utc();
+
+        // event pending yang lease-nya belum habis masih dipegang worker
+        Ok(self
+            .db
+            .tables()
+            .outbox
+            .iter_mut()
+            .find(|event| {
+                event.id == id
+                    && (event.status == OutboxStatus::Failed.as_str()
+                        || (event.status == OutboxStatus::Pending.as_str()
+                            && event.next_attempt_at <= now))
+            })
+            .map(|event| {
+                event.status = OutboxStatus::Pending.as_str().to_string();
+                event.attempts = 0;
+                event.last_error = None;
+                event.next_attempt_at = now;
+                event.clone()
+            }))
+    }
+
+    async fn mark_delivered(&self, id: i64) -> Result<(), AppError> {
+        self.with_event(id, |event| {
+            event.status = OutboxStatus::Delivered.as_str().to_string();
+            event.attempts += 1;
+            event.delivered_at = Some(Utc::now().naive_utc());
+        })
+    }
+
+    async fn schedule_retry(
+        &self,
+        id: i64,
+        next_attempt_at: NaiveDateTime,
+        last_error: &str,
+    ) -> Result<(), AppError> {
+        self.with_event(id, |event| {
+            event.attempts += 1;
+            event.last_error = Some(last_error.to_string());
+            event.next_attempt_at = next_attempt_at;
+        })
+    }
+
+    async fn mark_failed(&self, id: i64, last_error: &str) -> Result<(), AppError> {
+        self.with_event(id, |event| {
+            event.status = OutboxStatus::Failed.as_str().to_string();
+            event.attempts += 1;
+            event.last_error = Some(last_error.to_string());
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pending_event(id: i64) -> OutboxEvent {
+        let now = Utc::now().naive_utc();
+        OutboxEvent {
+            id,
+            event_type: "transfer.completed".to_string(),
+            payload: "{}".to_string(),
+            status: OutboxStatus::Pending.as_str().to_string(),
+            attempts: 0,
+            last_error: None,
+            next_attempt_at: now,
+            created_at: Some(now),
+            delivered_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn claimed_events_are_skipped_until_the_lease_expires() {
+        let db = InMemoryDatabase::new();
+        db.tables().outbox.extend((1..=3).map(pending_event));
+        let repository = InMemoryOutboxRepository::new(db);
+
+        let first = repository
+            .claim_pending(2, Duration::from_secs(60))
+            .await
+            .unwrap();
+        let second = repository
+            .claim_pending(2, Duration::from_secs(60))
+            .await
+            .unwrap();
+
+        assert_eq!(first.iter().map(|e| e.id).collect::<Vec<_>>(), [1, 2]);
+        assert_eq!(second.iter().map(|e| e.id).collect::<Vec<_>>(), [3]);
+
+        let expired = InMemoryOutboxRepository::new(InMemoryDatabase::new());
+        expired.db.tables().outbox.push(pending_event(1));
+        expired.claim_pending(1, Duration::ZERO).await.unwrap();
+
+        // lease habis berarti worker pemegangnya dianggap mati, event diklaim ulang
+        assert_eq!(
+            expired
+                .claim_pending(1, Duration::ZERO)
+                .await
+                .unwrap()
+                .len(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn leased_event_is_not_requeued() {
+        let db = InMemoryDatabase::new();
+        db.tables().outbox.push(pending_event(1));
+        let repository = InMemoryOutboxRepository::new(db);
+
+        let claimed = repository
+            .claim_pending(1, Duration::from_secs(60))
+            .await
+            .unwrap();
+        let lease_until = claimed[0].next_attempt_at;
+
+        assert!(repository.requeue(1).await.unwrap().is_none());
+
+        // lease tetap milik worker pertama, worker lain tidak bisa mengklaim event yang sama
+        let event = repository.find_by_id(1).await.unwrap().unwrap();
+        assert_eq!(event.next_attempt_at, lease_until);
+        assert!(
+            repository
+                .claim_pending(1, Duration::from_secs(60))
+                .await
+                .unwrap()
+                .is_empty()
+        );
+    }
+}
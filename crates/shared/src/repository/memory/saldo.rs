@@ -0,0 +1,386 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::{
+    abstract_trait::SaldoRepositoryTrait,
+    domain::request::{
+        AdjustBalanceRequest, CreateSaldoRequest, UpdateSaldoBalance, UpdateSaldoRequest,
+        UpdateSaldoWithdraw,
+    },
+    model::{
+        ledger::{LedgerEntry, LedgerEntryType},
+        saldo::{BalanceReconciliation, DEFAULT_SALDO_LABEL, Saldo, SaldoRanking, SaldoWithUser},
+        topup::TopupStatus,
+        transfer::Transfer,
+    },
+    repository::memory::{InMemoryDatabase, Tables, balance_floor, insufficient_balance, paginate},
+    utils::{AppError, checked_amount},
+};
+
+pub struct InMemorySaldoRepository {
+    db: Arc<InMemoryDatabase>,
+}
+
+impl InMemorySaldoRepository {
+    pub fn new(db: Arc<InMemoryDatabase>) -> Self {
+        Self { db }
+    }
+}
+
+// perhitungan yang sama dengan query rekonsiliasi Postgres
+fn reconcile(tables: &Tables, user_id: i32) -> BalanceReconciliation {
+    let sum = |amounts: Vec<i32>| amounts.into_iter().map(i64::from).sum::<i64>();
+
+    let stored = sum(tables
+        .saldos
+        .rows_where(|saldo| saldo.user_id == user_id)
+        .into_iter()
+        .map(|saldo| saldo.total_balance)
+        .collect());
+    let topups = sum(tables
+        .topups
+        .rows_where(|topup| {
+            topup.user_id == user_id
+                && (topup.status == TopupStatus::Success.as_str()
+                    || topup.status == TopupStatus::Refunded.as_str())
+        })
+        .into_iter()
+        .map(|topup| topup.topup_amount)
+        .collect());
+    // sama dengan settled_transfer: transfer yang ditahan dan tahanan yang dibatalkan tidak dihitung
+    let settled = |transfer: &Transfer| {
+        transfer.is_completed()
+            || (transfer.is_reversed()
+                && tables
+                    .reversal_of
+                    .values()
+                    .any(|original| *original == transfer.transfer_id))
+    };
+    let transfers_in = sum(tables
+        .transfers
+        .rows_where(|transfer| transfer.transfer_to == user_id && settled(transfer))
+        .into_iter()
+        .map(|transfer| transfer.transfer_amount)
+        .collect());
+    let transfers_out = sum(tables
+        .transfers
+        .rows_where(|transfer| transfer.transfer_from == user_id && settled(transfer))
+        .into_iter()
+        .map(|transfer| transfer.transfer_amount)
+        .collect());
+    let withdraws = sum(tables
+        .withdraws
+        .rows_where(|withdraw| withdraw.user_id == user_id)
+        .into_iter()
+        .map(|withdraw| withdraw.withdraw_amount)
+        .collect());
+    let ledger = sum(tables
+        .ledger
+        .iter()
+        .filter(|entry| {
+            entry.user_id == user_id && entry.entry_type != LedgerEntryType::Reconciliation.as_str()
+        })
+        .map(|entry| entry.amount)
+        .collect());
+
+    BalanceReconciliation {
+        user_id,
+        stored_balance: stored,
+        expected_balance: topups + transfers_in - transfers_out - withdraws + ledger,
+    }
+}
+
+fn saldo_not_found() -> AppError {
+    AppError::NotFound("Saldo not found".to_string())
+}
+
+#[async_trait]
+impl SaldoRepositoryTrait for InMemorySaldoRepository {
+    async fn find_all(
+        &self,
+        page: i32,
+        page_size: i32,
+        search: Option<String>,
+    ) -> Result<(Vec<Saldo>, i64), AppError> {
+        let saldos = self.db.tables().saldos.rows_where(|saldo| {
+            search
+                .as_ref()
+                .is_none_or(|term| saldo.user_id.to_string().starts_with(term.as_str()))
+        });
+
+        Ok(paginate(saldos, page, page_size))
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Saldo>, AppError> {
+        Ok(self.db.tables().saldos.get(id).cloned())
+    }
+
+    async fn exists_by_id(&self, id: i32) -> Result<bool, AppError> {
+        Ok(self.db.tables().saldos.get(id).is_some())
+    }
+
+    async fn find_by_users_id(
+        &self,
+        id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Saldo>, i64), AppError> {
+        let rows = self
+            .db
+            .tables()
+            .saldos
+            .rows_where(|saldo| saldo.user_id == id);
+
+        Ok(paginate(rows, page, page_size))
+    }
+
+    async fn find_by_user_id(&self, id: i32) -> Result<Option<Saldo>, AppError> {
+        Ok(self
+            .db
+            .tables()
+            .saldos
+            .first_where(|saldo| saldo.user_id == id && saldo.is_default()))
+    }
+
+    async fn find_with_user_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<SaldoWithUser>, AppError> {
+        let tables = self.db.tables();
+
+        Ok(tables
+            .users
+            .get(user_id)
+            .filter(|user| user.deleted_at.is_none())
+            .map(|user| SaldoWithUser {
+                user: user.clone(),
+                saldo: tables
+                    .saldos
+                    .first_where(|saldo| saldo.user_id == user_id && saldo.is_default()),
+            }))
+    }
+
+    async fn create(&self, input: &CreateSaldoRequest) -> Result<Saldo, AppError> {
+        let mut tables = self.db.tables();
+        if input.total_balance < balance_floor(&tables, input.user_id, input.label()) {
+            return Err(insufficient_balance());
+        }
+
+        if tables
+            .saldos
+            .first_id_where(|saldo| saldo.user_id == input.user_id && saldo.label == input.label())
+            .is_some()
+        {
+            return Err(AppError::Custom(
+                "User already has a saldo with this label".to_string(),
+            ));
+        }
+
+        let now = Utc::now().naive_utc();
+
+        Ok(tables.saldos.insert_with(|saldo_id| Saldo {
+            saldo_id,
+            user_id: input.user_id,
+            total_balance: input.total_balance,
+            withdraw_amount: None,
+            withdraw_time: None,
+            created_at: Some(now),
+            updated_at: Some(now),
+            label: input.label().to_string(),
+        }))
+    }
+
+    async fn update(&self, input: &UpdateSaldoRequest) -> Result<Saldo, AppError> {
+        let mut tables = self.db.tables();
+        let saldo = tables
+            .saldos
+            .get_mut(input.saldo_id)
+            .ok_or_else(saldo_not_found)?;
+
+        let withdraw_amount = input.withdraw_amount.unwrap_or(0);
+        let updated_balance = saldo.total_balance as i64 - withdraw_amount as i64;
+
+        if updated_balance < 50000 {
+            return Err(AppError::Custom(
+                "Insufficient balance: Saldo cannot be less than 50000".to_string(),
+            ));
+        }
+
+        let now = Utc::now().naive_utc();
+        saldo.total_balance = updated_balance as i32;
+        saldo.withdraw_amount = Some(withdraw_amount);
+        saldo.withdraw_time = Some(input.withdraw_time.unwrap_or(now));
+        saldo.updated_at = Some(now);
+
+        Ok(saldo.clone())
+    }
+
+    async fn update_balance(&self, input: &UpdateSaldoBalance) -> Result<Saldo, AppError> {
+        let mut tables = self.db.tables();
+        if input.total_balance < balance_floor(&tables, input.user_id, DEFAULT_SALDO_LABEL) {
+            return Err(insufficient_balance());
+        }
+
+        let saldo_id = tables
+            .saldos
+            .first_id_where(|saldo| saldo.user_id == input.user_id && saldo.is_default())
+            .ok_or_else(saldo_not_found)?;
+        let saldo = tables
+            .saldos
+            .get_mut(saldo_id)
+            .ok_or_else(saldo_not_found)?;
+
+        saldo.total_balance = input.total_balance;
+        saldo.updated_at = Some(Utc::now().naive_utc());
+
+        Ok(saldo.clone())
+    }
+
+    async fn update_saldo_withdraw(&self, input: &UpdateSaldoWithdraw) -> Result<Saldo, AppError> {
+        let mut tables = self.db.tables();
+        let saldo_id = tables
+            .saldos
+            .first_id_where(|saldo| saldo.user_id == input.user_id && saldo.is_default())
+            .ok_or_else(saldo_not_found)?;
+        let floor = balance_floor(&tables, input.user_id, DEFAULT_SALDO_LABEL);
+        let saldo = tables
+            .saldos
+            .get_mut(saldo_id)
+            .ok_or_else(saldo_not_found)?;
+
+        let withdraw_amount = input.withdraw_amount.unwrap_or(0);
+        let total_balance = checked_amount(saldo.total_balance.checked_sub(withdraw_amount))?;
+        if total_balance < floor {
+            return Err(insufficient_balance());
+        }
+
+        let now = Utc::now();
+        saldo.total_balance = total_balance;
+        saldo.withdraw_amount = Some(withdraw_amount);
+        saldo.withdraw_time = Some(input.withdraw_time.unwrap_or(now).naive_utc());
+        saldo.updated_at = Some(now.naive_utc());
+
+        Ok(saldo.clone())
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), AppError> {
+        self.db
+            .tables()
+            .saldos
+            .remove(id)
+            .map(|_| ())
+            .ok_or_else(|| AppError::NotFound(format!("Saldo with ID {id} not found")))
+    }
+
+    async fn totals(&self) -> Result<(i64, i64), AppError> {
+        let saldos = self.db.tables().saldos.rows_where(|_| true);
+        let total_balance = saldos.iter().map(|saldo| saldo.total_balance as i64).sum();
+
+        Ok((total_balance, saldos.len() as i64))
+    }
+
+    async fn top_by_balance(&self, limit: i32) -> Result<Vec<SaldoRanking>, AppError> {
+        let tables = self.db.tables();
+
+        let mut rankings: Vec<SaldoRanking> = tables
+            .saldos
+            .rows_where(|_| true)
+            .into_iter()
+            .filter_map(|saldo| {
+                let user = tables
+                    .users
+                    .get(saldo.user_id)
+                    .filter(|user| user.deleted_at.is_none())?;
+
+                Some(SaldoRanking {
+                    saldo_id: saldo.saldo_id,
+                    user_id: saldo.user_id,
+                    label: saldo.label,
+                    total_balance: saldo.total_balance,
+                    firstname: user.firstname.clone(),
+                    lastname: user.lastname.clone(),
+                })
+            })
+            .collect();
+
+        rankings.sort_by(|a, b| {
+            b.total_balance
+                .cmp(&a.total_balance)
+                .then(a.user_id.cmp(&b.user_id))
+                .then(a.saldo_id.cmp(&b.saldo_id))
+        });
+        rankings.truncate(limit.max(0) as usize);
+
+        Ok(rankings)
+    }
+
+    async fn adjust_balance(
+        &self,
+        actor_id: i32,
+        input: &AdjustBalanceRequest,
+        entry_type: LedgerEntryType,
+    ) -> Result<Saldo, AppError> {
+        let mut tables = self.db.tables();
+        let saldo_id = tables
+            .saldos
+            .first_id_where(|saldo| saldo.user_id == input.user_id && saldo.is_default())
+            .ok_or_else(saldo_not_found)?;
+        let floor = balance_floor(&tables, input.user_id, DEFAULT_SALDO_LABEL);
+        let saldo = tables
+            .saldos
+            .get_mut(saldo_id)
+            .ok_or_else(saldo_not_found)?;
+
+        let total_balance = saldo
+            .total_balance
+            .checked_add(input.delta)
+            .filter(|balance| *balance >= floor)
+            .ok_or_else(insufficient_balance)?;
+
+        let now = Utc::now().naive_utc();
+        saldo.total_balance = total_balance;
+        saldo.updated_at = Some(now);
+        let adjusted = saldo.clone();
+
+        let ledger_id = tables.ledger.len() as i64 + 1;
+        tables.ledger.push(LedgerEntry {
+            id: ledger_id,
+            user_id: input.user_id,
+            entry_type: entry_type.as_str().to_string(),
+            amount: input.delta,
+            reference_id: adjusted.saldo_id,
+            reason: Some(input.reason.trim().to_string()),
+            actor_id: Some(actor_id),
+            created_at: Some(now),
+        });
+
+        Ok(adjusted)
+    }
+
+    async fn find_reconciliation(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<BalanceReconciliation>, AppError> {
+        let tables = self.db.tables();
+
+        Ok(tables
+            .users
+            .get(user_id)
+            .filter(|user| user.deleted_at.is_none())
+            .map(|_| reconcile(&tables, user_id)))
+    }
+
+    async fn find_balance_mismatches(&self) -> Result<Vec<BalanceReconciliation>, AppError> {
+        let tables = self.db.tables();
+
+        Ok(tables
+            .users
+            .rows_where(|user| user.deleted_at.is_none())
+            .into_iter()
+            .map(|user| reconcile(&tables, user.user_id))
+            .filter(|reconciliation| !reconciliation.is_balanced())
+            .collect())
+    }
+}
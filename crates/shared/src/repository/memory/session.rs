@@ -0,0 +1,104 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::{
+    abstract_trait::SessionRepositoryTrait, domain::request::CreateSessionRequest,
+    model::session::Session, repository::memory::InMemoryDatabase, utils::AppError,
+};
+
+pub struct InMemorySessionRepository {
+    db: Arc<InMemoryDatabase>,
+}
+
+impl InMemorySessionRepository {
+    pub fn new(db: Arc<InMemoryDatabase>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl SessionRepositoryTrait for InMemorySessionRepository {
+    async fn create(&self, input: &CreateSessionRequest) -> Result<Session, AppError> {
+        let now = Utc::now().naive_utc();
+
+        Ok(self.db.tables().sessions.insert_with(|session_id| Session {
+            session_id,
+            user_id: input.user_id,
+            token_hash: input.token_hash.clone(),
+            user_agent: input.user_agent.clone(),
+            created_at: Some(now),
+            last_used_at: Some(now),
+            expires_at: input.expires_at,
+            revoked_at: None,
+        }))
+    }
+
+    async fn find_active_by_user(&self, user_id: i32) -> Result<Vec<Session>, AppError> {
+        let now = Utc::now().naive_utc();
+
+        Ok(self
+            .db
+            .tables()
+            .sessions
+            .rows_where(|session| session.user_id == user_id && session.is_active(now)))
+    }
+
+    async fn find_active_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<Session>, AppError> {
+        let now = Utc::now().naive_utc();
+
+        Ok(self
+            .db
+            .tables()
+            .sessions
+            .first_where(|session| session.token_hash == token_hash && session.is_active(now)))
+    }
+
+    async fn touch(&self, session_id: i32) -> Result<(), AppError> {
+        if let Some(session) = self.db.tables().sessions.get_mut(session_id) {
+            session.last_used_at = Some(Utc::now().naive_utc());
+        }
+
+        Ok(())
+    }
+
+    async fn revoke(&self, user_id: i32, session_id: i32) -> Result<(), AppError> {
+        let mut tables = self.db.tables();
+        let session = tables
+            .sessions
+            .get_mut(session_id)
+            .filter(|session| session.user_id == user_id && session.revoked_at.is_none())
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "Active session {session_id} not found for user {user_id}"
+                ))
+            })?;
+
+        session.revoked_at = Some(Utc::now().naive_utc());
+
+        Ok(())
+    }
+
+    async fn revoke_all_for_user(&self, user_id: i32) -> Result<u64, AppError> {
+        let mut tables = self.db.tables();
+        let ids = tables
+            .sessions
+            .rows_where(|session| session.user_id == user_id && session.revoked_at.is_none())
+            .into_iter()
+            .map(|session| session.session_id)
+            .collect::<Vec<_>>();
+
+        let now = Utc::now().naive_utc();
+        for id in &ids {
+            if let Some(session) = tables.sessions.get_mut(*id) {
+                session.revoked_at = Some(now);
+            }
+        }
+
+        Ok(ids.len() as u64)
+    }
+}
@@ -0,0 +1,375 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::{collections::BTreeMap, sync::Arc};
+
+use crate::{
+    abstract_trait::TopupRepositoryTrait,
+    domain::request::{
+        CreateTopupRequest, TopupStatsRequest, UpdateTopupAmount, UpdateTopupRequest,
+    },
+    model::{
+        ledger::{LedgerEntry, LedgerEntryType},
+        saldo::{DEFAULT_SALDO_LABEL, Saldo},
+        topup::{Topup, TopupMethodStats, TopupStatus},
+    },
+    repository::{
+        memory::{InMemoryDatabase, Tables, balance_floor, insufficient_balance, paginate},
+        topup::transition_rejection,
+    },
+    utils::{AppError, checked_amount},
+};
+
+pub struct InMemoryTopupRepository {
+    db: Arc<InMemoryDatabase>,
+}
+
+impl InMemoryTopupRepository {
+    pub fn new(db: Arc<InMemoryDatabase>) -> Self {
+        Self { db }
+    }
+}
+
+fn not_found(id: i32) -> AppError {
+    AppError::NotFound(format!("Topup with ID {id} not found"))
+}
+
+fn transition(
+    tables: &mut Tables,
+    id: i32,
+    from: TopupStatus,
+    target: TopupStatus,
+) -> Result<Topup, AppError> {
+    let topup = tables.topups.get_mut(id).ok_or_else(|| not_found(id))?;
+
+    if topup.status != from.as_str() {
+        return Err(AppError::Custom(transition_rejection(target)));
+    }
+
+    topup.status = target.as_str().to_string();
+    topup.updated_at = Some(Utc::now().naive_utc());
+
+    Ok(topup.clone())
+}
+
+#[async_trait]
+impl TopupRepositoryTrait for InMemoryTopupRepository {
+    async fn find_all(
+        &self,
+        page: i32,
+        page_size: i32,
+        search: Option<String>,
+    ) -> Result<(Vec<Topup>, i64), AppError> {
+        let topups = self.db.tables().topups.rows_where(|topup| {
+            search
+                .as_ref()
+                .is_none_or(|term| topup.topup_no.starts_with(term.as_str()))
+        });
+
+        Ok(paginate(topups, page, page_size))
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Topup>, AppError> {
+        Ok(self.db.tables().topups.get(id).cloned())
+    }
+
+    async fn find_by_topup_no(&self, topup_no: &str) -> Result<Option<Topup>, AppError> {
+        Ok(self
+            .db
+            .tables()
+            .topups
+            .first_where(|topup| topup.topup_no == topup_no))
+    }
+
+    async fn find_by_users(
+        &self,
+        id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Topup>, i64), AppError> {
+        let rows = self
+            .db
+            .tables()
+            .topups
+            .rows_where(|topup| topup.user_id == id);
+
+        Ok(paginate(rows, page, page_size))
+    }
+
+    async fn find_by_user(&self, id: i32) -> Result<Option<Topup>, AppError> {
+        Ok(self
+            .db
+            .tables()
+            .topups
+            .first_where(|topup| topup.user_id == id))
+    }
+
+    async fn find_recent_by_user(&self, user_id: i32, limit: i32) -> Result<Vec<Topup>, AppError> {
+        let mut rows = self
+            .db
+            .tables()
+            .topups
+            .rows_where(|topup| topup.user_id == user_id);
+        rows.reverse();
+        rows.truncate(limit.max(0) as usize);
+
+        Ok(rows)
+    }
+
+    async fn create(&self, input: &CreateTopupRequest) -> Result<Topup, AppError> {
+        let mut tables = self.db.tables();
+
+        // meniru unique index uq_topups_topup_no
+        if tables
+            .topups
+            .first_id_where(|topup| topup.topup_no == input.topup_no)
+            .is_some()
+        {
+            return Err(AppError::TopupNoAlreadyExists);
+        }
+
+        let now = Utc::now().naive_utc();
+
+        Ok(tables.topups.insert_with(|topup_id| Topup {
+            topup_id,
+            user_id: input.user_id,
+            topup_no: input.topup_no.clone(),
+            topup_amount: input.topup_amount,
+            topup_method: input.topup_method.clone(),
+            topup_time: now,
+            created_at: Some(now),
+            updated_at: Some(now),
+            status: TopupStatus::Pending.as_str().to_string(),
+        }))
+    }
+
+    async fn update(&self, input: &UpdateTopupRequest) -> Result<Topup, AppError> {
+        let mut tables = self.db.tables();
+        let topup = tables
+            .topups
+            .get_mut(input.topup_id)
+            .ok_or_else(|| not_found(input.topup_id))?;
+
+        if let Some(topup_amount) = input.topup_amount {
+            topup.topup_amount = topup_amount;
+        }
+        if let Some(topup_method) = &input.topup_method {
+            topup.topup_method = topup_method.clone();
+        }
+
+        // tanpa field apa pun topup dikembalikan apa adanya
+        if input.topup_amount.is_some() || input.topup_method.is_some() {
+            topup.updated_at = Some(Utc::now().naive_utc());
+        }
+
+        Ok(topup.clone())
+    }
+
+    async fn update_with_settlement(&self, input: &UpdateTopupRequest) -> Result<Topup, AppError> {
+        let mut tables = self.db.tables();
+        let original = tables
+            .topups
+            .get(input.topup_id)
+            .filter(|topup| topup.user_id == input.user_id)
+            .cloned()
+            .ok_or_else(|| not_found(input.topup_id))?;
+
+        if input.topup_amount.is_none() && input.topup_method.is_none() {
+            return Ok(original);
+        }
+
+        let now = Utc::now().naive_utc();
+
+        // saldo disesuaikan lebih dulu, topup baru berubah kalau saldo lolos, meniru rollback transaksi
+        let difference = match input.topup_amount {
+            Some(topup_amount) if original.is_success() => {
+                checked_amount(topup_amount.checked_sub(original.topup_amount))?
+            }
+            _ => 0,
+        };
+
+        if difference != 0 {
+            let floor = balance_floor(&tables, input.user_id, DEFAULT_SALDO_LABEL);
+            let saldo = tables
+                .saldos
+                .first_id_where(|saldo| saldo.user_id == input.user_id && saldo.is_default())
+                .and_then(|saldo_id| tables.saldos.get_mut(saldo_id))
+                .ok_or_else(|| {
+                    AppError::NotFound(format!("Saldo with User id {} not found", input.user_id))
+                })?;
+
+            let total_balance = checked_amount(saldo.total_balance.checked_add(difference))?;
+            if total_balance < floor {
+                return Err(insufficient_balance());
+            }
+
+            saldo.total_balance = total_balance;
+            saldo.updated_at = Some(now);
+        }
+
+        let topup = tables
+            .topups
+            .get_mut(input.topup_id)
+            .expect("topup exists while the tables are locked");
+        if let Some(topup_amount) = input.topup_amount {
+            topup.topup_amount = topup_amount;
+        }
+        if let Some(topup_method) = &input.topup_method {
+            topup.topup_method = topup_method.clone();
+        }
+        topup.updated_at = Some(now);
+
+        Ok(topup.clone())
+    }
+
+    async fn update_amount(&self, input: &UpdateTopupAmount) -> Result<Topup, AppError> {
+        let mut tables = self.db.tables();
+        let topup = tables
+            .topups
+            .get_mut(input.topup_id)
+            .ok_or_else(|| not_found(input.topup_id))?;
+
+        topup.topup_amount = input.topup_amount;
+        topup.updated_at = Some(Utc::now().naive_utc());
+
+        Ok(topup.clone())
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), AppError> {
+        self.db
+            .tables()
+            .topups
+            .remove(id)
+            .map(|_| ())
+            .ok_or_else(|| not_found(id))
+    }
+
+    async fn confirm(&self, id: i32) -> Result<Topup, AppError> {
+        let mut tables = self.db.tables();
+
+        // saldo yang akan meluap ditolak sebelum status topup berubah
+        if let Some(topup) = tables.topups.get(id) {
+            let balance = tables
+                .saldos
+                .first_where(|saldo| saldo.user_id == topup.user_id && saldo.is_default())
+                .map_or(0, |saldo| saldo.total_balance);
+            checked_amount(balance.checked_add(topup.topup_amount))?;
+        }
+
+        let confirmed = transition(&mut tables, id, TopupStatus::Pending, TopupStatus::Success)?;
+
+        let saldo_id = tables
+            .saldos
+            .first_id_where(|saldo| saldo.user_id == confirmed.user_id && saldo.is_default());
+
+        match saldo_id.and_then(|saldo_id| tables.saldos.get_mut(saldo_id)) {
+            Some(saldo) => {
+                saldo.total_balance += confirmed.topup_amount;
+                saldo.updated_at = Some(Utc::now().naive_utc());
+            }
+            None => {
+                let now = Utc::now().naive_utc();
+                tables.saldos.insert_with(|saldo_id| Saldo {
+                    saldo_id,
+                    user_id: confirmed.user_id,
+                    total_balance: confirmed.topup_amount,
+                    withdraw_amount: None,
+                    withdraw_time: None,
+                    created_at: Some(now),
+                    updated_at: Some(now),
+                    label: DEFAULT_SALDO_LABEL.to_string(),
+                });
+            }
+        }
+
+        Ok(confirmed)
+    }
+
+    async fn fail(&self, id: i32) -> Result<Topup, AppError> {
+        transition(
+            &mut self.db.tables(),
+            id,
+            TopupStatus::Pending,
+            TopupStatus::Failed,
+        )
+    }
+
+    async fn refund(&self, id: i32) -> Result<Topup, AppError> {
+        let mut tables = self.db.tables();
+
+        let topup = tables
+            .topups
+            .get(id)
+            .cloned()
+            .ok_or_else(|| not_found(id))?;
+        if topup.status != TopupStatus::Success.as_str() {
+            return Err(AppError::Custom(transition_rejection(
+                TopupStatus::Refunded,
+            )));
+        }
+
+        // saldo diperiksa sebelum status berubah, meniru rollback transaksi
+        let floor = balance_floor(&tables, topup.user_id, DEFAULT_SALDO_LABEL);
+        let saldo = tables
+            .saldos
+            .first_id_where(|saldo| saldo.user_id == topup.user_id && saldo.is_default())
+            .and_then(|saldo_id| tables.saldos.get_mut(saldo_id))
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Saldo with User id {} not found", topup.user_id))
+            })?;
+
+        let total_balance = checked_amount(saldo.total_balance.checked_sub(topup.topup_amount))?;
+        if total_balance < floor {
+            return Err(insufficient_balance());
+        }
+
+        let now = Utc::now().naive_utc();
+        saldo.total_balance = total_balance;
+        saldo.updated_at = Some(now);
+
+        let refunded = transition(&mut tables, id, TopupStatus::Success, TopupStatus::Refunded)?;
+
+        let ledger_id = tables.ledger.len() as i64 + 1;
+        tables.ledger.push(LedgerEntry {
+            id: ledger_id,
+            user_id: refunded.user_id,
+            entry_type: LedgerEntryType::TopupRefund.as_str().to_string(),
+            amount: -refunded.topup_amount,
+            reference_id: refunded.topup_id,
+            reason: None,
+            actor_id: None,
+            created_at: Some(now),
+        });
+
+        Ok(refunded)
+    }
+
+    async fn stats_by_method(
+        &self,
+        req: &TopupStatsRequest,
+    ) -> Result<Vec<TopupMethodStats>, AppError> {
+        let (start, end) = (req.start_time(), req.end_time());
+        let topups = self.db.tables().topups.rows_where(|t| {
+            start.is_none_or(|start| t.topup_time >= start)
+                && end.is_none_or(|end| t.topup_time < end)
+        });
+
+        let mut grouped: BTreeMap<String, (i64, i64)> = BTreeMap::new();
+        for topup in topups {
+            let entry = grouped.entry(topup.topup_method).or_default();
+            entry.0 += 1;
+            entry.1 += i64::from(topup.topup_amount);
+        }
+
+        Ok(grouped
+            .into_iter()
+            .map(
+                |(topup_method, (total_count, total_amount))| TopupMethodStats {
+                    topup_method,
+                    total_count,
+                    total_amount,
+                },
+            )
+            .collect())
+    }
+}
@@ -0,0 +1,506 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{NaiveDate, NaiveDateTime, Utc};
+use std::{collections::BTreeMap, sync::Arc, time::Duration};
+
+use crate::{
+    abstract_trait::TransferRepositoryTrait,
+    domain::request::{
+        CreateTransferRequest, DailyTransferTotalsRequest, StreamTransfersRequest, TransferFilter,
+        UpdateTransferAmountRequest, UpdateTransferRequest,
+    },
+    model::{
+        outbox::{OutboxEvent, OutboxStatus, TransferEvent},
+        transfer::{DailyTransferTotal, Transfer, TransferPreview, TransferStatus},
+    },
+    repository::memory::{InMemoryDatabase, Tables, balance_floor, insufficient_balance, paginate},
+    utils::{AppError, checked_amount},
+};
+
+pub struct InMemoryTransferRepository {
+    db: Arc<InMemoryDatabase>,
+}
+
+impl InMemoryTransferRepository {
+    pub fn new(db: Arc<InMemoryDatabase>) -> Self {
+        Self { db }
+    }
+}
+
+fn not_found(id: i32) -> AppError {
+    AppError::NotFound(format!("Transfer with ID {id} not found"))
+}
+
+// dipanggil setelah baris transfer dipastikan ada
+fn set_status(tables: &mut Tables, id: i32, status: TransferStatus) -> Transfer {
+    let transfer = tables
+        .transfers
+        .get_mut(id)
+        .expect("transfer exists while the tables are locked");
+    transfer.status = status.as_str().to_string();
+    transfer.updated_at = Some(Utc::now().naive_utc());
+    transfer.clone()
+}
+
+// validasi dan saldo akhir (saldo_id, saldo baru) tanpa mengubah tabel, dipakai juga oleh dry run
+fn project(tables: &Tables, input: &CreateTransferRequest) -> Result<[(i32, i32); 2], AppError> {
+    let mut saldo_ids = Vec::with_capacity(2);
+    for (user_id, label) in [
+        (input.transfer_from, input.from_label()),
+        (input.transfer_to, input.to_label()),
+    ] {
+        let saldo_id = tables
+            .saldos
+            .first_id_where(|saldo| saldo.user_id == user_id && saldo.label == label)
+            .ok_or_else(|| {
+                AppError::NotFound(format!("Saldo '{label}' with User id {user_id} not found"))
+            })?;
+        saldo_ids.push(saldo_id);
+    }
+
+    let balance = |saldo_id: i32| {
+        tables
+            .saldos
+            .get(saldo_id)
+            .map_or(0, |saldo| saldo.total_balance)
+    };
+
+    let sender_balance = checked_amount(balance(saldo_ids[0]).checked_sub(input.transfer_amount))?;
+    if sender_balance < balance_floor(tables, input.transfer_from, input.from_label()) {
+        return Err(insufficient_balance());
+    }
+
+    let receiver_balance =
+        checked_amount(balance(saldo_ids[1]).checked_add(input.transfer_amount))?;
+
+    Ok([
+        (saldo_ids[0], sender_balance),
+        (saldo_ids[1], receiver_balance),
+    ])
+}
+
+fn build_transfer(
+    transfer_id: i32,
+    input: &CreateTransferRequest,
+    status: TransferStatus,
+) -> Transfer {
+    let now = Utc::now().naive_utc();
+    Transfer {
+        transfer_id,
+        transfer_from: input.transfer_from,
+        transfer_to: input.transfer_to,
+        transfer_amount: input.transfer_amount,
+        transfer_time: now,
+        created_at: Some(now),
+        updated_at: Some(now),
+        note: input.note.clone(),
+        status: status.as_str().to_string(),
+        from_label: input.from_label().to_string(),
+        to_label: input.to_label().to_string(),
+    }
+}
+
+// validasi dilakukan sebelum ada perubahan, meniru rollback transaksi;
+// transfer needs_review hanya dicatat, saldonya ditahan sampai disetujui
+fn settle(
+    tables: &mut Tables,
+    input: &CreateTransferRequest,
+    status: TransferStatus,
+) -> Result<Transfer, AppError> {
+    let balances = project(tables, input)?;
+
+    let transfer = tables
+        .transfers
+        .insert_with(|transfer_id| build_transfer(transfer_id, input, status));
+
+    if status == TransferStatus::NeedsReview {
+        enqueue(tables, &TransferEvent::needs_review(&transfer))?;
+    } else {
+        apply_balances(tables, balances, transfer.transfer_time);
+        enqueue(tables, &TransferEvent::completed(&transfer))?;
+    }
+
+    Ok(transfer)
+}
+
+fn apply_balances(tables: &mut Tables, balances: [(i32, i32); 2], now: NaiveDateTime) {
+    for (saldo_id, balance) in balances {
+        if let Some(saldo) = tables.saldos.get_mut(saldo_id) {
+            saldo.total_balance = balance;
+            saldo.updated_at = Some(now);
+        }
+    }
+}
+
+fn enqueue(tables: &mut Tables, event: &TransferEvent) -> Result<(), AppError> {
+    let payload =
+        serde_json::to_string(event).map_err(|e| AppError::InternalError(e.to_string()))?;
+    let now = Utc::now().naive_utc();
+    let id = tables.outbox.len() as i64 + 1;
+    tables.outbox.push(OutboxEvent {
+        id,
+        event_type: event.event_type.clone(),
+        payload,
+        status: OutboxStatus::Pending.as_str().to_string(),
+        attempts: 0,
+        last_error: None,
+        next_attempt_at: now,
+        created_at: Some(now),
+        delivered_at: None,
+    });
+
+    Ok(())
+}
+
+#[async_trait]
+impl TransferRepositoryTrait for InMemoryTransferRepository {
+    async fn find_all(
+        &self,
+        page: i32,
+        page_size: i32,
+        filter: &TransferFilter,
+    ) -> Result<(Vec<Transfer>, i64), AppError> {
+        let transfers = self.db.tables().transfers.rows_where(|transfer| {
+            filter.search.as_ref().is_none_or(|term| {
+                transfer
+                    .transfer_from
+                    .to_string()
+                    .starts_with(term.as_str())
+            }) && filter
+                .min_amount
+                .is_none_or(|min| transfer.transfer_amount >= min)
+                && filter
+                    .max_amount
+                    .is_none_or(|max| transfer.transfer_amount <= max)
+                && (filter.statuses.is_empty()
+                    || filter
+                        .statuses
+                        .iter()
+                        .any(|status| transfer.status == status.as_str()))
+        });
+
+        Ok(paginate(transfers, page, page_size))
+    }
+
+    async fn find_batch_after(
+        &self,
+        after_id: i32,
+        req: &StreamTransfersRequest,
+    ) -> Result<Vec<Transfer>, AppError> {
+        let (start, end) = (req.start_time(), req.end_time());
+        let transfers = self.db.tables().transfers.rows_where(|transfer| {
+            transfer.transfer_id > after_id
+                && start.is_none_or(|start| transfer.transfer_time >= start)
+                && end.is_none_or(|end| transfer.transfer_time < end)
+        });
+
+        Ok(transfers
+            .into_iter()
+            .take(req.batch_size.max(1) as usize)
+            .collect())
+    }
+
+    async fn daily_totals(
+        &self,
+        req: &DailyTransferTotalsRequest,
+    ) -> Result<Vec<DailyTransferTotal>, AppError> {
+        let (start, end) = (req.start_time(), req.end_time());
+        let transfers =
+            self.db.tables().transfers.rows_where(|transfer| {
+                transfer.transfer_time >= start && transfer.transfer_time < end
+            });
+
+        let mut grouped: BTreeMap<(NaiveDate, i32), (i64, i64)> = BTreeMap::new();
+        for transfer in transfers {
+            let day = transfer.transfer_time.date();
+            let amount = i64::from(transfer.transfer_amount);
+            grouped.entry((day, transfer.transfer_from)).or_default().0 += amount;
+            grouped.entry((day, transfer.transfer_to)).or_default().1 += amount;
+        }
+
+        Ok(grouped
+            .into_iter()
+            .map(
+                |((day, user_id), (total_sent, total_received))| DailyTransferTotal {
+                    user_id,
+                    day,
+                    total_sent,
+                    total_received,
+                },
+            )
+            .collect())
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Transfer>, AppError> {
+        Ok(self.db.tables().transfers.get(id).cloned())
+    }
+
+    async fn find_by_users(
+        &self,
+        id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Transfer>, i64), AppError> {
+        let rows = self
+            .db
+            .tables()
+            .transfers
+            .rows_where(|transfer| transfer.transfer_from == id);
+
+        Ok(paginate(rows, page, page_size))
+    }
+
+    async fn find_all_involving_user(
+        &self,
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Transfer>, i64), AppError> {
+        let mut rows = self.db.tables().transfers.rows_where(|transfer| {
+            transfer.transfer_from == user_id || transfer.transfer_to == user_id
+        });
+        rows.sort_by(|a, b| {
+            b.transfer_time
+                .cmp(&a.transfer_time)
+                .then(b.transfer_id.cmp(&a.transfer_id))
+        });
+
+        Ok(paginate(rows, page, page_size))
+    }
+
+    async fn find_by_user(&self, id: i32) -> Result<Option<Transfer>, AppError> {
+        Ok(self
+            .db
+            .tables()
+            .transfers
+            .first_where(|transfer| transfer.transfer_from == id))
+    }
+
+    async fn create(&self, input: &CreateTransferRequest) -> Result<Transfer, AppError> {
+        let now = Utc::now().naive_utc();
+
+        Ok(self
+            .db
+            .tables()
+            .transfers
+            .insert_with(|transfer_id| Transfer {
+                transfer_id,
+                transfer_from: input.transfer_from,
+                transfer_to: input.transfer_to,
+                transfer_amount: input.transfer_amount,
+                transfer_time: now,
+                created_at: Some(now),
+                updated_at: Some(now),
+                note: input.note.clone(),
+                status: TransferStatus::Completed.as_str().to_string(),
+                from_label: input.from_label().to_string(),
+                to_label: input.to_label().to_string(),
+            }))
+    }
+
+    async fn count_recent_by_sender(
+        &self,
+        user_id: i32,
+        window: Duration,
+    ) -> Result<i64, AppError> {
+        // tidak ada jam database di sini, jam aplikasi dipakai sebagai gantinya
+        let since = Utc::now().naive_utc() - window;
+
+        let tables = self.db.tables();
+        let rows = tables.transfers.rows_where(|transfer| {
+            transfer.transfer_from == user_id
+                && transfer.transfer_to != user_id
+                && !tables.reversal_of.contains_key(&transfer.transfer_id)
+                && transfer.created_at.is_some_and(|created| created >= since)
+        });
+
+        Ok(rows.len() as i64)
+    }
+
+    async fn create_with_settlement(
+        &self,
+        input: &CreateTransferRequest,
+        status: TransferStatus,
+    ) -> Result<Transfer, AppError> {
+        settle(&mut self.db.tables(), input, status)
+    }
+
+    async fn preview_settlement(
+        &self,
+        input: &CreateTransferRequest,
+        status: TransferStatus,
+    ) -> Result<TransferPreview, AppError> {
+        let [(_, sender_balance), (_, receiver_balance)] = project(&self.db.tables(), input)?;
+
+        Ok(TransferPreview {
+            transfer: build_transfer(0, input, status),
+            sender_balance,
+            receiver_balance,
+        })
+    }
+
+    async fn reverse(&self, id: i32) -> Result<Transfer, AppError> {
+        let mut tables = self.db.tables();
+        let original = tables
+            .transfers
+            .get(id)
+            .cloned()
+            .ok_or_else(|| not_found(id))?;
+
+        if original.is_reversed() {
+            return Err(AppError::Custom(format!(
+                "Transfer with ID {id} has already been reversed"
+            )));
+        }
+
+        if tables.reversal_of.contains_key(&id) {
+            return Err(AppError::Custom(format!(
+                "Transfer with ID {id} is a reversal and cannot be reversed"
+            )));
+        }
+
+        if original.needs_review() {
+            return Ok(set_status(&mut tables, id, TransferStatus::Reversed));
+        }
+
+        let compensation = CreateTransferRequest {
+            transfer_from: original.transfer_to,
+            transfer_to: original.transfer_from,
+            transfer_amount: original.transfer_amount,
+            note: Some(format!("Reversal of transfer #{id}")),
+            dry_run: false,
+            from_label: Some(original.to_label.clone()),
+            to_label: Some(original.from_label.clone()),
+        };
+
+        let reversal = settle(&mut tables, &compensation, TransferStatus::Completed)?;
+        tables.reversal_of.insert(reversal.transfer_id, id);
+        set_status(&mut tables, id, TransferStatus::Reversed);
+
+        Ok(reversal)
+    }
+
+    async fn approve(&self, id: i32) -> Result<Transfer, AppError> {
+        let mut tables = self.db.tables();
+        let original = tables
+            .transfers
+            .get(id)
+            .cloned()
+            .ok_or_else(|| not_found(id))?;
+
+        if !original.needs_review() {
+            return Err(AppError::Custom(format!(
+                "Transfer with ID {id} is not awaiting review"
+            )));
+        }
+
+        let input = CreateTransferRequest {
+            transfer_from: original.transfer_from,
+            transfer_to: original.transfer_to,
+            transfer_amount: original.transfer_amount,
+            note: original.note.clone(),
+            dry_run: false,
+            from_label: Some(original.from_label.clone()),
+            to_label: Some(original.to_label.clone()),
+        };
+        let balances = project(&tables, &input)?;
+
+        let approved = set_status(&mut tables, id, TransferStatus::Completed);
+        apply_balances(&mut tables, balances, Utc::now().naive_utc());
+        enqueue(&mut tables, &TransferEvent::completed(&approved))?;
+
+        Ok(approved)
+    }
+
+    async fn update_with_settlement(
+        &self,
+        input: &UpdateTransferRequest,
+    ) -> Result<Transfer, AppError> {
+        let mut tables = self.db.tables();
+        let original = tables
+            .transfers
+            .get(input.transfer_id)
+            .cloned()
+            .ok_or_else(|| not_found(input.transfer_id))?;
+
+        if !original.is_completed() {
+            return Err(AppError::Custom(format!(
+                "Only completed transfers can be edited, transfer {} is {}",
+                original.transfer_id, original.status
+            )));
+        }
+
+        // selisih nominal diproyeksikan seperti transfer baru antara pihak yang sama
+        let adjustment = CreateTransferRequest {
+            transfer_from: original.transfer_from,
+            transfer_to: original.transfer_to,
+            transfer_amount: checked_amount(
+                input.transfer_amount.checked_sub(original.transfer_amount),
+            )?,
+            note: None,
+            dry_run: false,
+            from_label: Some(original.from_label.clone()),
+            to_label: Some(original.to_label.clone()),
+        };
+        let balances = project(&tables, &adjustment)?;
+
+        let now = Utc::now().naive_utc();
+        apply_balances(&mut tables, balances, now);
+
+        let transfer = tables
+            .transfers
+            .get_mut(input.transfer_id)
+            .expect("transfer exists while the tables are locked");
+        transfer.transfer_amount = input.transfer_amount;
+        transfer.transfer_time = now;
+        transfer.updated_at = Some(now);
+        transfer.note = input.note.clone();
+
+        Ok(transfer.clone())
+    }
+
+    async fn update(&self, input: &UpdateTransferRequest) -> Result<Transfer, AppError> {
+        let mut tables = self.db.tables();
+        let transfer = tables
+            .transfers
+            .get_mut(input.transfer_id)
+            .ok_or_else(|| not_found(input.transfer_id))?;
+
+        let now = Utc::now().naive_utc();
+        transfer.transfer_from = input.transfer_from;
+        transfer.transfer_to = input.transfer_to;
+        transfer.transfer_amount = input.transfer_amount;
+        transfer.transfer_time = now;
+        transfer.updated_at = Some(now);
+        transfer.note = input.note.clone();
+
+        Ok(transfer.clone())
+    }
+
+    async fn update_amount(
+        &self,
+        input: &UpdateTransferAmountRequest,
+    ) -> Result<Transfer, AppError> {
+        let mut tables = self.db.tables();
+        let transfer = tables
+            .transfers
+            .get_mut(input.transfer_id)
+            .ok_or_else(|| not_found(input.transfer_id))?;
+
+        transfer.transfer_amount = input.transfer_amount;
+        transfer.updated_at = Some(Utc::now().naive_utc());
+
+        Ok(transfer.clone())
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), AppError> {
+        let mut tables = self.db.tables();
+        tables.transfers.remove(id).ok_or_else(|| not_found(id))?;
+
+        // meniru ON DELETE SET NULL pada transfers.reversal_of
+        tables.reversal_of.remove(&id);
+        tables.reversal_of.retain(|_, original| *original != id);
+
+        Ok(())
+    }
+}
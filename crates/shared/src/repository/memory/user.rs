@@ -0,0 +1,358 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::{
+    abstract_trait::UserRepositoryTrait,
+    domain::request::{
+        normalize_email,
+        user::{CreateUserRequest, UpdateUserRequest, UserFilter},
+    },
+    model::{
+        saldo::{DEFAULT_SALDO_LABEL, Saldo},
+        user::{User, UserDeleteBlockers, UserPurge, UserStatus},
+    },
+    repository::memory::{InMemoryDatabase, Tables, paginate},
+    utils::AppError,
+};
+
+pub struct InMemoryUserRepository {
+    db: Arc<InMemoryDatabase>,
+}
+
+impl InMemoryUserRepository {
+    pub fn new(db: Arc<InMemoryDatabase>) -> Self {
+        Self { db }
+    }
+}
+
+fn matches_filter(user: &User, filter: &UserFilter) -> bool {
+    let created_at = user.created_at;
+
+    filter
+        .search
+        .as_ref()
+        .is_none_or(|term| user.email.starts_with(term.as_str()))
+        && filter
+            .email
+            .as_ref()
+            .is_none_or(|email| user.email == normalize_email(email))
+        && filter
+            .created_after
+            .is_none_or(|after| created_at.is_some_and(|at| at >= after))
+        && filter
+            .created_before
+            .is_none_or(|before| created_at.is_some_and(|at| at < before))
+        && (filter.include_deleted || user.deleted_at.is_none())
+}
+
+fn not_found(id: i32) -> AppError {
+    AppError::NotFound(format!("User with ID {id} not found"))
+}
+
+fn insert_user(tables: &mut Tables, input: &CreateUserRequest) -> Result<User, AppError> {
+    if tables
+        .users
+        .first_id_where(|user| user.email == normalize_email(&input.email))
+        .is_some()
+    {
+        return Err(AppError::EmailAlreadyExists);
+    }
+
+    let now = Utc::now().naive_utc();
+
+    let user = tables.users.insert_with(|user_id| User {
+        user_id,
+        firstname: input.firstname.clone(),
+        lastname: input.lastname.clone(),
+        email: normalize_email(&input.email),
+        password: input.password.clone(),
+        noc_transfer: input.noc_transfer.clone().unwrap_or_default(),
+        created_at: Some(now),
+        updated_at: Some(now),
+        deleted_at: None,
+        password_changed_at: None,
+        status: UserStatus::Active.as_str().to_string(),
+        overdraft_limit: 0,
+    });
+
+    if tables
+        .saldos
+        .first_id_where(|saldo| saldo.user_id == user.user_id && saldo.is_default())
+        .is_none()
+    {
+        tables.saldos.insert_with(|saldo_id| Saldo {
+            saldo_id,
+            user_id: user.user_id,
+            total_balance: 0,
+            withdraw_amount: None,
+            withdraw_time: None,
+            created_at: Some(now),
+            updated_at: Some(now),
+            label: DEFAULT_SALDO_LABEL.to_string(),
+        });
+    }
+
+    Ok(user)
+}
+
+#[async_trait]
+impl UserRepositoryTrait for InMemoryUserRepository {
+    async fn find_all(
+        &self,
+        page: i32,
+        page_size: i32,
+        filter: &UserFilter,
+    ) -> Result<(Vec<User>, i64), AppError> {
+        let users = self
+            .db
+            .tables()
+            .users
+            .rows_where(|user| matches_filter(user, filter));
+
+        Ok(paginate(users, page, page_size))
+    }
+
+    async fn find_by_email_exists(&self, email: &str) -> Result<bool, AppError> {
+        Ok(self
+            .db
+            .tables()
+            .users
+            .first_id_where(|user| user.email == normalize_email(email))
+            .is_some())
+    }
+
+    async fn create_user(&self, input: &CreateUserRequest) -> Result<User, AppError> {
+        insert_user(&mut self.db.tables(), input)
+    }
+
+    async fn create_many(
+        &self,
+        inputs: &[CreateUserRequest],
+        continue_on_error: bool,
+    ) -> Result<Vec<Result<User, AppError>>, AppError> {
+        let mut tables = self.db.tables();
+        let mut results = Vec::with_capacity(inputs.len());
+
+        for input in inputs {
+            let result = insert_user(&mut tables, input);
+            let failed = result.is_err();
+            results.push(result);
+
+            // meniru rollback transaksi: user yang sudah masuk dihapus lagi
+            if failed && !continue_on_error {
+                for user in results.iter().filter_map(|r| r.as_ref().ok()) {
+                    tables.users.remove(user.user_id);
+                    tables
+                        .saldos
+                        .remove_where(|saldo| saldo.user_id == user.user_id);
+                }
+                break;
+            }
+        }
+
+        Ok(results)
+    }
+
+    async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
+        Ok(self
+            .db
+            .tables()
+            .users
+            .first_where(|user| user.email == normalize_email(email) && user.deleted_at.is_none()))
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<User>, AppError> {
+        Ok(self
+            .db
+            .tables()
+            .users
+            .get(id)
+            .filter(|user| user.deleted_at.is_none())
+            .cloned())
+    }
+
+    async fn exists_by_id(&self, id: i32) -> Result<bool, AppError> {
+        Ok(self
+            .db
+            .tables()
+            .users
+            .get(id)
+            .is_some_and(|user| user.deleted_at.is_none()))
+    }
+
+    async fn update_user(&self, input: &UpdateUserRequest) -> Result<User, AppError> {
+        let mut tables = self.db.tables();
+
+        // meniru index unik LOWER(email) di Postgres
+        if let Some(email) = &input.email {
+            let email = normalize_email(email);
+            if tables
+                .users
+                .first_id_where(|user| user.email == email && user.user_id != input.id)
+                .is_some()
+            {
+                return Err(AppError::EmailAlreadyExists);
+            }
+        }
+
+        let user = tables
+            .users
+            .get_mut(input.id)
+            .filter(|user| user.deleted_at.is_none())
+            .ok_or_else(|| not_found(input.id))?;
+
+        if let Some(firstname) = &input.firstname {
+            user.firstname = firstname.clone();
+        }
+        if let Some(lastname) = &input.lastname {
+            user.lastname = lastname.clone();
+        }
+        if let Some(email) = &input.email {
+            user.email = normalize_email(email);
+        }
+
+        let now = Utc::now().naive_utc();
+        if let Some(password) = &input.password {
+            user.password = password.clone();
+            user.password_changed_at = Some(now);
+        }
+
+        // tanpa field apa pun user dikembalikan apa adanya
+        if input.firstname.is_some()
+            || input.lastname.is_some()
+            || input.email.is_some()
+            || input.password.is_some()
+        {
+            user.updated_at = Some(now);
+        }
+
+        Ok(user.clone())
+    }
+
+    async fn find_delete_blockers(&self, id: i32) -> Result<UserDeleteBlockers, AppError> {
+        let tables = self.db.tables();
+        if tables
+            .users
+            .get(id)
+            .filter(|user| user.deleted_at.is_none())
+            .is_none()
+        {
+            return Err(AppError::NotFound(format!("User ID {id} not found")));
+        }
+
+        let now = Utc::now().naive_utc();
+        Ok(UserDeleteBlockers {
+            incoming_transfers: tables
+                .transfers
+                .rows_where(|t| t.transfer_to == id && t.transfer_from != id)
+                .len() as i64,
+            active_sessions: tables
+                .sessions
+                .rows_where(|session| session.user_id == id && session.is_active(now))
+                .len() as i64,
+        })
+    }
+
+    async fn delete_user(&self, id: i32) -> Result<(), AppError> {
+        let mut tables = self.db.tables();
+        let user = tables
+            .users
+            .get_mut(id)
+            .filter(|user| user.deleted_at.is_none())
+            .ok_or_else(|| AppError::NotFound(format!("User ID {id} not found")))?;
+
+        user.deleted_at = Some(Utc::now().naive_utc());
+        Ok(())
+    }
+
+    async fn restore_user(&self, id: i32) -> Result<User, AppError> {
+        let mut tables = self.db.tables();
+        let user = tables
+            .users
+            .get_mut(id)
+            .filter(|user| user.deleted_at.is_some())
+            .ok_or_else(|| AppError::NotFound(format!("Deleted user with ID {id} not found")))?;
+
+        user.deleted_at = None;
+        user.updated_at = Some(Utc::now().naive_utc());
+        Ok(user.clone())
+    }
+
+    async fn update_password(&self, id: i32, password: &str) -> Result<User, AppError> {
+        let mut tables = self.db.tables();
+        let user = tables
+            .users
+            .get_mut(id)
+            .filter(|user| user.deleted_at.is_none())
+            .ok_or_else(|| not_found(id))?;
+
+        let now = Utc::now().naive_utc();
+        user.password = password.to_string();
+        user.password_changed_at = Some(now);
+        user.updated_at = Some(now);
+        Ok(user.clone())
+    }
+
+    async fn rehash_password(&self, id: i32, password: &str) -> Result<(), AppError> {
+        let mut tables = self.db.tables();
+        let user = tables
+            .users
+            .get_mut(id)
+            .filter(|user| user.deleted_at.is_none())
+            .ok_or_else(|| not_found(id))?;
+
+        user.password = password.to_string();
+        Ok(())
+    }
+
+    async fn update_status(&self, id: i32, status: UserStatus) -> Result<User, AppError> {
+        let mut tables = self.db.tables();
+        let user = tables
+            .users
+            .get_mut(id)
+            .filter(|user| user.deleted_at.is_none())
+            .ok_or_else(|| not_found(id))?;
+
+        user.status = status.as_str().to_string();
+        user.updated_at = Some(Utc::now().naive_utc());
+        Ok(user.clone())
+    }
+
+    async fn purge_user(&self, id: i32, force: bool) -> Result<UserPurge, AppError> {
+        let mut tables = self.db.tables();
+        if tables.users.get(id).is_none() {
+            return Err(AppError::NotFound(format!("User ID {id} not found")));
+        }
+
+        let received = tables
+            .transfers
+            .rows_where(|t| t.transfer_to == id && t.transfer_from != id)
+            .len();
+        if !force && received > 0 {
+            return Err(AppError::Custom(format!(
+                "User ID {id} is the counterparty of {received} transfer(s) from other users, use force to purge"
+            )));
+        }
+
+        let purge = UserPurge {
+            transfers: tables
+                .transfers
+                .remove_where(|t| t.transfer_from == id || t.transfer_to == id),
+            topups: tables.topups.remove_where(|topup| topup.user_id == id),
+            withdraws: tables.withdraws.remove_where(|w| w.user_id == id),
+            saldos: tables.saldos.remove_where(|saldo| saldo.user_id == id),
+        };
+
+        // meniru ON DELETE CASCADE
+        tables.ledger.retain(|entry| entry.user_id != id);
+        tables.virtual_cards.remove_where(|card| card.user_id == id);
+        tables
+            .sessions
+            .remove_where(|session| session.user_id == id);
+        tables.users.remove(id);
+
+        Ok(purge)
+    }
+}
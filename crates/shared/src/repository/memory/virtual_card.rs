@@ -0,0 +1,87 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use std::sync::Arc;
+
+use crate::{
+    abstract_trait::VirtualCardRepositoryTrait,
+    domain::request::CreateVirtualCardRequest,
+    model::virtual_card::VirtualCard,
+    repository::memory::{InMemoryDatabase, paginate},
+    utils::AppError,
+};
+
+pub struct InMemoryVirtualCardRepository {
+    db: Arc<InMemoryDatabase>,
+}
+
+impl InMemoryVirtualCardRepository {
+    pub fn new(db: Arc<InMemoryDatabase>) -> Self {
+        Self { db }
+    }
+}
+
+#[async_trait]
+impl VirtualCardRepositoryTrait for InMemoryVirtualCardRepository {
+    async fn create(&self, input: &CreateVirtualCardRequest) -> Result<VirtualCard, AppError> {
+        let mut tables = self.db.tables();
+
+        // meniru UNIQUE(card_number) di Postgres
+        if tables
+            .virtual_cards
+            .first_id_where(|card| card.card_number == input.card_number)
+            .is_some()
+        {
+            return Err(AppError::Custom("Card number already exists".to_string()));
+        }
+
+        let now = Utc::now().naive_utc();
+
+        Ok(tables
+            .virtual_cards
+            .insert_with(|virtual_card_id| VirtualCard {
+                virtual_card_id,
+                user_id: input.user_id,
+                card_number: input.card_number.clone(),
+                expiry_month: input.expiry_month,
+                expiry_year: input.expiry_year,
+                created_at: Some(now),
+                updated_at: Some(now),
+                revoked_at: None,
+            }))
+    }
+
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+        include_revoked: bool,
+    ) -> Result<(Vec<VirtualCard>, i64), AppError> {
+        let mut rows = self.db.tables().virtual_cards.rows_where(|card| {
+            card.user_id == user_id && (include_revoked || card.revoked_at.is_none())
+        });
+        rows.reverse();
+
+        Ok(paginate(rows, page, page_size))
+    }
+
+    async fn revoke(&self, user_id: i32, virtual_card_id: i32) -> Result<VirtualCard, AppError> {
+        let mut tables = self.db.tables();
+        let card = tables
+            .virtual_cards
+            .get_mut(virtual_card_id)
+            .filter(|card| card.user_id == user_id && card.revoked_at.is_none())
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "Active virtual card {virtual_card_id} not found for user {user_id}"
+                ))
+            })?;
+
+        let now = Utc::now().naive_utc();
+        card.revoked_at = Some(now);
+        card.updated_at = Some(now);
+
+        Ok(card.clone())
+    }
+}
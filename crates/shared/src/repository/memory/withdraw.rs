@@ -0,0 +1,231 @@
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{DateTime, NaiveDateTime, Utc};
+use std::sync::Arc;
+
+use crate::{
+    abstract_trait::WithdrawRepositoryTrait,
+    domain::request::{CreateWithdrawRequest, UpdateWithdrawRequest},
+    model::{saldo::DEFAULT_SALDO_LABEL, withdraw::Withdraw},
+    repository::memory::{InMemoryDatabase, balance_floor, insufficient_balance, paginate},
+    utils::{AppError, checked_amount},
+};
+
+pub struct InMemoryWithdrawRepository {
+    db: Arc<InMemoryDatabase>,
+}
+
+impl InMemoryWithdrawRepository {
+    pub fn new(db: Arc<InMemoryDatabase>) -> Self {
+        Self { db }
+    }
+}
+
+fn not_found(id: i32) -> AppError {
+    AppError::NotFound(format!("Withdraw with ID {id} not found"))
+}
+
+fn parse_withdraw_time(value: &str) -> Result<NaiveDateTime, AppError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc).naive_utc())
+        .map_err(|_| {
+            AppError::Custom(
+                "Invalid datetime format. Use RFC3339 (e.g., 2024-01-01T12:00:00Z).".to_string(),
+            )
+        })
+}
+
+#[async_trait]
+impl WithdrawRepositoryTrait for InMemoryWithdrawRepository {
+    async fn find_all(
+        &self,
+        page: i32,
+        page_size: i32,
+        search: Option<String>,
+    ) -> Result<(Vec<Withdraw>, i64), AppError> {
+        let search_id = search.and_then(|term| term.parse::<i32>().ok());
+        let withdraws = self
+            .db
+            .tables()
+            .withdraws
+            .rows_where(|withdraw| search_id.is_none_or(|id| withdraw.withdraw_id == id));
+
+        Ok(paginate(withdraws, page, page_size))
+    }
+
+    async fn find_by_id(&self, id: i32) -> Result<Option<Withdraw>, AppError> {
+        Ok(self.db.tables().withdraws.get(id).cloned())
+    }
+
+    async fn exists_by_id(&self, id: i32) -> Result<bool, AppError> {
+        Ok(self.db.tables().withdraws.get(id).is_some())
+    }
+
+    async fn find_by_users(
+        &self,
+        id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Withdraw>, i64), AppError> {
+        let rows = self
+            .db
+            .tables()
+            .withdraws
+            .rows_where(|withdraw| withdraw.user_id == id);
+
+        Ok(paginate(rows, page, page_size))
+    }
+
+    async fn find_by_user(&self, id: i32) -> Result<Option<Withdraw>, AppError> {
+        Ok(self
+            .db
+            .tables()
+            .withdraws
+            .first_where(|withdraw| withdraw.user_id == id))
+    }
+
+    async fn find_recent_by_user(
+        &self,
+        user_id: i32,
+        limit: i32,
+    ) -> Result<Vec<Withdraw>, AppError> {
+        let mut rows = self
+            .db
+            .tables()
+            .withdraws
+            .rows_where(|withdraw| withdraw.user_id == user_id);
+        rows.reverse();
+        rows.truncate(limit.max(0) as usize);
+
+        Ok(rows)
+    }
+
+    async fn create(&self, input: &CreateWithdrawRequest) -> Result<Withdraw, AppError> {
+        let withdraw_time = parse_withdraw_time(&input.withdraw_time)?;
+        let now = Utc::now().naive_utc();
+
+        Ok(self
+            .db
+            .tables()
+            .withdraws
+            .insert_with(|withdraw_id| Withdraw {
+                withdraw_id,
+                user_id: input.user_id,
+                withdraw_amount: input.withdraw_amount,
+                withdraw_time,
+                created_at: Some(now),
+                updated_at: Some(now),
+            }))
+    }
+
+    async fn create_with_settlement(
+        &self,
+        input: &CreateWithdrawRequest,
+    ) -> Result<Withdraw, AppError> {
+        let withdraw_time = parse_withdraw_time(&input.withdraw_time)?;
+
+        let mut tables = self.db.tables();
+        let saldo_id = tables
+            .saldos
+            .first_id_where(|saldo| saldo.user_id == input.user_id && saldo.is_default())
+            .ok_or_else(|| AppError::NotFound("Saldo not found".to_string()))?;
+        let floor = balance_floor(&tables, input.user_id, DEFAULT_SALDO_LABEL);
+        let saldo = tables
+            .saldos
+            .get_mut(saldo_id)
+            .expect("saldo exists while the tables are locked");
+
+        let total_balance = checked_amount(saldo.total_balance.checked_sub(input.withdraw_amount))?;
+        if total_balance < floor {
+            return Err(insufficient_balance());
+        }
+
+        let now = Utc::now().naive_utc();
+        saldo.total_balance = total_balance;
+        saldo.withdraw_amount = Some(input.withdraw_amount);
+        saldo.withdraw_time = Some(now);
+        saldo.updated_at = Some(now);
+
+        Ok(tables.withdraws.insert_with(|withdraw_id| Withdraw {
+            withdraw_id,
+            user_id: input.user_id,
+            withdraw_amount: input.withdraw_amount,
+            withdraw_time,
+            created_at: Some(now),
+            updated_at: Some(now),
+        }))
+    }
+
+    async fn update(&self, input: &UpdateWithdrawRequest) -> Result<Withdraw, AppError> {
+        let withdraw_time = parse_withdraw_time(&input.withdraw_time)?;
+
+        let mut tables = self.db.tables();
+        let withdraw = tables
+            .withdraws
+            .get_mut(input.withdraw_id)
+            .ok_or_else(|| not_found(input.withdraw_id))?;
+
+        withdraw.withdraw_amount = input.withdraw_amount;
+        withdraw.withdraw_time = withdraw_time;
+        withdraw.updated_at = Some(Utc::now().naive_utc());
+
+        Ok(withdraw.clone())
+    }
+
+    async fn update_with_settlement(
+        &self,
+        input: &UpdateWithdrawRequest,
+    ) -> Result<Withdraw, AppError> {
+        let withdraw_time = parse_withdraw_time(&input.withdraw_time)?;
+
+        let mut tables = self.db.tables();
+        let original = tables
+            .withdraws
+            .get(input.withdraw_id)
+            .filter(|withdraw| withdraw.user_id == input.user_id)
+            .cloned()
+            .ok_or_else(|| not_found(input.withdraw_id))?;
+
+        let difference =
+            checked_amount(input.withdraw_amount.checked_sub(original.withdraw_amount))?;
+        let saldo_id = tables
+            .saldos
+            .first_id_where(|saldo| saldo.user_id == input.user_id && saldo.is_default())
+            .ok_or_else(|| AppError::NotFound("Saldo not found".to_string()))?;
+        let floor = balance_floor(&tables, input.user_id, DEFAULT_SALDO_LABEL);
+        let saldo = tables
+            .saldos
+            .get_mut(saldo_id)
+            .expect("saldo exists while the tables are locked");
+
+        let total_balance = checked_amount(saldo.total_balance.checked_sub(difference))?;
+        if total_balance < floor {
+            return Err(insufficient_balance());
+        }
+
+        let now = Utc::now().naive_utc();
+        saldo.total_balance = total_balance;
+        saldo.withdraw_amount = Some(input.withdraw_amount);
+        saldo.withdraw_time = Some(now);
+        saldo.updated_at = Some(now);
+
+        let withdraw = tables
+            .withdraws
+            .get_mut(input.withdraw_id)
+            .expect("withdraw exists while the tables are locked");
+        withdraw.withdraw_amount = input.withdraw_amount;
+        withdraw.withdraw_time = withdraw_time;
+        withdraw.updated_at = Some(now);
+
+        Ok(withdraw.clone())
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), AppError> {
+        self.db
+            .tables()
+            .withdraws
+            .remove(id)
+            .map(|_| ())
+            .ok_or_else(|| not_found(id))
+    }
+}
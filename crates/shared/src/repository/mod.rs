@@ -1,5 +1,15 @@
+pub mod outbox;
+pub mod retry;
 pub mod saldo;
+pub mod session;
 pub mod topup;
 pub mod transfer;
 pub mod user;
+pub mod virtual_card;
 pub mod withdraw;
+
+#[cfg(any(test, feature = "test-utils"))]
+pub mod memory;
+
+#[cfg(test)]
+pub(crate) mod test_db;
@@ -0,0 +1,285 @@
+use crate::model::outbox::{OutboxEvent, OutboxStatus};
+use crate::schema::outbox::Outbox as OutboxSchema;
+use crate::utils::{AppError, DbSpan};
+use crate::{abstract_trait::OutboxRepositoryTrait, config::ConnectionPool};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::{NaiveDateTime, Utc};
+use sea_query::{Cond, Expr, LockBehavior, LockType, Order, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use std::time::Duration;
+use tracing::{error, info};
+
+const OUTBOX_COLUMNS: [OutboxSchema; 9] = [
+    OutboxSchema::Id,
+    OutboxSchema::EventType,
+    OutboxSchema::Payload,
+    OutboxSchema::Status,
+    OutboxSchema::Attempts,
+    OutboxSchema::LastError,
+    OutboxSchema::NextAttemptAt,
+    OutboxSchema::CreatedAt,
+    OutboxSchema::DeliveredAt,
+];
+
+pub struct OutboxRepository {
+    db_pool: ConnectionPool,
+}
+
+impl OutboxRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+#[async_trait]
+impl OutboxRepositoryTrait for OutboxRepository {
+    async fn claim_pending(
+        &self,
+        limit: i64,
+        lease: Duration,
+    ) -> Result<Vec<OutboxEvent>, AppError> {
+        let _span = DbSpan::start("outbox", "claim_pending");
+
+        let now = Utc::now().naive_utc();
+        let lease_until = now
+            + chrono::Duration::from_std(lease)
+                .map_err(|e| AppError::Custom(format!("Invalid outbox claim lease: {e}")))?;
+
+        // SKIP LOCKED membuat worker lain melewati baris yang sedang diklaim alih-alih
+        // menunggu lalu mengklaim baris yang sama
+        let claimable = Query::select()
+            .column(OutboxSchema::Id)
+            .from(OutboxSchema::Table)
+            .and_where(Expr::col(OutboxSchema::Status).eq(OutboxStatus::Pending.as_str()))
+            .and_where(Expr::col(OutboxSchema::NextAttemptAt).lte(now))
+            .order_by(OutboxSchema::Id, Order::Asc)
+            .limit(limit as u64)
+            .lock_with_behavior(LockType::Update, LockBehavior::SkipLocked)
+            .to_owned();
+
+        let (sql, values) = Query::update()
+            .table(OutboxSchema::Table)
+            .value(OutboxSchema::NextAttemptAt, lease_until)
+            .and_where(Expr::col(OutboxSchema::Id).in_subquery(claimable))
+            .returning(Query::returning().columns(OUTBOX_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let mut rows = sqlx::query_as_with::<_, OutboxEvent, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Outbox] Failed to claim pending events: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        // RETURNING tidak menjamin urutan, event tetap dikirim sesuai urutan dibuat
+        rows.sort_by_key(|event| event.id);
+
+        if !rows.is_empty() {
+            info!("📬 [Outbox] Claimed {} pending event(s)", rows.len());
+        }
+
+        Ok(rows)
+    }
+
+    async fn find_by_id(&self, id: i64) -> Result<Option<OutboxEvent>, AppError> {
+        let _span = DbSpan::start("outbox", "find_by_id");
+
+        let (sql, values) = Query::select()
+            .columns(OUTBOX_COLUMNS)
+            .from(OutboxSchema::Table)
+            .and_where(Expr::col(OutboxSchema::Id).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_as_with::<_, OutboxEvent, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Outbox] Failed to fetch event ID {id}: {e}");
+                AppError::SqlxError(e)
+            })
+    }
+
+    async fn requeue(&self, id: i64) -> Result<Option<OutboxEvent>, AppError> {
+        let _span = DbSpan::start("outbox", "requeue");
+
+        let now = Utc::now().naive_utc();
+
+        // hanya event failed atau pending yang lease-nya sudah habis; event pending yang sedang
+        // dipegang worker (next_attempt_at di masa depan) tidak disentuh supaya tidak terkirim dua kali
+        let (sql, values) = Query::update()
+            .table(OutboxSchema::Table)
+            .values([
+                (OutboxSchema::Status, OutboxStatus::Pending.as_str().into()),
+                (OutboxSchema::Attempts, 0.into()),
+                (OutboxSchema::LastError, Option::<String>::None.into()),
+                (OutboxSchema::NextAttemptAt, now.into()),
+            ])
+            .and_where(Expr::col(OutboxSchema::Id).eq(id))
+            .cond_where(
+                Cond::any()
+                    .add(Expr::col(OutboxSchema::Status).eq(OutboxStatus::Failed.as_str()))
+                    .add(
+                        Cond::all()
+                            .add(Expr::col(OutboxSchema::Status).eq(OutboxStatus::Pending.as_str()))
+                            .add(Expr::col(OutboxSchema::NextAttemptAt).lte(now)),
+                    ),
+            )
+            .returning(Query::returning().columns(OUTBOX_COLUMNS))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let event = sqlx::query_as_with::<_, OutboxEvent, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Outbox] Failed to requeue event ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        if event.is_some() {
+            info!("🔁 [Outbox] Event ID {id} requeued for delivery");
+        }
+
+        Ok(event)
+    }
+
+    async fn mark_delivered(&self, id: i64) -> Result<(), AppError> {
+        let _span = DbSpan::start("outbox", "mark_delivered");
+
+        let now = Utc::now().naive_utc();
+
+        let (sql, values) = Query::update()
+            .table(OutboxSchema::Table)
+            .values([
+                (
+                    OutboxSchema::Status,
+                    OutboxStatus::Delivered.as_str().into(),
+                ),
+                (
+                    OutboxSchema::Attempts,
+                    Expr::col(OutboxSchema::Attempts).add(1),
+                ),
+                (OutboxSchema::LastError, Option::<String>::None.into()),
+                (OutboxSchema::DeliveredAt, now.into()),
+            ])
+            .and_where(Expr::col(OutboxSchema::Id).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Outbox] Failed to mark event ID {id} as delivered: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!("✅ [Outbox] Event ID {id} delivered");
+        Ok(())
+    }
+
+    async fn schedule_retry(
+        &self,
+        id: i64,
+        next_attempt_at: NaiveDateTime,
+        last_error: &str,
+    ) -> Result<(), AppError> {
+        let _span = DbSpan::start("outbox", "schedule_retry");
+
+        let (sql, values) = Query::update()
+            .table(OutboxSchema::Table)
+            .values([
+                (
+                    OutboxSchema::Attempts,
+                    Expr::col(OutboxSchema::Attempts).add(1),
+                ),
+                (OutboxSchema::LastError, last_error.into()),
+                (OutboxSchema::NextAttemptAt, next_attempt_at.into()),
+            ])
+            .and_where(Expr::col(OutboxSchema::Id).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Outbox] Failed to schedule retry for event ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!("🔁 [Outbox] Event ID {id} will be retried at {next_attempt_at}");
+        Ok(())
+    }
+
+    async fn mark_failed(&self, id: i64, last_error: &str) -> Result<(), AppError> {
+        let _span = DbSpan::start("outbox", "mark_failed");
+
+        let (sql, values) = Query::update()
+            .table(OutboxSchema::Table)
+            .values([
+                (OutboxSchema::Status, OutboxStatus::Failed.as_str().into()),
+                (
+                    OutboxSchema::Attempts,
+                    Expr::col(OutboxSchema::Attempts).add(1),
+                ),
+                (OutboxSchema::LastError, last_error.into()),
+            ])
+            .and_where(Expr::col(OutboxSchema::Id).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Outbox] Failed to mark event ID {id} as failed: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        error!("💀 [Outbox] Event ID {id} gave up after too many attempts: {last_error}");
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::test_db;
+
+    async fn seed_event(pool: &ConnectionPool, status: OutboxStatus, due_in: i64) -> i64 {
+        let (id,) = sqlx::query_as::<_, (i64,)>(
+            "INSERT INTO outbox (event_type, payload, status, attempts, next_attempt_at) \
+             VALUES ('transfer.completed', '{}', $1, 3, now() + make_interval(secs => $2)) \
+             RETURNING id",
+        )
+        .bind(status.as_str())
+        .bind(due_in as f64)
+        .fetch_one(pool)
+        .await
+        .unwrap();
+        id
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn only_failed_or_unleased_events_are_requeued() {
+        let pool = test_db::pool().await;
+        let repository = OutboxRepository::new(pool.clone());
+
+        // pending dengan next_attempt_at di masa depan sedang dipegang worker
+        let leased = seed_event(&pool, OutboxStatus::Pending, 60).await;
+        assert!(repository.requeue(leased).await.unwrap().is_none());
+        let untouched = repository.find_by_id(leased).await.unwrap().unwrap();
+        assert_eq!(untouched.attempts, 3);
+
+        let expired = seed_event(&pool, OutboxStatus::Pending, -60).await;
+        let failed = seed_event(&pool, OutboxStatus::Failed, 60).await;
+        for id in [expired, failed] {
+            let requeued = repository.requeue(id).await.unwrap().unwrap();
+            assert_eq!(requeued.status, OutboxStatus::Pending.as_str());
+            assert_eq!(requeued.attempts, 0);
+        }
+
+        let delivered = seed_event(&pool, OutboxStatus::Delivered, -60).await;
+        assert!(repository.requeue(delivered).await.unwrap().is_none());
+    }
+}
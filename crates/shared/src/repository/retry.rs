@@ -0,0 +1,172 @@
+use std::{future::Future, sync::OnceLock, time::Duration};
+use tracing::warn;
+
+use crate::utils::AppError;
+
+const MAX_BACKOFF: Duration = Duration::from_secs(2);
+
+static POLICY: OnceLock<SqlRetryPolicy> = OnceLock::new();
+
+#[derive(Debug, Clone, Copy)]
+pub struct SqlRetryPolicy {
+    pub max_retries: u32,
+    pub initial_backoff: Duration,
+}
+
+impl Default for SqlRetryPolicy {
+    fn default() -> Self {
+        Self {
+            max_retries: 3,
+            initial_backoff: Duration::from_millis(50),
+        }
+    }
+}
+
+impl SqlRetryPolicy {
+    pub fn new(max_retries: u32, initial_backoff: Duration) -> Self {
+        Self {
+            max_retries,
+            initial_backoff,
+        }
+    }
+
+    // dipasang sekali saat startup; sebelum itu repository memakai nilai bawaan
+    pub fn install(self) {
+        if POLICY.set(self).is_err() {
+            warn!("⚠️  SQL retry policy already installed, ignoring new one");
+        }
+    }
+
+    fn current() -> Self {
+        POLICY.get().copied().unwrap_or_default()
+    }
+
+    fn backoff(&self, retry: u32) -> Duration {
+        self.initial_backoff
+            .saturating_mul(2u32.saturating_pow(retry.saturating_sub(1)))
+            .min(MAX_BACKOFF)
+    }
+}
+
+// hanya error yang hilang bila transaksi diulang; pelanggaran constraint dan error logika tidak
+pub(crate) fn is_transient(err: &sqlx::Error) -> bool {
+    match err {
+        sqlx::Error::Database(db_err) => matches!(
+            db_err.code().as_deref(),
+            // serialization_failure, deadlock_detected, admin_shutdown, koneksi putus (kelas 08)
+            Some("40001" | "40P01" | "57P01" | "08000" | "08003" | "08006")
+        ),
+        // Io di sini hanya dari statement sebelum COMMIT: transaksi yang koneksinya putus
+        // di-rollback server, error COMMIT sendiri dipetakan lewat `unknown_outcome`
+        sqlx::Error::Io(_) | sqlx::Error::PoolTimedOut | sqlx::Error::WorkerCrashed => true,
+        _ => false,
+    }
+}
+
+// dipakai untuk error COMMIT dan statement tulis autocommit: selain jawaban error dari server,
+// koneksi bisa putus setelah perintahnya terkirim sehingga data mungkin sudah tersimpan.
+// Hasil yang tidak pasti seperti itu tidak boleh diulang karena mutasinya bisa tertulis dua kali
+pub(crate) fn unknown_outcome(err: sqlx::Error) -> AppError {
+    match err {
+        sqlx::Error::Database(_) => AppError::SqlxError(err),
+        other => AppError::InternalError(format!(
+            "Outcome of database write is unknown, not retrying: {other}"
+        )),
+    }
+}
+
+// `attempt` harus menjalankan transaksi utuh dari awal karena transaksi yang gagal sudah di-rollback
+pub(crate) async fn with_sql_retry<T, F, Fut>(
+    operation: &str,
+    mut attempt: F,
+) -> Result<T, AppError>
+where
+    F: FnMut() -> Fut,
+    Fut: Future<Output = Result<T, AppError>>,
+{
+    let policy = SqlRetryPolicy::current();
+    let mut retry = 0;
+
+    loop {
+        match attempt().await {
+            Err(AppError::SqlxError(err)) if retry < policy.max_retries && is_transient(&err) => {
+                retry += 1;
+                let backoff = policy.backoff(retry);
+
+                warn!(
+                    "🔁 [Retry] {operation} hit a transient database error, retry {retry}/{} in {}ms: {err}",
+                    policy.max_retries,
+                    backoff.as_millis()
+                );
+
+                tokio::time::sleep(backoff).await;
+            }
+            result => return result,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::{
+        io,
+        sync::atomic::{AtomicU32, Ordering},
+    };
+
+    fn connection_reset() -> sqlx::Error {
+        sqlx::Error::Io(io::Error::from(io::ErrorKind::ConnectionReset))
+    }
+
+    #[tokio::test]
+    async fn retries_transient_error_until_success() {
+        let attempts = AtomicU32::new(0);
+
+        let result = with_sql_retry("test.transient", || async {
+            if attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+                Err(AppError::SqlxError(connection_reset()))
+            } else {
+                Ok(42)
+            }
+        })
+        .await;
+
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_logical_errors() {
+        let attempts = AtomicU32::new(0);
+
+        let result: Result<(), AppError> = with_sql_retry("test.logical", || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(AppError::Custom("Insufficient balance".to_string()))
+        })
+        .await;
+
+        assert!(matches!(result, Err(AppError::Custom(_))));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn does_not_retry_unknown_commit_outcome() {
+        let attempts = AtomicU32::new(0);
+
+        let result: Result<(), AppError> = with_sql_retry("test.commit", || async {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            Err(unknown_outcome(connection_reset()))
+        })
+        .await;
+
+        assert!(matches!(result, Err(AppError::InternalError(_))));
+        assert_eq!(attempts.load(Ordering::SeqCst), 1);
+    }
+
+    #[test]
+    fn only_connection_level_errors_are_transient() {
+        assert!(is_transient(&connection_reset()));
+        assert!(is_transient(&sqlx::Error::PoolTimedOut));
+        assert!(!is_transient(&sqlx::Error::RowNotFound));
+    }
+}
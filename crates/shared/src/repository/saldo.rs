@@ -1,31 +1,488 @@
-use crate::model::saldo::Saldo;
+use crate::domain::request::default_page_size;
+use crate::model::ledger::LedgerEntryType;
+use crate::model::saldo::{
+    BalanceReconciliation, DEFAULT_SALDO_LABEL, Saldo, SaldoRanking, SaldoWithUser,
+};
+use crate::model::topup::TopupStatus;
+use crate::model::transfer::TransferStatus;
+use crate::model::user::User;
+use crate::repository::retry::{unknown_outcome, with_sql_retry};
+use crate::schema::ledger::LedgerEntries as LedgerSchema;
 use crate::schema::saldo::Saldo as SaldoSchema;
-use crate::utils::AppError;
+use crate::schema::topup::Topups as TopupSchema;
+use crate::schema::transfer::Transfers as TransferSchema;
+use crate::schema::user::Users;
+use crate::schema::withdraw::Withdraws as WithdrawSchema;
+use crate::utils::{AppError, DbSpan, checked_amount};
 use crate::{
     abstract_trait::SaldoRepositoryTrait,
-    config::ConnectionPool,
+    config::{ConnectionPool, ReadPool},
     domain::request::saldo::{
-        CreateSaldoRequest, UpdateSaldoBalance, UpdateSaldoRequest, UpdateSaldoWithdraw,
+        AdjustBalanceRequest, CreateSaldoRequest, UpdateSaldoBalance, UpdateSaldoRequest,
+        UpdateSaldoWithdraw,
     },
 };
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::{NaiveDateTime, Utc};
-use sea_query::{Expr, Func, Order, PostgresQueryBuilder, Query, SimpleExpr, Value};
+use sea_query::{
+    Alias, Asterisk, Expr, Func, IntoIden, OnConflict, Order, PostgresQueryBuilder, Query,
+    SelectStatement, SimpleExpr, Value,
+};
 use sea_query_binder::SqlxBinder;
-use sqlx::Row;
+use sqlx::{FromRow, PgConnection, Row, postgres::PgRow};
 use tracing::{error, info};
 
 pub struct SaldoRepository {
     db_pool: ConnectionPool,
+    read_pool: ReadPool,
+}
+
+// dilempar trigger saldo saat saldo turun di bawah batas overdraft user
+const BALANCE_FLOOR_CONSTRAINT: &str = "chk_saldo_total_balance_floor";
+const UNIQUE_USER_LABEL_CONSTRAINT: &str = "uq_saldo_user_label";
+const NUMERIC_OUT_OF_RANGE: &str = "22003";
+
+// kolom saldo diberi prefix agar tidak bentrok dengan kolom users pada query join
+const JOINED_SALDO_COLUMNS: [(SaldoSchema, &str); 8] = [
+    (SaldoSchema::SaldoId, "saldo_id"),
+    (SaldoSchema::UserId, "saldo_user_id"),
+    (SaldoSchema::TotalBalance, "saldo_total_balance"),
+    (SaldoSchema::WithdrawAmount, "saldo_withdraw_amount"),
+    (SaldoSchema::WithdrawTime, "saldo_withdraw_time"),
+    (SaldoSchema::CreatedAt, "saldo_created_at"),
+    (SaldoSchema::UpdatedAt, "saldo_updated_at"),
+    (SaldoSchema::Label, "saldo_label"),
+];
+
+fn saldo_with_user_from_row(row: &PgRow) -> Result<SaldoWithUser, sqlx::Error> {
+    let user = User::from_row(row)?;
+
+    // saldo_id NULL berarti LEFT JOIN tidak menemukan saldo utama
+    let saldo = match row.try_get::<Option<i32>, _>("saldo_id")? {
+        Some(saldo_id) => Some(Saldo {
+            saldo_id,
+            user_id: row.try_get("saldo_user_id")?,
+            total_balance: row.try_get("saldo_total_balance")?,
+            withdraw_amount: row.try_get("saldo_withdraw_amount")?,
+            withdraw_time: row.try_get("saldo_withdraw_time")?,
+            created_at: row.try_get("saldo_created_at")?,
+            updated_at: row.try_get("saldo_updated_at")?,
+            label: row.try_get("saldo_label")?,
+        }),
+        None => None,
+    };
+
+    Ok(SaldoWithUser { user, saldo })
+}
+
+// SUM satu kolom milik user pada baris luar, 0 jika user belum punya baris
+fn user_sum(
+    table: impl IntoIden + 'static,
+    amount: impl IntoIden + 'static,
+    condition: SimpleExpr,
+) -> SimpleExpr {
+    let sum = Query::select()
+        .expr(Func::coalesce([
+            Func::sum(Expr::col(amount)).into(),
+            Expr::val(0).into(),
+        ]))
+        .from(table)
+        .and_where(condition)
+        .to_owned();
+
+    SimpleExpr::SubQuery(None, Box::new(sum.into_sub_query_statement()))
+}
+
+// transfer yang benar-benar memindahkan saldo: completed, atau reversed yang punya transfer
+// kompensasi; transfer yang ditahan (needs_review) dan tahanan yang dibatalkan tanpa kompensasi
+// tidak pernah memindahkan dana
+fn settled_transfer() -> SimpleExpr {
+    let status = || Expr::col((TransferSchema::Table, TransferSchema::Status));
+    let compensation = Alias::new("compensation");
+
+    let compensated = Query::select()
+        .expr(Expr::val(1))
+        .from_as(TransferSchema::Table, compensation.clone())
+        .and_where(
+            Expr::col((compensation, TransferSchema::ReversalOf))
+                .equals((TransferSchema::Table, TransferSchema::TransferId)),
+        )
+        .to_owned();
+
+    status().eq(TransferStatus::Completed.as_str()).or(status()
+        .eq(TransferStatus::Reversed.as_str())
+        .and(Expr::exists(compensated)))
+}
+
+// saldo yang diharapkan dihitung ulang dari topup yang pernah dikreditkan, transfer masuk
+// dan keluar yang sudah settle (pembalikan tercatat sebagai transfer kompensasi), withdraw, dan ledger;
+// koreksi hasil rekonsiliasi sendiri tidak ikut dihitung
+fn reconciliation_query() -> SelectStatement {
+    let user_id = || Expr::col((Users::Table, Users::UserId));
+
+    let stored = user_sum(
+        SaldoSchema::Table,
+        SaldoSchema::TotalBalance,
+        Expr::col((SaldoSchema::Table, SaldoSchema::UserId)).equals((Users::Table, Users::UserId)),
+    );
+
+    let topups = user_sum(
+        TopupSchema::Table,
+        TopupSchema::TopupAmount,
+        Expr::col((TopupSchema::Table, TopupSchema::UserId))
+            .equals((Users::Table, Users::UserId))
+            .and(Expr::col((TopupSchema::Table, TopupSchema::Status)).is_in([
+                TopupStatus::Success.as_str(),
+                TopupStatus::Refunded.as_str(),
+            ])),
+    );
+    let transfers_in = user_sum(
+        TransferSchema::Table,
+        TransferSchema::TransferAmount,
+        Expr::col((TransferSchema::Table, TransferSchema::TransferTo))
+            .equals((Users::Table, Users::UserId))
+            .and(settled_transfer()),
+    );
+    let transfers_out = user_sum(
+        TransferSchema::Table,
+        TransferSchema::TransferAmount,
+        Expr::col((TransferSchema::Table, TransferSchema::TransferFrom))
+            .equals((Users::Table, Users::UserId))
+            .and(settled_transfer()),
+    );
+    let withdraws = user_sum(
+        WithdrawSchema::Table,
+        WithdrawSchema::WithdrawAmount,
+        Expr::col((WithdrawSchema::Table, WithdrawSchema::UserId))
+            .equals((Users::Table, Users::UserId)),
+    );
+    let ledger = user_sum(
+        LedgerSchema::Table,
+        LedgerSchema::Amount,
+        Expr::col((LedgerSchema::Table, LedgerSchema::UserId))
+            .equals((Users::Table, Users::UserId))
+            .and(
+                Expr::col((LedgerSchema::Table, LedgerSchema::EntryType))
+                    .ne(LedgerEntryType::Reconciliation.as_str()),
+            ),
+    );
+
+    let expected = topups
+        .add(transfers_in)
+        .sub(transfers_out)
+        .sub(withdraws)
+        .add(ledger);
+
+    Query::select()
+        .expr_as(user_id(), Alias::new("user_id"))
+        .expr_as(stored, Alias::new("stored_balance"))
+        .expr_as(expected, Alias::new("expected_balance"))
+        .from(Users::Table)
+        .and_where(Expr::col((Users::Table, Users::DeletedAt)).is_null())
+        .to_owned()
 }
 
 impl SaldoRepository {
     pub fn new(db_pool: ConnectionPool) -> Self {
-        Self { db_pool }
+        Self {
+            read_pool: ReadPool::primary(db_pool.clone()),
+            db_pool,
+        }
+    }
+
+    pub fn with_read_pool(mut self, read_pool: ReadPool) -> Self {
+        self.read_pool = read_pool;
+        self
+    }
+
+    async fn update_balance_once(&self, input: &UpdateSaldoBalance) -> Result<Saldo, AppError> {
+        let _span = DbSpan::start("saldo", "update_balance");
+
+        info!(
+            "💵 [Saldo] Updating balance for user_id={} to {}",
+            input.user_id, input.total_balance
+        );
+
+        let (sql, values) = Query::select()
+            .from(SaldoSchema::Table)
+            .column(SaldoSchema::SaldoId)
+            .and_where(Expr::col(SaldoSchema::UserId).eq(input.user_id))
+            .and_where(Expr::col(SaldoSchema::Label).eq(DEFAULT_SALDO_LABEL))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let saldo_id: Option<i32> = sqlx::query_with(&sql, values)
+            .map(|row: sqlx::postgres::PgRow| row.get("saldo_id"))
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Saldo] Database error while fetching saldo_id for user_id={}: {e}",
+                    input.user_id,
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        let saldo_id = saldo_id.ok_or(AppError::NotFound("Saldo not found".into()))?;
+
+        let (update_sql, update_values) = Query::update()
+            .table(SaldoSchema::Table)
+            .values([(SaldoSchema::TotalBalance, input.total_balance.into())])
+            .and_where(Expr::col(SaldoSchema::SaldoId).eq(saldo_id))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [Saldo] Executing balance update: {update_sql} | Values: {:?}",
+            update_values
+        );
+
+        let updated: Saldo = sqlx::query_as_with::<_, Saldo, _>(&update_sql, update_values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Saldo] Failed to update balance for saldo_id={saldo_id}: {e}");
+                map_balance_error(e)
+            })?;
+
+        info!(
+            "✅ [Saldo] Balance updated successfully: saldo_id={} → {}",
+            updated.saldo_id, updated.total_balance
+        );
+
+        Ok(updated)
+    }
+
+    async fn update_saldo_withdraw_once(
+        &self,
+        input: &UpdateSaldoWithdraw,
+    ) -> Result<Saldo, AppError> {
+        let _span = DbSpan::start("saldo", "update_saldo_withdraw");
+
+        info!(
+            "💸 [Saldo] Processing withdrawal for user_id={} | Amount: {}",
+            input.user_id,
+            input.withdraw_amount.unwrap_or(0)
+        );
+
+        let (select_sql, select_values) = Query::select()
+            .from(SaldoSchema::Table)
+            .columns([
+                (SaldoSchema::Table, SaldoSchema::SaldoId),
+                (SaldoSchema::Table, SaldoSchema::TotalBalance),
+            ])
+            .column((Users::Table, Users::OverdraftLimit))
+            .inner_join(
+                Users::Table,
+                Expr::col((Users::Table, Users::UserId))
+                    .equals((SaldoSchema::Table, SaldoSchema::UserId)),
+            )
+            .and_where(Expr::col((SaldoSchema::Table, SaldoSchema::UserId)).eq(input.user_id))
+            .and_where(Expr::col((SaldoSchema::Table, SaldoSchema::Label)).eq(DEFAULT_SALDO_LABEL))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let row = sqlx::query_with(&select_sql, select_values)
+            .fetch_optional(&self.db_pool)
+            .await?
+            .ok_or(AppError::NotFound("Saldo not found".into()))?;
+
+        let saldo_id: i32 = row.get("saldo_id");
+        let current_balance: i32 = row.get("total_balance");
+        let overdraft_limit: i32 = row.get("overdraft_limit");
+
+        let withdraw_amount: i32 = input.withdraw_amount.unwrap_or(0);
+        let new_balance = checked_amount(current_balance.checked_sub(withdraw_amount))?;
+        if new_balance < -overdraft_limit {
+            error!(
+                "❌ [Saldo] Insufficient balance: {} - {} is below -{} for user_id={}",
+                current_balance, withdraw_amount, overdraft_limit, input.user_id
+            );
+            return Err(AppError::Custom("Insufficient balance".into()));
+        }
+
+        let (update_sql, update_values) = Query::update()
+            .table(SaldoSchema::Table)
+            .values([
+                (SaldoSchema::TotalBalance, new_balance.into()),
+                (SaldoSchema::WithdrawAmount, withdraw_amount.into()),
+                (
+                    SaldoSchema::WithdrawTime,
+                    input.withdraw_time.map(Into::into).unwrap_or_else(|| {
+                        SimpleExpr::Value(Value::ChronoDateTime(Some(Box::new(
+                            Utc::now().naive_utc(),
+                        ))))
+                    }),
+                ),
+            ])
+            .and_where(Expr::col(SaldoSchema::SaldoId).eq(saldo_id))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let updated: Saldo = sqlx::query_as_with::<_, Saldo, _>(&update_sql, update_values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Saldo] Failed to update saldo (withdraw) for user_id={}: {e}",
+                    input.user_id,
+                );
+                // saldo baru dihitung dari pembacaan sebelumnya, mengulang UPDATE yang mungkin
+                // sudah tersimpan akan memotong saldo dua kali
+                match e {
+                    sqlx::Error::Database(_) => map_balance_error(e),
+                    other => unknown_outcome(other),
+                }
+            })?;
+
+        info!(
+            "✅ [Saldo] Withdraw processed: user_id={} | Old: {}, New: {}",
+            input.user_id, current_balance, new_balance
+        );
+
+        Ok(updated)
+    }
+
+    async fn adjust_balance_once(
+        &self,
+        actor_id: i32,
+        input: &AdjustBalanceRequest,
+        entry_type: LedgerEntryType,
+    ) -> Result<Saldo, AppError> {
+        let _span = DbSpan::start("saldo", "adjust_balance");
+
+        info!(
+            "🛠️ [Saldo] Adjusting balance of user_id={} by {} (actor_id={actor_id})",
+            input.user_id, input.delta
+        );
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("❌ [Saldo] Failed to begin adjustment transaction: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        // trigger saldo menolak koreksi yang membuat saldo melewati batas overdraft
+        let (sql, values) = Query::update()
+            .table(SaldoSchema::Table)
+            .values([
+                (
+                    SaldoSchema::TotalBalance,
+                    Expr::col(SaldoSchema::TotalBalance).add(input.delta),
+                ),
+                (SaldoSchema::UpdatedAt, Utc::now().naive_utc().into()),
+            ])
+            .and_where(Expr::col(SaldoSchema::UserId).eq(input.user_id))
+            .and_where(Expr::col(SaldoSchema::Label).eq(DEFAULT_SALDO_LABEL))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let saldo = sqlx::query_as_with::<_, Saldo, _>(&sql, values)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Saldo] Failed to adjust saldo for user_id={}: {e}",
+                    input.user_id
+                );
+                map_balance_error(e)
+            })?
+            .ok_or_else(|| {
+                error!("❌ [Saldo] Saldo for user_id={} not found", input.user_id);
+                AppError::NotFound(format!("Saldo with User id {} not found", input.user_id))
+            })?;
+
+        let (sql, values) = Query::insert()
+            .into_table(LedgerSchema::Table)
+            .columns([
+                LedgerSchema::UserId,
+                LedgerSchema::EntryType,
+                LedgerSchema::Amount,
+                LedgerSchema::ReferenceId,
+                LedgerSchema::Reason,
+                LedgerSchema::ActorId,
+            ])
+            .values([
+                input.user_id.into(),
+                entry_type.as_str().into(),
+                input.delta.into(),
+                saldo.saldo_id.into(),
+                input.reason.trim().into(),
+                actor_id.into(),
+            ])
+            .unwrap()
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Saldo] Failed to record adjustment ledger entry for user_id={}: {e}",
+                    input.user_id
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        tx.commit().await.map_err(|e| {
+            error!("❌ [Saldo] Failed to commit balance adjustment: {e}");
+            unknown_outcome(e)
+        })?;
+
+        info!(
+            "✅ [Saldo] Balance of user_id={} adjusted by {}, now {}",
+            input.user_id, input.delta, saldo.total_balance
+        );
+
+        Ok(saldo)
+    }
+}
+
+// pelanggaran batas bawah saldo dan overflow saldo jadi error bisnis, bukan error sqlx mentah
+pub(crate) fn map_balance_error(e: sqlx::Error) -> AppError {
+    let constraint = e.as_database_error().and_then(|db_err| db_err.constraint());
+
+    match constraint {
+        Some(BALANCE_FLOOR_CONSTRAINT) => AppError::Custom("Insufficient balance".to_string()),
+        Some(UNIQUE_USER_LABEL_CONSTRAINT) => {
+            AppError::Custom("User already has a saldo with this label".to_string())
+        }
+        // SQLSTATE 22003: hasil penjumlahan saldo melewati batas integer
+        _ if e
+            .as_database_error()
+            .and_then(|db_err| db_err.code())
+            .as_deref()
+            == Some(NUMERIC_OUT_OF_RANGE) =>
+        {
+            AppError::Custom("amount overflow".to_string())
+        }
+        _ => AppError::SqlxError(e),
     }
 }
 
+// saldo nol untuk user baru; idempoten, baris yang sudah ada tidak disentuh
+pub(crate) async fn ensure_saldo(conn: &mut PgConnection, user_id: i32) -> Result<(), AppError> {
+    let (sql, values) = Query::insert()
+        .into_table(SaldoSchema::Table)
+        .columns([SaldoSchema::UserId, SaldoSchema::TotalBalance])
+        .values([user_id.into(), 0.into()])
+        .unwrap()
+        .on_conflict(
+            OnConflict::columns([SaldoSchema::UserId, SaldoSchema::Label])
+                .do_nothing()
+                .to_owned(),
+        )
+        .build_sqlx(PostgresQueryBuilder);
+
+    sqlx::query_with(&sql, values)
+        .execute(conn)
+        .await
+        .map_err(|e| {
+            error!("❌ [Saldo] Failed to ensure saldo for user_id={user_id}: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+    Ok(())
+}
+
 #[async_trait]
 impl SaldoRepositoryTrait for SaldoRepository {
     async fn find_all(
@@ -34,13 +491,19 @@ impl SaldoRepositoryTrait for SaldoRepository {
         page_size: i32,
         search: Option<String>,
     ) -> Result<(Vec<Saldo>, i64), AppError> {
+        let _span = DbSpan::start("saldo", "find_all");
+
         info!(
             "💰 [Saldos] Fetching all saldo records - page: {page}, page_size: {page_size}, search: {:?}",
             search
         );
 
         let page = if page > 0 { page } else { 1 };
-        let page_size = if page_size > 0 { page_size } else { 10 };
+        let page_size = if page_size > 0 {
+            page_size
+        } else {
+            default_page_size()
+        };
         let offset = (page - 1) * page_size;
 
         info!("🔢 [Saldos] Using pagination: LIMIT={page_size} OFFSET={offset}",);
@@ -55,6 +518,7 @@ impl SaldoRepositoryTrait for SaldoRepository {
                 SaldoSchema::WithdrawTime,
                 SaldoSchema::CreatedAt,
                 SaldoSchema::UpdatedAt,
+                SaldoSchema::Label,
             ])
             .from(SaldoSchema::Table)
             .order_by(SaldoSchema::SaldoId, Order::Asc)
@@ -70,7 +534,7 @@ impl SaldoRepositoryTrait for SaldoRepository {
         info!("🧾 [Saldos] Generated SQL: {sql} | Values: {:?}", values);
 
         let saldos_result = sqlx::query_as_with::<_, Saldo, _>(&sql, values)
-            .fetch_all(&self.db_pool)
+            .fetch_all(self.read_pool.get())
             .await;
 
         let saldos = match saldos_result {
@@ -103,7 +567,7 @@ impl SaldoRepositoryTrait for SaldoRepository {
         );
 
         let total_result = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(self.read_pool.get())
             .await;
 
         let total = match total_result {
@@ -126,6 +590,8 @@ impl SaldoRepositoryTrait for SaldoRepository {
     }
 
     async fn find_by_id(&self, id: i32) -> Result<Option<Saldo>, AppError> {
+        let _span = DbSpan::start("saldo", "find_by_id");
+
         info!("🔍 [Saldo] Finding saldo by ID: {id}");
 
         let (sql, values) = Query::select()
@@ -138,6 +604,7 @@ impl SaldoRepositoryTrait for SaldoRepository {
                 SaldoSchema::WithdrawTime,
                 SaldoSchema::CreatedAt,
                 SaldoSchema::UpdatedAt,
+                SaldoSchema::Label,
             ])
             .and_where(Expr::col(SaldoSchema::SaldoId).eq(id))
             .build_sqlx(PostgresQueryBuilder);
@@ -145,7 +612,7 @@ impl SaldoRepositoryTrait for SaldoRepository {
         info!("🧾 [Saldo] Executing query: {sql} | Values: {:?}", values);
 
         let row = sqlx::query_as_with::<_, Saldo, _>(&sql, values)
-            .fetch_optional(&self.db_pool)
+            .fetch_optional(self.read_pool.get())
             .await
             .map_err(|e| {
                 error!("❌ [Saldo] Failed to execute query for saldo_id={id}: {e}",);
@@ -168,6 +635,8 @@ impl SaldoRepositoryTrait for SaldoRepository {
     }
 
     async fn find_by_user_id(&self, user_id: i32) -> Result<Option<Saldo>, AppError> {
+        let _span = DbSpan::start("saldo", "find_by_user_id");
+
         info!("👤 [Saldo] Finding saldo for user_id: {user_id}");
 
         let (sql, values) = Query::select()
@@ -180,14 +649,16 @@ impl SaldoRepositoryTrait for SaldoRepository {
                 SaldoSchema::WithdrawTime,
                 SaldoSchema::CreatedAt,
                 SaldoSchema::UpdatedAt,
+                SaldoSchema::Label,
             ])
             .and_where(Expr::col(SaldoSchema::UserId).eq(user_id))
+            .and_where(Expr::col(SaldoSchema::Label).eq(DEFAULT_SALDO_LABEL))
             .build_sqlx(PostgresQueryBuilder);
 
         info!("🧾 [Saldo] Executing query: {sql} | Values: {:?}", values);
 
         let row = sqlx::query_as_with::<_, Saldo, _>(&sql, values)
-            .fetch_optional(&self.db_pool)
+            .fetch_optional(self.read_pool.get())
             .await
             .map_err(|e| {
                 error!("❌ [Saldo] Failed to fetch saldo for user_id={user_id}: {e}");
@@ -209,9 +680,50 @@ impl SaldoRepositoryTrait for SaldoRepository {
         Ok(row)
     }
 
-    async fn find_by_users_id(&self, user_id: i32) -> Result<Vec<Saldo>, AppError> {
+    async fn exists_by_id(&self, id: i32) -> Result<bool, AppError> {
+        let _span = DbSpan::start("saldo", "exists_by_id");
+
+        let (sql, values) = Query::select()
+            .expr(Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from(SaldoSchema::Table)
+                    .and_where(Expr::col(SaldoSchema::SaldoId).eq(id))
+                    .to_owned(),
+            ))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let exists: bool = sqlx::query_scalar_with(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Saldo] Failed to check saldo_id={id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!("🔍 [Saldo] saldo_id={id} exists: {exists}");
+
+        Ok(exists)
+    }
+
+    async fn find_by_users_id(
+        &self,
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Saldo>, i64), AppError> {
+        let _span = DbSpan::start("saldo", "find_by_users_id");
+
         info!("👥 [Saldo] Finding all saldos for user_id: {user_id}");
 
+        let page = if page > 0 { page } else { 1 };
+        let page_size = if page_size > 0 {
+            page_size
+        } else {
+            default_page_size()
+        };
+        let offset = (page - 1) * page_size;
+
         let (sql, values) = Query::select()
             .from(SaldoSchema::Table)
             .columns([
@@ -222,15 +734,18 @@ impl SaldoRepositoryTrait for SaldoRepository {
                 SaldoSchema::WithdrawTime,
                 SaldoSchema::CreatedAt,
                 SaldoSchema::UpdatedAt,
+                SaldoSchema::Label,
             ])
             .and_where(Expr::col(SaldoSchema::UserId).eq(user_id))
             .order_by(SaldoSchema::SaldoId, Order::Asc)
+            .limit(page_size as u64)
+            .offset(offset as u64)
             .build_sqlx(PostgresQueryBuilder);
 
         info!("🧾 [Saldo] Executing query: {sql} | Values: {:?}", values);
 
         let rows = sqlx::query_as_with::<_, Saldo, _>(&sql, values)
-            .fetch_all(&self.db_pool)
+            .fetch_all(self.read_pool.get())
             .await
             .map_err(|e| {
                 error!("❌ [Saldo] Failed to fetch saldos for user_id={user_id}: {e}",);
@@ -242,10 +757,90 @@ impl SaldoRepositoryTrait for SaldoRepository {
             rows.len(),
         );
 
-        Ok(rows)
+        let (count_sql, count_values) = Query::select()
+            .expr(Func::count(Expr::col(SaldoSchema::SaldoId)))
+            .from(SaldoSchema::Table)
+            .and_where(Expr::col(SaldoSchema::UserId).eq(user_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (total,) = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
+            .fetch_one(self.read_pool.get())
+            .await
+            .map_err(|e| {
+                error!("❌ [Saldo] Failed to count saldos for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        Ok((rows, total))
+    }
+
+    async fn find_with_user_by_user_id(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<SaldoWithUser>, AppError> {
+        let _span = DbSpan::start("saldo", "find_with_user_by_user_id");
+
+        info!("👤 [Saldo] Finding user with saldo for user_id: {user_id}");
+
+        let mut query = Query::select();
+        query
+            .columns([
+                (Users::Table, Users::UserId),
+                (Users::Table, Users::Firstname),
+                (Users::Table, Users::Lastname),
+                (Users::Table, Users::Email),
+                (Users::Table, Users::Password),
+                (Users::Table, Users::NocTransfer),
+                (Users::Table, Users::CreatedAt),
+                (Users::Table, Users::UpdatedAt),
+                (Users::Table, Users::DeletedAt),
+                (Users::Table, Users::PasswordChangedAt),
+                (Users::Table, Users::Status),
+                (Users::Table, Users::OverdraftLimit),
+            ])
+            .from(Users::Table)
+            .left_join(
+                SaldoSchema::Table,
+                Expr::col((SaldoSchema::Table, SaldoSchema::UserId))
+                    .equals((Users::Table, Users::UserId))
+                    .and(
+                        Expr::col((SaldoSchema::Table, SaldoSchema::Label)).eq(DEFAULT_SALDO_LABEL),
+                    ),
+            )
+            .and_where(Expr::col((Users::Table, Users::UserId)).eq(user_id))
+            .and_where(Expr::col((Users::Table, Users::DeletedAt)).is_null());
+
+        for (column, alias) in JOINED_SALDO_COLUMNS {
+            query.expr_as(Expr::col((SaldoSchema::Table, column)), Alias::new(alias));
+        }
+
+        let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [Saldo] Executing query: {sql} | Values: {:?}", values);
+
+        let row = sqlx::query_with(&sql, values)
+            .fetch_optional(self.read_pool.get())
+            .await
+            .and_then(|row| row.as_ref().map(saldo_with_user_from_row).transpose())
+            .map_err(|e| {
+                error!("❌ [Saldo] Failed to fetch user with saldo for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        match &row {
+            Some(found) => info!(
+                "✅ [Saldo] Found user_id={user_id} with saldo={}",
+                found.saldo.is_some()
+            ),
+            None => info!("🟡 [Saldo] No active user found for user_id={user_id}"),
+        }
+
+        Ok(row)
     }
 
     async fn create(&self, input: &CreateSaldoRequest) -> Result<Saldo, AppError> {
+        let _span = DbSpan::start("saldo", "create");
+
         info!(
             "➕ [Saldo] Creating new saldo for user_id={} with balance={}",
             input.user_id, input.total_balance
@@ -260,12 +855,14 @@ impl SaldoRepositoryTrait for SaldoRepository {
                 SaldoSchema::TotalBalance,
                 SaldoSchema::CreatedAt,
                 SaldoSchema::UpdatedAt,
+                SaldoSchema::Label,
             ])
             .values([
                 input.user_id.into(),
                 input.total_balance.into(),
                 now.into(),
                 now.into(),
+                input.label().into(),
             ])
             .unwrap()
             .returning_all()
@@ -281,7 +878,7 @@ impl SaldoRepositoryTrait for SaldoRepository {
                     "❌ [Saldo] Failed to create saldo for user_id={}: {e}",
                     input.user_id,
                 );
-                AppError::SqlxError(e)
+                map_balance_error(e)
             })?;
 
         info!(
@@ -293,6 +890,8 @@ impl SaldoRepositoryTrait for SaldoRepository {
     }
 
     async fn update(&self, input: &UpdateSaldoRequest) -> Result<Saldo, AppError> {
+        let _span = DbSpan::start("saldo", "update");
+
         info!("🔄 [Saldo] Updating saldo with ID: {}", input.saldo_id);
 
         let (sql, values) = Query::select()
@@ -353,7 +952,7 @@ impl SaldoRepositoryTrait for SaldoRepository {
             .await
             .map_err(|e| {
                 error!("❌ [Saldo] Failed to update saldo ID {saldo_id}: {e}");
-                AppError::SqlxError(e)
+                map_balance_error(e)
             })?;
 
         info!(
@@ -367,152 +966,498 @@ impl SaldoRepositoryTrait for SaldoRepository {
     }
 
     async fn update_balance(&self, input: &UpdateSaldoBalance) -> Result<Saldo, AppError> {
-        info!(
-            "💵 [Saldo] Updating balance for user_id={} to {}",
-            input.user_id, input.total_balance
-        );
+        with_sql_retry("saldo.update_balance", || self.update_balance_once(input)).await
+    }
 
-        let (sql, values) = Query::select()
-            .from(SaldoSchema::Table)
-            .column(SaldoSchema::SaldoId)
-            .and_where(Expr::col(SaldoSchema::UserId).eq(input.user_id))
+    async fn update_saldo_withdraw(&self, input: &UpdateSaldoWithdraw) -> Result<Saldo, AppError> {
+        with_sql_retry("saldo.update_saldo_withdraw", || {
+            self.update_saldo_withdraw_once(input)
+        })
+        .await
+    }
+
+    async fn delete(&self, id: i32) -> Result<(), AppError> {
+        let _span = DbSpan::start("saldo", "delete");
+
+        info!("🗑️ [Saldo] Deleting saldo with ID: {id}");
+
+        let (sql, values) = Query::delete()
+            .from_table(SaldoSchema::Table)
+            .and_where(Expr::col(SaldoSchema::SaldoId).eq(id))
             .build_sqlx(PostgresQueryBuilder);
 
-        let saldo_id: Option<i32> = sqlx::query_with(&sql, values)
-            .map(|row: sqlx::postgres::PgRow| row.get("saldo_id"))
-            .fetch_optional(&self.db_pool)
+        info!("🧾 [Saldo] DELETE query: {sql} | Values: {:?}", values);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
             .await
             .map_err(|e| {
-                error!(
-                    "❌ [Saldo] Database error while fetching saldo_id for user_id={}: {e}",
-                    input.user_id,
-                );
+                error!("❌ [Saldo] Failed to delete saldo ID {id}: {e}");
                 AppError::SqlxError(e)
             })?;
 
-        let saldo_id = saldo_id.ok_or(AppError::NotFound("Saldo not found".into()))?;
+        if result.rows_affected() == 0 {
+            error!("❌ [Saldo] Deletion failed: No saldo found with ID {id}");
+            return Err(AppError::NotFound(format!("Saldo with ID {id} not found",)));
+        }
 
-        let (update_sql, update_values) = Query::update()
-            .table(SaldoSchema::Table)
-            .values([(SaldoSchema::TotalBalance, input.total_balance.into())])
-            .and_where(Expr::col(SaldoSchema::SaldoId).eq(saldo_id))
-            .returning_all()
-            .build_sqlx(PostgresQueryBuilder);
+        info!("✅ [Saldo] Successfully deleted saldo ID: {id}");
+        Ok(())
+    }
 
-        info!(
-            "🧾 [Saldo] Executing balance update: {update_sql} | Values: {:?}",
-            update_values
-        );
+    async fn totals(&self) -> Result<(i64, i64), AppError> {
+        let _span = DbSpan::start("saldo", "totals");
 
-        let updated: Saldo = sqlx::query_as_with::<_, Saldo, _>(&update_sql, update_values)
+        let (sql, values) = Query::select()
+            .expr(Func::coalesce([
+                Func::sum(Expr::col(SaldoSchema::TotalBalance)).into(),
+                Expr::val(0).into(),
+            ]))
+            .expr(Func::count(Expr::col(SaldoSchema::SaldoId)))
+            .from(SaldoSchema::Table)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (total_balance, count) = sqlx::query_as_with::<_, (i64, i64), _>(&sql, values)
             .fetch_one(&self.db_pool)
             .await
             .map_err(|e| {
-                error!("❌ [Saldo] Failed to update balance for saldo_id={saldo_id}: {e}");
+                error!("❌ [Saldo] Failed to aggregate saldo totals: {e}");
                 AppError::SqlxError(e)
             })?;
 
-        info!(
-            "✅ [Saldo] Balance updated successfully: saldo_id={} → {}",
-            updated.saldo_id, updated.total_balance
-        );
+        info!("📊 [Saldo] Totals: balance={total_balance}, count={count}");
 
-        Ok(updated)
+        Ok((total_balance, count))
     }
 
-    async fn update_saldo_withdraw(&self, input: &UpdateSaldoWithdraw) -> Result<Saldo, AppError> {
-        info!(
-            "💸 [Saldo] Processing withdrawal for user_id={} | Amount: {}",
-            input.user_id,
-            input.withdraw_amount.unwrap_or(0)
-        );
+    async fn top_by_balance(&self, limit: i32) -> Result<Vec<SaldoRanking>, AppError> {
+        let _span = DbSpan::start("saldo", "top_by_balance");
 
-        let (select_sql, select_values) = Query::select()
+        info!("🏆 [Saldo] Fetching top {limit} saldos by balance");
+
+        // seri diurutkan berdasarkan user_id lalu saldo_id supaya hasilnya deterministik
+        let (sql, values) = Query::select()
+            .columns([
+                (SaldoSchema::Table, SaldoSchema::SaldoId),
+                (SaldoSchema::Table, SaldoSchema::UserId),
+                (SaldoSchema::Table, SaldoSchema::Label),
+                (SaldoSchema::Table, SaldoSchema::TotalBalance),
+            ])
+            .columns([
+                (Users::Table, Users::Firstname),
+                (Users::Table, Users::Lastname),
+            ])
             .from(SaldoSchema::Table)
-            .columns([SaldoSchema::SaldoId, SaldoSchema::TotalBalance])
-            .and_where(Expr::col(SaldoSchema::UserId).eq(input.user_id))
+            .inner_join(
+                Users::Table,
+                Expr::col((Users::Table, Users::UserId))
+                    .equals((SaldoSchema::Table, SaldoSchema::UserId)),
+            )
+            .and_where(Expr::col((Users::Table, Users::DeletedAt)).is_null())
+            .order_by((SaldoSchema::Table, SaldoSchema::TotalBalance), Order::Desc)
+            .order_by((SaldoSchema::Table, SaldoSchema::UserId), Order::Asc)
+            .order_by((SaldoSchema::Table, SaldoSchema::SaldoId), Order::Asc)
+            .limit(limit as u64)
             .build_sqlx(PostgresQueryBuilder);
 
-        let row = sqlx::query_with(&select_sql, select_values)
-            .fetch_optional(&self.db_pool)
-            .await?
-            .ok_or(AppError::NotFound("Saldo not found".into()))?;
+        info!("🧾 [Saldo] Generated SQL: {sql} | Values: {:?}", values);
 
-        let saldo_id: i32 = row.get("saldo_id");
-        let current_balance: i32 = row.get("total_balance");
+        let rankings = sqlx::query_as_with::<_, SaldoRanking, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Saldo] Failed to fetch top saldos: {e}");
+                AppError::SqlxError(e)
+            })?;
 
-        let withdraw_amount: i32 = input.withdraw_amount.unwrap_or(0);
-        if current_balance < withdraw_amount {
-            error!(
-                "❌ [Saldo] Insufficient balance: {} < {} for user_id={}",
-                current_balance, withdraw_amount, input.user_id
-            );
-            return Err(AppError::Custom("Insufficient balance".into()));
-        }
+        info!("✅ [Saldo] Fetched {} ranked saldo(s)", rankings.len());
 
-        let new_balance = current_balance - withdraw_amount;
+        Ok(rankings)
+    }
 
-        let (update_sql, update_values) = Query::update()
-            .table(SaldoSchema::Table)
-            .values([
-                (SaldoSchema::TotalBalance, new_balance.into()),
-                (SaldoSchema::WithdrawAmount, withdraw_amount.into()),
-                (
-                    SaldoSchema::WithdrawTime,
-                    input.withdraw_time.map(Into::into).unwrap_or_else(|| {
-                        SimpleExpr::Value(Value::ChronoDateTime(Some(Box::new(
-                            Utc::now().naive_utc(),
-                        ))))
-                    }),
-                ),
-            ])
-            .and_where(Expr::col(SaldoSchema::SaldoId).eq(saldo_id))
-            .returning_all()
+    async fn adjust_balance(
+        &self,
+        actor_id: i32,
+        input: &AdjustBalanceRequest,
+        entry_type: LedgerEntryType,
+    ) -> Result<Saldo, AppError> {
+        with_sql_retry("saldo.adjust_balance", || {
+            self.adjust_balance_once(actor_id, input, entry_type)
+        })
+        .await
+    }
+
+    async fn find_reconciliation(
+        &self,
+        user_id: i32,
+    ) -> Result<Option<BalanceReconciliation>, AppError> {
+        let _span = DbSpan::start("saldo", "find_reconciliation");
+
+        info!("🧮 [Saldo] Reconciling balance of user_id={user_id}");
+
+        let (sql, values) = reconciliation_query()
+            .and_where(Expr::col((Users::Table, Users::UserId)).eq(user_id))
             .build_sqlx(PostgresQueryBuilder);
 
-        let updated: Saldo = sqlx::query_as_with::<_, Saldo, _>(&update_sql, update_values)
-            .fetch_one(&self.db_pool)
+        info!("🧾 [Saldo] Executing query: {sql} | Values: {:?}", values);
+
+        let reconciliation = sqlx::query_as_with::<_, BalanceReconciliation, _>(&sql, values)
+            .fetch_optional(self.read_pool.get())
             .await
             .map_err(|e| {
-                error!(
-                    "❌ [Saldo] Failed to update saldo (withdraw) for user_id={}: {e}",
-                    input.user_id,
-                );
+                error!("❌ [Saldo] Failed to reconcile balance of user_id={user_id}: {e}");
                 AppError::SqlxError(e)
             })?;
 
-        info!(
-            "✅ [Saldo] Withdraw processed: user_id={} | Old: {}, New: {}",
-            input.user_id, current_balance, new_balance
-        );
+        if let Some(found) = &reconciliation {
+            info!(
+                "✅ [Saldo] user_id={user_id}: stored={}, expected={}",
+                found.stored_balance, found.expected_balance
+            );
+        }
 
-        Ok(updated)
+        Ok(reconciliation)
     }
 
-    async fn delete(&self, id: i32) -> Result<(), AppError> {
-        info!("🗑️ [Saldo] Deleting saldo with ID: {id}");
+    async fn find_balance_mismatches(&self) -> Result<Vec<BalanceReconciliation>, AppError> {
+        let _span = DbSpan::start("saldo", "find_balance_mismatches");
 
-        let (sql, values) = Query::delete()
-            .from_table(SaldoSchema::Table)
-            .and_where(Expr::col(SaldoSchema::SaldoId).eq(id))
+        info!("🧮 [Saldo] Scanning all users for balance mismatches");
+
+        let reconciliation = Alias::new("reconciliation");
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from_subquery(reconciliation_query(), reconciliation.clone())
+            .and_where(
+                Expr::col((reconciliation.clone(), Alias::new("stored_balance"))).ne(Expr::col((
+                    reconciliation.clone(),
+                    Alias::new("expected_balance"),
+                ))),
+            )
+            .order_by((reconciliation, Alias::new("user_id")), Order::Asc)
             .build_sqlx(PostgresQueryBuilder);
 
-        info!("🧾 [Saldo] DELETE query: {sql} | Values: {:?}", values);
+        info!("🧾 [Saldo] Executing query: {sql} | Values: {:?}", values);
 
-        let result = sqlx::query_with(&sql, values)
-            .execute(&self.db_pool)
+        let mismatches = sqlx::query_as_with::<_, BalanceReconciliation, _>(&sql, values)
+            .fetch_all(self.read_pool.get())
             .await
             .map_err(|e| {
-                error!("❌ [Saldo] Failed to delete saldo ID {id}: {e}");
+                error!("❌ [Saldo] Failed to scan balance mismatches: {e}");
                 AppError::SqlxError(e)
             })?;
 
-        if result.rows_affected() == 0 {
-            error!("❌ [Saldo] Deletion failed: No saldo found with ID {id}");
-            return Err(AppError::NotFound(format!("Saldo with ID {id} not found",)));
+        info!(
+            "✅ [Saldo] Found {} mismatched balance(s)",
+            mismatches.len()
+        );
+
+        Ok(mismatches)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abstract_trait::{TransferRepositoryTrait, UserRepositoryTrait},
+        domain::request::{CreateTransferRequest, CreateUserRequest},
+        repository::{test_db, transfer::TransferRepository, user::UserRepository},
+    };
+    use sqlx::error::{DatabaseError, ErrorKind};
+    use std::{borrow::Cow, error::Error as StdError, fmt};
+    use uuid::Uuid;
+
+    // error Postgres tiruan, cukup untuk menguji pemetaan constraint dan SQLSTATE
+    #[derive(Debug)]
+    struct PgError {
+        code: &'static str,
+        constraint: Option<&'static str>,
+    }
+
+    impl fmt::Display for PgError {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            write!(f, "postgres error {}", self.code)
         }
+    }
 
-        info!("✅ [Saldo] Successfully deleted saldo ID: {id}");
-        Ok(())
+    impl StdError for PgError {}
+
+    impl DatabaseError for PgError {
+        fn message(&self) -> &str {
+            "postgres error"
+        }
+
+        fn code(&self) -> Option<Cow<'_, str>> {
+            Some(Cow::Borrowed(self.code))
+        }
+
+        fn as_error(&self) -> &(dyn StdError + Send + Sync + 'static) {
+            self
+        }
+
+        fn as_error_mut(&mut self) -> &mut (dyn StdError + Send + Sync + 'static) {
+            self
+        }
+
+        fn into_error(self: Box<Self>) -> Box<dyn StdError + Send + Sync + 'static> {
+            self
+        }
+
+        fn constraint(&self) -> Option<&str> {
+            self.constraint
+        }
+
+        fn kind(&self) -> ErrorKind {
+            ErrorKind::CheckViolation
+        }
+    }
+
+    fn pg_error(code: &'static str, constraint: Option<&'static str>) -> sqlx::Error {
+        sqlx::Error::Database(Box::new(PgError { code, constraint }))
+    }
+
+    #[test]
+    fn balance_floor_violation_is_insufficient_balance() {
+        let err = map_balance_error(pg_error("23514", Some(BALANCE_FLOOR_CONSTRAINT)));
+
+        assert!(matches!(err, AppError::Custom(msg) if msg == "Insufficient balance"));
+    }
+
+    #[test]
+    fn other_database_errors_stay_sqlx_errors() {
+        let err = map_balance_error(pg_error("23503", Some("fk_saldo_user")));
+
+        assert!(matches!(err, AppError::SqlxError(_)));
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn negative_balance_update_is_rejected() {
+        let pool = test_db::pool().await;
+        let user_id = test_db::seed_user(&pool, 50_000).await;
+        let repository = SaldoRepository::new(pool.clone());
+
+        // trigger saldo menolak saldo di bawah 0 untuk akun tanpa overdraft
+        let err = repository
+            .update_balance(&UpdateSaldoBalance {
+                user_id,
+                total_balance: -1,
+            })
+            .await
+            .unwrap_err();
+
+        assert!(matches!(err, AppError::Custom(msg) if msg == "Insufficient balance"));
+        assert_eq!(test_db::balance_of(&pool, user_id).await, 50_000);
+
+        repository
+            .update_balance(&UpdateSaldoBalance {
+                user_id,
+                total_balance: 0,
+            })
+            .await
+            .unwrap();
+        assert_eq!(test_db::balance_of(&pool, user_id).await, 0);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn new_user_gets_one_zero_saldo_and_ensure_is_idempotent() {
+        let pool = test_db::pool().await;
+        let users = UserRepository::new(pool.clone());
+        let tag = Uuid::new_v4().simple().to_string();
+        let user = users
+            .create_user(&CreateUserRequest {
+                firstname: "Test".to_string(),
+                lastname: "User".to_string(),
+                email: format!("{tag}@example.com"),
+                password: "password".to_string(),
+                confirm_password: "password".to_string(),
+                noc_transfer: Some(tag),
+            })
+            .await
+            .unwrap();
+        assert_eq!(test_db::balance_of(&pool, user.user_id).await, 0);
+
+        sqlx::query("UPDATE saldo SET total_balance = 5000 WHERE user_id = $1")
+            .bind(user.user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        // menjalankan ulang tidak menambah baris atau mereset saldo
+        let mut conn = pool.acquire().await.unwrap();
+        ensure_saldo(&mut conn, user.user_id).await.unwrap();
+
+        let (rows,) = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM saldo WHERE user_id = $1")
+            .bind(user.user_id)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(rows, 1);
+        assert_eq!(test_db::balance_of(&pool, user.user_id).await, 5000);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn joined_lookup_matches_the_two_query_lookup() {
+        let pool = test_db::pool().await;
+        let user_id = test_db::seed_user(&pool, 125_000).await;
+        let saldo_repository = SaldoRepository::new(pool.clone());
+        let user_repository = UserRepository::new(pool.clone());
+
+        let joined = saldo_repository
+            .find_with_user_by_user_id(user_id)
+            .await
+            .unwrap()
+            .unwrap();
+        let user = user_repository.find_by_id(user_id).await.unwrap().unwrap();
+        let saldo = saldo_repository
+            .find_by_user_id(user_id)
+            .await
+            .unwrap()
+            .unwrap();
+
+        assert_eq!(
+            serde_json::to_value(&joined.user).unwrap(),
+            serde_json::to_value(&user).unwrap()
+        );
+        assert_eq!(
+            serde_json::to_value(joined.saldo.unwrap()).unwrap(),
+            serde_json::to_value(&saldo).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn joined_lookup_of_an_unknown_user_is_none() {
+        let pool = test_db::pool().await;
+        let repository = SaldoRepository::new(pool);
+
+        assert!(
+            repository
+                .find_with_user_by_user_id(-1)
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn reconciliation_entry_closes_a_corrupted_balance() {
+        let pool = test_db::pool().await;
+        // saldo ditulis langsung tanpa riwayat topup sehingga selisih dengan riwayatnya
+        let user_id = test_db::seed_user(&pool, 25_000).await;
+        let repository = SaldoRepository::new(pool.clone());
+
+        let reconciliation = repository
+            .find_reconciliation(user_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            reconciliation,
+            BalanceReconciliation {
+                user_id,
+                stored_balance: 25_000,
+                expected_balance: 0,
+            }
+        );
+        assert!(
+            repository
+                .find_balance_mismatches()
+                .await
+                .unwrap()
+                .contains(&reconciliation)
+        );
+
+        repository
+            .adjust_balance(
+                user_id,
+                &AdjustBalanceRequest {
+                    user_id,
+                    delta: reconciliation.difference() as i32,
+                    reason: "Reconciliation: stored 25000 expected 0".to_string(),
+                },
+                LedgerEntryType::Reconciliation,
+            )
+            .await
+            .unwrap();
+
+        let reconciled = repository
+            .find_reconciliation(user_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert!(reconciled.is_balanced());
+        assert_eq!(test_db::balance_of(&pool, user_id).await, 0);
+        assert!(
+            repository
+                .find_balance_mismatches()
+                .await
+                .unwrap()
+                .iter()
+                .all(|mismatch| mismatch.user_id != user_id)
+        );
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn held_and_cancelled_transfers_leave_the_reconciliation_clean() {
+        let pool = test_db::pool().await;
+        let sender = test_db::seed_user(&pool, 0).await;
+        let receiver = test_db::seed_user(&pool, 0).await;
+        let repository = SaldoRepository::new(pool.clone());
+        let transfers = TransferRepository::new(pool.clone());
+
+        repository
+            .adjust_balance(
+                sender,
+                &AdjustBalanceRequest {
+                    user_id: sender,
+                    delta: 100_000,
+                    reason: "Opening balance".to_string(),
+                },
+                LedgerEntryType::ManualAdjustment,
+            )
+            .await
+            .unwrap();
+
+        let transfer = |amount: i32| CreateTransferRequest {
+            transfer_from: sender,
+            transfer_to: receiver,
+            transfer_amount: amount,
+            note: None,
+            dry_run: false,
+            from_label: None,
+            to_label: None,
+        };
+        let settled = transfers
+            .create_with_settlement(&transfer(30_000), TransferStatus::Completed)
+            .await
+            .unwrap();
+        transfers
+            .create_with_settlement(&transfer(20_000), TransferStatus::NeedsReview)
+            .await
+            .unwrap();
+        let cancelled = transfers
+            .create_with_settlement(&transfer(10_000), TransferStatus::NeedsReview)
+            .await
+            .unwrap();
+        transfers.reverse(cancelled.transfer_id).await.unwrap();
+        // pembalikan transfer yang sudah settle tetap dihitung lewat transfer kompensasinya
+        transfers.reverse(settled.transfer_id).await.unwrap();
+
+        for user_id in [sender, receiver] {
+            let reconciliation = repository
+                .find_reconciliation(user_id)
+                .await
+                .unwrap()
+                .unwrap();
+            assert!(reconciliation.is_balanced(), "{reconciliation:?}");
+        }
+        assert_eq!(test_db::balance_of(&pool, sender).await, 100_000);
+        assert_eq!(test_db::balance_of(&pool, receiver).await, 0);
     }
 }
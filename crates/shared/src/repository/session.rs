@@ -0,0 +1,217 @@
+use crate::model::session::Session;
+use crate::schema::refresh_token::RefreshTokens;
+use crate::utils::{AppError, DbSpan};
+use crate::{
+    abstract_trait::SessionRepositoryTrait, config::ConnectionPool,
+    domain::request::CreateSessionRequest,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_query::{Expr, Order, PostgresQueryBuilder, Query, SelectStatement};
+use sea_query_binder::SqlxBinder;
+use tracing::{error, info};
+
+pub struct SessionRepository {
+    db_pool: ConnectionPool,
+}
+
+impl SessionRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self { db_pool }
+    }
+}
+
+// sesi aktif: belum dicabut dan belum kedaluwarsa
+fn select_active() -> SelectStatement {
+    Query::select()
+        .columns([
+            RefreshTokens::SessionId,
+            RefreshTokens::UserId,
+            RefreshTokens::TokenHash,
+            RefreshTokens::UserAgent,
+            RefreshTokens::CreatedAt,
+            RefreshTokens::LastUsedAt,
+            RefreshTokens::ExpiresAt,
+            RefreshTokens::RevokedAt,
+        ])
+        .from(RefreshTokens::Table)
+        .and_where(Expr::col(RefreshTokens::RevokedAt).is_null())
+        .and_where(Expr::col(RefreshTokens::ExpiresAt).gt(Utc::now().naive_utc()))
+        .to_owned()
+}
+
+#[async_trait]
+impl SessionRepositoryTrait for SessionRepository {
+    async fn create(&self, input: &CreateSessionRequest) -> Result<Session, AppError> {
+        let _span = DbSpan::start("refresh_tokens", "create");
+
+        info!(
+            "🔑 [Sessions] Creating session for user_id={}",
+            input.user_id
+        );
+
+        let now = Utc::now().naive_utc();
+
+        let (sql, values) = Query::insert()
+            .into_table(RefreshTokens::Table)
+            .columns([
+                RefreshTokens::UserId,
+                RefreshTokens::TokenHash,
+                RefreshTokens::UserAgent,
+                RefreshTokens::CreatedAt,
+                RefreshTokens::LastUsedAt,
+                RefreshTokens::ExpiresAt,
+            ])
+            .values([
+                input.user_id.into(),
+                input.token_hash.clone().into(),
+                input.user_agent.clone().into(),
+                now.into(),
+                now.into(),
+                input.expires_at.into(),
+            ])
+            .unwrap()
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let session = sqlx::query_as_with::<_, Session, _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Sessions] Failed to create session for user_id={}: {e}",
+                    input.user_id
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Sessions] Created session ID: {} for user_id={}",
+            session.session_id, session.user_id
+        );
+
+        Ok(session)
+    }
+
+    async fn find_active_by_user(&self, user_id: i32) -> Result<Vec<Session>, AppError> {
+        let _span = DbSpan::start("refresh_tokens", "find_active_by_user");
+
+        let (sql, values) = select_active()
+            .and_where(Expr::col(RefreshTokens::UserId).eq(user_id))
+            .order_by(RefreshTokens::SessionId, Order::Asc)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let sessions = sqlx::query_as_with::<_, Session, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Sessions] Failed to list sessions for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "📋 [Sessions] Found {} active session(s) for user_id={user_id}",
+            sessions.len()
+        );
+
+        Ok(sessions)
+    }
+
+    async fn find_active_by_token_hash(
+        &self,
+        token_hash: &str,
+    ) -> Result<Option<Session>, AppError> {
+        let _span = DbSpan::start("refresh_tokens", "find_active_by_token_hash");
+
+        let (sql, values) = select_active()
+            .and_where(Expr::col(RefreshTokens::TokenHash).eq(token_hash))
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_as_with::<_, Session, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Sessions] Failed to look up session by token: {e}");
+                AppError::SqlxError(e)
+            })
+    }
+
+    async fn touch(&self, session_id: i32) -> Result<(), AppError> {
+        let _span = DbSpan::start("refresh_tokens", "touch");
+
+        let (sql, values) = Query::update()
+            .table(RefreshTokens::Table)
+            .value(RefreshTokens::LastUsedAt, Utc::now().naive_utc())
+            .and_where(Expr::col(RefreshTokens::SessionId).eq(session_id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Sessions] Failed to touch session ID {session_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        Ok(())
+    }
+
+    async fn revoke(&self, user_id: i32, session_id: i32) -> Result<(), AppError> {
+        let _span = DbSpan::start("refresh_tokens", "revoke");
+
+        info!("🚫 [Sessions] Revoking session ID {session_id} of user_id={user_id}");
+
+        let (sql, values) = Query::update()
+            .table(RefreshTokens::Table)
+            .value(RefreshTokens::RevokedAt, Utc::now().naive_utc())
+            .and_where(Expr::col(RefreshTokens::SessionId).eq(session_id))
+            .and_where(Expr::col(RefreshTokens::UserId).eq(user_id))
+            .and_where(Expr::col(RefreshTokens::RevokedAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Sessions] Failed to revoke session ID {session_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!(
+                "Active session {session_id} not found for user {user_id}"
+            )));
+        }
+
+        info!("✅ [Sessions] Session ID {session_id} revoked");
+
+        Ok(())
+    }
+
+    async fn revoke_all_for_user(&self, user_id: i32) -> Result<u64, AppError> {
+        let _span = DbSpan::start("refresh_tokens", "revoke_all_for_user");
+
+        let (sql, values) = Query::update()
+            .table(RefreshTokens::Table)
+            .value(RefreshTokens::RevokedAt, Utc::now().naive_utc())
+            .and_where(Expr::col(RefreshTokens::UserId).eq(user_id))
+            .and_where(Expr::col(RefreshTokens::RevokedAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Sessions] Failed to revoke sessions of user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "🚫 [Sessions] Revoked {} session(s) of user_id={user_id}",
+            result.rows_affected()
+        );
+
+        Ok(result.rows_affected())
+    }
+}
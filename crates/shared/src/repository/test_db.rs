@@ -0,0 +1,52 @@
+use sqlx::postgres::PgPoolOptions;
+use uuid::Uuid;
+
+use crate::config::ConnectionPool;
+
+// Postgres sungguhan dengan semua migrasi terpasang, untuk test repository yang diberi #[ignore];
+// misalnya: TEST_DATABASE_URL=postgres://postgres@127.0.0.1:5432/pay cargo test -- --ignored
+pub(crate) async fn pool() -> ConnectionPool {
+    let url = std::env::var("TEST_DATABASE_URL").expect("TEST_DATABASE_URL must be set");
+
+    PgPoolOptions::new()
+        .max_connections(4)
+        .connect(&url)
+        .await
+        .expect("connect to TEST_DATABASE_URL")
+}
+
+// user baru dengan saldo utama `balance`; email dan noc unik supaya test bisa diulang
+pub(crate) async fn seed_user(pool: &ConnectionPool, balance: i32) -> i32 {
+    let tag = Uuid::new_v4().simple().to_string();
+
+    let (user_id,) = sqlx::query_as::<_, (i32,)>(
+        "INSERT INTO users (firstname, lastname, email, password, noc_transfer) \
+         VALUES ('Test', 'User', $1, 'password', $2) RETURNING user_id",
+    )
+    .bind(format!("{tag}@example.com"))
+    .bind(&tag)
+    .fetch_one(pool)
+    .await
+    .expect("seed user");
+
+    sqlx::query("INSERT INTO saldo (user_id, total_balance) VALUES ($1, $2)")
+        .bind(user_id)
+        .bind(balance)
+        .execute(pool)
+        .await
+        .expect("seed saldo");
+
+    user_id
+}
+
+pub(crate) async fn balance_of(pool: &ConnectionPool, user_id: i32) -> i32 {
+    let (balance,) = sqlx::query_as::<_, (i32,)>(
+        "SELECT total_balance FROM saldo WHERE user_id = $1 AND label = 'main'",
+    )
+    .bind(user_id)
+    .fetch_one(pool)
+    .await
+    .expect("find saldo");
+
+    balance
+}
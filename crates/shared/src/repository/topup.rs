@@ -1,25 +1,409 @@
-use crate::model::topup::Topup;
+use crate::domain::request::default_page_size;
+use crate::model::ledger::LedgerEntryType;
+use crate::model::saldo::DEFAULT_SALDO_LABEL;
+use crate::model::topup::{Topup, TopupMethodStats, TopupStatus};
+use crate::repository::retry::{unknown_outcome, with_sql_retry};
+use crate::repository::saldo::map_balance_error;
+use crate::schema::ledger::LedgerEntries as LedgerSchema;
+use crate::schema::saldo::Saldo as SaldoSchema;
 use crate::schema::topup::Topups as TopupSchema;
-use crate::utils::AppError;
+use crate::utils::{AppError, DbSpan, checked_amount};
 use crate::{
     abstract_trait::TopupRepositoryTrait,
-    config::ConnectionPool,
-    domain::request::topup::{CreateTopupRequest, UpdateTopupAmount, UpdateTopupRequest},
+    config::{ConnectionPool, ReadPool},
+    domain::request::topup::{
+        CreateTopupRequest, TopupStatsRequest, UpdateTopupAmount, UpdateTopupRequest,
+    },
 };
 use anyhow::Result;
 use async_trait::async_trait;
 use chrono::Utc;
-use sea_query::{Expr, Func, Order, PostgresQueryBuilder, Query};
+use sea_query::{Alias, Asterisk, Expr, Func, LockType, Order, PostgresQueryBuilder, Query};
 use sea_query_binder::SqlxBinder;
 use tracing::{error, info};
 
+const UNIQUE_TOPUP_NO_CONSTRAINT: &str = "uq_topups_topup_no";
+
+// topup_no duplikat (23505 pada index topup_no) dilaporkan sebagai error bisnis
+fn map_insert_error(e: sqlx::Error) -> AppError {
+    match e.as_database_error() {
+        Some(db_err)
+            if db_err.is_unique_violation()
+                && db_err.constraint() == Some(UNIQUE_TOPUP_NO_CONSTRAINT) =>
+        {
+            AppError::TopupNoAlreadyExists
+        }
+        _ => AppError::SqlxError(e),
+    }
+}
+
+pub(crate) fn transition_rejection(target: TopupStatus) -> String {
+    match target {
+        TopupStatus::Refunded => "Only successful topups can be refunded".to_string(),
+        _ => format!("Only pending topups can be marked as {}", target.as_str()),
+    }
+}
+
 pub struct TopupRepository {
     db_pool: ConnectionPool,
+    read_pool: ReadPool,
 }
 
 impl TopupRepository {
     pub fn new(db_pool: ConnectionPool) -> Self {
-        Self { db_pool }
+        Self {
+            read_pool: ReadPool::primary(db_pool.clone()),
+            db_pool,
+        }
+    }
+
+    pub fn with_read_pool(mut self, read_pool: ReadPool) -> Self {
+        self.read_pool = read_pool;
+        self
+    }
+
+    // dipanggil saat update status tidak mengenai baris dengan status asal yang diharapkan
+    async fn transition_error(&self, id: i32, target: TopupStatus) -> AppError {
+        match self.find_by_id(id).await {
+            Ok(Some(topup)) => {
+                error!(
+                    "🟡 [Topups] Topup ID {id} is '{}', cannot move to '{}'",
+                    topup.status,
+                    target.as_str()
+                );
+                AppError::Custom(transition_rejection(target))
+            }
+            Ok(None) => AppError::NotFound(format!("Topup with ID {id} not found")),
+            Err(e) => e,
+        }
+    }
+
+    async fn confirm_once(&self, id: i32) -> Result<Topup, AppError> {
+        let _span = DbSpan::start("topups", "confirm");
+
+        info!("✅ [Topups] Confirming topup ID: {id}");
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("❌ [Topups] Failed to begin transaction for topup ID {id}: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        let (sql, values) = Query::update()
+            .table(TopupSchema::Table)
+            .values([
+                (TopupSchema::Status, TopupStatus::Success.as_str().into()),
+                (TopupSchema::UpdatedAt, Utc::now().naive_utc().into()),
+            ])
+            .and_where(Expr::col(TopupSchema::TopupId).eq(id))
+            .and_where(Expr::col(TopupSchema::Status).eq(TopupStatus::Pending.as_str()))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [Topups] Executing CONFIRM: {sql} | Values: {:?}",
+            values
+        );
+
+        let confirmed = sqlx::query_as_with::<_, Topup, _>(&sql, values)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!("❌ [Topups] Failed to confirm topup ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        let Some(confirmed) = confirmed else {
+            drop(tx);
+            return Err(self.transition_error(id, TopupStatus::Success).await);
+        };
+
+        let (sql, values) = Query::update()
+            .table(SaldoSchema::Table)
+            .value(
+                SaldoSchema::TotalBalance,
+                Expr::col(SaldoSchema::TotalBalance).add(confirmed.topup_amount),
+            )
+            .and_where(Expr::col(SaldoSchema::UserId).eq(confirmed.user_id))
+            .and_where(Expr::col(SaldoSchema::Label).eq(DEFAULT_SALDO_LABEL))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let credited = sqlx::query_with(&sql, values)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Topups] Failed to credit saldo for user_id={}: {e}",
+                    confirmed.user_id
+                );
+                map_balance_error(e)
+            })?;
+
+        if credited.rows_affected() == 0 {
+            info!(
+                "🆕 [Topups] No saldo yet for user_id={}, creating one",
+                confirmed.user_id
+            );
+
+            let (sql, values) = Query::insert()
+                .into_table(SaldoSchema::Table)
+                .columns([SaldoSchema::UserId, SaldoSchema::TotalBalance])
+                .values([confirmed.user_id.into(), confirmed.topup_amount.into()])
+                .unwrap()
+                .build_sqlx(PostgresQueryBuilder);
+
+            sqlx::query_with(&sql, values)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| {
+                    error!(
+                        "❌ [Topups] Failed to create saldo for user_id={}: {e}",
+                        confirmed.user_id
+                    );
+                    AppError::SqlxError(e)
+                })?;
+        }
+
+        tx.commit().await.map_err(|e| {
+            error!("❌ [Topups] Failed to commit confirmation of topup ID {id}: {e}");
+            unknown_outcome(e)
+        })?;
+
+        info!(
+            "✅ [Topups] Topup ID {id} confirmed, credited {} to user_id={}",
+            confirmed.topup_amount, confirmed.user_id
+        );
+
+        Ok(confirmed)
+    }
+
+    async fn refund_once(&self, id: i32) -> Result<Topup, AppError> {
+        let _span = DbSpan::start("topups", "refund");
+
+        info!("↩️ [Topups] Refunding topup ID: {id}");
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("❌ [Topups] Failed to begin refund transaction for topup ID {id}: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        let now = Utc::now().naive_utc();
+
+        let (sql, values) = Query::update()
+            .table(TopupSchema::Table)
+            .values([
+                (TopupSchema::Status, TopupStatus::Refunded.as_str().into()),
+                (TopupSchema::UpdatedAt, now.into()),
+            ])
+            .and_where(Expr::col(TopupSchema::TopupId).eq(id))
+            .and_where(Expr::col(TopupSchema::Status).eq(TopupStatus::Success.as_str()))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let refunded = sqlx::query_as_with::<_, Topup, _>(&sql, values)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!("❌ [Topups] Failed to mark topup ID {id} as refunded: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        let Some(refunded) = refunded else {
+            drop(tx);
+            return Err(self.transition_error(id, TopupStatus::Refunded).await);
+        };
+
+        // trigger saldo menolak refund yang membuat saldo melewati batas overdraft
+        let (sql, values) = Query::update()
+            .table(SaldoSchema::Table)
+            .values([
+                (
+                    SaldoSchema::TotalBalance,
+                    Expr::col(SaldoSchema::TotalBalance).sub(refunded.topup_amount),
+                ),
+                (SaldoSchema::UpdatedAt, now.into()),
+            ])
+            .and_where(Expr::col(SaldoSchema::UserId).eq(refunded.user_id))
+            .and_where(Expr::col(SaldoSchema::Label).eq(DEFAULT_SALDO_LABEL))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let debited = sqlx::query_with(&sql, values)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Topups] Failed to debit saldo for user_id={}: {e}",
+                    refunded.user_id
+                );
+                map_balance_error(e)
+            })?;
+
+        if debited.rows_affected() == 0 {
+            error!(
+                "❌ [Topups] Saldo for user_id={} not found",
+                refunded.user_id
+            );
+            return Err(AppError::NotFound(format!(
+                "Saldo with User id {} not found",
+                refunded.user_id
+            )));
+        }
+
+        let (sql, values) = Query::insert()
+            .into_table(LedgerSchema::Table)
+            .columns([
+                LedgerSchema::UserId,
+                LedgerSchema::EntryType,
+                LedgerSchema::Amount,
+                LedgerSchema::ReferenceId,
+            ])
+            .values([
+                refunded.user_id.into(),
+                LedgerEntryType::TopupRefund.as_str().into(),
+                (-refunded.topup_amount).into(),
+                refunded.topup_id.into(),
+            ])
+            .unwrap()
+            .build_sqlx(PostgresQueryBuilder);
+
+        sqlx::query_with(&sql, values)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!("❌ [Topups] Failed to record ledger entry for topup ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        tx.commit().await.map_err(|e| {
+            error!("❌ [Topups] Failed to commit refund of topup ID {id}: {e}");
+            unknown_outcome(e)
+        })?;
+
+        info!(
+            "✅ [Topups] Topup ID {id} refunded, debited {} from user_id={}",
+            refunded.topup_amount, refunded.user_id
+        );
+
+        Ok(refunded)
+    }
+
+    async fn update_with_settlement_once(
+        &self,
+        input: &UpdateTopupRequest,
+    ) -> Result<Topup, AppError> {
+        let _span = DbSpan::start("topups", "update_with_settlement");
+
+        let id = input.topup_id;
+
+        info!("🔄 [Topups] Updating topup ID {id} with balance adjustment");
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("❌ [Topups] Failed to begin update transaction for topup ID {id}: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        // baris topup dikunci supaya selisih dihitung dari nominal yang benar-benar tersimpan
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(TopupSchema::Table)
+            .and_where(Expr::col(TopupSchema::TopupId).eq(id))
+            .and_where(Expr::col(TopupSchema::UserId).eq(input.user_id))
+            .lock(LockType::Update)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let original = sqlx::query_as_with::<_, Topup, _>(&sql, values)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!("❌ [Topups] Failed to lock topup ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| AppError::NotFound(format!("Topup with ID {id} not found")))?;
+
+        if input.topup_amount.is_none() && input.topup_method.is_none() {
+            info!("🟡 [Topups] No fields to update for topup ID: {id}");
+            return Ok(original);
+        }
+
+        let now = Utc::now().naive_utc();
+
+        // topup pending/failed belum pernah menambah saldo, jadi hanya topup sukses yang disesuaikan
+        let difference = match input.topup_amount {
+            Some(topup_amount) if original.is_success() => {
+                checked_amount(topup_amount.checked_sub(original.topup_amount))?
+            }
+            _ => 0,
+        };
+
+        if difference != 0 {
+            // batas bawah saldo (termasuk overdraft) dijaga trigger saldo
+            let (sql, values) = Query::update()
+                .table(SaldoSchema::Table)
+                .values([
+                    (
+                        SaldoSchema::TotalBalance,
+                        Expr::col(SaldoSchema::TotalBalance).add(difference),
+                    ),
+                    (SaldoSchema::UpdatedAt, now.into()),
+                ])
+                .and_where(Expr::col(SaldoSchema::UserId).eq(input.user_id))
+                .and_where(Expr::col(SaldoSchema::Label).eq(DEFAULT_SALDO_LABEL))
+                .build_sqlx(PostgresQueryBuilder);
+
+            let adjusted = sqlx::query_with(&sql, values)
+                .execute(&mut *tx)
+                .await
+                .map_err(|e| {
+                    error!(
+                        "❌ [Topups] Failed to adjust saldo for user_id={}: {e}",
+                        input.user_id
+                    );
+                    map_balance_error(e)
+                })?;
+
+            if adjusted.rows_affected() == 0 {
+                error!("❌ [Topups] Saldo for user_id={} not found", input.user_id);
+                return Err(AppError::NotFound(format!(
+                    "Saldo with User id {} not found",
+                    input.user_id
+                )));
+            }
+        }
+
+        let mut update_query = Query::update();
+        let mut query = update_query
+            .table(TopupSchema::Table)
+            .value(TopupSchema::UpdatedAt, now)
+            .and_where(Expr::col(TopupSchema::TopupId).eq(id));
+
+        if let Some(topup_amount) = input.topup_amount {
+            query = query.value(TopupSchema::TopupAmount, topup_amount);
+        }
+
+        if let Some(ref topup_method) = input.topup_method {
+            query = query.value(TopupSchema::TopupMethod, topup_method.clone());
+        }
+
+        let (sql, values) = query.returning_all().build_sqlx(PostgresQueryBuilder);
+
+        let updated = sqlx::query_as_with::<_, Topup, _>(&sql, values)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!("❌ [Topups] Database error updating topup ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        tx.commit().await.map_err(|e| {
+            error!("❌ [Topups] Failed to commit update of topup ID {id}: {e}");
+            unknown_outcome(e)
+        })?;
+
+        info!(
+            "✅ [Topups] Updated topup ID {id} | Amount: {} → {}, saldo adjusted by {difference}",
+            original.topup_amount, updated.topup_amount
+        );
+
+        Ok(updated)
     }
 }
 
@@ -31,13 +415,19 @@ impl TopupRepositoryTrait for TopupRepository {
         page_size: i32,
         search: Option<String>,
     ) -> Result<(Vec<Topup>, i64), AppError> {
+        let _span = DbSpan::start("topups", "find_all");
+
         info!(
             "💳 [Topups] Fetching all topups - page: {page}, page_size: {page_size}, search: {:?}",
             search
         );
 
         let page = if page > 0 { page } else { 1 };
-        let page_size = if page_size > 0 { page_size } else { 10 };
+        let page_size = if page_size > 0 {
+            page_size
+        } else {
+            default_page_size()
+        };
         let offset = (page - 1) * page_size;
 
         info!("🔢 [Topups] Using pagination: LIMIT={page_size} OFFSET={offset}",);
@@ -53,6 +443,7 @@ impl TopupRepositoryTrait for TopupRepository {
                 TopupSchema::TopupTime,
                 TopupSchema::CreatedAt,
                 TopupSchema::UpdatedAt,
+                TopupSchema::Status,
             ])
             .from(TopupSchema::Table)
             .order_by(TopupSchema::TopupId, Order::Asc)
@@ -68,7 +459,7 @@ impl TopupRepositoryTrait for TopupRepository {
         info!("🧾 [Topups] Generated SQL: {sql} | Values: {:?}", values);
 
         let topups_result = sqlx::query_as_with::<_, Topup, _>(&sql, values)
-            .fetch_all(&self.db_pool)
+            .fetch_all(self.read_pool.get())
             .await;
 
         let topups = match topups_result {
@@ -98,7 +489,7 @@ impl TopupRepositoryTrait for TopupRepository {
         );
 
         let total_result = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(self.read_pool.get())
             .await;
 
         let total = match total_result {
@@ -121,6 +512,8 @@ impl TopupRepositoryTrait for TopupRepository {
     }
 
     async fn find_by_id(&self, id: i32) -> Result<Option<Topup>, AppError> {
+        let _span = DbSpan::start("topups", "find_by_id");
+
         info!("🆔 [Topups] Finding topup by ID: {id}");
 
         let (sql, values) = Query::select()
@@ -134,6 +527,7 @@ impl TopupRepositoryTrait for TopupRepository {
                 TopupSchema::TopupTime,
                 TopupSchema::CreatedAt,
                 TopupSchema::UpdatedAt,
+                TopupSchema::Status,
             ])
             .and_where(Expr::col(TopupSchema::TopupId).eq(id))
             .build_sqlx(PostgresQueryBuilder);
@@ -141,7 +535,7 @@ impl TopupRepositoryTrait for TopupRepository {
         info!("🧾 [Topups] Executing query: {sql} | Values: {:?}", values);
 
         let row = sqlx::query_as_with::<_, Topup, _>(&sql, values)
-            .fetch_optional(&self.db_pool)
+            .fetch_optional(self.read_pool.get())
             .await
             .map_err(|e| {
                 error!("❌ [Topups] Failed to execute query for topup_id={id}: {e}");
@@ -163,9 +557,70 @@ impl TopupRepositoryTrait for TopupRepository {
         Ok(row)
     }
 
-    async fn find_by_users(&self, id: i32) -> Result<Vec<Topup>, AppError> {
+    async fn find_by_topup_no(&self, topup_no: &str) -> Result<Option<Topup>, AppError> {
+        let _span = DbSpan::start("topups", "find_by_topup_no");
+
+        info!("🔖 [Topups] Finding topup by topup_no: {topup_no}");
+
+        let (sql, values) = Query::select()
+            .from(TopupSchema::Table)
+            .columns([
+                TopupSchema::TopupId,
+                TopupSchema::UserId,
+                TopupSchema::TopupNo,
+                TopupSchema::TopupAmount,
+                TopupSchema::TopupMethod,
+                TopupSchema::TopupTime,
+                TopupSchema::CreatedAt,
+                TopupSchema::UpdatedAt,
+                TopupSchema::Status,
+            ])
+            .and_where(Expr::col(TopupSchema::TopupNo).eq(topup_no))
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [Topups] Executing query: {sql} | Values: {:?}", values);
+
+        let row = sqlx::query_as_with::<_, Topup, _>(&sql, values)
+            .fetch_optional(self.read_pool.get())
+            .await
+            .map_err(|e| {
+                error!("❌ [Topups] Failed to execute query for topup_no={topup_no}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        match &row {
+            Some(topup) => {
+                info!(
+                    "✅ [Topups] Found! ID: {}, User ID: {}, No: {}",
+                    topup.topup_id, topup.user_id, topup.topup_no
+                );
+            }
+            None => {
+                info!("🟡 [Topups] Not found for topup_no={topup_no}");
+            }
+        }
+
+        Ok(row)
+    }
+
+    async fn find_by_users(
+        &self,
+        id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Topup>, i64), AppError> {
+        let _span = DbSpan::start("topups", "find_by_users");
+
         info!("👥 [Topups] Fetching all topups for user_id: {id}");
 
+        let page = if page > 0 { page } else { 1 };
+        let page_size = if page_size > 0 {
+            page_size
+        } else {
+            default_page_size()
+        };
+        let offset = (page - 1) * page_size;
+
         let (sql, values) = Query::select()
             .from(TopupSchema::Table)
             .columns([
@@ -177,14 +632,18 @@ impl TopupRepositoryTrait for TopupRepository {
                 TopupSchema::TopupTime,
                 TopupSchema::CreatedAt,
                 TopupSchema::UpdatedAt,
+                TopupSchema::Status,
             ])
             .and_where(Expr::col(TopupSchema::UserId).eq(id))
+            .order_by(TopupSchema::TopupId, Order::Asc)
+            .limit(page_size as u64)
+            .offset(offset as u64)
             .build_sqlx(PostgresQueryBuilder);
 
         info!("🧾 [Topups] Executing query: {sql} | Values: {:?}", values);
 
         let rows = sqlx::query_as_with::<_, Topup, _>(&sql, values)
-            .fetch_all(&self.db_pool)
+            .fetch_all(self.read_pool.get())
             .await
             .map_err(|e| {
                 error!("❌ [Topups] Failed to fetch topups for user_id={id}: {e}",);
@@ -196,10 +655,26 @@ impl TopupRepositoryTrait for TopupRepository {
             rows.len(),
         );
 
-        Ok(rows)
+        let (count_sql, count_values) = Query::select()
+            .expr(Func::count(Expr::col(TopupSchema::TopupId)))
+            .from(TopupSchema::Table)
+            .and_where(Expr::col(TopupSchema::UserId).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (total,) = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
+            .fetch_one(self.read_pool.get())
+            .await
+            .map_err(|e| {
+                error!("❌ [Topups] Failed to count topups for user_id={id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        Ok((rows, total))
     }
 
     async fn find_by_user(&self, id: i32) -> Result<Option<Topup>, AppError> {
+        let _span = DbSpan::start("topups", "find_by_user");
+
         info!("👤 [Topups] Finding one topup for user_id: {id}");
 
         let (sql, values) = Query::select()
@@ -213,6 +688,7 @@ impl TopupRepositoryTrait for TopupRepository {
                 TopupSchema::TopupTime,
                 TopupSchema::CreatedAt,
                 TopupSchema::UpdatedAt,
+                TopupSchema::Status,
             ])
             .and_where(Expr::col(TopupSchema::UserId).eq(id))
             .build_sqlx(PostgresQueryBuilder);
@@ -220,7 +696,7 @@ impl TopupRepositoryTrait for TopupRepository {
         info!("🧾 [Topups] Executing query: {sql} | Values: {:?}", values);
 
         let row = sqlx::query_as_with::<_, Topup, _>(&sql, values)
-            .fetch_optional(&self.db_pool)
+            .fetch_optional(self.read_pool.get())
             .await
             .map_err(|e| {
                 error!("❌ [Topups] Failed to execute query for user_id={id}: {e}",);
@@ -242,7 +718,46 @@ impl TopupRepositoryTrait for TopupRepository {
         Ok(row)
     }
 
+    async fn find_recent_by_user(&self, user_id: i32, limit: i32) -> Result<Vec<Topup>, AppError> {
+        let _span = DbSpan::start("topups", "find_recent_by_user");
+
+        let (sql, values) = Query::select()
+            .from(TopupSchema::Table)
+            .columns([
+                TopupSchema::TopupId,
+                TopupSchema::UserId,
+                TopupSchema::TopupNo,
+                TopupSchema::TopupAmount,
+                TopupSchema::TopupMethod,
+                TopupSchema::TopupTime,
+                TopupSchema::CreatedAt,
+                TopupSchema::UpdatedAt,
+                TopupSchema::Status,
+            ])
+            .and_where(Expr::col(TopupSchema::UserId).eq(user_id))
+            .order_by(TopupSchema::TopupId, Order::Desc)
+            .limit(limit.max(0) as u64)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let rows = sqlx::query_as_with::<_, Topup, _>(&sql, values)
+            .fetch_all(self.read_pool.get())
+            .await
+            .map_err(|e| {
+                error!("❌ [Topups] Failed to fetch recent topups for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Topups] Retrieved {} recent topup(s) for user_id={user_id}",
+            rows.len()
+        );
+
+        Ok(rows)
+    }
+
     async fn create(&self, input: &CreateTopupRequest) -> Result<Topup, AppError> {
+        let _span = DbSpan::start("topups", "create");
+
         info!(
             "💳 [Topups] Creating new topup: user_id={}, amount={}, method={}",
             input.user_id, input.topup_amount, input.topup_method
@@ -258,6 +773,7 @@ impl TopupRepositoryTrait for TopupRepository {
                 TopupSchema::TopupAmount,
                 TopupSchema::TopupMethod,
                 TopupSchema::TopupTime,
+                TopupSchema::Status,
             ])
             .values([
                 input.user_id.into(),
@@ -265,6 +781,7 @@ impl TopupRepositoryTrait for TopupRepository {
                 input.topup_amount.into(),
                 input.topup_method.clone().into(),
                 now.into(),
+                TopupStatus::Pending.as_str().into(),
             ])
             .unwrap()
             .returning_all()
@@ -280,7 +797,7 @@ impl TopupRepositoryTrait for TopupRepository {
                     "❌ [Topups] Failed to create topup for user_id={}: {e}",
                     input.user_id,
                 );
-                AppError::SqlxError(e)
+                map_insert_error(e)
             })?;
 
         info!(
@@ -292,24 +809,44 @@ impl TopupRepositoryTrait for TopupRepository {
     }
 
     async fn update(&self, input: &UpdateTopupRequest) -> Result<Topup, AppError> {
-        info!(
-            "🔄 [Topups] Updating full topup with ID: {}",
-            input.topup_id
-        );
+        let _span = DbSpan::start("topups", "update");
 
-        let now = Utc::now().naive_utc();
+        info!("🔄 [Topups] Updating topup with ID: {}", input.topup_id);
 
-        let (sql, values) = Query::update()
+        let mut update_query = Query::update();
+        let mut query = update_query
             .table(TopupSchema::Table)
-            .values([
-                (TopupSchema::TopupAmount, input.topup_amount.into()),
-                (TopupSchema::TopupMethod, input.topup_method.clone().into()),
-                (TopupSchema::TopupTime, now.into()),
-            ])
-            .and_where(Expr::col(TopupSchema::TopupId).eq(input.topup_id))
-            .build_sqlx(PostgresQueryBuilder);
+            .and_where(Expr::col(TopupSchema::TopupId).eq(input.topup_id));
+
+        let mut updated_fields = Vec::new();
+
+        if let Some(topup_amount) = input.topup_amount {
+            query = query.value(TopupSchema::TopupAmount, topup_amount);
+            updated_fields.push(format!("topup_amount={topup_amount}"));
+        }
+
+        if let Some(ref topup_method) = input.topup_method {
+            query = query.value(TopupSchema::TopupMethod, topup_method.clone());
+            updated_fields.push(format!("topup_method='{topup_method}'"));
+        }
+
+        // field yang tidak dikirim tidak disentuh; tanpa field apa pun topup dikembalikan apa adanya
+        if updated_fields.is_empty() {
+            info!(
+                "🟡 [Topups] No fields to update for topup ID: {}",
+                input.topup_id
+            );
+            return self.find_by_id(input.topup_id).await?.ok_or_else(|| {
+                AppError::NotFound(format!("Topup with ID {} not found", input.topup_id))
+            });
+        }
+
+        query = query.value(TopupSchema::UpdatedAt, Expr::current_timestamp());
+
+        let (sql, values) = query.returning_all().build_sqlx(PostgresQueryBuilder);
 
         info!("🧾 [Topups] Executing UPDATE: {sql} | Values: {:?}", values);
+        info!("📝 [Topups] Updating fields: {}", updated_fields.join(", "));
 
         let updated = sqlx::query_as_with::<_, Topup, _>(&sql, values)
             .fetch_one(&self.db_pool)
@@ -339,7 +876,16 @@ impl TopupRepositoryTrait for TopupRepository {
         Ok(updated)
     }
 
+    async fn update_with_settlement(&self, input: &UpdateTopupRequest) -> Result<Topup, AppError> {
+        with_sql_retry("topups.update_with_settlement", || {
+            self.update_with_settlement_once(input)
+        })
+        .await
+    }
+
     async fn update_amount(&self, input: &UpdateTopupAmount) -> Result<Topup, AppError> {
+        let _span = DbSpan::start("topups", "update_amount");
+
         info!(
             "💵 [Topups] Updating amount for topup ID {}: {} → {}",
             input.topup_id, "current", input.topup_amount
@@ -386,6 +932,8 @@ impl TopupRepositoryTrait for TopupRepository {
     }
 
     async fn delete(&self, id: i32) -> Result<(), AppError> {
+        let _span = DbSpan::start("topups", "delete");
+
         info!("🗑️ [Topups] Deleting topup with ID: {id}");
 
         let (sql, values) = Query::delete()
@@ -411,4 +959,319 @@ impl TopupRepositoryTrait for TopupRepository {
         info!("✅ [Topups] Successfully deleted topup ID: {id}");
         Ok(())
     }
+
+    async fn confirm(&self, id: i32) -> Result<Topup, AppError> {
+        with_sql_retry("topups.confirm", || self.confirm_once(id)).await
+    }
+
+    async fn fail(&self, id: i32) -> Result<Topup, AppError> {
+        let _span = DbSpan::start("topups", "fail");
+
+        info!("❌ [Topups] Marking topup ID {id} as failed");
+
+        let (sql, values) = Query::update()
+            .table(TopupSchema::Table)
+            .values([
+                (TopupSchema::Status, TopupStatus::Failed.as_str().into()),
+                (TopupSchema::UpdatedAt, Utc::now().naive_utc().into()),
+            ])
+            .and_where(Expr::col(TopupSchema::TopupId).eq(id))
+            .and_where(Expr::col(TopupSchema::Status).eq(TopupStatus::Pending.as_str()))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [Topups] Executing FAIL: {sql} | Values: {:?}", values);
+
+        let failed = sqlx::query_as_with::<_, Topup, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Topups] Failed to mark topup ID {id} as failed: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        match failed {
+            Some(topup) => {
+                info!("✅ [Topups] Topup ID {id} marked as failed");
+                Ok(topup)
+            }
+            None => Err(self.transition_error(id, TopupStatus::Failed).await),
+        }
+    }
+
+    async fn refund(&self, id: i32) -> Result<Topup, AppError> {
+        with_sql_retry("topups.refund", || self.refund_once(id)).await
+    }
+
+    async fn stats_by_method(
+        &self,
+        req: &TopupStatsRequest,
+    ) -> Result<Vec<TopupMethodStats>, AppError> {
+        let _span = DbSpan::start("topups", "stats_by_method");
+
+        info!(
+            "📊 [Topups] Aggregating topups by method - start_date: {:?}, end_date: {:?}",
+            req.start_date, req.end_date
+        );
+
+        let mut select_query = Query::select();
+        select_query
+            .column(TopupSchema::TopupMethod)
+            .expr_as(
+                Func::count(Expr::col(TopupSchema::TopupId)),
+                Alias::new("total_count"),
+            )
+            .expr_as(
+                Func::sum(Expr::col(TopupSchema::TopupAmount)),
+                Alias::new("total_amount"),
+            )
+            .from(TopupSchema::Table)
+            .group_by_col(TopupSchema::TopupMethod)
+            .order_by(TopupSchema::TopupMethod, Order::Asc);
+
+        if let Some(start) = req.start_time() {
+            select_query.and_where(Expr::col(TopupSchema::TopupTime).gte(start));
+        }
+
+        if let Some(end) = req.end_time() {
+            select_query.and_where(Expr::col(TopupSchema::TopupTime).lt(end));
+        }
+
+        let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
+        info!("🧾 [Topups] Generated SQL: {sql} | Values: {:?}", values);
+
+        let stats = sqlx::query_as_with::<_, TopupMethodStats, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Topups] Failed to aggregate topups by method: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!("✅ [Topups] Aggregated {} topup method(s)", stats.len());
+
+        Ok(stats)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::test_db;
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+
+    // topup yang sudah dikonfirmasi, saldo user sudah bertambah `amount`
+    async fn confirmed_topup(repository: &TopupRepository, user_id: i32, amount: i32) -> Topup {
+        let topup = repository
+            .create(&CreateTopupRequest {
+                user_id,
+                topup_no: Uuid::new_v4().simple().to_string(),
+                topup_amount: amount,
+                topup_method: "bri".to_string(),
+            })
+            .await
+            .unwrap();
+
+        repository.confirm(topup.topup_id).await.unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn second_refund_is_rejected() {
+        let pool = test_db::pool().await;
+        let user_id = test_db::seed_user(&pool, 0).await;
+        let repository = TopupRepository::new(pool.clone());
+
+        let topup = confirmed_topup(&repository, user_id, 50_000).await;
+        assert_eq!(test_db::balance_of(&pool, user_id).await, 50_000);
+
+        let refunded = repository.refund(topup.topup_id).await.unwrap();
+        assert_eq!(refunded.status, TopupStatus::Refunded.as_str());
+        assert_eq!(test_db::balance_of(&pool, user_id).await, 0);
+
+        let err = repository.refund(topup.topup_id).await.unwrap_err();
+        assert!(
+            matches!(err, AppError::Custom(ref msg) if *msg == transition_rejection(TopupStatus::Refunded))
+        );
+        assert_eq!(test_db::balance_of(&pool, user_id).await, 0);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn refund_fails_when_the_credit_was_spent() {
+        let pool = test_db::pool().await;
+        let user_id = test_db::seed_user(&pool, 0).await;
+        let repository = TopupRepository::new(pool.clone());
+
+        let topup = confirmed_topup(&repository, user_id, 50_000).await;
+
+        // sebagian besar kredit topup sudah dipakai sebelum refund
+        sqlx::query("UPDATE saldo SET total_balance = 10000 WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let err = repository.refund(topup.topup_id).await.unwrap_err();
+        assert!(matches!(err, AppError::Custom(ref msg) if msg == "Insufficient balance"));
+        assert_eq!(test_db::balance_of(&pool, user_id).await, 10_000);
+
+        let unchanged = repository
+            .find_by_id(topup.topup_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(unchanged.status, TopupStatus::Success.as_str());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn settled_update_rolls_back_the_topup_when_the_saldo_is_short() {
+        let pool = test_db::pool().await;
+        let user_id = test_db::seed_user(&pool, 0).await;
+        let repository = TopupRepository::new(pool.clone());
+
+        let topup = confirmed_topup(&repository, user_id, 50_000).await;
+
+        let raised = repository
+            .update_with_settlement(&UpdateTopupRequest {
+                user_id,
+                topup_id: topup.topup_id,
+                topup_amount: Some(70_000),
+                topup_method: None,
+            })
+            .await
+            .unwrap();
+        assert_eq!(raised.topup_amount, 70_000);
+        assert_eq!(test_db::balance_of(&pool, user_id).await, 70_000);
+
+        sqlx::query("UPDATE saldo SET total_balance = 10000 WHERE user_id = $1")
+            .bind(user_id)
+            .execute(&pool)
+            .await
+            .unwrap();
+
+        let err = repository
+            .update_with_settlement(&UpdateTopupRequest {
+                user_id,
+                topup_id: topup.topup_id,
+                topup_amount: Some(20_000),
+                topup_method: Some("mandiri".to_string()),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Custom(ref msg) if msg == "Insufficient balance"));
+        assert_eq!(test_db::balance_of(&pool, user_id).await, 10_000);
+
+        let unchanged = repository
+            .find_by_id(topup.topup_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(unchanged.topup_amount, 70_000);
+        assert_eq!(unchanged.topup_method, "bri");
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn stats_sum_each_method_within_the_range() {
+        let pool = test_db::pool().await;
+        let user_id = test_db::seed_user(&pool, 0).await;
+        let repository = TopupRepository::new(pool.clone());
+        let tag = Uuid::new_v4().simple().to_string();
+        let (bank, card) = (format!("bank-{tag}"), format!("card-{tag}"));
+
+        for (method, amount, day) in [
+            (&bank, 50_000, "2001-02-03"),
+            (&bank, 70_000, "2001-02-04"),
+            (&card, 20_000, "2001-02-04"),
+            // di luar rentang
+            (&card, 99_000, "2001-02-06"),
+        ] {
+            sqlx::query(
+                "INSERT INTO topups (user_id, topup_no, topup_amount, topup_method, topup_time) \
+                 VALUES ($1, $2, $3, $4, $5::date)",
+            )
+            .bind(user_id)
+            .bind(Uuid::new_v4().simple().to_string())
+            .bind(amount)
+            .bind(method)
+            .bind(day)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let stats = repository
+            .stats_by_method(&TopupStatsRequest {
+                start_date: NaiveDate::from_ymd_opt(2001, 2, 3),
+                end_date: NaiveDate::from_ymd_opt(2001, 2, 4),
+            })
+            .await
+            .unwrap();
+
+        let ours: Vec<_> = stats
+            .iter()
+            .filter(|s| s.topup_method.ends_with(&tag))
+            .map(|s| (s.topup_method.clone(), s.total_count, s.total_amount))
+            .collect();
+        assert_eq!(ours, [(bank, 2, 120_000), (card, 1, 20_000)]);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn find_by_users_pages_a_heavy_user() {
+        let pool = test_db::pool().await;
+        let user_id = test_db::seed_user(&pool, 0).await;
+        let repository = TopupRepository::new(pool.clone());
+        for _ in 0..3 {
+            confirmed_topup(&repository, user_id, 50_000).await;
+        }
+
+        let (first, total) = repository.find_by_users(user_id, 1, 2).await.unwrap();
+        let (second, _) = repository.find_by_users(user_id, 2, 2).await.unwrap();
+
+        assert_eq!(total, 3);
+        assert_eq!(first.len(), 2);
+        assert_eq!(second.len(), 1);
+        assert!(first.iter().all(|t| t.topup_id < second[0].topup_id));
+
+        // page dan page_size tidak valid jatuh ke halaman pertama ukuran bawaan
+        let (fallback, _) = repository.find_by_users(user_id, 0, 0).await.unwrap();
+        assert_eq!(fallback.len(), 3);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn duplicate_topup_no_is_rejected_by_the_unique_index() {
+        let pool = test_db::pool().await;
+        let user_id = test_db::seed_user(&pool, 0).await;
+        let repository = TopupRepository::new(pool.clone());
+        let request = CreateTopupRequest {
+            user_id,
+            topup_no: Uuid::new_v4().simple().to_string(),
+            topup_amount: 50_000,
+            topup_method: "bri".to_string(),
+        };
+
+        let created = repository.create(&request).await.unwrap();
+        let found = repository
+            .find_by_topup_no(&request.topup_no)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found.topup_id, created.topup_id);
+
+        let err = repository.create(&request).await.unwrap_err();
+        assert!(matches!(err, AppError::TopupNoAlreadyExists));
+
+        assert!(
+            repository
+                .find_by_topup_no(&Uuid::new_v4().simple().to_string())
+                .await
+                .unwrap()
+                .is_none()
+        );
+    }
 }
@@ -1,27 +1,600 @@
-use crate::model::transfer::Transfer;
+use crate::domain::request::default_page_size;
+use crate::model::outbox::TransferEvent;
+use crate::model::saldo::DEFAULT_SALDO_LABEL;
+use crate::model::transfer::{DailyTransferTotal, Transfer, TransferPreview, TransferStatus};
+use crate::repository::retry::{unknown_outcome, with_sql_retry};
+use crate::repository::saldo::map_balance_error;
+use crate::schema::outbox::Outbox as OutboxSchema;
+use crate::schema::saldo::Saldo as SaldoSchema;
 use crate::schema::transfer::Transfers as TransferSchema;
-use crate::utils::AppError;
+use crate::schema::user::Users as UserSchema;
+use crate::utils::{AppError, DbSpan, checked_amount};
 use crate::{
     abstract_trait::TransferRepositoryTrait,
-    config::ConnectionPool,
+    config::{ConnectionPool, ReadPool},
     domain::request::transfer::{
-        CreateTransferRequest, UpdateTransferAmountRequest, UpdateTransferRequest,
+        CreateTransferRequest, DailyTransferTotalsRequest, StreamTransfersRequest, TransferFilter,
+        UpdateTransferAmountRequest, UpdateTransferRequest,
     },
 };
 use anyhow::Result;
 use async_trait::async_trait;
-use chrono::Utc;
-use sea_query::{Expr, Func, Order, PostgresQueryBuilder, Query};
+use chrono::{NaiveDateTime, Utc};
+use sea_query::{
+    Alias, Asterisk, Cond, Expr, Func, LockType, Order, PostgresQueryBuilder, Query,
+    SelectStatement, UnionType,
+};
 use sea_query_binder::SqlxBinder;
+use sqlx::PgConnection;
+use std::time::Duration;
 use tracing::{error, info};
 
 pub struct TransferRepository {
     db_pool: ConnectionPool,
+    read_pool: ReadPool,
 }
 
 impl TransferRepository {
     pub fn new(db_pool: ConnectionPool) -> Self {
-        Self { db_pool }
+        Self {
+            read_pool: ReadPool::primary(db_pool.clone()),
+            db_pool,
+        }
+    }
+
+    pub fn with_read_pool(mut self, read_pool: ReadPool) -> Self {
+        self.read_pool = read_pool;
+        self
+    }
+
+    async fn create_with_settlement_once(
+        &self,
+        input: &CreateTransferRequest,
+        status: TransferStatus,
+    ) -> Result<Transfer, AppError> {
+        let _span = DbSpan::start("transfers", "create_with_settlement");
+
+        info!(
+            "💸 [Transfers] Creating settled transfer: {} → {} | Amount: {}",
+            input.transfer_from, input.transfer_to, input.transfer_amount
+        );
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("❌ [Transfers] Failed to begin transfer transaction: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        let now = Utc::now().naive_utc();
+
+        // transfer yang ditahan untuk review hanya dicek saldonya, mutasi menunggu persetujuan
+        let created = if status == TransferStatus::NeedsReview {
+            project_settlement(&mut tx, input).await?;
+            let created = insert_transfer(&mut tx, input, status, None, now).await?;
+            enqueue_event(&mut tx, &TransferEvent::needs_review(&created)).await?;
+            created
+        } else {
+            let created = insert_transfer(&mut tx, input, status, None, now).await?;
+            settle_transfer(&mut tx, &created, now).await?;
+            created
+        };
+
+        tx.commit().await.map_err(|e| {
+            error!(
+                "❌ [Transfers] Failed to commit transfer ID {}: {e}",
+                created.transfer_id
+            );
+            unknown_outcome(e)
+        })?;
+
+        info!(
+            "✅ [Transfers] Settled transfer ID: {} | Amount: {}",
+            created.transfer_id, created.transfer_amount
+        );
+
+        Ok(created)
+    }
+
+    async fn reverse_once(&self, id: i32) -> Result<Transfer, AppError> {
+        let _span = DbSpan::start("transfers", "reverse");
+
+        info!("↩️ [Transfers] Reversing transfer ID: {id}");
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("❌ [Transfers] Failed to begin reversal transaction for ID {id}: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        let now = Utc::now().naive_utc();
+
+        let original = lock_transfer(&mut tx, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Transfer with ID {id} not found")))?;
+
+        if original.is_reversed() {
+            error!("🟡 [Transfers] Transfer ID {id} has already been reversed");
+            return Err(AppError::Custom(format!(
+                "Transfer with ID {id} has already been reversed"
+            )));
+        }
+
+        // membalik transfer kompensasi berarti memindahkan dana yang sama untuk kedua kalinya
+        if let Some(original_id) = reversal_of(&mut tx, id).await? {
+            error!("🟡 [Transfers] Transfer ID {id} is the reversal of transfer ID {original_id}");
+            return Err(AppError::Custom(format!(
+                "Transfer with ID {id} is a reversal and cannot be reversed"
+            )));
+        }
+
+        let reversed = set_transfer_status(&mut tx, id, TransferStatus::Reversed, now).await?;
+
+        // dana transfer yang ditahan belum pernah berpindah, jadi cukup dibatalkan
+        if original.needs_review() {
+            tx.commit().await.map_err(|e| {
+                error!("❌ [Transfers] Failed to commit cancellation of transfer ID {id}: {e}");
+                unknown_outcome(e)
+            })?;
+
+            info!("✅ [Transfers] Held transfer ID {id} cancelled without moving funds");
+
+            return Ok(reversed);
+        }
+
+        // transfer kompensasi ke arah sebaliknya dengan nominal yang sama
+        let compensation = CreateTransferRequest {
+            transfer_from: original.transfer_to,
+            transfer_to: original.transfer_from,
+            transfer_amount: original.transfer_amount,
+            note: Some(format!("Reversal of transfer #{id}")),
+            dry_run: false,
+            from_label: Some(original.to_label.clone()),
+            to_label: Some(original.from_label.clone()),
+        };
+
+        let reversal = insert_transfer(
+            &mut tx,
+            &compensation,
+            TransferStatus::Completed,
+            Some(id),
+            now,
+        )
+        .await?;
+        settle_transfer(&mut tx, &reversal, now).await?;
+
+        tx.commit().await.map_err(|e| {
+            error!("❌ [Transfers] Failed to commit reversal of transfer ID {id}: {e}");
+            unknown_outcome(e)
+        })?;
+
+        info!(
+            "✅ [Transfers] Transfer ID {id} reversed by transfer ID {} | Amount: {}",
+            reversal.transfer_id, reversal.transfer_amount
+        );
+
+        Ok(reversal)
+    }
+
+    async fn approve_once(&self, id: i32) -> Result<Transfer, AppError> {
+        let _span = DbSpan::start("transfers", "approve");
+
+        info!("✔️ [Transfers] Approving held transfer ID: {id}");
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("❌ [Transfers] Failed to begin approval transaction for ID {id}: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        let now = Utc::now().naive_utc();
+
+        let original = lock_transfer(&mut tx, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Transfer with ID {id} not found")))?;
+
+        if !original.needs_review() {
+            error!("🟡 [Transfers] Transfer ID {id} is not awaiting review");
+            return Err(AppError::Custom(format!(
+                "Transfer with ID {id} is not awaiting review"
+            )));
+        }
+
+        // saldo dicek ulang saat dana benar-benar berpindah, bukan saat transfer ditahan
+        let approved = set_transfer_status(&mut tx, id, TransferStatus::Completed, now).await?;
+        settle_transfer(&mut tx, &approved, now).await?;
+
+        tx.commit().await.map_err(|e| {
+            error!("❌ [Transfers] Failed to commit approval of transfer ID {id}: {e}");
+            unknown_outcome(e)
+        })?;
+
+        info!(
+            "✅ [Transfers] Held transfer ID {id} approved and settled | Amount: {}",
+            approved.transfer_amount
+        );
+
+        Ok(approved)
+    }
+
+    async fn update_with_settlement_once(
+        &self,
+        input: &UpdateTransferRequest,
+    ) -> Result<Transfer, AppError> {
+        let _span = DbSpan::start("transfers", "update_with_settlement");
+
+        let id = input.transfer_id;
+
+        info!("🔄 [Transfers] Updating transfer ID {id} with balance adjustment");
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("❌ [Transfers] Failed to begin update transaction for ID {id}: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        let now = Utc::now().naive_utc();
+
+        let original = lock_transfer(&mut tx, id)
+            .await?
+            .ok_or_else(|| AppError::NotFound(format!("Transfer with ID {id} not found")))?;
+
+        // transfer yang ditahan atau sudah di-reverse tidak punya dana yang bisa disesuaikan
+        if !original.is_completed() {
+            error!(
+                "🟡 [Transfers] Transfer ID {id} is {} and cannot be edited",
+                original.status
+            );
+            return Err(AppError::Custom(format!(
+                "Only completed transfers can be edited, transfer {id} is {}",
+                original.status
+            )));
+        }
+
+        let difference =
+            checked_amount(input.transfer_amount.checked_sub(original.transfer_amount))?;
+
+        // selisih nominal dipindahkan dalam transaksi yang sama dengan perubahan baris transfer
+        move_balance(
+            &mut tx,
+            original.transfer_from,
+            &original.from_label,
+            -difference,
+            now,
+        )
+        .await?;
+        move_balance(
+            &mut tx,
+            original.transfer_to,
+            &original.to_label,
+            difference,
+            now,
+        )
+        .await?;
+
+        // pihak transfer tidak ikut diubah: selisih di atas hanya dipindahkan antara pihak asli
+        let (sql, values) = Query::update()
+            .table(TransferSchema::Table)
+            .values([
+                (TransferSchema::TransferAmount, input.transfer_amount.into()),
+                (TransferSchema::TransferTime, now.into()),
+                (TransferSchema::UpdatedAt, now.into()),
+                (TransferSchema::Note, input.note.clone().into()),
+            ])
+            .and_where(Expr::col(TransferSchema::TransferId).eq(id))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let updated = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Database error updating transfer ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        tx.commit().await.map_err(|e| {
+            error!("❌ [Transfers] Failed to commit update of transfer ID {id}: {e}");
+            unknown_outcome(e)
+        })?;
+
+        info!(
+            "✅ [Transfers] Updated transfer ID {id} | Amount: {} → {}",
+            original.transfer_amount, updated.transfer_amount
+        );
+
+        Ok(updated)
+    }
+}
+
+// baris transfer dikunci sampai transaksi selesai, sehingga reversal dan persetujuan yang
+// berjalan bersamaan untuk transfer yang sama saling menunggu
+async fn lock_transfer(conn: &mut PgConnection, id: i32) -> Result<Option<Transfer>, AppError> {
+    let (sql, values) = Query::select()
+        .column(Asterisk)
+        .from(TransferSchema::Table)
+        .and_where(Expr::col(TransferSchema::TransferId).eq(id))
+        .lock(LockType::Update)
+        .build_sqlx(PostgresQueryBuilder);
+
+    sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!("❌ [Transfers] Failed to lock transfer ID {id}: {e}");
+            AppError::SqlxError(e)
+        })
+}
+
+// kolom reversal_of tidak ikut model Transfer; terisi hanya pada transfer kompensasi
+async fn reversal_of(conn: &mut PgConnection, id: i32) -> Result<Option<i32>, AppError> {
+    let (sql, values) = Query::select()
+        .column(TransferSchema::ReversalOf)
+        .from(TransferSchema::Table)
+        .and_where(Expr::col(TransferSchema::TransferId).eq(id))
+        .build_sqlx(PostgresQueryBuilder);
+
+    let row = sqlx::query_as_with::<_, (Option<i32>,), _>(&sql, values)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!("❌ [Transfers] Failed to read reversal_of of transfer ID {id}: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+    Ok(row.and_then(|(original_id,)| original_id))
+}
+
+async fn set_transfer_status(
+    conn: &mut PgConnection,
+    id: i32,
+    status: TransferStatus,
+    now: NaiveDateTime,
+) -> Result<Transfer, AppError> {
+    let (sql, values) = Query::update()
+        .table(TransferSchema::Table)
+        .values([
+            (TransferSchema::Status, status.as_str().into()),
+            (TransferSchema::UpdatedAt, now.into()),
+        ])
+        .and_where(Expr::col(TransferSchema::TransferId).eq(id))
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder);
+
+    sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!(
+                "❌ [Transfers] Failed to mark transfer ID {id} as {}: {e}",
+                status.as_str()
+            );
+            AppError::SqlxError(e)
+        })
+}
+
+// `reversal_of` diisi hanya untuk transfer kompensasi dari reversal
+async fn insert_transfer(
+    conn: &mut PgConnection,
+    input: &CreateTransferRequest,
+    status: TransferStatus,
+    reversal_of: Option<i32>,
+    now: NaiveDateTime,
+) -> Result<Transfer, AppError> {
+    let (sql, values) = Query::insert()
+        .into_table(TransferSchema::Table)
+        .columns([
+            TransferSchema::TransferFrom,
+            TransferSchema::TransferTo,
+            TransferSchema::TransferAmount,
+            TransferSchema::TransferTime,
+            TransferSchema::Note,
+            TransferSchema::FromLabel,
+            TransferSchema::ToLabel,
+            TransferSchema::Status,
+            TransferSchema::ReversalOf,
+        ])
+        .values([
+            input.transfer_from.into(),
+            input.transfer_to.into(),
+            input.transfer_amount.into(),
+            now.into(),
+            input.note.clone().into(),
+            input.from_label().into(),
+            input.to_label().into(),
+            status.as_str().into(),
+            reversal_of.into(),
+        ])
+        .unwrap()
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder);
+
+    sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+        .fetch_one(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!(
+                "❌ [Transfers] Failed to create transfer ({} → {}): {e}",
+                input.transfer_from, input.transfer_to,
+            );
+            AppError::SqlxError(e)
+        })
+}
+
+// mutasi saldo kedua pihak dan event outbox, dijalankan di dalam transaksi pemanggil;
+// mengembalikan saldo akhir pengirim dan penerima
+async fn settle_transfer(
+    conn: &mut PgConnection,
+    transfer: &Transfer,
+    now: NaiveDateTime,
+) -> Result<(i32, i32), AppError> {
+    // debit pengirim lalu kredit penerima, trigger saldo menolak saldo di bawah batas overdraft
+    let sender_balance = move_balance(
+        conn,
+        transfer.transfer_from,
+        &transfer.from_label,
+        -transfer.transfer_amount,
+        now,
+    )
+    .await?;
+    let receiver_balance = move_balance(
+        conn,
+        transfer.transfer_to,
+        &transfer.to_label,
+        transfer.transfer_amount,
+        now,
+    )
+    .await?;
+
+    enqueue_event(conn, &TransferEvent::completed(transfer)).await?;
+
+    Ok((sender_balance, receiver_balance))
+}
+
+// menambah `delta` ke saldo berlabel milik user di dalam transaksi pemanggil, mengembalikan saldo baru
+async fn move_balance(
+    conn: &mut PgConnection,
+    user_id: i32,
+    label: &str,
+    delta: i32,
+    now: NaiveDateTime,
+) -> Result<i32, AppError> {
+    let (sql, values) = Query::update()
+        .table(SaldoSchema::Table)
+        .values([
+            (
+                SaldoSchema::TotalBalance,
+                Expr::col(SaldoSchema::TotalBalance).add(delta),
+            ),
+            (SaldoSchema::UpdatedAt, now.into()),
+        ])
+        .and_where(Expr::col(SaldoSchema::UserId).eq(user_id))
+        .and_where(Expr::col(SaldoSchema::Label).eq(label))
+        .returning_col(SaldoSchema::TotalBalance)
+        .build_sqlx(PostgresQueryBuilder);
+
+    let balance = sqlx::query_as_with::<_, (i32,), _>(&sql, values)
+        .fetch_optional(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!("❌ [Transfers] Failed to update saldo for user_id={user_id}: {e}");
+            map_balance_error(e)
+        })?;
+
+    match balance {
+        Some((balance,)) => Ok(balance),
+        None => {
+            error!("❌ [Transfers] Saldo '{label}' for user_id={user_id} not found");
+            Err(AppError::NotFound(format!(
+                "Saldo '{label}' with User id {user_id} not found"
+            )))
+        }
+    }
+}
+
+// event webhook ikut transaksi pemanggil, terkirim hanya bila transaksinya commit
+async fn enqueue_event(conn: &mut PgConnection, event: &TransferEvent) -> Result<(), AppError> {
+    let payload = serde_json::to_string(event).map_err(|e| {
+        error!("❌ [Transfers] Failed to serialize transfer event: {e}");
+        AppError::InternalError(format!("Failed to serialize transfer event: {e}"))
+    })?;
+
+    let (sql, values) = Query::insert()
+        .into_table(OutboxSchema::Table)
+        .columns([OutboxSchema::EventType, OutboxSchema::Payload])
+        .values([event.event_type.clone().into(), payload.into()])
+        .unwrap()
+        .build_sqlx(PostgresQueryBuilder);
+
+    sqlx::query_with(&sql, values)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!(
+                "❌ [Transfers] Failed to enqueue {} event for transfer ID {}: {e}",
+                event.event_type, event.transfer_id
+            );
+            AppError::SqlxError(e)
+        })?;
+
+    Ok(())
+}
+
+// saldo akhir kedua pihak tanpa menulis apa pun; aturan batas bawah dan pesan error-nya
+// sama dengan trigger saldo yang menjaga settle_transfer
+async fn project_settlement(
+    conn: &mut PgConnection,
+    input: &CreateTransferRequest,
+) -> Result<(i32, i32), AppError> {
+    let mut rows = Vec::with_capacity(2);
+
+    for (user_id, label) in [
+        (input.transfer_from, input.from_label()),
+        (input.transfer_to, input.to_label()),
+    ] {
+        let (sql, values) = Query::select()
+            .column((SaldoSchema::Table, SaldoSchema::TotalBalance))
+            .column((UserSchema::Table, UserSchema::OverdraftLimit))
+            .from(SaldoSchema::Table)
+            .left_join(
+                UserSchema::Table,
+                Expr::col((UserSchema::Table, UserSchema::UserId))
+                    .equals((SaldoSchema::Table, SaldoSchema::UserId)),
+            )
+            .and_where(Expr::col((SaldoSchema::Table, SaldoSchema::UserId)).eq(user_id))
+            .and_where(Expr::col((SaldoSchema::Table, SaldoSchema::Label)).eq(label))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let row = sqlx::query_as_with::<_, (i32, Option<i32>), _>(&sql, values)
+            .fetch_optional(&mut *conn)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to read saldo for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        match row {
+            Some(row) => rows.push(row),
+            None => {
+                error!("❌ [Transfers] Saldo '{label}' for user_id={user_id} not found");
+                return Err(AppError::NotFound(format!(
+                    "Saldo '{label}' with User id {user_id} not found"
+                )));
+            }
+        }
+    }
+
+    let (sender_balance, overdraft_limit) = rows[0];
+    let sender_balance = checked_amount(sender_balance.checked_sub(input.transfer_amount))?;
+
+    // overdraft hanya berlaku untuk saldo utama, saldo berlabel minimal 0
+    let balance_floor = if input.from_label() == DEFAULT_SALDO_LABEL {
+        -overdraft_limit.unwrap_or(0)
+    } else {
+        0
+    };
+    if sender_balance < balance_floor {
+        return Err(AppError::Custom("Insufficient balance".to_string()));
+    }
+
+    let receiver_balance = checked_amount(rows[1].0.checked_add(input.transfer_amount))?;
+
+    Ok((sender_balance, receiver_balance))
+}
+
+fn apply_transfer_filter(query: &mut SelectStatement, filter: &TransferFilter) {
+    if let Some(ref term) = filter.search {
+        query.and_where(Expr::col(TransferSchema::TransferFrom).like(format!("{term}%")));
+    }
+
+    if let Some(min_amount) = filter.min_amount {
+        query.and_where(Expr::col(TransferSchema::TransferAmount).gte(min_amount));
+    }
+
+    if let Some(max_amount) = filter.max_amount {
+        query.and_where(Expr::col(TransferSchema::TransferAmount).lte(max_amount));
+    }
+
+    if !filter.statuses.is_empty() {
+        query.and_where(
+            Expr::col(TransferSchema::Status)
+                .is_in(filter.statuses.iter().map(|status| status.as_str())),
+        );
     }
 }
 
@@ -31,15 +604,21 @@ impl TransferRepositoryTrait for TransferRepository {
         &self,
         page: i32,
         page_size: i32,
-        search: Option<String>,
+        filter: &TransferFilter,
     ) -> Result<(Vec<Transfer>, i64), AppError> {
+        let _span = DbSpan::start("transfers", "find_all");
+
         info!(
-            "🔄 [Transfers] Fetching transfers - page: {page}, page_size: {page_size}, search: {:?}",
-            search
+            "🔄 [Transfers] Fetching transfers - page: {page}, page_size: {page_size}, filter: {:?}",
+            filter
         );
 
         let page = if page > 0 { page } else { 1 };
-        let page_size = if page_size > 0 { page_size } else { 10 };
+        let page_size = if page_size > 0 {
+            page_size
+        } else {
+            default_page_size()
+        };
         let offset = (page - 1) * page_size;
 
         info!("🔢 [Transfers] Using pagination: LIMIT={page_size} OFFSET={offset}",);
@@ -54,23 +633,26 @@ impl TransferRepositoryTrait for TransferRepository {
                 TransferSchema::TransferTime,
                 TransferSchema::CreatedAt,
                 TransferSchema::UpdatedAt,
+                TransferSchema::Note,
+                TransferSchema::Status,
+                TransferSchema::FromLabel,
+                TransferSchema::ToLabel,
             ])
             .from(TransferSchema::Table)
             .order_by(TransferSchema::TransferId, Order::Asc)
             .limit(page_size as u64)
             .offset(offset as u64);
 
-        if let Some(ref term) = search {
-            select_query
-                .and_where(Expr::col(TransferSchema::TransferFrom).like(format!("{term}%")));
+        if let Some(ref term) = filter.search {
             info!("🔍 [Transfers] Filtering by sender (transfer_from) like: {term}%");
         }
+        apply_transfer_filter(&mut select_query, filter);
 
         let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
         info!("🧾 [Transfers] Generated SQL: {sql} | Values: {:?}", values);
 
         let transfer_result = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
-            .fetch_all(&self.db_pool)
+            .fetch_all(self.read_pool.get())
             .await;
 
         let transfers = match transfer_result {
@@ -91,10 +673,7 @@ impl TransferRepositoryTrait for TransferRepository {
         count_query
             .expr(Func::count(Expr::col(TransferSchema::TransferId)))
             .from(TransferSchema::Table);
-
-        if let Some(ref term) = search {
-            count_query.and_where(Expr::col(TransferSchema::TransferFrom).like(format!("{term}%")));
-        }
+        apply_transfer_filter(&mut count_query, filter);
 
         let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
         info!(
@@ -103,7 +682,7 @@ impl TransferRepositoryTrait for TransferRepository {
         );
 
         let total_result = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(self.read_pool.get())
             .await;
 
         let total = match total_result {
@@ -125,7 +704,119 @@ impl TransferRepositoryTrait for TransferRepository {
         Ok((transfers, total))
     }
 
+    async fn find_batch_after(
+        &self,
+        after_id: i32,
+        req: &StreamTransfersRequest,
+    ) -> Result<Vec<Transfer>, AppError> {
+        let _span = DbSpan::start("transfers", "find_batch_after");
+
+        // keyset pagination: tetap cepat di halaman belakang, tidak seperti OFFSET
+        let mut select_query = Query::select();
+        select_query
+            .columns([
+                TransferSchema::TransferId,
+                TransferSchema::TransferFrom,
+                TransferSchema::TransferTo,
+                TransferSchema::TransferAmount,
+                TransferSchema::TransferTime,
+                TransferSchema::CreatedAt,
+                TransferSchema::UpdatedAt,
+                TransferSchema::Note,
+                TransferSchema::Status,
+                TransferSchema::FromLabel,
+                TransferSchema::ToLabel,
+            ])
+            .from(TransferSchema::Table)
+            .and_where(Expr::col(TransferSchema::TransferId).gt(after_id))
+            .order_by(TransferSchema::TransferId, Order::Asc)
+            .limit(req.batch_size.max(1) as u64);
+
+        if let Some(start) = req.start_time() {
+            select_query.and_where(Expr::col(TransferSchema::TransferTime).gte(start));
+        }
+
+        if let Some(end) = req.end_time() {
+            select_query.and_where(Expr::col(TransferSchema::TransferTime).lt(end));
+        }
+
+        let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
+        info!("🧾 [Transfers] Batch query: {sql} | Values: {:?}", values);
+
+        let transfers = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+            .fetch_all(self.read_pool.get())
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to fetch batch after ID {after_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "📦 [Transfers] Fetched batch of {} transfer(s) after ID {after_id}",
+            transfers.len()
+        );
+
+        Ok(transfers)
+    }
+
+    async fn daily_totals(
+        &self,
+        req: &DailyTransferTotalsRequest,
+    ) -> Result<Vec<DailyTransferTotal>, AppError> {
+        let _span = DbSpan::start("transfers", "daily_totals");
+
+        info!(
+            "📊 [Transfers] Aggregating daily totals from {} to {}",
+            req.start_date, req.end_date
+        );
+
+        // tiap transfer jadi dua baris (pengirim dan penerima) lalu dijumlahkan per user per hari;
+        // hari tanpa transfer tidak punya baris sehingga otomatis tidak muncul. transfer yang
+        // di-reverse tetap dihitung karena pengembaliannya tercatat sebagai transfer tersendiri
+        let mut movements = daily_movements(req, TransferSchema::TransferFrom, true);
+        movements.union(
+            UnionType::All,
+            daily_movements(req, TransferSchema::TransferTo, false),
+        );
+
+        let (sql, values) = Query::select()
+            .column(Alias::new("user_id"))
+            .column(Alias::new("day"))
+            .expr_as(
+                Func::sum(Expr::col(Alias::new("sent"))),
+                Alias::new("total_sent"),
+            )
+            .expr_as(
+                Func::sum(Expr::col(Alias::new("received"))),
+                Alias::new("total_received"),
+            )
+            .from_subquery(movements, Alias::new("movements"))
+            .group_by_columns([Alias::new("day"), Alias::new("user_id")])
+            .order_by(Alias::new("day"), Order::Asc)
+            .order_by(Alias::new("user_id"), Order::Asc)
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [Transfers] Generated SQL: {sql} | Values: {:?}", values);
+
+        let totals = sqlx::query_as_with::<_, DailyTransferTotal, _>(&sql, values)
+            .fetch_all(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to aggregate daily totals: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Transfers] Aggregated {} user-day total(s)",
+            totals.len()
+        );
+
+        Ok(totals)
+    }
+
     async fn find_by_id(&self, id: i32) -> Result<Option<Transfer>, AppError> {
+        let _span = DbSpan::start("transfers", "find_by_id");
+
         info!("🆔 [Transfers] Finding transfer by ID: {}", id);
 
         let (sql, values) = Query::select()
@@ -138,6 +829,10 @@ impl TransferRepositoryTrait for TransferRepository {
                 TransferSchema::TransferTime,
                 TransferSchema::CreatedAt,
                 TransferSchema::UpdatedAt,
+                TransferSchema::Note,
+                TransferSchema::Status,
+                TransferSchema::FromLabel,
+                TransferSchema::ToLabel,
             ])
             .and_where(Expr::col(TransferSchema::TransferId).eq(id))
             .build_sqlx(PostgresQueryBuilder);
@@ -148,7 +843,7 @@ impl TransferRepositoryTrait for TransferRepository {
         );
 
         let row = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
-            .fetch_optional(&self.db_pool)
+            .fetch_optional(self.read_pool.get())
             .await
             .map_err(|e| {
                 error!("❌ [Transfers] Database error while fetching transfer ID {id}: {e}",);
@@ -173,9 +868,24 @@ impl TransferRepositoryTrait for TransferRepository {
         Ok(row)
     }
 
-    async fn find_by_users(&self, id: i32) -> Result<Vec<Transfer>, AppError> {
+    async fn find_by_users(
+        &self,
+        id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Transfer>, i64), AppError> {
+        let _span = DbSpan::start("transfers", "find_by_users");
+
         info!("👥 [Transfers] Fetching all transfers sent by user ID: {id}",);
 
+        let page = if page > 0 { page } else { 1 };
+        let page_size = if page_size > 0 {
+            page_size
+        } else {
+            default_page_size()
+        };
+        let offset = (page - 1) * page_size;
+
         let (sql, values) = Query::select()
             .from(TransferSchema::Table)
             .columns([
@@ -186,8 +896,15 @@ impl TransferRepositoryTrait for TransferRepository {
                 TransferSchema::TransferTime,
                 TransferSchema::CreatedAt,
                 TransferSchema::UpdatedAt,
+                TransferSchema::Note,
+                TransferSchema::Status,
+                TransferSchema::FromLabel,
+                TransferSchema::ToLabel,
             ])
             .and_where(Expr::col(TransferSchema::TransferFrom).eq(id))
+            .order_by(TransferSchema::TransferId, Order::Asc)
+            .limit(page_size as u64)
+            .offset(offset as u64)
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -196,7 +913,7 @@ impl TransferRepositoryTrait for TransferRepository {
         );
 
         let rows = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
-            .fetch_all(&self.db_pool)
+            .fetch_all(self.read_pool.get())
             .await
             .map_err(|e| {
                 error!("❌ [Transfers] Failed to fetch transfers for sender user ID {id}: {e}",);
@@ -208,10 +925,110 @@ impl TransferRepositoryTrait for TransferRepository {
             rows.len(),
         );
 
-        Ok(rows)
+        let (count_sql, count_values) = Query::select()
+            .expr(Func::count(Expr::col(TransferSchema::TransferId)))
+            .from(TransferSchema::Table)
+            .and_where(Expr::col(TransferSchema::TransferFrom).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (total,) = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
+            .fetch_one(self.read_pool.get())
+            .await
+            .map_err(|e| {
+                error!("❌ [Transfers] Failed to count transfers for user_id={id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        Ok((rows, total))
+    }
+
+    async fn find_all_involving_user(
+        &self,
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Transfer>, i64), AppError> {
+        let _span = DbSpan::start("transfers", "find_all_involving_user");
+
+        info!("👥 [Transfers] Fetching transfer history for user ID: {user_id}");
+
+        let page = if page > 0 { page } else { 1 };
+        let page_size = if page_size > 0 {
+            page_size
+        } else {
+            default_page_size()
+        };
+        let offset = (page - 1) * page_size;
+
+        // transfer keluar maupun masuk milik user
+        let involving = Cond::any()
+            .add(Expr::col(TransferSchema::TransferFrom).eq(user_id))
+            .add(Expr::col(TransferSchema::TransferTo).eq(user_id));
+
+        let (sql, values) = Query::select()
+            .from(TransferSchema::Table)
+            .columns([
+                TransferSchema::TransferId,
+                TransferSchema::TransferFrom,
+                TransferSchema::TransferTo,
+                TransferSchema::TransferAmount,
+                TransferSchema::TransferTime,
+                TransferSchema::CreatedAt,
+                TransferSchema::UpdatedAt,
+                TransferSchema::Note,
+                TransferSchema::Status,
+                TransferSchema::FromLabel,
+                TransferSchema::ToLabel,
+            ])
+            .cond_where(involving.clone())
+            .order_by(TransferSchema::TransferTime, Order::Desc)
+            .order_by(TransferSchema::TransferId, Order::Desc)
+            .limit(page_size as u64)
+            .offset(offset as u64)
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [Transfers] Executing query: {sql} | Values: {:?}",
+            values
+        );
+
+        let rows = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
+            .fetch_all(self.read_pool.get())
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Transfers] Failed to fetch transfer history for user ID {user_id}: {e}"
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        let (count_sql, count_values) = Query::select()
+            .expr(Func::count(Expr::col(TransferSchema::TransferId)))
+            .from(TransferSchema::Table)
+            .cond_where(involving)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (total,) = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
+            .fetch_one(self.read_pool.get())
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Transfers] Failed to count transfer history for user ID {user_id}: {e}"
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Transfers] Fetched {} of {total} transfer(s) involving user ID: {user_id}",
+            rows.len(),
+        );
+
+        Ok((rows, total))
     }
 
     async fn find_by_user(&self, user_id: i32) -> Result<Option<Transfer>, AppError> {
+        let _span = DbSpan::start("transfers", "find_by_user");
+
         info!(
             "👤 [Transfers] Finding one transfer sent by user ID: {}",
             user_id
@@ -227,6 +1044,10 @@ impl TransferRepositoryTrait for TransferRepository {
                 TransferSchema::TransferTime,
                 TransferSchema::CreatedAt,
                 TransferSchema::UpdatedAt,
+                TransferSchema::Note,
+                TransferSchema::Status,
+                TransferSchema::FromLabel,
+                TransferSchema::ToLabel,
             ])
             .and_where(Expr::col(TransferSchema::TransferFrom).eq(user_id))
             .build_sqlx(PostgresQueryBuilder);
@@ -237,7 +1058,7 @@ impl TransferRepositoryTrait for TransferRepository {
         );
 
         let row = sqlx::query_as_with::<_, Transfer, _>(&sql, values)
-            .fetch_optional(&self.db_pool)
+            .fetch_optional(self.read_pool.get())
             .await
             .map_err(|e| {
                 error!(
@@ -262,6 +1083,8 @@ impl TransferRepositoryTrait for TransferRepository {
     }
 
     async fn create(&self, input: &CreateTransferRequest) -> Result<Transfer, AppError> {
+        let _span = DbSpan::start("transfers", "create");
+
         info!(
             "💸 [Transfers] Creating new transfer: {} → {} | Amount: {}",
             input.transfer_from, input.transfer_to, input.transfer_amount
@@ -276,12 +1099,18 @@ impl TransferRepositoryTrait for TransferRepository {
                 TransferSchema::TransferTo,
                 TransferSchema::TransferAmount,
                 TransferSchema::TransferTime,
+                TransferSchema::Note,
+                TransferSchema::FromLabel,
+                TransferSchema::ToLabel,
             ])
             .values([
                 input.transfer_from.into(),
                 input.transfer_to.into(),
                 input.transfer_amount.into(),
                 now.into(),
+                input.note.clone().into(),
+                input.from_label().into(),
+                input.to_label().into(),
             ])
             .unwrap()
             .returning_all()
@@ -308,7 +1137,127 @@ impl TransferRepositoryTrait for TransferRepository {
         Ok(created)
     }
 
+    async fn count_recent_by_sender(
+        &self,
+        user_id: i32,
+        window: Duration,
+    ) -> Result<i64, AppError> {
+        let _span = DbSpan::start("transfers", "count_recent_by_sender");
+
+        // batas jendela dihitung dari jam database, sama dengan default created_at,
+        // sehingga selisih jam antar instance aplikasi tidak berpengaruh
+        let (sql, values) = Query::select()
+            .expr(Func::count(Expr::col(TransferSchema::TransferId)))
+            .from(TransferSchema::Table)
+            .and_where(Expr::col(TransferSchema::TransferFrom).eq(user_id))
+            // kompensasi reversal dan pindah dana antar label milik sendiri bukan aktivitas
+            // pengirim yang perlu dibatasi
+            .and_where(Expr::col(TransferSchema::ReversalOf).is_null())
+            .and_where(Expr::col(TransferSchema::TransferTo).ne(user_id))
+            .and_where(
+                Expr::col(TransferSchema::CreatedAt).gte(Expr::cust_with_values(
+                    "LOCALTIMESTAMP - make_interval(secs => $1)",
+                    [window.as_secs_f64()],
+                )),
+            )
+            .build_sqlx(PostgresQueryBuilder);
+
+        // selalu ke primary: replica yang tertinggal bisa meloloskan transfer beruntun
+        let (count,) = sqlx::query_as_with::<_, (i64,), _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Transfers] Failed to count recent transfers for sender user ID {user_id}: {e}"
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "📈 [Transfers] Sender user ID {user_id} made {count} transfer(s) in the last {}s",
+            window.as_secs()
+        );
+
+        Ok(count)
+    }
+
+    async fn create_with_settlement(
+        &self,
+        input: &CreateTransferRequest,
+        status: TransferStatus,
+    ) -> Result<Transfer, AppError> {
+        with_sql_retry("transfers.create_with_settlement", || {
+            self.create_with_settlement_once(input, status)
+        })
+        .await
+    }
+
+    async fn preview_settlement(
+        &self,
+        input: &CreateTransferRequest,
+        status: TransferStatus,
+    ) -> Result<TransferPreview, AppError> {
+        let _span = DbSpan::start("transfers", "preview_settlement");
+
+        info!(
+            "🔍 [Transfers] Dry run transfer: {} → {} | Amount: {}",
+            input.transfer_from, input.transfer_to, input.transfer_amount
+        );
+
+        let mut conn = self.db_pool.acquire().await.map_err(|e| {
+            error!("❌ [Transfers] Failed to acquire connection for dry run: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        // hanya SELECT: tidak ada id transfer yang terpakai, lock saldo, atau event outbox
+        let (sender_balance, receiver_balance) = project_settlement(&mut conn, input).await?;
+
+        info!(
+            "✅ [Transfers] Dry run passed | Sender balance: {sender_balance} | Receiver balance: {receiver_balance}"
+        );
+
+        let now = Utc::now().naive_utc();
+
+        Ok(TransferPreview {
+            transfer: Transfer {
+                transfer_id: 0,
+                transfer_from: input.transfer_from,
+                transfer_to: input.transfer_to,
+                transfer_amount: input.transfer_amount,
+                transfer_time: now,
+                created_at: Some(now),
+                updated_at: Some(now),
+                note: input.note.clone(),
+                status: status.as_str().to_string(),
+                from_label: input.from_label().to_string(),
+                to_label: input.to_label().to_string(),
+            },
+            sender_balance,
+            receiver_balance,
+        })
+    }
+
+    async fn reverse(&self, id: i32) -> Result<Transfer, AppError> {
+        with_sql_retry("transfers.reverse", || self.reverse_once(id)).await
+    }
+
+    async fn approve(&self, id: i32) -> Result<Transfer, AppError> {
+        with_sql_retry("transfers.approve", || self.approve_once(id)).await
+    }
+
+    async fn update_with_settlement(
+        &self,
+        input: &UpdateTransferRequest,
+    ) -> Result<Transfer, AppError> {
+        with_sql_retry("transfers.update_with_settlement", || {
+            self.update_with_settlement_once(input)
+        })
+        .await
+    }
+
     async fn update(&self, input: &UpdateTransferRequest) -> Result<Transfer, AppError> {
+        let _span = DbSpan::start("transfers", "update");
+
         info!(
             "🔄 [Transfers] Updating full transfer with ID: {}",
             input.transfer_id
@@ -324,8 +1273,10 @@ impl TransferRepositoryTrait for TransferRepository {
                 (TransferSchema::TransferAmount, input.transfer_amount.into()),
                 (TransferSchema::TransferTime, now.into()),
                 (TransferSchema::UpdatedAt, now.into()),
+                (TransferSchema::Note, input.note.clone().into()),
             ])
             .and_where(Expr::col(TransferSchema::TransferId).eq(input.transfer_id))
+            .returning_all()
             .build_sqlx(PostgresQueryBuilder);
 
         info!("🧾 [Transfers] UPDATE query: {sql} | Values: {:?}", values);
@@ -365,6 +1316,8 @@ impl TransferRepositoryTrait for TransferRepository {
         &self,
         input: &UpdateTransferAmountRequest,
     ) -> Result<Transfer, AppError> {
+        let _span = DbSpan::start("transfers", "update_amount");
+
         info!(
             "💱 [Transfers] Updating amount for transfer ID: {} to {}",
             input.transfer_id, input.transfer_amount
@@ -379,6 +1332,7 @@ impl TransferRepositoryTrait for TransferRepository {
                 (TransferSchema::UpdatedAt, now.into()),
             ])
             .and_where(Expr::col(TransferSchema::TransferId).eq(input.transfer_id))
+            .returning_all()
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -415,6 +1369,8 @@ impl TransferRepositoryTrait for TransferRepository {
     }
 
     async fn delete(&self, id: i32) -> Result<(), AppError> {
+        let _span = DbSpan::start("transfers", "delete");
+
         info!("🗑️ [Transfers] Deleting transfer with ID: {id}");
 
         let (sql, values) = Query::delete()
@@ -443,3 +1399,377 @@ impl TransferRepositoryTrait for TransferRepository {
         Ok(())
     }
 }
+
+// satu sisi transfer dalam rentang: user_id, hari, jumlah keluar dan jumlah masuk
+fn daily_movements(
+    req: &DailyTransferTotalsRequest,
+    user_column: TransferSchema,
+    outgoing: bool,
+) -> SelectStatement {
+    let amount = || Expr::col(TransferSchema::TransferAmount);
+    let (sent, received) = if outgoing {
+        (amount(), Expr::val(0))
+    } else {
+        (Expr::val(0), amount())
+    };
+
+    Query::select()
+        .expr_as(Expr::col(user_column), Alias::new("user_id"))
+        .expr_as(
+            Expr::col(TransferSchema::TransferTime).cast_as(Alias::new("DATE")),
+            Alias::new("day"),
+        )
+        .expr_as(sent, Alias::new("sent"))
+        .expr_as(received, Alias::new("received"))
+        .from(TransferSchema::Table)
+        .and_where(Expr::col(TransferSchema::TransferTime).gte(req.start_time()))
+        .and_where(Expr::col(TransferSchema::TransferTime).lt(req.end_time()))
+        .to_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::test_db;
+
+    fn transfer(from: i32, to: i32, amount: i32) -> CreateTransferRequest {
+        CreateTransferRequest {
+            transfer_from: from,
+            transfer_to: to,
+            transfer_amount: amount,
+            note: None,
+            dry_run: false,
+            from_label: None,
+            to_label: None,
+        }
+    }
+
+    async fn event_types(pool: &ConnectionPool, transfer_id: i32) -> Vec<String> {
+        sqlx::query_as::<_, (String,)>(
+            "SELECT event_type FROM outbox WHERE (payload::jsonb ->> 'transfer_id')::int = $1 \
+             ORDER BY id",
+        )
+        .bind(transfer_id)
+        .fetch_all(pool)
+        .await
+        .unwrap()
+        .into_iter()
+        .map(|(event_type,)| event_type)
+        .collect()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn held_transfer_settles_only_when_approved() {
+        let pool = test_db::pool().await;
+        let sender = test_db::seed_user(&pool, 200_000).await;
+        let receiver = test_db::seed_user(&pool, 50_000).await;
+        let repository = TransferRepository::new(pool.clone());
+
+        let held = repository
+            .create_with_settlement(
+                &transfer(sender, receiver, 75_000),
+                TransferStatus::NeedsReview,
+            )
+            .await
+            .unwrap();
+
+        assert_eq!(test_db::balance_of(&pool, sender).await, 200_000);
+        assert_eq!(test_db::balance_of(&pool, receiver).await, 50_000);
+        assert_eq!(
+            event_types(&pool, held.transfer_id).await,
+            ["transfer.needs_review"]
+        );
+
+        let approved = repository.approve(held.transfer_id).await.unwrap();
+
+        assert!(approved.is_completed());
+        assert_eq!(test_db::balance_of(&pool, sender).await, 125_000);
+        assert_eq!(test_db::balance_of(&pool, receiver).await, 125_000);
+        assert_eq!(
+            event_types(&pool, held.transfer_id).await,
+            ["transfer.needs_review", "transfer.completed"]
+        );
+        assert!(repository.approve(held.transfer_id).await.is_err());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn settled_update_keeps_the_original_parties() {
+        let pool = test_db::pool().await;
+        let sender = test_db::seed_user(&pool, 200_000).await;
+        let receiver = test_db::seed_user(&pool, 50_000).await;
+        let other = test_db::seed_user(&pool, 50_000).await;
+        let repository = TransferRepository::new(pool.clone());
+
+        let original = repository
+            .create_with_settlement(
+                &transfer(sender, receiver, 75_000),
+                TransferStatus::Completed,
+            )
+            .await
+            .unwrap();
+
+        let updated = repository
+            .update_with_settlement(&UpdateTransferRequest {
+                transfer_id: original.transfer_id,
+                transfer_from: sender,
+                transfer_to: other,
+                transfer_amount: 100_000,
+                note: None,
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(updated.transfer_to, receiver);
+        assert_eq!(updated.transfer_amount, 100_000);
+        assert_eq!(test_db::balance_of(&pool, sender).await, 100_000);
+        assert_eq!(test_db::balance_of(&pool, receiver).await, 150_000);
+        assert_eq!(test_db::balance_of(&pool, other).await, 50_000);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn reversal_cannot_be_reversed_and_its_original_stays_deletable() {
+        let pool = test_db::pool().await;
+        let sender = test_db::seed_user(&pool, 200_000).await;
+        let receiver = test_db::seed_user(&pool, 50_000).await;
+        let repository = TransferRepository::new(pool.clone());
+
+        let original = repository
+            .create_with_settlement(
+                &transfer(sender, receiver, 75_000),
+                TransferStatus::Completed,
+            )
+            .await
+            .unwrap();
+        let reversal = repository.reverse(original.transfer_id).await.unwrap();
+
+        let err = repository.reverse(reversal.transfer_id).await.unwrap_err();
+        assert!(matches!(err, AppError::Custom(ref msg) if msg.contains("is a reversal")));
+        assert_eq!(test_db::balance_of(&pool, sender).await, 200_000);
+        assert_eq!(test_db::balance_of(&pool, receiver).await, 50_000);
+
+        // transfer asal yang sudah dibalik tetap bisa dihapus, tautan kompensasinya dilepas
+        repository.delete(original.transfer_id).await.unwrap();
+        let (reversal_of,) = sqlx::query_as::<_, (Option<i32>,)>(
+            "SELECT reversal_of FROM transfers WHERE transfer_id = $1",
+        )
+        .bind(reversal.transfer_id)
+        .fetch_one(&pool)
+        .await
+        .unwrap();
+        assert_eq!(reversal_of, None);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn reversal_compensation_is_not_counted_for_velocity() {
+        let pool = test_db::pool().await;
+        let sender = test_db::seed_user(&pool, 200_000).await;
+        let receiver = test_db::seed_user(&pool, 50_000).await;
+        let repository = TransferRepository::new(pool.clone());
+        let window = Duration::from_secs(600);
+
+        let original = repository
+            .create_with_settlement(
+                &transfer(sender, receiver, 75_000),
+                TransferStatus::Completed,
+            )
+            .await
+            .unwrap();
+        let reversal = repository.reverse(original.transfer_id).await.unwrap();
+
+        assert_eq!(reversal.transfer_from, receiver);
+        assert_eq!(
+            repository
+                .count_recent_by_sender(receiver, window)
+                .await
+                .unwrap(),
+            0
+        );
+        assert_eq!(
+            repository
+                .count_recent_by_sender(sender, window)
+                .await
+                .unwrap(),
+            1
+        );
+        assert_eq!(test_db::balance_of(&pool, sender).await, 200_000);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn edit_settles_the_difference_and_rejects_reversed_transfers() {
+        let pool = test_db::pool().await;
+        let sender = test_db::seed_user(&pool, 200_000).await;
+        let receiver = test_db::seed_user(&pool, 50_000).await;
+        let repository = TransferRepository::new(pool.clone());
+
+        let original = repository
+            .create_with_settlement(
+                &transfer(sender, receiver, 75_000),
+                TransferStatus::Completed,
+            )
+            .await
+            .unwrap();
+        let edit = UpdateTransferRequest {
+            transfer_id: original.transfer_id,
+            transfer_from: sender,
+            transfer_to: receiver,
+            transfer_amount: 100_000,
+            note: None,
+        };
+
+        let updated = repository.update_with_settlement(&edit).await.unwrap();
+
+        assert_eq!(updated.transfer_amount, 100_000);
+        assert_eq!(test_db::balance_of(&pool, sender).await, 100_000);
+        assert_eq!(test_db::balance_of(&pool, receiver).await, 150_000);
+
+        repository.reverse(original.transfer_id).await.unwrap();
+
+        assert!(repository.update_with_settlement(&edit).await.is_err());
+        assert_eq!(test_db::balance_of(&pool, sender).await, 200_000);
+        assert_eq!(test_db::balance_of(&pool, receiver).await, 50_000);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn history_matches_either_side_of_a_transfer() {
+        let pool = test_db::pool().await;
+        let user = test_db::seed_user(&pool, 200_000).await;
+        let other = test_db::seed_user(&pool, 200_000).await;
+        let stranger = test_db::seed_user(&pool, 200_000).await;
+        let repository = TransferRepository::new(pool.clone());
+
+        let mut settled = Vec::new();
+        for (from, to) in [(user, other), (other, user), (other, stranger)] {
+            let created = repository
+                .create_with_settlement(&transfer(from, to, 50_000), TransferStatus::Completed)
+                .await
+                .unwrap();
+            settled.push(created.transfer_id);
+        }
+
+        let (rows, total) = repository
+            .find_all_involving_user(user, 1, 10)
+            .await
+            .unwrap();
+        let mut ids: Vec<i32> = rows.iter().map(|t| t.transfer_id).collect();
+        ids.sort();
+
+        assert_eq!(total, 2);
+        assert_eq!(ids, settled[..2]);
+
+        let (none, total) = repository.find_all_involving_user(-1, 1, 10).await.unwrap();
+        assert!(none.is_empty());
+        assert_eq!(total, 0);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn status_filter_matches_any_listed_status() {
+        let pool = test_db::pool().await;
+        let sender = test_db::seed_user(&pool, 10_000_000).await;
+        let receiver = test_db::seed_user(&pool, 0).await;
+        let repository = TransferRepository::new(pool.clone());
+        // nominal unik per run supaya transfer dari test lain tidak ikut terhitung
+        let amount = 1_000_000 + sender;
+
+        let mut ids = Vec::new();
+        for status in [
+            TransferStatus::Completed,
+            TransferStatus::Reversed,
+            TransferStatus::NeedsReview,
+        ] {
+            let created = repository
+                .create_with_settlement(&transfer(sender, receiver, amount), status)
+                .await
+                .unwrap();
+            ids.push(created.transfer_id);
+        }
+
+        let matching = |statuses: Vec<TransferStatus>| {
+            let repository = &repository;
+            async move {
+                let filter = TransferFilter {
+                    min_amount: Some(amount),
+                    max_amount: Some(amount),
+                    statuses,
+                    ..TransferFilter::default()
+                };
+                let (rows, total) = repository.find_all(1, 10, &filter).await.unwrap();
+                assert_eq!(total, rows.len() as i64);
+                rows.iter().map(|t| t.transfer_id).collect::<Vec<_>>()
+            }
+        };
+
+        assert_eq!(matching(vec![TransferStatus::Reversed]).await, [ids[1]]);
+        assert_eq!(
+            matching(vec![TransferStatus::Completed, TransferStatus::NeedsReview]).await,
+            [ids[0], ids[2]]
+        );
+        assert_eq!(matching(Vec::new()).await, ids);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn daily_totals_are_grouped_per_user_per_day() {
+        let pool = test_db::pool().await;
+        let sender = test_db::seed_user(&pool, 0).await;
+        let receiver = test_db::seed_user(&pool, 0).await;
+        let repository = TransferRepository::new(pool.clone());
+
+        for (from, to, amount, time) in [
+            (sender, receiver, 50_000, "2001-02-03 08:00:00"),
+            (sender, receiver, 25_000, "2001-02-03 23:59:59"),
+            (receiver, sender, 10_000, "2001-02-05 00:00:00"),
+            (sender, receiver, 99_000, "2001-02-06 00:00:00"),
+        ] {
+            sqlx::query(
+                "INSERT INTO transfers (transfer_from, transfer_to, transfer_amount, transfer_time) \
+                 VALUES ($1, $2, $3, $4::timestamp)",
+            )
+            .bind(from)
+            .bind(to)
+            .bind(amount)
+            .bind(time)
+            .execute(&pool)
+            .await
+            .unwrap();
+        }
+
+        let totals = repository
+            .daily_totals(&DailyTransferTotalsRequest {
+                start_date: "2001-02-03".parse().unwrap(),
+                end_date: "2001-02-05".parse().unwrap(),
+            })
+            .await
+            .unwrap();
+        // database yang sama dipakai test lain, jadi hanya baris dua user ini yang dilihat
+        let totals: Vec<_> = totals
+            .into_iter()
+            .filter(|total| total.user_id == sender || total.user_id == receiver)
+            .map(|total| {
+                (
+                    total.day.to_string(),
+                    total.user_id,
+                    total.total_sent,
+                    total.total_received,
+                )
+            })
+            .collect();
+
+        let (day1, day3) = ("2001-02-03".to_string(), "2001-02-05".to_string());
+        assert_eq!(
+            totals,
+            [
+                (day1.clone(), sender, 75_000, 0),
+                (day1, receiver, 0, 75_000),
+                (day3.clone(), sender, 0, 10_000),
+                (day3, receiver, 10_000, 0),
+            ]
+        );
+    }
+}
@@ -1,71 +1,230 @@
 use async_trait::async_trait;
-use sea_query::{Expr, Func, Order, PostgresQueryBuilder, Query};
-use sea_query_binder::SqlxBinder;
-use tracing::{error, info};
+use sea_query::{
+    DeleteStatement, Expr, Func, LockType, Order, PostgresQueryBuilder, Query, SelectStatement,
+    SimpleExpr,
+};
+use sea_query_binder::{SqlxBinder, SqlxValues};
+use sqlx::{Acquire, PgConnection};
+use tracing::{error, info, warn};
 
 use crate::abstract_trait::UserRepositoryTrait;
-use crate::config::ConnectionPool;
-use crate::domain::request::user::{CreateUserRequest, UpdateUserRequest};
-use crate::model::user::User;
+use crate::config::{ConnectionPool, ReadPool};
+use crate::domain::request::user::{CreateUserRequest, UpdateUserRequest, UserFilter};
+use crate::domain::request::{default_page_size, normalize_email};
+use crate::model::user::{User, UserDeleteBlockers, UserPurge, UserStatus};
+use crate::repository::saldo::ensure_saldo;
+use crate::schema::refresh_token::RefreshTokens;
+use crate::schema::saldo::Saldo as SaldoSchema;
+use crate::schema::topup::Topups;
+use crate::schema::transfer::Transfers;
 use crate::schema::user::Users;
-use crate::utils::AppError;
+use crate::schema::withdraw::Withdraws;
+use crate::utils::{AppError, DbSpan};
 
 pub struct UserRepository {
     db_pool: ConnectionPool,
+    read_pool: ReadPool,
+}
+
+const USER_COLUMNS: [Users; 12] = [
+    Users::UserId,
+    Users::Firstname,
+    Users::Lastname,
+    Users::Email,
+    Users::Password,
+    Users::NocTransfer,
+    Users::CreatedAt,
+    Users::UpdatedAt,
+    Users::DeletedAt,
+    Users::PasswordChangedAt,
+    Users::Status,
+    Users::OverdraftLimit,
+];
+
+// constraint lama pada kolom email dan index fungsional LOWER(email); index sengaja tidak
+// parsial pada deleted_at: users_email_key sudah menahan email akun yang di-soft delete, dan
+// email yang tetap dicadangkan menjaga riwayat transfer akun lama tidak tertukar dengan akun baru
+const UNIQUE_EMAIL_CONSTRAINTS: [&str; 2] = ["users_email_key", "uq_users_email_lower"];
+
+// email duplikat (23505 pada constraint email) dilaporkan sebagai error bisnis, misalnya
+// saat dua registrasi dengan email sama lolos pengecekan awal secara bersamaan atau
+// saat update email bertabrakan dengan akun lain
+fn map_insert_error(e: sqlx::Error) -> AppError {
+    match e.as_database_error() {
+        Some(db_err)
+            if db_err.is_unique_violation()
+                && db_err
+                    .constraint()
+                    .is_some_and(|c| UNIQUE_EMAIL_CONSTRAINTS.contains(&c)) =>
+        {
+            AppError::EmailAlreadyExists
+        }
+        _ => AppError::SqlxError(e),
+    }
+}
+
+// dicocokkan lewat LOWER(email) supaya pencarian tidak peka huruf besar/kecil dan memakai
+// index uq_users_email_lower
+fn email_matches(email: &str) -> SimpleExpr {
+    Expr::expr(Func::lower(Expr::col(Users::Email))).eq(normalize_email(email))
+}
+
+fn insert_user_query(input: &CreateUserRequest) -> (String, SqlxValues) {
+    Query::insert()
+        .into_table(Users::Table)
+        .columns([
+            Users::Firstname,
+            Users::Lastname,
+            Users::Email,
+            Users::Password,
+            Users::NocTransfer,
+        ])
+        .values([
+            input.firstname.clone().into(),
+            input.lastname.clone().into(),
+            normalize_email(&input.email).into(),
+            input.password.clone().into(),
+            input.noc_transfer.clone().into(),
+        ])
+        .unwrap()
+        .returning_all()
+        .build_sqlx(PostgresQueryBuilder)
 }
 
 impl UserRepository {
     pub fn new(db_pool: ConnectionPool) -> Self {
-        Self { db_pool }
+        Self {
+            read_pool: ReadPool::primary(db_pool.clone()),
+            db_pool,
+        }
+    }
+
+    pub fn with_read_pool(mut self, read_pool: ReadPool) -> Self {
+        self.read_pool = read_pool;
+        self
+    }
+
+    // email unik, jadi cukup satu baris tanpa count query
+    async fn find_all_by_exact_email(
+        &self,
+        email: &str,
+        filter: &UserFilter,
+    ) -> Result<(Vec<User>, i64), AppError> {
+        let _span = DbSpan::start("users", "find_all_by_exact_email");
+
+        let mut select_query = Query::select();
+        select_query
+            .columns(USER_COLUMNS)
+            .from(Users::Table)
+            .and_where(email_matches(email))
+            .limit(1);
+
+        apply_user_filter(&mut select_query, filter);
+
+        let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
+        info!("🧾 [Users] Exact email SQL: {} | Values: {:?}", sql, values);
+
+        let user = sqlx::query_as_with::<_, User, _>(&sql, values)
+            .fetch_optional(self.read_pool.get())
+            .await
+            .map_err(|e| {
+                error!("❌ [Users] Failed to fetch user by exact email {email}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        let users: Vec<User> = user.into_iter().collect();
+        info!(
+            "✅ [Users] Exact email lookup for {email} returned {} user(s)",
+            users.len()
+        );
+
+        let total = users.len() as i64;
+        Ok((users, total))
+    }
+}
+
+fn apply_user_filter(query: &mut SelectStatement, filter: &UserFilter) {
+    if let Some(created_after) = filter.created_after {
+        query.and_where(Expr::col(Users::CreatedAt).gte(created_after));
+    }
+
+    if let Some(created_before) = filter.created_before {
+        query.and_where(Expr::col(Users::CreatedAt).lt(created_before));
+    }
+
+    if !filter.include_deleted {
+        query.and_where(Expr::col(Users::DeletedAt).is_null());
     }
 }
 
+// dijalankan di dalam transaksi purge, mengembalikan jumlah baris yang terhapus
+async fn delete_rows(
+    conn: &mut PgConnection,
+    statement: &DeleteStatement,
+    entity: &str,
+) -> Result<i64, AppError> {
+    let (sql, values) = statement.build_sqlx(PostgresQueryBuilder);
+
+    let result = sqlx::query_with(&sql, values)
+        .execute(&mut *conn)
+        .await
+        .map_err(|e| {
+            error!("❌ [User] Failed to purge {entity}: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+    Ok(result.rows_affected() as i64)
+}
+
 #[async_trait]
 impl UserRepositoryTrait for UserRepository {
     async fn find_all(
         &self,
         page: i32,
         page_size: i32,
-        search: Option<String>,
+        filter: &UserFilter,
     ) -> Result<(Vec<User>, i64), AppError> {
+        let _span = DbSpan::start("users", "find_all");
+
         info!(
-            "👥 [Users] Fetching all users - page: {page}, page_size: {page_size}, search: {:?}",
-            search
+            "👥 [Users] Fetching all users - page: {page}, page_size: {page_size}, filter: {:?}",
+            filter
         );
 
+        if let Some(ref email) = filter.email {
+            return self.find_all_by_exact_email(email, filter).await;
+        }
+
         let page = if page > 0 { page } else { 1 };
-        let page_size = if page_size > 0 { page_size } else { 10 };
+        let page_size = if page_size > 0 {
+            page_size
+        } else {
+            default_page_size()
+        };
         let offset = (page - 1) * page_size;
 
         info!("🔢 [Users] Using pagination: LIMIT={page_size} OFFSET={offset}");
 
         let mut select_query = Query::select();
         select_query
-            .columns([
-                Users::UserId,
-                Users::Firstname,
-                Users::Lastname,
-                Users::Email,
-                Users::Password,
-                Users::NocTransfer,
-                Users::CreatedAt,
-                Users::UpdatedAt,
-            ])
+            .columns(USER_COLUMNS)
             .from(Users::Table)
             .order_by(Users::UserId, Order::Asc)
             .limit(page_size as u64)
             .offset(offset as u64);
 
-        if let Some(ref term) = search {
+        if let Some(ref term) = filter.search {
             select_query.and_where(Expr::col(Users::Email).like(format!("{term}%")));
             info!("🔍 [Users] Filtering by email prefix: {}%", term);
         }
 
+        apply_user_filter(&mut select_query, filter);
+
         let (sql, values) = select_query.build_sqlx(PostgresQueryBuilder);
         info!("🧾 [Users] Generated SQL: {} | Values: {:?}", sql, values);
 
         let users_result = sqlx::query_as_with::<_, User, _>(&sql, values)
-            .fetch_all(&self.db_pool)
+            .fetch_all(self.read_pool.get())
             .await;
 
         let users = match users_result {
@@ -84,10 +243,12 @@ impl UserRepositoryTrait for UserRepository {
             .expr(Func::count(Expr::col(Users::UserId)))
             .from(Users::Table);
 
-        if let Some(ref term) = search {
+        if let Some(ref term) = filter.search {
             count_query.and_where(Expr::col(Users::Email).like(format!("{term}%")));
         }
 
+        apply_user_filter(&mut count_query, filter);
+
         let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
         info!(
             "[Users] Executing count query: {count_sql} | Values: {:?}",
@@ -95,7 +256,7 @@ impl UserRepositoryTrait for UserRepository {
         );
 
         let total_result = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(self.read_pool.get())
             .await;
 
         let total = match total_result {
@@ -118,12 +279,14 @@ impl UserRepositoryTrait for UserRepository {
     }
 
     async fn find_by_email_exists(&self, email: &str) -> Result<bool, AppError> {
+        let _span = DbSpan::start("users", "find_by_email_exists");
+
         info!("🔍 Checking if user with email '{email}' exists");
 
         let (sql, values) = Query::select()
             .expr(Expr::col(Users::UserId).count())
             .from(Users::Table)
-            .and_where(Expr::col(Users::Email).eq(email))
+            .and_where(email_matches(email))
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -132,7 +295,7 @@ impl UserRepositoryTrait for UserRepository {
         );
 
         let count: i64 = sqlx::query_scalar_with(&sql, values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(self.read_pool.get())
             .await
             .map_err(|e| match &e {
                 sqlx::Error::Database(db_err) => {
@@ -157,6 +320,8 @@ impl UserRepositoryTrait for UserRepository {
     }
 
     async fn find_by_email(&self, email: &str) -> Result<Option<User>, AppError> {
+        let _span = DbSpan::start("users", "find_by_email");
+
         info!("📧 Looking up user by email: '{}'", email);
 
         let (sql, values) = Query::select()
@@ -169,9 +334,14 @@ impl UserRepositoryTrait for UserRepository {
                 Users::NocTransfer,
                 Users::CreatedAt,
                 Users::UpdatedAt,
+                Users::DeletedAt,
+                Users::PasswordChangedAt,
+                Users::Status,
+                Users::OverdraftLimit,
             ])
             .from(Users::Table)
-            .and_where(Expr::col(Users::Email).eq(email))
+            .and_where(email_matches(email))
+            .and_where(Expr::col(Users::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -180,7 +350,7 @@ impl UserRepositoryTrait for UserRepository {
         );
 
         let user = sqlx::query_as_with::<_, User, _>(&sql, values)
-            .fetch_optional(&self.db_pool)
+            .fetch_optional(self.read_pool.get())
             .await
             .map_err(|e| {
                 error!("❌ Failed to query database for email '{email}': {e}");
@@ -203,6 +373,8 @@ impl UserRepositoryTrait for UserRepository {
     }
 
     async fn find_by_id(&self, id: i32) -> Result<Option<User>, AppError> {
+        let _span = DbSpan::start("users", "find_by_id");
+
         info!("🆔 Looking up user by ID: {id}");
 
         let (sql, values) = Query::select()
@@ -215,9 +387,14 @@ impl UserRepositoryTrait for UserRepository {
                 Users::NocTransfer,
                 Users::CreatedAt,
                 Users::UpdatedAt,
+                Users::DeletedAt,
+                Users::PasswordChangedAt,
+                Users::Status,
+                Users::OverdraftLimit,
             ])
             .from(Users::Table)
             .and_where(Expr::col(Users::UserId).eq(id))
+            .and_where(Expr::col(Users::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -226,7 +403,7 @@ impl UserRepositoryTrait for UserRepository {
         );
 
         let user = sqlx::query_as_with::<_, User, _>(&sql, values)
-            .fetch_optional(&self.db_pool)
+            .fetch_optional(self.read_pool.get())
             .await
             .map_err(|e| {
                 error!("❌ Database error while fetching user ID {id}: {e}");
@@ -249,44 +426,44 @@ impl UserRepositoryTrait for UserRepository {
     }
 
     async fn create_user(&self, input: &CreateUserRequest) -> Result<User, AppError> {
+        let _span = DbSpan::start("users", "create_user");
+
         info!(
             "👤 [User] Creating new user: {} {}",
             input.firstname, input.lastname
         );
 
-        let (sql, values) = Query::insert()
-            .into_table(Users::Table)
-            .columns([
-                Users::Firstname,
-                Users::Lastname,
-                Users::Email,
-                Users::Password,
-                Users::NocTransfer,
-            ])
-            .values([
-                input.firstname.clone().into(),
-                input.lastname.clone().into(),
-                input.email.clone().into(),
-                input.password.clone().into(),
-                input.noc_transfer.clone().into(),
-            ])
-            .unwrap()
-            .returning_all()
-            .build_sqlx(PostgresQueryBuilder);
+        let (sql, values) = insert_user_query(input);
 
         info!("🧾 [User] INSERT query: {} | Values: {:?}", sql, values);
 
+        // user dan saldo nol-nya dibuat bersamaan supaya setiap user selalu punya saldo
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("❌ [User] Failed to begin create transaction: {e}");
+            AppError::SqlxError(e)
+        })?;
+
         let user: User = sqlx::query_as_with(&sql, values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(&mut *tx)
             .await
             .map_err(|e| {
                 error!(
                     "❌ [User] Failed to create user '{} {}': {e}",
                     input.firstname, input.lastname,
                 );
-                AppError::SqlxError(e)
+                map_insert_error(e)
             })?;
 
+        ensure_saldo(&mut tx, user.user_id).await?;
+
+        tx.commit().await.map_err(|e| {
+            error!(
+                "❌ [User] Failed to commit creation of user ID {}: {e}",
+                user.user_id
+            );
+            AppError::SqlxError(e)
+        })?;
+
         info!(
             "✅ [User] Successfully created user ID: {} | Email: {}",
             user.user_id, user.email
@@ -294,7 +471,124 @@ impl UserRepositoryTrait for UserRepository {
         Ok(user)
     }
 
+    async fn create_many(
+        &self,
+        inputs: &[CreateUserRequest],
+        continue_on_error: bool,
+    ) -> Result<Vec<Result<User, AppError>>, AppError> {
+        let _span = DbSpan::start("users", "create_many");
+
+        info!(
+            "👥 [User] Importing {} users (continue_on_error: {continue_on_error})",
+            inputs.len()
+        );
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("❌ [User] Failed to begin import transaction: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        let mut results = Vec::with_capacity(inputs.len());
+
+        for input in inputs {
+            // savepoint per baris supaya baris yang gagal tidak membatalkan transaksi utama
+            let mut savepoint = tx.begin().await.map_err(|e| {
+                error!(
+                    "❌ [User] Failed to open savepoint for '{}': {e}",
+                    input.email
+                );
+                AppError::SqlxError(e)
+            })?;
+
+            let (sql, values) = insert_user_query(input);
+            let inserted = match sqlx::query_as_with::<_, User, _>(&sql, values)
+                .fetch_one(&mut *savepoint)
+                .await
+            {
+                Ok(user) => ensure_saldo(&mut savepoint, user.user_id)
+                    .await
+                    .map(|()| user),
+                Err(e) => Err(map_insert_error(e)),
+            };
+
+            match inserted {
+                Ok(user) => {
+                    savepoint.commit().await.map_err(|e| {
+                        error!(
+                            "❌ [User] Failed to release savepoint for '{}': {e}",
+                            user.email
+                        );
+                        AppError::SqlxError(e)
+                    })?;
+                    results.push(Ok(user));
+                }
+                Err(err) => {
+                    savepoint.rollback().await.map_err(|e| {
+                        error!(
+                            "❌ [User] Failed to roll back savepoint for '{}': {e}",
+                            input.email
+                        );
+                        AppError::SqlxError(e)
+                    })?;
+                    warn!("⚠️ [User] Import of '{}' failed: {err}", input.email);
+                    results.push(Err(err));
+
+                    if !continue_on_error {
+                        tx.rollback().await.map_err(|e| {
+                            error!("❌ [User] Failed to roll back import transaction: {e}");
+                            AppError::SqlxError(e)
+                        })?;
+
+                        info!("↩️ [User] Import aborted, no users were created");
+                        return Ok(results);
+                    }
+                }
+            }
+        }
+
+        tx.commit().await.map_err(|e| {
+            error!("❌ [User] Failed to commit import transaction: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        info!(
+            "✅ [User] Imported {} of {} users",
+            results.iter().filter(|r| r.is_ok()).count(),
+            inputs.len()
+        );
+        Ok(results)
+    }
+
+    async fn exists_by_id(&self, id: i32) -> Result<bool, AppError> {
+        let _span = DbSpan::start("users", "exists_by_id");
+
+        let (sql, values) = Query::select()
+            .expr(Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from(Users::Table)
+                    .and_where(Expr::col(Users::UserId).eq(id))
+                    .and_where(Expr::col(Users::DeletedAt).is_null())
+                    .to_owned(),
+            ))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let exists: bool = sqlx::query_scalar_with(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ Database error while checking user ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!("🔍 User ID {id} exists: {exists}");
+
+        Ok(exists)
+    }
+
     async fn update_user(&self, input: &UpdateUserRequest) -> Result<User, AppError> {
+        let _span = DbSpan::start("users", "update_user");
+
         info!("🔄 [User] Updating user with ID: {}", input.id);
 
         let id = input.id;
@@ -302,7 +596,8 @@ impl UserRepositoryTrait for UserRepository {
         let mut update_query = Query::update();
         let mut query = update_query
             .table(Users::Table)
-            .and_where(Expr::col(Users::UserId).eq(id));
+            .and_where(Expr::col(Users::UserId).eq(id))
+            .and_where(Expr::col(Users::DeletedAt).is_null());
 
         let mut updated_fields = Vec::new();
 
@@ -313,21 +608,33 @@ impl UserRepositoryTrait for UserRepository {
 
         if let Some(ref lastname) = input.lastname {
             query = query.value(Users::Lastname, lastname.clone());
-            updated_fields.push(format!("lastnameid='{lastname}'"));
+            updated_fields.push(format!("lastname='{lastname}'"));
         }
 
         if let Some(ref email) = input.email {
-            query = query.value(Users::Email, email.clone());
+            query = query.value(Users::Email, normalize_email(email));
             updated_fields.push(format!("email='{email}'"));
         }
 
+        // password sudah di-hash oleh service sebelum sampai ke repository
+        if let Some(ref password) = input.password {
+            query = query
+                .value(Users::Password, password.clone())
+                .value(Users::PasswordChangedAt, Expr::current_timestamp());
+            updated_fields.push("password=<redacted>".to_string());
+        }
+
+        // request tanpa field apa pun tidak mengubah data, cukup kembalikan user saat ini
         if updated_fields.is_empty() {
             info!("🟡 [User] No fields to update for user ID: {id}");
-            return Err(AppError::Custom(
-                "No fields provided for update".to_string(),
-            ));
+            return self
+                .find_by_id(id)
+                .await?
+                .ok_or_else(|| AppError::NotFound(format!("User with ID {id} not found")));
         }
 
+        query = query.value(Users::UpdatedAt, Expr::current_timestamp());
+
         query = query.returning_all();
 
         let (sql, values) = query.build_sqlx(PostgresQueryBuilder);
@@ -344,7 +651,7 @@ impl UserRepositoryTrait for UserRepository {
                 }
                 _ => {
                     error!("❌ [User] Database error while updating user ID {id}: {e}",);
-                    AppError::SqlxError(e)
+                    map_insert_error(e)
                 }
             })?;
 
@@ -355,15 +662,70 @@ impl UserRepositoryTrait for UserRepository {
         Ok(user)
     }
 
+    async fn find_delete_blockers(&self, id: i32) -> Result<UserDeleteBlockers, AppError> {
+        let _span = DbSpan::start("users", "find_delete_blockers");
+
+        info!("🔍 [User] Checking delete blockers for user ID: {id}");
+
+        if !self.exists_by_id(id).await? {
+            error!("❌ [User] Delete check failed: No active user found with ID {id}");
+            return Err(AppError::NotFound(format!("User ID {id} not found")));
+        }
+
+        let (sql, values) = Query::select()
+            .expr(Func::count(Expr::col(Transfers::TransferId)))
+            .from(Transfers::Table)
+            .and_where(Expr::col(Transfers::TransferTo).eq(id))
+            .and_where(Expr::col(Transfers::TransferFrom).ne(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (incoming_transfers,) = sqlx::query_as_with::<_, (i64,), _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [User] Failed to count incoming transfers for user ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        let (sql, values) = Query::select()
+            .expr(Func::count(Expr::col(RefreshTokens::SessionId)))
+            .from(RefreshTokens::Table)
+            .and_where(Expr::col(RefreshTokens::UserId).eq(id))
+            .and_where(Expr::col(RefreshTokens::RevokedAt).is_null())
+            .and_where(Expr::col(RefreshTokens::ExpiresAt).gt(chrono::Utc::now().naive_utc()))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (active_sessions,) = sqlx::query_as_with::<_, (i64,), _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [User] Failed to count active sessions for user ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [User] User ID {id} has {incoming_transfers} incoming transfer(s) and {active_sessions} active session(s)"
+        );
+
+        Ok(UserDeleteBlockers {
+            incoming_transfers,
+            active_sessions,
+        })
+    }
+
     async fn delete_user(&self, id: i32) -> Result<(), AppError> {
-        info!("🗑️ [User] Deleting user with ID: {}", id);
+        let _span = DbSpan::start("users", "delete_user");
 
-        let (sql, values) = Query::delete()
-            .from_table(Users::Table)
+        info!("🗑️ [User] Soft deleting user with ID: {}", id);
+
+        let (sql, values) = Query::update()
+            .table(Users::Table)
+            .value(Users::DeletedAt, Expr::current_timestamp())
             .and_where(Expr::col(Users::UserId).eq(id))
+            .and_where(Expr::col(Users::DeletedAt).is_null())
             .build_sqlx(PostgresQueryBuilder);
 
-        info!("🧾 [User] DELETE query: {sql} | Values: {:?}", values);
+        info!("🧾 [User] Soft DELETE query: {sql} | Values: {:?}", values);
 
         let result = sqlx::query_with(&sql, values)
             .execute(&self.db_pool)
@@ -374,11 +736,509 @@ impl UserRepositoryTrait for UserRepository {
             })?;
 
         if result.rows_affected() == 0 {
-            error!("❌ [User] Deletion failed: No user found with ID {id}");
+            error!("❌ [User] Deletion failed: No active user found with ID {id}");
             return Err(AppError::NotFound(format!("User ID {id} not found")));
         }
 
-        info!("✅ [User] Successfully deleted user ID: {id}");
+        info!("✅ [User] Successfully soft deleted user ID: {id}");
+        Ok(())
+    }
+
+    async fn restore_user(&self, id: i32) -> Result<User, AppError> {
+        let _span = DbSpan::start("users", "restore_user");
+
+        info!("♻️ [User] Restoring user with ID: {}", id);
+
+        let (sql, values) = Query::update()
+            .table(Users::Table)
+            .value(Users::DeletedAt, Option::<chrono::NaiveDateTime>::None)
+            .value(Users::UpdatedAt, Expr::current_timestamp())
+            .and_where(Expr::col(Users::UserId).eq(id))
+            .and_where(Expr::col(Users::DeletedAt).is_not_null())
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [User] RESTORE query: {sql} | Values: {:?}", values);
+
+        let user = sqlx::query_as_with::<_, User, _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::RowNotFound => {
+                    error!("❌ [User] Restore failed: No deleted user found with ID {id}");
+                    AppError::NotFound(format!("Deleted user with ID {id} not found"))
+                }
+                _ => {
+                    error!("❌ [User] Database error while restoring user ID {id}: {e}");
+                    AppError::SqlxError(e)
+                }
+            })?;
+
+        info!("✅ [User] Successfully restored user ID: {id}");
+        Ok(user)
+    }
+
+    async fn update_password(&self, id: i32, password: &str) -> Result<User, AppError> {
+        let _span = DbSpan::start("users", "update_password");
+
+        info!("🔑 [User] Updating password for user ID: {}", id);
+
+        let (sql, values) = Query::update()
+            .table(Users::Table)
+            .value(Users::Password, password)
+            .value(Users::PasswordChangedAt, Expr::current_timestamp())
+            .value(Users::UpdatedAt, Expr::current_timestamp())
+            .and_where(Expr::col(Users::UserId).eq(id))
+            .and_where(Expr::col(Users::DeletedAt).is_null())
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!("🧾 [User] UPDATE password query: {sql}");
+
+        let user = sqlx::query_as_with::<_, User, _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::RowNotFound => {
+                    error!("❌ [User] Password update failed: User with ID {id} not found");
+                    AppError::NotFound(format!("User with ID {id} not found"))
+                }
+                _ => {
+                    error!(
+                        "❌ [User] Database error while updating password for user ID {id}: {e}"
+                    );
+                    AppError::SqlxError(e)
+                }
+            })?;
+
+        info!("✅ [User] Successfully updated password for user ID: {id}");
+        Ok(user)
+    }
+
+    async fn rehash_password(&self, id: i32, password: &str) -> Result<(), AppError> {
+        let _span = DbSpan::start("users", "rehash_password");
+
+        info!("🔑 [User] Upgrading password hash for user ID: {}", id);
+
+        let (sql, values) = Query::update()
+            .table(Users::Table)
+            .value(Users::Password, password)
+            .and_where(Expr::col(Users::UserId).eq(id))
+            .and_where(Expr::col(Users::DeletedAt).is_null())
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [User] Database error while upgrading password hash for user ID {id}: {e}"
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        if result.rows_affected() == 0 {
+            return Err(AppError::NotFound(format!("User with ID {id} not found")));
+        }
+
+        info!("✅ [User] Password hash upgraded for user ID: {id}");
         Ok(())
     }
+
+    async fn update_status(&self, id: i32, status: UserStatus) -> Result<User, AppError> {
+        let _span = DbSpan::start("users", "update_status");
+
+        info!(
+            "🧊 [User] Setting status of user ID {} to '{}'",
+            id,
+            status.as_str()
+        );
+
+        let (sql, values) = Query::update()
+            .table(Users::Table)
+            .value(Users::Status, status.as_str())
+            .value(Users::UpdatedAt, Expr::current_timestamp())
+            .and_where(Expr::col(Users::UserId).eq(id))
+            .and_where(Expr::col(Users::DeletedAt).is_null())
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [User] UPDATE status query: {sql} | Values: {:?}",
+            values
+        );
+
+        let user = sqlx::query_as_with::<_, User, _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| match e {
+                sqlx::Error::RowNotFound => {
+                    error!("❌ [User] Status update failed: User with ID {id} not found");
+                    AppError::NotFound(format!("User with ID {id} not found"))
+                }
+                _ => {
+                    error!("❌ [User] Database error while updating status of user ID {id}: {e}");
+                    AppError::SqlxError(e)
+                }
+            })?;
+
+        info!("✅ [User] User ID {id} is now '{}'", user.status);
+        Ok(user)
+    }
+
+    async fn purge_user(&self, id: i32, force: bool) -> Result<UserPurge, AppError> {
+        let _span = DbSpan::start("users", "purge_user");
+
+        info!("🧨 [User] Purging user ID: {id} with all related records (force={force})");
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("❌ [User] Failed to begin purge transaction for user ID {id}: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        // baris user dikunci supaya tidak ada transaksi baru selama purge berjalan
+        let (sql, values) = Query::select()
+            .column(Users::UserId)
+            .from(Users::Table)
+            .and_where(Expr::col(Users::UserId).eq(id))
+            .lock(LockType::Update)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let locked = sqlx::query_as_with::<_, (i32,), _>(&sql, values)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!("❌ [User] Failed to lock user ID {id} for purge: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        if locked.is_none() {
+            error!("❌ [User] Purge failed: User ID {id} not found");
+            return Err(AppError::NotFound(format!("User ID {id} not found")));
+        }
+
+        if !force {
+            let (sql, values) = Query::select()
+                .expr(Func::count(Expr::col(Transfers::TransferId)))
+                .from(Transfers::Table)
+                .and_where(Expr::col(Transfers::TransferTo).eq(id))
+                .and_where(Expr::col(Transfers::TransferFrom).ne(id))
+                .build_sqlx(PostgresQueryBuilder);
+
+            let (received,) = sqlx::query_as_with::<_, (i64,), _>(&sql, values)
+                .fetch_one(&mut *tx)
+                .await
+                .map_err(|e| {
+                    error!("❌ [User] Failed to count incoming transfers for user ID {id}: {e}");
+                    AppError::SqlxError(e)
+                })?;
+
+            if received > 0 {
+                warn!("⚠️ [User] Purge refused: user ID {id} received {received} transfer(s)");
+                return Err(AppError::Custom(format!(
+                    "User ID {id} is the counterparty of {received} transfer(s) from other users, use force to purge"
+                )));
+            }
+        }
+
+        let transfers = delete_rows(
+            &mut tx,
+            Query::delete().from_table(Transfers::Table).cond_where(
+                Expr::col(Transfers::TransferFrom)
+                    .eq(id)
+                    .or(Expr::col(Transfers::TransferTo).eq(id)),
+            ),
+            "transfers",
+        )
+        .await?;
+
+        let topups = delete_rows(
+            &mut tx,
+            Query::delete()
+                .from_table(Topups::Table)
+                .and_where(Expr::col(Topups::UserId).eq(id)),
+            "topups",
+        )
+        .await?;
+
+        let withdraws = delete_rows(
+            &mut tx,
+            Query::delete()
+                .from_table(Withdraws::Table)
+                .and_where(Expr::col(Withdraws::UserId).eq(id)),
+            "withdraws",
+        )
+        .await?;
+
+        let saldos = delete_rows(
+            &mut tx,
+            Query::delete()
+                .from_table(SaldoSchema::Table)
+                .and_where(Expr::col(SaldoSchema::UserId).eq(id)),
+            "saldo",
+        )
+        .await?;
+
+        // ledger dan kartu virtual ikut terhapus lewat ON DELETE CASCADE
+        delete_rows(
+            &mut tx,
+            Query::delete()
+                .from_table(Users::Table)
+                .and_where(Expr::col(Users::UserId).eq(id)),
+            "user",
+        )
+        .await?;
+
+        tx.commit().await.map_err(|e| {
+            error!("❌ [User] Failed to commit purge of user ID {id}: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        let purge = UserPurge {
+            saldos,
+            topups,
+            transfers,
+            withdraws,
+        };
+
+        info!("✅ [User] Purged user ID {id}: {:?}", purge);
+        Ok(purge)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::test_db;
+    use chrono::NaiveDate;
+    use uuid::Uuid;
+
+    async fn create(repository: &UserRepository, email: &str) -> User {
+        repository
+            .create_user(&CreateUserRequest {
+                firstname: "Test".to_string(),
+                lastname: "User".to_string(),
+                email: email.to_string(),
+                password: "password".to_string(),
+                confirm_password: "password".to_string(),
+                noc_transfer: Some(Uuid::new_v4().simple().to_string()),
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn exact_email_finds_only_that_user() {
+        let pool = test_db::pool().await;
+        let repository = UserRepository::new(pool);
+        let tag = Uuid::new_v4().simple().to_string();
+        let user = create(&repository, &format!("{tag}@example.com")).await;
+        create(&repository, &format!("{tag}@example.com.au")).await;
+
+        let filter = UserFilter {
+            email: Some(format!("{}@EXAMPLE.com", tag.to_uppercase())),
+            ..UserFilter::default()
+        };
+        let (users, total) = repository.find_all(1, 10, &filter).await.unwrap();
+        assert_eq!(total, 1);
+        assert_eq!(users[0].user_id, user.user_id);
+
+        let filter = UserFilter {
+            email: Some(format!("missing-{tag}@example.com")),
+            ..UserFilter::default()
+        };
+        let (users, total) = repository.find_all(1, 10, &filter).await.unwrap();
+        assert_eq!(total, 0);
+        assert!(users.is_empty());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn created_range_bounds_the_listing() {
+        let pool = test_db::pool().await;
+        let repository = UserRepository::new(pool.clone());
+        let tag = Uuid::new_v4().simple().to_string();
+        let day = |d| {
+            NaiveDate::from_ymd_opt(2024, 1, d)
+                .unwrap()
+                .and_hms_opt(0, 0, 0)
+                .unwrap()
+        };
+
+        let mut ids = Vec::new();
+        for d in [1, 2, 3] {
+            let user = create(&repository, &format!("{tag}.{d}@example.com")).await;
+            sqlx::query("UPDATE users SET created_at = $1 WHERE user_id = $2")
+                .bind(day(d))
+                .bind(user.user_id)
+                .execute(&pool)
+                .await
+                .unwrap();
+            ids.push(user.user_id);
+        }
+
+        // batas bawah inklusif, batas atas eksklusif
+        let filter = UserFilter {
+            search: Some(tag.clone()),
+            created_after: Some(day(2)),
+            created_before: Some(day(3)),
+            ..UserFilter::default()
+        };
+        let (users, total) = repository.find_all(1, 10, &filter).await.unwrap();
+        assert_eq!(total, 1);
+        assert_eq!(users[0].user_id, ids[1]);
+
+        let filter = UserFilter {
+            search: Some(tag),
+            created_before: Some(day(1)),
+            ..UserFilter::default()
+        };
+        let (_, total) = repository.find_all(1, 10, &filter).await.unwrap();
+        assert_eq!(total, 0);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn partial_update_only_sets_given_columns() {
+        let pool = test_db::pool().await;
+        let repository = UserRepository::new(pool);
+        let tag = Uuid::new_v4().simple().to_string();
+        let user = create(&repository, &format!("{tag}@example.com")).await;
+        let untouched = UpdateUserRequest {
+            id: user.user_id,
+            firstname: None,
+            lastname: None,
+            email: None,
+            password: None,
+            confirm_password: None,
+        };
+
+        // semua None: tidak ada kolom yang berubah
+        let same = repository.update_user(&untouched).await.unwrap();
+        assert_eq!(same.email, user.email);
+        assert_eq!(same.firstname, user.firstname);
+        assert_eq!(same.password, user.password);
+
+        let updated = repository
+            .update_user(&UpdateUserRequest {
+                email: Some(format!("new-{tag}@example.com")),
+                ..untouched
+            })
+            .await
+            .unwrap();
+        assert_eq!(updated.email, format!("new-{tag}@example.com"));
+        assert_eq!(updated.firstname, user.firstname);
+        assert_eq!(updated.lastname, user.lastname);
+        assert_eq!(updated.password, user.password);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn purge_guards_counterparties_and_removes_related_rows() {
+        let pool = test_db::pool().await;
+        let repository = UserRepository::new(pool.clone());
+        let sender = test_db::seed_user(&pool, 100_000).await;
+        let receiver = test_db::seed_user(&pool, 0).await;
+        sqlx::query(
+            "INSERT INTO transfers (transfer_from, transfer_to, transfer_amount, transfer_time) \
+             VALUES ($1, $2, 10000, NOW())",
+        )
+        .bind(sender)
+        .bind(receiver)
+        .execute(&pool)
+        .await
+        .unwrap();
+
+        let err = repository.purge_user(receiver, false).await.unwrap_err();
+        assert!(err.to_string().contains("use force to purge"));
+        assert!(repository.find_by_id(receiver).await.unwrap().is_some());
+
+        let purge = repository.purge_user(sender, false).await.unwrap();
+        assert_eq!(purge.saldos, 1);
+        assert_eq!(purge.transfers, 1);
+        assert!(repository.find_by_id(sender).await.unwrap().is_none());
+
+        let (left,) = sqlx::query_as::<_, (i64,)>("SELECT COUNT(*) FROM saldo WHERE user_id = $1")
+            .bind(sender)
+            .fetch_one(&pool)
+            .await
+            .unwrap();
+        assert_eq!(left, 0);
+
+        // transfer masuk sudah ikut terhapus, purge tanpa force sekarang lolos
+        repository.purge_user(receiver, false).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn exists_by_id_reports_live_users_only() {
+        let pool = test_db::pool().await;
+        let repository = UserRepository::new(pool.clone());
+        let user_id = test_db::seed_user(&pool, 0).await;
+
+        assert!(repository.exists_by_id(user_id).await.unwrap());
+        assert!(!repository.exists_by_id(i32::MAX).await.unwrap());
+
+        // user yang di-soft-delete dianggap tidak ada
+        repository.delete_user(user_id).await.unwrap();
+        assert!(!repository.exists_by_id(user_id).await.unwrap());
+    }
+
+    fn import_row(email: &str) -> CreateUserRequest {
+        CreateUserRequest {
+            firstname: "Test".to_string(),
+            lastname: "User".to_string(),
+            email: email.to_string(),
+            password: "password".to_string(),
+            confirm_password: "password".to_string(),
+            noc_transfer: Some(Uuid::new_v4().simple().to_string()),
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn create_many_rolls_back_or_skips_duplicate_emails() {
+        let pool = test_db::pool().await;
+        let repository = UserRepository::new(pool);
+        let tag = Uuid::new_v4().simple().to_string();
+        let taken = create(&repository, &format!("taken-{tag}@example.com")).await;
+        let fresh = format!("fresh-{tag}@example.com");
+        let rows = [import_row(&fresh), import_row(&taken.email)];
+
+        let results = repository.create_many(&rows, false).await.unwrap();
+        assert!(results[1].is_err());
+        assert!(repository.find_by_email(&fresh).await.unwrap().is_none());
+
+        let results = repository.create_many(&rows, true).await.unwrap();
+        assert!(results[0].is_ok());
+        assert!(results[1].is_err());
+        assert!(repository.find_by_email(&fresh).await.unwrap().is_some());
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn duplicate_email_insert_is_email_already_exists() {
+        let pool = test_db::pool().await;
+        let repository = UserRepository::new(pool);
+        let email = format!("{}@example.com", Uuid::new_v4().simple());
+        create(&repository, &email).await;
+
+        // insert langsung ke repository melewati pengecekan service, constraint unik yang menolak
+        for duplicate in [email.clone(), email.to_uppercase()] {
+            let err = repository
+                .create_user(&CreateUserRequest {
+                    firstname: "Test".to_string(),
+                    lastname: "User".to_string(),
+                    email: duplicate,
+                    password: "password".to_string(),
+                    confirm_password: "password".to_string(),
+                    noc_transfer: Some(Uuid::new_v4().simple().to_string()),
+                })
+                .await
+                .unwrap_err();
+
+            assert!(matches!(err, AppError::EmailAlreadyExists), "{err:?}");
+        }
+    }
 }
@@ -0,0 +1,277 @@
+use crate::domain::request::default_page_size;
+use crate::model::virtual_card::VirtualCard;
+use crate::schema::virtual_card::VirtualCards;
+use crate::utils::{AppError, DbSpan};
+use crate::{
+    abstract_trait::VirtualCardRepositoryTrait,
+    config::{ConnectionPool, ReadPool},
+    domain::request::CreateVirtualCardRequest,
+};
+use anyhow::Result;
+use async_trait::async_trait;
+use chrono::Utc;
+use sea_query::{Expr, Func, Order, PostgresQueryBuilder, Query};
+use sea_query_binder::SqlxBinder;
+use tracing::{error, info};
+
+pub struct VirtualCardRepository {
+    db_pool: ConnectionPool,
+    read_pool: ReadPool,
+}
+
+impl VirtualCardRepository {
+    pub fn new(db_pool: ConnectionPool) -> Self {
+        Self {
+            read_pool: ReadPool::primary(db_pool.clone()),
+            db_pool,
+        }
+    }
+
+    pub fn with_read_pool(mut self, read_pool: ReadPool) -> Self {
+        self.read_pool = read_pool;
+        self
+    }
+}
+
+#[async_trait]
+impl VirtualCardRepositoryTrait for VirtualCardRepository {
+    async fn create(&self, input: &CreateVirtualCardRequest) -> Result<VirtualCard, AppError> {
+        let _span = DbSpan::start("virtual_cards", "create");
+
+        info!(
+            "💳 [VirtualCards] Creating virtual card for user_id={} expiring {:02}/{}",
+            input.user_id, input.expiry_month, input.expiry_year
+        );
+
+        let now = chrono::Utc::now();
+
+        let (sql, values) = Query::insert()
+            .into_table(VirtualCards::Table)
+            .columns([
+                VirtualCards::UserId,
+                VirtualCards::CardNumber,
+                VirtualCards::ExpiryMonth,
+                VirtualCards::ExpiryYear,
+                VirtualCards::CreatedAt,
+                VirtualCards::UpdatedAt,
+            ])
+            .values([
+                input.user_id.into(),
+                input.card_number.clone().into(),
+                input.expiry_month.into(),
+                input.expiry_year.into(),
+                now.into(),
+                now.into(),
+            ])
+            .unwrap()
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let inserted = sqlx::query_as_with::<_, VirtualCard, _>(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [VirtualCards] Failed to create virtual card for user_id={}: {e}",
+                    input.user_id
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [VirtualCards] Created virtual card ID: {} for user_id={}",
+            inserted.virtual_card_id, inserted.user_id
+        );
+
+        Ok(inserted)
+    }
+
+    async fn find_by_user_id(
+        &self,
+        user_id: i32,
+        page: i32,
+        page_size: i32,
+        include_revoked: bool,
+    ) -> Result<(Vec<VirtualCard>, i64), AppError> {
+        let _span = DbSpan::start("virtual_cards", "find_by_user_id");
+
+        info!(
+            "💳 [VirtualCards] Finding virtual cards for user_id={user_id} (include_revoked={include_revoked})"
+        );
+
+        let page = if page > 0 { page } else { 1 };
+        let page_size = if page_size > 0 {
+            page_size
+        } else {
+            default_page_size()
+        };
+        let offset = (page - 1) * page_size;
+
+        let mut query = Query::select();
+        query
+            .from(VirtualCards::Table)
+            .columns([
+                VirtualCards::VirtualCardId,
+                VirtualCards::UserId,
+                VirtualCards::CardNumber,
+                VirtualCards::ExpiryMonth,
+                VirtualCards::ExpiryYear,
+                VirtualCards::CreatedAt,
+                VirtualCards::UpdatedAt,
+                VirtualCards::RevokedAt,
+            ])
+            .and_where(Expr::col(VirtualCards::UserId).eq(user_id));
+
+        let mut count_query = Query::select();
+        count_query
+            .expr(Func::count(Expr::col(VirtualCards::VirtualCardId)))
+            .from(VirtualCards::Table)
+            .and_where(Expr::col(VirtualCards::UserId).eq(user_id));
+
+        if !include_revoked {
+            query.and_where(Expr::col(VirtualCards::RevokedAt).is_null());
+            count_query.and_where(Expr::col(VirtualCards::RevokedAt).is_null());
+        }
+
+        let (sql, values) = query
+            .order_by(VirtualCards::VirtualCardId, Order::Desc)
+            .limit(page_size as u64)
+            .offset(offset as u64)
+            .build_sqlx(PostgresQueryBuilder);
+
+        info!(
+            "🧾 [VirtualCards] Executing query: {sql} | Values: {:?}",
+            values
+        );
+
+        let rows = sqlx::query_as_with::<_, VirtualCard, _>(&sql, values)
+            .fetch_all(self.read_pool.get())
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [VirtualCards] Failed to fetch virtual cards for user_id={user_id}: {e}"
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        let (count_sql, count_values) = count_query.build_sqlx(PostgresQueryBuilder);
+
+        let (total,) = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
+            .fetch_one(self.read_pool.get())
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [VirtualCards] Failed to count virtual cards for user_id={user_id}: {e}"
+                );
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [VirtualCards] Retrieved {} of {total} virtual card(s) for user_id={user_id}",
+            rows.len()
+        );
+
+        Ok((rows, total))
+    }
+
+    async fn revoke(&self, user_id: i32, virtual_card_id: i32) -> Result<VirtualCard, AppError> {
+        let _span = DbSpan::start("virtual_cards", "revoke");
+
+        info!("🚫 [VirtualCards] Revoking virtual card ID {virtual_card_id} of user_id={user_id}");
+
+        let now = Utc::now().naive_utc();
+
+        let (sql, values) = Query::update()
+            .table(VirtualCards::Table)
+            .values([
+                (VirtualCards::RevokedAt, now.into()),
+                (VirtualCards::UpdatedAt, now.into()),
+            ])
+            .and_where(Expr::col(VirtualCards::VirtualCardId).eq(virtual_card_id))
+            .and_where(Expr::col(VirtualCards::UserId).eq(user_id))
+            .and_where(Expr::col(VirtualCards::RevokedAt).is_null())
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let revoked = sqlx::query_as_with::<_, VirtualCard, _>(&sql, values)
+            .fetch_optional(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [VirtualCards] Failed to revoke virtual card ID {virtual_card_id}: {e}");
+                AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| {
+                AppError::NotFound(format!(
+                    "Active virtual card {virtual_card_id} not found for user {user_id}"
+                ))
+            })?;
+
+        info!("✅ [VirtualCards] Virtual card ID {virtual_card_id} revoked");
+
+        Ok(revoked)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::test_db;
+    use uuid::Uuid;
+
+    async fn create_card(repository: &VirtualCardRepository, user_id: i32) -> VirtualCard {
+        // nomor kartu unik supaya test bisa diulang di database yang sama
+        let digits = Uuid::new_v4().as_u128() % 10u128.pow(16);
+
+        repository
+            .create(&CreateVirtualCardRequest {
+                user_id,
+                card_number: format!("{digits:016}"),
+                expiry_month: 12,
+                expiry_year: 2030,
+            })
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn revoked_cards_are_listed_only_when_included() {
+        let pool = test_db::pool().await;
+        let user_id = test_db::seed_user(&pool, 0).await;
+        let repository = VirtualCardRepository::new(pool);
+
+        let kept = create_card(&repository, user_id).await;
+        let revoked = create_card(&repository, user_id).await;
+
+        let card = repository
+            .revoke(user_id, revoked.virtual_card_id)
+            .await
+            .unwrap();
+        assert!(card.revoked_at.is_some());
+
+        let (active, total) = repository
+            .find_by_user_id(user_id, 1, 10, false)
+            .await
+            .unwrap();
+        assert_eq!(total, 1);
+        assert_eq!(active[0].virtual_card_id, kept.virtual_card_id);
+
+        let (all, total) = repository
+            .find_by_user_id(user_id, 1, 10, true)
+            .await
+            .unwrap();
+        assert_eq!(total, 2);
+        assert_eq!(
+            all.iter()
+                .map(|card| card.virtual_card_id)
+                .collect::<Vec<_>>(),
+            [revoked.virtual_card_id, kept.virtual_card_id]
+        );
+
+        let err = repository
+            .revoke(user_id, revoked.virtual_card_id)
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+    }
+}
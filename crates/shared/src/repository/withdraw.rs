@@ -1,28 +1,259 @@
+use crate::domain::request::default_page_size;
+use crate::model::saldo::DEFAULT_SALDO_LABEL;
 use crate::model::withdraw::Withdraw;
+use crate::repository::retry::{unknown_outcome, with_sql_retry};
+use crate::repository::saldo::map_balance_error;
+use crate::schema::saldo::Saldo as SaldoSchema;
 use crate::schema::withdraw::Withdraws as WithdrawSchema;
-use crate::utils::AppError;
+use crate::utils::{AppError, DbSpan, checked_amount};
 use crate::{
     abstract_trait::WithdrawRepositoryTrait,
-    config::ConnectionPool,
+    config::{ConnectionPool, ReadPool},
     domain::request::withdraw::{CreateWithdrawRequest, UpdateWithdrawRequest},
 };
 use anyhow::Result;
 use async_trait::async_trait;
-use chrono::{DateTime, Utc};
-use sea_query::{Expr, Func, Order, PostgresQueryBuilder, Query};
+use chrono::{DateTime, NaiveDateTime, Utc};
+use sea_query::{Asterisk, Expr, Func, LockType, Order, PostgresQueryBuilder, Query};
 use sea_query_binder::SqlxBinder;
 use tracing::{error, info};
 
 pub struct WithdrawRepository {
     db_pool: ConnectionPool,
+    read_pool: ReadPool,
 }
 
 impl WithdrawRepository {
     pub fn new(db_pool: ConnectionPool) -> Self {
-        Self { db_pool }
+        Self {
+            read_pool: ReadPool::primary(db_pool.clone()),
+            db_pool,
+        }
+    }
+
+    pub fn with_read_pool(mut self, read_pool: ReadPool) -> Self {
+        self.read_pool = read_pool;
+        self
+    }
+
+    async fn update_with_settlement_once(
+        &self,
+        input: &UpdateWithdrawRequest,
+    ) -> Result<Withdraw, AppError> {
+        let _span = DbSpan::start("withdraws", "update_with_settlement");
+
+        let id = input.withdraw_id;
+
+        info!("🔄 [Withdraw] Updating withdrawal ID {id} with balance adjustment");
+
+        let withdraw_time = parse_withdraw_time(&input.withdraw_time)?;
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!("❌ [Withdraw] Failed to begin update transaction for ID {id}: {e}");
+            AppError::SqlxError(e)
+        })?;
+
+        let now = Utc::now().naive_utc();
+
+        // baris withdraw dikunci supaya selisih dihitung dari nominal yang benar-benar tersimpan
+        let (sql, values) = Query::select()
+            .column(Asterisk)
+            .from(WithdrawSchema::Table)
+            .and_where(Expr::col(WithdrawSchema::WithdrawId).eq(id))
+            .and_where(Expr::col(WithdrawSchema::UserId).eq(input.user_id))
+            .lock(LockType::Update)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let original = sqlx::query_as_with::<_, Withdraw, _>(&sql, values)
+            .fetch_optional(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!("❌ [Withdraw] Failed to lock withdraw ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?
+            .ok_or_else(|| AppError::NotFound(format!("Withdraw with ID {id} not found")))?;
+
+        let difference =
+            checked_amount(input.withdraw_amount.checked_sub(original.withdraw_amount))?;
+
+        // batas bawah saldo (termasuk overdraft) dijaga trigger saldo
+        let (sql, values) = Query::update()
+            .table(SaldoSchema::Table)
+            .values([
+                (
+                    SaldoSchema::TotalBalance,
+                    Expr::col(SaldoSchema::TotalBalance).sub(difference),
+                ),
+                (SaldoSchema::WithdrawAmount, input.withdraw_amount.into()),
+                (SaldoSchema::WithdrawTime, now.into()),
+                (SaldoSchema::UpdatedAt, now.into()),
+            ])
+            .and_where(Expr::col(SaldoSchema::UserId).eq(input.user_id))
+            .and_where(Expr::col(SaldoSchema::Label).eq(DEFAULT_SALDO_LABEL))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Withdraw] Failed to adjust saldo for user_id={}: {e}",
+                    input.user_id
+                );
+                map_balance_error(e)
+            })?;
+
+        if result.rows_affected() == 0 {
+            error!(
+                "❌ [Withdraw] Saldo for user_id={} not found",
+                input.user_id
+            );
+            return Err(AppError::NotFound("Saldo not found".to_string()));
+        }
+
+        let (sql, values) = Query::update()
+            .table(WithdrawSchema::Table)
+            .values([
+                (WithdrawSchema::WithdrawAmount, input.withdraw_amount.into()),
+                (WithdrawSchema::WithdrawTime, withdraw_time.into()),
+                (WithdrawSchema::UpdatedAt, now.into()),
+            ])
+            .and_where(Expr::col(WithdrawSchema::WithdrawId).eq(id))
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let updated = sqlx::query_as_with::<_, Withdraw, _>(&sql, values)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!("❌ [Withdraw] Database error updating withdraw ID {id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        tx.commit().await.map_err(|e| {
+            error!("❌ [Withdraw] Failed to commit update of withdraw ID {id}: {e}");
+            unknown_outcome(e)
+        })?;
+
+        info!(
+            "✅ [Withdraw] Updated withdraw ID {id} | Amount: {} → {}",
+            original.withdraw_amount, updated.withdraw_amount
+        );
+
+        Ok(updated)
+    }
+
+    async fn create_with_settlement_once(
+        &self,
+        input: &CreateWithdrawRequest,
+    ) -> Result<Withdraw, AppError> {
+        let _span = DbSpan::start("withdraws", "create_with_settlement");
+
+        info!(
+            "💸 [Withdraw] Creating withdrawal with balance debit: user_id={}, amount={}",
+            input.user_id, input.withdraw_amount
+        );
+
+        let withdraw_time = parse_withdraw_time(&input.withdraw_time)?;
+
+        let mut tx = self.db_pool.begin().await.map_err(|e| {
+            error!(
+                "❌ [Withdraw] Failed to begin create transaction for user_id={}: {e}",
+                input.user_id
+            );
+            AppError::SqlxError(e)
+        })?;
+
+        let now = Utc::now().naive_utc();
+
+        // saldo dipotong relatif terhadap nilai yang tersimpan, bukan ditimpa dengan hasil hitungan
+        // di service, jadi kredit yang masuk bersamaan tidak hilang
+        let (sql, values) = Query::update()
+            .table(SaldoSchema::Table)
+            .values([
+                (
+                    SaldoSchema::TotalBalance,
+                    Expr::col(SaldoSchema::TotalBalance).sub(input.withdraw_amount),
+                ),
+                (SaldoSchema::WithdrawAmount, input.withdraw_amount.into()),
+                (SaldoSchema::WithdrawTime, now.into()),
+                (SaldoSchema::UpdatedAt, now.into()),
+            ])
+            .and_where(Expr::col(SaldoSchema::UserId).eq(input.user_id))
+            .and_where(Expr::col(SaldoSchema::Label).eq(DEFAULT_SALDO_LABEL))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let result = sqlx::query_with(&sql, values)
+            .execute(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!(
+                    "❌ [Withdraw] Failed to debit saldo for user_id={}: {e}",
+                    input.user_id
+                );
+                map_balance_error(e)
+            })?;
+
+        if result.rows_affected() == 0 {
+            error!(
+                "❌ [Withdraw] Saldo for user_id={} not found",
+                input.user_id
+            );
+            return Err(AppError::NotFound("Saldo not found".to_string()));
+        }
+
+        let (sql, values) = Query::insert()
+            .into_table(WithdrawSchema::Table)
+            .columns([
+                WithdrawSchema::UserId,
+                WithdrawSchema::WithdrawAmount,
+                WithdrawSchema::WithdrawTime,
+            ])
+            .values([
+                input.user_id.into(),
+                input.withdraw_amount.into(),
+                withdraw_time.into(),
+            ])
+            .unwrap()
+            .returning_all()
+            .build_sqlx(PostgresQueryBuilder);
+
+        let created = sqlx::query_as_with::<_, Withdraw, _>(&sql, values)
+            .fetch_one(&mut *tx)
+            .await
+            .map_err(|e| {
+                error!("❌ [Withdraw] Failed to create withdrawal: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        tx.commit().await.map_err(|e| {
+            error!(
+                "❌ [Withdraw] Failed to commit withdrawal for user_id={}: {e}",
+                input.user_id
+            );
+            unknown_outcome(e)
+        })?;
+
+        info!(
+            "✅ [Withdraw] Created withdraw_id={} for user_id={}, debited {}",
+            created.withdraw_id, created.user_id, created.withdraw_amount
+        );
+
+        Ok(created)
     }
 }
 
+fn parse_withdraw_time(value: &str) -> Result<NaiveDateTime, AppError> {
+    DateTime::parse_from_rfc3339(value)
+        .map(|time| time.with_timezone(&Utc).naive_utc())
+        .map_err(|e| {
+            error!("❌ [Withdraw] Invalid datetime string '{value}': {e}");
+            AppError::Custom(
+                "Invalid datetime format. Use RFC3339 (e.g., 2024-01-01T12:00:00Z).".to_string(),
+            )
+        })
+}
+
 #[async_trait]
 impl WithdrawRepositoryTrait for WithdrawRepository {
     async fn find_all(
@@ -31,13 +262,19 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         page_size: i32,
         search: Option<String>,
     ) -> Result<(Vec<Withdraw>, i64), AppError> {
+        let _span = DbSpan::start("withdraws", "find_all");
+
         info!(
             "📄 [Withdraw] Fetching all records - page: {}, page_size: {}, search: {:?}",
             page, page_size, search
         );
 
         let page = if page > 0 { page } else { 1 };
-        let page_size = if page_size > 0 { page_size } else { 10 };
+        let page_size = if page_size > 0 {
+            page_size
+        } else {
+            default_page_size()
+        };
         let offset = (page - 1) * page_size;
 
         info!(
@@ -73,7 +310,7 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         );
 
         let withdraws_result = sqlx::query_as_with::<_, Withdraw, _>(&sql, values)
-            .fetch_all(&self.db_pool)
+            .fetch_all(self.read_pool.get())
             .await;
 
         let withdraws = match withdraws_result {
@@ -107,7 +344,7 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         );
 
         let total_result = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
-            .fetch_one(&self.db_pool)
+            .fetch_one(self.read_pool.get())
             .await;
 
         let total = match total_result {
@@ -131,6 +368,8 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
     }
 
     async fn find_by_id(&self, id: i32) -> Result<Option<Withdraw>, AppError> {
+        let _span = DbSpan::start("withdraws", "find_by_id");
+
         info!("🆔 [Withdraw] Finding withdraw by ID: {}", id);
 
         let (sql, values) = Query::select()
@@ -152,7 +391,7 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         );
 
         let row = sqlx::query_as_with::<_, Withdraw, _>(&sql, values)
-            .fetch_optional(&self.db_pool)
+            .fetch_optional(self.read_pool.get())
             .await
             .map_err(|e| {
                 error!(
@@ -177,9 +416,50 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         Ok(row)
     }
 
-    async fn find_by_users(&self, id: i32) -> Result<Vec<Withdraw>, AppError> {
+    async fn exists_by_id(&self, id: i32) -> Result<bool, AppError> {
+        let _span = DbSpan::start("withdraws", "exists_by_id");
+
+        let (sql, values) = Query::select()
+            .expr(Expr::exists(
+                Query::select()
+                    .expr(Expr::val(1))
+                    .from(WithdrawSchema::Table)
+                    .and_where(Expr::col(WithdrawSchema::WithdrawId).eq(id))
+                    .to_owned(),
+            ))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let exists: bool = sqlx::query_scalar_with(&sql, values)
+            .fetch_one(&self.db_pool)
+            .await
+            .map_err(|e| {
+                error!("❌ [Withdraw] Failed to check withdraw_id={id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!("🔍 [Withdraw] withdraw_id={id} exists: {exists}");
+
+        Ok(exists)
+    }
+
+    async fn find_by_users(
+        &self,
+        id: i32,
+        page: i32,
+        page_size: i32,
+    ) -> Result<(Vec<Withdraw>, i64), AppError> {
+        let _span = DbSpan::start("withdraws", "find_by_users");
+
         info!("👥 [Withdraw] Finding all withdraws for user_id: {}", id);
 
+        let page = if page > 0 { page } else { 1 };
+        let page_size = if page_size > 0 {
+            page_size
+        } else {
+            default_page_size()
+        };
+        let offset = (page - 1) * page_size;
+
         let (sql, values) = Query::select()
             .from(WithdrawSchema::Table)
             .columns([
@@ -191,6 +471,9 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
                 WithdrawSchema::UpdatedAt,
             ])
             .and_where(Expr::col(WithdrawSchema::UserId).eq(id))
+            .order_by(WithdrawSchema::WithdrawId, Order::Asc)
+            .limit(page_size as u64)
+            .offset(offset as u64)
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -199,7 +482,7 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         );
 
         let rows = sqlx::query_as_with::<_, Withdraw, _>(&sql, values)
-            .fetch_all(&self.db_pool)
+            .fetch_all(self.read_pool.get())
             .await
             .map_err(|e| {
                 error!(
@@ -215,10 +498,26 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
             id
         );
 
-        Ok(rows)
+        let (count_sql, count_values) = Query::select()
+            .expr(Func::count(Expr::col(WithdrawSchema::WithdrawId)))
+            .from(WithdrawSchema::Table)
+            .and_where(Expr::col(WithdrawSchema::UserId).eq(id))
+            .build_sqlx(PostgresQueryBuilder);
+
+        let (total,) = sqlx::query_as_with::<_, (i64,), _>(&count_sql, count_values)
+            .fetch_one(self.read_pool.get())
+            .await
+            .map_err(|e| {
+                error!("❌ [Withdraw] Failed to count withdraws for user_id={id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        Ok((rows, total))
     }
 
     async fn find_by_user(&self, id: i32) -> Result<Option<Withdraw>, AppError> {
+        let _span = DbSpan::start("withdraws", "find_by_user");
+
         info!("👤 [Withdraw] Finding one withdraw for user_id: {}", id);
 
         let (sql, values) = Query::select()
@@ -240,7 +539,7 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         );
 
         let row = sqlx::query_as_with::<_, Withdraw, _>(&sql, values)
-            .fetch_optional(&self.db_pool)
+            .fetch_optional(self.read_pool.get())
             .await
             .map_err(|e| {
                 error!(
@@ -265,7 +564,47 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         Ok(row)
     }
 
+    async fn find_recent_by_user(
+        &self,
+        user_id: i32,
+        limit: i32,
+    ) -> Result<Vec<Withdraw>, AppError> {
+        let _span = DbSpan::start("withdraws", "find_recent_by_user");
+
+        let (sql, values) = Query::select()
+            .from(WithdrawSchema::Table)
+            .columns([
+                WithdrawSchema::WithdrawId,
+                WithdrawSchema::UserId,
+                WithdrawSchema::WithdrawAmount,
+                WithdrawSchema::WithdrawTime,
+                WithdrawSchema::CreatedAt,
+                WithdrawSchema::UpdatedAt,
+            ])
+            .and_where(Expr::col(WithdrawSchema::UserId).eq(user_id))
+            .order_by(WithdrawSchema::WithdrawId, Order::Desc)
+            .limit(limit.max(0) as u64)
+            .build_sqlx(PostgresQueryBuilder);
+
+        let rows = sqlx::query_as_with::<_, Withdraw, _>(&sql, values)
+            .fetch_all(self.read_pool.get())
+            .await
+            .map_err(|e| {
+                error!("❌ [Withdraw] Failed to fetch recent withdraws for user_id={user_id}: {e}");
+                AppError::SqlxError(e)
+            })?;
+
+        info!(
+            "✅ [Withdraw] Retrieved {} recent withdraw(s) for user_id={user_id}",
+            rows.len()
+        );
+
+        Ok(rows)
+    }
+
     async fn create(&self, input: &CreateWithdrawRequest) -> Result<Withdraw, AppError> {
+        let _span = DbSpan::start("withdraws", "create");
+
         info!(
             "💸 [Withdraw] Creating new withdrawal: user_id={}, amount={}, time={}",
             input.user_id, input.withdraw_amount, input.withdraw_time
@@ -323,7 +662,19 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         Ok(row)
     }
 
+    async fn create_with_settlement(
+        &self,
+        input: &CreateWithdrawRequest,
+    ) -> Result<Withdraw, AppError> {
+        with_sql_retry("withdraws.create_with_settlement", || {
+            self.create_with_settlement_once(input)
+        })
+        .await
+    }
+
     async fn update(&self, input: &UpdateWithdrawRequest) -> Result<Withdraw, AppError> {
+        let _span = DbSpan::start("withdraws", "update");
+
         info!(
             "🔄 [Withdraw] Updating withdrawal: id={}, amount={}, time={}",
             input.withdraw_id, input.withdraw_amount, input.withdraw_time
@@ -351,6 +702,7 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
                 (WithdrawSchema::WithdrawTime, withdraw_time_naive),
             ])
             .and_where(Expr::col(WithdrawSchema::WithdrawId).eq(input.withdraw_id))
+            .returning_all()
             .build_sqlx(PostgresQueryBuilder);
 
         info!(
@@ -386,7 +738,19 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         Ok(row)
     }
 
+    async fn update_with_settlement(
+        &self,
+        input: &UpdateWithdrawRequest,
+    ) -> Result<Withdraw, AppError> {
+        with_sql_retry("withdraws.update_with_settlement", || {
+            self.update_with_settlement_once(input)
+        })
+        .await
+    }
+
     async fn delete(&self, id: i32) -> Result<(), AppError> {
+        let _span = DbSpan::start("withdraws", "delete");
+
         info!("🗑️ [Withdraw] Deleting withdrawal with ID: {}", id);
 
         let (sql, values) = Query::delete()
@@ -421,3 +785,113 @@ impl WithdrawRepositoryTrait for WithdrawRepository {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abstract_trait::TopupRepositoryTrait,
+        domain::request::{CreateTopupRequest, UpdateTopupRequest},
+        repository::{test_db, topup::TopupRepository},
+    };
+    use uuid::Uuid;
+
+    fn update(user_id: i32, withdraw_id: i32, amount: i32) -> UpdateWithdrawRequest {
+        UpdateWithdrawRequest {
+            user_id,
+            withdraw_id,
+            withdraw_amount: amount,
+            withdraw_time: "2026-10-18T13:00:00Z".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn settled_update_moves_only_the_difference() {
+        let pool = test_db::pool().await;
+        let user = test_db::seed_user(&pool, 140_000).await;
+        let other = test_db::seed_user(&pool, 0).await;
+        let repository = WithdrawRepository::new(pool.clone());
+
+        // saldo awal dianggap sudah terpotong withdraw 60.000 ini
+        let created = repository
+            .create(&CreateWithdrawRequest {
+                user_id: user,
+                withdraw_amount: 60_000,
+                withdraw_time: "2026-10-18T12:00:00Z".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let updated = repository
+            .update_with_settlement(&update(user, created.withdraw_id, 90_000))
+            .await
+            .unwrap();
+        assert_eq!(updated.withdraw_amount, 90_000);
+        assert_eq!(test_db::balance_of(&pool, user).await, 110_000);
+
+        let err = repository
+            .update_with_settlement(&update(user, created.withdraw_id, 250_000))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::Custom(ref msg) if msg == "Insufficient balance"));
+
+        let err = repository
+            .update_with_settlement(&update(other, created.withdraw_id, 50_000))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, AppError::NotFound(_)));
+
+        let stored = repository
+            .find_by_id(created.withdraw_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.withdraw_amount, 90_000);
+        assert_eq!(test_db::balance_of(&pool, user).await, 110_000);
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a migrated Postgres at TEST_DATABASE_URL"]
+    async fn concurrent_withdraw_and_topup_changes_all_reach_the_balance() {
+        let pool = test_db::pool().await;
+        let user = test_db::seed_user(&pool, 100_000).await;
+        let withdraws = WithdrawRepository::new(pool.clone());
+        let topups = TopupRepository::new(pool.clone());
+
+        let topup = |amount: i32| CreateTopupRequest {
+            user_id: user,
+            topup_no: Uuid::new_v4().simple().to_string(),
+            topup_amount: amount,
+            topup_method: "bri".to_string(),
+        };
+        let pending = topups.create(&topup(50_000)).await.unwrap();
+        let edited = topups.create(&topup(30_000)).await.unwrap();
+        topups.confirm(edited.topup_id).await.unwrap();
+        assert_eq!(test_db::balance_of(&pool, user).await, 130_000);
+
+        let debit = CreateWithdrawRequest {
+            user_id: user,
+            withdraw_amount: 40_000,
+            withdraw_time: "2026-10-18T12:00:00Z".to_string(),
+        };
+        let raise = UpdateTopupRequest {
+            user_id: user,
+            topup_id: edited.topup_id,
+            topup_amount: Some(45_000),
+            topup_method: None,
+        };
+
+        let (confirmed, withdrawn, raised) = tokio::join!(
+            topups.confirm(pending.topup_id),
+            withdraws.create_with_settlement(&debit),
+            topups.update_with_settlement(&raise),
+        );
+        confirmed.unwrap();
+        withdrawn.unwrap();
+        raised.unwrap();
+
+        // 130.000 + 50.000 (konfirmasi) - 40.000 (withdraw) + 15.000 (selisih edit)
+        assert_eq!(test_db::balance_of(&pool, user).await, 155_000);
+    }
+}
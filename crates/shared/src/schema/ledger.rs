@@ -0,0 +1,14 @@
+use sea_query::Iden;
+
+#[derive(Debug, Iden)]
+pub enum LedgerEntries {
+    Table,
+    Id,
+    UserId,
+    EntryType,
+    Amount,
+    ReferenceId,
+    Reason,
+    ActorId,
+    CreatedAt,
+}
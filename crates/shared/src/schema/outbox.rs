@@ -0,0 +1,15 @@
+use sea_query::Iden;
+
+#[derive(Debug, Iden)]
+pub enum Outbox {
+    Table,
+    Id,
+    EventType,
+    Payload,
+    Status,
+    Attempts,
+    LastError,
+    NextAttemptAt,
+    CreatedAt,
+    DeliveredAt,
+}
@@ -0,0 +1,14 @@
+use sea_query::Iden;
+
+#[derive(Debug, Iden)]
+pub enum RefreshTokens {
+    Table,
+    SessionId,
+    UserId,
+    TokenHash,
+    UserAgent,
+    CreatedAt,
+    LastUsedAt,
+    ExpiresAt,
+    RevokedAt,
+}
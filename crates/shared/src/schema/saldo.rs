@@ -10,4 +10,5 @@ pub enum Saldo {
     WithdrawTime,
     CreatedAt,
     UpdatedAt,
+    Label,
 }
@@ -11,4 +11,5 @@ pub enum Topups {
     TopupTime,
     CreatedAt,
     UpdatedAt,
+    Status,
 }
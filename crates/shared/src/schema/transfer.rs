@@ -10,4 +10,9 @@ pub enum Transfers {
     TransferTime,
     CreatedAt,
     UpdatedAt,
+    Note,
+    Status,
+    FromLabel,
+    ToLabel,
+    ReversalOf,
 }
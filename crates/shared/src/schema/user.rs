@@ -11,4 +11,8 @@ pub enum Users {
     NocTransfer,
     CreatedAt,
     UpdatedAt,
+    DeletedAt,
+    PasswordChangedAt,
+    Status,
+    OverdraftLimit,
 }
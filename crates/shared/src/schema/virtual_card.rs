@@ -0,0 +1,14 @@
+use sea_query::Iden;
+
+#[derive(Debug, Iden)]
+pub enum VirtualCards {
+    Table,
+    VirtualCardId,
+    UserId,
+    CardNumber,
+    ExpiryMonth,
+    ExpiryYear,
+    CreatedAt,
+    UpdatedAt,
+    RevokedAt,
+}
@@ -0,0 +1,477 @@
+use async_trait::async_trait;
+use opentelemetry::{
+    Context, KeyValue,
+    context::FutureExt,
+    global::{self, BoxedTracer},
+    trace::{Span, SpanKind, TraceContextExt, Tracer},
+};
+use prometheus_client::registry::Registry;
+use std::sync::Arc;
+use tokio::time::Instant;
+use tonic::Request;
+use tracing::{error, info};
+use validator::Validate;
+
+use crate::{
+    abstract_trait::{
+        AccountServiceTrait, DynSaldoRepository, DynTopupRepository, DynTransferRepository,
+        DynUserRepository, DynWithdrawRepository,
+    },
+    cache::CacheStore,
+    domain::{
+        request::{AccountSummaryRequest, MAX_SUMMARY_ITEMS},
+        response::{
+            ApiResponse, ErrorResponse, account::AccountSummaryResponse, saldo::SaldoResponse,
+            topup::TopupResponse, transfer::TransferResponse, withdraw::WithdrawResponse,
+        },
+    },
+    utils::{
+        AppError, MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext,
+        cancellable,
+    },
+};
+
+pub fn account_summary_cache_key(user_id: i32) -> String {
+    format!("account_summary:user_id={user_id}")
+}
+
+// dipanggil setiap service yang mengubah saldo, topup, transfer, atau withdraw milik user
+pub fn invalidate_account_summary(cache_store: &CacheStore, user_ids: &[i32]) {
+    for user_id in user_ids {
+        cache_store.delete_from_cache(&account_summary_cache_key(*user_id));
+    }
+}
+
+// repository yang dibaca bersamaan untuk menyusun ringkasan akun
+pub struct AccountRepositories {
+    pub user: DynUserRepository,
+    pub saldo: DynSaldoRepository,
+    pub topup: DynTopupRepository,
+    pub transfer: DynTransferRepository,
+    pub withdraw: DynWithdrawRepository,
+}
+
+#[derive(Clone)]
+pub struct AccountService {
+    user_repository: DynUserRepository,
+    saldo_repository: DynSaldoRepository,
+    topup_repository: DynTopupRepository,
+    transfer_repository: DynTransferRepository,
+    withdraw_repository: DynWithdrawRepository,
+    metrics: Arc<Metrics>,
+    cache_store: Arc<CacheStore>,
+}
+
+impl std::fmt::Debug for AccountService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AccountService")
+            .field("user_repository", &"DynUserRepository")
+            .field("saldo_repository", &"DynSaldoRepository")
+            .field("topup_repository", &"DynTopupRepository")
+            .field("transfer_repository", &"DynTransferRepository")
+            .field("withdraw_repository", &"DynWithdrawRepository")
+            .finish()
+    }
+}
+
+impl AccountService {
+    pub async fn new(
+        repositories: AccountRepositories,
+        metrics: Arc<Metrics>,
+        registry: &mut Registry,
+        cache_store: Arc<CacheStore>,
+    ) -> Self {
+        registry.register(
+            "account_service_request_counter",
+            "Total number of requests to the AccountService",
+            metrics.request_counter.clone(),
+        );
+        registry.register(
+            "account_service_request_duration",
+            "Histogram of requests durations for the AccountService",
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "account_service_error_counter",
+            "Total number of failed requests to the AccountService by error kind",
+            metrics.error_counter.clone(),
+        );
+
+        Self {
+            user_repository: repositories.user,
+            saldo_repository: repositories.saldo,
+            topup_repository: repositories.topup,
+            transfer_repository: repositories.transfer,
+            withdraw_repository: repositories.withdraw,
+            metrics,
+            cache_store,
+        }
+    }
+
+    // keempat query tidak saling bergantung, jadi dijalankan bersamaan
+    async fn build_summary(&self, user_id: i32) -> Result<AccountSummaryResponse, AppError> {
+        let (saldo, topups, transfers, withdraws) = tokio::join!(
+            self.saldo_repository.find_by_user_id(user_id),
+            self.topup_repository
+                .find_recent_by_user(user_id, MAX_SUMMARY_ITEMS),
+            self.transfer_repository
+                .find_all_involving_user(user_id, 1, MAX_SUMMARY_ITEMS),
+            self.withdraw_repository
+                .find_recent_by_user(user_id, MAX_SUMMARY_ITEMS),
+        );
+
+        Ok(AccountSummaryResponse {
+            user_id,
+            saldo: saldo?.map(SaldoResponse::from),
+            recent_topups: topups?.into_iter().map(TopupResponse::from).collect(),
+            recent_transfers: transfers?
+                .0
+                .into_iter()
+                .map(TransferResponse::from)
+                .collect(),
+            recent_withdraws: withdraws?.into_iter().map(WithdrawResponse::from).collect(),
+        })
+    }
+
+    fn get_tracer(&self) -> BoxedTracer {
+        global::tracer("account-service")
+    }
+
+    fn inject_trace_context<T>(&self, cx: &Context, request: &mut Request<T>) {
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(cx, &mut MetadataInjector(request.metadata_mut()))
+        });
+    }
+
+    fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
+        let start_time = Instant::now();
+        let tracer = self.get_tracer();
+        let mut span = tracer
+            .span_builder(operation_name.to_string())
+            .with_kind(SpanKind::Server)
+            .with_attributes(attributes)
+            .start(&tracer);
+
+        info!("Starting operation: {operation_name}");
+
+        span.add_event(
+            "Operation started",
+            vec![
+                KeyValue::new("operation", operation_name.to_string()),
+                KeyValue::new("timestamp", start_time.elapsed().as_secs_f64().to_string()),
+            ],
+        );
+
+        let cx = Context::current_with_span(span);
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
+    }
+
+    async fn complete_tracing_success(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        message: &str,
+    ) {
+        self.complete_tracing_internal(tracing_ctx, method, true, message)
+            .await;
+    }
+
+    async fn complete_tracing_error(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        error_kind: &str,
+        error_message: &str,
+    ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
+        self.complete_tracing_internal(tracing_ctx, method, false, error_message)
+            .await;
+    }
+
+    async fn complete_tracing_internal(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        is_success: bool,
+        message: &str,
+    ) {
+        let status_str = if is_success { "SUCCESS" } else { "ERROR" };
+        let status = if is_success {
+            StatusUtils::Success
+        } else {
+            StatusUtils::Error
+        };
+        let elapsed = tracing_ctx.start_time.elapsed().as_secs_f64();
+
+        tracing_ctx.cx.span().add_event(
+            "Operation completed",
+            vec![
+                KeyValue::new("status", status_str),
+                KeyValue::new("duration_secs", elapsed.to_string()),
+                KeyValue::new("message", message.to_string()),
+            ],
+        );
+
+        if is_success {
+            info!("Operation completed successfully: {message}");
+        } else {
+            error!("Operation failed: {message}");
+        }
+
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
+
+        tracing_ctx.cx.span().end();
+    }
+}
+
+#[async_trait]
+impl AccountServiceTrait for AccountService {
+    async fn get_account_summary(
+        &self,
+        user_id: i32,
+        req: &AccountSummaryRequest,
+    ) -> Result<ApiResponse<AccountSummaryResponse>, ErrorResponse> {
+        let method = Method::Get;
+
+        let tracing_ctx = self.start_tracing(
+            "GetAccountSummary",
+            vec![
+                KeyValue::new("component", "account"),
+                KeyValue::new("user_id", user_id.to_string()),
+                KeyValue::new("limit", req.limit as i64),
+            ],
+        );
+
+        let mut request = Request::new(user_id);
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        if let Err(errors) = req.validate() {
+            let msg = format!("Invalid account summary request: {errors}");
+            let err = AppError::ValidationError(errors);
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        let limit = req.limit as usize;
+        let cache_key = account_summary_cache_key(user_id);
+
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<AccountSummaryResponse>(&cache_key)
+        {
+            info!("Found account summary for user {user_id} in cache");
+
+            self.complete_tracing_success(
+                &tracing_ctx,
+                method,
+                "Account summary retrieved from cache",
+            )
+            .await;
+
+            return Ok(ApiResponse {
+                status: "success".to_string(),
+                message: "Account summary retrieved successfully (from cache)".to_string(),
+                data: cached.truncated(limit),
+            });
+        }
+
+        match cancellable(self.user_repository.find_by_id(user_id))
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                let msg = format!("User with id {user_id} not found");
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to fetch user {user_id}: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        }
+
+        let summary = match cancellable(self.build_summary(user_id))
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(summary) => summary,
+            Err(err) => {
+                let msg = format!("Failed to build account summary for user {user_id}: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        self.cache_store
+            .set_to_cache(&cache_key, &summary, self.cache_store.ttl());
+
+        self.complete_tracing_success(
+            &tracing_ctx,
+            method,
+            "Account summary retrieved from database",
+        )
+        .await;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Account summary retrieved successfully".to_string(),
+            data: summary.truncated(limit),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::request::{CreateTopupRequest, CreateTransferRequest, CreateWithdrawRequest},
+        repository::memory::{InMemoryRepositories, offline_cache_store},
+    };
+    use axum::http::StatusCode;
+
+    async fn account_service(repos: &InMemoryRepositories) -> AccountService {
+        AccountService::new(
+            AccountRepositories {
+                user: repos.user.clone(),
+                saldo: repos.saldo.clone(),
+                topup: repos.topup.clone(),
+                transfer: repos.transfer.clone(),
+                withdraw: repos.withdraw.clone(),
+            },
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+            offline_cache_store(),
+        )
+        .await
+    }
+
+    async fn seed_topup(repos: &InMemoryRepositories, user_id: i32, topup_no: &str) -> i32 {
+        repos
+            .topup
+            .create(&CreateTopupRequest {
+                user_id,
+                topup_no: topup_no.to_string(),
+                topup_amount: 100_000,
+                topup_method: "bri".to_string(),
+            })
+            .await
+            .unwrap()
+            .topup_id
+    }
+
+    async fn seed_transfer(repos: &InMemoryRepositories, from: i32, to: i32) -> i32 {
+        repos
+            .transfer
+            .create(&CreateTransferRequest {
+                transfer_from: from,
+                transfer_to: to,
+                transfer_amount: 60_000,
+                note: None,
+                dry_run: false,
+                from_label: None,
+                to_label: None,
+            })
+            .await
+            .unwrap()
+            .transfer_id
+    }
+
+    async fn seed_withdraw(repos: &InMemoryRepositories, user_id: i32) -> i32 {
+        repos
+            .withdraw
+            .create(&CreateWithdrawRequest {
+                user_id,
+                withdraw_amount: 70_000,
+                withdraw_time: "2026-10-18T12:00:00Z".to_string(),
+            })
+            .await
+            .unwrap()
+            .withdraw_id
+    }
+
+    #[tokio::test]
+    async fn summary_has_the_balance_and_newest_items_of_each_type() {
+        let repos = InMemoryRepositories::new();
+        let alice = repos.seed_user("alice@example.com", 250_000).await.user_id;
+        let bob = repos.seed_user("bob@example.com", 90_000).await.user_id;
+
+        let mut topups = Vec::new();
+        for topup_no in ["TOPUP-1", "TOPUP-2", "TOPUP-3"] {
+            topups.push(seed_topup(&repos, alice, topup_no).await);
+        }
+        seed_topup(&repos, bob, "TOPUP-BOB").await;
+        let sent = seed_transfer(&repos, alice, bob).await;
+        let received = seed_transfer(&repos, bob, alice).await;
+        let withdraw = seed_withdraw(&repos, alice).await;
+        seed_withdraw(&repos, bob).await;
+        let service = account_service(&repos).await;
+
+        let summary = service
+            .get_account_summary(alice, &AccountSummaryRequest { limit: 2 })
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(summary.user_id, alice);
+        assert_eq!(summary.saldo.unwrap().total_balance, 250_000);
+        assert_eq!(
+            summary
+                .recent_topups
+                .iter()
+                .map(|t| t.topup_id)
+                .collect::<Vec<_>>(),
+            [topups[2], topups[1]]
+        );
+        let mut transfers: Vec<i32> = summary
+            .recent_transfers
+            .iter()
+            .map(|t| t.transfer_id)
+            .collect();
+        transfers.sort();
+        assert_eq!(transfers, [sent, received]);
+        assert_eq!(
+            summary
+                .recent_withdraws
+                .iter()
+                .map(|w| w.withdraw_id)
+                .collect::<Vec<_>>(),
+            [withdraw]
+        );
+    }
+
+    #[tokio::test]
+    async fn summary_limit_is_validated_and_unknown_users_are_not_found() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("alice@example.com", 0).await.user_id;
+        let service = account_service(&repos).await;
+
+        for limit in [0, MAX_SUMMARY_ITEMS + 1] {
+            let err = service
+                .get_account_summary(user, &AccountSummaryRequest { limit })
+                .await
+                .unwrap_err();
+            assert_eq!(err.code, StatusCode::UNPROCESSABLE_ENTITY, "limit {limit}");
+        }
+
+        let err = service
+            .get_account_summary(999, &AccountSummaryRequest::default())
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, StatusCode::NOT_FOUND);
+    }
+}
@@ -1,33 +1,68 @@
 use crate::{
-    abstract_trait::{AuthServiceTrait, DynHashing, DynJwtService, DynUserRepository},
+    abstract_trait::{
+        AuthServiceTrait, DynHashing, DynJwtService, DynSessionRepository, DynUserRepository,
+    },
     cache::CacheStore,
     domain::{
-        request::{CreateUserRequest, LoginRequest, RegisterRequest},
-        response::{ApiResponse, ErrorResponse, user::UserResponse},
+        request::{
+            ChangePasswordRequest, CreateSessionRequest, CreateUserRequest, LoginRequest,
+            RefreshTokenRequest, RegisterRequest, normalize_email,
+        },
+        response::{
+            ApiResponse, ErrorResponse,
+            auth::{LoginResponse, SessionResponse},
+            user::UserResponse,
+        },
     },
+    model::user::UserStatus,
     utils::{
         AppError, MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext,
         random_vcc,
     },
 };
 use async_trait::async_trait;
+use chrono::{Duration, Utc};
 use opentelemetry::{
     Context, KeyValue,
+    context::FutureExt,
     global::{self, BoxedTracer},
     trace::{Span, SpanKind, TraceContextExt, Tracer},
 };
 use prometheus_client::registry::Registry;
-use std::{sync::Arc, time::Duration};
-use tokio::{sync::Mutex, time::Instant};
+use rand::Rng;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tokio::time::Instant;
 use tonic::Request;
 use tracing::{error, info, warn};
+use validator::Validate;
+
+const REFRESH_TOKEN_TTL_DAYS: i64 = 30;
+
+// setelah MAX_FAILED_LOGINS percobaan gagal, email dikunci sampai jendela LOGIN_LOCKOUT habis
+const MAX_FAILED_LOGINS: i64 = 5;
+const LOGIN_LOCKOUT: std::time::Duration = std::time::Duration::from_secs(15 * 60);
+
+fn login_failures_key(email: &str) -> String {
+    format!("auth:login_failures:{}", normalize_email(email))
+}
+
+// refresh token acak 256-bit; yang disimpan di refresh_tokens hanya hash sha256-nya
+fn generate_refresh_token() -> String {
+    hex::encode(rand::rng().random::<[u8; 32]>())
+}
+
+fn hash_refresh_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
 
 #[derive(Clone)]
 pub struct AuthService {
     repository: DynUserRepository,
+    session_repository: DynSessionRepository,
     hashing: DynHashing,
     jwt_config: DynJwtService,
-    metrics: Arc<Mutex<Metrics>>,
+    metrics: Arc<Metrics>,
     cache_store: Arc<CacheStore>,
 }
 
@@ -35,34 +70,47 @@ impl std::fmt::Debug for AuthService {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("AuthService")
             .field("repository", &"DynUserRepository")
+            .field("session_repository", &"DynSessionRepository")
             .field("hashing", &"Hashing")
             .field("jwt_config", &"JwtConfig")
             .finish()
     }
 }
 
+// repository user dan session yang dipakai bersama oleh AuthService
+pub struct AuthRepositories {
+    pub user: DynUserRepository,
+    pub session: DynSessionRepository,
+}
+
 impl AuthService {
     pub async fn new(
-        repository: DynUserRepository,
+        repositories: AuthRepositories,
         hashing: DynHashing,
         jwt_config: DynJwtService,
-        metrics: Arc<Mutex<Metrics>>,
+        metrics: Arc<Metrics>,
         registry: &mut Registry,
         cache_store: Arc<CacheStore>,
     ) -> Self {
         registry.register(
             "auth_service_request_counter",
             "Total number of requests to the AuthService",
-            metrics.lock().await.request_counter.clone(),
+            metrics.request_counter.clone(),
         );
         registry.register(
             "auth_service_request_duration",
             "Histogram of request durations for the AuthService",
-            metrics.lock().await.request_duration.clone(),
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "auth_service_error_counter",
+            "Total number of failed requests to the AuthService by error kind",
+            metrics.error_counter.clone(),
         );
 
         Self {
-            repository,
+            repository: repositories.user,
+            session_repository: repositories.session,
             hashing,
             jwt_config,
             metrics,
@@ -70,6 +118,42 @@ impl AuthService {
         }
     }
 
+    // sisa detik penguncian jika email sudah melewati batas percobaan gagal
+    fn login_lockout(&self, failures_key: &str) -> Option<u64> {
+        let failures = self.cache_store.get_from_cache::<i64>(failures_key)?;
+
+        (failures >= MAX_FAILED_LOGINS).then(|| {
+            self.cache_store
+                .remaining_ttl(failures_key)
+                .unwrap_or(LOGIN_LOCKOUT.as_secs())
+        })
+    }
+
+    // hash lama (misal bcrypt) diganti ke algoritma aktif selagi password asli tersedia;
+    // kegagalan di sini tidak boleh menggagalkan login
+    async fn upgrade_password_hash(&self, user_id: i32, email: &str, password: &str) {
+        let hashed_password = match self.hashing.hash_password(password).await {
+            Ok(hashed) => hashed,
+            Err(e) => {
+                warn!("⚠️ [LOGIN] Failed to rehash password | Email: {email} | Error: {e}");
+                return;
+            }
+        };
+
+        match self
+            .repository
+            .rehash_password(user_id, &hashed_password)
+            .await
+        {
+            Ok(()) => info!("🔐 [LOGIN] Password hash upgraded | Email: {email}"),
+            Err(e) => {
+                warn!(
+                    "⚠️ [LOGIN] Failed to store upgraded password hash | Email: {email} | Error: {e}"
+                )
+            }
+        }
+    }
+
     fn get_tracer(&self) -> BoxedTracer {
         global::tracer("auth-service")
     }
@@ -100,7 +184,11 @@ impl AuthService {
         );
 
         let cx = Context::current_with_span(span);
-        TracingContext { cx, start_time }
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
     }
 
     async fn complete_tracing_success(
@@ -117,8 +205,12 @@ impl AuthService {
         &self,
         tracing_ctx: &TracingContext,
         method: Method,
+        error_kind: &str,
         error_message: &str,
     ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
         self.complete_tracing_internal(tracing_ctx, method, false, error_message)
             .await;
     }
@@ -153,7 +245,8 @@ impl AuthService {
             error!("Operation failed: {}", message);
         }
 
-        self.metrics.lock().await.record(method, status, elapsed);
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
 
         tracing_ctx.cx.span().end();
     }
@@ -165,6 +258,12 @@ impl AuthServiceTrait for AuthService {
         &self,
         input: &RegisterRequest,
     ) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        // request gRPC tidak lewat deserializer JSON, jadi email dinormalisasi lagi di sini
+        let input = &RegisterRequest {
+            email: normalize_email(&input.email),
+            ..input.clone()
+        };
+
         let log_msg = format!(
             "📝 [REGISTER] Starting user registration | Email: {}",
             input.email
@@ -183,9 +282,25 @@ impl AuthServiceTrait for AuthService {
         let mut request = Request::new(input.clone());
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
+        if let Err(errors) = input.validate() {
+            let msg = format!("Invalid register request: {errors}");
+            let log_msg = format!(
+                "❌ [REGISTER] Registration rejected: Invalid input | Email: {} | Error: {errors}",
+                input.email
+            );
+            warn!("{log_msg}");
+
+            let err = AppError::ValidationError(errors);
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
         let cache_key = format!("auth:registered:{}", input.email);
 
-        if let Some(cached_user) = self.cache_store.get_from_cache(&cache_key) {
+        if self.cache_store.is_enabled()
+            && let Some(cached_user) = self.cache_store.get_from_cache(&cache_key)
+        {
             let log_msg = format!(
                 "✅ [REGISTER] Cache hit! User already registered | Email: {}",
                 input.email
@@ -206,7 +321,12 @@ impl AuthServiceTrait for AuthService {
             });
         }
 
-        match self.repository.find_by_email_exists(&input.email).await {
+        match self
+            .repository
+            .find_by_email_exists(&input.email)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(true) => {
                 let msg = "Email already exists";
                 let log_msg = format!(
@@ -215,8 +335,10 @@ impl AuthServiceTrait for AuthService {
                 );
                 warn!("{log_msg}");
 
-                self.complete_tracing_error(&tracing_ctx, method, msg).await;
-                return Err(ErrorResponse::from(AppError::EmailAlreadyExists));
+                let err = AppError::EmailAlreadyExists;
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
             }
             Ok(false) => {
                 let check_log = format!(
@@ -233,7 +355,7 @@ impl AuthServiceTrait for AuthService {
                 );
                 error!("{log_msg}");
 
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
@@ -256,9 +378,10 @@ impl AuthServiceTrait for AuthService {
                 );
                 error!("{log_msg}");
 
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::HashingError(e);
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::HashingError(e)));
+                return Err(ErrorResponse::from(err));
             }
         };
 
@@ -273,7 +396,12 @@ impl AuthServiceTrait for AuthService {
             noc_transfer: noc_transfer.to_owned(),
         };
 
-        match self.repository.create_user(&create_user_request).await {
+        match self
+            .repository
+            .create_user(&create_user_request)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(user) => {
                 let success_msg = format!(
                     "🎉 [REGISTER] User successfully created | Email: {}",
@@ -290,10 +418,11 @@ impl AuthServiceTrait for AuthService {
                 self.cache_store.set_to_cache(
                     &cache_key,
                     &response.data.clone(),
-                    Duration::from_secs(60),
+                    self.cache_store.ttl(),
                 );
                 let cache_log = format!(
-                    "💾 [REGISTER] User response cached for 60s | Email: {}",
+                    "💾 [REGISTER] User response cached for {}s | Email: {}",
+                    self.cache_store.ttl().as_secs(),
                     input.email
                 );
                 info!("{cache_log}");
@@ -303,6 +432,20 @@ impl AuthServiceTrait for AuthService {
 
                 Ok(response)
             }
+            // registrasi paralel dengan email sama bisa lolos pengecekan di atas,
+            // constraint unik di database yang menolaknya
+            Err(AppError::EmailAlreadyExists) => {
+                let msg = "Email already exists";
+                warn!(
+                    "❌ [REGISTER] Registration rejected: Email taken concurrently | Email: {}",
+                    input.email
+                );
+
+                let err = AppError::EmailAlreadyExists;
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
             Err(err) => {
                 let msg = format!("User registration failed: {err}");
                 let log_msg = format!(
@@ -311,14 +454,23 @@ impl AuthServiceTrait for AuthService {
                 );
                 error!("{log_msg}");
 
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 Err(ErrorResponse::from(err))
             }
         }
     }
 
-    async fn login_user(&self, input: &LoginRequest) -> Result<ApiResponse<String>, ErrorResponse> {
+    async fn login_user(
+        &self,
+        input: &LoginRequest,
+    ) -> Result<ApiResponse<LoginResponse>, ErrorResponse> {
+        // login cocok dengan email terdaftar apa pun huruf besar/kecil yang diketik
+        let input = &LoginRequest {
+            email: normalize_email(&input.email),
+            ..input.clone()
+        };
+
         let method = Method::Post;
 
         let tracing_ctx = self.start_tracing(
@@ -332,41 +484,56 @@ impl AuthServiceTrait for AuthService {
         let mut request = Request::new(input.clone());
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let cache_key = format!("auth:login:{}", input.email);
-
-        if let Some(cached_token) = self.cache_store.get_from_cache(&cache_key) {
+        if let Err(errors) = input.validate() {
+            let msg = format!("Invalid login request: {errors}");
             let log_msg = format!(
-                "✅ [LOGIN] Cache hit! User already logged in | Email: {}",
+                "❌ [LOGIN] Login rejected: Invalid input | Email: {} | Error: {errors}",
                 input.email
             );
-            info!("{log_msg}");
+            warn!("{log_msg}");
 
-            self.complete_tracing_success(
-                &tracing_ctx,
-                method,
-                "User already logged in (from cache)",
-            )
-            .await;
+            let err = AppError::ValidationError(errors);
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
 
-            return Ok(ApiResponse {
-                status: "success".to_string(),
-                message: "User already logged in (from cache)".to_string(),
-                data: cached_token,
-            });
+        let failures_key = login_failures_key(&input.email);
+
+        if let Some(retry_after) = self.login_lockout(&failures_key) {
+            let log_msg = format!(
+                "🚫 [LOGIN] Too many failed attempts | Email: {} | Retry after: {retry_after}s",
+                input.email
+            );
+            warn!("{log_msg}");
+
+            let err = AppError::RateLimited(retry_after);
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &log_msg)
+                .await;
+            return Err(ErrorResponse::from(err));
         }
 
         let log_msg = format!("🔄 [LOGIN] Authenticating user | Email: {}", input.email);
         info!("{log_msg}");
 
-        let user = match self.repository.find_by_email(&input.email).await {
+        let user = match self
+            .repository
+            .find_by_email(&input.email)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(Some(user)) => user,
             Ok(None) => {
                 let msg = "User not found";
                 let log_msg = format!("❌ [LOGIN] User not found | Email: {}", input.email);
                 warn!("{log_msg}");
 
-                self.complete_tracing_error(&tracing_ctx, method, msg).await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg.to_string())));
+                self.cache_store.increment(&failures_key, LOGIN_LOCKOUT);
+
+                let err = AppError::NotFound(msg.to_string());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
             }
             Err(err) => {
                 let msg = format!("Error finding user: {err}");
@@ -376,7 +543,7 @@ impl AuthServiceTrait for AuthService {
                 );
                 error!("{log_msg}");
 
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
@@ -391,9 +558,20 @@ impl AuthServiceTrait for AuthService {
             let log_msg = format!("🔐 [LOGIN] Invalid password | Email: {}", input.email);
             warn!("{log_msg}");
 
-            self.complete_tracing_error(&tracing_ctx, method, "Invalid credentials")
+            self.cache_store.increment(&failures_key, LOGIN_LOCKOUT);
+
+            let err = AppError::InvalidCredentials;
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), "Invalid credentials")
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        self.cache_store.delete_from_cache(&failures_key);
+
+        if self.hashing.needs_rehash(&user.password) {
+            self.upgrade_password_hash(user.user_id, &input.email, &input.password)
+                .with_context(tracing_ctx.cx.clone())
                 .await;
-            return Err(ErrorResponse::from(AppError::InvalidCredentials));
         }
 
         let token = match self.jwt_config.generate_token(user.user_id as i64) {
@@ -406,21 +584,46 @@ impl AuthServiceTrait for AuthService {
                 );
                 error!("{log_msg}");
 
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
         };
 
-        self.cache_store
-            .set_to_cache(&cache_key, &token, Duration::from_secs(60));
-        let cache_log_msg = format!("💾 [LOGIN] Token cached for 60s | Email: {}", input.email);
-        info!("{cache_log_msg}");
+        // setiap login adalah sesi baru yang bisa dilihat dan dicabut admin
+        let refresh_token = generate_refresh_token();
+        let session = CreateSessionRequest {
+            user_id: user.user_id,
+            token_hash: hash_refresh_token(&refresh_token),
+            user_agent: input.user_agent.clone(),
+            expires_at: Utc::now().naive_utc() + Duration::days(REFRESH_TOKEN_TTL_DAYS),
+        };
+
+        if let Err(err) = self
+            .session_repository
+            .create(&session)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            let msg = format!("Failed to create session: {err}");
+            let log_msg = format!(
+                "🛑 [LOGIN] Failed to create session | Email: {} | Error: {err}",
+                input.email,
+            );
+            error!("{log_msg}");
+
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
 
         let response = ApiResponse {
             status: "success".to_string(),
             message: "Login successful".to_string(),
-            data: token,
+            data: LoginResponse {
+                access_token: token,
+                refresh_token,
+            },
         };
 
         let success_log_msg = format!(
@@ -446,18 +649,56 @@ impl AuthServiceTrait for AuthService {
             ],
         );
 
-        match self.repository.find_by_id(id).await {
+        let cache_key = format!("user:id={id}");
+        let user_ttl = self.cache_store.ttl_config().user;
+
+        // user yang dihapus atau dibekukan selalu dibaca ulang dari database
+        if !user_ttl.is_zero()
+            && let Some(cached_user) = self.cache_store.get_from_cache::<UserResponse>(&cache_key)
+            && cached_user.deleted_at.is_none()
+            && cached_user.status != UserStatus::Frozen.as_str()
+        {
+            let log_msg = format!("✅ [GET /me] Cache hit | ID: {id}");
+            info!("{log_msg}");
+
+            self.complete_tracing_success(
+                &tracing_ctx,
+                method,
+                "User retrieved successfully (from cache)",
+            )
+            .await;
+
+            return Ok(ApiResponse {
+                status: "success".to_string(),
+                message: "User retrieved successfully".to_string(),
+                data: cached_user,
+            });
+        }
+
+        match self
+            .repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(Some(user)) => {
                 let log_msg = format!("✅ [GET /me] User retrieved successfully | ID: {id}");
                 info!("{log_msg}");
 
+                let is_frozen = user.is_frozen();
+                let data = UserResponse::from(user);
+
+                if !is_frozen {
+                    self.cache_store.set_to_cache(&cache_key, &data, user_ttl);
+                }
+
                 self.complete_tracing_success(&tracing_ctx, method, "User retrieved successfully")
                     .await;
 
                 Ok(ApiResponse {
                     status: "success".to_string(),
                     message: "User retrieved successfully".to_string(),
-                    data: UserResponse::from(user),
+                    data,
                 })
             }
             Ok(None) => {
@@ -465,19 +706,817 @@ impl AuthServiceTrait for AuthService {
                 let log_msg = format!("❌ [GET /me] User not found | ID: {id}");
                 warn!("{log_msg}");
 
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                Err(ErrorResponse::from(AppError::NotFound(msg)))
+                Err(ErrorResponse::from(err))
             }
             Err(err) => {
                 let msg = format!("Failed to retrieve user {id}: {err}");
                 let log_msg = format!("🛑 [GET /me] Internal error | ID: {id} | Error: {err}");
                 error!("{log_msg}");
 
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 Err(ErrorResponse::from(err))
             }
         }
     }
+
+    async fn change_password(
+        &self,
+        id: i32,
+        input: &ChangePasswordRequest,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        let method = Method::Post;
+
+        let tracing_ctx = self.start_tracing(
+            "ChangePassword",
+            vec![
+                KeyValue::new("component", "auth"),
+                KeyValue::new("user.id", id.to_string()),
+            ],
+        );
+
+        if let Err(errors) = input.validate() {
+            let msg = format!("Invalid change password request: {errors}");
+            let log_msg = format!(
+                "❌ [CHANGE PASSWORD] Rejected: Invalid input | ID: {id} | Error: {errors}"
+            );
+            warn!("{log_msg}");
+
+            let err = AppError::ValidationError(errors);
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        let user = match self
+            .repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(user)) => user,
+            Ok(None) => {
+                let msg = format!("User with id {id} not found");
+                let log_msg = format!("❌ [CHANGE PASSWORD] User not found | ID: {id}");
+                warn!("{log_msg}");
+
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to retrieve user {id}: {err}");
+                let log_msg = format!(
+                    "🛑 [CHANGE PASSWORD] Database error during user lookup | ID: {id} | Error: {err}"
+                );
+                error!("{log_msg}");
+
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        if self
+            .hashing
+            .compare_password(&user.password, &input.old_password)
+            .await
+            .is_err()
+        {
+            let log_msg = format!("🔐 [CHANGE PASSWORD] Invalid old password | ID: {id}");
+            warn!("{log_msg}");
+
+            let err = AppError::InvalidCredentials;
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), "Invalid credentials")
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        let hashed_password = match self.hashing.hash_password(&input.new_password).await {
+            Ok(hashed) => hashed,
+            Err(e) => {
+                let msg = format!("Password hashing failed: {e}");
+                let log_msg = format!(
+                    "🛑 [CHANGE PASSWORD] Critical error: Failed to hash password | ID: {id} | Error: {e}"
+                );
+                error!("{log_msg}");
+
+                let err = AppError::HashingError(e);
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        if let Err(err) = self
+            .repository
+            .update_password(id, &hashed_password)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            let msg = format!("Failed to update password for user {id}: {err}");
+            let log_msg = format!(
+                "🛑 [CHANGE PASSWORD] Failed to save new password | ID: {id} | Error: {err}"
+            );
+            error!("{log_msg}");
+
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        // sesi lama tidak boleh dipakai lagi setelah password diganti
+        if let Err(err) = self
+            .session_repository
+            .revoke_all_for_user(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            error!("🛑 [CHANGE PASSWORD] Failed to revoke sessions | ID: {id} | Error: {err}");
+        }
+        self.cache_store.delete_from_cache(&format!("user:id={id}"));
+
+        let log_msg = format!("🎉 [CHANGE PASSWORD] Password changed successfully | ID: {id}");
+        info!("{log_msg}");
+
+        self.complete_tracing_success(&tracing_ctx, method, "Password changed successfully")
+            .await;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Password changed successfully".to_string(),
+            data: (),
+        })
+    }
+
+    async fn refresh_token(
+        &self,
+        input: &RefreshTokenRequest,
+    ) -> Result<ApiResponse<LoginResponse>, ErrorResponse> {
+        let method = Method::Post;
+
+        let tracing_ctx =
+            self.start_tracing("RefreshToken", vec![KeyValue::new("component", "auth")]);
+
+        if let Err(errors) = input.validate() {
+            let msg = format!("Invalid refresh token request: {errors}");
+            let err = AppError::ValidationError(errors);
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        let token_hash = hash_refresh_token(&input.refresh_token);
+
+        // sesi yang dicabut atau kedaluwarsa tidak pernah ditemukan di sini
+        let session = match self
+            .session_repository
+            .find_active_by_token_hash(&token_hash)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(session)) => session,
+            Ok(None) => {
+                warn!("🔐 [REFRESH] Unknown, revoked or expired refresh token");
+
+                let err = AppError::TokenValidationError;
+                self.complete_tracing_error(
+                    &tracing_ctx,
+                    method,
+                    err.kind(),
+                    "Invalid refresh token",
+                )
+                .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to look up session: {err}");
+                error!("🛑 [REFRESH] {msg}");
+
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        match self
+            .repository
+            .find_by_id(session.user_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(user)) if !user.is_frozen() => {}
+            Ok(_) => {
+                let msg = format!("User {} is frozen or no longer exists", session.user_id);
+                warn!("🔐 [REFRESH] {msg}");
+
+                let err = AppError::TokenValidationError;
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to fetch user {}: {err}", session.user_id);
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        }
+
+        let access_token = match self.jwt_config.generate_token(session.user_id as i64) {
+            Ok(token) => token,
+            Err(err) => {
+                let msg = format!("Token generation failed: {err}");
+                error!("🛑 [REFRESH] {msg}");
+
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        if let Err(err) = self
+            .session_repository
+            .touch(session.session_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            warn!(
+                "⚠️ [REFRESH] Failed to update last use of session {}: {err}",
+                session.session_id
+            );
+        }
+
+        info!(
+            "🔄 [REFRESH] Access token refreshed | User ID: {} | Session: {}",
+            session.user_id, session.session_id
+        );
+
+        self.complete_tracing_success(&tracing_ctx, method, "Token refreshed")
+            .await;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Token refreshed successfully".to_string(),
+            data: LoginResponse {
+                access_token,
+                refresh_token: input.refresh_token.clone(),
+            },
+        })
+    }
+
+    async fn list_sessions(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<Vec<SessionResponse>>, ErrorResponse> {
+        let method = Method::Get;
+
+        let tracing_ctx = self.start_tracing(
+            "ListSessions",
+            vec![
+                KeyValue::new("component", "auth"),
+                KeyValue::new("user.id", user_id.to_string()),
+            ],
+        );
+
+        match self
+            .session_repository
+            .find_active_by_user(user_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(sessions) => {
+                self.complete_tracing_success(&tracing_ctx, method, "Sessions retrieved")
+                    .await;
+
+                Ok(ApiResponse {
+                    status: "success".to_string(),
+                    message: "Sessions retrieved successfully".to_string(),
+                    data: sessions.into_iter().map(SessionResponse::from).collect(),
+                })
+            }
+            Err(err) => {
+                let msg = format!("Failed to list sessions of user {user_id}: {err}");
+                error!("{msg}");
+
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
+        }
+    }
+
+    async fn revoke_session(
+        &self,
+        user_id: i32,
+        session_id: i32,
+    ) -> Result<ApiResponse<()>, ErrorResponse> {
+        let method = Method::Delete;
+
+        let tracing_ctx = self.start_tracing(
+            "RevokeSession",
+            vec![
+                KeyValue::new("component", "auth"),
+                KeyValue::new("user.id", user_id.to_string()),
+                KeyValue::new("session.id", session_id.to_string()),
+            ],
+        );
+
+        match self
+            .session_repository
+            .revoke(user_id, session_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(()) => {
+                info!("🚫 [SESSIONS] Session {session_id} of user {user_id} revoked");
+
+                self.complete_tracing_success(&tracing_ctx, method, "Session revoked")
+                    .await;
+
+                Ok(ApiResponse {
+                    status: "success".to_string(),
+                    message: "Session revoked successfully".to_string(),
+                    data: (),
+                })
+            }
+            Err(err) => {
+                let msg = format!("Failed to revoke session {session_id}: {err}");
+                warn!("{msg}");
+
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::RETRY_AFTER_HEADER;
+    use crate::{
+        abstract_trait::UserServiceTrait,
+        config::{CacheTtlConfig, HashAlgorithm, Hashing, JwtConfig},
+        domain::request::UpdateUserRequest,
+        repository::memory::{InMemoryRepositories, offline_cache_store},
+        service::user::UserService,
+    };
+    use axum::{http::StatusCode, response::IntoResponse};
+
+    async fn auth_service(repos: &InMemoryRepositories) -> AuthService {
+        auth_service_with(repos, HashAlgorithm::Bcrypt).await
+    }
+
+    async fn auth_service_with(
+        repos: &InMemoryRepositories,
+        algorithm: HashAlgorithm,
+    ) -> AuthService {
+        AuthService::new(
+            AuthRepositories {
+                user: repos.user.clone(),
+                session: repos.session.clone(),
+            },
+            Arc::new(Hashing::new(algorithm)),
+            Arc::new(JwtConfig::new("test-secret")),
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+            offline_cache_store(),
+        )
+        .await
+    }
+
+    fn register(email: &str) -> RegisterRequest {
+        RegisterRequest {
+            firstname: "Alice".to_string(),
+            lastname: "Smith".to_string(),
+            email: email.to_string(),
+            password: "password123".to_string(),
+            confirm_password: "password123".to_string(),
+        }
+    }
+
+    fn login(email: &str) -> LoginRequest {
+        LoginRequest {
+            email: email.to_string(),
+            password: "password123".to_string(),
+            user_agent: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn email_case_variants_are_the_same_account() {
+        let repos = InMemoryRepositories::new();
+        let service = auth_service(&repos).await;
+
+        let registered = service
+            .register_user(&register("Alice@Example.com"))
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(registered.email, "alice@example.com");
+
+        // request gRPC tidak dinormalisasi deserializer, varian huruf dan spasi tetap ditolak
+        let err = service
+            .register_user(&register(" ALICE@example.COM "))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, StatusCode::CONFLICT);
+
+        let tokens = service
+            .login_user(&login("aLiCe@EXAMPLE.com"))
+            .await
+            .unwrap()
+            .data;
+        assert!(!tokens.access_token.is_empty());
+    }
+
+    async fn stored_hash(repos: &InMemoryRepositories) -> String {
+        repos
+            .user
+            .find_by_email("alice@example.com")
+            .await
+            .unwrap()
+            .unwrap()
+            .password
+    }
+
+    #[tokio::test]
+    async fn login_upgrades_an_outdated_password_hash() {
+        let repos = InMemoryRepositories::new();
+        auth_service(&repos)
+            .await
+            .register_user(&register("alice@example.com"))
+            .await
+            .unwrap();
+
+        assert_eq!(
+            HashAlgorithm::detect(&stored_hash(&repos).await),
+            Some(HashAlgorithm::Bcrypt)
+        );
+
+        // konfigurasi berpindah ke argon2: hash lama tetap diterima lalu diganti
+        let upgraded = auth_service_with(&repos, HashAlgorithm::Argon2).await;
+        upgraded
+            .login_user(&login("alice@example.com"))
+            .await
+            .unwrap();
+        assert_eq!(
+            HashAlgorithm::detect(&stored_hash(&repos).await),
+            Some(HashAlgorithm::Argon2)
+        );
+
+        upgraded
+            .login_user(&login("alice@example.com"))
+            .await
+            .unwrap();
+    }
+
+    fn change(old_password: &str, new_password: &str) -> ChangePasswordRequest {
+        ChangePasswordRequest {
+            old_password: old_password.to_string(),
+            new_password: new_password.to_string(),
+            confirm_password: new_password.to_string(),
+        }
+    }
+
+    async fn registered_id(service: &AuthService) -> i32 {
+        service
+            .register_user(&register("alice@example.com"))
+            .await
+            .unwrap()
+            .data
+            .id
+    }
+
+    #[tokio::test]
+    async fn changed_password_replaces_the_old_one_and_revokes_sessions() {
+        let repos = InMemoryRepositories::new();
+        let service = auth_service(&repos).await;
+        let id = registered_id(&service).await;
+        let tokens = service
+            .login_user(&login("alice@example.com"))
+            .await
+            .unwrap()
+            .data;
+
+        service
+            .change_password(id, &change("password123", "NewPassw0rd"))
+            .await
+            .unwrap();
+
+        let err = service
+            .login_user(&login("alice@example.com"))
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, StatusCode::UNAUTHORIZED);
+        service
+            .login_user(&LoginRequest {
+                password: "NewPassw0rd".to_string(),
+                ..login("alice@example.com")
+            })
+            .await
+            .unwrap();
+
+        let err = service
+            .refresh_token(&RefreshTokenRequest {
+                refresh_token: tokens.refresh_token,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, StatusCode::UNAUTHORIZED);
+    }
+
+    #[tokio::test]
+    async fn wrong_old_password_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        let service = auth_service(&repos).await;
+        let id = registered_id(&service).await;
+        let before = stored_hash(&repos).await;
+
+        let err = service
+            .change_password(id, &change("not-my-password", "NewPassw0rd"))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::UNAUTHORIZED);
+        assert_eq!(stored_hash(&repos).await, before);
+    }
+
+    #[tokio::test]
+    async fn weak_new_password_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        let service = auth_service(&repos).await;
+        let id = registered_id(&service).await;
+        let before = stored_hash(&repos).await;
+
+        for weak in ["short1A", "alllowercase1", "NoDigitsHere"] {
+            let err = service
+                .change_password(id, &change("password123", weak))
+                .await
+                .unwrap_err();
+            assert_eq!(err.code, StatusCode::UNPROCESSABLE_ENTITY);
+        }
+        assert_eq!(stored_hash(&repos).await, before);
+    }
+
+    fn redis_cache() -> Arc<CacheStore> {
+        let url = std::env::var("TEST_REDIS_URL")
+            .unwrap_or_else(|_| "redis://127.0.0.1:6379/".to_string());
+
+        Arc::new(
+            CacheStore::new(redis::Client::open(url).unwrap(), CacheTtlConfig::default())
+                .without_user_locks(),
+        )
+    }
+
+    // butuh Redis sungguhan, misalnya: TEST_REDIS_URL=redis://127.0.0.1:6379/ cargo test -- --ignored
+    #[tokio::test]
+    #[ignore = "needs a Redis server at TEST_REDIS_URL"]
+    async fn get_me_is_served_from_cache_until_the_user_changes() {
+        let cache = redis_cache();
+        let repos = InMemoryRepositories::new();
+        let auth = AuthService::new(
+            AuthRepositories {
+                user: repos.user.clone(),
+                session: repos.session.clone(),
+            },
+            Arc::new(Hashing::new(HashAlgorithm::Bcrypt)),
+            Arc::new(JwtConfig::new("test-secret")),
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+            cache.clone(),
+        )
+        .await;
+        let users = UserService::new(
+            repos.user.clone(),
+            repos.session.clone(),
+            Arc::new(Hashing::new(HashAlgorithm::Bcrypt)),
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+            cache.clone(),
+        )
+        .await;
+        let id = registered_id(&auth).await;
+        cache.delete_from_cache(&format!("user:id={id}"));
+        let rename = |firstname: &str| UpdateUserRequest {
+            id,
+            firstname: Some(firstname.to_string()),
+            lastname: None,
+            email: None,
+            password: None,
+            confirm_password: None,
+        };
+
+        assert_eq!(auth.get_me(id).await.unwrap().data.firstname, "Alice");
+
+        // perubahan langsung di repository tidak terlihat: get_me kedua tidak membaca repository
+        repos.user.update_user(&rename("Bypassed")).await.unwrap();
+        assert_eq!(auth.get_me(id).await.unwrap().data.firstname, "Alice");
+
+        // update lewat service menghapus cache, jadi bacaan berikutnya segar
+        users.update_user(&rename("Alicia")).await.unwrap();
+        assert_eq!(auth.get_me(id).await.unwrap().data.firstname, "Alicia");
+    }
+
+    #[tokio::test]
+    async fn registered_user_starts_with_a_zero_saldo() {
+        let repos = InMemoryRepositories::new();
+        let service = auth_service(&repos).await;
+
+        let user_id = registered_id(&service).await;
+
+        assert_eq!(repos.balance_of(user_id).await, 0);
+    }
+
+    #[tokio::test]
+    async fn duplicate_registration_creates_no_second_saldo() {
+        let repos = InMemoryRepositories::new();
+        let service = auth_service(&repos).await;
+        let user_id = registered_id(&service).await;
+
+        let err = service
+            .register_user(&register("alice@example.com"))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::CONFLICT);
+        let (_, total) = repos.saldo.find_by_users_id(user_id, 1, 10).await.unwrap();
+        assert_eq!(total, 1);
+    }
+
+    #[tokio::test]
+    async fn concurrent_duplicate_registrations_conflict_instead_of_failing() {
+        let repos = InMemoryRepositories::new();
+        let service = auth_service(&repos).await;
+
+        let request = register("alice@example.com");
+
+        let (first, second) = tokio::join!(
+            service.register_user(&request),
+            service.register_user(&request),
+        );
+
+        let err = match (first, second) {
+            (Ok(_), Err(err)) | (Err(err), Ok(_)) => err,
+            _ => panic!("exactly one registration should succeed"),
+        };
+        assert_eq!(err.code, StatusCode::CONFLICT);
+        assert_eq!(err.message, "Email already exists");
+    }
+
+    async fn login_from(service: &AuthService, user_agent: &str) -> LoginResponse {
+        service
+            .login_user(&LoginRequest {
+                user_agent: Some(user_agent.to_string()),
+                ..login("alice@example.com")
+            })
+            .await
+            .unwrap()
+            .data
+    }
+
+    #[tokio::test]
+    async fn each_login_is_listed_as_a_session() {
+        let repos = InMemoryRepositories::new();
+        let service = auth_service(&repos).await;
+        let id = registered_id(&service).await;
+        login_from(&service, "laptop").await;
+        login_from(&service, "phone").await;
+
+        let sessions = service.list_sessions(id).await.unwrap().data;
+
+        let mut agents: Vec<_> = sessions
+            .iter()
+            .map(|session| session.user_agent.clone().unwrap())
+            .collect();
+        agents.sort();
+        assert_eq!(agents, ["laptop", "phone"]);
+        assert!(sessions.iter().all(|session| session.user_id == id));
+    }
+
+    #[tokio::test]
+    async fn revoked_session_stops_refreshing_while_others_keep_working() {
+        let repos = InMemoryRepositories::new();
+        let service = auth_service(&repos).await;
+        let id = registered_id(&service).await;
+        let laptop = login_from(&service, "laptop").await;
+        let phone = login_from(&service, "phone").await;
+        let laptop_session = service
+            .list_sessions(id)
+            .await
+            .unwrap()
+            .data
+            .into_iter()
+            .find(|session| session.user_agent.as_deref() == Some("laptop"))
+            .unwrap();
+
+        service.revoke_session(id, laptop_session.id).await.unwrap();
+
+        let err = service
+            .refresh_token(&RefreshTokenRequest {
+                refresh_token: laptop.refresh_token,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, StatusCode::UNAUTHORIZED);
+        service
+            .refresh_token(&RefreshTokenRequest {
+                refresh_token: phone.refresh_token,
+            })
+            .await
+            .unwrap();
+        let sessions = service.list_sessions(id).await.unwrap().data;
+        assert!(
+            sessions
+                .iter()
+                .all(|session| session.id != laptop_session.id)
+        );
+    }
+
+    async fn redis_auth_service(repos: &InMemoryRepositories) -> AuthService {
+        AuthService::new(
+            AuthRepositories {
+                user: repos.user.clone(),
+                session: repos.session.clone(),
+            },
+            Arc::new(Hashing::new(HashAlgorithm::Bcrypt)),
+            Arc::new(JwtConfig::new("test-secret")),
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+            redis_cache(),
+        )
+        .await
+    }
+
+    fn wrong_login(email: &str) -> LoginRequest {
+        LoginRequest {
+            password: "not-my-password".to_string(),
+            ..login(email)
+        }
+    }
+
+    // butuh Redis sungguhan, misalnya: TEST_REDIS_URL=redis://127.0.0.1:6379/ cargo test -- --ignored
+    #[tokio::test]
+    #[ignore = "needs a Redis server at TEST_REDIS_URL"]
+    async fn failures_below_the_threshold_are_plain_unauthorized() {
+        let repos = InMemoryRepositories::new();
+        let service = redis_auth_service(&repos).await;
+        let email = format!("{}@example.com", uuid::Uuid::new_v4().simple());
+        service.register_user(&register(&email)).await.unwrap();
+
+        for _ in 0..MAX_FAILED_LOGINS - 1 {
+            let err = service.login_user(&wrong_login(&email)).await.unwrap_err();
+            assert_eq!(err.code, StatusCode::UNAUTHORIZED);
+            assert_eq!(err.retry_after, None);
+        }
+
+        service.login_user(&login(&email)).await.unwrap();
+    }
+
+    #[tokio::test]
+    #[ignore = "needs a Redis server at TEST_REDIS_URL"]
+    async fn locked_out_login_is_429_with_retry_after() {
+        let repos = InMemoryRepositories::new();
+        let service = redis_auth_service(&repos).await;
+        let email = format!("{}@example.com", uuid::Uuid::new_v4().simple());
+        service.register_user(&register(&email)).await.unwrap();
+
+        for _ in 0..MAX_FAILED_LOGINS {
+            service.login_user(&wrong_login(&email)).await.unwrap_err();
+        }
+
+        // password benar pun ditolak selama masa kunci
+        let err = service.login_user(&login(&email)).await.unwrap_err();
+        assert_eq!(err.code, StatusCode::TOO_MANY_REQUESTS);
+        let retry_after = err.retry_after.unwrap();
+        assert!(
+            retry_after > LOGIN_LOCKOUT.as_secs() - 5 && retry_after <= LOGIN_LOCKOUT.as_secs()
+        );
+
+        let response = err.into_response();
+        assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            response.headers()[RETRY_AFTER_HEADER],
+            retry_after.to_string().as_str()
+        );
+
+        let err = service.login_user(&login(&email)).await.unwrap_err();
+        let status = tonic::Status::from(err);
+        assert_eq!(status.code(), tonic::Code::ResourceExhausted);
+        let hint: u64 = status
+            .metadata()
+            .get(RETRY_AFTER_HEADER)
+            .unwrap()
+            .to_str()
+            .unwrap()
+            .parse()
+            .unwrap();
+        assert!(hint > 0 && hint <= retry_after);
+    }
 }
@@ -1,6 +1,9 @@
+pub mod account;
 pub mod auth;
 pub mod saldo;
 pub mod topup;
 pub mod transfer;
 pub mod user;
+pub mod virtual_card;
+pub mod webhook;
 pub mod withdraw;
@@ -1,33 +1,47 @@
 use async_trait::async_trait;
 use opentelemetry::{
     Context, KeyValue,
+    context::FutureExt,
     global::{self, BoxedTracer},
     trace::{Span, SpanKind, TraceContextExt, Tracer},
 };
 use prometheus_client::registry::Registry;
-use std::{sync::Arc, time::Duration};
-use tokio::{sync::Mutex, time::Instant};
+use std::sync::Arc;
+use tokio::time::Instant;
 use tonic::Request;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+use validator::Validate;
 
 use crate::{
     abstract_trait::{DynSaldoRepository, DynUserRepository, SaldoServiceTrait},
     cache::CacheStore,
     domain::{
-        request::{CreateSaldoRequest, FindAllSaldoRequest, UpdateSaldoRequest},
+        request::{
+            AdjustBalanceRequest, CreateSaldoRequest, FindAllSaldoRequest, PageRequest,
+            ReconcileBalanceRequest, TopSaldosRequest, UpdateSaldoRequest,
+        },
         response::{
-            ApiResponse, ApiResponsePagination, ErrorResponse, pagination::Pagination,
-            saldo::SaldoResponse,
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            pagination::Pagination,
+            saldo::{BalanceReconciliationResponse, SaldoRankingResponse, SaldoResponse},
         },
     },
-    utils::{AppError, MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+    model::{
+        ledger::LedgerEntryType,
+        saldo::{BalanceReconciliation, Saldo},
+    },
+    service::account::invalidate_account_summary,
+    utils::{
+        AppError, MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext,
+        cancellable,
+    },
 };
 
 #[derive(Clone)]
 pub struct SaldoService {
     user_repository: DynUserRepository,
     saldo_repository: DynSaldoRepository,
-    metrics: Arc<Mutex<Metrics>>,
+    metrics: Arc<Metrics>,
     cache_store: Arc<CacheStore>,
 }
 
@@ -44,19 +58,24 @@ impl SaldoService {
     pub async fn new(
         user_repository: DynUserRepository,
         saldo_repository: DynSaldoRepository,
-        metrics: Arc<Mutex<Metrics>>,
+        metrics: Arc<Metrics>,
         registry: &mut Registry,
         cache_store: Arc<CacheStore>,
     ) -> Self {
         registry.register(
             "saldo_service_request_counter",
             "Total number of requests to the SaldoService",
-            metrics.lock().await.request_counter.clone(),
+            metrics.request_counter.clone(),
         );
         registry.register(
             "saldo_service_request_duration",
             "Histogram of requests durations for the SaldoService",
-            metrics.lock().await.request_duration.clone(),
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "saldo_service_error_counter",
+            "Total number of failed requests to the SaldoService by error kind",
+            metrics.error_counter.clone(),
         );
 
         Self {
@@ -97,7 +116,11 @@ impl SaldoService {
         );
 
         let cx = Context::current_with_span(span);
-        TracingContext { cx, start_time }
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
     }
 
     async fn complete_tracing_success(
@@ -114,8 +137,12 @@ impl SaldoService {
         &self,
         tracing_ctx: &TracingContext,
         method: Method,
+        error_kind: &str,
         error_message: &str,
     ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
         self.complete_tracing_internal(tracing_ctx, method, false, error_message)
             .await;
     }
@@ -150,10 +177,57 @@ impl SaldoService {
             error!("Operation failed: {message}");
         }
 
-        self.metrics.lock().await.record(method, status, elapsed);
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
 
         tracing_ctx.cx.span().end();
     }
+
+    // saldo lama di cache tidak boleh bertahan setelah koreksi
+    fn invalidate_saldo_cache(&self, saldo: &Saldo) {
+        for key in [
+            format!("saldo:id={}", saldo.saldo_id),
+            format!("saldo_user:id={}", saldo.user_id),
+            PageRequest::default().cache_key("saldo_users", saldo.user_id),
+        ] {
+            self.cache_store.delete_from_cache(&key);
+        }
+        invalidate_account_summary(&self.cache_store, &[saldo.user_id]);
+    }
+
+    // selisih ditutup lewat saldo utama dengan entri ledger bertipe reconciliation,
+    // sehingga tercatat siapa yang mengoreksi dan berapa nilai sebelumnya
+    async fn correct_balance(
+        &self,
+        actor_id: i32,
+        reconciliation: &BalanceReconciliation,
+    ) -> Result<Saldo, AppError> {
+        let delta = i32::try_from(reconciliation.difference()).map_err(|_| {
+            AppError::Custom(format!(
+                "Difference of {} for user {} is too large to correct automatically",
+                reconciliation.difference(),
+                reconciliation.user_id
+            ))
+        })?;
+
+        let adjustment = AdjustBalanceRequest {
+            user_id: reconciliation.user_id,
+            delta,
+            reason: format!(
+                "Reconciliation: stored {} expected {}",
+                reconciliation.stored_balance, reconciliation.expected_balance
+            ),
+        };
+
+        let saldo = self
+            .saldo_repository
+            .adjust_balance(actor_id, &adjustment, LedgerEntryType::Reconciliation)
+            .await?;
+
+        self.invalidate_saldo_cache(&saldo);
+
+        Ok(saldo)
+    }
 }
 
 #[async_trait]
@@ -164,8 +238,9 @@ impl SaldoServiceTrait for SaldoService {
     ) -> Result<ApiResponsePagination<Vec<SaldoResponse>>, ErrorResponse> {
         let method = Method::Get;
 
-        let page = if req.page > 0 { req.page } else { 1 };
-        let page_size = if req.page_size > 0 { req.page_size } else { 10 };
+        let PageRequest { page, page_size } = PageRequest::new(req.page, req.page_size)
+            .bounded()
+            .map_err(ErrorResponse::from)?;
         let search = if req.search.is_empty() {
             None
         } else {
@@ -195,62 +270,53 @@ impl SaldoServiceTrait for SaldoService {
             search.clone().unwrap_or_default()
         );
 
-        if let Some(cached) = self
-            .cache_store
-            .get_from_cache::<ApiResponsePagination<Vec<SaldoResponse>>>(&cache_key)
-        {
-            let log_msg = format!(
-                "✅ [GET /saldos] Cache hit! Retrieved from cache | Page: {page}, Size: {page_size}, Search: '{}'",
-                search.clone().unwrap_or_default()
-            );
-            info!("{log_msg}");
-
-            self.complete_tracing_success(&tracing_ctx, method, "Saldos retrieved from cache")
-                .await;
-
-            return Ok(cached);
-        }
-
-        let log_msg = format!(
-            "🔁 [GET /saldos] Cache miss, querying DB | Page: {page}, Size: {page_size}, Search: '{}'",
-            search.clone().unwrap_or_default()
-        );
-
-        info!("{log_msg}");
-
-        match self
-            .saldo_repository
-            .find_all(page, page_size, search.clone())
-            .await
-        {
-            Ok((saldos, total_items)) => {
-                let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
-                let saldo_responses: Vec<SaldoResponse> =
-                    saldos.into_iter().map(SaldoResponse::from).collect();
-
-                let response = ApiResponsePagination {
-                    status: "success".to_string(),
-                    message: "Saldos retrieved successfully".to_string(),
-                    data: saldo_responses,
-                    pagination: Pagination {
-                        page,
-                        page_size,
-                        total_items,
-                        total_pages,
-                    },
-                };
-
-                self.cache_store
-                    .set_to_cache(&cache_key, &response, Duration::from_secs(300));
+        // refresh ikut berjalan di task background, jadi semua yang dipakai harus dimiliki
+        let refresh = {
+            let saldo_repository = self.saldo_repository.clone();
+            let search = search.clone();
+
+            move || {
+                let saldo_repository = saldo_repository.clone();
+                let search = search.clone();
+                async move {
+                    info!(
+                        "🔁 [GET /saldos] Querying DB | Page: {page}, Size: {page_size}, Search: '{}'",
+                        search.clone().unwrap_or_default()
+                    );
+
+                    let (saldos, total_items) =
+                        cancellable(saldo_repository.find_all(page, page_size, search)).await?;
+
+                    let saldo_responses: Vec<SaldoResponse> =
+                        saldos.into_iter().map(SaldoResponse::from).collect();
+
+                    Ok::<_, AppError>(ApiResponsePagination {
+                        status: "success".to_string(),
+                        message: "Saldos retrieved successfully".to_string(),
+                        data: saldo_responses,
+                        pagination: Pagination::new(page, page_size, total_items),
+                    })
+                }
+            }
+        };
 
-                let cache_log =
-                    format!("💾 [GET /saldos] Response cached | Key: '{cache_key}' | TTL: 300s");
-                info!("{cache_log}");
+        let result = self
+            .cache_store
+            .get_with_swr(
+                &cache_key,
+                self.cache_store.ttl_config().saldo_soft,
+                self.cache_store.ttl(),
+                refresh,
+            )
+            .with_context(tracing_ctx.cx.clone())
+            .await;
 
+        match result {
+            Ok(response) => {
                 self.complete_tracing_success(
                     &tracing_ctx,
                     method,
-                    "Saldos retrieved from database",
+                    "Saldos retrieved successfully",
                 )
                 .await;
 
@@ -265,7 +331,7 @@ impl SaldoServiceTrait for SaldoService {
                 );
                 error!("{log_msg}");
 
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
 
                 Err(ErrorResponse::from(err))
@@ -292,9 +358,10 @@ impl SaldoServiceTrait for SaldoService {
 
         let cache_key = format!("saldo:id={id}");
 
-        if let Some(cached) = self
-            .cache_store
-            .get_from_cache::<ApiResponse<Option<SaldoResponse>>>(&cache_key)
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<ApiResponse<Option<SaldoResponse>>>(&cache_key)
         {
             info!("Found saldo in cache");
 
@@ -304,7 +371,12 @@ impl SaldoServiceTrait for SaldoService {
             return Ok(cached);
         }
 
-        match self.saldo_repository.find_by_id(id).await {
+        match self
+            .saldo_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(Some(saldo)) => {
                 let response = ApiResponse {
                     status: "success".to_string(),
@@ -313,7 +385,7 @@ impl SaldoServiceTrait for SaldoService {
                 };
 
                 self.cache_store
-                    .set_to_cache(&cache_key, &response, Duration::from_secs(60 * 5));
+                    .set_to_cache(&cache_key, &response, self.cache_store.ttl());
 
                 self.complete_tracing_success(
                     &tracing_ctx,
@@ -326,14 +398,15 @@ impl SaldoServiceTrait for SaldoService {
             }
             Ok(None) => {
                 let msg = format!("Saldo with id {id} not found");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
 
-                Err(ErrorResponse::from(AppError::NotFound(msg)))
+                Err(ErrorResponse::from(err))
             }
             Err(err) => {
                 let msg = format!("Failed to retrieve saldo: {err}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
 
                 Err(ErrorResponse::from(err))
@@ -344,25 +417,30 @@ impl SaldoServiceTrait for SaldoService {
     async fn get_saldo_users(
         &self,
         id: i32,
-    ) -> Result<ApiResponse<Option<Vec<SaldoResponse>>>, ErrorResponse> {
+        req: &PageRequest,
+    ) -> Result<ApiResponsePagination<Vec<SaldoResponse>>, ErrorResponse> {
         let method = Method::Get;
+        let req = req.bounded().map_err(ErrorResponse::from)?;
 
         let tracing_ctx = self.start_tracing(
             "GetSaldoUsers",
             vec![
                 KeyValue::new("component", "saldo"),
                 KeyValue::new("user_id", id.to_string()),
+                KeyValue::new("page", req.page.to_string()),
+                KeyValue::new("page_size", req.page_size.to_string()),
             ],
         );
 
         let mut request = Request::new(id);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let cache_key = format!("saldo_users:id={id}");
+        let cache_key = req.cache_key("saldo_users", id);
 
-        if let Some(cached) = self
-            .cache_store
-            .get_from_cache::<ApiResponse<Option<Vec<SaldoResponse>>>>(&cache_key)
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<ApiResponsePagination<Vec<SaldoResponse>>>(&cache_key)
         {
             info!("Found user saldo in cache");
 
@@ -372,71 +450,63 @@ impl SaldoServiceTrait for SaldoService {
             return Ok(cached);
         }
 
-        let user_result = self.user_repository.find_by_id(id).await;
+        let user_result = self
+            .user_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await;
 
         let _user = match user_result {
             Ok(user) => user,
             Err(_) => {
                 let msg = format!("User with id {id} not found");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
         };
 
-        let saldo_result = self.saldo_repository.find_by_users_id(id).await;
+        let saldo_result = cancellable(self.saldo_repository.find_by_users_id(
+            id,
+            req.page,
+            req.page_size,
+        ))
+        .with_context(tracing_ctx.cx.clone())
+        .await;
 
-        let saldo = match saldo_result {
-            Ok(s) => s,
+        let (saldo, total_items) = match saldo_result {
+            Ok(page) => page,
             Err(err) => {
                 let msg = format!("Failed to retrieve saldo for user {id}: {err}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                error!("{msg}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
         };
 
-        let saldo_responses = if saldo.is_empty() {
-            None
+        let message = if saldo.is_empty() {
+            format!("No saldo found for user with id {id}")
         } else {
-            Some(
-                saldo
-                    .into_iter()
-                    .map(SaldoResponse::from)
-                    .collect::<Vec<_>>(),
-            )
+            "Success".to_string()
         };
 
-        let response = if let Some(ref data) = saldo_responses {
-            let response = ApiResponse {
-                status: "success".to_string(),
-                data: Some(data.clone()),
-                message: "Success".to_string(),
-            };
+        let response: ApiResponsePagination<Vec<SaldoResponse>> = ApiResponsePagination {
+            status: "success".to_string(),
+            message,
+            data: saldo.into_iter().map(SaldoResponse::from).collect(),
+            pagination: Pagination::new(req.page, req.page_size, total_items),
+        };
 
+        if !response.data.is_empty() {
             self.cache_store
-                .set_to_cache(&cache_key, &response, Duration::from_secs(60 * 5));
+                .set_to_cache(&cache_key, &response, self.cache_store.ttl());
+        }
 
-            self.complete_tracing_success(
-                &tracing_ctx,
-                method,
-                "User saldo retrieved from database",
-            )
+        self.complete_tracing_success(&tracing_ctx, method, "User saldo retrieved from database")
             .await;
 
-            response
-        } else {
-            let response = ApiResponse {
-                status: "success".to_string(),
-                data: None,
-                message: format!("No saldo found for user with id {id}"),
-            };
-
-            self.complete_tracing_success(&tracing_ctx, method, "No saldo found")
-                .await;
-            response
-        };
-
         Ok(response)
     }
 
@@ -459,9 +529,10 @@ impl SaldoServiceTrait for SaldoService {
 
         let cache_key = format!("saldo_user:id={id}");
 
-        if let Some(cached) = self
-            .cache_store
-            .get_from_cache::<ApiResponse<Option<SaldoResponse>>>(&cache_key)
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<ApiResponse<Option<SaldoResponse>>>(&cache_key)
         {
             info!("Found saldo in cache for user_id: {id}");
 
@@ -471,23 +542,24 @@ impl SaldoServiceTrait for SaldoService {
             return Ok(cached);
         }
 
-        let user_result = self.user_repository.find_by_id(id).await;
-        let _user = match user_result {
-            Ok(user) => user,
-            Err(_) => {
+        // user dan saldo utama diambil sekaligus dengan satu query join
+        let saldo_opt = match self
+            .saldo_repository
+            .find_with_user_by_user_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(found)) => found.saldo.map(SaldoResponse::from),
+            Ok(None) => {
                 let msg = format!("User with id {id} not found");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
-        };
-
-        let saldo_result = self.saldo_repository.find_by_user_id(id).await;
-        let saldo_opt = match saldo_result {
-            Ok(s) => s.map(SaldoResponse::from),
             Err(err) => {
                 let msg = format!("Failed to retrieve saldo for user_id {id}: {err}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
@@ -502,7 +574,7 @@ impl SaldoServiceTrait for SaldoService {
                 };
 
                 self.cache_store
-                    .set_to_cache(&cache_key, &response, Duration::from_secs(60 * 5));
+                    .set_to_cache(&cache_key, &response, self.cache_store.ttl());
 
                 self.complete_tracing_success(
                     &tracing_ctx,
@@ -515,10 +587,11 @@ impl SaldoServiceTrait for SaldoService {
             }
             None => {
                 let msg = format!("No saldo found for user_id: {id}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
 
-                Err(ErrorResponse::from(AppError::NotFound(msg)))
+                Err(ErrorResponse::from(err))
             }
         }
     }
@@ -541,26 +614,45 @@ impl SaldoServiceTrait for SaldoService {
         let mut request = Request::new(input.user_id);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let _user = match self.user_repository.find_by_id(input.user_id).await {
-            Ok(user) => user,
-            Err(_) => {
+        match self
+            .user_repository
+            .exists_by_id(input.user_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) => {
                 let msg = format!("User with id {} not found", input.user_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
-        };
+            Err(err) => {
+                let msg = format!("Failed to check user {}: {err}", input.user_id);
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        }
 
-        let saldo = match self.saldo_repository.create(input).await {
+        let saldo = match self
+            .saldo_repository
+            .create(input)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(saldo) => saldo,
             Err(err) => {
                 let msg = format!("Failed to create saldo for user {}: {err}", input.user_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
         };
 
+        invalidate_account_summary(&self.cache_store, &[saldo.user_id]);
+
         let response = ApiResponse {
             status: "success".to_string(),
             message: "Saldo created successfully".to_string(),
@@ -589,42 +681,67 @@ impl SaldoServiceTrait for SaldoService {
             ],
         );
 
-        let _user = match self.user_repository.find_by_id(input.user_id).await {
-            Ok(user) => user,
-            Err(_) => {
+        match self
+            .user_repository
+            .exists_by_id(input.user_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) => {
                 let msg = format!("User with id {} not found", input.user_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
-        };
+            Err(err) => {
+                let msg = format!("Failed to check user {}: {err}", input.user_id);
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        }
 
-        let _existing_saldo = match self.saldo_repository.find_by_id(input.saldo_id).await {
-            Ok(Some(saldo)) => saldo,
-            Ok(None) => {
+        match self
+            .saldo_repository
+            .exists_by_id(input.saldo_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(true) => {}
+            Ok(false) => {
                 let msg = format!("Saldo with id {} not found", input.saldo_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
             Err(err) => {
-                let msg = format!("Error finding saldo with id {}: {}", input.saldo_id, err);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let msg = format!("Failed to check saldo {}: {err}", input.saldo_id);
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
-        };
+        }
 
-        let updated_saldo = match self.saldo_repository.update(input).await {
+        let updated_saldo = match self
+            .saldo_repository
+            .update(input)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(saldo) => saldo,
             Err(err) => {
                 let msg = format!("Failed to update saldo {}: {}", input.saldo_id, err);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
         };
 
+        invalidate_account_summary(&self.cache_store, &[updated_saldo.user_id]);
+
         let response = ApiResponse {
             status: "success".to_string(),
             message: "Saldo updated successfully".to_string(),
@@ -648,44 +765,61 @@ impl SaldoServiceTrait for SaldoService {
             ],
         );
 
-        let user = match self.user_repository.find_by_id(id).await {
+        let user = match self
+            .user_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(Some(user)) => user,
             Ok(None) => {
                 let msg = format!("User with id {id} not found");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
             Err(err) => {
                 let msg = format!("Failed to fetch user {id}: {err}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
         };
 
-        let existing_saldo = match self.saldo_repository.find_by_user_id(user.user_id).await {
+        let existing_saldo = match self
+            .saldo_repository
+            .find_by_user_id(user.user_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(Some(saldo)) => saldo,
             Ok(None) => {
                 let msg = format!("Saldo with user_id {} not found", user.user_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
             Err(err) => {
                 let msg = format!("Failed to find saldo for user_id {}: {err}", user.user_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
         };
 
-        if let Err(err) = self.saldo_repository.delete(existing_saldo.saldo_id).await {
+        if let Err(err) = self
+            .saldo_repository
+            .delete(existing_saldo.saldo_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             let msg = format!(
                 "Failed to delete saldo id {}: {}",
                 existing_saldo.saldo_id, err
             );
-            self.complete_tracing_error(&tracing_ctx, method, &msg)
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                 .await;
             return Err(ErrorResponse::from(err));
         }
@@ -693,6 +827,7 @@ impl SaldoServiceTrait for SaldoService {
         let cache_key = format!("saldo_user:id={}", user.user_id);
 
         self.cache_store.delete_from_cache(&cache_key);
+        invalidate_account_summary(&self.cache_store, &[user.user_id]);
 
         info!("Saldo deleted successfully for user_id: {}", user.user_id);
 
@@ -705,4 +840,625 @@ impl SaldoServiceTrait for SaldoService {
             data: (),
         })
     }
+
+    async fn get_top_saldos(
+        &self,
+        req: &TopSaldosRequest,
+    ) -> Result<ApiResponse<Vec<SaldoRankingResponse>>, ErrorResponse> {
+        let method = Method::Get;
+
+        let tracing_ctx = self.start_tracing(
+            "GetTopSaldos",
+            vec![
+                KeyValue::new("component", "saldo"),
+                KeyValue::new("limit", req.limit as i64),
+            ],
+        );
+
+        if let Err(errors) = req.validate() {
+            let msg = format!("Invalid top saldos request: {errors}");
+            error!("{msg}");
+            let err = AppError::ValidationError(errors);
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        let cache_key = format!("saldos:top:limit={}", req.limit);
+
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<Vec<SaldoRankingResponse>>(&cache_key)
+        {
+            info!("Found top {} saldos in cache", req.limit);
+
+            self.complete_tracing_success(&tracing_ctx, method, "Top saldos retrieved from cache")
+                .await;
+
+            return Ok(ApiResponse {
+                status: "success".to_string(),
+                message: "Top saldos retrieved successfully (from cache)".to_string(),
+                data: cached,
+            });
+        }
+
+        match cancellable(self.saldo_repository.top_by_balance(req.limit))
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(rankings) => {
+                let data = rankings
+                    .into_iter()
+                    .enumerate()
+                    .map(|(index, ranking)| SaldoRankingResponse::new(index as i32 + 1, ranking))
+                    .collect::<Vec<_>>();
+
+                // leaderboard boleh sedikit tertinggal, TTL-nya sama dengan statistik
+                self.cache_store.set_to_cache(
+                    &cache_key,
+                    &data,
+                    self.cache_store.ttl_config().stats,
+                );
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Top saldos retrieved from database",
+                )
+                .await;
+
+                Ok(ApiResponse {
+                    status: "success".to_string(),
+                    message: "Top saldos retrieved successfully".to_string(),
+                    data,
+                })
+            }
+            Err(err) => {
+                let msg = format!("Failed to retrieve top saldos: {err}");
+                error!("{msg}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
+        }
+    }
+
+    async fn adjust_balance(
+        &self,
+        actor_id: i32,
+        input: &AdjustBalanceRequest,
+    ) -> Result<ApiResponse<SaldoResponse>, ErrorResponse> {
+        let method = Method::Post;
+
+        let tracing_ctx = self.start_tracing(
+            "AdjustBalance",
+            vec![
+                KeyValue::new("component", "saldo"),
+                KeyValue::new("user_id", input.user_id.to_string()),
+                KeyValue::new("actor_id", actor_id.to_string()),
+                KeyValue::new("delta", input.delta.to_string()),
+            ],
+        );
+
+        if let Err(errors) = input.validate() {
+            let msg = format!("Invalid balance adjustment: {errors}");
+            error!("{msg}");
+            let err = AppError::ValidationError(errors);
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        let saldo = match self
+            .saldo_repository
+            .adjust_balance(actor_id, input, LedgerEntryType::ManualAdjustment)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(saldo) => saldo,
+            Err(err) => {
+                let msg = format!(
+                    "Failed to adjust balance of user {} by {}: {err}",
+                    input.user_id, input.delta
+                );
+                error!("{msg}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        info!(
+            "🛠️ Balance of user {} adjusted by {} by admin {actor_id} | Reason: {}",
+            input.user_id,
+            input.delta,
+            input.reason.trim()
+        );
+
+        self.invalidate_saldo_cache(&saldo);
+
+        self.complete_tracing_success(&tracing_ctx, method, "Balance adjusted successfully")
+            .await;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Balance adjusted successfully".to_string(),
+            data: SaldoResponse::from(saldo),
+        })
+    }
+
+    async fn reconcile_balance(
+        &self,
+        actor_id: i32,
+        input: &ReconcileBalanceRequest,
+    ) -> Result<ApiResponse<Vec<BalanceReconciliationResponse>>, ErrorResponse> {
+        let method = Method::Post;
+
+        let tracing_ctx = self.start_tracing(
+            "ReconcileBalance",
+            vec![
+                KeyValue::new("component", "saldo"),
+                KeyValue::new(
+                    "user_id",
+                    input
+                        .user_id
+                        .map_or_else(|| "all".to_string(), |id| id.to_string()),
+                ),
+                KeyValue::new("actor_id", actor_id.to_string()),
+                KeyValue::new("fix", input.fix),
+            ],
+        );
+
+        if let Err(errors) = input.validate() {
+            let msg = format!("Invalid reconciliation request: {errors}");
+            error!("{msg}");
+            let err = AppError::ValidationError(errors);
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        // mode satu user melaporkan hasilnya walau seimbang, mode batch hanya yang selisih
+        let reconciliations = match input.user_id {
+            Some(user_id) => match self
+                .saldo_repository
+                .find_reconciliation(user_id)
+                .with_context(tracing_ctx.cx.clone())
+                .await
+            {
+                Ok(Some(reconciliation)) => vec![reconciliation],
+                Ok(None) => {
+                    let msg = format!("User with id {user_id} not found");
+                    let err = AppError::NotFound(msg.clone());
+                    self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+                Err(err) => {
+                    let msg = format!("Failed to reconcile balance of user {user_id}: {err}");
+                    error!("{msg}");
+                    self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+            },
+            None => match cancellable(self.saldo_repository.find_balance_mismatches())
+                .with_context(tracing_ctx.cx.clone())
+                .await
+            {
+                Ok(mismatches) => mismatches,
+                Err(err) => {
+                    let msg = format!("Failed to scan balance mismatches: {err}");
+                    error!("{msg}");
+                    self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+            },
+        };
+
+        let mut data = Vec::with_capacity(reconciliations.len());
+        let mut mismatched = 0;
+        let mut corrected = 0;
+
+        for reconciliation in &reconciliations {
+            if reconciliation.is_balanced() {
+                data.push(BalanceReconciliationResponse::new(reconciliation, false));
+                continue;
+            }
+
+            mismatched += 1;
+            warn!(
+                "⚖️ Balance mismatch for user {}: stored={}, expected={}, difference={}",
+                reconciliation.user_id,
+                reconciliation.stored_balance,
+                reconciliation.expected_balance,
+                reconciliation.difference()
+            );
+
+            if !input.fix {
+                data.push(BalanceReconciliationResponse::new(reconciliation, false));
+                continue;
+            }
+
+            // satu koreksi yang gagal tidak menghentikan pemindaian batch
+            match self
+                .correct_balance(actor_id, reconciliation)
+                .with_context(tracing_ctx.cx.clone())
+                .await
+            {
+                Ok(_) => {
+                    info!(
+                        "🛠️ Balance of user {} corrected by {} by admin {actor_id}",
+                        reconciliation.user_id,
+                        reconciliation.difference()
+                    );
+                    corrected += 1;
+                    data.push(BalanceReconciliationResponse::new(reconciliation, true));
+                }
+                Err(err) if input.user_id.is_some() => {
+                    let msg = format!(
+                        "Failed to correct balance of user {}: {err}",
+                        reconciliation.user_id
+                    );
+                    error!("{msg}");
+                    self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+                Err(err) => {
+                    error!(
+                        "❌ Failed to correct balance of user {}: {err}",
+                        reconciliation.user_id
+                    );
+                    data.push(BalanceReconciliationResponse::new(reconciliation, false));
+                }
+            }
+        }
+
+        let message = match (mismatched, input.fix) {
+            (0, _) => "No balance mismatch found".to_string(),
+            (_, false) => format!("Found {mismatched} balance mismatch(es)"),
+            (_, true) => format!("Corrected {corrected} of {mismatched} balance mismatch(es)"),
+        };
+
+        self.complete_tracing_success(&tracing_ctx, method, &message)
+            .await;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message,
+            data,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::request::{
+            CreateTransferRequest, MAX_TOP_SALDOS, default_page_size, max_page_size,
+        },
+        model::transfer::TransferStatus,
+        repository::memory::{InMemoryRepositories, offline_cache_store},
+    };
+    use axum::http::StatusCode;
+
+    const ADMIN_ID: i32 = 99;
+
+    async fn saldo_service(repos: &InMemoryRepositories) -> SaldoService {
+        SaldoService::new(
+            repos.user.clone(),
+            repos.saldo.clone(),
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+            offline_cache_store(),
+        )
+        .await
+    }
+
+    fn adjustment(user_id: i32, delta: i32) -> AdjustBalanceRequest {
+        AdjustBalanceRequest {
+            user_id,
+            delta,
+            reason: "Correcting a failed settlement".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn adjustments_apply_delta_and_record_the_actor() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("adjust@example.com", 50_000).await;
+        let service = saldo_service(&repos).await;
+
+        let credited = service
+            .adjust_balance(ADMIN_ID, &adjustment(user.user_id, 20_000))
+            .await
+            .unwrap();
+        assert_eq!(credited.data.total_balance, 70_000);
+
+        let debited = service
+            .adjust_balance(ADMIN_ID, &adjustment(user.user_id, -30_000))
+            .await
+            .unwrap();
+        assert_eq!(debited.data.total_balance, 40_000);
+
+        let ledger = repos.database.tables().ledger.clone();
+        assert_eq!(
+            ledger.iter().map(|entry| entry.amount).collect::<Vec<_>>(),
+            [20_000, -30_000]
+        );
+        assert!(ledger.iter().all(|entry| {
+            entry.entry_type == LedgerEntryType::ManualAdjustment.as_str()
+                && entry.actor_id == Some(ADMIN_ID)
+                && entry.reason.as_deref() == Some("Correcting a failed settlement")
+        }));
+    }
+
+    #[tokio::test]
+    async fn over_debit_is_rejected_without_a_ledger_entry() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("adjust@example.com", 50_000).await;
+        let service = saldo_service(&repos).await;
+
+        let err = service
+            .adjust_balance(ADMIN_ID, &adjustment(user.user_id, -60_000))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.message, "Insufficient balance");
+        assert_eq!(repos.balance_of(user.user_id).await, 50_000);
+        assert!(repos.database.tables().ledger.is_empty());
+    }
+
+    async fn top(service: &SaldoService, limit: i32) -> Result<Vec<(i32, i32)>, ErrorResponse> {
+        let ranking = service.get_top_saldos(&TopSaldosRequest { limit }).await?;
+
+        Ok(ranking
+            .data
+            .iter()
+            .map(|saldo| (saldo.user_id, saldo.total_balance))
+            .collect())
+    }
+
+    #[tokio::test]
+    async fn top_saldos_are_ordered_with_ties_broken_by_user_id() {
+        let repos = InMemoryRepositories::new();
+        let mut ids = Vec::new();
+        for (email, balance) in [
+            ("a@example.com", 300_000),
+            ("b@example.com", 100_000),
+            ("c@example.com", 300_000),
+            ("d@example.com", 200_000),
+        ] {
+            ids.push(repos.seed_user(email, balance).await.user_id);
+        }
+        let service = saldo_service(&repos).await;
+
+        let ranking = top(&service, 10).await.unwrap();
+        assert_eq!(
+            ranking,
+            [
+                (ids[0], 300_000),
+                (ids[2], 300_000),
+                (ids[3], 200_000),
+                (ids[1], 100_000)
+            ]
+        );
+
+        let ranking = service
+            .get_top_saldos(&TopSaldosRequest { limit: 2 })
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(ranking.len(), 2);
+        assert_eq!(ranking[0].rank, 1);
+        assert_eq!(ranking[0].name, "Test User");
+    }
+
+    #[tokio::test]
+    async fn top_saldos_limit_outside_the_cap_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        repos.seed_user("a@example.com", 100_000).await;
+        let service = saldo_service(&repos).await;
+
+        for limit in [0, MAX_TOP_SALDOS + 1] {
+            let err = top(&service, limit).await.unwrap_err();
+            assert_eq!(err.code, StatusCode::UNPROCESSABLE_ENTITY, "limit {limit}");
+        }
+        assert_eq!(top(&service, MAX_TOP_SALDOS).await.unwrap().len(), 1);
+    }
+
+    fn find_all(page: i32, page_size: i32) -> FindAllSaldoRequest {
+        FindAllSaldoRequest {
+            page,
+            page_size,
+            search: String::new(),
+        }
+    }
+
+    #[tokio::test]
+    async fn oversized_page_size_is_capped_and_reported() {
+        let repos = InMemoryRepositories::new();
+        for email in ["a@example.com", "b@example.com", "c@example.com"] {
+            repos.seed_user(email, 100_000).await;
+        }
+        let service = saldo_service(&repos).await;
+
+        let saldos = service.get_saldos(&find_all(1, 1_000_000)).await.unwrap();
+
+        assert_eq!(saldos.pagination.page_size, max_page_size());
+        assert_eq!(saldos.pagination.total_items, 3);
+        assert_eq!(saldos.data.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn negative_page_is_rejected_and_zero_means_the_first_page() {
+        let repos = InMemoryRepositories::new();
+        repos.seed_user("a@example.com", 100_000).await;
+        let service = saldo_service(&repos).await;
+
+        let err = service.get_saldos(&find_all(-1, 10)).await.unwrap_err();
+        assert_eq!(err.code, StatusCode::BAD_REQUEST);
+
+        let saldos = service.get_saldos(&find_all(0, 0)).await.unwrap();
+        assert_eq!(saldos.pagination.page, 1);
+        assert_eq!(saldos.pagination.page_size, default_page_size());
+    }
+
+    fn reconcile(user_id: Option<i32>, fix: bool) -> ReconcileBalanceRequest {
+        ReconcileBalanceRequest { user_id, fix }
+    }
+
+    // seed_user menulis saldo tanpa riwayat, jadi saldo awal selain 0 adalah saldo yang rusak
+    async fn seed_balanced_and_corrupted(repos: &InMemoryRepositories) -> (i32, i32) {
+        let balanced = repos.seed_user("balanced@example.com", 0).await.user_id;
+        let corrupted = repos
+            .seed_user("corrupted@example.com", 25_000)
+            .await
+            .user_id;
+
+        let service = saldo_service(repos).await;
+        service
+            .adjust_balance(ADMIN_ID, &adjustment(balanced, 20_000))
+            .await
+            .unwrap();
+
+        (balanced, corrupted)
+    }
+
+    #[tokio::test]
+    async fn corrupted_balance_is_reported_without_being_changed() {
+        let repos = InMemoryRepositories::new();
+        let (balanced, corrupted) = seed_balanced_and_corrupted(&repos).await;
+        let service = saldo_service(&repos).await;
+
+        let single = service
+            .reconcile_balance(ADMIN_ID, &reconcile(Some(balanced), false))
+            .await
+            .unwrap();
+        assert_eq!(single.message, "No balance mismatch found");
+        assert_eq!(single.data.len(), 1);
+        assert_eq!(single.data[0].difference, 0);
+
+        let batch = service
+            .reconcile_balance(ADMIN_ID, &reconcile(None, false))
+            .await
+            .unwrap();
+        assert_eq!(batch.message, "Found 1 balance mismatch(es)");
+        assert_eq!(batch.data.len(), 1);
+        let mismatch = &batch.data[0];
+        assert_eq!(mismatch.user_id, corrupted);
+        assert_eq!(
+            (mismatch.stored_balance, mismatch.expected_balance),
+            (25_000, 0)
+        );
+        assert_eq!(mismatch.difference, -25_000);
+        assert!(!mismatch.corrected);
+
+        assert_eq!(repos.balance_of(corrupted).await, 25_000);
+        assert_eq!(repos.database.tables().ledger.len(), 1);
+    }
+
+    #[tokio::test]
+    async fn fix_corrects_the_mismatch_with_an_audited_ledger_entry() {
+        let repos = InMemoryRepositories::new();
+        let (balanced, corrupted) = seed_balanced_and_corrupted(&repos).await;
+        let service = saldo_service(&repos).await;
+
+        let fixed = service
+            .reconcile_balance(ADMIN_ID, &reconcile(None, true))
+            .await
+            .unwrap();
+        assert_eq!(fixed.message, "Corrected 1 of 1 balance mismatch(es)");
+        assert_eq!(fixed.data.len(), 1);
+        assert!(fixed.data[0].corrected);
+
+        assert_eq!(repos.balance_of(corrupted).await, 0);
+        assert_eq!(repos.balance_of(balanced).await, 20_000);
+
+        let ledger = repos.database.tables().ledger.clone();
+        let entry = ledger.last().unwrap();
+        assert_eq!(entry.entry_type, LedgerEntryType::Reconciliation.as_str());
+        assert_eq!(
+            (entry.user_id, entry.amount, entry.actor_id),
+            (corrupted, -25_000, Some(ADMIN_ID))
+        );
+        assert_eq!(
+            entry.reason.as_deref(),
+            Some("Reconciliation: stored 25000 expected 0")
+        );
+
+        // koreksi sendiri tidak boleh terhitung sebagai selisih baru
+        let rerun = service
+            .reconcile_balance(ADMIN_ID, &reconcile(Some(corrupted), false))
+            .await
+            .unwrap();
+        assert_eq!(rerun.message, "No balance mismatch found");
+        assert_eq!(rerun.data[0].difference, 0);
+    }
+
+    #[tokio::test]
+    async fn reconciling_an_unknown_user_is_not_found() {
+        let repos = InMemoryRepositories::new();
+        let service = saldo_service(&repos).await;
+
+        let err = service
+            .reconcile_balance(ADMIN_ID, &reconcile(Some(404), true))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::NOT_FOUND);
+        assert!(repos.database.tables().ledger.is_empty());
+    }
+
+    #[tokio::test]
+    async fn held_and_cancelled_transfers_do_not_count_as_moved_money() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 0).await.user_id;
+        let receiver = repos.seed_user("receiver@example.com", 0).await.user_id;
+        let service = saldo_service(&repos).await;
+        service
+            .adjust_balance(ADMIN_ID, &adjustment(sender, 100_000))
+            .await
+            .unwrap();
+
+        let transfer = |amount: i32| CreateTransferRequest {
+            transfer_from: sender,
+            transfer_to: receiver,
+            transfer_amount: amount,
+            note: None,
+            dry_run: false,
+            from_label: None,
+            to_label: None,
+        };
+        repos
+            .transfer
+            .create_with_settlement(&transfer(30_000), TransferStatus::Completed)
+            .await
+            .unwrap();
+        repos
+            .transfer
+            .create_with_settlement(&transfer(20_000), TransferStatus::NeedsReview)
+            .await
+            .unwrap();
+        let cancelled = repos
+            .transfer
+            .create_with_settlement(&transfer(10_000), TransferStatus::NeedsReview)
+            .await
+            .unwrap();
+        repos.transfer.reverse(cancelled.transfer_id).await.unwrap();
+
+        let result = service
+            .reconcile_balance(ADMIN_ID, &reconcile(None, true))
+            .await
+            .unwrap();
+
+        assert_eq!(result.message, "No balance mismatch found");
+        assert!(result.data.is_empty());
+        assert_eq!(repos.balance_of(sender).await, 70_000);
+        assert_eq!(repos.balance_of(receiver).await, 30_000);
+    }
 }
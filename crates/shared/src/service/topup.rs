@@ -1,37 +1,41 @@
 use crate::{
-    abstract_trait::{
-        DynSaldoRepository, DynTopupRepository, DynUserRepository, TopupServiceTrait,
-    },
+    abstract_trait::{DynTopupRepository, DynUserRepository, TopupServiceTrait},
     cache::CacheStore,
     domain::{
         request::{
-            CreateSaldoRequest, CreateTopupRequest, FindAllTopupRequest, UpdateSaldoBalance,
-            UpdateTopupAmount, UpdateTopupRequest,
+            CreateTopupRequest, FindAllTopupRequest, PageRequest, TopupStatsRequest,
+            UpdateTopupRequest,
         },
         response::{
-            ApiResponse, ApiResponsePagination, ErrorResponse, pagination::Pagination,
-            topup::TopupResponse,
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            pagination::Pagination,
+            topup::{TopupMethodStatsResponse, TopupResponse},
         },
     },
-    utils::{AppError, MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+    model::topup::Topup,
+    service::account::invalidate_account_summary,
+    utils::{
+        AppError, MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext,
+        cancellable,
+    },
 };
 use async_trait::async_trait;
 use opentelemetry::{
     Context, KeyValue,
+    context::FutureExt,
     global::{self, BoxedTracer},
     trace::{Span, SpanKind, TraceContextExt, Tracer},
 };
 use prometheus_client::registry::Registry;
-use std::{sync::Arc, time::Duration};
-use tokio::{sync::Mutex, time::Instant};
+use std::sync::Arc;
+use tokio::time::Instant;
 use tonic::Request;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 pub struct TopupService {
     topup_repository: DynTopupRepository,
-    saldo_repository: DynSaldoRepository,
     user_repository: DynUserRepository,
-    metrics: Arc<Mutex<Metrics>>,
+    metrics: Arc<Metrics>,
     cache_store: Arc<CacheStore>,
 }
 
@@ -39,7 +43,6 @@ impl std::fmt::Debug for TopupService {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("TopupService")
             .field("topup_repository", &"DynTopupRepository")
-            .field("saldo_repository", &"DynSaldoRepository")
             .field("user_repository", &"DynUserRepository")
             .finish()
     }
@@ -48,26 +51,29 @@ impl std::fmt::Debug for TopupService {
 impl TopupService {
     pub async fn new(
         topup_repository: DynTopupRepository,
-        saldo_repository: DynSaldoRepository,
         user_repository: DynUserRepository,
-        metrics: Arc<Mutex<Metrics>>,
+        metrics: Arc<Metrics>,
         registry: &mut Registry,
         cache_store: Arc<CacheStore>,
     ) -> Self {
         registry.register(
             "category_service_request_counter",
             "Total number of requests to the CategoryService",
-            metrics.lock().await.request_counter.clone(),
+            metrics.request_counter.clone(),
         );
         registry.register(
             "category_service_request_duration",
             "Histogram of request durations for the CategoryService",
-            metrics.lock().await.request_duration.clone(),
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "category_service_error_counter",
+            "Total number of failed requests to the CategoryService by error kind",
+            metrics.error_counter.clone(),
         );
 
         Self {
             topup_repository,
-            saldo_repository,
             user_repository,
             metrics,
             cache_store,
@@ -104,7 +110,11 @@ impl TopupService {
         );
 
         let cx = Context::current_with_span(span);
-        TracingContext { cx, start_time }
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
     }
 
     async fn complete_tracing_success(
@@ -121,8 +131,12 @@ impl TopupService {
         &self,
         tracing_ctx: &TracingContext,
         method: Method,
+        error_kind: &str,
         error_message: &str,
     ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
         self.complete_tracing_internal(tracing_ctx, method, false, error_message)
             .await;
     }
@@ -157,10 +171,152 @@ impl TopupService {
             error!("Operation failed: {message}");
         }
 
-        self.metrics.lock().await.record(method, status, elapsed);
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
 
         tracing_ctx.cx.span().end();
     }
+
+    // status topup dan saldo user berubah, buang cache yang terkait
+    fn invalidate_topup_cache(&self, topup: &Topup) {
+        for key in [
+            format!("topup:id={}", topup.topup_id),
+            format!("topup_user:id={}", topup.user_id),
+            PageRequest::default().cache_key("topup_users", topup.user_id),
+            format!("saldo_user:id={}", topup.user_id),
+            PageRequest::default().cache_key("saldo_users", topup.user_id),
+        ] {
+            self.cache_store.delete_from_cache(&key);
+        }
+
+        invalidate_account_summary(&self.cache_store, &[topup.user_id]);
+    }
+
+    // akun yang dibekukan tidak boleh berubah saldonya lewat topup, sama seperti transfer dan withdraw
+    async fn ensure_account_not_frozen(
+        &self,
+        user_id: i32,
+        tracing_ctx: &TracingContext,
+        method: Method,
+    ) -> Result<(), ErrorResponse> {
+        let user = match self
+            .user_repository
+            .find_by_id(user_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(user)) => user,
+            Ok(None) => {
+                let msg = format!("User with id {user_id} not found");
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to fetch user {user_id}: {err}");
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        if user.is_frozen() {
+            let msg = format!("Account {user_id} is frozen");
+            warn!("{msg}");
+            let err = AppError::Custom("Account is frozen".to_string());
+            self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        Ok(())
+    }
+
+    // topup yang akan dikonfirmasi atau direfund, hanya untuk memeriksa status akun pemiliknya
+    async fn find_topup_owner(
+        &self,
+        id: i32,
+        tracing_ctx: &TracingContext,
+        method: Method,
+    ) -> Result<i32, ErrorResponse> {
+        match self
+            .topup_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(topup)) => Ok(topup.user_id),
+            Ok(None) => {
+                let msg = format!("Topup with id {id} not found");
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
+            Err(err) => {
+                let msg = format!("Failed to fetch topup {id}: {err}");
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
+        }
+    }
+
+    // dijalankan selama memegang lock saldo user; topup dan selisih saldonya disimpan dalam
+    // satu transaksi sehingga tidak ada rollback manual kalau saldo gagal diperbarui
+    async fn update_topup_locked(
+        &self,
+        input: &UpdateTopupRequest,
+        tracing_ctx: &TracingContext,
+        method: Method,
+    ) -> Result<ApiResponse<TopupResponse>, ErrorResponse> {
+        // topup milik user lain diperlakukan sama dengan topup yang tidak ada
+        match self.topup_repository.find_by_id(input.topup_id).await {
+            Ok(Some(topup)) if topup.user_id == input.user_id => {}
+            Ok(_) => {
+                let msg = format!("Topup with id {} not found", input.topup_id);
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to fetch topup {}: {}", input.topup_id, err);
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        match self.topup_repository.update_with_settlement(input).await {
+            Ok(topup) => {
+                self.invalidate_topup_cache(&topup);
+
+                info!(
+                    "Topup {} updated for user {}, amount {}",
+                    topup.topup_id, input.user_id, topup.topup_amount
+                );
+
+                self.complete_tracing_success(tracing_ctx, method, "Topup updated successfully")
+                    .await;
+                Ok(ApiResponse {
+                    status: "success".to_string(),
+                    message: "Topup updated successfully".to_string(),
+                    data: TopupResponse::from(topup),
+                })
+            }
+            Err(err) => {
+                let msg = format!(
+                    "Failed to update topup {} for user {}: {}",
+                    input.topup_id, input.user_id, err
+                );
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -171,8 +327,9 @@ impl TopupServiceTrait for TopupService {
     ) -> Result<ApiResponsePagination<Vec<TopupResponse>>, ErrorResponse> {
         let method = Method::Get;
 
-        let page = if req.page > 0 { req.page } else { 1 };
-        let page_size = if req.page_size > 0 { req.page_size } else { 10 };
+        let PageRequest { page, page_size } = PageRequest::new(req.page, req.page_size)
+            .bounded()
+            .map_err(ErrorResponse::from)?;
         let search = if req.search.is_empty() {
             None
         } else {
@@ -202,9 +359,10 @@ impl TopupServiceTrait for TopupService {
             search.clone().unwrap_or_default()
         );
 
-        if let Some(cached) = self
-            .cache_store
-            .get_from_cache::<ApiResponsePagination<Vec<TopupResponse>>>(&cache_key)
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<ApiResponsePagination<Vec<TopupResponse>>>(&cache_key)
         {
             info!("Found topups in cache");
 
@@ -214,13 +372,11 @@ impl TopupServiceTrait for TopupService {
             return Ok(cached);
         }
 
-        match self
-            .topup_repository
-            .find_all(page, page_size, search)
+        match cancellable(self.topup_repository.find_all(page, page_size, search))
+            .with_context(tracing_ctx.cx.clone())
             .await
         {
             Ok((topups, total_items)) => {
-                let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
                 let topup_responses = topups
                     .into_iter()
                     .map(TopupResponse::from)
@@ -230,16 +386,11 @@ impl TopupServiceTrait for TopupService {
                     status: "success".to_string(),
                     message: "topups retrieved successfully".to_string(),
                     data: topup_responses.clone(),
-                    pagination: Pagination {
-                        page,
-                        page_size,
-                        total_items,
-                        total_pages,
-                    },
+                    pagination: Pagination::new(page, page_size, total_items),
                 };
 
                 self.cache_store
-                    .set_to_cache(&cache_key, &response, Duration::from_secs(60 * 5));
+                    .set_to_cache(&cache_key, &response, self.cache_store.ttl());
 
                 self.complete_tracing_success(
                     &tracing_ctx,
@@ -255,6 +406,7 @@ impl TopupServiceTrait for TopupService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    err.kind(),
                     &format!("Failed to retrieve topups: {err}"),
                 )
                 .await;
@@ -280,7 +432,9 @@ impl TopupServiceTrait for TopupService {
 
         let cache_key = format!("topup:id={id}");
 
-        if let Some(cached) = self.cache_store.get_from_cache::<TopupResponse>(&cache_key) {
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self.cache_store.get_from_cache::<TopupResponse>(&cache_key)
+        {
             info!("Topup with id {id} found in cache");
 
             self.complete_tracing_success(&tracing_ctx, method, "Topup retrieved from cache")
@@ -293,7 +447,11 @@ impl TopupServiceTrait for TopupService {
             });
         }
 
-        let topup = self.topup_repository.find_by_id(id).await;
+        let topup = self
+            .topup_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await;
 
         match topup {
             Ok(Some(topup)) => {
@@ -304,7 +462,7 @@ impl TopupServiceTrait for TopupService {
                 };
 
                 self.cache_store
-                    .set_to_cache(&cache_key, &response, Duration::from_secs(60 * 5));
+                    .set_to_cache(&cache_key, &response, self.cache_store.ttl());
 
                 info!("Successfully retrieved topup with id {id}");
 
@@ -319,16 +477,74 @@ impl TopupServiceTrait for TopupService {
             }
             Ok(None) => {
                 let msg = format!("Topup with id {id} not found");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
 
                 error!("{msg}");
 
-                Err(ErrorResponse::from(AppError::NotFound(msg)))
+                Err(ErrorResponse::from(err))
             }
             Err(err) => {
                 let msg = format!("Error fetching topup with id {id}: {err}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+
+                Err(err.into())
+            }
+        }
+    }
+
+    async fn get_topup_by_no(
+        &self,
+        topup_no: &str,
+    ) -> Result<ApiResponse<TopupResponse>, ErrorResponse> {
+        let method = Method::Get;
+
+        let tracing_ctx = self.start_tracing(
+            "GetTopupByNo",
+            vec![
+                KeyValue::new("component", "topup"),
+                KeyValue::new("topup_no", topup_no.to_string()),
+            ],
+        );
+
+        // tidak di-cache: dipakai rekonsiliasi sehingga status harus selalu terbaru
+        match self
+            .topup_repository
+            .find_by_topup_no(topup_no)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(topup)) => {
+                info!("Successfully retrieved topup with topup_no {topup_no}");
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Topup retrieved from database",
+                )
+                .await;
+
+                Ok(ApiResponse {
+                    status: "success".to_string(),
+                    message: "Topup retrieved successfully".to_string(),
+                    data: TopupResponse::from(topup),
+                })
+            }
+            Ok(None) => {
+                let msg = format!("Topup with topup_no {topup_no} not found");
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+
+                error!("{msg}");
+
+                Err(ErrorResponse::from(err))
+            }
+            Err(err) => {
+                let msg = format!("Error fetching topup with topup_no {topup_no}: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
 
                 Err(err.into())
@@ -339,26 +555,30 @@ impl TopupServiceTrait for TopupService {
     async fn get_topup_users(
         &self,
         id: i32,
-    ) -> Result<ApiResponse<Option<Vec<TopupResponse>>>, ErrorResponse> {
+        req: &PageRequest,
+    ) -> Result<ApiResponsePagination<Vec<TopupResponse>>, ErrorResponse> {
         let method = Method::Get;
+        let req = req.bounded().map_err(ErrorResponse::from)?;
 
         let tracing_ctx = self.start_tracing(
             "GetTopupUsers",
             vec![
                 KeyValue::new("component", "topup"),
                 KeyValue::new("user_id", id.to_string()),
+                KeyValue::new("page", req.page.to_string()),
+                KeyValue::new("page_size", req.page_size.to_string()),
             ],
         );
 
         let mut request = Request::new(id);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let cache_key = format!("topup_users:id={id}");
+        let cache_key = req.cache_key("topup_users", id);
 
-        // Coba ambil dari cache
-        if let Some(cached) = self
-            .cache_store
-            .get_from_cache::<ApiResponse<Option<Vec<TopupResponse>>>>(&cache_key)
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<ApiResponsePagination<Vec<TopupResponse>>>(&cache_key)
         {
             info!("Found user topups in cache");
 
@@ -368,72 +588,63 @@ impl TopupServiceTrait for TopupService {
             return Ok(cached);
         }
 
-        let user_result = self.user_repository.find_by_id(id).await;
+        let user_result = self
+            .user_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await;
 
         let _user = match user_result {
             Ok(user) => user,
             Err(_) => {
                 let msg = format!("User with id {id} not found");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
         };
 
-        let topup_result = self.topup_repository.find_by_users(id).await;
+        let topups_result = cancellable(self.topup_repository.find_by_users(
+            id,
+            req.page,
+            req.page_size,
+        ))
+        .with_context(tracing_ctx.cx.clone())
+        .await;
 
-        let topups = match topup_result {
-            Ok(t) => t,
+        let (topups, total_items) = match topups_result {
+            Ok(page) => page,
             Err(err) => {
-                let msg = format!("Failed to retrieve topups for user {id}: {err}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let msg = format!("Failed to retrieve topup for user {id}: {err}");
+                error!("{msg}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
         };
 
-        let topup_responses = if topups.is_empty() {
-            None
+        let message = if topups.is_empty() {
+            format!("No topup found for user with id {id}")
         } else {
-            Some(
-                topups
-                    .into_iter()
-                    .map(TopupResponse::from)
-                    .collect::<Vec<_>>(),
-            )
+            "Success".to_string()
         };
 
-        let response = if let Some(ref data) = topup_responses {
-            let response = ApiResponse {
-                status: "success".to_string(),
-                message: "Success".to_string(),
-                data: Some(data.clone()),
-            };
+        let response: ApiResponsePagination<Vec<TopupResponse>> = ApiResponsePagination {
+            status: "success".to_string(),
+            message,
+            data: topups.into_iter().map(TopupResponse::from).collect(),
+            pagination: Pagination::new(req.page, req.page_size, total_items),
+        };
 
+        if !response.data.is_empty() {
             self.cache_store
-                .set_to_cache(&cache_key, &response, Duration::from_secs(60 * 5));
+                .set_to_cache(&cache_key, &response, self.cache_store.ttl());
+        }
 
-            self.complete_tracing_success(
-                &tracing_ctx,
-                method,
-                "User topups retrieved from database",
-            )
+        self.complete_tracing_success(&tracing_ctx, method, "User topups retrieved from database")
             .await;
 
-            response
-        } else {
-            let response = ApiResponse {
-                status: "success".to_string(),
-                message: format!("No topup found for user with id {id}"),
-                data: None,
-            };
-
-            self.complete_tracing_success(&tracing_ctx, method, "No topup found")
-                .await;
-
-            response
-        };
-
         Ok(response)
     }
 
@@ -456,9 +667,10 @@ impl TopupServiceTrait for TopupService {
 
         let cache_key = format!("topup_user:id={id}");
 
-        if let Some(cached) = self
-            .cache_store
-            .get_from_cache::<ApiResponse<Option<TopupResponse>>>(&cache_key)
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<ApiResponse<Option<TopupResponse>>>(&cache_key)
         {
             info!("Found topup in cache for user_id: {id}");
 
@@ -468,23 +680,32 @@ impl TopupServiceTrait for TopupService {
             return Ok(cached);
         }
 
-        let user_result = self.user_repository.find_by_id(id).await;
+        let user_result = self
+            .user_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await;
         let _user = match user_result {
             Ok(user) => user,
             Err(_) => {
                 let msg = format!("User with id {id} not found");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
         };
 
-        let topup_result = self.topup_repository.find_by_user(id).await;
+        let topup_result = self
+            .topup_repository
+            .find_by_user(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await;
         let topup_opt = match topup_result {
             Ok(topup) => topup.map(TopupResponse::from),
             Err(err) => {
                 let msg = format!("Failed to retrieve topup for user_id {id}: {err}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
@@ -499,7 +720,7 @@ impl TopupServiceTrait for TopupService {
                 };
 
                 self.cache_store
-                    .set_to_cache(&cache_key, &response, Duration::from_secs(60 * 5));
+                    .set_to_cache(&cache_key, &response, self.cache_store.ttl());
 
                 self.complete_tracing_success(
                     &tracing_ctx,
@@ -512,10 +733,11 @@ impl TopupServiceTrait for TopupService {
             }
             None => {
                 let msg = format!("No topup found for user_id: {id}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
 
-                Err(ErrorResponse::from(AppError::NotFound(msg)))
+                Err(ErrorResponse::from(err))
             }
         }
     }
@@ -538,97 +760,59 @@ impl TopupServiceTrait for TopupService {
         let mut request = Request::new(input.clone());
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let _user = match self.user_repository.find_by_id(input.user_id).await {
-            Ok(user) => user,
-            Err(_) => {
+        let user = match self
+            .user_repository
+            .find_by_id(input.user_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(user)) => user,
+            Ok(None) => {
                 let msg = format!("User with id {} not found", input.user_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
-        };
-
-        let topup = self.topup_repository.create(input).await?;
-
-        match self.saldo_repository.find_by_user_id(input.user_id).await {
-            Ok(Some(current_saldo)) => {
-                let new_balance = current_saldo.total_balance + topup.topup_amount;
-                let request = UpdateSaldoBalance {
-                    user_id: input.user_id,
-                    total_balance: new_balance,
-                };
-
-                if let Err(db_err) = self.saldo_repository.update_balance(&request).await {
-                    let msg = format!(
-                        "Failed to update saldo balance for user {}: {}",
-                        input.user_id, db_err
-                    );
-                    error!("{msg}");
-
-                    if let Err(rb_err) = self.topup_repository.delete(topup.topup_id).await {
-                        error!(
-                            "Failed to rollback topup creation for user {}: {}",
-                            input.user_id, rb_err
-                        );
-                    }
-
-                    self.complete_tracing_error(&tracing_ctx, method, &msg)
-                        .await;
-
-                    return Err(db_err.into());
-                }
+            Err(err) => {
+                let msg = format!("Failed to fetch user {}: {err}", input.user_id);
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
             }
-            Ok(None) => {
-                let create_saldo_request = CreateSaldoRequest {
-                    user_id: input.user_id,
-                    total_balance: topup.topup_amount,
-                };
-
-                if let Err(db_err) = self.saldo_repository.create(&create_saldo_request).await {
-                    let msg = format!(
-                        "Failed to create initial saldo for user {}: {db_err}",
-                        input.user_id,
-                    );
-
-                    error!("{msg}");
-
-                    if let Err(rb_err) = self.topup_repository.delete(topup.topup_id).await {
-                        error!(
-                            "Failed to rollback topup creation for user {}: {rb_err}",
-                            input.user_id,
-                        );
-                    }
+        };
 
-                    self.complete_tracing_error(&tracing_ctx, method, &msg)
-                        .await;
+        if user.is_frozen() {
+            let msg = format!("Account {} is frozen", input.user_id);
+            warn!("{msg}");
+            let err = AppError::Custom("Account is frozen".to_string());
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
 
-                    return Err(db_err.into());
-                }
-            }
+        let topup = match self
+            .topup_repository
+            .create(input)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(topup) => topup,
             Err(err) => {
-                let msg = format!(
-                    "Failed to retrieve saldo for user {}: {}",
-                    input.user_id, err
-                );
+                let msg = format!("Failed to create topup for user {}: {err}", input.user_id);
                 error!("{msg}");
-
-                if let Err(rb_err) = self.topup_repository.delete(topup.topup_id).await {
-                    error!(
-                        "Failed to rollback topup creation for user {}: {}",
-                        input.user_id, rb_err
-                    );
-                }
-
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-
-                return Err(ErrorResponse::from(AppError::Custom(msg.clone())));
+                return Err(ErrorResponse::from(err));
             }
-        }
+        };
 
+        invalidate_account_summary(&self.cache_store, &[topup.user_id]);
+
+        // saldo baru ditambah saat topup dikonfirmasi lewat confirm_topup
         let message = format!(
-            "Topup successfully created for user {}. Total balance updated.",
-            input.user_id
+            "Topup {} created as pending for user {}",
+            topup.topup_id, input.user_id
         );
         info!("{message}");
 
@@ -637,7 +821,7 @@ impl TopupServiceTrait for TopupService {
 
         Ok(ApiResponse {
             status: "success".to_string(),
-            message: "Topup created successfully".to_string(),
+            message: "Topup created successfully, waiting for confirmation".to_string(),
             data: TopupResponse::from(topup),
         })
     }
@@ -657,172 +841,731 @@ impl TopupServiceTrait for TopupService {
             ],
         );
 
-        let _user = match self.user_repository.find_by_id(input.user_id).await {
-            Ok(Some(user)) => user,
-            Ok(None) => {
-                let msg = format!("User with id {} not found", input.user_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
-                    .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
-            }
-            Err(err) => {
-                let msg = format!("Failed to fetch user {}: {}", input.user_id, err);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
-                    .await;
-                return Err(ErrorResponse::from(err));
-            }
-        };
+        self.ensure_account_not_frozen(input.user_id, &tracing_ctx, method.clone())
+            .await?;
 
         info!(
             "User with id {} found, proceeding with topup update",
             input.user_id
         );
 
-        let existing_topup = match self.topup_repository.find_by_id(input.topup_id).await {
-            Ok(Some(topup)) => topup,
-            Ok(None) => {
-                let msg = format!("Topup with id {} not found", input.topup_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
-                    .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
-            }
+        // edit topup diserialkan dengan perubahan saldo lain milik user yang sama
+        let locked = self
+            .cache_store
+            .with_user_lock(input.user_id, || {
+                self.update_topup_locked(input, &tracing_ctx, method.clone())
+            })
+            .with_context(tracing_ctx.cx.clone())
+            .await;
+
+        match locked {
+            Ok(result) => result,
             Err(err) => {
-                let msg = format!("Failed to fetch topup {}: {}", input.topup_id, err);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let msg = format!("Failed to update topup {}: {err}", input.topup_id);
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(err));
+                Err(ErrorResponse::from(err))
             }
-        };
+        }
+    }
+
+    async fn delete_topup(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
+        let user = self.user_repository.find_by_id(id).await.map_err(|_| {
+            ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
+        })?;
 
-        let topup_difference = input.topup_amount - existing_topup.topup_amount;
+        let existing_topup = self
+            .topup_repository
+            .find_by_user(user.unwrap().user_id)
+            .await?;
 
-        info!(
-            "Calculating topup difference: new amount {} - old amount {} = difference {topup_difference}",
-            input.topup_amount, existing_topup.topup_amount,
-        );
+        match existing_topup {
+            Some(topup) => {
+                self.topup_repository.delete(topup.topup_id).await?;
 
-        let update_topup = UpdateTopupAmount {
-            topup_id: input.topup_id,
-            topup_amount: input.topup_amount,
-        };
+                invalidate_account_summary(&self.cache_store, &[topup.user_id]);
 
-        if let Err(err) = self.topup_repository.update_amount(&update_topup).await {
-            let msg = format!(
-                "Failed to update topup {} for user {}: {}",
-                input.topup_id, input.user_id, err
-            );
-            self.complete_tracing_error(&tracing_ctx, method, &msg)
-                .await;
-            return Err(ErrorResponse::from(err));
+                info!("Topup deleted successfully for id: {id}");
+
+                Ok(ApiResponse {
+                    status: "success".to_string(),
+                    message: "Topup deleted successfully".to_string(),
+                    data: (),
+                })
+            }
+            None => {
+                error!("Topup with id {id} not found");
+                Err(ErrorResponse::from(AppError::NotFound(format!(
+                    "Topup with id {id} not found",
+                ))))
+            }
         }
+    }
 
-        match self.saldo_repository.find_by_user_id(input.user_id).await {
-            Ok(Some(current_saldo)) => {
-                let new_balance = current_saldo.total_balance + topup_difference;
+    async fn confirm_topup(&self, id: i32) -> Result<ApiResponse<TopupResponse>, ErrorResponse> {
+        let method = Method::Post;
 
-                info!(
-                    "Updating saldo: current balance {} + topup difference {topup_difference} = new balance {new_balance}",
-                    current_saldo.total_balance,
-                );
+        let tracing_ctx = self.start_tracing(
+            "ConfirmTopup",
+            vec![
+                KeyValue::new("component", "topup"),
+                KeyValue::new("topup_id", id.to_string()),
+            ],
+        );
 
-                let request = UpdateSaldoBalance {
-                    user_id: input.user_id,
-                    total_balance: new_balance,
-                };
+        let user_id = self
+            .find_topup_owner(id, &tracing_ctx, method.clone())
+            .await?;
+        self.ensure_account_not_frozen(user_id, &tracing_ctx, method.clone())
+            .await?;
 
-                if let Err(db_err) = self.saldo_repository.update_balance(&request).await {
-                    let msg = format!(
-                        "Failed to update saldo balance for user {}: {}",
-                        input.user_id, db_err
-                    );
-                    self.complete_tracing_error(&tracing_ctx, method, &msg)
-                        .await;
-
-                    let rollback = UpdateTopupAmount {
-                        topup_id: existing_topup.topup_id,
-                        topup_amount: existing_topup.topup_amount,
-                    };
-
-                    if let Err(rb_err) = self.topup_repository.update_amount(&rollback).await {
-                        error!(
-                            "Failed to rollback topup update for user {}: {}",
-                            input.user_id, rb_err
-                        );
-                    }
-
-                    return Err(db_err.into());
-                }
+        match self
+            .topup_repository
+            .confirm(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(topup) => {
+                self.invalidate_topup_cache(&topup);
 
-                info!(
-                    "Saldo updated successfully for user {}. New balance: {}",
-                    input.user_id, new_balance
+                let message = format!(
+                    "Topup {id} confirmed, credited {} to user {}",
+                    topup.topup_amount, topup.user_id
                 );
-            }
-            Ok(None) => {
-                let msg = format!("Saldo for user {} not found", input.user_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                info!("{message}");
+
+                self.complete_tracing_success(&tracing_ctx, method, &message)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+
+                Ok(ApiResponse {
+                    status: "success".to_string(),
+                    message: "Topup confirmed successfully".to_string(),
+                    data: TopupResponse::from(topup),
+                })
             }
-            Err(e) => {
-                let msg = format!("Failed to retrieve saldo for user {}: {}", input.user_id, e);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+            Err(err) => {
+                let msg = format!("Failed to confirm topup {id}: {err}");
+                error!("{msg}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(e.into());
+                Err(ErrorResponse::from(err))
             }
         }
+    }
 
-        let updated_topup = self.topup_repository.find_by_id(input.topup_id).await?;
+    async fn fail_topup(&self, id: i32) -> Result<ApiResponse<TopupResponse>, ErrorResponse> {
+        let method = Method::Post;
 
-        match updated_topup {
-            Some(topup) => {
-                self.complete_tracing_success(&tracing_ctx, method, "Topup updated successfully")
+        let tracing_ctx = self.start_tracing(
+            "FailTopup",
+            vec![
+                KeyValue::new("component", "topup"),
+                KeyValue::new("topup_id", id.to_string()),
+            ],
+        );
+
+        match self
+            .topup_repository
+            .fail(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(topup) => {
+                self.invalidate_topup_cache(&topup);
+
+                let message = format!("Topup {id} marked as failed");
+                info!("{message}");
+
+                self.complete_tracing_success(&tracing_ctx, method, &message)
                     .await;
+
                 Ok(ApiResponse {
                     status: "success".to_string(),
-                    message: "Topup updated successfully".to_string(),
+                    message: "Topup marked as failed".to_string(),
                     data: TopupResponse::from(topup),
                 })
             }
-            None => {
-                let msg = format!("Topup with id {} not found", input.topup_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+            Err(err) => {
+                let msg = format!("Failed to mark topup {id} as failed: {err}");
+                error!("{msg}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                Err(ErrorResponse::from(AppError::NotFound(msg)))
+                Err(ErrorResponse::from(err))
             }
         }
     }
 
-    async fn delete_topup(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
-        let user = self.user_repository.find_by_id(id).await.map_err(|_| {
-            ErrorResponse::from(AppError::NotFound(format!("User with id {id} not found")))
-        })?;
+    async fn refund_topup(&self, id: i32) -> Result<ApiResponse<TopupResponse>, ErrorResponse> {
+        let method = Method::Post;
 
-        let existing_topup = self
-            .topup_repository
-            .find_by_user(user.unwrap().user_id)
+        let tracing_ctx = self.start_tracing(
+            "RefundTopup",
+            vec![
+                KeyValue::new("component", "topup"),
+                KeyValue::new("topup_id", id.to_string()),
+            ],
+        );
+
+        let user_id = self
+            .find_topup_owner(id, &tracing_ctx, method.clone())
+            .await?;
+        self.ensure_account_not_frozen(user_id, &tracing_ctx, method.clone())
             .await?;
 
-        match existing_topup {
-            Some(_) => {
-                self.topup_repository
-                    .delete(existing_topup.unwrap().topup_id)
-                    .await?;
+        match self
+            .topup_repository
+            .refund(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(topup) => {
+                self.invalidate_topup_cache(&topup);
 
-                info!("Topup deleted successfully for id: {id}");
+                let message = format!(
+                    "Topup {id} refunded, debited {} from user {}",
+                    topup.topup_amount, topup.user_id
+                );
+                info!("{message}");
+
+                self.complete_tracing_success(&tracing_ctx, method, &message)
+                    .await;
 
                 Ok(ApiResponse {
                     status: "success".to_string(),
-                    message: "Topup deleted successfully".to_string(),
-                    data: (),
+                    message: "Topup refunded successfully".to_string(),
+                    data: TopupResponse::from(topup),
                 })
             }
-            None => {
-                error!("Topup with id {id} not found");
-                Err(ErrorResponse::from(AppError::NotFound(format!(
-                    "Topup with id {id} not found",
-                ))))
+            Err(err) => {
+                let msg = format!("Failed to refund topup {id}: {err}");
+                error!("{msg}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
+        }
+    }
+
+    async fn get_topup_stats(
+        &self,
+        req: &TopupStatsRequest,
+    ) -> Result<ApiResponse<Vec<TopupMethodStatsResponse>>, ErrorResponse> {
+        let method = Method::Get;
+
+        let range = format!(
+            "{}..{}",
+            req.start_date.map(|d| d.to_string()).unwrap_or_default(),
+            req.end_date.map(|d| d.to_string()).unwrap_or_default()
+        );
+
+        let tracing_ctx = self.start_tracing(
+            "GetTopupStats",
+            vec![
+                KeyValue::new("component", "topup"),
+                KeyValue::new("range", range.clone()),
+            ],
+        );
+
+        if !req.is_valid_range() {
+            let err = AppError::Custom("start_date must not be after end_date".to_string());
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &err.to_string())
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        let cache_key = format!("topups:stats:range={range}");
+
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<Vec<TopupMethodStatsResponse>>(&cache_key)
+        {
+            info!("Found topup stats for {range} in cache");
+
+            self.complete_tracing_success(&tracing_ctx, method, "Topup stats retrieved from cache")
+                .await;
+
+            return Ok(ApiResponse {
+                status: "success".to_string(),
+                message: "Topup stats retrieved successfully (from cache)".to_string(),
+                data: cached,
+            });
+        }
+
+        match cancellable(self.topup_repository.stats_by_method(req))
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(stats) => {
+                let data = stats
+                    .into_iter()
+                    .map(TopupMethodStatsResponse::from)
+                    .collect::<Vec<_>>();
+
+                // statistik cukup segar untuk dashboard, TTL-nya sengaja pendek
+                self.cache_store.set_to_cache(
+                    &cache_key,
+                    &data,
+                    self.cache_store.ttl_config().stats,
+                );
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Topup stats retrieved from database",
+                )
+                .await;
+
+                Ok(ApiResponse {
+                    status: "success".to_string(),
+                    message: "Topup stats retrieved successfully".to_string(),
+                    data,
+                })
             }
+            Err(err) => {
+                let msg = format!("Failed to retrieve topup stats: {err}");
+                error!("{msg}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        domain::request::{CreateTopupRequest, UpdateSaldoBalance},
+        model::{ledger::LedgerEntryType, user::UserStatus},
+        repository::memory::{InMemoryRepositories, offline_cache_store},
+    };
+    use chrono::{NaiveDate, Utc};
+
+    async fn topup_service(repos: &InMemoryRepositories) -> TopupService {
+        TopupService::new(
+            repos.topup.clone(),
+            repos.user.clone(),
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+            offline_cache_store(),
+        )
+        .await
+    }
+
+    async fn pending_topup(service: &TopupService, user_id: i32, amount: i32) -> TopupResponse {
+        service
+            .create_topup(&CreateTopupRequest {
+                user_id,
+                topup_no: format!("TOPUP-{user_id}-{amount}"),
+                topup_amount: amount,
+                topup_method: "bri".to_string(),
+            })
+            .await
+            .unwrap()
+            .data
+    }
+
+    fn freeze(repos: &InMemoryRepositories, user_id: i32) {
+        if let Some(user) = repos.database.tables().users.get_mut(user_id) {
+            user.status = UserStatus::Frozen.as_str().to_string();
         }
     }
+
+    #[tokio::test]
+    async fn confirmed_topup_credits_saldo_once() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("topup@example.com", 50_000).await;
+        let service = topup_service(&repos).await;
+
+        let topup = pending_topup(&service, user.user_id, 25_000).await;
+        assert_eq!(topup.status, "pending");
+        assert_eq!(repos.balance_of(user.user_id).await, 50_000);
+
+        let confirmed = service.confirm_topup(topup.topup_id).await.unwrap().data;
+        assert_eq!(confirmed.status, "success");
+        assert_eq!(repos.balance_of(user.user_id).await, 75_000);
+
+        // hanya topup pending yang bisa dikonfirmasi
+        assert!(service.confirm_topup(topup.topup_id).await.is_err());
+        assert_eq!(repos.balance_of(user.user_id).await, 75_000);
+    }
+
+    #[tokio::test]
+    async fn failed_topup_never_credits_saldo() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("topup@example.com", 50_000).await;
+        let service = topup_service(&repos).await;
+
+        let topup = pending_topup(&service, user.user_id, 25_000).await;
+
+        let failed = service.fail_topup(topup.topup_id).await.unwrap().data;
+        assert_eq!(failed.status, "failed");
+        assert_eq!(repos.balance_of(user.user_id).await, 50_000);
+
+        assert!(service.confirm_topup(topup.topup_id).await.is_err());
+        assert_eq!(repos.balance_of(user.user_id).await, 50_000);
+    }
+
+    #[tokio::test]
+    async fn refund_debits_the_credit_and_records_a_ledger_entry() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("refund@example.com", 50_000).await;
+        let service = topup_service(&repos).await;
+
+        let topup = pending_topup(&service, user.user_id, 25_000).await;
+
+        // topup pending belum menambah saldo, jadi tidak ada yang bisa direfund
+        assert!(service.refund_topup(topup.topup_id).await.is_err());
+
+        service.confirm_topup(topup.topup_id).await.unwrap();
+        let refunded = service.refund_topup(topup.topup_id).await.unwrap().data;
+
+        assert_eq!(refunded.status, "refunded");
+        assert_eq!(repos.balance_of(user.user_id).await, 50_000);
+
+        let ledger = repos.database.tables().ledger.clone();
+        assert_eq!(ledger.len(), 1);
+        assert_eq!(ledger[0].entry_type, LedgerEntryType::TopupRefund.as_str());
+        assert_eq!(ledger[0].amount, -25_000);
+        assert_eq!(ledger[0].reference_id, topup.topup_id);
+
+        // refund kedua ditolak dan saldo tidak didebit lagi
+        assert!(service.refund_topup(topup.topup_id).await.is_err());
+        assert_eq!(repos.balance_of(user.user_id).await, 50_000);
+    }
+
+    #[tokio::test]
+    async fn refund_is_rejected_when_the_credit_was_already_spent() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("refund@example.com", 50_000).await;
+        let service = topup_service(&repos).await;
+
+        let topup = pending_topup(&service, user.user_id, 25_000).await;
+        service.confirm_topup(topup.topup_id).await.unwrap();
+
+        repos
+            .saldo
+            .update_balance(&UpdateSaldoBalance {
+                user_id: user.user_id,
+                total_balance: 10_000,
+            })
+            .await
+            .unwrap();
+
+        let err = service.refund_topup(topup.topup_id).await.unwrap_err();
+
+        assert_eq!(err.message, "Insufficient balance");
+        assert_eq!(repos.balance_of(user.user_id).await, 10_000);
+        assert!(repos.database.tables().ledger.is_empty());
+    }
+
+    #[tokio::test]
+    async fn confirm_near_i32_max_fails_instead_of_wrapping() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("rich@example.com", i32::MAX - 10).await;
+        let service = topup_service(&repos).await;
+
+        let topup = pending_topup(&service, user.user_id, 25_000).await;
+        let err = service.confirm_topup(topup.topup_id).await.unwrap_err();
+
+        assert_eq!(err.message, "amount overflow");
+        assert_eq!(repos.balance_of(user.user_id).await, i32::MAX - 10);
+    }
+
+    #[tokio::test]
+    async fn amount_only_update_keeps_method_and_adjusts_saldo() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("partial@example.com", 50_000).await;
+        let service = topup_service(&repos).await;
+
+        let topup = pending_topup(&service, user.user_id, 25_000).await;
+        service.confirm_topup(topup.topup_id).await.unwrap();
+
+        let updated = service
+            .update_topup(&UpdateTopupRequest {
+                user_id: user.user_id,
+                topup_id: topup.topup_id,
+                topup_amount: Some(40_000),
+                topup_method: None,
+            })
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(updated.topup_amount, 40_000);
+        assert_eq!(updated.topup_method, "bri");
+        assert_eq!(repos.balance_of(user.user_id).await, 90_000);
+    }
+
+    #[tokio::test]
+    async fn topup_of_another_user_is_not_found() {
+        let repos = InMemoryRepositories::new();
+        let owner = repos.seed_user("owner@example.com", 50_000).await;
+        let other = repos.seed_user("other@example.com", 50_000).await;
+        let service = topup_service(&repos).await;
+
+        let topup = pending_topup(&service, owner.user_id, 25_000).await;
+        service.confirm_topup(topup.topup_id).await.unwrap();
+
+        let err = service
+            .update_topup(&UpdateTopupRequest {
+                user_id: other.user_id,
+                topup_id: topup.topup_id,
+                topup_amount: Some(40_000),
+                topup_method: None,
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, axum::http::StatusCode::NOT_FOUND);
+        assert_eq!(repos.balance_of(owner.user_id).await, 75_000);
+        assert_eq!(repos.balance_of(other.user_id).await, 50_000);
+        let stored = service
+            .get_topup(topup.topup_id)
+            .await
+            .unwrap()
+            .data
+            .unwrap();
+        assert_eq!(stored.topup_amount, 25_000);
+    }
+
+    #[tokio::test]
+    async fn failed_saldo_adjustment_leaves_the_topup_unchanged() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("spent@example.com", 50_000).await;
+        let service = topup_service(&repos).await;
+
+        let topup = pending_topup(&service, user.user_id, 25_000).await;
+        service.confirm_topup(topup.topup_id).await.unwrap();
+
+        // kredit topup sudah dipakai, menurunkan nominal akan membuat saldo negatif
+        repos
+            .saldo
+            .update_balance(&UpdateSaldoBalance {
+                user_id: user.user_id,
+                total_balance: 10_000,
+            })
+            .await
+            .unwrap();
+
+        let err = service
+            .update_topup(&UpdateTopupRequest {
+                user_id: user.user_id,
+                topup_id: topup.topup_id,
+                topup_amount: Some(5_000),
+                topup_method: Some("mandiri".to_string()),
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.message, "Insufficient balance");
+        assert_eq!(repos.balance_of(user.user_id).await, 10_000);
+        let stored = service
+            .get_topup(topup.topup_id)
+            .await
+            .unwrap()
+            .data
+            .unwrap();
+        assert_eq!(stored.topup_amount, 25_000);
+        assert_eq!(stored.topup_method, "bri");
+    }
+
+    #[tokio::test]
+    async fn method_only_update_leaves_saldo_untouched() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("partial@example.com", 50_000).await;
+        let service = topup_service(&repos).await;
+
+        let topup = pending_topup(&service, user.user_id, 25_000).await;
+        service.confirm_topup(topup.topup_id).await.unwrap();
+
+        let updated = service
+            .update_topup(&UpdateTopupRequest {
+                user_id: user.user_id,
+                topup_id: topup.topup_id,
+                topup_amount: None,
+                topup_method: Some("mandiri".to_string()),
+            })
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(updated.topup_amount, 25_000);
+        assert_eq!(updated.topup_method, "mandiri");
+        assert_eq!(repos.balance_of(user.user_id).await, 75_000);
+    }
+
+    #[tokio::test]
+    async fn frozen_account_topup_cannot_be_confirmed() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("frozen@example.com", 50_000).await;
+        let service = topup_service(&repos).await;
+
+        let topup = pending_topup(&service, user.user_id, 25_000).await;
+        freeze(&repos, user.user_id);
+
+        let err = service.confirm_topup(topup.topup_id).await.unwrap_err();
+
+        assert_eq!(err.message, "Account is frozen");
+        assert_eq!(repos.balance_of(user.user_id).await, 50_000);
+        let stored = repos
+            .topup
+            .find_by_id(topup.topup_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.status, "pending");
+    }
+
+    #[tokio::test]
+    async fn frozen_account_topup_cannot_be_refunded() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("frozen@example.com", 50_000).await;
+        let service = topup_service(&repos).await;
+
+        let topup = pending_topup(&service, user.user_id, 25_000).await;
+        service.confirm_topup(topup.topup_id).await.unwrap();
+        freeze(&repos, user.user_id);
+
+        let err = service.refund_topup(topup.topup_id).await.unwrap_err();
+
+        assert_eq!(err.message, "Account is frozen");
+        assert_eq!(repos.balance_of(user.user_id).await, 75_000);
+        assert!(repos.database.tables().ledger.is_empty());
+    }
+
+    #[tokio::test]
+    async fn frozen_account_topup_cannot_be_updated() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("frozen@example.com", 50_000).await;
+        let service = topup_service(&repos).await;
+
+        let topup = pending_topup(&service, user.user_id, 25_000).await;
+        service.confirm_topup(topup.topup_id).await.unwrap();
+        freeze(&repos, user.user_id);
+
+        let err = service
+            .update_topup(&UpdateTopupRequest {
+                user_id: user.user_id,
+                topup_id: topup.topup_id,
+                topup_amount: Some(40_000),
+                topup_method: None,
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.message, "Account is frozen");
+        assert_eq!(repos.balance_of(user.user_id).await, 75_000);
+        let stored = repos
+            .topup
+            .find_by_id(topup.topup_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.topup_amount, 25_000);
+    }
+
+    fn stats_between(start: Option<NaiveDate>, end: Option<NaiveDate>) -> TopupStatsRequest {
+        TopupStatsRequest {
+            start_date: start,
+            end_date: end,
+        }
+    }
+
+    #[tokio::test]
+    async fn stats_group_topups_by_method() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("stats@example.com", 0).await;
+        let service = topup_service(&repos).await;
+        for (no, amount, method) in [
+            (1, 50_000, "bank"),
+            (2, 70_000, "bank"),
+            (3, 20_000, "ewallet"),
+        ] {
+            service
+                .create_topup(&CreateTopupRequest {
+                    user_id: user.user_id,
+                    topup_no: format!("TOPUP-STATS-{no}"),
+                    topup_amount: amount,
+                    topup_method: method.to_string(),
+                })
+                .await
+                .unwrap();
+        }
+
+        let stats = service
+            .get_topup_stats(&stats_between(None, None))
+            .await
+            .unwrap()
+            .data;
+
+        // metode tanpa topup (misalnya card) tidak muncul
+        let totals: Vec<_> = stats
+            .iter()
+            .map(|s| (s.topup_method.as_str(), s.total_count, s.total_amount))
+            .collect();
+        assert_eq!(totals, [("bank", 2, 120_000), ("ewallet", 1, 20_000)]);
+
+        let today = Utc::now().date_naive();
+        let later = service
+            .get_topup_stats(&stats_between(today.succ_opt(), None))
+            .await
+            .unwrap()
+            .data;
+        assert!(later.is_empty());
+    }
+
+    #[tokio::test]
+    async fn reversed_stats_range_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        let service = topup_service(&repos).await;
+        let today = Utc::now().date_naive();
+
+        let err = service
+            .get_topup_stats(&stats_between(Some(today), today.pred_opt()))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.message, "start_date must not be after end_date");
+    }
+
+    #[tokio::test]
+    async fn topup_is_found_by_its_topup_no() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("topup@example.com", 0).await;
+        let service = topup_service(&repos).await;
+        let created = pending_topup(&service, user.user_id, 50_000).await;
+        pending_topup(&service, user.user_id, 75_000).await;
+
+        let found = service.get_topup_by_no(&created.topup_no).await.unwrap();
+        assert_eq!(found.data.topup_id, created.topup_id);
+        assert_eq!(found.data.topup_amount, 50_000);
+
+        let err = service.get_topup_by_no("TOPUP-unknown").await.unwrap_err();
+        assert_eq!(err.code, axum::http::StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn duplicate_topup_no_is_a_conflict() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("topup@example.com", 0).await;
+        let service = topup_service(&repos).await;
+        let first = pending_topup(&service, user.user_id, 50_000).await;
+
+        let err = service
+            .create_topup(&CreateTopupRequest {
+                user_id: user.user_id,
+                topup_no: first.topup_no.clone(),
+                topup_amount: 60_000,
+                topup_method: "bri".to_string(),
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, axum::http::StatusCode::CONFLICT);
+        assert_eq!(err.message, "Topup number already exists");
+        assert_eq!(repos.database.tables().topups.rows_where(|_| true).len(), 1);
+    }
 }
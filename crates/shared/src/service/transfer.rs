@@ -1,31 +1,40 @@
 use async_trait::async_trait;
 use opentelemetry::{
     Context, KeyValue,
+    context::FutureExt,
     global::{self, BoxedTracer},
     trace::{Span, SpanKind, TraceContextExt, Tracer},
 };
 use prometheus_client::registry::Registry;
-use std::{sync::Arc, time::Duration};
-use tokio::{sync::Mutex, time::Instant};
+use std::sync::Arc;
+use tokio::time::Instant;
 use tonic::Request;
-use tracing::{error, info};
+use tracing::{error, info, warn};
+use validator::Validate;
 
 use crate::{
     abstract_trait::{
         DynSaldoRepository, DynTransferRepository, DynUserRepository, TransferServiceTrait,
     },
     cache::CacheStore,
+    config::{TransferVelocityConfig, VelocityMode},
     domain::{
         request::{
-            CreateTransferRequest, FindAllTransferRequest, UpdateSaldoBalance,
-            UpdateTransferRequest,
+            CreateTransferRequest, DailyTransferTotalsRequest, FindAllTransferRequest, PageRequest,
+            StreamTransfersRequest, TransferFilter, UpdateTransferRequest,
         },
         response::{
-            ApiResponse, ApiResponsePagination, ErrorResponse, pagination::Pagination,
-            transfer::TransferResponse,
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            pagination::Pagination,
+            transfer::{DailyTransferTotalResponse, TransferResponse},
         },
     },
-    utils::{AppError, MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+    model::transfer::{Transfer, TransferPreview, TransferStatus},
+    service::account::invalidate_account_summary,
+    utils::{
+        AppError, MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext,
+        cancellable, checked_amount,
+    },
 };
 
 #[derive(Clone)]
@@ -33,8 +42,9 @@ pub struct TransferService {
     transfer_repository: DynTransferRepository,
     saldo_repository: DynSaldoRepository,
     user_repository: DynUserRepository,
-    metrics: Arc<Mutex<Metrics>>,
+    metrics: Arc<Metrics>,
     cache_store: Arc<CacheStore>,
+    velocity: TransferVelocityConfig,
 }
 
 impl std::fmt::Debug for TransferService {
@@ -43,6 +53,7 @@ impl std::fmt::Debug for TransferService {
             .field("transfer_repository", &"DynTransferRepository")
             .field("saldo_repository", &"DynSaldoRepository")
             .field("user_repository", &"DynUserRepository")
+            .field("velocity", &self.velocity)
             .finish()
     }
 }
@@ -52,19 +63,24 @@ impl TransferService {
         transfer_repository: DynTransferRepository,
         saldo_repository: DynSaldoRepository,
         user_repository: DynUserRepository,
-        metrics: Arc<Mutex<Metrics>>,
+        metrics: Arc<Metrics>,
         registry: &mut Registry,
         cache_store: Arc<CacheStore>,
     ) -> Self {
         registry.register(
             "transfer_service_request_counter",
             "Total number of requests to the TransferService",
-            metrics.lock().await.request_counter.clone(),
+            metrics.request_counter.clone(),
         );
         registry.register(
             "transfer_service_request_duration",
             "Histogram of request durations for the TransferService",
-            metrics.lock().await.request_duration.clone(),
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "transfer_service_error_counter",
+            "Total number of failed requests to the TransferService by error kind",
+            metrics.error_counter.clone(),
         );
 
         Self {
@@ -73,9 +89,15 @@ impl TransferService {
             user_repository,
             metrics,
             cache_store,
+            velocity: TransferVelocityConfig::default(),
         }
     }
 
+    pub fn with_velocity(mut self, velocity: TransferVelocityConfig) -> Self {
+        self.velocity = velocity;
+        self
+    }
+
     fn get_tracer(&self) -> BoxedTracer {
         global::tracer("transfer-service")
     }
@@ -106,7 +128,11 @@ impl TransferService {
         );
 
         let cx = Context::current_with_span(span);
-        TracingContext { cx, start_time }
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
     }
 
     async fn complete_tracing_success(
@@ -123,8 +149,12 @@ impl TransferService {
         &self,
         tracing_ctx: &TracingContext,
         method: Method,
+        error_kind: &str,
         error_message: &str,
     ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
         self.complete_tracing_internal(tracing_ctx, method, false, error_message)
             .await;
     }
@@ -159,10 +189,226 @@ impl TransferService {
             error!("Operation failed: {message}");
         }
 
-        self.metrics.lock().await.record(method, status, elapsed);
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
 
         tracing_ctx.cx.span().end();
     }
+
+    // status transfer baru menurut jumlah transfer pengirim dalam jendela velocity;
+    // error bila mode reject dan batasnya sudah tercapai
+    async fn velocity_status(&self, user_id: i32) -> Result<TransferStatus, AppError> {
+        if !self.velocity.is_enabled() {
+            return Ok(TransferStatus::Completed);
+        }
+
+        let recent = self
+            .transfer_repository
+            .count_recent_by_sender(user_id, self.velocity.window)
+            .await?;
+
+        if recent < i64::from(self.velocity.max_transfers) {
+            return Ok(TransferStatus::Completed);
+        }
+
+        warn!(
+            "🚨 Sender {user_id} made {recent} transfer(s) in the last {}s (limit {}), mode: {}",
+            self.velocity.window.as_secs(),
+            self.velocity.max_transfers,
+            self.velocity.mode.as_str()
+        );
+
+        match self.velocity.mode {
+            VelocityMode::Reject => Err(AppError::Custom("transfer velocity limit".to_string())),
+            VelocityMode::Review => Ok(TransferStatus::NeedsReview),
+            VelocityMode::Off => Ok(TransferStatus::Completed),
+        }
+    }
+
+    // dijalankan selama memegang lock kedua user, sehingga transfer beruntun dari pengirim
+    // yang sama dihitung satu per satu dan tidak bisa lolos bersamaan
+    async fn create_transfer_locked(
+        &self,
+        input: &CreateTransferRequest,
+    ) -> Result<Transfer, AppError> {
+        let status = self.velocity_status(input.transfer_from).await?;
+
+        self.transfer_repository
+            .create_with_settlement(input, status)
+            .await
+    }
+
+    async fn preview_transfer(
+        &self,
+        input: &CreateTransferRequest,
+    ) -> Result<TransferPreview, AppError> {
+        let status = self.velocity_status(input.transfer_from).await?;
+
+        self.transfer_repository
+            .preview_settlement(input, status)
+            .await
+    }
+
+    // dijalankan selama memegang lock saldo pengirim dan penerima
+    async fn update_transfer_locked(
+        &self,
+        input: &UpdateTransferRequest,
+        transfer: Transfer,
+        amount_difference: i32,
+        tracing_ctx: &TracingContext,
+        method: Method,
+    ) -> Result<ApiResponse<TransferResponse>, ErrorResponse> {
+        // akun pihak transfer divalidasi sekaligus dengan saldo utamanya
+        let (sender, sender_saldo) = match self
+            .saldo_repository
+            .find_with_user_by_user_id(transfer.transfer_from)
+            .await
+        {
+            Ok(Some(found)) => match found.saldo {
+                Some(saldo) => (found.user, saldo),
+                None => {
+                    let msg = format!("Saldo missing for user_id {}", transfer.transfer_from);
+                    let err = AppError::InternalError(msg.clone());
+                    self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+            },
+            Ok(None) => {
+                let msg = format!("User with id {} not found", transfer.transfer_from);
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!(
+                    "Failed to fetch saldo for user_id {}: {err}",
+                    transfer.transfer_from
+                );
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        if sender.is_frozen() {
+            let msg = format!("Sender account {} is frozen", transfer.transfer_from);
+            warn!("{msg}");
+            let err = AppError::Custom("Account is frozen".to_string());
+            self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        let new_sender_balance =
+            match checked_amount(sender_saldo.total_balance.checked_sub(amount_difference)) {
+                Ok(balance) => balance,
+                Err(err) => {
+                    let msg = format!("Failed to update sender's saldo: {err}");
+                    self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+            };
+
+        if new_sender_balance < sender.balance_floor() {
+            let msg = "Insufficient balance for sender".to_string();
+            let err = AppError::Custom(msg.clone());
+            self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        // akun pihak transfer divalidasi sekaligus dengan saldo utamanya
+        let (receiver, receiver_saldo) = match self
+            .saldo_repository
+            .find_with_user_by_user_id(transfer.transfer_to)
+            .await
+        {
+            Ok(Some(found)) => match found.saldo {
+                Some(saldo) => (found.user, saldo),
+                None => {
+                    let msg = format!("Saldo missing for user_id {}", transfer.transfer_to);
+                    let err = AppError::InternalError(msg.clone());
+                    self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+            },
+            Ok(None) => {
+                let msg = format!("User with id {} not found", transfer.transfer_to);
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!(
+                    "Failed to fetch saldo for user_id {}: {err}",
+                    transfer.transfer_to
+                );
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        if receiver.is_frozen() {
+            let msg = format!("Receiver account {} is frozen", transfer.transfer_to);
+            warn!("{msg}");
+            let err = AppError::Custom("Account is frozen".to_string());
+            self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        if let Err(err) =
+            checked_amount(receiver_saldo.total_balance.checked_add(amount_difference))
+        {
+            let msg = format!("Failed to update receiver's saldo: {err}");
+            self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        // selisih saldo kedua pihak dan baris transfer ditulis dalam satu transaksi
+        let updated_transfer = match self
+            .transfer_repository
+            .update_with_settlement(input)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(updated) => updated,
+            Err(err) => {
+                let msg = format!("Failed to update transfer {}: {err}", input.transfer_id);
+                error!("{msg}");
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        invalidate_account_summary(
+            &self.cache_store,
+            &[
+                transfer.transfer_from,
+                transfer.transfer_to,
+                updated_transfer.transfer_from,
+                updated_transfer.transfer_to,
+            ],
+        );
+
+        let msg = format!("Transfer updated successfully: id {}", input.transfer_id);
+        self.complete_tracing_success(tracing_ctx, method, &msg)
+            .await;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Transfer updated successfully".to_string(),
+            data: TransferResponse::from(updated_transfer),
+        })
+    }
 }
 
 #[async_trait]
@@ -173,8 +419,9 @@ impl TransferServiceTrait for TransferService {
     ) -> Result<ApiResponsePagination<Vec<TransferResponse>>, ErrorResponse> {
         let method = Method::Get;
 
-        let page = if req.page > 0 { req.page } else { 1 };
-        let page_size = if req.page_size > 0 { req.page_size } else { 10 };
+        let PageRequest { page, page_size } = PageRequest::new(req.page, req.page_size)
+            .bounded()
+            .map_err(ErrorResponse::from)?;
         let search = if req.search.is_empty() {
             None
         } else {
@@ -188,25 +435,59 @@ impl TransferServiceTrait for TransferService {
                 KeyValue::new("page", page.to_string()),
                 KeyValue::new("page_size", page_size.to_string()),
                 KeyValue::new("search", search.clone().unwrap_or_default()),
+                KeyValue::new("min_amount", format!("{:?}", req.min_amount)),
+                KeyValue::new("max_amount", format!("{:?}", req.max_amount)),
+                KeyValue::new("status", req.status.clone()),
             ],
         );
 
+        let statuses = match req.statuses() {
+            Ok(statuses) => statuses,
+            Err(errors) => {
+                let msg = format!("Invalid transfer status filter: {errors}");
+                error!("{msg}");
+                let err = AppError::ValidationError(errors);
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+        let status_key = statuses
+            .iter()
+            .map(|status| status.as_str())
+            .collect::<Vec<_>>()
+            .join(",");
+
         let mut request = Request::new(FindAllTransferRequest {
             page,
             page_size,
             search: search.clone().unwrap_or_default(),
+            min_amount: req.min_amount,
+            max_amount: req.max_amount,
+            status: status_key.clone(),
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
+        let amount_key = |bound: Option<i32>| bound.map(|v| v.to_string()).unwrap_or_default();
         let cache_key = format!(
-            "transfers:page={page}:size={page_size}:search={}",
-            search.clone().unwrap_or_default()
+            "transfers:page={page}:size={page_size}:search={}:amount={}-{}:status={status_key}",
+            search.clone().unwrap_or_default(),
+            amount_key(req.min_amount),
+            amount_key(req.max_amount),
         );
 
-        if let Some(cached) = self
-            .cache_store
-            .get_from_cache::<ApiResponsePagination<Vec<TransferResponse>>>(&cache_key)
+        let filter = TransferFilter {
+            search,
+            min_amount: req.min_amount,
+            max_amount: req.max_amount,
+            statuses,
+        };
+
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<ApiResponsePagination<Vec<TransferResponse>>>(&cache_key)
         {
             info!("Found transfers in cache");
 
@@ -216,13 +497,11 @@ impl TransferServiceTrait for TransferService {
             return Ok(cached);
         }
 
-        match self
-            .transfer_repository
-            .find_all(page, page_size, search)
+        match cancellable(self.transfer_repository.find_all(page, page_size, &filter))
+            .with_context(tracing_ctx.cx.clone())
             .await
         {
             Ok((transfers, total_items)) => {
-                let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
                 let category_responses = transfers
                     .into_iter()
                     .map(TransferResponse::from)
@@ -232,16 +511,11 @@ impl TransferServiceTrait for TransferService {
                     status: "success".to_string(),
                     message: "transfers retrieved successfully".to_string(),
                     data: category_responses.clone(),
-                    pagination: Pagination {
-                        page,
-                        page_size,
-                        total_items,
-                        total_pages,
-                    },
+                    pagination: Pagination::new(page, page_size, total_items),
                 };
 
                 self.cache_store
-                    .set_to_cache(&cache_key, &response, Duration::from_secs(60 * 5));
+                    .set_to_cache(&cache_key, &response, self.cache_store.ttl());
 
                 self.complete_tracing_success(
                     &tracing_ctx,
@@ -257,6 +531,7 @@ impl TransferServiceTrait for TransferService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    err.kind(),
                     &format!("Failed to retrieve transfers: {err}"),
                 )
                 .await;
@@ -266,6 +541,58 @@ impl TransferServiceTrait for TransferService {
         }
     }
 
+    async fn get_transfer_batch(
+        &self,
+        req: &StreamTransfersRequest,
+        after_id: i32,
+    ) -> Result<Vec<TransferResponse>, ErrorResponse> {
+        let method = Method::Get;
+
+        let batch_size = req.effective_batch_size();
+
+        let tracing_ctx = self.start_tracing(
+            "GetTransferBatch",
+            vec![
+                KeyValue::new("component", "transfer"),
+                KeyValue::new("after_id", after_id.to_string()),
+                KeyValue::new("batch_size", batch_size.to_string()),
+            ],
+        );
+
+        let req = StreamTransfersRequest {
+            batch_size,
+            ..req.clone()
+        };
+
+        let mut request = Request::new(req.clone());
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        match self
+            .transfer_repository
+            .find_batch_after(after_id, &req)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(transfers) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    &format!("Fetched {} transfer(s) after {after_id}", transfers.len()),
+                )
+                .await;
+
+                Ok(transfers.into_iter().map(TransferResponse::from).collect())
+            }
+            Err(err) => {
+                let msg = format!("Failed to fetch transfer batch after {after_id}: {err}");
+                error!("{msg}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
+        }
+    }
+
     async fn get_transfer(
         &self,
         id: i32,
@@ -285,9 +612,10 @@ impl TransferServiceTrait for TransferService {
 
         let cache_key = format!("transfer:id={id}");
 
-        if let Some(cached) = self
-            .cache_store
-            .get_from_cache::<ApiResponse<Option<TransferResponse>>>(&cache_key)
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<ApiResponse<Option<TransferResponse>>>(&cache_key)
         {
             info!("Found transfer in cache");
 
@@ -297,7 +625,12 @@ impl TransferServiceTrait for TransferService {
             return Ok(cached);
         }
 
-        match self.transfer_repository.find_by_id(id).await {
+        match self
+            .transfer_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(Some(transfer)) => {
                 let response = ApiResponse {
                     status: "success".to_string(),
@@ -306,7 +639,7 @@ impl TransferServiceTrait for TransferService {
                 };
 
                 self.cache_store
-                    .set_to_cache(&cache_key, &response, Duration::from_secs(60 * 5));
+                    .set_to_cache(&cache_key, &response, self.cache_store.ttl());
 
                 self.complete_tracing_success(
                     &tracing_ctx,
@@ -319,14 +652,15 @@ impl TransferServiceTrait for TransferService {
             }
             Ok(None) => {
                 let msg = format!("Transfer with id {id} not found");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
 
-                Err(ErrorResponse::from(AppError::NotFound(msg)))
+                Err(ErrorResponse::from(err))
             }
             Err(err) => {
                 let msg = format!("Failed to retrieve transfer: {err}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
 
                 Err(ErrorResponse::from(err))
@@ -337,25 +671,30 @@ impl TransferServiceTrait for TransferService {
     async fn get_transfer_users(
         &self,
         id: i32,
-    ) -> Result<ApiResponse<Option<Vec<TransferResponse>>>, ErrorResponse> {
+        req: &PageRequest,
+    ) -> Result<ApiResponsePagination<Vec<TransferResponse>>, ErrorResponse> {
         let method = Method::Get;
+        let req = req.bounded().map_err(ErrorResponse::from)?;
 
         let tracing_ctx = self.start_tracing(
             "GetTransferUsers",
             vec![
                 KeyValue::new("component", "transfer"),
                 KeyValue::new("user_id", id.to_string()),
+                KeyValue::new("page", req.page.to_string()),
+                KeyValue::new("page_size", req.page_size.to_string()),
             ],
         );
 
         let mut request = Request::new(id);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let cache_key = format!("transfer_users:id={id}");
+        let cache_key = req.cache_key("transfer_users", id);
 
-        if let Some(cached) = self
-            .cache_store
-            .get_from_cache::<ApiResponse<Option<Vec<TransferResponse>>>>(&cache_key)
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<ApiResponsePagination<Vec<TransferResponse>>>(&cache_key)
         {
             info!("Found user transfer in cache");
 
@@ -369,72 +708,156 @@ impl TransferServiceTrait for TransferService {
             return Ok(cached);
         }
 
-        let user_result = self.user_repository.find_by_id(id).await;
+        let user_result = self
+            .user_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await;
 
         let _user = match user_result {
             Ok(user) => user,
             Err(_) => {
                 let msg = format!("User with id {id} not found");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
         };
 
-        let transfer_result = self.transfer_repository.find_by_users(id).await;
+        let transfer_result = cancellable(self.transfer_repository.find_by_users(
+            id,
+            req.page,
+            req.page_size,
+        ))
+        .with_context(tracing_ctx.cx.clone())
+        .await;
 
-        let transfer = match transfer_result {
-            Ok(t) => t,
+        let (transfer, total_items) = match transfer_result {
+            Ok(page) => page,
             Err(err) => {
                 let msg = format!("Failed to retrieve transfer for user {id}: {err}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                error!("{msg}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
         };
 
-        let transfer_responses = if transfer.is_empty() {
-            None
+        let message = if transfer.is_empty() {
+            format!("No transfer found for user with id {id}")
         } else {
-            Some(
-                transfer
-                    .into_iter()
-                    .map(TransferResponse::from)
-                    .collect::<Vec<_>>(),
-            )
+            "Success".to_string()
         };
 
-        let response = if let Some(ref data) = transfer_responses {
-            let response = ApiResponse {
-                status: "success".to_string(),
-                data: Some(data.clone()),
-                message: "Success".to_string(),
-            };
+        let response: ApiResponsePagination<Vec<TransferResponse>> = ApiResponsePagination {
+            status: "success".to_string(),
+            message,
+            data: transfer.into_iter().map(TransferResponse::from).collect(),
+            pagination: Pagination::new(req.page, req.page_size, total_items),
+        };
 
+        if !response.data.is_empty() {
             self.cache_store
-                .set_to_cache(&cache_key, &response, Duration::from_secs(60 * 5));
+                .set_to_cache(&cache_key, &response, self.cache_store.ttl());
+        }
 
-            self.complete_tracing_success(
-                &tracing_ctx,
-                method,
-                "User transfer retrieved from database",
-            )
-            .await;
+        self.complete_tracing_success(
+            &tracing_ctx,
+            method,
+            "User transfer retrieved from database",
+        )
+        .await;
 
-            response
-        } else {
-            let response = ApiResponse {
-                status: "success".to_string(),
-                data: None,
-                message: format!("No transfer found for user with id {id}"),
-            };
+        Ok(response)
+    }
 
-            self.complete_tracing_success(&tracing_ctx, method, "No transfer found")
-                .await;
+    async fn get_user_transfer_history(
+        &self,
+        user_id: i32,
+        req: &PageRequest,
+    ) -> Result<ApiResponsePagination<Vec<TransferResponse>>, ErrorResponse> {
+        let method = Method::Get;
+        let req = req.bounded().map_err(ErrorResponse::from)?;
+
+        let tracing_ctx = self.start_tracing(
+            "GetUserTransferHistory",
+            vec![
+                KeyValue::new("component", "transfer"),
+                KeyValue::new("user_id", user_id.to_string()),
+                KeyValue::new("page", req.page.to_string()),
+                KeyValue::new("page_size", req.page_size.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(user_id);
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        match self
+            .user_repository
+            .find_by_id(user_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                let msg = format!("User with id {user_id} not found");
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to find user {user_id}: {err}");
+                error!("{msg}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        }
+
+        let history_result = cancellable(self.transfer_repository.find_all_involving_user(
+            user_id,
+            req.page,
+            req.page_size,
+        ))
+        .with_context(tracing_ctx.cx.clone())
+        .await;
+
+        let (transfers, total_items) = match history_result {
+            Ok(page) => page,
+            Err(err) => {
+                let msg = format!("Failed to retrieve transfer history for user {user_id}: {err}");
+                error!("{msg}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        let message = if transfers.is_empty() {
+            format!("No transfer history found for user with id {user_id}")
+        } else {
+            "Success".to_string()
+        };
 
-            response
+        let response = ApiResponsePagination {
+            status: "success".to_string(),
+            message,
+            data: transfers
+                .into_iter()
+                .map(|transfer| TransferResponse::for_user(transfer, user_id))
+                .collect(),
+            pagination: Pagination::new(req.page, req.page_size, total_items),
         };
 
+        self.complete_tracing_success(
+            &tracing_ctx,
+            method,
+            "User transfer history retrieved from database",
+        )
+        .await;
+
         Ok(response)
     }
 
@@ -457,9 +880,10 @@ impl TransferServiceTrait for TransferService {
 
         let cache_key = format!("transfer_user:id={id}");
 
-        if let Some(cached) = self
-            .cache_store
-            .get_from_cache::<ApiResponse<Option<TransferResponse>>>(&cache_key)
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<ApiResponse<Option<TransferResponse>>>(&cache_key)
         {
             info!("Found transfer in cache for user_id: {id}");
 
@@ -469,23 +893,32 @@ impl TransferServiceTrait for TransferService {
             return Ok(cached);
         }
 
-        let user_result = self.user_repository.find_by_id(id).await;
+        let user_result = self
+            .user_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await;
         let _user = match user_result {
             Ok(user) => user,
             Err(_) => {
                 let msg = format!("User with id {id} not found");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
         };
 
-        let transfer_result = self.transfer_repository.find_by_user(id).await;
+        let transfer_result = self
+            .transfer_repository
+            .find_by_user(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await;
         let transfer_opt = match transfer_result {
             Ok(t) => t.map(TransferResponse::from),
             Err(err) => {
                 let msg = format!("Failed to retrieve transfer for user_id {id}: {err}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
@@ -500,7 +933,7 @@ impl TransferServiceTrait for TransferService {
                 };
 
                 self.cache_store
-                    .set_to_cache(&cache_key, &response, Duration::from_secs(60 * 5));
+                    .set_to_cache(&cache_key, &response, self.cache_store.ttl());
 
                 self.complete_tracing_success(
                     &tracing_ctx,
@@ -513,10 +946,11 @@ impl TransferServiceTrait for TransferService {
             }
             None => {
                 let msg = format!("No transfer found for user_id: {id}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
 
-                Err(ErrorResponse::from(AppError::NotFound(msg)))
+                Err(ErrorResponse::from(err))
             }
         }
     }
@@ -534,105 +968,147 @@ impl TransferServiceTrait for TransferService {
                 KeyValue::new("from_user_id", input.transfer_from.to_string()),
                 KeyValue::new("to_user_id", input.transfer_to.to_string()),
                 KeyValue::new("amount", input.transfer_amount.to_string()),
+                KeyValue::new("dry_run", input.dry_run),
             ],
         );
 
         let mut request = Request::new(input.transfer_from);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let _sender = match self.user_repository.find_by_id(input.transfer_from).await {
-            Ok(user) => user,
-            Err(_) => {
+        if let Err(errors) = input.validate() {
+            let msg = format!("Invalid create transfer request: {errors}");
+            error!("{msg}");
+            let err = AppError::ValidationError(errors);
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        if input.is_same_balance() {
+            let msg = format!(
+                "Transfer source and destination are the same saldo '{}' of user {}",
+                input.from_label(),
+                input.transfer_from
+            );
+            warn!("{msg}");
+            let err = AppError::Custom("Cannot transfer to the same balance".to_string());
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        let sender = match self
+            .user_repository
+            .find_by_id(input.transfer_from)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(user)) => user,
+            Ok(None) => {
                 let msg = format!("User with id {} not found", input.transfer_from);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
-        };
-
-        let _receiver = match self.user_repository.find_by_id(input.transfer_to).await {
-            Ok(user) => user,
-            Err(_) => {
-                let msg = format!("User with id {} not found", input.transfer_to);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+            Err(err) => {
+                let msg = format!("Failed to fetch user {}: {err}", input.transfer_from);
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
         };
 
-        let transfer = self.transfer_repository.create(input).await?;
-
-        let sender_saldo = match self
-            .saldo_repository
-            .find_by_user_id(input.transfer_from)
+        let receiver = match self
+            .user_repository
+            .find_by_id(input.transfer_to)
+            .with_context(tracing_ctx.cx.clone())
             .await
         {
-            Ok(saldo) => saldo,
-            Err(_) => {
-                let msg = format!("Saldo with User id {} not found", input.transfer_from);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+            Ok(Some(user)) => user,
+            Ok(None) => {
+                let msg = format!("User with id {} not found", input.transfer_to);
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to fetch user {}: {err}", input.transfer_to);
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
             }
         };
 
-        let sender_balance = sender_saldo.unwrap().total_balance - input.transfer_amount;
-
-        let request_sender_balance = UpdateSaldoBalance {
-            user_id: input.transfer_from,
-            total_balance: sender_balance,
-        };
-
-        if let Err(db_err) = self
-            .saldo_repository
-            .update_balance(&request_sender_balance)
-            .await
-        {
-            error!("Failed to update saldo balance for sender: {db_err}");
-            let msg = "Failed to update saldo balance for sender".to_string();
-            self.complete_tracing_error(&tracing_ctx, method, &msg)
+        if sender.is_frozen() {
+            let msg = format!("Sender account {} is frozen", input.transfer_from);
+            warn!("{msg}");
+            let err = AppError::Custom("Account is frozen".to_string());
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                 .await;
+            return Err(ErrorResponse::from(err));
+        }
 
-            self.transfer_repository
-                .delete(transfer.transfer_id)
-                .await?;
-            return Err(db_err.into());
+        if receiver.is_frozen() {
+            let msg = format!("Receiver account {} is frozen", input.transfer_to);
+            warn!("{msg}");
+            let err = AppError::Custom("Account is frozen".to_string());
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
         }
 
-        let receiver_saldo = match self
-            .saldo_repository
-            .find_by_user_id(input.transfer_to)
-            .await
-        {
-            Ok(saldo) => saldo,
-            Err(_) => {
-                let msg = format!("Saldo with User id {} not found", input.transfer_to);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
-                    .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
-            }
-        };
+        if input.dry_run {
+            let preview = match self
+                .preview_transfer(input)
+                .with_context(tracing_ctx.cx.clone())
+                .await
+            {
+                Ok(preview) => preview,
+                Err(err) => {
+                    let msg = format!("Failed to create transfer: {err}");
+                    self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+            };
 
-        let receiver_balance = receiver_saldo.unwrap().total_balance + input.transfer_amount;
+            self.complete_tracing_success(&tracing_ctx, method, "Transfer dry run succeeded")
+                .await;
 
-        let request_receiver_balance = UpdateSaldoBalance {
-            user_id: input.transfer_to,
-            total_balance: receiver_balance,
-        };
+            return Ok(ApiResponse {
+                status: "success".to_string(),
+                message: "Transfer dry run succeeded, nothing was saved".to_string(),
+                data: TransferResponse::from(preview),
+            });
+        }
 
-        if let Err(db_err) = self
-            .saldo_repository
-            .update_balance(&request_receiver_balance)
+        // transfer, mutasi saldo, dan event outbox ditulis dalam satu transaksi; lock kedua user
+        // menahannya selama mutasi lain yang membaca lalu menulis saldo mereka masih berjalan
+        let settled = self
+            .cache_store
+            .with_user_locks(&[input.transfer_from, input.transfer_to], || {
+                self.create_transfer_locked(input)
+            })
+            .with_context(tracing_ctx.cx.clone())
             .await
-        {
-            error!("Failed to update saldo balance for receiver: {db_err}");
-            let msg = "Failed to update saldo balance for receiver".to_string();
-            self.complete_tracing_error(&tracing_ctx, method, &msg)
-                .await;
+            .and_then(|result| result);
 
-            let _ = self.transfer_repository.delete(transfer.transfer_id).await;
-            return Err(db_err.into());
-        }
+        let transfer = match settled {
+            Ok(transfer) => transfer,
+            Err(err) => {
+                let msg = format!("Failed to create transfer: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        invalidate_account_summary(
+            &self.cache_store,
+            &[transfer.transfer_from, transfer.transfer_to],
+        );
 
         self.complete_tracing_success(&tracing_ctx, method, "Transfer created successfully")
             .await;
@@ -662,116 +1138,118 @@ impl TransferServiceTrait for TransferService {
         let mut request = Request::new(input.transfer_id);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let transfer = match self.transfer_repository.find_by_id(input.transfer_id).await {
-            Ok(Some(t)) => t,
-            _ => {
-                let msg = format!("Transfer with id {} not found", input.transfer_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
-                    .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
-            }
-        };
-
-        let amount_difference = input.transfer_amount as i64 - transfer.transfer_amount as i64;
+        if let Err(errors) = input.validate() {
+            let msg = format!("Invalid update transfer request: {errors}");
+            error!("{msg}");
+            let err = AppError::ValidationError(errors);
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
 
-        let sender_saldo = match self
-            .saldo_repository
-            .find_by_user_id(transfer.transfer_from)
+        let transfer = match self
+            .transfer_repository
+            .find_by_id(input.transfer_id)
+            .with_context(tracing_ctx.cx.clone())
             .await
         {
-            Ok(Some(saldo)) => saldo,
+            Ok(Some(t)) => t,
             _ => {
-                let msg = format!("Saldo with User id {} not found", transfer.transfer_from);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let msg = format!("Transfer with id {} not found", input.transfer_id);
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
         };
 
-        let new_sender_balance = sender_saldo.total_balance - amount_difference as i32;
-
-        if new_sender_balance < 0 {
-            let msg = "Insufficient balance for sender".to_string();
-            self.complete_tracing_error(&tracing_ctx, method, &msg)
+        // transfer yang ditahan atau sudah di-reverse tidak punya dana yang bisa disesuaikan
+        if !transfer.is_completed() {
+            let msg = format!(
+                "Transfer {} is {} and cannot be edited",
+                transfer.transfer_id, transfer.status
+            );
+            warn!("{msg}");
+            let err = AppError::Custom(format!(
+                "Only completed transfers can be edited, transfer {} is {}",
+                transfer.transfer_id, transfer.status
+            ));
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                 .await;
-            return Err(ErrorResponse::from(AppError::Custom(msg)));
+            return Err(ErrorResponse::from(err));
         }
 
-        let update_sender_balance = UpdateSaldoBalance {
-            user_id: transfer.transfer_from,
-            total_balance: new_sender_balance,
-        };
-
-        if let Err(db_err) = self
-            .saldo_repository
-            .update_balance(&update_sender_balance)
-            .await
+        // penyesuaian hanya memindahkan selisih nominal antara pihak yang tersimpan
+        if input.transfer_from != transfer.transfer_from
+            || input.transfer_to != transfer.transfer_to
         {
-            let msg = format!("Failed to update sender's saldo: {db_err}");
-            error!("{msg}");
-            self.complete_tracing_error(&tracing_ctx, method, &msg)
+            let msg = format!(
+                "Transfer {} parties cannot change from {} → {} to {} → {}",
+                transfer.transfer_id,
+                transfer.transfer_from,
+                transfer.transfer_to,
+                input.transfer_from,
+                input.transfer_to
+            );
+            warn!("{msg}");
+            let err = AppError::Custom(
+                "Transfer parties cannot be changed, reverse it instead".to_string(),
+            );
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                 .await;
-            return Err(db_err.into());
+            return Err(ErrorResponse::from(err));
         }
 
-        let receiver_saldo = match self
-            .saldo_repository
-            .find_by_user_id(transfer.transfer_to)
-            .await
-        {
-            Ok(Some(saldo)) => saldo,
-            _ => {
-                let msg = format!("Saldo with User id {} not found", transfer.transfer_to);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
-                    .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
-            }
-        };
-
-        let new_receiver_balance = receiver_saldo.total_balance + amount_difference as i32;
-
-        let update_receiver_balance = UpdateSaldoBalance {
-            user_id: transfer.transfer_to,
-            total_balance: new_receiver_balance,
-        };
-
-        if let Err(db_err) = self
-            .saldo_repository
-            .update_balance(&update_receiver_balance)
-            .await
-        {
-            let msg = format!("Failed to update receiver's saldo: {db_err}");
-            error!("{msg}");
-            self.complete_tracing_error(&tracing_ctx, method, &msg)
+        // penyesuaian di bawah hanya menyentuh saldo bawaan
+        if !transfer.uses_default_labels() {
+            let msg = format!(
+                "Transfer {} moves between labeled saldos and cannot be edited",
+                transfer.transfer_id
+            );
+            warn!("{msg}");
+            let err = AppError::Custom(
+                "Transfers between labeled saldos cannot be edited, reverse it instead".to_string(),
+            );
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                 .await;
-
-            let rollback_sender_balance = UpdateSaldoBalance {
-                user_id: transfer.transfer_from,
-                total_balance: sender_saldo.total_balance,
-            };
-
-            if let Err(rollback_err) = self
-                .saldo_repository
-                .update_balance(&rollback_sender_balance)
-                .await
-            {
-                error!("Failed to rollback sender's saldo update: {rollback_err}");
-            }
-
-            return Err(db_err.into());
+            return Err(ErrorResponse::from(err));
         }
 
-        let updated_transfer = self.transfer_repository.update(input).await?;
+        let amount_difference =
+            match checked_amount(input.transfer_amount.checked_sub(transfer.transfer_amount)) {
+                Ok(difference) => difference,
+                Err(err) => {
+                    let msg = format!("Failed to update transfer {}: {err}", input.transfer_id);
+                    self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+            };
 
-        let msg = format!("Transfer updated successfully: id {}", input.transfer_id);
-        self.complete_tracing_success(&tracing_ctx, method, &msg)
+        // saldo kedua pihak dibaca lalu ditulis ulang, jadi keduanya dikunci selama penyesuaian
+        let locked = self
+            .cache_store
+            .with_user_locks(&[transfer.transfer_from, transfer.transfer_to], || {
+                self.update_transfer_locked(
+                    input,
+                    transfer,
+                    amount_difference,
+                    &tracing_ctx,
+                    method.clone(),
+                )
+            })
+            .with_context(tracing_ctx.cx.clone())
             .await;
 
-        Ok(ApiResponse {
-            status: "success".to_string(),
-            message: "Transfer updated successfully".to_string(),
-            data: TransferResponse::from(updated_transfer),
-        })
+        match locked {
+            Ok(result) => result,
+            Err(err) => {
+                let msg = format!("Failed to update transfer {}: {err}", input.transfer_id);
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
+        }
     }
 
     async fn delete_transfer(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
@@ -785,36 +1263,48 @@ impl TransferServiceTrait for TransferService {
             ],
         );
 
-        let user = match self.user_repository.find_by_id(id).await {
+        let user = match self
+            .user_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(Some(user)) => user,
             Ok(None) => {
                 let msg = format!("User with id {id} not found");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
             Err(err) => {
                 let msg = format!("Failed to fetch user {id}: {err}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
         };
 
-        let existing_transfer = match self.transfer_repository.find_by_user(user.user_id).await {
+        let existing_transfer = match self
+            .transfer_repository
+            .find_by_user(user.user_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(Some(transfer)) => transfer,
             Ok(None) => {
                 let msg = format!("Transfer with user_id {} not found", user.user_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
             Err(err) => {
                 let msg = format!(
                     "Failed to find transfer for user_id {}: {err}",
                     user.user_id
                 );
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
@@ -823,17 +1313,26 @@ impl TransferServiceTrait for TransferService {
         if let Err(err) = self
             .transfer_repository
             .delete(existing_transfer.transfer_id)
+            .with_context(tracing_ctx.cx.clone())
             .await
         {
             let msg = format!(
                 "Failed to delete transfer id {}: {}",
                 existing_transfer.transfer_id, err
             );
-            self.complete_tracing_error(&tracing_ctx, method, &msg)
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                 .await;
             return Err(ErrorResponse::from(err));
         }
 
+        invalidate_account_summary(
+            &self.cache_store,
+            &[
+                existing_transfer.transfer_from,
+                existing_transfer.transfer_to,
+            ],
+        );
+
         info!(
             "Transfer deleted successfully for user_id: {}",
             user.user_id
@@ -848,4 +1347,1432 @@ impl TransferServiceTrait for TransferService {
             data: (),
         })
     }
+
+    async fn reverse_transfer(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<TransferResponse>, ErrorResponse> {
+        let method = Method::Post;
+
+        let tracing_ctx = self.start_tracing(
+            "ReverseTransfer",
+            vec![
+                KeyValue::new("component", "transfer"),
+                KeyValue::new("transfer_id", id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(id);
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let transfer = match self
+            .transfer_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(transfer)) => transfer,
+            Ok(None) => {
+                let msg = format!("Transfer with id {id} not found");
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to retrieve transfer {id}: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        // pengembalian dana memindahkan saldo lagi, jadi kedua akun tidak boleh beku
+        for user_id in [transfer.transfer_from, transfer.transfer_to] {
+            match self
+                .user_repository
+                .find_by_id(user_id)
+                .with_context(tracing_ctx.cx.clone())
+                .await
+            {
+                Ok(Some(user)) if user.is_frozen() => {
+                    let msg = format!("Account {user_id} is frozen");
+                    warn!("{msg}");
+                    let err = AppError::Custom("Account is frozen".to_string());
+                    self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    let msg = format!("Failed to fetch user {user_id}: {err}");
+                    self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+            }
+        }
+
+        // status transfer asal, transfer kompensasi, dan mutasi saldo ditulis dalam satu transaksi;
+        // saldo kedua pihak dikunci seperti saat transfer dibuat
+        let reversal = self
+            .cache_store
+            .with_user_locks(&[transfer.transfer_from, transfer.transfer_to], || {
+                self.transfer_repository.reverse(id)
+            })
+            .with_context(tracing_ctx.cx.clone())
+            .await
+            .and_then(|result| result);
+
+        let reversal = match reversal {
+            Ok(reversal) => reversal,
+            Err(err) => {
+                let msg = format!("Failed to reverse transfer {id}: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        self.cache_store
+            .delete_from_cache(&format!("transfer:id={id}"));
+        invalidate_account_summary(
+            &self.cache_store,
+            &[transfer.transfer_from, transfer.transfer_to],
+        );
+
+        // transfer yang ditahan dibatalkan tanpa kompensasi, yang dikembalikan transfer itu sendiri
+        if reversal.transfer_id == id {
+            info!("Held transfer {id} cancelled without moving funds");
+        } else {
+            info!(
+                "Transfer {id} reversed by transfer {}",
+                reversal.transfer_id
+            );
+        }
+
+        self.complete_tracing_success(&tracing_ctx, method, "Transfer reversed successfully")
+            .await;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Transfer reversed successfully".to_string(),
+            data: TransferResponse::from(reversal),
+        })
+    }
+
+    async fn approve_transfer(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<TransferResponse>, ErrorResponse> {
+        let method = Method::Post;
+
+        let tracing_ctx = self.start_tracing(
+            "ApproveTransfer",
+            vec![
+                KeyValue::new("component", "transfer"),
+                KeyValue::new("transfer_id", id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(id);
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let transfer = match self
+            .transfer_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(transfer)) => transfer,
+            Ok(None) => {
+                let msg = format!("Transfer with id {id} not found");
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to retrieve transfer {id}: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        // persetujuan baru memindahkan dana yang ditahan, jadi kedua akun tidak boleh beku
+        for user_id in [transfer.transfer_from, transfer.transfer_to] {
+            match self
+                .user_repository
+                .find_by_id(user_id)
+                .with_context(tracing_ctx.cx.clone())
+                .await
+            {
+                Ok(Some(user)) if user.is_frozen() => {
+                    let msg = format!("Account {user_id} is frozen");
+                    warn!("{msg}");
+                    let err = AppError::Custom("Account is frozen".to_string());
+                    self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+                Ok(_) => {}
+                Err(err) => {
+                    let msg = format!("Failed to fetch user {user_id}: {err}");
+                    self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+            }
+        }
+
+        // status, mutasi saldo, dan event transfer.completed ditulis dalam satu transaksi
+        let approved = self
+            .cache_store
+            .with_user_locks(&[transfer.transfer_from, transfer.transfer_to], || {
+                self.transfer_repository.approve(id)
+            })
+            .with_context(tracing_ctx.cx.clone())
+            .await
+            .and_then(|result| result);
+
+        let approved = match approved {
+            Ok(approved) => approved,
+            Err(err) => {
+                let msg = format!("Failed to approve transfer {id}: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        self.cache_store
+            .delete_from_cache(&format!("transfer:id={id}"));
+        invalidate_account_summary(
+            &self.cache_store,
+            &[transfer.transfer_from, transfer.transfer_to],
+        );
+
+        info!("Held transfer {id} approved and settled");
+
+        self.complete_tracing_success(&tracing_ctx, method, "Transfer approved successfully")
+            .await;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Transfer approved successfully".to_string(),
+            data: TransferResponse::from(approved),
+        })
+    }
+
+    async fn get_daily_transfer_totals(
+        &self,
+        req: &DailyTransferTotalsRequest,
+    ) -> Result<ApiResponse<Vec<DailyTransferTotalResponse>>, ErrorResponse> {
+        let method = Method::Get;
+
+        let tracing_ctx = self.start_tracing(
+            "GetDailyTransferTotals",
+            vec![
+                KeyValue::new("component", "transfer"),
+                KeyValue::new("start_date", req.start_date.to_string()),
+                KeyValue::new("end_date", req.end_date.to_string()),
+            ],
+        );
+
+        if let Err(msg) = req.validate_range() {
+            let err = AppError::Custom(msg);
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &err.to_string())
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        let cache_key = req.cache_key();
+
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<Vec<DailyTransferTotalResponse>>(&cache_key)
+        {
+            info!(
+                "Found daily transfer totals for {}..{} in cache",
+                req.start_date, req.end_date
+            );
+
+            self.complete_tracing_success(
+                &tracing_ctx,
+                method,
+                "Daily transfer totals retrieved from cache",
+            )
+            .await;
+
+            return Ok(ApiResponse {
+                status: "success".to_string(),
+                message: "Daily transfer totals retrieved successfully (from cache)".to_string(),
+                data: cached,
+            });
+        }
+
+        match cancellable(self.transfer_repository.daily_totals(req))
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(totals) => {
+                let data = totals
+                    .into_iter()
+                    .map(DailyTransferTotalResponse::from)
+                    .collect::<Vec<_>>();
+
+                self.cache_store.set_to_cache(
+                    &cache_key,
+                    &data,
+                    self.cache_store.ttl_config().stats,
+                );
+
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "Daily transfer totals retrieved from database",
+                )
+                .await;
+
+                Ok(ApiResponse {
+                    status: "success".to_string(),
+                    message: "Daily transfer totals retrieved successfully".to_string(),
+                    data,
+                })
+            }
+            Err(err) => {
+                let msg = format!("Failed to retrieve daily transfer totals: {err}");
+                error!("{msg}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        config::CacheTtlConfig,
+        domain::request::CreateSaldoRequest,
+        model::outbox::{TRANSFER_COMPLETED_EVENT, TRANSFER_NEEDS_REVIEW_EVENT},
+        repository::memory::{InMemoryRepositories, offline_cache_store},
+    };
+    use axum::http::StatusCode;
+    use chrono::Utc;
+
+    async fn transfer_service(repos: &InMemoryRepositories) -> TransferService {
+        TransferService::new(
+            repos.transfer.clone(),
+            repos.saldo.clone(),
+            repos.user.clone(),
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+            offline_cache_store(),
+        )
+        .await
+    }
+
+    fn transfer(from: i32, to: i32, amount: i32) -> CreateTransferRequest {
+        CreateTransferRequest {
+            transfer_from: from,
+            transfer_to: to,
+            transfer_amount: amount,
+            note: None,
+            dry_run: false,
+            from_label: None,
+            to_label: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn transfer_debits_sender_and_credits_receiver() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let service = transfer_service(&repos).await;
+
+        let response = service
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 75_000))
+            .await
+            .unwrap();
+
+        assert_eq!(response.data.transfer_amount, 75_000);
+        assert_eq!(repos.balance_of(sender.user_id).await, 125_000);
+        assert_eq!(repos.balance_of(receiver.user_id).await, 125_000);
+    }
+
+    #[tokio::test]
+    async fn transfer_beyond_balance_moves_nothing() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 60_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let service = transfer_service(&repos).await;
+
+        let result = service
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 75_000))
+            .await;
+
+        assert!(result.is_err());
+        assert_eq!(repos.balance_of(sender.user_id).await, 60_000);
+        assert_eq!(repos.balance_of(receiver.user_id).await, 50_000);
+    }
+
+    #[tokio::test]
+    async fn reversal_moves_the_amount_back_once() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let service = transfer_service(&repos).await;
+
+        let original = service
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 75_000))
+            .await
+            .unwrap()
+            .data;
+
+        service
+            .reverse_transfer(original.transfer_id)
+            .await
+            .unwrap();
+
+        assert_eq!(repos.balance_of(sender.user_id).await, 200_000);
+        assert_eq!(repos.balance_of(receiver.user_id).await, 50_000);
+        let reversed = repos
+            .transfer
+            .find_by_id(original.transfer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(reversed.status, TransferStatus::Reversed.as_str());
+
+        // transfer yang sudah dibalik tidak bisa dibalik lagi
+        assert!(
+            service
+                .reverse_transfer(original.transfer_id)
+                .await
+                .is_err()
+        );
+        assert_eq!(repos.balance_of(sender.user_id).await, 200_000);
+        assert_eq!(repos.balance_of(receiver.user_id).await, 50_000);
+    }
+
+    #[tokio::test]
+    async fn reversing_a_reversal_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let service = transfer_service(&repos).await;
+
+        let original = service
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 75_000))
+            .await
+            .unwrap()
+            .data;
+        let reversal = service
+            .reverse_transfer(original.transfer_id)
+            .await
+            .unwrap()
+            .data;
+
+        let err = service
+            .reverse_transfer(reversal.transfer_id)
+            .await
+            .unwrap_err();
+
+        assert_eq!(
+            err.message,
+            format!(
+                "Transfer with ID {} is a reversal and cannot be reversed",
+                reversal.transfer_id
+            )
+        );
+        assert_eq!(repos.balance_of(sender.user_id).await, 200_000);
+        assert_eq!(repos.balance_of(receiver.user_id).await, 50_000);
+    }
+
+    #[tokio::test]
+    async fn reversal_is_refused_without_the_balance_locks() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let original = transfer_service(&repos)
+            .await
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 75_000))
+            .await
+            .unwrap()
+            .data;
+
+        // Redis mati dan lock wajib: pengembalian dana ditolak seperti transfer baru
+        let locked = TransferService::new(
+            repos.transfer.clone(),
+            repos.saldo.clone(),
+            repos.user.clone(),
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+            Arc::new(CacheStore::new(
+                redis::Client::open("redis://127.0.0.1:1/").unwrap(),
+                CacheTtlConfig::default(),
+            )),
+        )
+        .await;
+        let err = locked
+            .reverse_transfer(original.transfer_id)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.message, "operation in progress, retry");
+        assert_eq!(repos.balance_of(sender.user_id).await, 125_000);
+        assert_eq!(repos.balance_of(receiver.user_id).await, 125_000);
+    }
+
+    #[tokio::test]
+    async fn reversal_cannot_overdraw_the_receiver() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let other = repos.seed_user("other@example.com", 50_000).await;
+        let service = transfer_service(&repos).await;
+
+        let original = service
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 75_000))
+            .await
+            .unwrap()
+            .data;
+
+        // penerima sudah memakai sebagian dana, sisa saldonya kurang dari nominal transfer
+        service
+            .create_transfer(&transfer(receiver.user_id, other.user_id, 100_000))
+            .await
+            .unwrap();
+
+        let err = service
+            .reverse_transfer(original.transfer_id)
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.message, "Insufficient balance");
+        assert_eq!(repos.balance_of(sender.user_id).await, 125_000);
+        assert_eq!(repos.balance_of(receiver.user_id).await, 25_000);
+    }
+
+    fn edit(original: &TransferResponse, amount: i32) -> UpdateTransferRequest {
+        UpdateTransferRequest {
+            transfer_id: original.transfer_id,
+            transfer_from: original.transfer_from,
+            transfer_to: original.transfer_to,
+            transfer_amount: amount,
+            note: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn editing_a_transfer_moves_only_the_difference() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let service = transfer_service(&repos).await;
+
+        let original = service
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 75_000))
+            .await
+            .unwrap()
+            .data;
+
+        let updated = service
+            .update_transfer(&edit(&original, 100_000))
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(updated.transfer_amount, 100_000);
+        assert_eq!(repos.balance_of(sender.user_id).await, 100_000);
+        assert_eq!(repos.balance_of(receiver.user_id).await, 150_000);
+    }
+
+    #[tokio::test]
+    async fn editing_the_parties_of_a_transfer_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let other = repos.seed_user("other@example.com", 50_000).await;
+        let service = transfer_service(&repos).await;
+
+        let original = service
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 75_000))
+            .await
+            .unwrap()
+            .data;
+
+        let redirected = UpdateTransferRequest {
+            transfer_to: other.user_id,
+            ..edit(&original, 75_000)
+        };
+        let err = service.update_transfer(&redirected).await.unwrap_err();
+
+        assert_eq!(
+            err.message,
+            "Transfer parties cannot be changed, reverse it instead"
+        );
+        let stored = repos
+            .transfer
+            .find_by_id(original.transfer_id)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(stored.transfer_to, receiver.user_id);
+        assert_eq!(repos.balance_of(receiver.user_id).await, 125_000);
+        assert_eq!(repos.balance_of(other.user_id).await, 50_000);
+    }
+
+    #[tokio::test]
+    async fn editing_a_reversed_transfer_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let service = transfer_service(&repos).await;
+
+        let original = service
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 75_000))
+            .await
+            .unwrap()
+            .data;
+        service
+            .reverse_transfer(original.transfer_id)
+            .await
+            .unwrap();
+
+        let err = service
+            .update_transfer(&edit(&original, 100_000))
+            .await
+            .unwrap_err();
+
+        assert!(
+            err.message
+                .contains("Only completed transfers can be edited")
+        );
+        assert_eq!(repos.balance_of(sender.user_id).await, 200_000);
+        assert_eq!(repos.balance_of(receiver.user_id).await, 50_000);
+    }
+
+    #[tokio::test]
+    async fn dry_run_projects_balances_without_saving() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let service = transfer_service(&repos).await;
+
+        let preview = service
+            .create_transfer(&CreateTransferRequest {
+                dry_run: true,
+                ..transfer(sender.user_id, receiver.user_id, 75_000)
+            })
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(preview.projected_sender_balance, Some(125_000));
+        assert_eq!(preview.projected_receiver_balance, Some(125_000));
+        assert_eq!(repos.balance_of(sender.user_id).await, 200_000);
+        assert_eq!(repos.balance_of(receiver.user_id).await, 50_000);
+        assert!(
+            repos
+                .database
+                .tables()
+                .transfers
+                .rows_where(|_| true)
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn dry_run_fails_exactly_like_the_real_transfer() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 60_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let service = transfer_service(&repos).await;
+        let request = transfer(sender.user_id, receiver.user_id, 75_000);
+
+        let dry_run = service
+            .create_transfer(&CreateTransferRequest {
+                dry_run: true,
+                ..request.clone()
+            })
+            .await
+            .unwrap_err();
+        let real = service.create_transfer(&request).await.unwrap_err();
+
+        assert_eq!(dry_run.message, real.message);
+        assert_eq!(dry_run.code, real.code);
+        assert!(
+            repos
+                .database
+                .tables()
+                .transfers
+                .rows_where(|_| true)
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn credit_past_i32_max_fails_instead_of_wrapping() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("rich@example.com", i32::MAX - 10).await;
+        let service = transfer_service(&repos).await;
+
+        let err = service
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 75_000))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.message, "amount overflow");
+        assert_eq!(repos.balance_of(sender.user_id).await, 200_000);
+        assert_eq!(repos.balance_of(receiver.user_id).await, i32::MAX - 10);
+    }
+
+    #[tokio::test]
+    async fn overdraft_sender_can_transfer_below_zero() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("premium@example.com", 60_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        if let Some(user) = repos.database.tables().users.get_mut(sender.user_id) {
+            user.overdraft_limit = 50_000;
+        }
+        let service = transfer_service(&repos).await;
+
+        service
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 100_000))
+            .await
+            .unwrap();
+
+        assert_eq!(repos.balance_of(sender.user_id).await, -40_000);
+        assert_eq!(repos.balance_of(receiver.user_id).await, 150_000);
+    }
+
+    fn velocity(mode: VelocityMode) -> TransferVelocityConfig {
+        TransferVelocityConfig {
+            mode,
+            max_transfers: 2,
+            ..TransferVelocityConfig::default()
+        }
+    }
+
+    #[tokio::test]
+    async fn transfers_under_the_velocity_limit_complete() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 500_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let service = transfer_service(&repos)
+            .await
+            .with_velocity(velocity(VelocityMode::Reject));
+
+        for _ in 0..2 {
+            let response = service
+                .create_transfer(&transfer(sender.user_id, receiver.user_id, 50_000))
+                .await
+                .unwrap();
+            assert_eq!(response.data.status, TransferStatus::Completed.as_str());
+        }
+    }
+
+    #[tokio::test]
+    async fn transfer_over_the_velocity_limit_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 500_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let service = transfer_service(&repos)
+            .await
+            .with_velocity(velocity(VelocityMode::Reject));
+
+        for _ in 0..2 {
+            service
+                .create_transfer(&transfer(sender.user_id, receiver.user_id, 50_000))
+                .await
+                .unwrap();
+        }
+
+        let err = service
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 50_000))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.message, "transfer velocity limit");
+        assert_eq!(repos.balance_of(sender.user_id).await, 400_000);
+    }
+
+    #[tokio::test]
+    async fn transfer_over_the_velocity_limit_is_flagged_in_review_mode() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 500_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let service = transfer_service(&repos)
+            .await
+            .with_velocity(velocity(VelocityMode::Review));
+
+        for _ in 0..2 {
+            service
+                .create_transfer(&transfer(sender.user_id, receiver.user_id, 50_000))
+                .await
+                .unwrap();
+        }
+
+        let flagged = service
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 50_000))
+            .await
+            .unwrap();
+
+        // dana ditahan: saldo tidak berpindah dan webhook-nya bukan transfer.completed
+        assert_eq!(flagged.data.status, TransferStatus::NeedsReview.as_str());
+        assert_eq!(repos.balance_of(sender.user_id).await, 400_000);
+        assert_eq!(repos.balance_of(receiver.user_id).await, 150_000);
+        let events = repos.database.tables().outbox.clone();
+        assert_eq!(
+            events.last().unwrap().event_type,
+            TRANSFER_NEEDS_REVIEW_EVENT
+        );
+    }
+
+    // pengirim yang sudah melewati batas, transfer ketiganya ditahan untuk review
+    async fn held_transfer(repos: &InMemoryRepositories) -> (TransferService, TransferResponse) {
+        let sender = repos.seed_user("sender@example.com", 500_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let service = transfer_service(repos)
+            .await
+            .with_velocity(velocity(VelocityMode::Review));
+
+        for _ in 0..2 {
+            service
+                .create_transfer(&transfer(sender.user_id, receiver.user_id, 50_000))
+                .await
+                .unwrap();
+        }
+
+        let held = service
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 50_000))
+            .await
+            .unwrap()
+            .data;
+
+        (service, held)
+    }
+
+    #[tokio::test]
+    async fn approving_a_held_transfer_moves_the_funds() {
+        let repos = InMemoryRepositories::new();
+        let (service, held) = held_transfer(&repos).await;
+
+        let approved = service
+            .approve_transfer(held.transfer_id)
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(approved.status, TransferStatus::Completed.as_str());
+        assert_eq!(repos.balance_of(held.transfer_from).await, 350_000);
+        assert_eq!(repos.balance_of(held.transfer_to).await, 200_000);
+        let events = repos.database.tables().outbox.clone();
+        assert_eq!(events.last().unwrap().event_type, TRANSFER_COMPLETED_EVENT);
+
+        // transfer yang sudah disetujui tidak bisa disetujui lagi
+        let err = service
+            .approve_transfer(held.transfer_id)
+            .await
+            .unwrap_err();
+        assert!(err.message.contains("not awaiting review"));
+        assert_eq!(repos.balance_of(held.transfer_from).await, 350_000);
+    }
+
+    #[tokio::test]
+    async fn reversing_a_held_transfer_moves_nothing() {
+        let repos = InMemoryRepositories::new();
+        let (service, held) = held_transfer(&repos).await;
+
+        let cancelled = service
+            .reverse_transfer(held.transfer_id)
+            .await
+            .unwrap()
+            .data;
+
+        assert_eq!(cancelled.transfer_id, held.transfer_id);
+        assert_eq!(cancelled.status, TransferStatus::Reversed.as_str());
+        assert_eq!(repos.balance_of(held.transfer_from).await, 400_000);
+        assert_eq!(repos.balance_of(held.transfer_to).await, 150_000);
+        assert!(service.approve_transfer(held.transfer_id).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn velocity_ignores_reversals_and_own_label_transfers() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 500_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 500_000).await;
+        repos
+            .saldo
+            .create(&CreateSaldoRequest {
+                user_id: receiver.user_id,
+                total_balance: 50_000,
+                label: Some("savings".to_string()),
+            })
+            .await
+            .unwrap();
+        let service = transfer_service(&repos)
+            .await
+            .with_velocity(velocity(VelocityMode::Reject));
+
+        // dua transfer sender yang dibalik: kompensasinya tercatat dengan receiver sebagai pengirim
+        for _ in 0..2 {
+            let original = service
+                .create_transfer(&transfer(sender.user_id, receiver.user_id, 50_000))
+                .await
+                .unwrap()
+                .data;
+            service
+                .reverse_transfer(original.transfer_id)
+                .await
+                .unwrap();
+        }
+
+        // pindah dana antar label milik sendiri
+        for _ in 0..2 {
+            service
+                .create_transfer(&CreateTransferRequest {
+                    to_label: Some("savings".to_string()),
+                    ..transfer(receiver.user_id, receiver.user_id, 50_000)
+                })
+                .await
+                .unwrap();
+        }
+
+        let response = service
+            .create_transfer(&transfer(receiver.user_id, sender.user_id, 50_000))
+            .await
+            .unwrap();
+        assert_eq!(response.data.status, TransferStatus::Completed.as_str());
+    }
+
+    #[tokio::test]
+    async fn transfer_note_is_stored_and_read_back() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let service = transfer_service(&repos).await;
+
+        let created = service
+            .create_transfer(&CreateTransferRequest {
+                note: Some("split dinner".to_string()),
+                ..transfer(sender.user_id, receiver.user_id, 75_000)
+            })
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(created.note.as_deref(), Some("split dinner"));
+
+        let found = service
+            .get_transfer(created.transfer_id)
+            .await
+            .unwrap()
+            .data
+            .unwrap();
+        assert_eq!(found.note.as_deref(), Some("split dinner"));
+    }
+
+    #[tokio::test]
+    async fn overlong_transfer_note_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let service = transfer_service(&repos).await;
+
+        let err = service
+            .create_transfer(&CreateTransferRequest {
+                note: Some("x".repeat(256)),
+                ..transfer(sender.user_id, receiver.user_id, 75_000)
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::UNPROCESSABLE_ENTITY);
+        assert!(err.errors.unwrap().contains_key("note"));
+        assert_eq!(repos.balance_of(sender.user_id).await, 200_000);
+    }
+
+    #[tokio::test]
+    async fn transfer_to_a_known_user_passes_the_existence_check() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 100_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 0).await;
+        assert!(repos.user.exists_by_id(receiver.user_id).await.unwrap());
+
+        transfer_service(&repos)
+            .await
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 50_000))
+            .await
+            .unwrap();
+
+        assert_eq!(repos.balance_of(receiver.user_id).await, 50_000);
+    }
+
+    #[tokio::test]
+    async fn transfer_to_an_unknown_user_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 100_000).await;
+        let unknown = sender.user_id + 1_000;
+        assert!(!repos.user.exists_by_id(unknown).await.unwrap());
+
+        let err = transfer_service(&repos)
+            .await
+            .create_transfer(&transfer(sender.user_id, unknown, 50_000))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::NOT_FOUND);
+        assert_eq!(repos.balance_of(sender.user_id).await, 100_000);
+    }
+
+    // membaca semua batch seperti stream_all_transfers di server
+    async fn export(service: &TransferService, req: &StreamTransfersRequest) -> Vec<i32> {
+        let mut ids = Vec::new();
+        loop {
+            let batch = service
+                .get_transfer_batch(req, ids.last().copied().unwrap_or(0))
+                .await
+                .unwrap();
+            if batch.is_empty() {
+                return ids;
+            }
+            ids.extend(batch.iter().map(|transfer| transfer.transfer_id));
+        }
+    }
+
+    #[tokio::test]
+    async fn batched_export_yields_every_transfer_once() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 1_000_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 0).await;
+        let service = transfer_service(&repos).await;
+        for _ in 0..5 {
+            service
+                .create_transfer(&transfer(sender.user_id, receiver.user_id, 50_000))
+                .await
+                .unwrap();
+        }
+
+        let ids = export(
+            &service,
+            &StreamTransfersRequest {
+                start_date: None,
+                end_date: None,
+                batch_size: 2,
+            },
+        )
+        .await;
+
+        assert_eq!(ids.len(), 5);
+        assert!(ids.windows(2).all(|pair| pair[0] < pair[1]));
+    }
+
+    #[tokio::test]
+    async fn export_outside_the_date_range_is_empty() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 1_000_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 0).await;
+        let service = transfer_service(&repos).await;
+        service
+            .create_transfer(&transfer(sender.user_id, receiver.user_id, 50_000))
+            .await
+            .unwrap();
+        let tomorrow = Utc::now().date_naive().succ_opt().unwrap();
+
+        let ids = export(
+            &service,
+            &StreamTransfersRequest {
+                start_date: Some(tomorrow),
+                end_date: None,
+                batch_size: 2,
+            },
+        )
+        .await;
+
+        assert!(ids.is_empty());
+    }
+
+    async fn amounts_between(
+        service: &TransferService,
+        min_amount: Option<i32>,
+        max_amount: Option<i32>,
+    ) -> Vec<i32> {
+        let mut amounts: Vec<i32> = service
+            .get_transfers(&FindAllTransferRequest {
+                page: 1,
+                page_size: 10,
+                search: String::new(),
+                min_amount,
+                max_amount,
+                status: String::new(),
+            })
+            .await
+            .unwrap()
+            .data
+            .iter()
+            .map(|transfer| transfer.transfer_amount)
+            .collect();
+        amounts.sort();
+        amounts
+    }
+
+    async fn seeded_transfers(repos: &InMemoryRepositories) -> TransferService {
+        let sender = repos.seed_user("sender@example.com", 1_000_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 0).await;
+        let service = transfer_service(repos).await;
+        for amount in [50_000, 75_000, 100_000] {
+            service
+                .create_transfer(&transfer(sender.user_id, receiver.user_id, amount))
+                .await
+                .unwrap();
+        }
+        service
+    }
+
+    #[tokio::test]
+    async fn amount_bounds_are_inclusive() {
+        let repos = InMemoryRepositories::new();
+        let service = seeded_transfers(&repos).await;
+
+        assert_eq!(
+            amounts_between(&service, Some(75_000), None).await,
+            [75_000, 100_000]
+        );
+        assert_eq!(
+            amounts_between(&service, None, Some(75_000)).await,
+            [50_000, 75_000]
+        );
+        assert_eq!(
+            amounts_between(&service, Some(75_000), Some(75_000)).await,
+            [75_000]
+        );
+        assert_eq!(amounts_between(&service, None, None).await.len(), 3);
+    }
+
+    #[tokio::test]
+    async fn amount_bounds_outside_every_transfer_match_nothing() {
+        let repos = InMemoryRepositories::new();
+        let service = seeded_transfers(&repos).await;
+
+        assert!(
+            amounts_between(&service, Some(100_001), None)
+                .await
+                .is_empty()
+        );
+        assert!(
+            amounts_between(&service, Some(80_000), Some(90_000))
+                .await
+                .is_empty()
+        );
+    }
+
+    async fn label_balance(repos: &InMemoryRepositories, user_id: i32, label: &str) -> i32 {
+        let (saldos, _) = repos.saldo.find_by_users_id(user_id, 1, 10).await.unwrap();
+        saldos
+            .into_iter()
+            .find(|saldo| saldo.label == label)
+            .expect("labeled saldo exists")
+            .total_balance
+    }
+
+    #[tokio::test]
+    async fn money_moves_between_two_labeled_balances_of_one_user() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("owner@example.com", 0).await;
+        for (label, balance) in [("savings", 200_000), ("spending", 50_000)] {
+            repos
+                .saldo
+                .create(&CreateSaldoRequest {
+                    user_id: user.user_id,
+                    total_balance: balance,
+                    label: Some(label.to_string()),
+                })
+                .await
+                .unwrap();
+        }
+
+        transfer_service(&repos)
+            .await
+            .create_transfer(&CreateTransferRequest {
+                from_label: Some("savings".to_string()),
+                to_label: Some("spending".to_string()),
+                ..transfer(user.user_id, user.user_id, 75_000)
+            })
+            .await
+            .unwrap();
+
+        assert_eq!(
+            label_balance(&repos, user.user_id, "savings").await,
+            125_000
+        );
+        assert_eq!(
+            label_balance(&repos, user.user_id, "spending").await,
+            125_000
+        );
+        assert_eq!(repos.balance_of(user.user_id).await, 0);
+    }
+
+    #[tokio::test]
+    async fn transfer_into_the_same_labeled_balance_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("owner@example.com", 0).await;
+        repos
+            .saldo
+            .create(&CreateSaldoRequest {
+                user_id: user.user_id,
+                total_balance: 200_000,
+                label: Some("savings".to_string()),
+            })
+            .await
+            .unwrap();
+
+        let err = transfer_service(&repos)
+            .await
+            .create_transfer(&CreateTransferRequest {
+                from_label: Some("savings".to_string()),
+                to_label: Some("savings".to_string()),
+                ..transfer(user.user_id, user.user_id, 75_000)
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.message, "Cannot transfer to the same balance");
+        assert_eq!(
+            label_balance(&repos, user.user_id, "savings").await,
+            200_000
+        );
+    }
+
+    #[tokio::test]
+    async fn history_lists_sent_and_received_transfers_with_direction() {
+        let repos = InMemoryRepositories::new();
+        let alice = repos.seed_user("alice@example.com", 200_000).await;
+        let bob = repos.seed_user("bob@example.com", 200_000).await;
+        let carol = repos.seed_user("carol@example.com", 200_000).await;
+        let service = transfer_service(&repos).await;
+
+        let sent = service
+            .create_transfer(&transfer(alice.user_id, bob.user_id, 50_000))
+            .await
+            .unwrap()
+            .data;
+        let received = service
+            .create_transfer(&transfer(bob.user_id, alice.user_id, 60_000))
+            .await
+            .unwrap()
+            .data;
+        // transfer yang tidak melibatkan alice tidak ikut
+        service
+            .create_transfer(&transfer(bob.user_id, carol.user_id, 70_000))
+            .await
+            .unwrap();
+
+        let history = service
+            .get_user_transfer_history(alice.user_id, &PageRequest::default())
+            .await
+            .unwrap();
+
+        let mut directions: Vec<(i32, Option<String>)> = history
+            .data
+            .into_iter()
+            .map(|t| (t.transfer_id, t.direction))
+            .collect();
+        directions.sort();
+        assert_eq!(
+            directions,
+            vec![
+                (sent.transfer_id, Some("out".to_string())),
+                (received.transfer_id, Some("in".to_string())),
+            ]
+        );
+        assert_eq!(history.pagination.total_items, 2);
+    }
+
+    #[tokio::test]
+    async fn history_of_an_unknown_user_is_not_found() {
+        let repos = InMemoryRepositories::new();
+        let service = transfer_service(&repos).await;
+
+        let err = service
+            .get_user_transfer_history(999, &PageRequest::default())
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::NOT_FOUND);
+    }
+
+    async fn statuses_matching(service: &TransferService, status: &str) -> Vec<(i32, String)> {
+        let mut transfers: Vec<(i32, String)> = service
+            .get_transfers(&FindAllTransferRequest {
+                page: 1,
+                page_size: 10,
+                search: String::new(),
+                min_amount: None,
+                max_amount: None,
+                status: status.to_string(),
+            })
+            .await
+            .unwrap()
+            .data
+            .into_iter()
+            .map(|transfer| (transfer.transfer_id, transfer.status))
+            .collect();
+        transfers.sort();
+        transfers
+    }
+
+    #[tokio::test]
+    async fn transfer_list_filters_by_one_or_more_statuses() {
+        let repos = InMemoryRepositories::new();
+        let (service, held) = held_transfer(&repos).await;
+        let completed = statuses_matching(&service, "completed").await;
+        let reversed_id = completed[0].0;
+        service.reverse_transfer(reversed_id).await.unwrap();
+
+        assert_eq!(
+            statuses_matching(&service, "reversed").await,
+            vec![(reversed_id, "reversed".to_string())]
+        );
+
+        // reversal mencatat transfer balik berstatus completed
+        let all = statuses_matching(&service, "").await;
+        assert_eq!(all.len(), 4);
+
+        let active = statuses_matching(&service, " needs_review,completed,completed ").await;
+        let expected: Vec<_> = all
+            .into_iter()
+            .filter(|(id, _)| *id != reversed_id)
+            .collect();
+        assert_eq!(active, expected);
+        assert!(active.contains(&(held.transfer_id, "needs_review".to_string())));
+    }
+
+    #[tokio::test]
+    async fn unknown_status_filter_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        let service = seeded_transfers(&repos).await;
+
+        let err = service
+            .get_transfers(&FindAllTransferRequest {
+                page: 1,
+                page_size: 10,
+                search: String::new(),
+                min_amount: None,
+                max_amount: None,
+                status: "completed,pending".to_string(),
+            })
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::UNPROCESSABLE_ENTITY);
+    }
+
+    fn daily_totals(start: &str, end: &str) -> DailyTransferTotalsRequest {
+        DailyTransferTotalsRequest {
+            start_date: start.parse().unwrap(),
+            end_date: end.parse().unwrap(),
+        }
+    }
+
+    async fn totals_between(
+        service: &TransferService,
+        start: &str,
+        end: &str,
+    ) -> Vec<(String, i32, i64, i64)> {
+        service
+            .get_daily_transfer_totals(&daily_totals(start, end))
+            .await
+            .unwrap()
+            .data
+            .into_iter()
+            .map(|total| {
+                (
+                    total.date.to_string(),
+                    total.user_id,
+                    total.total_sent,
+                    total.total_received,
+                )
+            })
+            .collect()
+    }
+
+    #[tokio::test]
+    async fn daily_totals_sum_sent_and_received_per_user_per_day() {
+        let repos = InMemoryRepositories::new();
+        let a = repos.seed_user("a@example.com", 1_000_000).await.user_id;
+        let b = repos.seed_user("b@example.com", 1_000_000).await.user_id;
+        let c = repos.seed_user("c@example.com", 1_000_000).await.user_id;
+        let service = transfer_service(&repos).await;
+
+        for (from, to, amount, time) in [
+            (a, b, 50_000, "2024-03-01T08:00:00"),
+            (a, b, 60_000, "2024-03-01T23:59:59"),
+            (b, c, 70_000, "2024-03-01T12:00:00"),
+            (c, a, 80_000, "2024-03-03T00:00:00"),
+        ] {
+            let id = service
+                .create_transfer(&transfer(from, to, amount))
+                .await
+                .unwrap()
+                .data
+                .transfer_id;
+            // transfer dibuat sekarang, waktunya dimundurkan ke hari yang diuji
+            repos
+                .database
+                .tables()
+                .transfers
+                .get_mut(id)
+                .unwrap()
+                .transfer_time = time.parse().unwrap();
+        }
+
+        let day1 = "2024-03-01".to_string();
+        let day3 = "2024-03-03".to_string();
+        // 2024-03-02 tanpa aktivitas sehingga tidak muncul
+        assert_eq!(
+            totals_between(&service, "2024-03-01", "2024-03-03").await,
+            [
+                (day1.clone(), a, 110_000, 0),
+                (day1.clone(), b, 70_000, 110_000),
+                (day1, c, 0, 70_000),
+                (day3.clone(), a, 0, 80_000),
+                (day3, c, 80_000, 0),
+            ]
+        );
+
+        assert!(
+            totals_between(&service, "2024-03-02", "2024-03-02")
+                .await
+                .is_empty()
+        );
+    }
+
+    #[tokio::test]
+    async fn daily_totals_reject_an_inverted_or_oversized_range() {
+        let repos = InMemoryRepositories::new();
+        let service = transfer_service(&repos).await;
+
+        // 2023-01-01..2024-01-02 mencakup 367 hari, satu hari di atas batas
+        for (start, end) in [("2024-03-02", "2024-03-01"), ("2023-01-01", "2024-01-02")] {
+            let err = service
+                .get_daily_transfer_totals(&daily_totals(start, end))
+                .await
+                .unwrap_err();
+            assert_eq!(err.code, StatusCode::BAD_REQUEST, "{start}..{end}");
+        }
+
+        assert!(
+            totals_between(&service, "2023-01-01", "2024-01-01")
+                .await
+                .is_empty()
+        );
+    }
 }
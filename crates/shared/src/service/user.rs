@@ -1,36 +1,47 @@
 use async_trait::async_trait;
 use opentelemetry::{
     Context, KeyValue,
+    context::FutureExt,
     global::{self, BoxedTracer},
     trace::{Span, SpanKind, TraceContextExt, Tracer},
 };
 use prometheus_client::registry::Registry;
-use std::{sync::Arc, time::Duration};
-use tokio::{sync::Mutex, time::Instant};
+use std::sync::Arc;
+use tokio::time::Instant;
 use tonic::Request;
 use tracing::{error, info};
+use validator::Validate;
 
 use crate::{
-    abstract_trait::{DynHashing, DynUserRepository, UserServiceTrait},
+    abstract_trait::{DynHashing, DynSessionRepository, DynUserRepository, UserServiceTrait},
     cache::CacheStore,
     domain::{
-        request::{CreateUserRequest, FindAllUserRequest, RegisterRequest, UpdateUserRequest},
+        request::{
+            CreateUserRequest, FindAllUserRequest, ImportUsersRequest, MAX_IMPORT_ROWS,
+            PageRequest, RegisterRequest, UpdateUserRequest, UserFilter, normalize_email,
+        },
         response::{
-            ApiResponse, ApiResponsePagination, ErrorResponse, pagination::Pagination,
-            user::UserResponse,
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            pagination::Pagination,
+            user::{
+                ImportUserResult, ImportUsersResponse, PurgeUserResponse,
+                UserDeletionCheckResponse, UserResponse,
+            },
         },
     },
+    model::user::UserStatus,
     utils::{
         AppError, MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext,
-        random_vcc,
+        cancellable, random_vcc,
     },
 };
 
 #[derive(Clone)]
 pub struct UserService {
     repository: DynUserRepository,
+    session_repository: DynSessionRepository,
     hashing: DynHashing,
-    metrics: Arc<Mutex<Metrics>>,
+    metrics: Arc<Metrics>,
     cache_store: Arc<CacheStore>,
 }
 
@@ -46,24 +57,31 @@ impl std::fmt::Debug for UserService {
 impl UserService {
     pub async fn new(
         repository: DynUserRepository,
+        session_repository: DynSessionRepository,
         hashing: DynHashing,
-        metrics: Arc<Mutex<Metrics>>,
+        metrics: Arc<Metrics>,
         registry: &mut Registry,
         cache_store: Arc<CacheStore>,
     ) -> Self {
         registry.register(
             "user_service_request_counter",
             "Total number of requests to the UserService",
-            metrics.lock().await.request_counter.clone(),
+            metrics.request_counter.clone(),
         );
         registry.register(
             "user_service_request_duration",
             "Histogram of request> durations for the UserService",
-            metrics.lock().await.request_duration.clone(),
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "user_service_error_counter",
+            "Total number of failed requests to the UserService by error kind",
+            metrics.error_counter.clone(),
         );
 
         Self {
             repository,
+            session_repository,
             hashing,
             metrics,
             cache_store,
@@ -100,7 +118,11 @@ impl UserService {
         );
 
         let cx = Context::current_with_span(span);
-        TracingContext { cx, start_time }
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
     }
 
     async fn complete_tracing_success(
@@ -117,8 +139,12 @@ impl UserService {
         &self,
         tracing_ctx: &TracingContext,
         method: Method,
+        error_kind: &str,
         error_message: &str,
     ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
         self.complete_tracing_internal(tracing_ctx, method, false, error_message)
             .await;
     }
@@ -153,10 +179,59 @@ impl UserService {
             error!("Operation failed: {message}");
         }
 
-        self.metrics.lock().await.record(method, status, elapsed);
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
 
         tracing_ctx.cx.span().end();
     }
+
+    async fn update_status(
+        &self,
+        id: i32,
+        status: UserStatus,
+        operation: &str,
+        success_message: &str,
+    ) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        let method = Method::Put;
+
+        let tracing_ctx = self.start_tracing(
+            operation,
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("user_id", id.to_string()),
+                KeyValue::new("status", status.as_str()),
+            ],
+        );
+
+        match self
+            .repository
+            .update_status(id, status)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(user) => {
+                self.cache_store.delete_from_cache(&format!("user:id={id}"));
+
+                self.complete_tracing_success(&tracing_ctx, method, success_message)
+                    .await;
+
+                Ok(ApiResponse {
+                    status: "success".to_string(),
+                    message: success_message.to_string(),
+                    data: UserResponse::from(user),
+                })
+            }
+            Err(err) => {
+                let msg = format!(
+                    "Failed to set status of user {id} to {}: {err}",
+                    status.as_str()
+                );
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
+        }
+    }
 }
 
 #[async_trait]
@@ -167,13 +242,19 @@ impl UserServiceTrait for UserService {
     ) -> Result<ApiResponsePagination<Vec<UserResponse>>, ErrorResponse> {
         let method = Method::Get;
 
-        let page = if req.page > 0 { req.page } else { 1 };
-        let page_size = if req.page_size > 0 { req.page_size } else { 10 };
+        let PageRequest { page, page_size } = PageRequest::new(req.page, req.page_size)
+            .bounded()
+            .map_err(ErrorResponse::from)?;
         let search = if req.search.is_empty() {
             None
         } else {
             Some(req.search.clone())
         };
+        let email = if req.email.is_empty() {
+            None
+        } else {
+            Some(req.email.clone())
+        };
 
         let tracing_ctx = self.start_tracing(
             "Getusers",
@@ -182,6 +263,8 @@ impl UserServiceTrait for UserService {
                 KeyValue::new("page", page.to_string()),
                 KeyValue::new("page_size", page_size.to_string()),
                 KeyValue::new("search", search.clone().unwrap_or_default()),
+                KeyValue::new("include_deleted", req.include_deleted.to_string()),
+                KeyValue::new("email", email.clone().unwrap_or_default()),
             ],
         );
 
@@ -189,18 +272,39 @@ impl UserServiceTrait for UserService {
             page,
             page_size,
             search: search.clone().unwrap_or_default(),
+            include_deleted: req.include_deleted,
+            email: email.clone().unwrap_or_default(),
+            created_before: req.created_before,
+            created_after: req.created_after,
         });
 
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
+        let created_after = req.created_after.map(|d| d.timestamp()).unwrap_or_default();
+        let created_before = req
+            .created_before
+            .map(|d| d.timestamp())
+            .unwrap_or_default();
+
         let cache_key = format!(
-            "users:page={page}:size={page_size}:search={}",
-            search.clone().unwrap_or_default()
+            "users:page={page}:size={page_size}:search={}:include_deleted={}:email={}:created={created_after}-{created_before}",
+            search.clone().unwrap_or_default(),
+            req.include_deleted,
+            email.clone().unwrap_or_default(),
         );
 
-        if let Some(cached) = self
-            .cache_store
-            .get_from_cache::<ApiResponsePagination<Vec<UserResponse>>>(&cache_key)
+        let filter = UserFilter {
+            search,
+            email,
+            created_before: req.created_before.map(|d| d.naive_utc()),
+            created_after: req.created_after.map(|d| d.naive_utc()),
+            include_deleted: req.include_deleted,
+        };
+
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<ApiResponsePagination<Vec<UserResponse>>>(&cache_key)
         {
             info!("Found users in cache");
 
@@ -210,9 +314,11 @@ impl UserServiceTrait for UserService {
             return Ok(cached);
         }
 
-        match self.repository.find_all(page, page_size, search).await {
+        match cancellable(self.repository.find_all(page, page_size, &filter))
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok((users, total_items)) => {
-                let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
                 let users_responses = users
                     .into_iter()
                     .map(UserResponse::from)
@@ -222,16 +328,11 @@ impl UserServiceTrait for UserService {
                     status: "success".to_string(),
                     message: "users retrieved successfully".to_string(),
                     data: users_responses.clone(),
-                    pagination: Pagination {
-                        page,
-                        page_size,
-                        total_items,
-                        total_pages,
-                    },
+                    pagination: Pagination::new(page, page_size, total_items),
                 };
 
                 self.cache_store
-                    .set_to_cache(&cache_key, &response, Duration::from_secs(60 * 5));
+                    .set_to_cache(&cache_key, &response, self.cache_store.ttl());
 
                 self.complete_tracing_success(
                     &tracing_ctx,
@@ -247,6 +348,7 @@ impl UserServiceTrait for UserService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    err.kind(),
                     &format!("Failed to retrieve users: {err}"),
                 )
                 .await;
@@ -267,7 +369,12 @@ impl UserServiceTrait for UserService {
             ],
         );
 
-        match self.repository.find_by_id(id).await {
+        match self
+            .repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(Some(user)) => {
                 self.complete_tracing_success(&tracing_ctx, method, "User retrieved successfully")
                     .await;
@@ -280,13 +387,14 @@ impl UserServiceTrait for UserService {
             }
             Ok(None) => {
                 let msg = format!("User with id {id} not found");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                Err(ErrorResponse::from(AppError::NotFound(msg)))
+                Err(ErrorResponse::from(err))
             }
             Err(err) => {
                 let msg = format!("Failed to retrieve user {id}: {err}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 Err(ErrorResponse::from(err))
             }
@@ -297,6 +405,10 @@ impl UserServiceTrait for UserService {
         &self,
         input: &RegisterRequest,
     ) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        let input = &RegisterRequest {
+            email: normalize_email(&input.email),
+            ..input.clone()
+        };
         let method = Method::Post;
 
         let tracing_ctx = self.start_tracing(
@@ -310,16 +422,30 @@ impl UserServiceTrait for UserService {
         let mut request = Request::new(input.email.clone());
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
+        if let Err(errors) = input.validate() {
+            let msg = format!("Invalid create user request: {errors}");
+            error!("{msg}");
+            let err = AppError::ValidationError(errors);
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
         info!("Attempting to register user with email: {}", input.email);
 
-        let exists = self.repository.find_by_email_exists(&input.email).await?;
+        let exists = self
+            .repository
+            .find_by_email_exists(&input.email)
+            .with_context(tracing_ctx.cx.clone())
+            .await?;
 
         if exists {
             let msg = format!("Email already exists: {}", input.email);
             error!("{}", msg);
-            self.complete_tracing_error(&tracing_ctx, method, &msg)
+            let err = AppError::EmailAlreadyExists;
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                 .await;
-            return Err(ErrorResponse::from(AppError::EmailAlreadyExists));
+            return Err(ErrorResponse::from(err));
         }
 
         let hashed_password = self.hashing.hash_password(&input.password).await;
@@ -330,10 +456,11 @@ impl UserServiceTrait for UserService {
                 let msg = format!("Failed to hash password for email {}: {}", input.email, e);
                 error!("{msg}");
 
-                self.complete_tracing_error(&tracing_ctx, method.clone(), &msg)
+                let err = AppError::HashingError(e);
+                self.complete_tracing_error(&tracing_ctx, method.clone(), err.kind(), &msg)
                     .await;
 
-                return Err(AppError::HashingError(e).into());
+                return Err(err.into());
             }
         };
 
@@ -349,7 +476,11 @@ impl UserServiceTrait for UserService {
         };
 
         info!("Creating user with email: {}", input.email);
-        let create_user = self.repository.create_user(request).await?;
+        let create_user = self
+            .repository
+            .create_user(request)
+            .with_context(tracing_ctx.cx.clone())
+            .await?;
 
         info!("User created successfully with email: {}", input.email);
         self.complete_tracing_success(&tracing_ctx, method.clone(), "User created successfully")
@@ -378,21 +509,61 @@ impl UserServiceTrait for UserService {
         let mut request = Request::new(input.clone());
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.repository.update_user(input).await {
+        if let Err(errors) = input.validate() {
+            let msg = format!("Invalid update user request: {errors}");
+            error!("{msg}");
+            let err = AppError::ValidationError(errors);
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        let mut input = input.clone();
+
+        if let Some(password) = input.password.take() {
+            match self.hashing.hash_password(&password).await {
+                Ok(hashed) => input.password = Some(hashed),
+                Err(e) => {
+                    let msg = format!("Failed to hash password for user {}: {e}", input.id);
+                    error!("{msg}");
+
+                    let err = AppError::HashingError(e);
+                    self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+            }
+        }
+
+        match self
+            .repository
+            .update_user(&input)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(user) => {
                 let user_id = user.clone().user_id;
 
+                // sesi lama tidak boleh dipakai lagi setelah password diganti
+                if input.password.is_some()
+                    && let Err(err) = self
+                        .session_repository
+                        .revoke_all_for_user(user_id)
+                        .with_context(tracing_ctx.cx.clone())
+                        .await
+                {
+                    error!("Failed to revoke sessions of user {user_id}: {err}");
+                }
+
                 let response = ApiResponse {
                     status: "success".to_string(),
                     message: "User updated successfully".to_string(),
                     data: UserResponse::from(user),
                 };
 
-                self.cache_store.set_to_cache(
-                    &format!("user:id={user_id}"),
-                    &response,
-                    Duration::from_secs(60 * 5),
-                );
+                // profil get_me dibuang, dibaca ulang dari database pada request berikutnya
+                self.cache_store
+                    .delete_from_cache(&format!("user:id={user_id}"));
 
                 self.complete_tracing_success(&tracing_ctx, method, "User updated successfully")
                     .await;
@@ -403,6 +574,7 @@ impl UserServiceTrait for UserService {
                 self.complete_tracing_error(
                     &tracing_ctx,
                     method,
+                    err.kind(),
                     &format!("Failed to update user: {err}"),
                 )
                 .await;
@@ -412,7 +584,50 @@ impl UserServiceTrait for UserService {
         }
     }
 
-    async fn delete_user(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
+    async fn can_delete_user(
+        &self,
+        id: i32,
+    ) -> Result<ApiResponse<UserDeletionCheckResponse>, ErrorResponse> {
+        let method = Method::Get;
+
+        let tracing_ctx = self.start_tracing(
+            "CanDeleteUser",
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("user_id", id.to_string()),
+            ],
+        );
+
+        match self
+            .repository
+            .find_delete_blockers(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(blockers) => {
+                self.complete_tracing_success(
+                    &tracing_ctx,
+                    method,
+                    "User deletion check completed",
+                )
+                .await;
+
+                Ok(ApiResponse {
+                    status: "success".to_string(),
+                    message: "User deletion check completed".to_string(),
+                    data: UserDeletionCheckResponse::new(id, blockers),
+                })
+            }
+            Err(err) => {
+                let msg = format!("Failed to check deletion of user {id}: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
+        }
+    }
+
+    async fn delete_user(&self, id: i32, force: bool) -> Result<ApiResponse<()>, ErrorResponse> {
         let method = Method::Delete;
 
         let tracing_ctx = self.start_tracing(
@@ -420,11 +635,41 @@ impl UserServiceTrait for UserService {
             vec![
                 KeyValue::new("component", "user"),
                 KeyValue::new("user_id", id.to_string()),
+                KeyValue::new("force", force),
             ],
         );
 
-        match self.repository.delete_user(id).await {
+        // tanpa force, referensi yang masih ada membatalkan soft delete
+        let result = if force {
+            self.repository
+                .delete_user(id)
+                .with_context(tracing_ctx.cx.clone())
+                .await
+        } else {
+            match self
+                .repository
+                .find_delete_blockers(id)
+                .with_context(tracing_ctx.cx.clone())
+                .await
+            {
+                Ok(blockers) if !blockers.is_empty() => Err(AppError::Custom(format!(
+                    "User ID {id} cannot be deleted: {}; use force to delete anyway",
+                    blockers.describe().join(", ")
+                ))),
+                Ok(_) => {
+                    self.repository
+                        .delete_user(id)
+                        .with_context(tracing_ctx.cx.clone())
+                        .await
+                }
+                Err(err) => Err(err),
+            }
+        };
+
+        match result {
             Ok(_) => {
+                self.cache_store.delete_from_cache(&format!("user:id={id}"));
+
                 self.complete_tracing_success(&tracing_ctx, method, "User deleted successfully")
                     .await;
 
@@ -436,10 +681,741 @@ impl UserServiceTrait for UserService {
             }
             Err(err) => {
                 let msg = format!("Failed to delete user {id}: {err}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
+        }
+    }
+
+    async fn restore_user(&self, id: i32) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        let method = Method::Put;
+
+        let tracing_ctx = self.start_tracing(
+            "RestoreUser",
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("user_id", id.to_string()),
+            ],
+        );
+
+        match self
+            .repository
+            .restore_user(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(user) => {
+                self.cache_store.delete_from_cache(&format!("user:id={id}"));
+
+                self.complete_tracing_success(&tracing_ctx, method, "User restored successfully")
+                    .await;
+
+                Ok(ApiResponse {
+                    status: "success".to_string(),
+                    message: "User restored successfully".to_string(),
+                    data: UserResponse::from(user),
+                })
+            }
+            Err(err) => {
+                let msg = format!("Failed to restore user {id}: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 Err(ErrorResponse::from(err))
             }
         }
     }
+
+    async fn freeze_user(&self, id: i32) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        self.update_status(
+            id,
+            UserStatus::Frozen,
+            "FreezeUser",
+            "User frozen successfully",
+        )
+        .await
+    }
+
+    async fn unfreeze_user(&self, id: i32) -> Result<ApiResponse<UserResponse>, ErrorResponse> {
+        self.update_status(
+            id,
+            UserStatus::Active,
+            "UnfreezeUser",
+            "User unfrozen successfully",
+        )
+        .await
+    }
+
+    async fn purge_user(
+        &self,
+        id: i32,
+        force: bool,
+    ) -> Result<ApiResponse<PurgeUserResponse>, ErrorResponse> {
+        let method = Method::Delete;
+
+        let tracing_ctx = self.start_tracing(
+            "PurgeUser",
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("user_id", id.to_string()),
+                KeyValue::new("force", force),
+            ],
+        );
+
+        match self
+            .repository
+            .purge_user(id, force)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(purge) => {
+                self.cache_store.delete_from_cache(&format!("user:id={id}"));
+
+                self.complete_tracing_success(&tracing_ctx, method, "User purged successfully")
+                    .await;
+
+                Ok(ApiResponse {
+                    status: "success".to_string(),
+                    message: "User and related records purged successfully".to_string(),
+                    data: PurgeUserResponse::new(id, purge),
+                })
+            }
+            Err(err) => {
+                let msg = format!("Failed to purge user {id}: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                Err(ErrorResponse::from(err))
+            }
+        }
+    }
+
+    async fn import_users(
+        &self,
+        input: &ImportUsersRequest,
+    ) -> Result<ApiResponse<ImportUsersResponse>, ErrorResponse> {
+        let method = Method::Post;
+
+        let tracing_ctx = self.start_tracing(
+            "ImportUsers",
+            vec![
+                KeyValue::new("component", "user"),
+                KeyValue::new("rows", input.rows.len() as i64),
+                KeyValue::new("continue_on_error", input.continue_on_error),
+            ],
+        );
+
+        if input.rows.is_empty() || input.rows.len() > MAX_IMPORT_ROWS {
+            let msg = format!(
+                "Import must contain between 1 and {MAX_IMPORT_ROWS} rows, got {}",
+                input.rows.len()
+            );
+            let err = AppError::Custom(msg.clone());
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        // baris yang tidak valid tidak pernah sampai ke database
+        let mut results: Vec<Option<ImportUserResult>> = input
+            .rows
+            .iter()
+            .enumerate()
+            .map(|(index, row)| {
+                row.validate().err().map(|errors| {
+                    ImportUserResult::failed(
+                        index as i32 + 1,
+                        &row.email,
+                        format!("Invalid row: {errors}"),
+                    )
+                })
+            })
+            .collect();
+
+        let invalid = results.iter().flatten().count();
+        let skip_insert = invalid > 0 && !input.continue_on_error;
+        let mut pending = Vec::with_capacity(input.rows.len() - invalid);
+
+        for (index, row) in input.rows.iter().enumerate() {
+            if skip_insert || results[index].is_some() {
+                continue;
+            }
+
+            let row_no = index as i32 + 1;
+            let password = match self.hashing.hash_password(&row.password).await {
+                Ok(hashed) => hashed,
+                Err(e) => {
+                    let msg = format!("Failed to hash password for import row {row_no}: {e}");
+                    error!("{msg}");
+                    let err = AppError::HashingError(e);
+                    self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+            };
+
+            let (firstname, lastname) = row.split_name();
+            pending.push((
+                index,
+                CreateUserRequest {
+                    firstname,
+                    lastname,
+                    email: row.email.clone(),
+                    password: password.clone(),
+                    confirm_password: password,
+                    noc_transfer: random_vcc().ok(),
+                },
+            ));
+        }
+
+        let aborted = if skip_insert {
+            true
+        } else {
+            let inputs: Vec<CreateUserRequest> =
+                pending.iter().map(|(_, request)| request.clone()).collect();
+
+            let created = match self
+                .repository
+                .create_many(&inputs, input.continue_on_error)
+                .with_context(tracing_ctx.cx.clone())
+                .await
+            {
+                Ok(created) => created,
+                Err(err) => {
+                    let msg = format!("Failed to import users: {err}");
+                    self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+            };
+
+            let aborted = !input.continue_on_error && created.iter().any(|r| r.is_err());
+
+            for ((index, request), result) in pending.iter().zip(created) {
+                let row_no = *index as i32 + 1;
+                results[*index] = Some(match result {
+                    Ok(user) if !aborted => ImportUserResult::created(row_no, &user),
+                    Ok(_) => continue,
+                    Err(err) => ImportUserResult::failed(row_no, &request.email, err.to_string()),
+                });
+            }
+
+            aborted
+        };
+
+        // saat dibatalkan, baris yang sebenarnya valid ikut dilaporkan tidak diimport
+        let results: Vec<ImportUserResult> = results
+            .into_iter()
+            .zip(&input.rows)
+            .enumerate()
+            .map(|(index, (result, row))| {
+                result.unwrap_or_else(|| {
+                    ImportUserResult::failed(
+                        index as i32 + 1,
+                        &row.email,
+                        "Not imported: import aborted".to_string(),
+                    )
+                })
+            })
+            .collect();
+
+        let response = ImportUsersResponse::new(results);
+
+        let (status, message) = if aborted {
+            ("fail", "Import aborted, no users were created".to_string())
+        } else {
+            (
+                "success",
+                format!(
+                    "Imported {} of {} users",
+                    response.imported,
+                    input.rows.len()
+                ),
+            )
+        };
+
+        info!("{message}");
+        self.complete_tracing_success(&tracing_ctx, method, &message)
+            .await;
+
+        Ok(ApiResponse {
+            status: status.to_string(),
+            message,
+            data: response,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abstract_trait::TransferServiceTrait,
+        config::{HashAlgorithm, Hashing},
+        domain::request::{CreateSessionRequest, CreateTransferRequest, UpdateTransferRequest},
+        repository::memory::{InMemoryRepositories, offline_cache_store},
+        service::transfer::TransferService,
+        utils::ErrorLabels,
+    };
+    use axum::http::StatusCode;
+
+    async fn user_service(repos: &InMemoryRepositories) -> UserService {
+        UserService::new(
+            repos.user.clone(),
+            repos.session.clone(),
+            Arc::new(Hashing::new(HashAlgorithm::Bcrypt)),
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+            offline_cache_store(),
+        )
+        .await
+    }
+
+    async fn transfer_service(repos: &InMemoryRepositories) -> TransferService {
+        TransferService::new(
+            repos.transfer.clone(),
+            repos.saldo.clone(),
+            repos.user.clone(),
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+            offline_cache_store(),
+        )
+        .await
+    }
+
+    fn transfer(from: i32, to: i32) -> CreateTransferRequest {
+        CreateTransferRequest {
+            transfer_from: from,
+            transfer_to: to,
+            transfer_amount: 50_000,
+            note: None,
+            dry_run: false,
+            from_label: None,
+            to_label: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn frozen_sender_cannot_transfer_until_unfrozen() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let users = user_service(&repos).await;
+        let transfers = transfer_service(&repos).await;
+
+        users.freeze_user(sender.user_id).await.unwrap();
+
+        let err = transfers
+            .create_transfer(&transfer(sender.user_id, receiver.user_id))
+            .await
+            .unwrap_err();
+        assert_eq!(err.message, "Account is frozen");
+        assert_eq!(repos.balance_of(sender.user_id).await, 200_000);
+
+        // akun beku tetap bisa dibaca
+        let frozen = users.get_user(sender.user_id).await.unwrap();
+        assert!(frozen.data.is_some());
+
+        users.unfreeze_user(sender.user_id).await.unwrap();
+
+        transfers
+            .create_transfer(&transfer(sender.user_id, receiver.user_id))
+            .await
+            .unwrap();
+        assert_eq!(repos.balance_of(sender.user_id).await, 150_000);
+    }
+
+    #[tokio::test]
+    async fn frozen_receiver_blocks_incoming_transfer() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let users = user_service(&repos).await;
+
+        users.freeze_user(receiver.user_id).await.unwrap();
+
+        let err = transfer_service(&repos)
+            .await
+            .create_transfer(&transfer(sender.user_id, receiver.user_id))
+            .await
+            .unwrap_err();
+        assert_eq!(err.message, "Account is frozen");
+        assert_eq!(repos.balance_of(receiver.user_id).await, 50_000);
+    }
+
+    #[tokio::test]
+    async fn frozen_receiver_blocks_transfer_edit() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 50_000).await;
+        let users = user_service(&repos).await;
+        let transfers = transfer_service(&repos).await;
+
+        let original = transfers
+            .create_transfer(&transfer(sender.user_id, receiver.user_id))
+            .await
+            .unwrap()
+            .data;
+        users.freeze_user(receiver.user_id).await.unwrap();
+
+        let err = transfers
+            .update_transfer(&UpdateTransferRequest {
+                transfer_id: original.transfer_id,
+                transfer_from: sender.user_id,
+                transfer_to: receiver.user_id,
+                transfer_amount: 100_000,
+                note: None,
+            })
+            .await
+            .unwrap_err();
+        assert_eq!(err.message, "Account is frozen");
+        assert_eq!(repos.balance_of(sender.user_id).await, 150_000);
+        assert_eq!(repos.balance_of(receiver.user_id).await, 100_000);
+    }
+
+    #[tokio::test]
+    async fn missing_user_counts_a_not_found_error() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("known@example.com", 0).await;
+        let metrics = Arc::new(Metrics::new(vec![0.1]));
+        let service = UserService::new(
+            repos.user.clone(),
+            repos.session.clone(),
+            Arc::new(Hashing::new(HashAlgorithm::Bcrypt)),
+            metrics.clone(),
+            &mut Registry::default(),
+            offline_cache_store(),
+        )
+        .await;
+        let not_found = ErrorLabels {
+            method: Method::Get,
+            operation: "GetUser".to_string(),
+            error_kind: "not_found".to_string(),
+        };
+
+        service.get_user(user.user_id).await.unwrap();
+        assert_eq!(metrics.error_counter.get_or_create(&not_found).get(), 0);
+
+        let err = service.get_user(user.user_id + 1_000).await.unwrap_err();
+        assert_eq!(err.code, StatusCode::NOT_FOUND);
+        assert_eq!(metrics.error_counter.get_or_create(&not_found).get(), 1);
+    }
+
+    fn list_users(include_deleted: bool) -> FindAllUserRequest {
+        FindAllUserRequest {
+            page: 1,
+            page_size: 10,
+            search: String::new(),
+            include_deleted,
+            email: String::new(),
+            created_before: None,
+            created_after: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn deleted_user_is_hidden_until_restored() {
+        let repos = InMemoryRepositories::new();
+        let service = user_service(&repos).await;
+        let user = repos.seed_user("gone@example.com", 0).await;
+
+        service.delete_user(user.user_id, false).await.unwrap();
+
+        let err = service.get_user(user.user_id).await.unwrap_err();
+        assert_eq!(err.code, StatusCode::NOT_FOUND);
+        let listed = service.get_users(&list_users(false)).await.unwrap();
+        assert!(listed.data.iter().all(|u| u.id != user.user_id));
+        let listed = service.get_users(&list_users(true)).await.unwrap();
+        assert!(listed.data.iter().any(|u| u.id == user.user_id));
+
+        let restored = service.restore_user(user.user_id).await.unwrap();
+        assert_eq!(restored.data.id, user.user_id);
+        assert!(service.get_user(user.user_id).await.unwrap().data.is_some());
+    }
+
+    #[tokio::test]
+    async fn restoring_an_active_user_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        let service = user_service(&repos).await;
+        let user = repos.seed_user("active@example.com", 0).await;
+
+        let err = service.restore_user(user.user_id).await.unwrap_err();
+
+        assert_eq!(err.code, StatusCode::NOT_FOUND);
+        assert!(service.get_user(user.user_id).await.unwrap().data.is_some());
+    }
+
+    fn update(id: i32) -> UpdateUserRequest {
+        UpdateUserRequest {
+            id,
+            firstname: None,
+            lastname: None,
+            email: None,
+            password: None,
+            confirm_password: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn email_only_update_keeps_name_and_password() {
+        let repos = InMemoryRepositories::new();
+        let service = user_service(&repos).await;
+        let user = repos.seed_user("old@example.com", 0).await;
+
+        let updated = service
+            .update_user(&UpdateUserRequest {
+                email: Some("new@example.com".to_string()),
+                ..update(user.user_id)
+            })
+            .await
+            .unwrap();
+        assert_eq!(updated.data.email, "new@example.com");
+
+        let stored = repos.user.find_by_id(user.user_id).await.unwrap().unwrap();
+        assert_eq!(stored.email, "new@example.com");
+        assert_eq!(stored.firstname, user.firstname);
+        assert_eq!(stored.lastname, user.lastname);
+        assert_eq!(stored.password, user.password);
+    }
+
+    #[tokio::test]
+    async fn empty_update_is_a_no_op() {
+        let repos = InMemoryRepositories::new();
+        let service = user_service(&repos).await;
+        let user = repos.seed_user("same@example.com", 0).await;
+
+        service.update_user(&update(user.user_id)).await.unwrap();
+
+        let stored = repos.user.find_by_id(user.user_id).await.unwrap().unwrap();
+        assert_eq!(stored.email, user.email);
+        assert_eq!(stored.firstname, user.firstname);
+        assert_eq!(stored.lastname, user.lastname);
+        assert_eq!(stored.password, user.password);
+    }
+
+    #[tokio::test]
+    async fn purge_removes_the_user_and_related_rows() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("purged@example.com", 200_000).await;
+        let receiver = repos.seed_user("kept@example.com", 0).await;
+        let users = user_service(&repos).await;
+        transfer_service(&repos)
+            .await
+            .create_transfer(&transfer(sender.user_id, receiver.user_id))
+            .await
+            .unwrap();
+
+        let purged = users.purge_user(sender.user_id, false).await.unwrap().data;
+        assert_eq!(purged.saldos, 1);
+        assert_eq!(purged.transfers, 1);
+
+        let err = users.get_user(sender.user_id).await.unwrap_err();
+        assert_eq!(err.code, StatusCode::NOT_FOUND);
+        assert!(
+            repos
+                .saldo
+                .find_by_user_id(sender.user_id)
+                .await
+                .unwrap()
+                .is_none()
+        );
+        assert_eq!(repos.balance_of(receiver.user_id).await, 50_000);
+    }
+
+    #[tokio::test]
+    async fn transfer_counterparty_needs_force_to_purge() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 0).await;
+        let users = user_service(&repos).await;
+        transfer_service(&repos)
+            .await
+            .create_transfer(&transfer(sender.user_id, receiver.user_id))
+            .await
+            .unwrap();
+
+        let err = users.purge_user(receiver.user_id, false).await.unwrap_err();
+        assert!(err.message.contains("use force to purge"));
+        assert!(
+            users
+                .get_user(receiver.user_id)
+                .await
+                .unwrap()
+                .data
+                .is_some()
+        );
+        assert_eq!(repos.balance_of(receiver.user_id).await, 50_000);
+
+        let purged = users.purge_user(receiver.user_id, true).await.unwrap().data;
+        assert_eq!(purged.transfers, 1);
+        assert_eq!(purged.saldos, 1);
+    }
+
+    async fn open_session(repos: &InMemoryRepositories, user_id: i32, valid_for: chrono::Duration) {
+        repos
+            .session
+            .create(&CreateSessionRequest {
+                user_id,
+                token_hash: format!("hash-{user_id}-{}", valid_for.num_seconds()),
+                user_agent: None,
+                expires_at: chrono::Utc::now().naive_utc() + valid_for,
+            })
+            .await
+            .unwrap();
+    }
+
+    #[tokio::test]
+    async fn user_without_references_is_deletable() {
+        let repos = InMemoryRepositories::new();
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 0).await;
+        let users = user_service(&repos).await;
+        transfer_service(&repos)
+            .await
+            .create_transfer(&transfer(sender.user_id, receiver.user_id))
+            .await
+            .unwrap();
+        // transfer keluar dan sesi yang sudah kedaluwarsa tidak menghalangi
+        open_session(&repos, sender.user_id, chrono::Duration::hours(-1)).await;
+
+        let check = users.can_delete_user(sender.user_id).await.unwrap().data;
+        assert!(check.deletable);
+        assert_eq!((check.incoming_transfers, check.active_sessions), (0, 0));
+        assert!(check.blockers.is_empty());
+
+        users.delete_user(sender.user_id, false).await.unwrap();
+        let err = users.get_user(sender.user_id).await.unwrap_err();
+        assert_eq!(err.code, StatusCode::NOT_FOUND);
+    }
+
+    async fn seed_blocked_user(repos: &InMemoryRepositories) -> i32 {
+        let sender = repos.seed_user("sender@example.com", 200_000).await;
+        let receiver = repos.seed_user("receiver@example.com", 0).await;
+        transfer_service(repos)
+            .await
+            .create_transfer(&transfer(sender.user_id, receiver.user_id))
+            .await
+            .unwrap();
+        open_session(repos, receiver.user_id, chrono::Duration::hours(1)).await;
+
+        receiver.user_id
+    }
+
+    #[tokio::test]
+    async fn incoming_transfers_and_sessions_block_delete_without_force() {
+        let repos = InMemoryRepositories::new();
+        let receiver = seed_blocked_user(&repos).await;
+        let users = user_service(&repos).await;
+
+        let check = users.can_delete_user(receiver).await.unwrap().data;
+        assert!(!check.deletable);
+        assert_eq!((check.incoming_transfers, check.active_sessions), (1, 1));
+        assert_eq!(
+            check.blockers,
+            [
+                "1 incoming transfer(s) from other users",
+                "1 active session(s)"
+            ]
+        );
+
+        let err = users.delete_user(receiver, false).await.unwrap_err();
+        assert_eq!(err.code, StatusCode::BAD_REQUEST);
+        assert_eq!(
+            err.message,
+            format!(
+                "User ID {receiver} cannot be deleted: 1 incoming transfer(s) from other users, \
+                 1 active session(s); use force to delete anyway"
+            )
+        );
+        assert!(users.get_user(receiver).await.unwrap().data.is_some());
+    }
+
+    #[tokio::test]
+    async fn forced_delete_ignores_blockers() {
+        let repos = InMemoryRepositories::new();
+        let receiver = seed_blocked_user(&repos).await;
+        let users = user_service(&repos).await;
+
+        users.delete_user(receiver, true).await.unwrap();
+
+        let err = users.get_user(receiver).await.unwrap_err();
+        assert_eq!(err.code, StatusCode::NOT_FOUND);
+        let err = users.can_delete_user(receiver).await.unwrap_err();
+        assert_eq!(err.code, StatusCode::NOT_FOUND);
+    }
+
+    async fn import(
+        service: &UserService,
+        csv: &str,
+        continue_on_error: bool,
+    ) -> ApiResponse<ImportUsersResponse> {
+        let request = ImportUsersRequest::from_csv(csv, continue_on_error).unwrap();
+        service.import_users(&request).await.unwrap()
+    }
+
+    async fn is_registered(repos: &InMemoryRepositories, email: &str) -> bool {
+        repos.user.find_by_email_exists(email).await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn clean_csv_import_creates_every_user() {
+        let repos = InMemoryRepositories::new();
+        let service = user_service(&repos).await;
+
+        let response = import(
+            &service,
+            "name,email,password\n\
+             Budi Santoso,budi@example.com,password123\n\
+             Siti Aminah,siti@example.com,password123\n",
+            false,
+        )
+        .await;
+
+        assert_eq!(response.status, "success");
+        assert_eq!((response.data.imported, response.data.failed), (2, 0));
+        assert!(is_registered(&repos, "budi@example.com").await);
+        assert!(is_registered(&repos, "siti@example.com").await);
+    }
+
+    #[tokio::test]
+    async fn invalid_email_row_fails_alone_or_aborts_the_import() {
+        let repos = InMemoryRepositories::new();
+        let service = user_service(&repos).await;
+        let csv = "name,email,password\n\
+                   Budi Santoso,budi@example.com,password123\n\
+                   Siti Aminah,not-an-email,password123\n";
+
+        let aborted = import(&service, csv, false).await;
+        assert_eq!(aborted.status, "fail");
+        assert_eq!(aborted.data.imported, 0);
+        assert!(!is_registered(&repos, "budi@example.com").await);
+
+        let partial = import(&service, csv, true).await;
+        assert_eq!((partial.data.imported, partial.data.failed), (1, 1));
+        let failed = &partial.data.results[1];
+        assert_eq!(failed.row, 2);
+        assert!(failed.error.as_deref().unwrap().starts_with("Invalid row"));
+        assert!(is_registered(&repos, "budi@example.com").await);
+    }
+
+    #[tokio::test]
+    async fn duplicate_email_fails_its_row_or_aborts_the_import() {
+        let repos = InMemoryRepositories::new();
+        let service = user_service(&repos).await;
+        repos.seed_user("taken@example.com", 0).await;
+        let csv = "name,email,password\n\
+                   Budi Santoso,budi@example.com,password123\n\
+                   Siti Aminah,taken@example.com,password123\n";
+
+        let aborted = import(&service, csv, false).await;
+        assert_eq!(aborted.status, "fail");
+        assert_eq!(aborted.data.imported, 0);
+        assert!(!is_registered(&repos, "budi@example.com").await);
+
+        let partial = import(&service, csv, true).await;
+        assert_eq!(partial.status, "success");
+        assert_eq!((partial.data.imported, partial.data.failed), (1, 1));
+        assert!(partial.data.results[0].user_id.is_some());
+        assert_eq!(partial.data.results[1].user_id, None);
+        assert!(is_registered(&repos, "budi@example.com").await);
+    }
 }
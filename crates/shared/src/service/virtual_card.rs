@@ -0,0 +1,495 @@
+use async_trait::async_trait;
+use chrono::{Datelike, Utc};
+use opentelemetry::{
+    Context, KeyValue,
+    context::FutureExt,
+    global::{self, BoxedTracer},
+    trace::{Span, SpanKind, TraceContextExt, Tracer},
+};
+use prometheus_client::registry::Registry;
+use std::sync::Arc;
+use tokio::time::Instant;
+use tonic::Request;
+use tracing::{error, info};
+
+use crate::{
+    abstract_trait::{DynUserRepository, DynVirtualCardRepository, VirtualCardServiceTrait},
+    domain::{
+        request::{CreateVirtualCardRequest, FindVirtualCardsRequest, PageRequest},
+        response::{
+            ApiResponse, ApiResponsePagination, ErrorResponse,
+            pagination::Pagination,
+            virtual_card::{VirtualCardResponse, VirtualCardSummaryResponse},
+        },
+    },
+    utils::{
+        AppError, MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext,
+        luhn_valid, random_vcc,
+    },
+};
+
+// masa berlaku kartu virtual dihitung dari bulan pembuatan
+const VIRTUAL_CARD_VALIDITY_YEARS: i32 = 3;
+
+#[derive(Clone)]
+pub struct VirtualCardService {
+    virtual_card_repository: DynVirtualCardRepository,
+    user_repository: DynUserRepository,
+    metrics: Arc<Metrics>,
+}
+
+impl std::fmt::Debug for VirtualCardService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("VirtualCardService")
+            .field("virtual_card_repository", &"DynVirtualCardRepository")
+            .field("user_repository", &"DynUserRepository")
+            .finish()
+    }
+}
+
+impl VirtualCardService {
+    pub async fn new(
+        virtual_card_repository: DynVirtualCardRepository,
+        user_repository: DynUserRepository,
+        metrics: Arc<Metrics>,
+        registry: &mut Registry,
+    ) -> Self {
+        registry.register(
+            "virtual_card_service_request_counter",
+            "Total number of requests to the VirtualCardService",
+            metrics.request_counter.clone(),
+        );
+        registry.register(
+            "virtual_card_service_request_duration",
+            "Histogram of requests durations for the VirtualCardService",
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "virtual_card_service_error_counter",
+            "Total number of failed requests to the VirtualCardService by error kind",
+            metrics.error_counter.clone(),
+        );
+
+        Self {
+            virtual_card_repository,
+            user_repository,
+            metrics,
+        }
+    }
+
+    fn get_tracer(&self) -> BoxedTracer {
+        global::tracer("virtual-card-service")
+    }
+
+    fn inject_trace_context<T>(&self, cx: &Context, request: &mut Request<T>) {
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(cx, &mut MetadataInjector(request.metadata_mut()))
+        });
+    }
+
+    fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
+        let start_time = Instant::now();
+        let tracer = self.get_tracer();
+        let mut span = tracer
+            .span_builder(operation_name.to_string())
+            .with_kind(SpanKind::Server)
+            .with_attributes(attributes)
+            .start(&tracer);
+
+        info!("Starting operation: {operation_name}");
+
+        span.add_event(
+            "Operation started",
+            vec![
+                KeyValue::new("operation", operation_name.to_string()),
+                KeyValue::new("timestamp", start_time.elapsed().as_secs_f64().to_string()),
+            ],
+        );
+
+        let cx = Context::current_with_span(span);
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
+    }
+
+    async fn complete_tracing_success(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        message: &str,
+    ) {
+        self.complete_tracing_internal(tracing_ctx, method, true, message)
+            .await;
+    }
+
+    async fn complete_tracing_error(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        error_kind: &str,
+        error_message: &str,
+    ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
+        self.complete_tracing_internal(tracing_ctx, method, false, error_message)
+            .await;
+    }
+
+    async fn complete_tracing_internal(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        is_success: bool,
+        message: &str,
+    ) {
+        let status_str = if is_success { "SUCCESS" } else { "ERROR" };
+        let status = if is_success {
+            StatusUtils::Success
+        } else {
+            StatusUtils::Error
+        };
+        let elapsed = tracing_ctx.start_time.elapsed().as_secs_f64();
+
+        tracing_ctx.cx.span().add_event(
+            "Operation completed",
+            vec![
+                KeyValue::new("status", status_str),
+                KeyValue::new("duration_secs", elapsed.to_string()),
+                KeyValue::new("message", message.to_string()),
+            ],
+        );
+
+        if is_success {
+            info!("Operation completed successfully: {message}");
+        } else {
+            error!("Operation failed: {message}");
+        }
+
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
+
+        tracing_ctx.cx.span().end();
+    }
+}
+
+#[async_trait]
+impl VirtualCardServiceTrait for VirtualCardService {
+    async fn generate_virtual_card(
+        &self,
+        user_id: i32,
+    ) -> Result<ApiResponse<VirtualCardResponse>, ErrorResponse> {
+        let method = Method::Post;
+
+        let tracing_ctx = self.start_tracing(
+            "GenerateVirtualCard",
+            vec![
+                KeyValue::new("component", "virtual_card"),
+                KeyValue::new("user_id", user_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(user_id);
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        match self
+            .user_repository
+            .find_by_id(user_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(user)) if user.is_frozen() => {
+                let msg = format!("Account {user_id} is frozen");
+                let err = AppError::Custom("Account is frozen".to_string());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                let msg = format!("User with id {user_id} not found");
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to fetch user {user_id}: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        }
+
+        let card_number = match random_vcc() {
+            Ok(number) if luhn_valid(&number) => number,
+            _ => {
+                let msg = "Generated card number failed Luhn validation".to_string();
+                let err = AppError::InternalError(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        let today = Utc::now().date_naive();
+        let input = CreateVirtualCardRequest {
+            user_id,
+            card_number,
+            expiry_month: today.month() as i32,
+            expiry_year: today.year() + VIRTUAL_CARD_VALIDITY_YEARS,
+        };
+
+        let card = match self
+            .virtual_card_repository
+            .create(&input)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(card) => card,
+            Err(err) => {
+                let msg = format!("Failed to create virtual card for user {user_id}: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        let response = ApiResponse {
+            status: "success".to_string(),
+            message: "Virtual card generated successfully".to_string(),
+            data: VirtualCardResponse::from(card),
+        };
+
+        self.complete_tracing_success(&tracing_ctx, method, "Virtual card generated successfully")
+            .await;
+
+        Ok(response)
+    }
+
+    async fn list_virtual_cards(
+        &self,
+        user_id: i32,
+        req: &FindVirtualCardsRequest,
+    ) -> Result<ApiResponsePagination<Vec<VirtualCardSummaryResponse>>, ErrorResponse> {
+        let method = Method::Get;
+
+        let PageRequest { page, page_size } = PageRequest::new(req.page, req.page_size)
+            .bounded()
+            .map_err(ErrorResponse::from)?;
+
+        let tracing_ctx = self.start_tracing(
+            "ListVirtualCards",
+            vec![
+                KeyValue::new("component", "virtual_card"),
+                KeyValue::new("user_id", user_id.to_string()),
+                KeyValue::new("page", page.to_string()),
+                KeyValue::new("page_size", page_size.to_string()),
+                KeyValue::new("include_revoked", req.include_revoked),
+            ],
+        );
+
+        let mut request = Request::new(user_id);
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let (cards, total_items) = match self
+            .virtual_card_repository
+            .find_by_user_id(user_id, page, page_size, req.include_revoked)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(result) => result,
+            Err(err) => {
+                let msg = format!("Failed to list virtual cards for user {user_id}: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        let message = if cards.is_empty() {
+            format!("No virtual cards found for user with id {user_id}")
+        } else {
+            "Virtual cards retrieved successfully".to_string()
+        };
+
+        let response = ApiResponsePagination {
+            status: "success".to_string(),
+            message,
+            data: cards
+                .into_iter()
+                .map(VirtualCardSummaryResponse::from)
+                .collect(),
+            pagination: Pagination::new(page, page_size, total_items),
+        };
+
+        self.complete_tracing_success(&tracing_ctx, method, "Virtual cards retrieved successfully")
+            .await;
+
+        Ok(response)
+    }
+
+    async fn revoke_virtual_card(
+        &self,
+        user_id: i32,
+        virtual_card_id: i32,
+    ) -> Result<ApiResponse<VirtualCardSummaryResponse>, ErrorResponse> {
+        let method = Method::Post;
+
+        let tracing_ctx = self.start_tracing(
+            "RevokeVirtualCard",
+            vec![
+                KeyValue::new("component", "virtual_card"),
+                KeyValue::new("user_id", user_id.to_string()),
+                KeyValue::new("virtual_card_id", virtual_card_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(virtual_card_id);
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        // kartu milik user lain diperlakukan sama dengan kartu yang tidak ada
+        let card = match self
+            .virtual_card_repository
+            .revoke(user_id, virtual_card_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(card) => card,
+            Err(err) => {
+                let msg = format!(
+                    "Failed to revoke virtual card {virtual_card_id} for user {user_id}: {err}"
+                );
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        let response = ApiResponse {
+            status: "success".to_string(),
+            message: "Virtual card revoked successfully".to_string(),
+            data: VirtualCardSummaryResponse::from(card),
+        };
+
+        self.complete_tracing_success(&tracing_ctx, method, "Virtual card revoked successfully")
+            .await;
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::memory::InMemoryRepositories;
+    use axum::http::StatusCode;
+
+    async fn virtual_card_service(repos: &InMemoryRepositories) -> VirtualCardService {
+        VirtualCardService::new(
+            repos.virtual_card.clone(),
+            repos.user.clone(),
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+        )
+        .await
+    }
+
+    fn find(include_revoked: bool) -> FindVirtualCardsRequest {
+        FindVirtualCardsRequest {
+            page: 1,
+            page_size: 10,
+            include_revoked,
+        }
+    }
+
+    async fn generate(service: &VirtualCardService, user_id: i32) -> VirtualCardResponse {
+        service.generate_virtual_card(user_id).await.unwrap().data
+    }
+
+    #[tokio::test]
+    async fn lists_only_the_users_own_cards_masked_newest_first() {
+        let repos = InMemoryRepositories::new();
+        let owner = repos.seed_user("owner@example.com", 0).await.user_id;
+        let other = repos.seed_user("other@example.com", 0).await.user_id;
+        let service = virtual_card_service(&repos).await;
+
+        let first = generate(&service, owner).await;
+        let second = generate(&service, owner).await;
+        generate(&service, other).await;
+
+        let listed = service
+            .list_virtual_cards(owner, &find(false))
+            .await
+            .unwrap();
+
+        assert_eq!(listed.pagination.total_items, 2);
+        assert_eq!(
+            listed.data.iter().map(|card| card.id).collect::<Vec<_>>(),
+            [second.id, first.id]
+        );
+        let card = &listed.data[1];
+        assert_eq!(card.masked_number, first.masked_number);
+        assert_ne!(card.masked_number, first.card_number);
+        assert!(card.masked_number.ends_with(&first.card_number[12..]));
+        assert_eq!(card.expiry, first.expiry);
+        assert!(card.created_at.is_some());
+        assert!(card.revoked_at.is_none());
+    }
+
+    #[tokio::test]
+    async fn revoked_cards_are_hidden_unless_requested() {
+        let repos = InMemoryRepositories::new();
+        let owner = repos.seed_user("owner@example.com", 0).await.user_id;
+        let service = virtual_card_service(&repos).await;
+
+        let kept = generate(&service, owner).await;
+        let revoked = generate(&service, owner).await;
+
+        let response = service
+            .revoke_virtual_card(owner, revoked.id)
+            .await
+            .unwrap();
+        assert_eq!(response.data.id, revoked.id);
+        assert!(response.data.revoked_at.is_some());
+
+        let active = service
+            .list_virtual_cards(owner, &find(false))
+            .await
+            .unwrap();
+        assert_eq!(active.pagination.total_items, 1);
+        assert_eq!(active.data[0].id, kept.id);
+
+        let all = service
+            .list_virtual_cards(owner, &find(true))
+            .await
+            .unwrap();
+        assert_eq!(all.pagination.total_items, 2);
+        let revoked_card = all.data.iter().find(|card| card.id == revoked.id).unwrap();
+        assert!(revoked_card.revoked_at.is_some());
+    }
+
+    #[tokio::test]
+    async fn revoking_a_revoked_or_foreign_card_is_not_found() {
+        let repos = InMemoryRepositories::new();
+        let owner = repos.seed_user("owner@example.com", 0).await.user_id;
+        let other = repos.seed_user("other@example.com", 0).await.user_id;
+        let service = virtual_card_service(&repos).await;
+        let card = generate(&service, owner).await;
+
+        let err = service
+            .revoke_virtual_card(other, card.id)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, StatusCode::NOT_FOUND);
+
+        service.revoke_virtual_card(owner, card.id).await.unwrap();
+        let err = service
+            .revoke_virtual_card(owner, card.id)
+            .await
+            .unwrap_err();
+        assert_eq!(err.code, StatusCode::NOT_FOUND);
+    }
+}
@@ -0,0 +1,344 @@
+use async_trait::async_trait;
+use opentelemetry::{
+    Context, KeyValue,
+    context::FutureExt,
+    global::{self, BoxedTracer},
+    trace::{Span, SpanKind, TraceContextExt, Tracer},
+};
+use prometheus_client::registry::Registry;
+use std::sync::Arc;
+use tokio::time::Instant;
+use tonic::Request;
+use tracing::{error, info};
+
+use crate::{
+    abstract_trait::{DynOutboxRepository, WebhookServiceTrait},
+    domain::response::{ApiResponse, ErrorResponse, webhook::WebhookDeliveryResponse},
+    model::outbox::OutboxStatus,
+    utils::{AppError, MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+};
+
+#[derive(Clone)]
+pub struct WebhookService {
+    outbox_repository: DynOutboxRepository,
+    metrics: Arc<Metrics>,
+}
+
+impl std::fmt::Debug for WebhookService {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("WebhookService")
+            .field("outbox_repository", &"DynOutboxRepository")
+            .finish()
+    }
+}
+
+impl WebhookService {
+    pub async fn new(
+        outbox_repository: DynOutboxRepository,
+        metrics: Arc<Metrics>,
+        registry: &mut Registry,
+    ) -> Self {
+        registry.register(
+            "webhook_service_request_counter",
+            "Total number of requests to the WebhookService",
+            metrics.request_counter.clone(),
+        );
+        registry.register(
+            "webhook_service_request_duration",
+            "Histogram of requests durations for the WebhookService",
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "webhook_service_error_counter",
+            "Total number of failed requests to the WebhookService by error kind",
+            metrics.error_counter.clone(),
+        );
+
+        Self {
+            outbox_repository,
+            metrics,
+        }
+    }
+
+    fn get_tracer(&self) -> BoxedTracer {
+        global::tracer("webhook-service")
+    }
+
+    fn inject_trace_context<T>(&self, cx: &Context, request: &mut Request<T>) {
+        global::get_text_map_propagator(|propagator| {
+            propagator.inject_context(cx, &mut MetadataInjector(request.metadata_mut()))
+        });
+    }
+
+    fn start_tracing(&self, operation_name: &str, attributes: Vec<KeyValue>) -> TracingContext {
+        let start_time = Instant::now();
+        let tracer = self.get_tracer();
+        let mut span = tracer
+            .span_builder(operation_name.to_string())
+            .with_kind(SpanKind::Server)
+            .with_attributes(attributes)
+            .start(&tracer);
+
+        info!("Starting operation: {operation_name}");
+
+        span.add_event(
+            "Operation started",
+            vec![
+                KeyValue::new("operation", operation_name.to_string()),
+                KeyValue::new("timestamp", start_time.elapsed().as_secs_f64().to_string()),
+            ],
+        );
+
+        let cx = Context::current_with_span(span);
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
+    }
+
+    async fn complete_tracing_success(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        message: &str,
+    ) {
+        self.complete_tracing_internal(tracing_ctx, method, true, message)
+            .await;
+    }
+
+    async fn complete_tracing_error(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        error_kind: &str,
+        error_message: &str,
+    ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
+        self.complete_tracing_internal(tracing_ctx, method, false, error_message)
+            .await;
+    }
+
+    async fn complete_tracing_internal(
+        &self,
+        tracing_ctx: &TracingContext,
+        method: Method,
+        is_success: bool,
+        message: &str,
+    ) {
+        let status_str = if is_success { "SUCCESS" } else { "ERROR" };
+        let status = if is_success {
+            StatusUtils::Success
+        } else {
+            StatusUtils::Error
+        };
+        let elapsed = tracing_ctx.start_time.elapsed().as_secs_f64();
+
+        tracing_ctx.cx.span().add_event(
+            "Operation completed",
+            vec![
+                KeyValue::new("status", status_str),
+                KeyValue::new("duration_secs", elapsed.to_string()),
+                KeyValue::new("message", message.to_string()),
+            ],
+        );
+
+        if is_success {
+            info!("Operation completed successfully: {message}");
+        } else {
+            error!("Operation failed: {message}");
+        }
+
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
+
+        tracing_ctx.cx.span().end();
+    }
+}
+
+#[async_trait]
+impl WebhookServiceTrait for WebhookService {
+    async fn retry_webhook(
+        &self,
+        event_id: i64,
+    ) -> Result<ApiResponse<WebhookDeliveryResponse>, ErrorResponse> {
+        let method = Method::Post;
+
+        let tracing_ctx = self.start_tracing(
+            "RetryWebhook",
+            vec![
+                KeyValue::new("component", "webhook"),
+                KeyValue::new("event_id", event_id.to_string()),
+            ],
+        );
+
+        let mut request = Request::new(event_id);
+        self.inject_trace_context(&tracing_ctx.cx, &mut request);
+
+        let already_delivered = || {
+            let msg = format!("Webhook event {event_id} has already been delivered");
+            (msg.clone(), AppError::Custom(msg))
+        };
+
+        match self
+            .outbox_repository
+            .find_by_id(event_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(event)) if event.status == OutboxStatus::Delivered.as_str() => {
+                let (msg, err) = already_delivered();
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Ok(Some(_)) => {}
+            Ok(None) => {
+                let msg = format!("Webhook event {event_id} not found");
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to fetch webhook event {event_id}: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        }
+
+        // event yang sedang diklaim worker (atau terkirim di antara pengecekan dan requeue)
+        // tidak di-requeue supaya tidak terkirim dua kali
+        let event = match self
+            .outbox_repository
+            .requeue(event_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
+            Ok(Some(event)) => event,
+            Ok(None) => {
+                let msg = format!("Webhook event {event_id} is currently being delivered");
+                let err = AppError::Custom(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to requeue webhook event {event_id}: {err}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        let response = ApiResponse {
+            status: "success".to_string(),
+            message: "Webhook delivery requeued successfully".to_string(),
+            data: WebhookDeliveryResponse::from(event),
+        };
+
+        self.complete_tracing_success(
+            &tracing_ctx,
+            method,
+            "Webhook delivery requeued successfully",
+        )
+        .await;
+
+        Ok(response)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{model::outbox::OutboxEvent, repository::memory::InMemoryRepositories};
+    use axum::http::StatusCode;
+    use chrono::Utc;
+    use std::time::Duration;
+
+    async fn webhook_service(repos: &InMemoryRepositories) -> WebhookService {
+        WebhookService::new(
+            repos.outbox.clone(),
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+        )
+        .await
+    }
+
+    fn seed_event(repos: &InMemoryRepositories, id: i64, status: OutboxStatus) {
+        let now = Utc::now().naive_utc();
+        repos.database.tables().outbox.push(OutboxEvent {
+            id,
+            event_type: "transfer.completed".to_string(),
+            payload: "{}".to_string(),
+            status: status.as_str().to_string(),
+            attempts: 8,
+            last_error: Some("HTTP 503".to_string()),
+            next_attempt_at: now + chrono::Duration::days(1),
+            created_at: Some(now),
+            delivered_at: (status == OutboxStatus::Delivered).then_some(now),
+        });
+    }
+
+    #[tokio::test]
+    async fn retrying_a_failed_event_requeues_it_for_the_worker() {
+        let repos = InMemoryRepositories::new();
+        seed_event(&repos, 1, OutboxStatus::Failed);
+        let service = webhook_service(&repos).await;
+
+        let delivery = service.retry_webhook(1).await.unwrap().data;
+
+        assert_eq!(delivery.status, OutboxStatus::Pending.as_str());
+        assert_eq!(delivery.attempts, 0);
+        assert!(delivery.last_error.is_none());
+
+        let claimed = repos
+            .outbox
+            .claim_pending(10, Duration::from_secs(60))
+            .await
+            .unwrap();
+        assert_eq!(claimed.iter().map(|e| e.id).collect::<Vec<_>>(), [1]);
+    }
+
+    #[tokio::test]
+    async fn retrying_a_delivered_event_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        seed_event(&repos, 1, OutboxStatus::Delivered);
+        let service = webhook_service(&repos).await;
+
+        let err = service.retry_webhook(1).await.unwrap_err();
+
+        assert_eq!(err.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.message, "Webhook event 1 has already been delivered");
+        let event = repos.outbox.find_by_id(1).await.unwrap().unwrap();
+        assert_eq!(event.status, OutboxStatus::Delivered.as_str());
+        assert_eq!(event.attempts, 8);
+    }
+
+    #[tokio::test]
+    async fn retrying_an_unknown_event_is_not_found() {
+        let repos = InMemoryRepositories::new();
+        let service = webhook_service(&repos).await;
+
+        let err = service.retry_webhook(42).await.unwrap_err();
+
+        assert_eq!(err.code, StatusCode::NOT_FOUND);
+    }
+
+    #[tokio::test]
+    async fn retrying_an_event_leased_by_a_worker_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        seed_event(&repos, 1, OutboxStatus::Pending);
+        let service = webhook_service(&repos).await;
+
+        let err = service.retry_webhook(1).await.unwrap_err();
+
+        assert_eq!(err.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.message, "Webhook event 1 is currently being delivered");
+        let event = repos.outbox.find_by_id(1).await.unwrap().unwrap();
+        assert_eq!(event.attempts, 8);
+    }
+}
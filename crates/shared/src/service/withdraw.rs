@@ -5,35 +5,38 @@ use crate::{
     cache::CacheStore,
     domain::{
         request::{
-            CreateWithdrawRequest, FindAllWithdrawRequest, UpdateSaldoWithdraw,
-            UpdateWithdrawRequest,
+            CreateWithdrawRequest, FindAllWithdrawRequest, PageRequest, UpdateWithdrawRequest,
         },
         response::{
             ApiResponse, ApiResponsePagination, ErrorResponse, pagination::Pagination,
             withdraw::WithdrawResponse,
         },
     },
-    utils::{AppError, MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext},
+    service::account::invalidate_account_summary,
+    utils::{
+        AppError, MetadataInjector, Method, Metrics, Status as StatusUtils, TracingContext,
+        cancellable, checked_amount,
+    },
 };
 use async_trait::async_trait;
-use chrono::Utc;
 use opentelemetry::{
     Context, KeyValue,
+    context::FutureExt,
     global::{self, BoxedTracer},
     trace::{Span, SpanKind, TraceContextExt, Tracer},
 };
 use prometheus_client::registry::Registry;
-use std::{sync::Arc, time::Duration};
-use tokio::{sync::Mutex, time::Instant};
+use std::sync::Arc;
+use tokio::time::Instant;
 use tonic::Request;
-use tracing::{error, info};
+use tracing::{error, info, warn};
 
 #[derive(Clone)]
 pub struct WithdrawService {
     withdraw_repository: DynWithdrawRepository,
     saldo_repository: DynSaldoRepository,
     user_repository: DynUserRepository,
-    metrics: Arc<Mutex<Metrics>>,
+    metrics: Arc<Metrics>,
     cache_store: Arc<CacheStore>,
 }
 
@@ -52,19 +55,24 @@ impl WithdrawService {
         withdraw_repository: DynWithdrawRepository,
         saldo_repository: DynSaldoRepository,
         user_repository: DynUserRepository,
-        metrics: Arc<Mutex<Metrics>>,
+        metrics: Arc<Metrics>,
         registry: &mut Registry,
         cache_store: Arc<CacheStore>,
     ) -> Self {
         registry.register(
             "withdraw_service_request_counter",
             "Total number of requests to the WithdrawService",
-            metrics.lock().await.request_counter.clone(),
+            metrics.request_counter.clone(),
         );
         registry.register(
             "withdraw_service_request_duration",
             "Histogram of requests durations for the WithdrawService",
-            metrics.lock().await.request_duration.clone(),
+            metrics.request_duration.clone(),
+        );
+        registry.register(
+            "withdraw_service_error_counter",
+            "Total number of failed requests to the WithdrawService by error kind",
+            metrics.error_counter.clone(),
         );
 
         Self {
@@ -106,7 +114,11 @@ impl WithdrawService {
         );
 
         let cx = Context::current_with_span(span);
-        TracingContext { cx, start_time }
+        TracingContext {
+            cx,
+            start_time,
+            operation: operation_name.to_string(),
+        }
     }
 
     async fn complete_tracing_success(
@@ -123,8 +135,12 @@ impl WithdrawService {
         &self,
         tracing_ctx: &TracingContext,
         method: Method,
+        error_kind: &str,
         error_message: &str,
     ) {
+        self.metrics
+            .record_error(method.clone(), &tracing_ctx.operation, error_kind);
+
         self.complete_tracing_internal(tracing_ctx, method, false, error_message)
             .await;
     }
@@ -159,10 +175,249 @@ impl WithdrawService {
             error!("Operation failed: {message}");
         }
 
-        self.metrics.lock().await.record(method, status, elapsed);
+        self.metrics
+            .record(method, status, &tracing_ctx.operation, elapsed);
 
         tracing_ctx.cx.span().end();
     }
+
+    // dijalankan selama memegang lock saldo user, dari baca saldo sampai withdraw tersimpan
+    async fn create_withdraw_locked(
+        &self,
+        input: &CreateWithdrawRequest,
+        tracing_ctx: &TracingContext,
+        method: Method,
+    ) -> Result<ApiResponse<WithdrawResponse>, ErrorResponse> {
+        // user dan saldo utama diambil sekaligus dengan satu query join
+        let (user, saldo) = match self
+            .saldo_repository
+            .find_with_user_by_user_id(input.user_id)
+            .await
+        {
+            Ok(Some(found)) => (found.user, found.saldo),
+            Ok(None) => {
+                let msg = format!("User with id {} not found", input.user_id);
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to find user {}: {err}", input.user_id);
+                error!("{msg}");
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        if user.is_frozen() {
+            let msg = format!("Account {} is frozen", input.user_id);
+            warn!("{msg}");
+            let err = AppError::Custom("Account is frozen".to_string());
+            self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        // saldo dibuat bersama user saat registrasi, jadi saldo kosong berarti data tidak konsisten
+        let Some(saldo) = saldo else {
+            let msg = format!("Saldo missing for user_id {}", input.user_id);
+            error!("{msg}");
+            let err = AppError::InternalError(msg.clone());
+            self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        };
+
+        info!(
+            "Saldo found for user_id: {}. Current balance: {}",
+            input.user_id, saldo.total_balance
+        );
+
+        let new_total_balance =
+            match checked_amount(saldo.total_balance.checked_sub(input.withdraw_amount)) {
+                Ok(balance) => balance,
+                Err(err) => {
+                    let msg = format!("Failed to withdraw for user {}: {err}", input.user_id);
+                    self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+            };
+
+        // akun dengan overdraft boleh turun sampai -overdraft_limit
+        if new_total_balance < user.balance_floor() {
+            let msg = format!(
+                "Insufficient balance for user_id: {}. Attempted withdrawal: {}, overdraft limit: {}",
+                input.user_id, input.withdraw_amount, user.overdraft_limit
+            );
+            error!("{msg}");
+            let err = AppError::Custom("Insufficient balance".to_string());
+            self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        info!("User has sufficient balance for withdrawal");
+
+        // saldo dipotong relatif di repository dalam transaksi yang sama dengan insert withdraw,
+        // jadi konfirmasi topup atau penyesuaian admin yang masuk bersamaan tidak tertimpa
+        let withdraw_create_result =
+            match self.withdraw_repository.create_with_settlement(input).await {
+                Ok(w) => w,
+                Err(err) => {
+                    let msg = format!("Failed to create withdraw: {err}");
+                    error!("{msg}");
+                    self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                        .await;
+                    return Err(ErrorResponse::from(err));
+                }
+            };
+
+        info!(
+            "Saldo balance debited for user_id: {}. Expected balance: {new_total_balance}",
+            input.user_id
+        );
+
+        invalidate_account_summary(&self.cache_store, &[input.user_id]);
+
+        info!(
+            "Withdraw created successfully for user_id: {}",
+            input.user_id
+        );
+
+        self.complete_tracing_success(tracing_ctx, method, "Withdraw created successfully")
+            .await;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Withdraw created successfully".to_string(),
+            data: withdraw_create_result.into(),
+        })
+    }
+
+    // dijalankan selama memegang lock saldo user, dari baca saldo sampai saldo baru tersimpan
+    async fn update_withdraw_locked(
+        &self,
+        input: &UpdateWithdrawRequest,
+        tracing_ctx: &TracingContext,
+        method: Method,
+    ) -> Result<ApiResponse<WithdrawResponse>, ErrorResponse> {
+        // withdraw milik user lain diperlakukan sama dengan withdraw yang tidak ada
+        let withdraw = match self.withdraw_repository.find_by_id(input.withdraw_id).await {
+            Ok(Some(withdraw)) if withdraw.user_id == input.user_id => withdraw,
+            Ok(_) => {
+                let msg = format!("Withdraw with id {} not found", input.withdraw_id);
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to check withdraw {}: {err}", input.withdraw_id);
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        // user dan saldo utama diambil sekaligus dengan satu query join
+        let (user, saldo) = match self
+            .saldo_repository
+            .find_with_user_by_user_id(input.user_id)
+            .await
+        {
+            Ok(Some(found)) => (found.user, found.saldo),
+            Ok(None) => {
+                let msg = format!("User with id {} not found", input.user_id);
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+            Err(err) => {
+                let msg = format!("Failed to fetch saldo for user_id {}: {err}", input.user_id);
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        if user.is_frozen() {
+            let msg = format!("Account {} is frozen", input.user_id);
+            warn!("{msg}");
+            let err = AppError::Custom("Account is frozen".to_string());
+            self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        // saldo dibuat bersama user saat registrasi, jadi saldo kosong berarti data tidak konsisten
+        let Some(saldo) = saldo else {
+            let msg = format!("Saldo missing for user_id {}", input.user_id);
+            error!("{msg}");
+            let err = AppError::InternalError(msg.clone());
+            self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        };
+
+        // nominal lama sudah terpotong saat withdraw dibuat, jadi hanya selisihnya yang dipotong
+        let new_total_balance = match checked_amount(
+            input
+                .withdraw_amount
+                .checked_sub(withdraw.withdraw_amount)
+                .and_then(|difference| saldo.total_balance.checked_sub(difference)),
+        ) {
+            Ok(balance) => balance,
+            Err(err) => {
+                let msg = format!("Failed to withdraw for user {}: {err}", input.user_id);
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        // batas bawah sama dengan saat withdraw dibuat, termasuk overdraft
+        if new_total_balance < user.balance_floor() {
+            let msg = format!(
+                "Insufficient balance for user_id: {}. Attempted withdrawal update: {} → {}, overdraft limit: {}",
+                input.user_id,
+                withdraw.withdraw_amount,
+                input.withdraw_amount,
+                user.overdraft_limit
+            );
+            error!("{msg}");
+            let err = AppError::Custom("Insufficient balance".to_string());
+            self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                .await;
+            return Err(ErrorResponse::from(err));
+        }
+
+        // saldo dan baris withdraw berubah dalam satu transaksi database
+        let updated_withdraw = match self.withdraw_repository.update_with_settlement(input).await {
+            Ok(withdraw) => withdraw,
+            Err(err) => {
+                let msg = format!("Withdraw update failed: {err}");
+                error!("{msg}");
+                self.complete_tracing_error(tracing_ctx, method, err.kind(), &msg)
+                    .await;
+                return Err(ErrorResponse::from(err));
+            }
+        };
+
+        invalidate_account_summary(&self.cache_store, &[input.user_id]);
+
+        self.complete_tracing_success(tracing_ctx, method, "Withdraw updated successfully")
+            .await;
+
+        Ok(ApiResponse {
+            status: "success".to_string(),
+            message: "Withdraw updated successfully".to_string(),
+            data: updated_withdraw.into(),
+        })
+    }
 }
 
 #[async_trait]
@@ -171,23 +426,20 @@ impl WithdrawServiceTrait for WithdrawService {
         &self,
         req: &FindAllWithdrawRequest,
     ) -> Result<ApiResponsePagination<Vec<WithdrawResponse>>, ErrorResponse> {
-        let page = if req.page > 0 { req.page } else { 1 };
-        let page_size = if req.page_size > 0 { req.page_size } else { 10 };
+        let PageRequest { page, page_size } = PageRequest::new(req.page, req.page_size)
+            .bounded()
+            .map_err(ErrorResponse::from)?;
         let search = if req.search.is_empty() {
             None
         } else {
             Some(req.search.clone())
         };
 
-        let (withdraws, total_items) = self
-            .withdraw_repository
-            .find_all(page, page_size, search)
-            .await?;
+        let (withdraws, total_items) =
+            cancellable(self.withdraw_repository.find_all(page, page_size, search)).await?;
 
         info!("Found {} withdraws", withdraws.len());
 
-        let total_pages = (total_items as f64 / page_size as f64).ceil() as i32;
-
         let withdraw_responses: Vec<WithdrawResponse> =
             withdraws.into_iter().map(WithdrawResponse::from).collect();
 
@@ -195,12 +447,7 @@ impl WithdrawServiceTrait for WithdrawService {
             status: "success".to_string(),
             message: "Withdraws retrieved successfully".to_string(),
             data: withdraw_responses,
-            pagination: Pagination {
-                page,
-                page_size,
-                total_items,
-                total_pages,
-            },
+            pagination: Pagination::new(page, page_size, total_items),
         })
     }
 
@@ -221,7 +468,12 @@ impl WithdrawServiceTrait for WithdrawService {
         let mut request = Request::new(id);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        match self.withdraw_repository.find_by_id(id).await {
+        match self
+            .withdraw_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(Some(withdraw)) => {
                 let response = ApiResponse {
                     status: "success".to_string(),
@@ -243,16 +495,17 @@ impl WithdrawServiceTrait for WithdrawService {
                 let msg = format!("Withdraw with id {id} not found");
                 error!("{}", msg);
 
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
 
-                Err(ErrorResponse::from(AppError::NotFound(msg)))
+                Err(ErrorResponse::from(err))
             }
             Err(err) => {
                 let msg = format!("Failed to retrieve withdraw: {err}");
                 error!("{}", msg);
 
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
 
                 Err(ErrorResponse::from(err))
@@ -263,75 +516,76 @@ impl WithdrawServiceTrait for WithdrawService {
     async fn get_withdraw_users(
         &self,
         id: i32,
-    ) -> Result<ApiResponse<Option<Vec<WithdrawResponse>>>, ErrorResponse> {
+        req: &PageRequest,
+    ) -> Result<ApiResponsePagination<Vec<WithdrawResponse>>, ErrorResponse> {
         let method = Method::Get;
+        let req = req.bounded().map_err(ErrorResponse::from)?;
 
         let tracing_ctx = self.start_tracing(
             "GetWithdrawUsers",
             vec![
                 KeyValue::new("component", "withdraw"),
                 KeyValue::new("user_id", id.to_string()),
+                KeyValue::new("page", req.page.to_string()),
+                KeyValue::new("page_size", req.page_size.to_string()),
             ],
         );
 
         let mut request = Request::new(id);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let user_result = self.user_repository.find_by_id(id).await;
+        let user_result = self
+            .user_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await;
+
         let _user = match user_result {
             Ok(user) => user,
             Err(_) => {
                 let msg = format!("User with id {id} not found");
-                error!("{}", msg);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
         };
 
-        let withdraw_result = self.withdraw_repository.find_by_users(id).await;
+        let withdraws_result = cancellable(self.withdraw_repository.find_by_users(
+            id,
+            req.page,
+            req.page_size,
+        ))
+        .with_context(tracing_ctx.cx.clone())
+        .await;
 
-        let withdraws = match withdraw_result {
-            Ok(w) => w,
+        let (withdraws, total_items) = match withdraws_result {
+            Ok(page) => page,
             Err(err) => {
-                let msg = format!("Failed to retrieve withdraws for user {id}: {err}");
-                error!("{}", msg);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let msg = format!("Failed to retrieve withdraw for user {id}: {err}");
+                error!("{msg}");
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
         };
 
-        let withdraw_response: Option<Vec<WithdrawResponse>> = if withdraws.is_empty() {
-            None
+        let message = if withdraws.is_empty() {
+            format!("No withdraw found for user with id {id}")
         } else {
-            Some(withdraws.into_iter().map(WithdrawResponse::from).collect())
+            "Withdraw retrieved successfully".to_string()
         };
 
-        let response = if let Some(ref data) = withdraw_response {
-            let response = ApiResponse {
-                status: "success".to_string(),
-                data: Some(data.clone()),
-                message: "Withdraw retrieved successfully".to_string(),
-            };
-
-            self.complete_tracing_success(&tracing_ctx, method, "Withdraw retrieved from database")
-                .await;
-
-            response
-        } else {
-            let response = ApiResponse {
-                status: "success".to_string(),
-                data: None,
-                message: format!("No withdraw found for user with id {id}"),
-            };
-
-            self.complete_tracing_success(&tracing_ctx, method, "No withdraw found")
-                .await;
-
-            response
+        let response: ApiResponsePagination<Vec<WithdrawResponse>> = ApiResponsePagination {
+            status: "success".to_string(),
+            message,
+            data: withdraws.into_iter().map(WithdrawResponse::from).collect(),
+            pagination: Pagination::new(req.page, req.page_size, total_items),
         };
 
+        self.complete_tracing_success(&tracing_ctx, method, "Withdraw retrieved from database")
+            .await;
+
         Ok(response)
     }
 
@@ -354,9 +608,10 @@ impl WithdrawServiceTrait for WithdrawService {
 
         let cache_key = format!("withdraw_user:id={id}");
 
-        if let Some(cached) = self
-            .cache_store
-            .get_from_cache::<ApiResponse<Option<WithdrawResponse>>>(&cache_key)
+        if self.cache_store.is_enabled()
+            && let Some(cached) = self
+                .cache_store
+                .get_from_cache::<ApiResponse<Option<WithdrawResponse>>>(&cache_key)
         {
             info!("Found withdraw in cache for user_id: {id}");
 
@@ -366,23 +621,32 @@ impl WithdrawServiceTrait for WithdrawService {
             return Ok(cached);
         }
 
-        let user_result = self.user_repository.find_by_id(id).await;
+        let user_result = self
+            .user_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await;
         let _user = match user_result {
             Ok(user) => user,
             Err(_) => {
                 let msg = format!("User with id {id} not found");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
         };
 
-        let withdraw_result = self.withdraw_repository.find_by_user(id).await;
+        let withdraw_result = self
+            .withdraw_repository
+            .find_by_user(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await;
         let withdraw_opt = match withdraw_result {
             Ok(w) => w.map(WithdrawResponse::from),
             Err(err) => {
                 let msg = format!("Failed to retrieve withdraw for user_id {id}: {err}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
@@ -397,7 +661,7 @@ impl WithdrawServiceTrait for WithdrawService {
                 };
 
                 self.cache_store
-                    .set_to_cache(&cache_key, &response, Duration::from_secs(60 * 5));
+                    .set_to_cache(&cache_key, &response, self.cache_store.ttl());
 
                 self.complete_tracing_success(
                     &tracing_ctx,
@@ -410,10 +674,11 @@ impl WithdrawServiceTrait for WithdrawService {
             }
             None => {
                 let msg = format!("No withdraw found for user_id: {id}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
 
-                Err(ErrorResponse::from(AppError::NotFound(msg)))
+                Err(ErrorResponse::from(err))
             }
         }
     }
@@ -438,99 +703,27 @@ impl WithdrawServiceTrait for WithdrawService {
 
         info!("Creating withdraw for user_id: {}", input.user_id);
 
-        let saldo_opt = match self.saldo_repository.find_by_user_id(input.user_id).await {
-            Ok(s) => s,
-            Err(_) => {
-                let msg = format!("Saldo with user_id {} not found", input.user_id);
-                error!("{msg}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
-                    .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
-            }
-        };
-
-        let saldo_ref = match saldo_opt {
-            Some(ref s) => s,
-            None => {
-                let msg = format!("Saldo not found for user_id: {}", input.user_id);
-                error!("{msg}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
-                    .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
-            }
-        };
-
-        info!(
-            "Saldo found for user_id: {}. Current balance: {}",
-            input.user_id, saldo_ref.total_balance
-        );
-
-        if saldo_ref.total_balance < input.withdraw_amount {
-            let msg = format!(
-                "Insufficient balance for user_id: {}. Attempted withdrawal: {}",
-                input.user_id, input.withdraw_amount
-            );
-            error!("{msg}");
-            self.complete_tracing_error(&tracing_ctx, method, &msg)
-                .await;
-            return Err(ErrorResponse::from(AppError::Custom(
-                "Insufficient balance".to_string(),
-            )));
-        }
-
-        info!("User has sufficient balance for withdrawal");
-
-        let new_total_balance = saldo_ref.total_balance - input.withdraw_amount;
-
-        let _update_saldo_balance = match self
-            .saldo_repository
-            .update_saldo_withdraw(&UpdateSaldoWithdraw {
-                user_id: input.user_id,
-                withdraw_amount: Some(input.withdraw_amount),
-                withdraw_time: Some(Utc::now()),
-                total_balance: new_total_balance,
+        // withdraw bersamaan untuk user yang sama tidak boleh membaca saldo yang sama lalu saling menimpa
+        let locked = self
+            .cache_store
+            .with_user_lock(input.user_id, || {
+                self.create_withdraw_locked(input, &tracing_ctx, method.clone())
             })
-            .await
-        {
-            Ok(s) => s,
-            Err(err) => {
-                let msg = format!("Failed to update saldo: {err}");
-                error!("{msg}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
-                    .await;
-                return Err(ErrorResponse::from(err));
-            }
-        };
-
-        info!(
-            "Saldo balance updated for user_id: {}. New balance: {new_total_balance}",
-            input.user_id
-        );
+            .with_context(tracing_ctx.cx.clone())
+            .await;
 
-        let withdraw_create_result = match self.withdraw_repository.create(input).await {
-            Ok(w) => w,
+        match locked {
+            Ok(result) => result,
             Err(err) => {
-                let msg = format!("Failed to create withdraw: {err}");
-                error!("{msg}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let msg = format!(
+                    "Failed to create withdraw for user {}: {err}",
+                    input.user_id
+                );
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(err));
+                Err(ErrorResponse::from(err))
             }
-        };
-
-        info!(
-            "Withdraw created successfully for user_id: {}",
-            input.user_id
-        );
-
-        self.complete_tracing_success(&tracing_ctx, method, "Withdraw created successfully")
-            .await;
-
-        Ok(ApiResponse {
-            status: "success".to_string(),
-            message: "Withdraw created successfully".to_string(),
-            data: withdraw_create_result.into(),
-        })
+        }
     }
 
     async fn update_withdraw(
@@ -552,87 +745,24 @@ impl WithdrawServiceTrait for WithdrawService {
         let mut request = Request::new(input.user_id);
         self.inject_trace_context(&tracing_ctx.cx, &mut request);
 
-        let _withdraw = match self.withdraw_repository.find_by_id(input.withdraw_id).await {
-            Ok(w) => w,
-            Err(_) => {
-                let msg = format!("Withdraw with id {} not found", input.withdraw_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
-                    .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
-            }
-        };
-
-        let saldo_opt = match self.saldo_repository.find_by_user_id(input.user_id).await {
-            Ok(s) => s,
-            Err(_) => {
-                let msg = format!("Saldo with user_id {} not found", input.user_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
-                    .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
-            }
-        };
-
-        let saldo_ref = match saldo_opt {
-            Some(ref s) => s,
-            None => {
-                let msg = format!("Saldo not found for user_id: {}", input.user_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
-                    .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
-            }
-        };
-
-        let new_total_balance = saldo_ref.total_balance - input.withdraw_amount;
-
-        let updated_withdraw = self.withdraw_repository.update(input).await;
-
-        if let Err(err) = updated_withdraw {
-            let _rollback_saldo = self
-                .saldo_repository
-                .update_saldo_withdraw(&UpdateSaldoWithdraw {
-                    user_id: input.user_id,
-                    withdraw_amount: None,
-                    withdraw_time: None,
-                    total_balance: saldo_ref.total_balance,
-                })
-                .await?;
-
-            let msg = format!("Withdraw update failed: {err}");
-            error!("Rollback: {msg}");
-
-            self.complete_tracing_error(&tracing_ctx, method, &msg)
-                .await;
-            return Err(err.into());
-        }
-
-        let _update_saldo = match self
-            .saldo_repository
-            .update_saldo_withdraw(&UpdateSaldoWithdraw {
-                user_id: input.user_id,
-                withdraw_amount: Some(input.withdraw_amount),
-                withdraw_time: Some(Utc::now()),
-                total_balance: new_total_balance,
+        // withdraw dan saldo dibaca di dalam lock supaya selisih dihitung dari nilai terbaru
+        let locked = self
+            .cache_store
+            .with_user_lock(input.user_id, || {
+                self.update_withdraw_locked(input, &tracing_ctx, method.clone())
             })
-            .await
-        {
-            Ok(v) => v,
+            .with_context(tracing_ctx.cx.clone())
+            .await;
+
+        match locked {
+            Ok(result) => result,
             Err(err) => {
-                let msg = format!("Failed to update saldo: {err}");
-                error!("{msg}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let msg = format!("Failed to update withdraw {}: {err}", input.withdraw_id);
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(err));
+                Err(ErrorResponse::from(err))
             }
-        };
-
-        self.complete_tracing_success(&tracing_ctx, method, "Withdraw updated successfully")
-            .await;
-
-        Ok(ApiResponse {
-            status: "success".to_string(),
-            message: "Withdraw updated successfully".to_string(),
-            data: updated_withdraw.unwrap().into(),
-        })
+        }
     }
 
     async fn delete_withdraw(&self, id: i32) -> Result<ApiResponse<()>, ErrorResponse> {
@@ -646,53 +776,71 @@ impl WithdrawServiceTrait for WithdrawService {
             ],
         );
 
-        let user = match self.user_repository.find_by_id(id).await {
+        let user = match self
+            .user_repository
+            .find_by_id(id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(Some(user)) => user,
             Ok(None) => {
                 let msg = format!("User with id {id} not found");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
             Err(err) => {
                 let msg = format!("Failed to fetch user {id}: {err}");
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
         };
 
-        let existing = match self.withdraw_repository.find_by_user(user.user_id).await {
+        let existing = match self
+            .withdraw_repository
+            .find_by_user(user.user_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             Ok(Some(withdraw)) => withdraw,
             Ok(None) => {
                 let msg = format!("Withdraw with user_id {} not found", user.user_id);
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                let err = AppError::NotFound(msg.clone());
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
-                return Err(ErrorResponse::from(AppError::NotFound(msg)));
+                return Err(ErrorResponse::from(err));
             }
             Err(err) => {
                 let msg = format!(
                     "Failed to find withdraw for user_id {}: {err}",
                     user.user_id
                 );
-                self.complete_tracing_error(&tracing_ctx, method, &msg)
+                self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                     .await;
                 return Err(ErrorResponse::from(err));
             }
         };
 
-        if let Err(err) = self.withdraw_repository.delete(existing.withdraw_id).await {
+        if let Err(err) = self
+            .withdraw_repository
+            .delete(existing.withdraw_id)
+            .with_context(tracing_ctx.cx.clone())
+            .await
+        {
             let msg = format!(
                 "Failed to delete withdraw id {}: {}",
                 existing.withdraw_id, err
             );
-            self.complete_tracing_error(&tracing_ctx, method, &msg)
+            self.complete_tracing_error(&tracing_ctx, method, err.kind(), &msg)
                 .await;
             return Err(ErrorResponse::from(err));
         }
 
         let cache_key = format!("withdraw_user:id={}", user.user_id);
         self.cache_store.delete_from_cache(&cache_key);
+        invalidate_account_summary(&self.cache_store, &[user.user_id]);
 
         info!(
             "Withdraw deleted successfully for user_id: {}",
@@ -709,3 +857,278 @@ impl WithdrawServiceTrait for WithdrawService {
         })
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        abstract_trait::TopupServiceTrait,
+        domain::request::CreateTopupRequest,
+        model::user::UserStatus,
+        repository::memory::{InMemoryRepositories, offline_cache_store},
+        service::topup::TopupService,
+    };
+    use axum::http::StatusCode;
+
+    async fn withdraw_service(repos: &InMemoryRepositories) -> WithdrawService {
+        WithdrawService::new(
+            repos.withdraw.clone(),
+            repos.saldo.clone(),
+            repos.user.clone(),
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+            offline_cache_store(),
+        )
+        .await
+    }
+
+    fn withdraw(user_id: i32, amount: i32) -> CreateWithdrawRequest {
+        CreateWithdrawRequest {
+            user_id,
+            withdraw_amount: amount,
+            withdraw_time: "2026-10-18T12:00:00Z".to_string(),
+        }
+    }
+
+    fn set_overdraft_limit(repos: &InMemoryRepositories, user_id: i32, limit: i32) {
+        if let Some(user) = repos.database.tables().users.get_mut(user_id) {
+            user.overdraft_limit = limit;
+        }
+    }
+
+    fn freeze(repos: &InMemoryRepositories, user_id: i32) {
+        if let Some(user) = repos.database.tables().users.get_mut(user_id) {
+            user.status = UserStatus::Frozen.as_str().to_string();
+        }
+    }
+
+    #[tokio::test]
+    async fn overdraft_account_can_withdraw_below_zero() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("premium@example.com", 60_000).await;
+        set_overdraft_limit(&repos, user.user_id, 50_000);
+        let service = withdraw_service(&repos).await;
+
+        service
+            .create_withdraw(&withdraw(user.user_id, 100_000))
+            .await
+            .unwrap();
+        assert_eq!(repos.balance_of(user.user_id).await, -40_000);
+
+        // batas overdraft tetap berlaku
+        let err = service
+            .create_withdraw(&withdraw(user.user_id, 60_000))
+            .await
+            .unwrap_err();
+        assert_eq!(err.message, "Insufficient balance");
+        assert_eq!(repos.balance_of(user.user_id).await, -40_000);
+    }
+
+    #[tokio::test]
+    async fn regular_account_is_floored_at_zero() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("regular@example.com", 60_000).await;
+        let service = withdraw_service(&repos).await;
+
+        let err = service
+            .create_withdraw(&withdraw(user.user_id, 60_001))
+            .await
+            .unwrap_err();
+        assert_eq!(err.message, "Insufficient balance");
+
+        service
+            .create_withdraw(&withdraw(user.user_id, 60_000))
+            .await
+            .unwrap();
+        assert_eq!(repos.balance_of(user.user_id).await, 0);
+    }
+
+    #[tokio::test]
+    async fn concurrent_withdrawals_never_overdraw() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("busy@example.com", 100_000).await;
+        let service = withdraw_service(&repos).await;
+
+        let request = withdraw(user.user_id, 60_000);
+
+        let (first, second) = tokio::join!(
+            service.create_withdraw(&request),
+            service.create_withdraw(&request),
+        );
+
+        assert!(first.is_ok() ^ second.is_ok());
+        assert_eq!(repos.balance_of(user.user_id).await, 40_000);
+    }
+
+    #[tokio::test]
+    async fn frozen_account_cannot_update_withdraw() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("frozen@example.com", 200_000).await;
+        let service = withdraw_service(&repos).await;
+
+        let created = service
+            .create_withdraw(&withdraw(user.user_id, 60_000))
+            .await
+            .unwrap()
+            .data;
+        freeze(&repos, user.user_id);
+
+        let err = service
+            .update_withdraw(&update(user.user_id, created.withdraw_id, 70_000))
+            .await
+            .unwrap_err();
+        assert_eq!(err.message, "Account is frozen");
+        assert_eq!(repos.balance_of(user.user_id).await, 140_000);
+    }
+
+    fn update(user_id: i32, withdraw_id: i32, amount: i32) -> UpdateWithdrawRequest {
+        UpdateWithdrawRequest {
+            user_id,
+            withdraw_id,
+            withdraw_amount: amount,
+            withdraw_time: "2026-10-18T13:00:00Z".to_string(),
+        }
+    }
+
+    #[tokio::test]
+    async fn withdraw_update_respects_the_balance_floor() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("regular@example.com", 200_000).await;
+        let service = withdraw_service(&repos).await;
+
+        let created = service
+            .create_withdraw(&withdraw(user.user_id, 60_000))
+            .await
+            .unwrap()
+            .data;
+
+        // selisih 150.000 melebihi sisa saldo 140.000
+        let err = service
+            .update_withdraw(&update(user.user_id, created.withdraw_id, 210_000))
+            .await
+            .unwrap_err();
+        assert_eq!(err.message, "Insufficient balance");
+        assert_eq!(repos.balance_of(user.user_id).await, 140_000);
+
+        service
+            .update_withdraw(&update(user.user_id, created.withdraw_id, 200_000))
+            .await
+            .unwrap();
+        assert_eq!(repos.balance_of(user.user_id).await, 0);
+    }
+
+    #[tokio::test]
+    async fn withdraw_update_applies_only_the_difference() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("regular@example.com", 200_000).await;
+        let service = withdraw_service(&repos).await;
+
+        let created = service
+            .create_withdraw(&withdraw(user.user_id, 60_000))
+            .await
+            .unwrap()
+            .data;
+
+        let raised = service
+            .update_withdraw(&update(user.user_id, created.withdraw_id, 90_000))
+            .await
+            .unwrap()
+            .data;
+        assert_eq!(raised.withdraw_amount, 90_000);
+        assert_eq!(repos.balance_of(user.user_id).await, 110_000);
+
+        // nominal yang diturunkan mengembalikan selisihnya ke saldo
+        service
+            .update_withdraw(&update(user.user_id, created.withdraw_id, 50_000))
+            .await
+            .unwrap();
+        assert_eq!(repos.balance_of(user.user_id).await, 150_000);
+    }
+
+    #[tokio::test]
+    async fn user_withdraws_are_split_into_pages() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("heavy@example.com", 500_000).await;
+        let service = withdraw_service(&repos).await;
+        for _ in 0..3 {
+            service
+                .create_withdraw(&withdraw(user.user_id, 60_000))
+                .await
+                .unwrap();
+        }
+
+        let first = service
+            .get_withdraw_users(user.user_id, &PageRequest::new(1, 2))
+            .await
+            .unwrap();
+        let second = service
+            .get_withdraw_users(user.user_id, &PageRequest::new(2, 2))
+            .await
+            .unwrap();
+
+        assert_eq!(first.data.len(), 2);
+        assert_eq!(second.data.len(), 1);
+        assert_eq!(first.pagination.total_items, 3);
+        assert_eq!(first.pagination.total_pages, 2);
+        assert!(first.pagination.has_next && !second.pagination.has_next);
+
+        // tanpa parameter jatuh ke halaman 1 ukuran 10
+        let all = service
+            .get_withdraw_users(user.user_id, &PageRequest::default())
+            .await
+            .unwrap();
+        assert_eq!(all.data.len(), 3);
+        assert_eq!((all.pagination.page, all.pagination.page_size), (1, 10));
+    }
+
+    #[tokio::test]
+    async fn negative_page_is_rejected() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("heavy@example.com", 500_000).await;
+        let service = withdraw_service(&repos).await;
+
+        let err = service
+            .get_withdraw_users(user.user_id, &PageRequest::new(-1, 2))
+            .await
+            .unwrap_err();
+
+        assert_eq!(err.code, StatusCode::BAD_REQUEST);
+        assert_eq!(err.message, "page must not be negative, got -1");
+    }
+
+    #[tokio::test]
+    async fn withdraw_racing_a_topup_confirmation_keeps_the_credit() {
+        let repos = InMemoryRepositories::new();
+        let user = repos.seed_user("race@example.com", 100_000).await;
+        let service = withdraw_service(&repos).await;
+        let topups = TopupService::new(
+            repos.topup.clone(),
+            repos.user.clone(),
+            Arc::new(Metrics::new(vec![0.1])),
+            &mut Registry::default(),
+            offline_cache_store(),
+        )
+        .await;
+
+        let topup = topups
+            .create_topup(&CreateTopupRequest {
+                user_id: user.user_id,
+                topup_no: "TOPUP-RACE".to_string(),
+                topup_amount: 50_000,
+                topup_method: "bri".to_string(),
+            })
+            .await
+            .unwrap()
+            .data;
+
+        let debit = withdraw(user.user_id, 40_000);
+        let (withdrawn, confirmed) = tokio::join!(
+            service.create_withdraw(&debit),
+            topups.confirm_topup(topup.topup_id),
+        );
+        withdrawn.unwrap();
+        confirmed.unwrap();
+
+        assert_eq!(repos.balance_of(user.user_id).await, 110_000);
+    }
+}
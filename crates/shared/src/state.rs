@@ -1,43 +1,97 @@
 use anyhow::{Context, Result};
 use prometheus_client::registry::Registry;
-use std::sync::Arc;
+use std::{sync::Arc, time::Duration};
 use tokio::sync::Mutex;
+use tracing::warn;
 
 use crate::{
-    abstract_trait::{DynHashing, DynJwtService},
-    config::{ConnectionPool, Hashing, JwtConfig},
-    utils::{DependenciesInject, Metrics, SystemMetrics, run_metrics_collector},
+    abstract_trait::{DynHashing, DynJwtService, DynSaldoRepository},
+    cache::CacheStore,
+    config::{
+        CacheTtlConfig, ConnectionPool, DatabasePools, HashAlgorithm, Hashing, JwtConfig,
+        RedisClient, RedisConfig, TransferVelocityConfig,
+    },
+    repository::saldo::SaldoRepository,
+    utils::{
+        DependenciesInject, Metrics, SaldoMetrics, SystemMetrics, run_metrics_collector,
+        run_saldo_metrics_collector,
+    },
 };
 
 #[derive(Clone, Debug)]
 pub struct AppState {
     pub di_container: DependenciesInject,
+    pub db_pool: ConnectionPool,
+    pub redis: RedisClient,
+    // store yang sama dengan milik DI, sehingga status breaker-nya mencerminkan cache service
+    pub cache: CacheStore,
     pub jwt_config: DynJwtService,
     pub registry: Arc<Mutex<Registry>>,
-    pub metrics: Arc<Mutex<Metrics>>,
+    pub metrics: Arc<Metrics>,
     pub system_metrics: Arc<SystemMetrics>,
+    pub saldo_metrics: Arc<SaldoMetrics>,
 }
 
 impl AppState {
-    pub async fn new(pool: ConnectionPool, jwt_secret: &str) -> Result<Self> {
-        let jwt_config = Arc::new(JwtConfig::new(jwt_secret)) as DynJwtService;
-        let hashing = Arc::new(Hashing::new()) as DynHashing;
+    #[allow(clippy::too_many_arguments)]
+    pub async fn new(
+        pools: DatabasePools,
+        jwt: JwtConfig,
+        metrics_buckets: Vec<f64>,
+        cache_ttl: CacheTtlConfig,
+        saldo_metrics_interval: Duration,
+        hash_algorithm: HashAlgorithm,
+        transfer_velocity: TransferVelocityConfig,
+    ) -> Result<Self> {
+        let jwt_config = Arc::new(jwt) as DynJwtService;
+        let hashing = Arc::new(Hashing::new(hash_algorithm)) as DynHashing;
         let registry = Arc::new(Mutex::new(Registry::default()));
-        let metrics = Arc::new(Mutex::new(Metrics::new()));
+        let metrics = Arc::new(Metrics::new(metrics_buckets));
         let system_metrics = Arc::new(SystemMetrics::new());
 
         registry.lock().await.register_metrics(&system_metrics);
 
         tokio::spawn(run_metrics_collector(system_metrics.clone()));
 
+        let saldo_metrics = Arc::new(SaldoMetrics::default());
+        saldo_metrics.register(&mut *registry.lock().await);
+
+        let saldo_repository =
+            Arc::new(SaldoRepository::new(pools.primary.clone())) as DynSaldoRepository;
+        tokio::spawn(run_saldo_metrics_collector(
+            saldo_repository,
+            saldo_metrics.clone(),
+            saldo_metrics_interval,
+        ));
+
+        let redis_config = RedisConfig {
+            host: "redis".into(),
+            port: 6379,
+            db: 1,
+            password: Some("dragon_knight".into()),
+        };
+
+        let redis = RedisClient::new(&redis_config)
+            .await
+            .context("Failed to connect to Redis")?;
+
+        // Redis hanya cache; tanpa Redis service tetap jalan dan membaca langsung dari DB
+        if let Err(e) = redis.ping() {
+            warn!("⚠️  Failed to ping Redis server, starting with cache bypassed: {e:#}");
+        }
+
+        let cache = CacheStore::new(redis.client.clone(), cache_ttl);
+
         let di_container = {
             let mut registry_guard = registry.lock().await;
             DependenciesInject::new(
-                pool,
+                pools.clone(),
                 hashing,
                 jwt_config.clone(),
                 metrics.clone(),
                 &mut registry_guard,
+                cache.clone(),
+                transfer_velocity,
             )
             .await
             .context("Failed to initialize dependency injection container")?
@@ -46,9 +100,13 @@ impl AppState {
         Ok(Self {
             registry,
             di_container,
+            db_pool: pools.primary,
+            redis,
+            cache,
             jwt_config,
             metrics,
             system_metrics,
+            saldo_metrics,
         })
     }
 }
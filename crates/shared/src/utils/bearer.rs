@@ -0,0 +1,23 @@
+use std::future::Future;
+
+pub const AUTHORIZATION_HEADER: &str = "authorization";
+
+tokio::task_local! {
+    static BEARER_TOKEN: String;
+}
+
+// token user yang login diteruskan ke server gRPC untuk semua panggilan di dalam future ini
+pub async fn with_bearer_token<F: Future>(token: String, future: F) -> F::Output {
+    BEARER_TOKEN.scope(token, future).await
+}
+
+pub fn current_bearer_token() -> Option<String> {
+    BEARER_TOKEN.try_with(Clone::clone).ok()
+}
+
+pub fn parse_bearer_token(value: &str) -> Option<&str> {
+    value
+        .strip_prefix("Bearer ")
+        .map(str::trim)
+        .filter(|token| !token.is_empty())
+}
@@ -0,0 +1,33 @@
+use prometheus_client_derive_encode::EncodeLabelSet;
+use serde::Serialize;
+
+// diisi build.rs saat kompilasi supaya build yang berjalan bisa dilacak saat insiden
+#[derive(Debug, Clone, Copy, Serialize)]
+pub struct BuildInfo {
+    pub version: &'static str,
+    pub git_sha: &'static str,
+    pub build_timestamp: &'static str,
+}
+
+pub const BUILD_INFO: BuildInfo = BuildInfo {
+    version: env!("CARGO_PKG_VERSION"),
+    git_sha: env!("BUILD_GIT_SHA"),
+    build_timestamp: env!("BUILD_TIMESTAMP"),
+};
+
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct BuildInfoLabels {
+    pub version: String,
+    pub git_sha: String,
+    pub build_timestamp: String,
+}
+
+impl From<BuildInfo> for BuildInfoLabels {
+    fn from(info: BuildInfo) -> Self {
+        Self {
+            version: info.version.to_string(),
+            git_sha: info.git_sha.to_string(),
+            build_timestamp: info.build_timestamp.to_string(),
+        }
+    }
+}
@@ -0,0 +1,132 @@
+use std::future::Future;
+
+use tokio::time::Instant;
+use tokio_util::sync::CancellationToken;
+
+use crate::utils::{AppError, current_deadline};
+
+tokio::task_local! {
+    static CANCELLATION: CancellationToken;
+}
+
+// token dibatalkan saat client memutus request, berlaku untuk semua pemanggilan di dalam future ini
+pub async fn with_cancellation<F: Future>(token: CancellationToken, future: F) -> F::Output {
+    CANCELLATION.scope(token, future).await
+}
+
+pub fn current_cancellation() -> Option<CancellationToken> {
+    CANCELLATION.try_with(Clone::clone).ok()
+}
+
+// query panjang dihentikan lebih awal kalau client sudah tidak menunggu hasilnya
+// atau deadline request sudah lewat
+pub async fn cancellable<T, F>(future: F) -> Result<T, AppError>
+where
+    F: Future<Output = Result<T, AppError>>,
+{
+    let token = current_cancellation();
+    let deadline = current_deadline();
+
+    if token.is_none() && deadline.is_none() {
+        return future.await;
+    }
+
+    if token.as_ref().is_some_and(CancellationToken::is_cancelled) {
+        return Err(AppError::Cancelled);
+    }
+
+    if deadline.is_some_and(|deadline| deadline <= Instant::now()) {
+        return Err(AppError::DeadlineExceeded);
+    }
+
+    let cancelled = async {
+        match &token {
+            Some(token) => token.cancelled().await,
+            None => std::future::pending().await,
+        }
+    };
+
+    let expired = async {
+        match deadline {
+            Some(deadline) => tokio::time::sleep_until(deadline).await,
+            None => std::future::pending().await,
+        }
+    };
+
+    let result = tokio::select! {
+        biased;
+        _ = cancelled => return Err(AppError::Cancelled),
+        _ = expired => return Err(AppError::DeadlineExceeded),
+        result = future => result,
+    };
+
+    if token.as_ref().is_some_and(CancellationToken::is_cancelled) {
+        return Err(AppError::Cancelled);
+    }
+
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::{
+        Arc,
+        atomic::{AtomicBool, Ordering},
+    };
+    use std::time::Duration;
+
+    #[tokio::test]
+    async fn finished_query_returns_its_result() {
+        let result =
+            with_cancellation(CancellationToken::new(), cancellable(async { Ok(7) })).await;
+
+        assert_eq!(result.unwrap(), 7);
+    }
+
+    #[tokio::test]
+    async fn cancelled_client_stops_the_query() {
+        let token = CancellationToken::new();
+        let finished = Arc::new(AtomicBool::new(false));
+
+        let query = {
+            let finished = finished.clone();
+            async move {
+                tokio::time::sleep(Duration::from_secs(5)).await;
+                finished.store(true, Ordering::SeqCst);
+                Ok(())
+            }
+        };
+        let task = tokio::spawn(with_cancellation(token.clone(), cancellable(query)));
+
+        // client memutus koneksi di tengah query
+        tokio::time::sleep(Duration::from_millis(20)).await;
+        token.cancel();
+
+        let result = tokio::time::timeout(Duration::from_secs(1), task)
+            .await
+            .expect("query should stop right after cancellation")
+            .unwrap();
+        assert!(matches!(result, Err(AppError::Cancelled)));
+        assert!(!finished.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn already_cancelled_token_skips_the_query() {
+        let token = CancellationToken::new();
+        token.cancel();
+        let started = Arc::new(AtomicBool::new(false));
+
+        let query = {
+            let started = started.clone();
+            async move {
+                started.store(true, Ordering::SeqCst);
+                Ok(())
+            }
+        };
+        let result = with_cancellation(token, cancellable(query)).await;
+
+        assert!(matches!(result, Err(AppError::Cancelled)));
+        assert!(!started.load(Ordering::SeqCst));
+    }
+}
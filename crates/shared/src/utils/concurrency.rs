@@ -0,0 +1,43 @@
+use std::{sync::Arc, time::Duration};
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+
+pub const RETRY_AFTER_HEADER: &str = "retry-after";
+
+// batas request yang diproses bersamaan; kelebihannya langsung ditolak, tidak diantrekan
+#[derive(Debug, Clone)]
+pub struct ConcurrencyLimit {
+    semaphore: Arc<Semaphore>,
+    max_concurrent: usize,
+    retry_after: Duration,
+}
+
+impl ConcurrencyLimit {
+    pub fn new(max_concurrent: usize, retry_after: Duration) -> Self {
+        Self {
+            semaphore: Arc::new(Semaphore::new(max_concurrent)),
+            max_concurrent,
+            retry_after,
+        }
+    }
+
+    // permit dilepas otomatis saat di-drop, yaitu ketika request selesai
+    pub fn try_acquire(&self) -> Option<OwnedSemaphorePermit> {
+        self.semaphore.clone().try_acquire_owned().ok()
+    }
+
+    // selesai saat semua permit kembali, artinya tidak ada request yang sedang berjalan
+    pub async fn wait_idle(&self) {
+        let _ = self
+            .semaphore
+            .acquire_many(self.max_concurrent as u32)
+            .await;
+    }
+
+    pub fn max_concurrent(&self) -> usize {
+        self.max_concurrent
+    }
+
+    pub fn retry_after_secs(&self) -> u64 {
+        self.retry_after.as_secs()
+    }
+}
@@ -0,0 +1,60 @@
+use std::{future::Future, time::Duration};
+
+use tokio::time::Instant;
+
+pub const TIMEOUT_HEADER: &str = "x-timeout-ms";
+
+pub const GRPC_TIMEOUT_HEADER: &str = "grpc-timeout";
+
+tokio::task_local! {
+    static DEADLINE: Instant;
+}
+
+// batas waktu request, berlaku untuk semua pemanggilan di dalam future ini
+pub async fn with_deadline<F: Future>(deadline: Instant, future: F) -> F::Output {
+    DEADLINE.scope(deadline, future).await
+}
+
+pub fn current_deadline() -> Option<Instant> {
+    DEADLINE.try_with(|deadline| *deadline).ok()
+}
+
+// sisa waktu sebelum deadline, nol kalau deadline sudah lewat
+pub fn remaining_time() -> Option<Duration> {
+    current_deadline().map(|deadline| deadline.saturating_duration_since(Instant::now()))
+}
+
+// nilai header X-Timeout-Ms harus bilangan bulat positif dalam milidetik
+pub fn parse_timeout_ms(value: &str) -> Option<Duration> {
+    value
+        .trim()
+        .parse::<u64>()
+        .ok()
+        .filter(|ms| *ms > 0)
+        .map(Duration::from_millis)
+}
+
+// format grpc-timeout: maksimal 8 digit diikuti satuan H/M/S/m/u/n
+pub fn parse_grpc_timeout(value: &str) -> Option<Duration> {
+    if value.len() < 2 || !value.is_ascii() {
+        return None;
+    }
+
+    let (amount, unit) = value.split_at(value.len() - 1);
+    if amount.len() > 8 {
+        return None;
+    }
+
+    let amount: u64 = amount.parse().ok()?;
+    let duration = match unit {
+        "H" => Duration::from_secs(amount * 60 * 60),
+        "M" => Duration::from_secs(amount * 60),
+        "S" => Duration::from_secs(amount),
+        "m" => Duration::from_millis(amount),
+        "u" => Duration::from_micros(amount),
+        "n" => Duration::from_nanos(amount),
+        _ => return None,
+    };
+
+    Some(duration)
+}
@@ -1,108 +1,131 @@
-use anyhow::{Context, Result};
+use anyhow::Result;
 use prometheus_client::registry::Registry;
 use std::sync::Arc;
-use tokio::sync::Mutex;
 
 use crate::{
     abstract_trait::{
-        DynAuthService, DynHashing, DynJwtService, DynSaldoRepository, DynSaldoService,
-        DynTopupRepository, DynTopupService, DynTransferRepository, DynTransferService,
-        DynUserRepository, DynUserService, DynWithdrawRepository, DynWithdrawService,
+        DynAccountService, DynAuthService, DynHashing, DynJwtService, DynOutboxRepository,
+        DynSaldoRepository, DynSaldoService, DynSessionRepository, DynTopupRepository,
+        DynTopupService, DynTransferRepository, DynTransferService, DynUserRepository,
+        DynUserService, DynVirtualCardRepository, DynVirtualCardService, DynWebhookService,
+        DynWithdrawRepository, DynWithdrawService,
     },
     cache::CacheStore,
-    config::{ConnectionPool, RedisClient, RedisConfig},
+    config::{DatabasePools, TransferVelocityConfig},
     repository::{
-        saldo::SaldoRepository, topup::TopupRepository, transfer::TransferRepository,
-        user::UserRepository, withdraw::WithdrawRepository,
+        outbox::OutboxRepository, saldo::SaldoRepository, session::SessionRepository,
+        topup::TopupRepository, transfer::TransferRepository, user::UserRepository,
+        virtual_card::VirtualCardRepository, withdraw::WithdrawRepository,
     },
     service::{
-        auth::AuthService, saldo::SaldoService, topup::TopupService, transfer::TransferService,
-        user::UserService, withdraw::WithdrawService,
+        account::{AccountRepositories, AccountService},
+        auth::{AuthRepositories, AuthService},
+        saldo::SaldoService,
+        topup::TopupService,
+        transfer::TransferService,
+        user::UserService,
+        virtual_card::VirtualCardService,
+        webhook::WebhookService,
+        withdraw::WithdrawService,
     },
     utils::Metrics,
 };
 
 #[derive(Clone)]
 pub struct DependenciesInject {
+    pub account_service: DynAccountService,
     pub auth_service: DynAuthService,
     pub user_service: DynUserService,
     pub saldo_service: DynSaldoService,
     pub topup_service: DynTopupService,
     pub transfer_service: DynTransferService,
     pub withdraw_service: DynWithdrawService,
+    pub virtual_card_service: DynVirtualCardService,
+    pub webhook_service: DynWebhookService,
 }
 
 impl std::fmt::Debug for DependenciesInject {
     fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
         f.debug_struct("DependenciesInject")
+            .field("account_service", &"DynAccountService")
             .field("auth_service", &"DynAuthService")
             .field("user_service", &"DynUserService")
             .field("saldo_service", &"DynSaldoService")
             .field("topup_service", &"DynTopupService")
             .field("transfer_service", &"DynTransferService")
             .field("withdraw_service", &"DynWithdrawService")
+            .field("virtual_card_service", &"DynVirtualCardService")
+            .field("webhook_service", &"DynWebhookService")
             .finish()
     }
 }
 
 impl DependenciesInject {
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
-        pool: ConnectionPool,
+        pools: DatabasePools,
         hashing: DynHashing,
         jwt_config: DynJwtService,
-        metrics: Arc<Mutex<Metrics>>,
+        metrics: Arc<Metrics>,
         registry: &mut Registry,
+        cache: CacheStore,
+        transfer_velocity: TransferVelocityConfig,
     ) -> Result<Self> {
-        let config = RedisConfig {
-            host: "redis".into(),
-            port: 6379,
-            db: 1,
-            password: Some("dragon_knight".into()),
-        };
-
-        let redis = RedisClient::new(&config)
-            .await
-            .context("Failed to connect to Redis")
-            .unwrap();
-
-        redis.ping().context("Failed to ping Redis server").unwrap();
+        let ttl = cache.ttl_config();
+        let DatabasePools {
+            primary: pool,
+            read: read_pool,
+        } = pools;
 
-        let cache = Arc::new(CacheStore::new(redis.client.clone()));
+        let user_repository =
+            Arc::new(UserRepository::new(pool.clone()).with_read_pool(read_pool.clone()))
+                as DynUserRepository;
 
-        let user_repository = Arc::new(UserRepository::new(pool.clone())) as DynUserRepository;
+        let session_repository =
+            Arc::new(SessionRepository::new(pool.clone())) as DynSessionRepository;
 
         let user_service = Arc::new(
             UserService::new(
                 user_repository.clone(),
+                session_repository.clone(),
                 hashing.clone(),
                 metrics.clone(),
                 registry,
-                cache.clone(),
+                Arc::new(cache.for_entity(ttl.user)),
             )
             .await,
         ) as DynUserService;
 
         let auth_service = Arc::new(
             AuthService::new(
-                user_repository.clone(),
+                AuthRepositories {
+                    user: user_repository.clone(),
+                    session: session_repository,
+                },
                 hashing.clone(),
                 jwt_config,
                 metrics.clone(),
                 registry,
-                cache.clone(),
+                Arc::new(cache.for_entity(ttl.auth)),
             )
             .await,
         ) as DynAuthService;
 
-        let saldo_repository = Arc::new(SaldoRepository::new(pool.clone())) as DynSaldoRepository;
+        let saldo_repository =
+            Arc::new(SaldoRepository::new(pool.clone()).with_read_pool(read_pool.clone()))
+                as DynSaldoRepository;
 
-        let topup_repository = Arc::new(TopupRepository::new(pool.clone())) as DynTopupRepository;
+        let topup_repository =
+            Arc::new(TopupRepository::new(pool.clone()).with_read_pool(read_pool.clone()))
+                as DynTopupRepository;
 
         let transfer_repository =
-            Arc::new(TransferRepository::new(pool.clone())) as DynTransferRepository;
+            Arc::new(TransferRepository::new(pool.clone()).with_read_pool(read_pool.clone()))
+                as DynTransferRepository;
 
         let withdraw_repository =
-            Arc::new(WithdrawRepository::new(pool.clone())) as DynWithdrawRepository;
+            Arc::new(WithdrawRepository::new(pool.clone()).with_read_pool(read_pool.clone()))
+                as DynWithdrawRepository;
 
         let saldo_service = Arc::new(
             SaldoService::new(
@@ -110,7 +133,7 @@ impl DependenciesInject {
                 saldo_repository.clone(),
                 metrics.clone(),
                 registry,
-                cache.clone(),
+                Arc::new(cache.for_entity(ttl.saldo)),
             )
             .await,
         ) as DynSaldoService;
@@ -118,11 +141,10 @@ impl DependenciesInject {
         let topup_service = Arc::new(
             TopupService::new(
                 topup_repository.clone(),
-                saldo_repository.clone(),
                 user_repository.clone(),
                 metrics.clone(),
                 registry,
-                cache.clone(),
+                Arc::new(cache.for_entity(ttl.topup)),
             )
             .await,
         ) as DynTopupService;
@@ -134,9 +156,10 @@ impl DependenciesInject {
                 user_repository.clone(),
                 metrics.clone(),
                 registry,
-                cache.clone(),
+                Arc::new(cache.for_entity(ttl.transfer)),
             )
-            .await,
+            .await
+            .with_velocity(transfer_velocity),
         ) as DynTransferService;
 
         let withdraw_service = Arc::new(
@@ -146,18 +169,58 @@ impl DependenciesInject {
                 user_repository.clone(),
                 metrics.clone(),
                 registry,
-                cache.clone(),
+                Arc::new(cache.for_entity(ttl.withdraw)),
             )
             .await,
         ) as DynWithdrawService;
 
+        let virtual_card_repository =
+            Arc::new(VirtualCardRepository::new(pool.clone()).with_read_pool(read_pool.clone()))
+                as DynVirtualCardRepository;
+
+        let virtual_card_service = Arc::new(
+            VirtualCardService::new(
+                virtual_card_repository,
+                user_repository.clone(),
+                metrics.clone(),
+                registry,
+            )
+            .await,
+        ) as DynVirtualCardService;
+
+        let account_service = Arc::new(
+            AccountService::new(
+                AccountRepositories {
+                    user: user_repository.clone(),
+                    saldo: saldo_repository.clone(),
+                    topup: topup_repository.clone(),
+                    transfer: transfer_repository.clone(),
+                    withdraw: withdraw_repository.clone(),
+                },
+                metrics.clone(),
+                registry,
+                Arc::new(cache.for_entity(ttl.account_summary)),
+            )
+            .await,
+        ) as DynAccountService;
+
+        let outbox_repository =
+            Arc::new(OutboxRepository::new(pool.clone())) as DynOutboxRepository;
+
+        let webhook_service =
+            Arc::new(WebhookService::new(outbox_repository, metrics.clone(), registry).await)
+                as DynWebhookService;
+
         Ok(Self {
+            account_service,
             auth_service,
             user_service,
             saldo_service,
             topup_service,
             transfer_service,
             withdraw_service,
+            virtual_card_service,
+            webhook_service,
         })
     }
 }
@@ -1,9 +1,15 @@
+use crate::domain::response::ErrorResponse;
 use anyhow::Error as AnyhowError;
+use axum::{
+    http::StatusCode,
+    response::{IntoResponse, Response},
+};
 use bcrypt::BcryptError;
 use jsonwebtoken::errors::Error as JwtError;
 use serde::Serialize;
 use sqlx::Error as SqlxError;
 use thiserror::Error;
+use tonic::{Code, Status};
 use validator::ValidationErrors;
 
 #[derive(Debug, Error)]
@@ -35,6 +41,9 @@ pub enum AppError {
     #[error("Email already exists")]
     EmailAlreadyExists,
 
+    #[error("Topup number already exists")]
+    TopupNoAlreadyExists,
+
     #[error("Validation error: {0}")]
     ValidationError(ValidationErrors),
 
@@ -43,6 +52,110 @@ pub enum AppError {
 
     #[error("{0}")]
     Custom(String),
+
+    #[error("Request cancelled by client")]
+    Cancelled,
+
+    #[error("Request deadline exceeded")]
+    DeadlineExceeded,
+
+    #[error("Rate limited, retry after {0}s")]
+    RateLimited(u64),
+
+    #[error("Upstream error ({code:?}): {message}")]
+    Upstream { code: Code, message: String },
+}
+
+// kode non-standar (nginx) untuk request yang ditinggal client sebelum selesai
+pub const CLIENT_CLOSED_REQUEST: u16 = 499;
+
+impl AppError {
+    pub fn kind(&self) -> &'static str {
+        match self {
+            AppError::SqlxError(_) => "sqlx_error",
+            AppError::HashingError(_) => "hashing_error",
+            AppError::InvalidCredentials => "invalid_credentials",
+            AppError::TokenExpiredError => "token_expired",
+            AppError::TokenValidationError => "token_validation",
+            AppError::TokenGenerationError(_) => "token_generation",
+            AppError::BcryptError(_) => "bcrypt_error",
+            AppError::NotFound(_) => "not_found",
+            AppError::EmailAlreadyExists => "email_already_exists",
+            AppError::TopupNoAlreadyExists => "topup_no_already_exists",
+            AppError::ValidationError(_) => "validation_error",
+            AppError::InternalError(_) => "internal_error",
+            AppError::Custom(_) => "custom",
+            AppError::Cancelled => "cancelled",
+            AppError::DeadlineExceeded => "deadline_exceeded",
+            AppError::RateLimited(_) => "rate_limited",
+            AppError::Upstream { .. } => "upstream",
+        }
+    }
+
+    pub fn status_code(&self) -> StatusCode {
+        match self {
+            AppError::NotFound(_) => StatusCode::NOT_FOUND,
+            AppError::InvalidCredentials
+            | AppError::TokenExpiredError
+            | AppError::TokenValidationError => StatusCode::UNAUTHORIZED,
+            AppError::EmailAlreadyExists | AppError::TopupNoAlreadyExists => StatusCode::CONFLICT,
+            AppError::ValidationError(_) => StatusCode::UNPROCESSABLE_ENTITY,
+            AppError::Custom(_) => StatusCode::BAD_REQUEST,
+            AppError::RateLimited(_) => StatusCode::TOO_MANY_REQUESTS,
+            AppError::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+            AppError::Upstream { code, .. } => upstream_status_code(*code),
+            AppError::Cancelled => {
+                StatusCode::from_u16(CLIENT_CLOSED_REQUEST).expect("499 is a valid status code")
+            }
+            AppError::SqlxError(_)
+            | AppError::HashingError(_)
+            | AppError::TokenGenerationError(_)
+            | AppError::BcryptError(_)
+            | AppError::InternalError(_) => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+}
+
+// kode grpc dari server diterjemahkan ke status http yang setara untuk REST gateway
+fn upstream_status_code(code: Code) -> StatusCode {
+    match code {
+        Code::NotFound => StatusCode::NOT_FOUND,
+        Code::Unauthenticated => StatusCode::UNAUTHORIZED,
+        Code::PermissionDenied => StatusCode::FORBIDDEN,
+        Code::AlreadyExists | Code::Aborted => StatusCode::CONFLICT,
+        Code::InvalidArgument => StatusCode::UNPROCESSABLE_ENTITY,
+        Code::FailedPrecondition | Code::OutOfRange => StatusCode::BAD_REQUEST,
+        Code::ResourceExhausted => StatusCode::TOO_MANY_REQUESTS,
+        Code::Unimplemented => StatusCode::NOT_IMPLEMENTED,
+        Code::Unavailable => StatusCode::SERVICE_UNAVAILABLE,
+        Code::DeadlineExceeded => StatusCode::GATEWAY_TIMEOUT,
+        Code::Cancelled => {
+            StatusCode::from_u16(CLIENT_CLOSED_REQUEST).expect("499 is a valid status code")
+        }
+        Code::Ok | Code::Unknown | Code::Internal | Code::DataLoss => {
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+impl From<Status> for AppError {
+    fn from(status: Status) -> Self {
+        AppError::Upstream {
+            code: status.code(),
+            message: status.message().to_string(),
+        }
+    }
+}
+
+// aritmetika uang i32 yang meluap ditolak, bukan dibiarkan wrap diam-diam
+pub fn checked_amount(value: Option<i32>) -> Result<i32, AppError> {
+    value.ok_or_else(|| AppError::Custom("amount overflow".to_string()))
+}
+
+impl IntoResponse for AppError {
+    fn into_response(self) -> Response {
+        ErrorResponse::from(self).into_response()
+    }
 }
 
 impl From<AnyhowError> for AppError {
@@ -59,3 +172,46 @@ impl Serialize for AppError {
         serializer.serialize_str(self.to_string().as_ref())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn upstream_grpc_codes_map_to_http_statuses() {
+        let cases = [
+            (Code::NotFound, StatusCode::NOT_FOUND),
+            (Code::Unauthenticated, StatusCode::UNAUTHORIZED),
+            (Code::PermissionDenied, StatusCode::FORBIDDEN),
+            (Code::AlreadyExists, StatusCode::CONFLICT),
+            (Code::InvalidArgument, StatusCode::UNPROCESSABLE_ENTITY),
+            (Code::FailedPrecondition, StatusCode::BAD_REQUEST),
+            (Code::ResourceExhausted, StatusCode::TOO_MANY_REQUESTS),
+            (Code::Unavailable, StatusCode::SERVICE_UNAVAILABLE),
+            (Code::DeadlineExceeded, StatusCode::GATEWAY_TIMEOUT),
+            (
+                Code::Cancelled,
+                StatusCode::from_u16(CLIENT_CLOSED_REQUEST).unwrap(),
+            ),
+            (Code::Internal, StatusCode::INTERNAL_SERVER_ERROR),
+            (Code::Unknown, StatusCode::INTERNAL_SERVER_ERROR),
+        ];
+
+        for (code, expected) in cases {
+            let err = AppError::from(Status::new(code, "upstream failed"));
+
+            assert_eq!(err.status_code(), expected, "{code:?}");
+            assert_eq!(ErrorResponse::from(err).code, expected, "{code:?}");
+        }
+    }
+
+    #[test]
+    fn upstream_message_is_kept() {
+        let err = AppError::from(Status::unavailable("server is draining"));
+
+        assert!(matches!(
+            err,
+            AppError::Upstream { code: Code::Unavailable, ref message } if message == "server is draining"
+        ));
+    }
+}
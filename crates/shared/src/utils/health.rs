@@ -0,0 +1,129 @@
+use serde::Serialize;
+use std::{collections::BTreeMap, future::Future, time::Duration};
+
+use crate::config::{ConnectionPool, RedisClient, migration_status};
+
+const CHECK_TIMEOUT: Duration = Duration::from_secs(2);
+
+#[derive(Debug, Serialize)]
+pub struct HealthReport {
+    pub status: String,
+    pub checks: BTreeMap<String, String>,
+}
+
+impl HealthReport {
+    pub fn is_healthy(&self) -> bool {
+        self.status == "ok"
+    }
+
+    // dipakai readiness probe selama AppState belum selesai diinisialisasi
+    pub fn not_ready(reason: &str) -> Self {
+        HealthReport {
+            status: "not_ready".to_string(),
+            checks: BTreeMap::from([("startup".to_string(), reason.to_string())]),
+        }
+    }
+}
+
+#[derive(Debug, Default)]
+pub struct HealthChecker {
+    checks: BTreeMap<String, Result<(), String>>,
+}
+
+impl HealthChecker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub async fn check<F, E>(mut self, name: &str, probe: F) -> Self
+    where
+        F: Future<Output = Result<(), E>>,
+        E: std::fmt::Display,
+    {
+        let result = match tokio::time::timeout(CHECK_TIMEOUT, probe).await {
+            Ok(Ok(())) => Ok(()),
+            Ok(Err(e)) => Err(e.to_string()),
+            Err(_) => Err(format!("timed out after {}s", CHECK_TIMEOUT.as_secs())),
+        };
+
+        self.checks.insert(name.to_string(), result);
+        self
+    }
+
+    pub fn report(self) -> HealthReport {
+        let healthy = self.checks.values().all(Result::is_ok);
+
+        HealthReport {
+            status: if healthy { "ok" } else { "unavailable" }.to_string(),
+            checks: self
+                .checks
+                .into_iter()
+                .map(|(name, result)| (name, result.err().unwrap_or_else(|| "ok".to_string())))
+                .collect(),
+        }
+    }
+}
+
+pub async fn check_database(pool: &ConnectionPool) -> Result<(), sqlx::Error> {
+    sqlx::query("SELECT 1").execute(pool).await.map(|_| ())
+}
+
+// readiness gagal selama masih ada migrasi yang belum jalan atau gagal di tengah jalan
+pub async fn check_migrations(pool: &ConnectionPool) -> anyhow::Result<()> {
+    let status = migration_status(pool).await?;
+
+    if status.is_up_to_date() {
+        Ok(())
+    } else {
+        Err(anyhow::anyhow!(status.summary()))
+    }
+}
+
+pub async fn check_redis(redis: &RedisClient) -> anyhow::Result<()> {
+    redis.ping_async().await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use sqlx::postgres::PgPoolOptions;
+
+    // port 1 tidak pernah dilayani, jadi koneksi langsung ditolak seperti DB yang mati
+    fn unreachable_pool() -> ConnectionPool {
+        PgPoolOptions::new()
+            .acquire_timeout(Duration::from_millis(500))
+            .connect_lazy("postgres://postgres@127.0.0.1:1/pay")
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn all_checks_passing_is_healthy() {
+        let report = HealthChecker::new()
+            .check("database", async { Ok::<_, String>(()) })
+            .await
+            .check("redis", async { Ok::<_, String>(()) })
+            .await
+            .report();
+
+        assert!(report.is_healthy());
+        assert_eq!(report.checks["database"], "ok");
+        assert_eq!(report.checks["redis"], "ok");
+    }
+
+    #[tokio::test]
+    async fn database_down_makes_the_report_unavailable() {
+        let pool = unreachable_pool();
+
+        let report = HealthChecker::new()
+            .check("database", check_database(&pool))
+            .await
+            .check("redis", async { Ok::<_, String>(()) })
+            .await
+            .report();
+
+        assert!(!report.is_healthy());
+        assert_eq!(report.status, "unavailable");
+        assert_ne!(report.checks["database"], "ok");
+        assert_eq!(report.checks["redis"], "ok");
+    }
+}
@@ -1,33 +1,116 @@
+use opentelemetry::{Context, trace::TraceContextExt};
 use opentelemetry_appender_tracing::layer::OpenTelemetryTracingBridge;
 use opentelemetry_sdk::logs::SdkLoggerProvider;
+use std::fmt;
+use tracing::{Event, Subscriber};
 use tracing_appender::{
     non_blocking,
     rolling::{RollingFileAppender, Rotation},
 };
-use tracing_subscriber::{EnvFilter, fmt, prelude::*};
+use tracing_subscriber::{
+    EnvFilter, Layer,
+    fmt::{
+        FmtContext, FormatEvent, FormatFields,
+        format::{Format, Json, JsonFields, Writer},
+    },
+    prelude::*,
+    registry::LookupSpan,
+};
+
+use crate::config::{LogConfig, LogFormat};
+
+// format JSON bawaan tracing_subscriber ditambah trace_id/span_id OpenTelemetry
+pub struct OtelJsonFormat {
+    inner: Format<Json>,
+}
+
+impl Default for OtelJsonFormat {
+    fn default() -> Self {
+        Self {
+            inner: tracing_subscriber::fmt::format().json(),
+        }
+    }
+}
+
+impl<S, N> FormatEvent<S, N> for OtelJsonFormat
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+    N: for<'a> FormatFields<'a> + 'static,
+{
+    fn format_event(
+        &self,
+        ctx: &FmtContext<'_, S, N>,
+        mut writer: Writer<'_>,
+        event: &Event<'_>,
+    ) -> fmt::Result {
+        let mut line = String::new();
+        self.inner
+            .format_event(ctx, Writer::new(&mut line), event)?;
+
+        let cx = Context::current();
+        let span = cx.span();
+        let span_context = span.span_context();
 
-pub fn init_logger(sdk_logger_provider: SdkLoggerProvider, component: &str) {
+        if !span_context.is_valid() {
+            return writer.write_str(&line);
+        }
+
+        match serde_json::from_str::<serde_json::Value>(&line) {
+            Ok(serde_json::Value::Object(mut fields)) => {
+                fields.insert(
+                    "trace_id".to_string(),
+                    span_context.trace_id().to_string().into(),
+                );
+                fields.insert(
+                    "span_id".to_string(),
+                    span_context.span_id().to_string().into(),
+                );
+                writeln!(writer, "{}", serde_json::Value::Object(fields))
+            }
+            _ => writer.write_str(&line),
+        }
+    }
+}
+
+pub fn init_logger(sdk_logger_provider: SdkLoggerProvider, component: &str, config: &LogConfig) {
     let log_dir = "/var/log/app";
     let file_name = format!("rust_app_{component}.log");
     let file_appender = RollingFileAppender::new(Rotation::DAILY, log_dir, file_name);
     let (file_writer, guard) = non_blocking(file_appender);
 
-    let file_layer = fmt::layer()
+    let file_layer = tracing_subscriber::fmt::layer()
         .with_writer(file_writer)
         .with_ansi(false)
         .json()
-        .with_filter(EnvFilter::new("info"));
+        .with_filter(config.env_filter("info"));
 
     let console_filter =
-        EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new("off"));
+        EnvFilter::try_from_default_env().unwrap_or_else(|_| config.env_filter("off"));
+
+    let (text_layer, json_layer) = match config.format {
+        LogFormat::Text => (
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .pretty()
+                    .with_thread_names(true)
+                    .with_ansi(true),
+            ),
+            None,
+        ),
+        LogFormat::Json => (
+            None,
+            Some(
+                tracing_subscriber::fmt::layer()
+                    .fmt_fields(JsonFields::new())
+                    .event_format(OtelJsonFormat::default()),
+            ),
+        ),
+    };
 
-    let console_layer = fmt::layer()
-        .pretty()
-        .with_thread_names(true)
-        .with_ansi(true)
-        .with_filter(console_filter);
+    let console_layer = Layer::and_then(text_layer, json_layer).with_filter(console_filter);
 
-    let otel_filter = EnvFilter::new("info")
+    let otel_filter = config
+        .env_filter("info")
         .add_directive("hyper=off".parse().unwrap())
         .add_directive("opentelemetry=off".parse().unwrap())
         .add_directive("tonic=off".parse().unwrap())
@@ -44,3 +127,101 @@ pub fn init_logger(sdk_logger_provider: SdkLoggerProvider, component: &str) {
 
     std::mem::forget(guard);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::trace::{SpanContext, SpanId, TraceFlags, TraceId, TraceState};
+    use std::{
+        io,
+        sync::{Arc, Mutex},
+    };
+    use tracing_subscriber::fmt::MakeWriter;
+
+    #[derive(Clone, Default)]
+    struct Captured(Arc<Mutex<Vec<u8>>>);
+
+    impl Captured {
+        fn lines(&self) -> Vec<String> {
+            String::from_utf8(self.0.lock().unwrap().clone())
+                .unwrap()
+                .lines()
+                .map(str::to_owned)
+                .collect()
+        }
+    }
+
+    impl io::Write for Captured {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().extend_from_slice(buf);
+            Ok(buf.len())
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    impl<'a> MakeWriter<'a> for Captured {
+        type Writer = Self;
+
+        fn make_writer(&'a self) -> Self::Writer {
+            self.clone()
+        }
+    }
+
+    fn json_subscriber(captured: &Captured) -> impl Subscriber + Send + Sync + use<> {
+        tracing_subscriber::registry().with(
+            tracing_subscriber::fmt::layer()
+                .with_writer(captured.clone())
+                .fmt_fields(JsonFields::new())
+                .event_format(OtelJsonFormat::default()),
+        )
+    }
+
+    #[test]
+    fn json_format_emits_parseable_lines_with_trace_ids() {
+        let captured = Captured::default();
+        let trace_id = TraceId::from_hex("4bf92f3577b34da6a3ce929d0e0e4736").unwrap();
+        let span_id = SpanId::from_hex("00f067aa0ba902b7").unwrap();
+
+        tracing::subscriber::with_default(json_subscriber(&captured), || {
+            tracing::warn!(target: "shared::service::transfer", "transfer rejected");
+
+            let cx = Context::new().with_remote_span_context(SpanContext::new(
+                trace_id,
+                span_id,
+                TraceFlags::SAMPLED,
+                true,
+                TraceState::default(),
+            ));
+            let _guard = cx.attach();
+            tracing::info!(target: "shared::service::topup", "topup confirmed");
+        });
+
+        let lines: Vec<serde_json::Value> = captured
+            .lines()
+            .iter()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 2);
+
+        assert_eq!(lines[0]["level"], "WARN");
+        assert_eq!(lines[0]["target"], "shared::service::transfer");
+        assert!(lines[0].get("trace_id").is_none());
+
+        assert_eq!(lines[1]["level"], "INFO");
+        assert_eq!(lines[1]["target"], "shared::service::topup");
+        assert_eq!(lines[1]["trace_id"], trace_id.to_string());
+        assert_eq!(lines[1]["span_id"], span_id.to_string());
+    }
+
+    #[test]
+    fn unknown_log_format_is_rejected() {
+        assert_eq!(" JSON ".parse::<LogFormat>().unwrap(), LogFormat::Json);
+        assert_eq!("text".parse::<LogFormat>().unwrap(), LogFormat::Text);
+
+        let err = "yaml".parse::<LogFormat>().unwrap_err();
+        assert!(err.to_string().contains("LOG_FORMAT"));
+    }
+}
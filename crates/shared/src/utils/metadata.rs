@@ -1,6 +1,12 @@
-use opentelemetry::propagation::Injector;
+use axum::http::HeaderMap;
+use opentelemetry::propagation::{Extractor, Injector};
 use tonic::metadata::{MetadataKey, MetadataMap, MetadataValue};
 
+use crate::utils::{
+    bearer::{AUTHORIZATION_HEADER, current_bearer_token},
+    request_id::{REQUEST_ID_HEADER, current_request_id},
+};
+
 pub struct MetadataInjector<'a>(pub &'a mut MetadataMap);
 
 impl<'a> Injector for MetadataInjector<'a> {
@@ -12,3 +18,29 @@ impl<'a> Injector for MetadataInjector<'a> {
         }
     }
 }
+
+impl MetadataInjector<'_> {
+    pub fn inject_request_id(&mut self) {
+        if let Some(request_id) = current_request_id() {
+            self.set(REQUEST_ID_HEADER, request_id);
+        }
+    }
+
+    pub fn inject_bearer_token(&mut self) {
+        if let Some(token) = current_bearer_token() {
+            self.set(AUTHORIZATION_HEADER, format!("Bearer {token}"));
+        }
+    }
+}
+
+pub struct HeaderExtractor<'a>(pub &'a HeaderMap);
+
+impl<'a> Extractor for HeaderExtractor<'a> {
+    fn get(&self, key: &str) -> Option<&str> {
+        self.0.get(key).and_then(|value| value.to_str().ok())
+    }
+
+    fn keys(&self) -> Vec<&str> {
+        self.0.keys().map(|key| key.as_str()).collect()
+    }
+}
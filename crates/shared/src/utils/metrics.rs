@@ -1,13 +1,24 @@
 use prometheus_client::metrics::histogram::Histogram;
-use prometheus_client::metrics::{counter::Counter, family::Family, gauge::Gauge};
+use prometheus_client::metrics::{
+    counter::Counter,
+    family::{Family, MetricConstructor},
+    gauge::Gauge,
+};
 use prometheus_client::registry::Registry;
 use prometheus_client_derive_encode::{EncodeLabelSet, EncodeLabelValue};
+use serde::Serialize;
 use std::{
     fs,
     sync::Arc,
     time::{SystemTime, UNIX_EPOCH},
 };
 use sysinfo::System;
+use tracing::error;
+
+use crate::{
+    abstract_trait::DynSaldoRepository,
+    utils::{BUILD_INFO, BuildInfoLabels},
+};
 
 fn get_thread_count(pid: usize) -> Option<i64> {
     let path = format!("/proc/{pid}/status");
@@ -24,6 +35,15 @@ fn get_thread_count(pid: usize) -> Option<i64> {
     None
 }
 
+// nilai terakhir yang dikumpulkan `run_metrics_collector`, tanpa membaca /proc lagi
+#[derive(Debug, Clone, Serialize)]
+pub struct ProcessSnapshot {
+    pub uptime_secs: u64,
+    pub thread_count: Option<i64>,
+    pub memory_bytes: Option<i64>,
+    pub virtual_memory_bytes: Option<i64>,
+}
+
 #[derive(Debug, Clone)]
 pub struct SystemMetrics {
     pub memory_alloc_bytes: Gauge,
@@ -32,6 +52,7 @@ pub struct SystemMetrics {
     pub thread_usage: Gauge,
     pub total_cpu_usage: Counter,
     pub process_start_time: Gauge,
+    pub build_info: Family<BuildInfoLabels, Gauge>,
 }
 
 impl Default for SystemMetrics {
@@ -54,10 +75,15 @@ impl SystemMetrics {
             thread_usage: Gauge::default(),
             total_cpu_usage: Counter::default(),
             process_start_time: Gauge::default(),
+            build_info: Family::default(),
         };
 
         metrics.process_start_time.set(start_time as i64);
         metrics
+            .build_info
+            .get_or_create(&BuildInfoLabels::from(BUILD_INFO))
+            .set(1);
+        metrics
     }
 
     pub fn register(&self, registry: &mut Registry) {
@@ -96,6 +122,28 @@ impl SystemMetrics {
             "Start time of the process since unix epoch in seconds",
             self.process_start_time.clone(),
         );
+
+        registry.register(
+            "build_info",
+            "Build version, git SHA and build timestamp of the running binary",
+            self.build_info.clone(),
+        );
+    }
+
+    // gauge bernilai 0 berarti belum pernah terkumpul atau tidak tersedia di platform ini
+    pub fn snapshot(&self) -> ProcessSnapshot {
+        let now = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs() as i64)
+            .unwrap_or_default();
+        let collected = |value: i64| (value > 0).then_some(value);
+
+        ProcessSnapshot {
+            uptime_secs: now.saturating_sub(self.process_start_time.get()).max(0) as u64,
+            thread_count: collected(self.thread_usage.get()),
+            memory_bytes: collected(self.memory_alloc_bytes.get()),
+            virtual_memory_bytes: collected(self.memory_sys_bytes.get()),
+        }
     }
 
     pub async fn update_metrics(&self) {
@@ -139,40 +187,117 @@ pub enum Status {
 #[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
 pub struct Labels {
     pub method: Method,
+    pub operation: String,
     pub status: Status,
 }
 
+#[derive(Clone, Debug, Hash, PartialEq, Eq, EncodeLabelSet)]
+pub struct ErrorLabels {
+    pub method: Method,
+    pub operation: String,
+    pub error_kind: String,
+}
+
 #[derive(Clone, Debug)]
 pub struct Metrics {
     pub request_counter: Family<Labels, Counter>,
-    pub request_duration: Family<Labels, Histogram>,
+    pub request_duration: Family<Labels, Histogram, HistogramBuckets>,
+    pub error_counter: Family<ErrorLabels, Counter>,
 }
 
+#[derive(Clone, Debug)]
+pub struct HistogramBuckets(Vec<f64>);
+
+impl MetricConstructor<Histogram> for HistogramBuckets {
+    fn new_metric(&self) -> Histogram {
+        Histogram::new(self.0.iter().copied())
+    }
+}
+
+pub const DEFAULT_HISTOGRAM_BUCKETS: [f64; 11] = [
+    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
+];
+
 impl Default for Metrics {
     fn default() -> Self {
-        Self::new()
+        Self::new(DEFAULT_HISTOGRAM_BUCKETS.to_vec())
     }
 }
 
 impl Metrics {
-    pub fn new() -> Self {
+    pub fn new(buckets: Vec<f64>) -> Self {
         Self {
             request_counter: Family::default(),
-            request_duration: Family::new_with_constructor(|| {
-                Histogram::new(vec![
-                    0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0,
-                ])
-            }),
+            request_duration: Family::new_with_constructor(HistogramBuckets(buckets)),
+            error_counter: Family::default(),
         }
     }
 
-    pub fn record(&self, method: Method, status: Status, duration_secs: f64) {
-        let labels = Labels { method, status };
+    pub fn record(&self, method: Method, status: Status, operation: &str, duration_secs: f64) {
+        let labels = Labels {
+            method,
+            operation: operation.to_string(),
+            status,
+        };
         self.request_counter.get_or_create(&labels).inc();
         self.request_duration
             .get_or_create(&labels)
             .observe(duration_secs);
     }
+
+    pub fn record_error(&self, method: Method, operation: &str, error_kind: &str) {
+        let labels = ErrorLabels {
+            method,
+            operation: operation.to_string(),
+            error_kind: error_kind.to_string(),
+        };
+        self.error_counter.get_or_create(&labels).inc();
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct SaldoMetrics {
+    pub total_balance: Gauge,
+    pub saldo_count: Gauge,
+}
+
+impl SaldoMetrics {
+    pub fn register(&self, registry: &mut Registry) {
+        registry.register(
+            "saldo_total_balance",
+            "Sum of total_balance across all saldo records",
+            self.total_balance.clone(),
+        );
+
+        registry.register(
+            "saldo_records",
+            "Number of saldo records",
+            self.saldo_count.clone(),
+        );
+    }
+
+    // kalau query gagal, nilai gauge sebelumnya tetap dipakai
+    pub async fn update_metrics(&self, repository: &DynSaldoRepository) {
+        match repository.totals().await {
+            Ok((total_balance, count)) => {
+                self.total_balance.set(total_balance);
+                self.saldo_count.set(count);
+            }
+            Err(e) => error!("Failed to collect saldo metrics: {e}"),
+        }
+    }
+}
+
+pub async fn run_saldo_metrics_collector(
+    repository: DynSaldoRepository,
+    saldo_metrics: Arc<SaldoMetrics>,
+    interval: std::time::Duration,
+) {
+    let mut interval = tokio::time::interval(interval);
+    loop {
+        interval.tick().await;
+        saldo_metrics.update_metrics(&repository).await;
+    }
 }
 
 pub async fn run_metrics_collector(system_metrics: Arc<SystemMetrics>) {
@@ -182,3 +307,177 @@ pub async fn run_metrics_collector(system_metrics: Arc<SystemMetrics>) {
         system_metrics.update_metrics().await;
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::repository::{memory::InMemoryRepositories, saldo::SaldoRepository};
+    use prometheus_client::encoding::text::encode;
+    use sqlx::postgres::PgPoolOptions;
+    use std::time::Duration;
+
+    fn scrape(metrics: &Metrics) -> String {
+        let mut registry = Registry::default();
+        registry.register(
+            "service_request_counter",
+            "Total number of requests",
+            metrics.request_counter.clone(),
+        );
+        registry.register(
+            "service_request_duration",
+            "Histogram of request durations",
+            metrics.request_duration.clone(),
+        );
+
+        let mut body = String::new();
+        encode(&mut body, &registry).unwrap();
+        body
+    }
+
+    #[test]
+    fn each_operation_gets_its_own_series() {
+        let metrics = Metrics::default();
+        metrics.record(Method::Post, Status::Success, "CreateTransfer", 0.2);
+        metrics.record(Method::Get, Status::Success, "FindAllSaldo", 0.01);
+        metrics.record(Method::Get, Status::Success, "FindAllSaldo", 0.01);
+
+        let body = scrape(&metrics);
+
+        assert!(body.contains(
+            r#"service_request_counter_total{method="Post",operation="CreateTransfer",status="Success"} 1"#
+        ));
+        assert!(body.contains(
+            r#"service_request_counter_total{method="Get",operation="FindAllSaldo",status="Success"} 2"#
+        ));
+        assert!(body.contains(
+            r#"service_request_duration_count{method="Post",operation="CreateTransfer",status="Success"} 1"#
+        ));
+    }
+
+    #[test]
+    fn failed_calls_do_not_share_the_success_series() {
+        let metrics = Metrics::default();
+        metrics.record(Method::Post, Status::Success, "CreateTransfer", 0.2);
+        metrics.record(Method::Post, Status::Error, "CreateTransfer", 0.3);
+
+        let body = scrape(&metrics);
+
+        assert!(body.contains(
+            r#"service_request_counter_total{method="Post",operation="CreateTransfer",status="Success"} 1"#
+        ));
+        assert!(body.contains(
+            r#"service_request_counter_total{method="Post",operation="CreateTransfer",status="Error"} 1"#
+        ));
+        assert!(!body.contains(r#"operation="FindAllSaldo""#));
+    }
+
+    #[test]
+    fn histogram_uses_the_configured_buckets() {
+        let metrics = Metrics::new(vec![0.0005, 0.001, 30.0]);
+        metrics.record(Method::Get, Status::Success, "FindAllSaldo", 0.0002);
+
+        let body = scrape(&metrics);
+
+        for bucket in ["0.0005", "0.001", "30.0", "+Inf"] {
+            assert!(body.contains(&format!(
+                r#"service_request_duration_bucket{{le="{bucket}",method="Get",operation="FindAllSaldo",status="Success"}} 1"#
+            )));
+        }
+        assert!(!body.contains(r#"le="0.005""#));
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn concurrent_records_are_all_counted() {
+        let metrics = Arc::new(Metrics::default());
+
+        let tasks: Vec<_> = (0..64)
+            .map(|i| {
+                let metrics = metrics.clone();
+                tokio::spawn(async move {
+                    for _ in 0..100 {
+                        if i % 4 == 0 {
+                            metrics.record(Method::Post, Status::Error, "CreateTransfer", 0.01);
+                            metrics.record_error(Method::Post, "CreateTransfer", "custom");
+                        } else {
+                            metrics.record(Method::Post, Status::Success, "CreateTransfer", 0.01);
+                        }
+                    }
+                })
+            })
+            .collect();
+        for task in tasks {
+            task.await.unwrap();
+        }
+
+        let labels = |status| Labels {
+            method: Method::Post,
+            operation: "CreateTransfer".to_string(),
+            status,
+        };
+        assert_eq!(
+            metrics
+                .request_counter
+                .get_or_create(&labels(Status::Success))
+                .get(),
+            4_800
+        );
+        assert_eq!(
+            metrics
+                .request_counter
+                .get_or_create(&labels(Status::Error))
+                .get(),
+            1_600
+        );
+        let errors = ErrorLabels {
+            method: Method::Post,
+            operation: "CreateTransfer".to_string(),
+            error_kind: "custom".to_string(),
+        };
+        assert_eq!(metrics.error_counter.get_or_create(&errors).get(), 1_600);
+    }
+
+    #[test]
+    fn build_info_is_a_constant_one_labelled_with_the_build() {
+        let mut registry = Registry::default();
+        SystemMetrics::new().register(&mut registry);
+
+        let mut body = String::new();
+        encode(&mut body, &registry).unwrap();
+
+        assert!(body.contains(&format!(
+            r#"build_info{{version="{}",git_sha="{}",build_timestamp="{}"}} 1"#,
+            BUILD_INFO.version, BUILD_INFO.git_sha, BUILD_INFO.build_timestamp
+        )));
+    }
+
+    #[tokio::test]
+    async fn saldo_gauges_reflect_the_seeded_balances() {
+        let repos = InMemoryRepositories::new();
+        repos.seed_user("a@example.com", 150_000).await;
+        repos.seed_user("b@example.com", 50_000).await;
+        let saldo_metrics = SaldoMetrics::default();
+
+        saldo_metrics.update_metrics(&repos.saldo).await;
+
+        assert_eq!(saldo_metrics.total_balance.get(), 200_000);
+        assert_eq!(saldo_metrics.saldo_count.get(), 2);
+    }
+
+    #[tokio::test]
+    async fn failed_collection_keeps_the_last_value() {
+        // port 1 tidak pernah dilayani, query totals selalu gagal
+        let pool = PgPoolOptions::new()
+            .acquire_timeout(Duration::from_millis(200))
+            .connect_lazy("postgres://postgres@127.0.0.1:1/pay")
+            .unwrap();
+        let repository: DynSaldoRepository = Arc::new(SaldoRepository::new(pool));
+        let saldo_metrics = SaldoMetrics::default();
+        saldo_metrics.total_balance.set(200_000);
+        saldo_metrics.saldo_count.set(2);
+
+        saldo_metrics.update_metrics(&repository).await;
+
+        assert_eq!(saldo_metrics.total_balance.get(), 200_000);
+        assert_eq!(saldo_metrics.saldo_count.get(), 2);
+    }
+}
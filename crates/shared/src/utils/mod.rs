@@ -1,6 +1,12 @@
+mod bearer;
+mod build_info;
+mod cancellation;
+mod concurrency;
+mod deadline;
 mod di;
 mod errors;
 mod gracefulshutdown;
+mod health;
 mod log;
 mod metadata;
 mod method_validator;
@@ -8,13 +14,35 @@ mod metrics;
 mod otel;
 mod parsetime;
 mod random_vcc;
+mod request_id;
+mod webhook;
 
+pub use self::bearer::{
+    AUTHORIZATION_HEADER, current_bearer_token, parse_bearer_token, with_bearer_token,
+};
+pub use self::build_info::{BUILD_INFO, BuildInfo, BuildInfoLabels};
+pub use self::cancellation::{cancellable, current_cancellation, with_cancellation};
+pub use self::concurrency::{ConcurrencyLimit, RETRY_AFTER_HEADER};
+pub use self::deadline::{
+    GRPC_TIMEOUT_HEADER, TIMEOUT_HEADER, current_deadline, parse_grpc_timeout, parse_timeout_ms,
+    remaining_time, with_deadline,
+};
 pub use self::di::DependenciesInject;
-pub use self::errors::AppError;
+pub use self::errors::{AppError, CLIENT_CLOSED_REQUEST, checked_amount};
 pub use self::gracefulshutdown::shutdown_signal;
+pub use self::health::{
+    HealthChecker, HealthReport, check_database, check_migrations, check_redis,
+};
 pub use self::log::init_logger;
-pub use self::metadata::MetadataInjector;
-pub use self::metrics::{Method, Metrics, Status, SystemMetrics, run_metrics_collector};
-pub use self::otel::{Telemetry, TracingContext};
+pub use self::metadata::{HeaderExtractor, MetadataInjector};
+pub use self::metrics::{
+    DEFAULT_HISTOGRAM_BUCKETS, ErrorLabels, HistogramBuckets, Method, Metrics, ProcessSnapshot,
+    SaldoMetrics, Status, SystemMetrics, run_metrics_collector, run_saldo_metrics_collector,
+};
+pub use self::otel::{DbSpan, Telemetry, TracingContext};
 pub use self::parsetime::parse_datetime;
-pub use self::random_vcc::random_vcc;
+pub use self::random_vcc::{luhn_valid, mask_card_number, random_vcc};
+pub use self::request_id::{
+    REQUEST_ID_HEADER, current_request_id, is_valid_request_id, with_request_id,
+};
+pub use self::webhook::{EVENT_TYPE_HEADER, SIGNATURE_HEADER, run_outbox_worker, sign_payload};
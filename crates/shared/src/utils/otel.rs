@@ -1,10 +1,17 @@
 use std::sync::OnceLock;
 
 use anyhow::Result;
-use opentelemetry::{Context, global};
+use opentelemetry::{
+    Context, KeyValue, global,
+    trace::{Span, SpanKind, Tracer},
+};
 use opentelemetry_otlp::{LogExporter, MetricExporter, SpanExporter, WithExportConfig};
 use opentelemetry_sdk::{
-    Resource, logs::SdkLoggerProvider, metrics::SdkMeterProvider, trace::SdkTracerProvider,
+    Resource,
+    logs::SdkLoggerProvider,
+    metrics::SdkMeterProvider,
+    propagation::TraceContextPropagator,
+    trace::{Sampler, SdkTracerProvider},
 };
 use tokio::time::Instant;
 
@@ -12,21 +19,67 @@ use tokio::time::Instant;
 pub struct Telemetry {
     service_name: String,
     otel_endpoint: String,
+    sample_ratio: f64,
 }
 
 pub struct TracingContext {
     pub cx: Context,
     pub start_time: Instant,
+    pub operation: String,
+}
+
+// span untuk satu operasi repository, otomatis ditutup saat di-drop; parent-nya diambil dari
+// Context::current(), jadi service harus menjalankan pemanggilan repository dengan
+// `.with_context(tracing_ctx.cx.clone())` supaya span ini menjadi anak span service
+pub struct DbSpan {
+    span: global::BoxedSpan,
+    start_time: Instant,
+}
+
+impl DbSpan {
+    pub fn start(table: &'static str, operation: &'static str) -> Self {
+        let tracer = global::tracer("repository");
+        let span = tracer
+            .span_builder(format!("{table}.{operation}"))
+            .with_kind(SpanKind::Client)
+            .with_attributes(vec![
+                KeyValue::new("db.system", "postgresql"),
+                KeyValue::new("db.sql.table", table),
+                KeyValue::new("db.operation", operation),
+            ])
+            .start(&tracer);
+
+        Self {
+            span,
+            start_time: Instant::now(),
+        }
+    }
+}
+
+impl Drop for DbSpan {
+    fn drop(&mut self) {
+        let elapsed_ms = self.start_time.elapsed().as_secs_f64() * 1000.0;
+        self.span
+            .set_attribute(KeyValue::new("db.duration_ms", elapsed_ms));
+        self.span.end();
+    }
 }
 
 impl Telemetry {
-    pub fn new(service_name: impl Into<String>, otel_endpoint: String) -> Self {
+    pub fn new(service_name: impl Into<String>, otel_endpoint: String, sample_ratio: f64) -> Self {
         Self {
             service_name: service_name.into(),
             otel_endpoint,
+            sample_ratio,
         }
     }
 
+    // root span diambil sesuai rasio, span dengan parent mengikuti keputusan parent-nya
+    // sehingga trace dari client tetap utuh sampai ke server
+    fn sampler(&self) -> Sampler {
+        Sampler::ParentBased(Box::new(Sampler::TraceIdRatioBased(self.sample_ratio)))
+    }
+
     fn get_resource(&self) -> Resource {
         static RESOURCE: OnceLock<Resource> = OnceLock::new();
         RESOURCE
@@ -47,10 +100,12 @@ impl Telemetry {
 
         let provider = SdkTracerProvider::builder()
             .with_resource(self.get_resource())
+            .with_sampler(self.sampler())
             .with_batch_exporter(exporter)
             .build();
 
         global::set_tracer_provider(provider.clone());
+        global::set_text_map_propagator(TraceContextPropagator::new());
 
         provider
     }
@@ -105,3 +160,77 @@ impl Telemetry {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use opentelemetry::{
+        context::FutureExt,
+        trace::{
+            SpanContext, SpanId, TraceContextExt, TraceFlags, TraceId, TraceState, TracerProvider,
+        },
+    };
+
+    #[tokio::test]
+    async fn db_span_joins_the_attached_service_span() {
+        global::set_tracer_provider(SdkTracerProvider::builder().build());
+
+        let tracer = global::tracer("service");
+        let cx = Context::current_with_span(tracer.start("GetUser"));
+        let trace_id = cx.span().span_context().trace_id();
+
+        let nested = async {
+            DbSpan::start("users", "find_by_id")
+                .span
+                .span_context()
+                .trace_id()
+        }
+        .with_context(cx.clone())
+        .await;
+        assert_eq!(nested, trace_id);
+
+        let detached = DbSpan::start("users", "find_by_id")
+            .span
+            .span_context()
+            .trace_id();
+        assert_ne!(detached, trace_id);
+    }
+
+    fn sampled_tracer(ratio: f64) -> opentelemetry_sdk::trace::SdkTracer {
+        let telemetry = Telemetry::new("test", String::new(), ratio);
+
+        SdkTracerProvider::builder()
+            .with_sampler(telemetry.sampler())
+            .build()
+            .tracer("test")
+    }
+
+    #[test]
+    fn full_ratio_samples_root_spans() {
+        let span = sampled_tracer(1.0).start("GetUser");
+
+        assert!(span.span_context().is_sampled());
+    }
+
+    #[test]
+    fn zero_ratio_drops_roots_but_keeps_children_of_sampled_parents() {
+        let tracer = sampled_tracer(0.0);
+
+        let root = tracer.start("GetUser");
+        assert!(!root.span_context().is_sampled());
+
+        // parent dari client yang sudah di-sample, seperti hasil propagasi traceparent
+        let parent = SpanContext::new(
+            TraceId::from(1),
+            SpanId::from(1),
+            TraceFlags::SAMPLED,
+            true,
+            TraceState::default(),
+        );
+        let cx = Context::new().with_remote_span_context(parent);
+        let child = tracer.start_with_context("FindUser", &cx);
+
+        assert!(child.span_context().is_sampled());
+        assert_eq!(child.span_context().trace_id(), TraceId::from(1));
+    }
+}
@@ -3,8 +3,9 @@ use rand::Rng;
 pub fn random_vcc() -> Result<String, &'static str> {
     let mut rng = rand::rng();
 
+    // prefix "4" + 14 digit acak + 1 check digit = 16 digit
     let mut random_number = String::new();
-    for _ in 0..15 {
+    for _ in 0..14 {
         random_number.push_str(&rng.random_range(0..10).to_string());
     }
 
@@ -15,9 +16,27 @@ pub fn random_vcc() -> Result<String, &'static str> {
     Ok(credit_card_number)
 }
 
+pub fn luhn_valid(number: &str) -> bool {
+    if number.len() < 2 || !number.chars().all(|c| c.is_ascii_digit()) {
+        return false;
+    }
+
+    luhn_sum(number, false) % 10 == 0
+}
+
+pub fn mask_card_number(number: &str) -> String {
+    let last4 = &number[number.len().saturating_sub(4)..];
+    format!("**** **** **** {last4}")
+}
+
 fn calculate_check_digit(number: &str) -> u32 {
+    // digit paling kanan dari nomor parsial ikut digandakan karena check digit akan ditambahkan di belakangnya
+    (10 - (luhn_sum(number, true) % 10)) % 10
+}
+
+fn luhn_sum(number: &str, double_first: bool) -> u32 {
     let mut sum = 0;
-    let mut alternate = false;
+    let mut alternate = double_first;
 
     for digit_char in number.chars().rev() {
         let mut digit = digit_char.to_digit(10).expect("Invalid digit in number");
@@ -33,5 +52,38 @@ fn calculate_check_digit(number: &str) -> u32 {
         alternate = !alternate;
     }
 
-    (10 - (sum % 10)) % 10
+    sum
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn generated_numbers_pass_luhn() {
+        for _ in 0..100 {
+            let number = random_vcc().unwrap();
+
+            assert_eq!(number.len(), 16);
+            assert!(number.starts_with('4'));
+            assert!(luhn_valid(&number), "{number} fails the Luhn check");
+        }
+    }
+
+    #[test]
+    fn known_numbers_are_checked() {
+        assert!(luhn_valid("4111111111111111"));
+        // digit terakhir diubah sehingga check digit tidak cocok
+        assert!(!luhn_valid("4111111111111112"));
+        assert!(!luhn_valid("4111 1111 1111 1111"));
+        assert!(!luhn_valid("0"));
+    }
+
+    #[test]
+    fn mask_keeps_only_the_last_four_digits() {
+        let masked = mask_card_number("4111111111111234");
+
+        assert_eq!(masked, "**** **** **** 1234");
+        assert_eq!(masked.chars().filter(char::is_ascii_digit).count(), 4);
+    }
 }
@@ -0,0 +1,218 @@
+use anyhow::{Context, Result};
+use chrono::Utc;
+use hmac::{Hmac, Mac};
+use sha2::Sha256;
+use tokio::sync::broadcast;
+use tracing::{error, info, warn};
+
+use crate::{
+    abstract_trait::DynOutboxRepository, config::WebhookConfig, model::outbox::OutboxEvent,
+};
+
+type HmacSha256 = Hmac<Sha256>;
+
+pub const SIGNATURE_HEADER: &str = "X-Signature";
+pub const EVENT_TYPE_HEADER: &str = "X-Event-Type";
+// id event yang sama dikirim ulang pada redelivery, dipakai penerima untuk dedup
+pub const EVENT_ID_HEADER: &str = "X-Event-Id";
+
+pub fn sign_payload(secret: &str, payload: &str) -> String {
+    let mut mac =
+        HmacSha256::new_from_slice(secret.as_bytes()).expect("HMAC accepts keys of any length");
+    mac.update(payload.as_bytes());
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+pub async fn run_outbox_worker(
+    repository: DynOutboxRepository,
+    config: WebhookConfig,
+    mut shutdown_rx: broadcast::Receiver<()>,
+) -> Result<()> {
+    let Some(url) = config.url.clone() else {
+        info!("📭 Webhook URL not configured, outbox worker disabled");
+        return Ok(());
+    };
+
+    let client = reqwest::Client::builder()
+        .timeout(config.timeout)
+        .build()
+        .context("Failed to build webhook HTTP client")?;
+
+    info!("📮 Outbox worker started, delivering events to {url}");
+
+    let mut interval = tokio::time::interval(config.poll_interval);
+
+    loop {
+        tokio::select! {
+            _ = interval.tick() => {}
+            _ = shutdown_rx.recv() => {
+                info!("Outbox worker received shutdown signal");
+                return Ok(());
+            }
+        }
+
+        relay_batch(&repository, &client, &url, &config).await;
+    }
+}
+
+// satu putaran worker: klaim event pending lalu kirim satu per satu, event yang gagal
+// dijadwalkan ulang dengan backoff atau ditandai gagal setelah max_attempts
+async fn relay_batch(
+    repository: &DynOutboxRepository,
+    client: &reqwest::Client,
+    url: &str,
+    config: &WebhookConfig,
+) {
+    let events = match repository
+        .claim_pending(config.batch_size, config.claim_lease())
+        .await
+    {
+        Ok(events) => events,
+        Err(e) => {
+            error!("❌ Outbox worker failed to fetch pending events: {e}");
+            return;
+        }
+    };
+
+    for event in events {
+        let result = match deliver(client, url, &config.secret, &event).await {
+            Ok(()) => repository.mark_delivered(event.id).await,
+            Err(e) => {
+                let message = e.to_string();
+                let attempts = event.attempts + 1;
+
+                if attempts >= config.max_attempts {
+                    repository.mark_failed(event.id, &message).await
+                } else {
+                    warn!(
+                        "⚠️ Webhook delivery of event ID {} failed (attempt {attempts}): {message}",
+                        event.id
+                    );
+                    let next_attempt_at = Utc::now().naive_utc() + config.backoff(event.attempts);
+                    repository
+                        .schedule_retry(event.id, next_attempt_at, &message)
+                        .await
+                }
+            }
+        };
+
+        if let Err(e) = result {
+            error!(
+                "❌ Outbox worker failed to update event ID {}: {e}",
+                event.id
+            );
+        }
+    }
+}
+
+async fn deliver(
+    client: &reqwest::Client,
+    url: &str,
+    secret: &str,
+    event: &OutboxEvent,
+) -> Result<()> {
+    let response = client
+        .post(url)
+        .header(reqwest::header::CONTENT_TYPE, "application/json")
+        .header(SIGNATURE_HEADER, sign_payload(secret, &event.payload))
+        .header(EVENT_TYPE_HEADER, &event.event_type)
+        .header(EVENT_ID_HEADER, event.id.to_string())
+        .body(event.payload.clone())
+        .send()
+        .await
+        .context("Webhook request failed")?;
+
+    let status = response.status();
+    if !status.is_success() {
+        return Err(anyhow::anyhow!("Webhook responded with status {status}"));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        model::outbox::OutboxStatus,
+        repository::memory::{InMemoryDatabase, InMemoryOutboxRepository},
+    };
+    use axum::{Router, http::StatusCode, routing::post};
+    use std::{
+        sync::{
+            Arc,
+            atomic::{AtomicU32, Ordering},
+        },
+        time::Duration,
+    };
+
+    // target webhook tiruan: request pertama gagal dengan 500, berikutnya diterima
+    async fn flaky_target(calls: Arc<AtomicU32>) -> String {
+        let app = Router::new().route(
+            "/hook",
+            post(move || async move {
+                if calls.fetch_add(1, Ordering::SeqCst) == 0 {
+                    StatusCode::INTERNAL_SERVER_ERROR
+                } else {
+                    StatusCode::OK
+                }
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+
+        format!("http://{addr}/hook")
+    }
+
+    fn pending_event() -> OutboxEvent {
+        let now = Utc::now().naive_utc();
+        OutboxEvent {
+            id: 1,
+            event_type: "transfer.completed".to_string(),
+            payload: r#"{"transfer_id":1}"#.to_string(),
+            status: OutboxStatus::Pending.as_str().to_string(),
+            attempts: 0,
+            last_error: None,
+            next_attempt_at: now,
+            created_at: Some(now),
+            delivered_at: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn failed_delivery_is_retried_until_delivered() {
+        let db = InMemoryDatabase::new();
+        db.tables().outbox.push(pending_event());
+        let repository: DynOutboxRepository = Arc::new(InMemoryOutboxRepository::new(db.clone()));
+
+        let calls = Arc::new(AtomicU32::new(0));
+        let url = flaky_target(calls.clone()).await;
+        let config = WebhookConfig {
+            url: Some(url.clone()),
+            secret: "secret".to_string(),
+            initial_backoff: Duration::ZERO,
+            // klaim tanpa lease supaya putaran berikutnya langsung melihat event yang dijadwalkan ulang
+            poll_interval: Duration::ZERO,
+            timeout: Duration::ZERO,
+            ..WebhookConfig::default()
+        };
+        let client = reqwest::Client::new();
+
+        relay_batch(&repository, &client, &url, &config).await;
+
+        let event = repository.find_by_id(1).await.unwrap().unwrap();
+        assert_eq!(event.status, OutboxStatus::Pending.as_str());
+        assert_eq!(event.attempts, 1);
+        assert!(event.last_error.unwrap().contains("500"));
+
+        relay_batch(&repository, &client, &url, &config).await;
+
+        let event = repository.find_by_id(1).await.unwrap().unwrap();
+        assert_eq!(event.status, OutboxStatus::Delivered.as_str());
+        assert_eq!(event.attempts, 2);
+        assert!(event.delivered_at.is_some());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+}
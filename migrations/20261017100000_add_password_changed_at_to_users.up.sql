@@ -0,0 +1,2 @@
+-- Add up migration script here
+ALTER TABLE "users" ADD COLUMN IF NOT EXISTS password_changed_at TIMESTAMP NULL;
@@ -0,0 +1,2 @@
+-- Add down migration script here
+ALTER TABLE "saldo" DROP CONSTRAINT IF EXISTS chk_saldo_total_balance_non_negative;
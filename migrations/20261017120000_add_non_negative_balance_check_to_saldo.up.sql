@@ -0,0 +1,3 @@
+-- Add up migration script here
+ALTER TABLE "saldo"
+    ADD CONSTRAINT chk_saldo_total_balance_non_negative CHECK (total_balance >= 0);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+ALTER TABLE "users" DROP CONSTRAINT IF EXISTS chk_users_status;
+
+ALTER TABLE "users" DROP COLUMN IF EXISTS status;
@@ -0,0 +1,4 @@
+-- Add up migration script here
+ALTER TABLE "users"
+    ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'active',
+    ADD CONSTRAINT chk_users_status CHECK (status IN ('active', 'frozen'));
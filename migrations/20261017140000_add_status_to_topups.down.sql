@@ -0,0 +1,4 @@
+-- Add down migration script here
+ALTER TABLE "topups" DROP CONSTRAINT IF EXISTS chk_topups_status;
+
+ALTER TABLE "topups" DROP COLUMN IF EXISTS status;
@@ -0,0 +1,8 @@
+-- Add up migration script here
+-- topup lama sudah menambah saldo, jadi dianggap success
+ALTER TABLE "topups" ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'success';
+
+ALTER TABLE "topups" ALTER COLUMN status SET DEFAULT 'pending';
+
+ALTER TABLE "topups"
+    ADD CONSTRAINT chk_topups_status CHECK (status IN ('pending', 'success', 'failed'));
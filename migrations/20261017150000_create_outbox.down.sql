@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_outbox_pending;
+
+DROP TABLE IF EXISTS "outbox";
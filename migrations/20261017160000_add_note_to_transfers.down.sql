@@ -0,0 +1,2 @@
+-- Add down migration script here
+ALTER TABLE "transfers" DROP COLUMN IF EXISTS note;
@@ -0,0 +1,2 @@
+-- Add up migration script here
+ALTER TABLE "transfers" ADD COLUMN IF NOT EXISTS note VARCHAR(255);
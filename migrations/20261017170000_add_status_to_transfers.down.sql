@@ -0,0 +1,4 @@
+-- Add down migration script here
+ALTER TABLE "transfers" DROP CONSTRAINT IF EXISTS chk_transfers_status;
+
+ALTER TABLE "transfers" DROP COLUMN IF EXISTS status;
@@ -0,0 +1,4 @@
+-- Add up migration script here
+ALTER TABLE "transfers"
+    ADD COLUMN IF NOT EXISTS status VARCHAR(20) NOT NULL DEFAULT 'completed',
+    ADD CONSTRAINT chk_transfers_status CHECK (status IN ('completed', 'reversed'));
@@ -0,0 +1,5 @@
+-- Add down migration script here
+ALTER TABLE "topups" DROP CONSTRAINT IF EXISTS chk_topups_status;
+
+ALTER TABLE "topups"
+    ADD CONSTRAINT chk_topups_status CHECK (status IN ('pending', 'success', 'failed'));
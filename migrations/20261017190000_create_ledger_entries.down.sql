@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_ledger_entries_user;
+
+DROP TABLE IF EXISTS "ledger_entries";
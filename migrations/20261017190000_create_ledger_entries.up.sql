@@ -0,0 +1,16 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS "ledger_entries" (
+    "id" BIGSERIAL PRIMARY KEY,
+    "user_id" INTEGER NOT NULL,
+    "entry_type" VARCHAR(50) NOT NULL,
+    "amount" INTEGER NOT NULL,
+    "reference_id" INTEGER NOT NULL,
+    "created_at" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_ledger_entries_user
+        FOREIGN KEY(user_id)
+        REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE
+);
+
+CREATE INDEX IF NOT EXISTS idx_ledger_entries_user ON "ledger_entries" (user_id);
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_virtual_cards_user;
+
+DROP TABLE IF EXISTS "virtual_cards";
@@ -0,0 +1,18 @@
+-- Add up migration script here
+CREATE TABLE IF NOT EXISTS "virtual_cards" (
+    "virtual_card_id" SERIAL PRIMARY KEY,
+    "user_id" INTEGER NOT NULL,
+    "card_number" VARCHAR(19) NOT NULL UNIQUE,
+    "expiry_month" INTEGER NOT NULL,
+    "expiry_year" INTEGER NOT NULL,
+    "created_at" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    "updated_at" TIMESTAMP DEFAULT CURRENT_TIMESTAMP,
+    CONSTRAINT fk_virtual_cards_user
+        FOREIGN KEY(user_id)
+        REFERENCES users(user_id)
+        ON UPDATE CASCADE
+        ON DELETE CASCADE,
+    CONSTRAINT chk_virtual_cards_expiry_month CHECK (expiry_month BETWEEN 1 AND 12)
+);
+
+CREATE INDEX IF NOT EXISTS idx_virtual_cards_user ON "virtual_cards" (user_id);
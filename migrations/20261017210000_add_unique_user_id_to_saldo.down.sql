@@ -0,0 +1,2 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS uq_saldo_user_id;
@@ -0,0 +1,7 @@
+-- Add up migration script here
+INSERT INTO "saldo" (user_id, total_balance)
+SELECT u.user_id, 0
+FROM users u
+WHERE NOT EXISTS (SELECT 1 FROM "saldo" s WHERE s.user_id = u.user_id);
+
+CREATE UNIQUE INDEX IF NOT EXISTS uq_saldo_user_id ON "saldo" (user_id);
@@ -0,0 +1,11 @@
+-- Add down migration script here
+ALTER TABLE "transfers"
+    DROP COLUMN IF EXISTS to_label,
+    DROP COLUMN IF EXISTS from_label;
+
+DELETE FROM "saldo" WHERE label <> 'main';
+
+DROP INDEX IF EXISTS uq_saldo_user_label;
+CREATE UNIQUE INDEX IF NOT EXISTS uq_saldo_user_id ON "saldo" (user_id);
+
+ALTER TABLE "saldo" DROP COLUMN IF EXISTS label;
@@ -0,0 +1,9 @@
+-- Add up migration script here
+ALTER TABLE "saldo" ADD COLUMN IF NOT EXISTS label VARCHAR(50) NOT NULL DEFAULT 'main';
+
+DROP INDEX IF EXISTS uq_saldo_user_id;
+CREATE UNIQUE INDEX IF NOT EXISTS uq_saldo_user_label ON "saldo" (user_id, label);
+
+ALTER TABLE "transfers"
+    ADD COLUMN IF NOT EXISTS from_label VARCHAR(50) NOT NULL DEFAULT 'main',
+    ADD COLUMN IF NOT EXISTS to_label VARCHAR(50) NOT NULL DEFAULT 'main';
@@ -0,0 +1,4 @@
+-- Add down migration script here
+ALTER TABLE "ledger_entries"
+    DROP COLUMN IF EXISTS actor_id,
+    DROP COLUMN IF EXISTS reason;
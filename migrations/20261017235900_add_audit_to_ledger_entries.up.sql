@@ -0,0 +1,4 @@
+-- Add up migration script here
+ALTER TABLE "ledger_entries"
+    ADD COLUMN IF NOT EXISTS reason TEXT,
+    ADD COLUMN IF NOT EXISTS actor_id INTEGER;
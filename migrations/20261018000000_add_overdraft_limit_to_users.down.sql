@@ -0,0 +1,25 @@
+-- Add down migration script here
+-- saldo yang sudah memakai overdraft tidak di-clamp ke 0 karena itu menghapus utang user,
+-- rollback ditolak sampai saldo negatif tersebut dilunasi atau disesuaikan manual
+DO $$
+DECLARE
+    negative_count INTEGER;
+BEGIN
+    SELECT COUNT(*) INTO negative_count FROM "saldo" WHERE total_balance < 0;
+
+    IF negative_count > 0 THEN
+        RAISE EXCEPTION 'cannot drop overdraft support: % saldo row(s) are still below zero', negative_count
+            USING HINT = 'settle the overdrawn saldos before rolling back this migration';
+    END IF;
+END;
+$$;
+
+DROP TRIGGER IF EXISTS trg_saldo_balance_floor ON "saldo";
+DROP FUNCTION IF EXISTS enforce_saldo_balance_floor();
+
+ALTER TABLE "saldo"
+    ADD CONSTRAINT chk_saldo_total_balance_non_negative CHECK (total_balance >= 0);
+
+ALTER TABLE "users" DROP CONSTRAINT IF EXISTS chk_users_overdraft_limit_non_negative;
+
+ALTER TABLE "users" DROP COLUMN IF EXISTS overdraft_limit;
@@ -0,0 +1,29 @@
+-- Add up migration script here
+ALTER TABLE "users"
+    ADD COLUMN IF NOT EXISTS overdraft_limit INTEGER NOT NULL DEFAULT 0,
+    ADD CONSTRAINT chk_users_overdraft_limit_non_negative CHECK (overdraft_limit >= 0);
+
+-- CHECK tidak bisa membaca tabel users, batas bawah saldo per user dijaga trigger
+ALTER TABLE "saldo" DROP CONSTRAINT IF EXISTS chk_saldo_total_balance_non_negative;
+
+-- overdraft hanya berlaku untuk saldo utama, saldo berlabel tetap minimal 0
+CREATE OR REPLACE FUNCTION enforce_saldo_balance_floor() RETURNS TRIGGER AS $$
+DECLARE
+    balance_floor INTEGER := 0;
+BEGIN
+    IF NEW.label = 'main' THEN
+        SELECT -overdraft_limit INTO balance_floor FROM "users" WHERE user_id = NEW.user_id;
+    END IF;
+
+    IF NEW.total_balance < COALESCE(balance_floor, 0) THEN
+        RAISE EXCEPTION 'saldo of user % would drop below %', NEW.user_id, balance_floor
+            USING ERRCODE = 'check_violation', CONSTRAINT = 'chk_saldo_total_balance_floor';
+    END IF;
+
+    RETURN NEW;
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER trg_saldo_balance_floor
+    BEFORE INSERT OR UPDATE OF total_balance, label ON "saldo"
+    FOR EACH ROW EXECUTE FUNCTION enforce_saldo_balance_floor();
@@ -0,0 +1,4 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_virtual_cards_user_active;
+
+ALTER TABLE "virtual_cards" DROP COLUMN IF EXISTS revoked_at;
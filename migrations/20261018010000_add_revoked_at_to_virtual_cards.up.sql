@@ -0,0 +1,7 @@
+-- Add up migration script here
+ALTER TABLE "virtual_cards" ADD COLUMN IF NOT EXISTS revoked_at TIMESTAMP NULL;
+
+-- daftar bawaan hanya membaca kartu aktif milik satu user
+CREATE INDEX IF NOT EXISTS idx_virtual_cards_user_active
+    ON "virtual_cards" (user_id, virtual_card_id)
+    WHERE revoked_at IS NULL;
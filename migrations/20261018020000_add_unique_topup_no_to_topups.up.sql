@@ -0,0 +1,3 @@
+-- Add up migration script here
+-- topup_no adalah referensi bisnis dari provider, satu nomor hanya boleh dipakai satu topup
+CREATE UNIQUE INDEX IF NOT EXISTS uq_topups_topup_no ON "topups" (topup_no);
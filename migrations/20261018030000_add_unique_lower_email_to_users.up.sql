@@ -0,0 +1,7 @@
+-- Add up migration script here
+-- email lama yang tersimpan apa adanya diseragamkan dulu; jika dua akun hanya berbeda huruf
+-- besar/kecil, migrasi ini gagal dan duplikatnya harus diselesaikan manual
+UPDATE "users" SET email = LOWER(TRIM(email)) WHERE email <> LOWER(TRIM(email));
+
+-- varian huruf besar/kecil dari email yang sama tidak boleh terdaftar dua kali
+CREATE UNIQUE INDEX IF NOT EXISTS uq_users_email_lower ON "users" (LOWER(email));
@@ -0,0 +1,9 @@
+-- Add down migration script here
+DROP INDEX IF EXISTS idx_transfers_transfer_from_created_at;
+
+UPDATE "transfers" SET status = 'completed' WHERE status = 'needs_review';
+
+ALTER TABLE "transfers" DROP CONSTRAINT IF EXISTS chk_transfers_status;
+
+ALTER TABLE "transfers"
+    ADD CONSTRAINT chk_transfers_status CHECK (status IN ('completed', 'reversed'));
@@ -0,0 +1,7 @@
+-- Add up migration script here
+ALTER TABLE "transfers" DROP CONSTRAINT IF EXISTS chk_transfers_status;
+
+ALTER TABLE "transfers"
+    ADD CONSTRAINT chk_transfers_status CHECK (status IN ('completed', 'reversed', 'needs_review'));
+
+CREATE INDEX IF NOT EXISTS idx_transfers_transfer_from_created_at ON "transfers" (transfer_from, created_at);
@@ -0,0 +1,17 @@
+-- Add up migration script here
+-- transfer kompensasi menunjuk transfer yang dibatalkannya, supaya tidak ikut dihitung
+-- sebagai transfer pengirim oleh pemeriksaan velocity
+ALTER TABLE "transfers"
+    ADD COLUMN IF NOT EXISTS reversal_of INTEGER REFERENCES "transfers" (transfer_id);
+
+-- kompensasi lama hanya dikenali dari catatannya, jadi harus cocok dengan transfer asal
+-- yang sudah reversed dengan arah terbalik dan nominal yang sama
+UPDATE "transfers" AS reversal
+SET reversal_of = original.transfer_id
+FROM "transfers" AS original
+WHERE reversal.note = 'Reversal of transfer #' || original.transfer_id
+  AND original.status = 'reversed'
+  AND reversal.transfer_from = original.transfer_to
+  AND reversal.transfer_to = original.transfer_from
+  AND reversal.transfer_amount = original.transfer_amount
+  AND reversal.reversal_of IS NULL;
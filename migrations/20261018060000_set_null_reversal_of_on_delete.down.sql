@@ -0,0 +1,6 @@
+-- Add down migration script here
+ALTER TABLE "transfers" DROP CONSTRAINT IF EXISTS transfers_reversal_of_fkey;
+
+ALTER TABLE "transfers"
+    ADD CONSTRAINT transfers_reversal_of_fkey FOREIGN KEY (reversal_of)
+        REFERENCES "transfers" (transfer_id);
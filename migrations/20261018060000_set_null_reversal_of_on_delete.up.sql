@@ -0,0 +1,8 @@
+-- Add up migration script here
+-- transfer asal yang dihapus (manual atau lewat purge user) melepas tautan kompensasinya,
+-- bukan menggagalkan penghapusan dengan pelanggaran foreign key
+ALTER TABLE "transfers" DROP CONSTRAINT IF EXISTS transfers_reversal_of_fkey;
+
+ALTER TABLE "transfers"
+    ADD CONSTRAINT transfers_reversal_of_fkey FOREIGN KEY (reversal_of)
+        REFERENCES "transfers" (transfer_id) ON DELETE SET NULL;